@@ -5,11 +5,11 @@ use std::fs;
 use std::io;
 use std::io::{BufRead, Write};
 
-use riscv_emu::exec::{step_rv32, ExecStatus};
+use riscv_emu::exec::{run_rv32_traced, Commit, RunLimits, SpikeLog, StopReason, Tracer};
 use riscv_emu::isas::RV32;
 use riscv_emu::Int;
 use riscv_emu::IntRegister;
-use riscv_emu::{ops, Instruction, RawInstruction};
+use riscv_emu::ops;
 use riscv_emu::{AddressConverter, AddressTransformer, Bus, Memory};
 use riscv_emu::{Hart, SingleThreadUserHart};
 
@@ -70,35 +70,23 @@ fn exec_raw_program(mut img: Vec<u8>, sig_range: std::ops::Range<u32>, mut sig_f
     );
     let mut hart: SingleThreadUserHart<RV32, _> = SingleThreadUserHart::new(mem);
     hart.write_pc(start_pc);
+    hart.set_report_exceptions(true);
 
-    let mut steps = 0;
-    loop {
-        if steps >= 512 {
-            panic!(
-                "test program is still running after {} steps, so aborting",
-                steps
-            );
-        }
-        let pc = hart.read_pc();
-        hart.with_memory(|mem| match mem.read_word(pc) {
-            Ok(v) => {
-                let raw_inst = RawInstruction::new(v);
-                let inst: Instruction<ops::RV32, _> = Instruction::decode_raw(raw_inst, pc);
-                println!("0x{:08x}: {:?}", pc, inst.op);
-                if let ops::RV32::Invalid = inst.op {
-                    panic!("hit invalid instruction, so aborting");
-                }
-            }
-            Err(_) => {
-                println!("0x{:08x}: <failed to read instruction from memory>", pc);
-            }
-        });
-        steps += 1;
-        let status = step_rv32(&mut hart);
-        match status {
-            ExecStatus::EnvironmentCall(_) => break,
-            _ => (),
-        }
+    let mut log = Log::new();
+    let limits = RunLimits::new().with_max_steps(512);
+    let result = run_rv32_traced(&mut hart, &mut log, &limits);
+    match result.reason {
+        StopReason::EnvironmentCall(_) => (),
+        StopReason::StepLimit => panic!(
+            "test program is still running after {} steps, so aborting",
+            result.retired
+        ),
+        StopReason::InvalidInstruction { .. } => panic!("hit invalid instruction, so aborting"),
+        StopReason::Exception { cause, pc, tval } => panic!(
+            "unhandled {:?} exception at 0x{:08x} (tval 0x{:08x}), so aborting",
+            cause, pc, tval
+        ),
+        reason => panic!("test program stopped unexpectedly: {:?}", reason),
     }
 
     // By convention the tests leave their own sense of the test result in
@@ -121,3 +109,25 @@ fn exec_raw_program(mut img: Vec<u8>, sig_range: std::ops::Range<u32>, mut sig_f
         }
     })
 }
+
+// Prints a commit log in the same format as Spike as the program runs, so
+// that the execution of a test program can be compared with Spike's.
+struct Log {
+    spike: SpikeLog<String>,
+}
+
+impl Log {
+    fn new() -> Self {
+        let mut spike = SpikeLog::new(String::new());
+        spike.set_disassembly(true);
+        Self { spike }
+    }
+}
+
+impl Tracer<ops::RV32, u32> for Log {
+    fn commit(&mut self, commit: &Commit<ops::RV32, u32>) {
+        self.spike.commit(commit);
+        print!("{}", self.spike.output());
+        self.spike.output().clear();
+    }
+}
@@ -0,0 +1,328 @@
+mod cosim_32;
+
+pub use cosim_32::lockstep_rv32;
+
+use crate::exec::Commit;
+use crate::instruction::OperationRV32;
+use core::fmt;
+use core::iter::Peekable;
+
+/// One retired instruction from a reference simulator's commit log.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ReferenceCommit {
+    /// The address of the instruction.
+    pub pc: u64,
+
+    /// The raw encoding of the instruction, if the log includes it.
+    pub bits: Option<u32>,
+
+    /// The integer register written by the instruction and its new value.
+    pub int_write: Option<(usize, u64)>,
+
+    /// The floating point register written by the instruction and the bit
+    /// pattern of its new value.
+    pub float_write: Option<(usize, u64)>,
+}
+
+impl fmt::Display for ReferenceCommit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:08x}", self.pc)?;
+        if let Some(bits) = self.bits {
+            write!(f, " (0x{:08x})", bits)?;
+        }
+        if let Some((rd, v)) = self.int_write {
+            write!(f, " x{:<2} 0x{:08x}", rd, v)?;
+        }
+        if let Some((rd, v)) = self.float_write {
+            write!(f, " f{:<2} 0x{:016x}", rd, v)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads a reference commit log from a sequence of lines, producing one
+/// `ReferenceCommit` for each instruction it describes.
+///
+/// Two formats are understood, and can even be mixed:
+///
+/// - The output of Spike's `--log-commits` option, where each instruction is
+///   described by a line like
+///   `core   0: 3 0x80000000 (0x00000297) x5  0x80000000`.
+///
+/// - The output of the Sail RISC-V model, where each instruction begins with
+///   a line like `[0] [M]: 0x80000000 (0x00000297) auipc t0, 0` and is
+///   followed by lines such as `x5 <- 0x80000000` for its register writes.
+///
+/// Lines that are in neither format, including Spike's disassembly lines,
+/// are ignored, as are details of the commits that aren't represented in
+/// `ReferenceCommit`, such as CSR writes and memory accesses.
+pub struct ReferenceTrace<I: Iterator> {
+    lines: Peekable<I>,
+}
+
+impl<I, S> ReferenceTrace<I>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    pub fn new(lines: I) -> Self {
+        Self {
+            lines: lines.peekable(),
+        }
+    }
+}
+
+impl<I, S> Iterator for ReferenceTrace<I>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    type Item = ReferenceCommit;
+
+    fn next(&mut self) -> Option<ReferenceCommit> {
+        loop {
+            let line = self.lines.next()?;
+            if let Some(commit) = parse_spike_commit(line.as_ref()) {
+                return Some(commit);
+            }
+            if let Some(mut commit) = parse_sail_header(line.as_ref()) {
+                while let Some(line) = self.lines.peek() {
+                    if parse_sail_header(line.as_ref()).is_some() {
+                        break;
+                    }
+                    parse_sail_write(line.as_ref(), &mut commit);
+                    self.lines.next();
+                }
+                return Some(commit);
+            }
+        }
+    }
+}
+
+/// Describes the first instruction at which a co-simulation found the hart
+/// behaving differently from the reference log.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Divergence {
+    /// The number of instructions that matched before this one.
+    pub index: usize,
+
+    /// What the reference log says the instruction should have done.
+    pub expected: ReferenceCommit,
+
+    /// What the hart actually did, or `None` if it didn't retire an
+    /// instruction at all.
+    pub actual: Option<Commit<OperationRV32, u32>>,
+
+    pub mismatch: Mismatch,
+}
+
+/// The aspect of an instruction's execution that differed from the
+/// reference log.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Mismatch {
+    /// The hart executed an instruction at a different address.
+    Pc,
+
+    /// The instruction at the expected address has a different encoding.
+    Encoding,
+
+    /// The instructions wrote different integer registers or values.
+    IntWrite,
+
+    /// The instructions wrote different float registers or values.
+    FloatWrite,
+
+    /// The hart repeatedly raised exceptions without retiring any
+    /// instruction.
+    NotRetired,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.mismatch {
+            Mismatch::Pc => "program counter",
+            Mismatch::Encoding => "instruction encoding",
+            Mismatch::IntWrite => "integer register write",
+            Mismatch::FloatWrite => "float register write",
+            Mismatch::NotRetired => "no instruction retired",
+        };
+        writeln!(
+            f,
+            "diverged from reference after {} instructions: {}",
+            self.index, what
+        )?;
+        writeln!(f, "  expected: {}", self.expected)?;
+        match &self.actual {
+            Some(actual) => {
+                write!(
+                    f,
+                    "  actual:   0x{:08x} (0x{:08x})",
+                    actual.inst.pc, actual.bits
+                )?;
+                if let Some((rd, v)) = actual.int_write {
+                    write!(f, " x{:<2} 0x{:08x}", rd.num(), v)?;
+                }
+                if let Some((rd, v)) = actual.float_write {
+                    write!(f, " f{:<2} 0x{:016x}", rd.num(), v)?;
+                }
+                write!(f, "  # {}", actual.inst.op)
+            }
+            None => write!(f, "  actual:   (nothing retired)"),
+        }
+    }
+}
+
+fn parse_hex(s: &str) -> Option<u64> {
+    let digits = s.strip_prefix("0x")?;
+    u64::from_str_radix(digits, 16).ok()
+}
+
+// Parses a register name like "x5" or "f12" into its kind and number.
+fn parse_register(s: &str) -> Option<(char, usize)> {
+    let kind = s.chars().next()?;
+    let n: usize = s.get(1..)?.parse().ok()?;
+    if (kind == 'x' || kind == 'f') && n < 32 {
+        Some((kind, n))
+    } else {
+        None
+    }
+}
+
+fn record_write(commit: &mut ReferenceCommit, reg: (char, usize), v: u64) {
+    match reg {
+        ('x', n) => commit.int_write = Some((n, v)),
+        (_, n) => commit.float_write = Some((n, v)),
+    }
+}
+
+// Parses a line like
+// "core   0: 3 0x80000000 (0x00000297) x5  0x80000000 mem 0x80001000".
+fn parse_spike_commit(line: &str) -> Option<ReferenceCommit> {
+    let rest = line.trim().strip_prefix("core")?;
+    let (_, rest) = rest.split_once(':')?;
+    let mut tokens = rest.split_whitespace();
+    let privilege = tokens.next()?;
+    if privilege.len() != 1 || !privilege.as_bytes()[0].is_ascii_digit() {
+        // Disassembly lines and exception reports have no privilege level.
+        return None;
+    }
+    let mut commit = ReferenceCommit {
+        pc: parse_hex(tokens.next()?)?,
+        ..ReferenceCommit::default()
+    };
+    let mut tokens = tokens.peekable();
+    if let Some(bits) = tokens.peek().and_then(|t| t.strip_prefix('(')) {
+        commit.bits = parse_hex(bits.strip_suffix(')')?).map(|v| v as u32);
+        tokens.next();
+    }
+    while let Some(token) = tokens.next() {
+        if token == "mem" {
+            // The address, and then the value written if it's a store.
+            tokens.next();
+            if tokens.peek().is_some_and(|t| t.starts_with("0x")) {
+                tokens.next();
+            }
+            continue;
+        }
+        match parse_register(token) {
+            Some(reg) => record_write(&mut commit, reg, parse_hex(tokens.next()?)?),
+            None => {
+                // Some other kind of write that we don't compare, such as
+                // to a CSR, which is also followed by its value.
+                if tokens.peek().is_some_and(|t| t.starts_with("0x")) {
+                    tokens.next();
+                }
+            }
+        }
+    }
+    Some(commit)
+}
+
+// Parses a line like "[0] [M]: 0x80000000 (0x00000297) auipc t0, 0".
+fn parse_sail_header(line: &str) -> Option<ReferenceCommit> {
+    let mut tokens = line.split_whitespace();
+    let count = tokens.next()?;
+    let mode = tokens.next()?;
+    if !count.starts_with('[') || !mode.starts_with('[') || !mode.ends_with("]:") {
+        return None;
+    }
+    let pc = parse_hex(tokens.next()?)?;
+    let bits = tokens
+        .next()
+        .and_then(|t| t.strip_prefix('('))
+        .and_then(|t| t.strip_suffix(')'))
+        .and_then(parse_hex)
+        .map(|v| v as u32);
+    Some(ReferenceCommit {
+        pc,
+        bits,
+        ..ReferenceCommit::default()
+    })
+}
+
+// Parses a line like "x5 <- 0x80000000" into the given commit, if it is
+// a register write.
+fn parse_sail_write(line: &str, commit: &mut ReferenceCommit) {
+    let mut tokens = line.split_whitespace();
+    let reg = tokens.next().and_then(parse_register);
+    if let (Some(reg), Some("<-"), Some(v)) = (reg, tokens.next(), tokens.next()) {
+        if let Some(v) = parse_hex(v) {
+            record_write(commit, reg, v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReferenceCommit, ReferenceTrace};
+    use std::vec::Vec;
+
+    #[test]
+    fn parse_reference_logs() {
+        let spike = "\
+core   0: 0x80000000 (0x00000297) auipc   t0, 0x0
+core   0: 3 0x80000000 (0x00000297) x5  0x80000000
+core   0: 3 0x80000004 (0x0005a503) x10 0x00000007 mem 0x80001000
+core   0: 3 0x80000008 (0x00a5a223) mem 0x80001004 0x00000007
+core   0: exception trap_illegal_instruction, epc 0x8000000c
+core   0: 3 0x80000100 (0x34202f73) x30 0x00000002 c834_mcause 0x00000002
+core   0: 3 0x80000104 (0x0505) x10 0x00000008
+";
+        let sail = "\
+[0] [M]: 0x0000000080000000 (0x00000297) auipc t0, 0
+x5 <- 0x0000000080000000
+[1] [M]: 0x0000000080000004 (0x00A5A223) sw a0, 4(a1)
+mem[0x0000000080001004] <- 0x00000007
+[2] [M]: 0x0000000080000008 (0x0000B007) fld ft0, 0(ra)
+f0 <- 0x3FF0000000000000
+";
+        let commit = |pc, bits, int_write, float_write| ReferenceCommit {
+            pc,
+            bits: Some(bits),
+            int_write,
+            float_write,
+        };
+
+        let got: Vec<ReferenceCommit> = ReferenceTrace::new(spike.lines()).collect();
+        assert_eq!(
+            got,
+            [
+                commit(0x80000000, 0x00000297, Some((5, 0x80000000)), None),
+                commit(0x80000004, 0x0005a503, Some((10, 7)), None),
+                commit(0x80000008, 0x00a5a223, None, None),
+                commit(0x80000100, 0x34202f73, Some((30, 2)), None),
+                commit(0x80000104, 0x0505, Some((10, 8)), None),
+            ]
+        );
+
+        let got: Vec<ReferenceCommit> = ReferenceTrace::new(sail.lines()).collect();
+        assert_eq!(
+            got,
+            [
+                commit(0x80000000, 0x00000297, Some((5, 0x80000000)), None),
+                commit(0x80000004, 0x00a5a223, None, None),
+                commit(0x80000008, 0x0000b007, None, Some((0, 0x3ff0000000000000))),
+            ]
+        );
+    }
+}
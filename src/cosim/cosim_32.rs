@@ -0,0 +1,180 @@
+use crate::cosim::{Divergence, Mismatch, ReferenceCommit};
+use crate::exec::{step_rv32_traced, Commit, ExecStatus, Tracer};
+use crate::hart::Hart;
+use crate::instruction::OperationRV32;
+use crate::memory::Bus;
+
+type Op = OperationRV32;
+
+// The number of consecutive steps that may end in an exception before we
+// conclude that the hart is stuck, such as when its trap vector is itself
+// not executable.
+const MAX_TRAPS: usize = 8;
+
+/// Executes the given RV32 hart in lockstep with a reference commit log,
+/// comparing the address, encoding and register write of each instruction
+/// it retires with the corresponding entry in the log, and stopping at the
+/// first difference.
+///
+/// Instructions that raise exceptions don't appear in a commit log, so they
+/// are skipped here too. The same is true of `ecall` and `ebreak`, which
+/// are instead passed to `on_status` along with their `ExecStatus` so that
+/// the caller can emulate the execution environment. `on_status` returns
+/// `false` to end the co-simulation early.
+///
+/// Returns the number of instructions that matched if the reference log
+/// ends (or `on_status` asks to stop) without any differences, or a
+/// description of the first difference otherwise.
+// A divergence is reported at most once per co-simulation, so its size
+// doesn't matter, and boxing it would require an allocator.
+#[allow(clippy::result_large_err)]
+pub fn lockstep_rv32<Mem, H, F>(
+    hart: &mut H,
+    reference: impl IntoIterator<Item = ReferenceCommit>,
+    mut on_status: F,
+) -> Result<usize, Divergence>
+where
+    Mem: Bus<u32>,
+    H: Hart<u32, u32, f64, Mem>,
+    F: FnMut(&mut H, ExecStatus<u32>) -> bool,
+{
+    let mut capture = Capture(None);
+    let mut index = 0;
+    for expected in reference {
+        let mut traps = 0;
+        let actual = loop {
+            capture.0 = None;
+            let status = step_rv32_traced(hart, &mut capture);
+            match (capture.0.take(), status) {
+                (Some(commit), ExecStatus::Running | ExecStatus::WaitingForInterrupt)
+                    if !commit.trapped =>
+                {
+                    break commit;
+                }
+                (_, ExecStatus::Running | ExecStatus::WaitingForInterrupt) => {
+                    traps += 1;
+                    if traps >= MAX_TRAPS {
+                        return Err(Divergence {
+                            index,
+                            expected,
+                            actual: None,
+                            mismatch: Mismatch::NotRetired,
+                        });
+                    }
+                }
+                (_, status) => {
+                    if !on_status(hart, status) {
+                        return Ok(index);
+                    }
+                }
+            }
+        };
+        if let Some(mismatch) = compare(&expected, &actual) {
+            return Err(Divergence {
+                index,
+                expected,
+                actual: Some(actual),
+                mismatch,
+            });
+        }
+        index += 1;
+    }
+    Ok(index)
+}
+
+fn compare(expected: &ReferenceCommit, actual: &Commit<Op, u32>) -> Option<Mismatch> {
+    if expected.pc as u32 != actual.inst.pc {
+        return Some(Mismatch::Pc);
+    }
+    if expected.bits.is_some_and(|bits| bits != actual.bits) {
+        return Some(Mismatch::Encoding);
+    }
+    // Some reference simulators report writes to x0, which we never do.
+    let expected_int = expected.int_write.filter(|(rd, _)| *rd != 0);
+    let actual_int = actual.int_write.map(|(rd, v)| (rd.num(), v as u64));
+    if expected_int.map(|(rd, v)| (rd, v as u32 as u64)) != actual_int {
+        return Some(Mismatch::IntWrite);
+    }
+    let actual_float = actual.float_write.map(|(rd, v)| (rd.num(), v));
+    if expected.float_write != actual_float {
+        return Some(Mismatch::FloatWrite);
+    }
+    None
+}
+
+// A tracer that just remembers the most recent commit.
+struct Capture(Option<Commit<Op, u32>>);
+
+impl Tracer<Op, u32> for Capture {
+    fn commit(&mut self, commit: &Commit<Op, u32>) {
+        self.0 = Some(commit.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lockstep_rv32;
+    use crate::cosim::{Mismatch, ReferenceTrace};
+    use crate::exec::{step_rv32_traced, ExecStatus, SpikeLog};
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::RV32;
+    use crate::memory::{AddressConverter, Bus, Memory};
+    use std::format;
+    use std::string::String;
+
+    #[test]
+    fn lockstep() {
+        let program: [u32; 5] = [
+            0x00500513, // li      a0, 5
+            0x00a50593, // addi    a1, a0, 10
+            0x00000073, // ecall
+            0x40b50633, // sub     a2, a0, a1
+            0x00c02223, // sw      a2, 4(zero)
+        ];
+        let mut buf = [0u8; 64];
+        let mem = AddressConverter::<u32, usize, _>::new(Memory::new_ram(&mut buf));
+        let mut hart: SingleThreadUserHart<RV32, _> = SingleThreadUserHart::new(mem);
+        hart.with_memory(|mem| {
+            for (i, word) in program.iter().enumerate() {
+                mem.write_word(i as u32 * 4 + 8, *word).unwrap();
+            }
+        });
+
+        // Our own commit log, run back through the co-simulation, should
+        // match perfectly.
+        hart.write_pc(8);
+        let mut log = SpikeLog::new(String::new());
+        for _ in 0..program.len() {
+            step_rv32_traced(&mut hart, &mut log);
+        }
+        let reference = log.into_inner();
+
+        hart.write_pc(8);
+        let mut ecalls = 0;
+        let result = lockstep_rv32(&mut hart, ReferenceTrace::new(reference.lines()), |_, s| {
+            if let ExecStatus::EnvironmentCall(16) = s {
+                ecalls += 1;
+            }
+            true
+        });
+        assert_eq!(result, Ok(4));
+        assert_eq!(ecalls, 1);
+
+        // If the reference disagrees about a result, the divergence is
+        // reported at that instruction.
+        let reference = reference.replace("x12 0xfffffff6", "x12 0xfffffff7");
+        hart.write_pc(8);
+        let err = lockstep_rv32(&mut hart, ReferenceTrace::new(reference.lines()), |_, _| {
+            true
+        })
+        .unwrap_err();
+        assert_eq!(err.index, 2);
+        assert_eq!(err.mismatch, Mismatch::IntWrite);
+        assert_eq!(
+            format!("{}", err),
+            "diverged from reference after 2 instructions: integer register write\n  \
+             expected: 0x00000014 (0x40b50633) x12 0xfffffff7\n  \
+             actual:   0x00000014 (0x40b50633) x12 0xfffffff6  # sub     a2, a0, a1"
+        );
+    }
+}
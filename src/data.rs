@@ -15,7 +15,8 @@ pub type Byte = u8;
 /// some specified size or as a RISC-V word (32-bit), signed or unsigned.
 ///
 /// The `WordUnsigned` (`u32`) implementation uses RV32I behaviors, while the
-/// `LongwordUnsigned` (`u64`) implementation uses RV64I behaviors.
+/// `LongwordUnsigned` (`u64`) and `QuadwordUnsigned` (`u128`) implementations
+/// use RV64I and RV128I behaviors respectively.
 pub trait Int
 where
     Self: Copy + Zero,
@@ -139,6 +140,50 @@ impl Int for LongwordUnsigned {
     }
 }
 
+/// RV128I implementation of `Int` using `QuadwordUnsigned` (`u128`) as backing storage.
+impl Int for QuadwordUnsigned {
+    type Signed = QuadwordSigned;
+    type Unsigned = QuadwordUnsigned;
+
+    fn from_signed(v: QuadwordSigned) -> Self {
+        unsafe { transmute(v) }
+    }
+
+    fn from_unsigned(v: QuadwordUnsigned) -> Self {
+        v
+    }
+
+    fn from_signed_word(v: WordSigned) -> Self {
+        let nv = v as QuadwordSigned; // automatic sign extension
+        unsafe { transmute(nv) }
+    }
+
+    fn from_unsigned_word(v: WordUnsigned) -> Self {
+        // As for RV64I, word results are sign-extended to the full register
+        // width even when they were produced as unsigned.
+        let signed32: WordSigned = unsafe { transmute(v) };
+        let signed128: QuadwordSigned = signed32 as i128;
+        unsafe { transmute(signed128) } // i128 to u128
+    }
+
+    fn to_signed(self) -> QuadwordSigned {
+        unsafe { transmute(self) }
+    }
+
+    fn to_unsigned(self) -> QuadwordUnsigned {
+        self
+    }
+
+    fn to_signed_word(self) -> WordSigned {
+        let nv = self as WordUnsigned; // just truncate existing bits
+        unsafe { transmute(nv) }
+    }
+
+    fn to_unsigned_word(self) -> WordUnsigned {
+        self as WordUnsigned // just truncate existing bits
+    }
+}
+
 /// Represents the raw storage of a float that can be interpreted either as a
 /// single- or double-precision float, and can also be interpreted as a raw
 /// set of bits (either word-size or longword-size) in order to interact with
@@ -293,6 +338,18 @@ mod tests {
         assert_eq!(u64::from_unsigned_word(0xffffffff).to_signed(), -1);
     }
 
+    #[test]
+    fn int_128() {
+        assert_eq!(u128::zero(), 0u128);
+        assert_eq!(u128::from_signed(-1), u128::MAX);
+        assert_eq!(u128::from_unsigned_word(0xffffffff), u128::MAX); // sign-extended even though not signed
+        assert_eq!(u128::from_unsigned_word(0x7fffffff), 0x7fffffff);
+        assert_eq!(u128::from_signed_word(-2).to_signed(), -2);
+        assert_eq!(u128::from_unsigned(u128::MAX).to_signed(), -1);
+        assert_eq!((u128::MAX - 1).to_signed_word(), -2);
+        assert_eq!((1u128 << 64 | 5).to_unsigned_word(), 5);
+    }
+
     #[test]
     fn float_64() {
         assert_eq!(f64::zero(), 0.0 as f64);
@@ -0,0 +1,417 @@
+use crate::data::Byte;
+use crate::data::HalfwordUnsigned as Halfword;
+use crate::data::LongwordUnsigned as Longword;
+use crate::data::QuadwordUnsigned as Quadword;
+use crate::data::WordUnsigned as Word;
+use crate::exec::{step_rv32, ExecStatus};
+use crate::hart::Hart;
+use crate::memory::{AccessKind, AtomicOp, Bus, FenceSet, MemoryError};
+
+use core::ops::Range;
+use std::vec::Vec;
+
+/// Selects which kinds of access a watchpoint responds to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WatchKind {
+    /// Trigger only when the watched memory is read.
+    Read,
+    /// Trigger only when the watched memory is written.
+    Write,
+    /// Trigger when the watched memory is either read or written.
+    Access,
+}
+
+impl WatchKind {
+    // An atomic read-modify-write both reads and writes, so it matches
+    // either kind of watchpoint.
+    fn matches(self, kind: AccessKind) -> bool {
+        match self {
+            WatchKind::Read => kind == AccessKind::Load || kind == AccessKind::Amo,
+            WatchKind::Write => kind == AccessKind::Store || kind == AccessKind::Amo,
+            WatchKind::Access => true,
+        }
+    }
+}
+
+/// Describes a memory access that triggered a watchpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WatchpointHit {
+    /// The address of the access, which may be before the start of the
+    /// watched range if the access only partially overlaps it.
+    pub addr: u32,
+    /// The size of the access in bytes.
+    pub size: u32,
+    /// The kind of the access, which is `AccessKind::Amo` for an atomic
+    /// read-modify-write, or `AccessKind::Load` for a compare-exchange that
+    /// failed and so only read.
+    pub kind: AccessKind,
+}
+
+/// Represents the reasons why `Debugger::run_rv32` can stop running a hart.
+#[derive(Debug)]
+pub enum StopReason {
+    /// The program counter reached the given breakpoint address. The
+    /// instruction at that address has not yet been executed.
+    Breakpoint(u32),
+
+    /// The instruction at the given address made an access matching a
+    /// watchpoint. The access has already completed and the program counter
+    /// refers to the following instruction.
+    Watchpoint { pc: u32, hit: WatchpointHit },
+
+    /// The executor returned a status other than `ExecStatus::Running`,
+    /// which the caller must handle before running the hart again.
+    Exec(ExecStatus<u32>),
+
+    /// The requested number of instructions was executed without any other
+    /// reason to stop.
+    StepLimit,
+}
+
+/// Wraps another `Bus` to monitor the accesses made through it, recording
+/// any that overlap a watched address range.
+///
+/// Watchpoints never prevent an access; they only record it so that a
+/// `Debugger` can stop after the instruction that made it. Only the first
+/// matching access since the last call to `take_hit` is recorded.
+pub struct Watchpoints<Wrapped: Bus<u32>> {
+    wrapped: Wrapped,
+    watches: Vec<(Range<u32>, WatchKind)>,
+    hit: Option<WatchpointHit>,
+}
+
+impl<Wrapped: Bus<u32>> Watchpoints<Wrapped> {
+    /// Consumes a bus and produces a wrapping `Watchpoints` that initially
+    /// has no watched ranges.
+    pub fn new(wrapped: Wrapped) -> Self {
+        Self {
+            wrapped,
+            watches: Vec::new(),
+            hit: None,
+        }
+    }
+
+    /// Starts watching the given address range for the given kind of access.
+    pub fn add(&mut self, range: Range<u32>, kind: WatchKind) {
+        self.watches.push((range, kind));
+    }
+
+    /// Stops watching the given address range for the given kind of access,
+    /// returning false if there was no such watchpoint.
+    pub fn remove(&mut self, range: Range<u32>, kind: WatchKind) -> bool {
+        match self
+            .watches
+            .iter()
+            .position(|w| *w == (range.clone(), kind))
+        {
+            Some(i) => {
+                self.watches.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the access that triggered a watchpoint since the last call,
+    /// if any, and resets the record.
+    pub fn take_hit(&mut self) -> Option<WatchpointHit> {
+        self.hit.take()
+    }
+
+    /// Borrows the wrapped bus.
+    pub fn wrapped(&mut self) -> &mut Wrapped {
+        &mut self.wrapped
+    }
+
+    fn check(&mut self, addr: u32, size: u32, kind: AccessKind) {
+        if self.hit.is_some() {
+            return;
+        }
+        let end = addr.wrapping_add(size);
+        let triggered = self
+            .watches
+            .iter()
+            .any(|(range, watch)| watch.matches(kind) && addr < range.end && range.start < end);
+        if triggered {
+            self.hit = Some(WatchpointHit { addr, size, kind });
+        }
+    }
+}
+
+impl<Wrapped: Bus<u32>> Bus<u32> for Watchpoints<Wrapped> {
+    fn read_byte(&mut self, addr: u32) -> Result<Byte, MemoryError> {
+        let v = self.wrapped.read_byte(addr)?;
+        self.check(addr, 1, AccessKind::Load);
+        Ok(v)
+    }
+
+    fn read_halfword(&mut self, addr: u32) -> Result<Halfword, MemoryError> {
+        let v = self.wrapped.read_halfword(addr)?;
+        self.check(addr, 2, AccessKind::Load);
+        Ok(v)
+    }
+
+    fn read_word(&mut self, addr: u32) -> Result<Word, MemoryError> {
+        let v = self.wrapped.read_word(addr)?;
+        self.check(addr, 4, AccessKind::Load);
+        Ok(v)
+    }
+
+    fn read_longword(&mut self, addr: u32) -> Result<Longword, MemoryError> {
+        let v = self.wrapped.read_longword(addr)?;
+        self.check(addr, 8, AccessKind::Load);
+        Ok(v)
+    }
+
+    fn read_quadword(&mut self, addr: u32) -> Result<Quadword, MemoryError> {
+        let v = self.wrapped.read_quadword(addr)?;
+        self.check(addr, 16, AccessKind::Load);
+        Ok(v)
+    }
+
+    fn write_byte(&mut self, addr: u32, data: Byte) -> Result<(), MemoryError> {
+        self.wrapped.write_byte(addr, data)?;
+        self.check(addr, 1, AccessKind::Store);
+        Ok(())
+    }
+
+    fn write_halfword(&mut self, addr: u32, data: Halfword) -> Result<(), MemoryError> {
+        self.wrapped.write_halfword(addr, data)?;
+        self.check(addr, 2, AccessKind::Store);
+        Ok(())
+    }
+
+    fn write_word(&mut self, addr: u32, data: Word) -> Result<(), MemoryError> {
+        self.wrapped.write_word(addr, data)?;
+        self.check(addr, 4, AccessKind::Store);
+        Ok(())
+    }
+
+    fn write_longword(&mut self, addr: u32, data: Longword) -> Result<(), MemoryError> {
+        self.wrapped.write_longword(addr, data)?;
+        self.check(addr, 8, AccessKind::Store);
+        Ok(())
+    }
+
+    fn write_quadword(&mut self, addr: u32, data: Quadword) -> Result<(), MemoryError> {
+        self.wrapped.write_quadword(addr, data)?;
+        self.check(addr, 16, AccessKind::Store);
+        Ok(())
+    }
+
+    // Instruction fetches don't trigger read watchpoints.
+    fn fetch_halfword(&mut self, addr: u32) -> Result<Halfword, MemoryError> {
+        self.wrapped.fetch_halfword(addr)
+    }
+
+    // The atomic operations read and then write, so they can trigger either
+    // kind of watchpoint, but a failed compare-exchange only reads.
+    fn amo_word(&mut self, addr: u32, op: AtomicOp, operand: Word) -> Result<Word, MemoryError> {
+        let v = self.wrapped.amo_word(addr, op, operand)?;
+        self.check(addr, 4, AccessKind::Amo);
+        Ok(v)
+    }
+
+    fn compare_exchange_word(
+        &mut self,
+        addr: u32,
+        current: Word,
+        new: Word,
+    ) -> Result<Result<Word, Word>, MemoryError> {
+        let result = self.wrapped.compare_exchange_word(addr, current, new)?;
+        let kind = if result.is_ok() {
+            AccessKind::Amo
+        } else {
+            AccessKind::Load
+        };
+        self.check(addr, 4, kind);
+        Ok(result)
+    }
+
+    fn fence(&mut self, pred: FenceSet, succ: FenceSet) {
+        self.wrapped.fence(pred, succ)
+    }
+}
+
+/// Runs a hart with host-side breakpoints and watchpoints, which (unlike
+/// breakpoints made by writing `ebreak` into guest memory) work for code in
+/// ROM and are invisible to the guest program.
+///
+/// Breakpoints are addresses that the debugger checks the program counter
+/// against before each instruction. Watchpoints are managed by a
+/// `Watchpoints` wrapper around the hart's memory bus.
+pub struct Debugger {
+    breakpoints: Vec<u32>,
+    resume_from: Option<u32>,
+}
+
+impl Debugger {
+    pub fn new() -> Self {
+        Self {
+            breakpoints: Vec::new(),
+            resume_from: None,
+        }
+    }
+
+    /// Adds a breakpoint at the given instruction address.
+    pub fn add_breakpoint(&mut self, addr: u32) {
+        if !self.breakpoints.contains(&addr) {
+            self.breakpoints.push(addr);
+        }
+    }
+
+    /// Removes the breakpoint at the given instruction address, returning
+    /// false if there was no such breakpoint.
+    pub fn remove_breakpoint(&mut self, addr: u32) -> bool {
+        match self.breakpoints.iter().position(|a| *a == addr) {
+            Some(i) => {
+                self.breakpoints.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the addresses of the current breakpoints.
+    pub fn breakpoints(&self) -> &[u32] {
+        &self.breakpoints
+    }
+
+    /// Executes up to `max_steps` instructions on the given RV32 hart,
+    /// stopping early at a breakpoint, a watchpoint, or any status from the
+    /// executor other than `ExecStatus::Running`.
+    ///
+    /// If the previous call stopped at a breakpoint and the program counter
+    /// is unchanged, the instruction at the breakpoint is executed rather
+    /// than stopping there again.
+    pub fn run_rv32<Inner, H>(&mut self, hart: &mut H, max_steps: u64) -> StopReason
+    where
+        Inner: Bus<u32>,
+        H: Hart<u32, u32, f64, Watchpoints<Inner>>,
+    {
+        let resume_from = self.resume_from.take();
+        for step in 0..max_steps {
+            let pc = hart.read_pc();
+            if self.breakpoints.contains(&pc) && !(step == 0 && resume_from == Some(pc)) {
+                self.resume_from = Some(pc);
+                return StopReason::Breakpoint(pc);
+            }
+            let status = step_rv32(hart);
+            if let Some(hit) = hart.with_memory(|mem| mem.take_hit()) {
+                return StopReason::Watchpoint { pc, hit };
+            }
+            match status {
+                ExecStatus::Running => {}
+                status => return StopReason::Exec(status),
+            }
+        }
+        StopReason::StepLimit
+    }
+}
+
+impl Default for Debugger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Debugger, StopReason, WatchKind, Watchpoints};
+    use crate::exec::ExecStatus;
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::RV32;
+    use crate::memory::{AccessKind, AddressConverter, AtomicOp, Bus, Memory};
+    use crate::register::IntRegister;
+
+    #[test]
+    fn breakpoints_and_watchpoints() {
+        let mut mem_buf = [0u8; 256];
+        let program: [u32; 6] = [
+            0x00500513, // li a0, 5
+            0x10a02023, // sw a0, 0x100(x0)
+            0x10402583, // lw a1, 0x104(x0)
+            0x10002603, // lw a2, 0x100(x0)
+            0x00000073, // ecall
+            0x0000006f, // j .
+        ];
+        for (i, word) in program.iter().enumerate() {
+            mem_buf[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+        }
+        let mem = Watchpoints::new(AddressConverter::new(Memory::new_ram(&mut mem_buf)));
+        let mut hart: SingleThreadUserHart<
+            RV32,
+            Watchpoints<AddressConverter<u32, usize, Memory>>,
+        > = SingleThreadUserHart::new(mem);
+        hart.with_memory(|mem| {
+            mem.add(0x100..0x104, WatchKind::Write);
+            mem.add(0x100..0x104, WatchKind::Read);
+            // A watchpoint covering the code shouldn't be triggered by
+            // instruction fetches.
+            mem.add(0x0..0x18, WatchKind::Read);
+        });
+
+        let mut dbg = Debugger::new();
+        dbg.add_breakpoint(0x4);
+        match dbg.run_rv32(&mut hart, 100) {
+            StopReason::Breakpoint(0x4) => {}
+            reason => panic!("expected breakpoint, got {:?}", reason),
+        }
+        assert_eq!(hart.read_pc(), 0x4, "breakpoint stops before execution");
+        assert_eq!(hart.read_int_register(IntRegister::numbered(10)), 5);
+
+        match dbg.run_rv32(&mut hart, 100) {
+            StopReason::Watchpoint { pc: 0x4, hit } => {
+                assert_eq!(hit.addr, 0x100);
+                assert_eq!(hit.size, 4);
+                assert_eq!(hit.kind, AccessKind::Store);
+            }
+            reason => panic!("expected write watchpoint, got {:?}", reason),
+        }
+        assert_eq!(hart.read_pc(), 0x8, "watchpoint stops after the access");
+
+        // The load from 0x104 doesn't overlap the watched range, so the next
+        // stop is for the load from 0x100.
+        match dbg.run_rv32(&mut hart, 100) {
+            StopReason::Watchpoint { pc: 0xc, hit } => {
+                assert_eq!(hit.kind, AccessKind::Load);
+            }
+            reason => panic!("expected read watchpoint, got {:?}", reason),
+        }
+
+        match dbg.run_rv32(&mut hart, 100) {
+            StopReason::Exec(ExecStatus::EnvironmentCall(0x10)) => {}
+            reason => panic!("expected environment call, got {:?}", reason),
+        }
+        match dbg.run_rv32(&mut hart, 10) {
+            StopReason::StepLimit => {}
+            reason => panic!("expected step limit, got {:?}", reason),
+        }
+
+        assert!(dbg.remove_breakpoint(0x4));
+        assert!(!dbg.remove_breakpoint(0x4));
+        assert_eq!(dbg.breakpoints(), &[]);
+    }
+
+    #[test]
+    fn atomic_watchpoints() {
+        let mut mem_buf = [0u8; 16];
+        let mut mem = Watchpoints::new(AddressConverter::new(Memory::new_ram(&mut mem_buf)));
+        mem.add(0x4..0x8, WatchKind::Read);
+        mem.add(0x8..0xc, WatchKind::Write);
+
+        // Atomic operations both read and write, so they trigger either kind.
+        mem.amo_word(0x4, AtomicOp::Add, 1).unwrap();
+        assert_eq!(mem.take_hit().map(|hit| hit.kind), Some(AccessKind::Amo));
+        mem.amo_word(0x8, AtomicOp::Add, 1).unwrap();
+        assert_eq!(mem.take_hit().map(|hit| hit.kind), Some(AccessKind::Amo));
+
+        // A compare-exchange that fails only reads.
+        assert_eq!(mem.compare_exchange_word(0x8, 0, 2).unwrap(), Err(1));
+        assert_eq!(mem.take_hit(), None);
+        assert_eq!(mem.compare_exchange_word(0x4, 0, 2).unwrap(), Err(1));
+        assert_eq!(mem.take_hit().map(|hit| hit.kind), Some(AccessKind::Load));
+        assert_eq!(mem.compare_exchange_word(0x8, 1, 2).unwrap(), Ok(1));
+        assert_eq!(mem.take_hit().map(|hit| hit.kind), Some(AccessKind::Amo));
+    }
+}
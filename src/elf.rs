@@ -0,0 +1,362 @@
+use crate::memory::{Bus, MemoryError};
+
+/// Represents the ways in which parsing or loading an ELF image can fail.
+#[derive(Debug)]
+pub enum ElfError {
+    /// The image is shorter than its headers claim.
+    Truncated,
+    /// The image does not begin with the ELF magic number.
+    BadMagic,
+    /// The image is not a little-endian 32-bit RISC-V executable.
+    Unsupported,
+    /// Writing a segment into memory failed.
+    Memory(MemoryError),
+}
+
+/// The `p_type` of a loadable segment.
+pub const PT_LOAD: u32 = 1;
+/// The `p_type` of the segment describing the program header table itself.
+pub const PT_PHDR: u32 = 6;
+
+const SHT_SYMTAB: u32 = 2;
+
+const EM_RISCV: u16 = 243;
+const ELFCLASS32: u8 = 1;
+const ELFDATA2LSB: u8 = 1;
+
+/// A read-only view of a 32-bit RISC-V ELF image held in host memory.
+///
+/// The parser does not allocate, so it is usable without the standard
+/// library. It understands just enough of the format to load an executable
+/// into a hart's memory and to find symbols in its symbol table.
+pub struct Elf32<'a> {
+    data: &'a [u8],
+    entry: u32,
+    phoff: u32,
+    phentsize: u16,
+    phnum: u16,
+    shoff: u32,
+    shentsize: u16,
+    shnum: u16,
+}
+
+/// A program header (segment descriptor) from an `Elf32` image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgramHeader32 {
+    pub p_type: u32,
+    pub offset: u32,
+    pub vaddr: u32,
+    pub paddr: u32,
+    pub filesz: u32,
+    pub memsz: u32,
+    pub flags: u32,
+    pub align: u32,
+}
+
+/// Describes where an ELF image ended up after `Elf32::load`, giving the
+/// information a caller needs to start executing it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoadedImage32 {
+    /// The address of the program entry point.
+    pub entry: u32,
+    /// The address of the program header table in memory, or zero if the
+    /// table is not part of any loaded segment.
+    pub phdr: u32,
+    /// The size of each program header table entry.
+    pub phent: u32,
+    /// The number of program header table entries.
+    pub phnum: u32,
+    /// The address just after the end of the highest loaded segment, which
+    /// is conventionally where the program break begins.
+    pub end: u32,
+}
+
+impl<'a> Elf32<'a> {
+    /// Parses the ELF header from the given image, verifying that it is for
+    /// a little-endian 32-bit RISC-V target.
+    pub fn parse(data: &'a [u8]) -> Result<Self, ElfError> {
+        if data.len() < 52 {
+            return Err(ElfError::Truncated);
+        }
+        if &data[0..4] != b"\x7fELF" {
+            return Err(ElfError::BadMagic);
+        }
+        if data[4] != ELFCLASS32 || data[5] != ELFDATA2LSB || read_u16(data, 18)? != EM_RISCV {
+            return Err(ElfError::Unsupported);
+        }
+        let elf = Self {
+            data,
+            entry: read_u32(data, 24)?,
+            phoff: read_u32(data, 28)?,
+            shoff: read_u32(data, 32)?,
+            phentsize: read_u16(data, 42)?,
+            phnum: read_u16(data, 44)?,
+            shentsize: read_u16(data, 46)?,
+            shnum: read_u16(data, 48)?,
+        };
+        // Make sure the tables are all present, so that the accessors below
+        // can't fail later.
+        let ph_end = elf.phoff as usize + elf.phentsize as usize * elf.phnum as usize;
+        let sh_end = elf.shoff as usize + elf.shentsize as usize * elf.shnum as usize;
+        if ph_end > data.len() || sh_end > data.len() {
+            return Err(ElfError::Truncated);
+        }
+        Ok(elf)
+    }
+
+    /// Returns the address of the program entry point.
+    pub fn entry(&self) -> u32 {
+        self.entry
+    }
+
+    /// Returns an iterator over the program headers.
+    pub fn program_headers(&self) -> impl Iterator<Item = ProgramHeader32> + '_ {
+        (0..self.phnum as usize).map(move |i| {
+            let base = self.phoff as usize + i * self.phentsize as usize;
+            let word = |n: usize| read_u32(self.data, base + n * 4).unwrap_or(0);
+            ProgramHeader32 {
+                p_type: word(0),
+                offset: word(1),
+                vaddr: word(2),
+                paddr: word(3),
+                filesz: word(4),
+                memsz: word(5),
+                flags: word(6),
+                align: word(7),
+            }
+        })
+    }
+
+    /// Returns the value of the symbol with the given name from the image's
+    /// symbol table, or `None` if there is no such symbol or no symbol table.
+    ///
+    /// This is typically used to find the addresses of special locations
+    /// that an execution environment must know about, such as `tohost`.
+    pub fn symbol(&self, name: &[u8]) -> Option<u32> {
+        for i in 0..self.shnum as usize {
+            let sh = self.shoff as usize + i * self.shentsize as usize;
+            if read_u32(self.data, sh + 4).ok()? != SHT_SYMTAB {
+                continue;
+            }
+            let offset = read_u32(self.data, sh + 16).ok()? as usize;
+            let size = read_u32(self.data, sh + 20).ok()? as usize;
+            let link = read_u32(self.data, sh + 24).ok()? as usize;
+            let entsize = read_u32(self.data, sh + 36).ok()? as usize;
+            let strtab = self.shoff as usize + link * self.shentsize as usize;
+            let str_offset = read_u32(self.data, strtab + 16).ok()? as usize;
+            if entsize == 0 {
+                continue;
+            }
+            for sym in (offset..offset + size).step_by(entsize) {
+                let start = str_offset + read_u32(self.data, sym).ok()? as usize;
+                let end = start + name.len();
+                if self.data.get(start..end) == Some(name) && self.data.get(end) == Some(&0) {
+                    return read_u32(self.data, sym + 4).ok();
+                }
+            }
+        }
+        None
+    }
+
+    /// Copies each loadable segment into memory at its virtual address,
+    /// zero-filling any part of a segment that has no file content.
+    pub fn load<Mem: Bus<u32>>(&self, mem: &mut Mem) -> Result<LoadedImage32, ElfError> {
+        let mut end: u32 = 0;
+        let mut phdr: u32 = 0;
+        for ph in self.program_headers() {
+            if ph.p_type == PT_PHDR {
+                phdr = ph.vaddr;
+            }
+            if ph.p_type != PT_LOAD {
+                continue;
+            }
+            // A segment that claims to extend past the end of the address
+            // space certainly extends past the end of the image.
+            let file_end = ph
+                .offset
+                .checked_add(ph.filesz)
+                .ok_or(ElfError::Truncated)?;
+            let content = self
+                .data
+                .get(ph.offset as usize..file_end as usize)
+                .ok_or(ElfError::Truncated)?;
+            for i in 0..ph.memsz {
+                let v = content.get(i as usize).copied().unwrap_or(0);
+                mem.write_byte(ph.vaddr.wrapping_add(i), v)
+                    .map_err(ElfError::Memory)?;
+            }
+            if phdr == 0 && self.phoff >= ph.offset && self.phoff < file_end {
+                phdr = ph.vaddr.wrapping_add(self.phoff - ph.offset);
+            }
+            end = core::cmp::max(end, ph.vaddr.wrapping_add(ph.memsz));
+        }
+        Ok(LoadedImage32 {
+            entry: self.entry,
+            phdr,
+            phent: self.phentsize as u32,
+            phnum: self.phnum as u32,
+            end,
+        })
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, ElfError> {
+    match data.get(offset..offset + 2) {
+        Some(b) => Ok(u16::from_le_bytes([b[0], b[1]])),
+        None => Err(ElfError::Truncated),
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, ElfError> {
+    match data.get(offset..offset + 4) {
+        Some(b) => Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        None => Err(ElfError::Truncated),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{Elf32, ElfError, LoadedImage32, PT_LOAD};
+    use crate::memory::{AddressConverter, Bus, Memory};
+
+    // Builds a minimal executable image with a single loadable segment
+    // containing the given code, to be loaded at the given address. The
+    // program headers are part of the loaded segment, as they would be for
+    // a typical static executable.
+    pub(crate) fn build_elf(code: &[u32], vaddr: u32, bss: u32, buf: &mut [u8]) -> usize {
+        let code_offset = 52 + 32;
+        let len = code_offset + code.len() * 4;
+        let put16 =
+            |buf: &mut [u8], o: usize, v: u16| buf[o..o + 2].copy_from_slice(&v.to_le_bytes());
+        let put32 =
+            |buf: &mut [u8], o: usize, v: u32| buf[o..o + 4].copy_from_slice(&v.to_le_bytes());
+        buf[0..4].copy_from_slice(b"\x7fELF");
+        buf[4] = 1; // ELFCLASS32
+        buf[5] = 1; // ELFDATA2LSB
+        buf[6] = 1; // EV_CURRENT
+        put16(buf, 16, 2); // ET_EXEC
+        put16(buf, 18, 243); // EM_RISCV
+        put32(buf, 20, 1);
+        put32(buf, 24, vaddr + code_offset as u32); // e_entry
+        put32(buf, 28, 52); // e_phoff
+        put16(buf, 40, 52); // e_ehsize
+        put16(buf, 42, 32); // e_phentsize
+        put16(buf, 44, 1); // e_phnum
+        put32(buf, 52, PT_LOAD);
+        put32(buf, 56, 0); // p_offset
+        put32(buf, 60, vaddr);
+        put32(buf, 64, vaddr);
+        put32(buf, 68, len as u32); // p_filesz
+        put32(buf, 72, len as u32 + bss); // p_memsz
+        put32(buf, 76, 0b101); // PF_R | PF_X
+        put32(buf, 80, 0x1000);
+        for (i, word) in code.iter().enumerate() {
+            put32(buf, code_offset + i * 4, *word);
+        }
+        len
+    }
+
+    #[test]
+    fn load() {
+        let mut img = [0u8; 256];
+        let len = build_elf(&[0x00000073], 0x100, 8, &mut img);
+        let elf = Elf32::parse(&img[..len]).unwrap();
+        assert_eq!(elf.entry(), 0x154);
+        assert_eq!(elf.program_headers().count(), 1);
+
+        let mut mem_buf = [0xffu8; 512];
+        let mut mem: AddressConverter<u32, usize, Memory> =
+            AddressConverter::new(Memory::new_ram(&mut mem_buf));
+        let loaded = elf.load(&mut mem).unwrap();
+        assert_eq!(
+            loaded,
+            LoadedImage32 {
+                entry: 0x154,
+                phdr: 0x134,
+                phent: 32,
+                phnum: 1,
+                end: 0x160,
+            }
+        );
+        assert_eq!(mem.read_word(0x154).unwrap(), 0x00000073, "code loaded");
+        assert_eq!(mem.read_word(0x158).unwrap(), 0, "bss zeroed");
+        assert_eq!(mem.read_word(0x160).unwrap(), 0xffffffff, "beyond segment");
+
+        assert_eq!(elf.symbol(b"tohost"), None, "no symbol table");
+        assert!(Elf32::parse(&img[..40]).is_err(), "truncated header");
+        img[56..60].copy_from_slice(&0xffffff00u32.to_le_bytes()); // p_offset
+        assert!(
+            matches!(
+                Elf32::parse(&img[..len]).unwrap().load(&mut mem),
+                Err(ElfError::Truncated)
+            ),
+            "segment beyond the address space"
+        );
+        img[18] = 62; // EM_X86_64
+        assert!(Elf32::parse(&img[..len]).is_err(), "wrong machine");
+    }
+
+    // Appends a section header table to an image made by `build_elf`,
+    // containing a symbol table that defines the given symbols.
+    pub(crate) fn add_symbols(buf: &mut [u8], len: usize, symbols: &[(&[u8], u32)]) -> usize {
+        let put16 =
+            |buf: &mut [u8], o: usize, v: u16| buf[o..o + 2].copy_from_slice(&v.to_le_bytes());
+        let put32 =
+            |buf: &mut [u8], o: usize, v: u32| buf[o..o + 4].copy_from_slice(&v.to_le_bytes());
+
+        // String table, starting with the empty string as usual.
+        let strtab = len;
+        let mut next = strtab + 1;
+        buf[strtab] = 0;
+        let mut names = [0u32; 8];
+        for (i, (name, _)) in symbols.iter().enumerate() {
+            names[i] = (next - strtab) as u32;
+            buf[next..next + name.len()].copy_from_slice(name);
+            buf[next + name.len()] = 0;
+            next += name.len() + 1;
+        }
+        let strtab_size = next - strtab;
+
+        // Symbol table, starting with the null symbol.
+        let symtab = (next + 3) & !3;
+        buf[symtab..symtab + 16].copy_from_slice(&[0; 16]);
+        for (i, (_, value)) in symbols.iter().enumerate() {
+            let sym = symtab + (i + 1) * 16;
+            buf[sym..sym + 16].copy_from_slice(&[0; 16]);
+            put32(buf, sym, names[i]);
+            put32(buf, sym + 4, *value);
+        }
+        let symtab_size = (symbols.len() + 1) * 16;
+
+        // Section headers: null, .symtab, .strtab.
+        let shoff = symtab + symtab_size;
+        buf[shoff..shoff + 120].copy_from_slice(&[0; 120]);
+        let sh = shoff + 40;
+        put32(buf, sh + 4, 2); // SHT_SYMTAB
+        put32(buf, sh + 16, symtab as u32);
+        put32(buf, sh + 20, symtab_size as u32);
+        put32(buf, sh + 24, 2); // sh_link to .strtab
+        put32(buf, sh + 36, 16);
+        let sh = shoff + 80;
+        put32(buf, sh + 4, 3); // SHT_STRTAB
+        put32(buf, sh + 16, strtab as u32);
+        put32(buf, sh + 20, strtab_size as u32);
+
+        put32(buf, 32, shoff as u32); // e_shoff
+        put16(buf, 46, 40); // e_shentsize
+        put16(buf, 48, 3); // e_shnum
+        shoff + 120
+    }
+
+    #[test]
+    fn symbols() {
+        let mut img = [0u8; 512];
+        let len = build_elf(&[0x00000073], 0x100, 0, &mut img);
+        let len = add_symbols(&mut img, len, &[(b"tohost", 0x1000), (b"fromhost", 0x1040)]);
+        let elf = Elf32::parse(&img[..len]).unwrap();
+        assert_eq!(elf.symbol(b"tohost"), Some(0x1000));
+        assert_eq!(elf.symbol(b"fromhost"), Some(0x1040));
+        assert_eq!(elf.symbol(b"from"), None, "prefix of a symbol name");
+        assert_eq!(elf.symbol(b"begin_signature"), None);
+    }
+}
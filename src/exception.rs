@@ -1,5 +1,6 @@
 /// Represents the exception cause codes from the RISC-V machine ISA, as would
 /// be written to the `mcause` CSR.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ExceptionCause {
     InstructionAddressMisaligned = 0,
     InstructionAccessFault = 1,
@@ -1,6 +1,26 @@
+use crate::exception::ExceptionCause;
+use crate::instruction::{Instruction, Operation};
+use crate::register::{FloatRegister, IntRegister};
+
+use core::sync::atomic::AtomicBool;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+mod common;
+mod exec_128;
+mod exec_64;
 mod exec_32;
+mod run_32;
+mod trace_32;
+mod vector_32;
 
+#[cfg(feature = "std")]
+pub(crate) use common::fetch;
+pub use exec_128::step_rv128;
+pub use exec_64::step_rv64;
 pub use exec_32::step_rv32;
+pub use run_32::{run_rv32, run_rv32_traced};
+pub use trace_32::{step_rv32_traced, SpikeLog};
 
 /// Represents the outcome of perfoming one or more execution steps on a Hart.
 #[derive(Debug)]
@@ -52,4 +72,173 @@ pub enum ExecStatus<Addr> {
     /// program counter value because the hart PC will already have been
     /// adjusted to point to the `ebreak`'s direct successor.
     EnvironmentBreak(Addr),
+
+    /// Indicates that an instruction raised an exception that the hart did
+    /// not handle itself, because `Hart::exception` returned `false`.
+    ///
+    /// `pc` is the address of the instruction that raised the exception,
+    /// which the hart's program counter also still refers to, and `tval` is
+    /// the value that would be written to the trap value CSR, such as the
+    /// faulting address of a load or store. The instruction has had no
+    /// effect, so the caller can deal with the cause and then resume at the
+    /// same instruction, or skip over it.
+    Exception {
+        cause: ExceptionCause,
+        pc: Addr,
+        tval: Addr,
+    },
+}
+
+/// Conditions under which a run of many execution steps, such as with
+/// `run_rv32`, should stop even though the program has not reached an
+/// instruction that stops it.
+///
+/// The default value, also returned by `new`, has no limits at all. The
+/// `with_` methods each add a limit to it. Which limits are available
+/// depends on the crate features, so the struct can't be constructed
+/// directly outside of this crate.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct RunLimits<'a> {
+    /// The maximum number of execution steps to take, including any in
+    /// which the instruction could not be fetched.
+    pub max_steps: Option<u64>,
+
+    /// The time after which to stop. The clock is only checked every few
+    /// hundred steps, so the run may overshoot the deadline slightly.
+    #[cfg(feature = "std")]
+    pub deadline: Option<Instant>,
+
+    /// A flag that another host thread can set to stop the run. It is
+    /// checked before each step.
+    pub cancel: Option<&'a AtomicBool>,
+}
+
+impl<'a> RunLimits<'a> {
+    /// Returns limits that never stop a run.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the same limits with the given maximum number of steps.
+    pub fn with_max_steps(self, max_steps: u64) -> Self {
+        Self {
+            max_steps: Some(max_steps),
+            ..self
+        }
+    }
+
+    /// Returns the same limits with the given deadline.
+    #[cfg(feature = "std")]
+    pub fn with_deadline(self, deadline: Instant) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..self
+        }
+    }
+
+    /// Returns the same limits with the given cancellation flag.
+    pub fn with_cancel(self, cancel: &'a AtomicBool) -> Self {
+        Self {
+            cancel: Some(cancel),
+            ..self
+        }
+    }
+}
+
+/// The reason that a run of many execution steps stopped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopReason<Addr> {
+    /// The hart executed an "environment call" instruction at the given
+    /// address. See `ExecStatus::EnvironmentCall`.
+    EnvironmentCall(Addr),
+
+    /// The hart executed an "environment break" instruction at the given
+    /// address. See `ExecStatus::EnvironmentBreak`.
+    EnvironmentBreak(Addr),
+
+    /// The hart executed a "wait for interrupt" instruction.
+    WaitingForInterrupt,
+
+    /// An instruction raised an exception that the hart did not handle
+    /// itself. See `ExecStatus::Exception`.
+    Exception {
+        cause: ExceptionCause,
+        pc: Addr,
+        tval: Addr,
+    },
+
+    /// The hart's next instruction, at the given address and with the given
+    /// raw encoding, is not one that the executor recognizes. It has not
+    /// been executed, so the program counter still refers to it.
+    InvalidInstruction { pc: Addr, bits: u32 },
+
+    /// The run took the maximum number of steps in its `RunLimits`.
+    StepLimit,
+
+    /// The run passed the deadline in its `RunLimits`.
+    Deadline,
+
+    /// The cancellation flag in the run's `RunLimits` was set.
+    Cancelled,
+}
+
+/// Describes how a run of many execution steps ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunResult<Addr> {
+    pub reason: StopReason<Addr>,
+
+    /// The number of instructions that the hart retired, including a
+    /// stopping instruction such as `ecall`. Invalid instructions, which are
+    /// not executed, and instructions that raised an exception, whether the
+    /// hart handled it or the run stopped for it, are not counted, and
+    /// neither are steps in which the instruction could not be fetched.
+    pub retired: u64,
+}
+
+/// Describes the architectural effects of a single retired instruction, as
+/// reported to a `Tracer`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Commit<Op: Operation, Addr> {
+    /// The instruction that was executed, including its address.
+    pub inst: Instruction<Op, Addr>,
+
+    /// The raw encoding of the instruction. Only the low 16 bits are
+    /// significant when `inst.length` is 2.
+    pub bits: u32,
+
+    /// Indicates that the instruction raised an exception rather than
+    /// retiring, in which case none of the effects below are reported.
+    pub trapped: bool,
+
+    /// The integer register written by the instruction and the value written
+    /// to it. Writes to `x0` are not reported.
+    pub int_write: Option<(IntRegister, Addr)>,
+
+    /// The floating point register written by the instruction, and the bit
+    /// pattern of the value written to it.
+    pub float_write: Option<(FloatRegister, u64)>,
+
+    /// The memory location read by the instruction, if it is a load or an
+    /// atomic memory operation.
+    pub load: Option<MemoryAccess<Addr>>,
+
+    /// The memory location written by the instruction, if it is a store, and
+    /// the value that was written.
+    pub store: Option<(MemoryAccess<Addr>, u64)>,
+}
+
+/// Describes the location of a memory access performed by an instruction.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MemoryAccess<Addr> {
+    pub addr: Addr,
+
+    /// The size of the access, in bytes.
+    pub size: usize,
+}
+
+/// Implemented by types that want to observe each instruction executed by
+/// a traced execution step, such as `step_rv32_traced`.
+pub trait Tracer<Op: Operation, Addr> {
+    fn commit(&mut self, commit: &Commit<Op, Addr>);
 }
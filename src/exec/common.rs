@@ -0,0 +1,452 @@
+use crate::data::{Int, IntOps};
+use crate::exception::ExceptionCause;
+use crate::exec::ExecStatus;
+use crate::hart::Hart;
+use crate::instruction::{Instruction, Operation};
+use crate::isa::Extensions;
+use crate::memory::{Bus, FenceSet, MemoryError};
+use crate::raw_instruction::{instruction_length, RawInstruction, MAX_INSTRUCTION_LENGTH};
+use crate::register::{ControlStatusRegister, IntRegister};
+
+// The parts of the executors that don't depend on the XLEN are written once
+// here, generic over the type that a hart uses both for its addresses and for
+// its integer registers, and shared by the executors for each base ISA.
+
+/// Implemented by the unsigned integer types that can serve as a hart's
+/// XLEN-sized addresses and integer registers in the shared parts of the
+/// executors.
+pub(crate) trait Xlen: Int<Unsigned = Self> + IntOps + PartialEq {
+    /// Converts a byte count, such as an instruction length, to this type.
+    fn from_usize(v: usize) -> Self;
+
+    /// Converts the address from a `MemoryError`, which was originally an
+    /// address of this type, back to this type without losing any bits.
+    fn from_error_addr(v: u128) -> Self;
+
+    fn wrapping_add(self, other: Self) -> Self;
+}
+
+macro_rules! xlen_impl {
+    ($t:ty) => {
+        impl Xlen for $t {
+            fn from_usize(v: usize) -> Self {
+                v as $t
+            }
+
+            fn from_error_addr(v: u128) -> Self {
+                v as $t
+            }
+
+            fn wrapping_add(self, other: Self) -> Self {
+                <$t>::wrapping_add(self, other)
+            }
+        }
+    };
+}
+
+xlen_impl!(u32);
+xlen_impl!(u64);
+xlen_impl!(u128);
+
+// Reads the raw instruction at the given address, or returns the error
+// describing the failed instruction fetch.
+//
+// The instruction is read one parcel at a time, as many as its first parcel
+// says it has, so that a compressed instruction at the very end of a memory
+// region can be fetched without touching the bytes after it.
+pub(crate) fn fetch<X: Xlen, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    pc: X,
+) -> Result<RawInstruction, MemoryError> {
+    hart.with_memory(|mem| {
+        let mut parcels = [0; MAX_INSTRUCTION_LENGTH / 2];
+        parcels[0] = mem.fetch_halfword(pc)?;
+        let length = instruction_length(parcels[0]);
+        if length <= MAX_INSTRUCTION_LENGTH {
+            for (i, parcel) in parcels.iter_mut().enumerate().take(length / 2).skip(1) {
+                *parcel = mem.fetch_halfword(pc.wrapping_add(X::from_usize(i * 2)))?;
+            }
+        }
+        Ok(RawInstruction::from_parcels(&parcels))
+    })
+}
+
+// Prepares to execute an instruction that was decoded from the hart's
+// current program counter, or raises an illegal instruction exception if the
+// hart doesn't currently implement its operation, in which case the caller
+// must return the given status without executing it.
+pub(crate) fn begin_execute<X: Xlen, Op: Operation, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    inst: &Instruction<Op, X>,
+) -> Result<(), ExecStatus<X>> {
+    let pc = inst.pc;
+
+    // Operations from extensions that the hart doesn't currently implement
+    // are illegal, even though the decoder recognizes them.
+    let extensions = hart.extensions();
+    if !extensions.contains(inst.op.required_extensions()) {
+        return Err(raise(
+            hart,
+            pc,
+            ExceptionCause::IllegalInstruction,
+            X::zero(),
+        ));
+    }
+
+    // The embedded base ISAs have only registers x0 to x15, so referring to
+    // any other integer register is illegal too.
+    if extensions.contains(Extensions::E)
+        && inst
+            .op
+            .int_registers()
+            .iter()
+            .flatten()
+            .any(|r| r.num() >= 16)
+    {
+        return Err(raise(
+            hart,
+            pc,
+            ExceptionCause::IllegalInstruction,
+            X::zero(),
+        ));
+    }
+
+    // We pre-increment the program counter to the default successor
+    // instruction here because we've already captured the current
+    // instruction's PC as part of inst. Depending on which instruction
+    // this is, executing it might change the program counter again,
+    // overriding this default.
+    hart.write_pc(pc.wrapping_add(X::from_usize(inst.length)));
+    Ok(())
+}
+
+// Writes the target of a jump or taken branch to the program counter, or
+// raises an instruction-address-misaligned exception on the jump at `pc` if
+// the target doesn't meet the hart's IALIGN, in which case the caller must
+// return the given status without writing any other results.
+pub(crate) fn jump<X: Xlen, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    pc: X,
+    new_pc: X,
+) -> Result<(), ExecStatus<X>> {
+    if new_pc % X::from_usize(hart.instruction_alignment()) != X::zero() {
+        return Err(raise(
+            hart,
+            pc,
+            ExceptionCause::InstructionAddressMisaligned,
+            new_pc,
+        ));
+    }
+    hart.write_pc(new_pc);
+    Ok(())
+}
+
+// Raises an exception for the instruction at `pc`, which the hart may handle
+// itself or leave to the caller, and returns the resulting status. In either
+// case, the hart sees the program counter referring to the instruction.
+pub(crate) fn raise<X: Xlen, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    pc: X,
+    cause: ExceptionCause,
+    tval: X,
+) -> ExecStatus<X> {
+    hart.write_pc(pc);
+    if hart.exception(cause, tval) {
+        ExecStatus::Running
+    } else {
+        ExecStatus::Exception { cause, pc, tval }
+    }
+}
+
+// CSR Atomic Clear Bit: CSR Atomic Clear Bit reads the CSR, clears CSR bits set in rs1, and writes previous value to rd.
+//
+// >
+pub(crate) fn exec_csrrc<X: Xlen, Op: Operation, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    inst: Instruction<Op, X>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    csr: u32,
+) -> ExecStatus<X> {
+    let mask = hart.read_int_register(rs1);
+    let write = rs1.num() != 0;
+    exec_csr(hart, inst, rd, csr, true, |old| {
+        write.then_some(old & !mask)
+    })
+}
+
+// CSR Atomic Clear Bit Immediate: CSR Atomic Clear Bit Immediate reads the CSR, clears CSR bits set in the immediate, and writes previous value to rd.
+//
+// >
+pub(crate) fn exec_csrrci<X: Xlen, Op: Operation, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    inst: Instruction<Op, X>,
+    rd: IntRegister,
+    uimm: u32,
+    csr: u32,
+) -> ExecStatus<X> {
+    let mask = X::from_unsigned_word(uimm);
+    exec_csr(hart, inst, rd, csr, true, |old| {
+        (uimm != 0).then_some(old & !mask)
+    })
+}
+
+// CSR Atomic Set Bit: CSR Atomic Set Bit reads the CSR, sets CSR bits set in rs1, and writes previous value to rd.
+//
+// >
+pub(crate) fn exec_csrrs<X: Xlen, Op: Operation, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    inst: Instruction<Op, X>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    csr: u32,
+) -> ExecStatus<X> {
+    let mask = hart.read_int_register(rs1);
+    let write = rs1.num() != 0;
+    exec_csr(hart, inst, rd, csr, true, |old| write.then_some(old | mask))
+}
+
+// CSR Atomic Set Bit Immediate: CSR Atomic Set Bit Immediate reads the CSR, sets CSR bits set in the immediate, and writes previous value to rd.
+//
+// >
+pub(crate) fn exec_csrrsi<X: Xlen, Op: Operation, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    inst: Instruction<Op, X>,
+    rd: IntRegister,
+    uimm: u32,
+    csr: u32,
+) -> ExecStatus<X> {
+    let mask = X::from_unsigned_word(uimm);
+    exec_csr(hart, inst, rd, csr, true, |old| {
+        (uimm != 0).then_some(old | mask)
+    })
+}
+
+// CSR Atomic Read Write: CSR Atomic Read Write writes the value in rs1 to the CSR, and writes previous value to rd.
+//
+// >
+pub(crate) fn exec_csrrw<X: Xlen, Op: Operation, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    inst: Instruction<Op, X>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    csr: u32,
+) -> ExecStatus<X> {
+    let v = hart.read_int_register(rs1);
+    exec_csr(hart, inst, rd, csr, rd.num() != 0, |_| Some(v))
+}
+
+// CSR Atomic Read Write Immediate: CSR Atomic Read Write Immediate writes the immediate value to the CSR, and writes previous value to rd.
+//
+// >
+pub(crate) fn exec_csrrwi<X: Xlen, Op: Operation, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    inst: Instruction<Op, X>,
+    rd: IntRegister,
+    uimm: u32,
+    csr: u32,
+) -> ExecStatus<X> {
+    let v = X::from_unsigned_word(uimm);
+    exec_csr(hart, inst, rd, csr, rd.num() != 0, |_| Some(v))
+}
+
+// Performs the common part of the CSR instructions: reading the CSR into rd
+// if `read` is set, and then writing the value that `update` computes from
+// the old one, if any. An instruction that neither reads nor writes the CSR
+// must have no side-effects, so the CSR is accessed only as required, and an
+// unsupported CSR raises an illegal instruction exception.
+fn exec_csr<X: Xlen, Op: Operation, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    inst: Instruction<Op, X>,
+    rd: IntRegister,
+    csr: u32,
+    read: bool,
+    update: impl FnOnce(X) -> Option<X>,
+) -> ExecStatus<X> {
+    let reg = ControlStatusRegister::numbered(csr as usize);
+    let old = if read {
+        match hart.read_csr(reg) {
+            Ok(v) => v,
+            Err(_) => return raise(hart, inst.pc, ExceptionCause::IllegalInstruction, X::zero()),
+        }
+    } else {
+        X::zero()
+    };
+    if let Some(new) = update(old) {
+        if hart.write_csr(reg, new).is_err() {
+            return raise(hart, inst.pc, ExceptionCause::IllegalInstruction, X::zero());
+        }
+    }
+    if read {
+        hart.write_int_register(rd, old);
+    }
+    ExecStatus::Running
+}
+
+// Environment Break to Debugger: .
+//
+// >
+pub(crate) fn exec_ebreak<X: Xlen, Op: Operation, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    inst: Instruction<Op, X>,
+) -> ExecStatus<X> {
+    if !hart.environment_break(inst.pc) {
+        ExecStatus::EnvironmentBreak(inst.pc)
+    } else {
+        ExecStatus::Running
+    }
+}
+
+// Environment Call: .
+//
+// >
+pub(crate) fn exec_ecall<X: Xlen, Op: Operation, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    inst: Instruction<Op, X>,
+) -> ExecStatus<X> {
+    if !hart.environment_call(inst.pc) {
+        ExecStatus::EnvironmentCall(inst.pc)
+    } else {
+        ExecStatus::Running
+    }
+}
+
+// Fence: Order device I/O and memory accesses viewed by other threads and devices.
+//
+// >
+pub(crate) fn exec_fence<X: Xlen, Op: Operation, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    _inst: Instruction<Op, X>,
+    pred: u32,
+    succ: u32,
+) -> ExecStatus<X> {
+    hart.fence_data(FenceSet::from_bits(pred), FenceSet::from_bits(succ));
+    ExecStatus::Running
+}
+
+// Fence Instruction: Synchronize the instruction and data streams.
+//
+// >
+pub(crate) fn exec_fence_i<X: Xlen, Op: Operation, Mem: Bus<X>>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    _inst: Instruction<Op, X>,
+) -> ExecStatus<X> {
+    hart.fence_code();
+    ExecStatus::Running
+}
+
+pub(crate) fn exec_binary_op<X: Xlen, Mem: Bus<X>, F: FnOnce(X, X) -> X>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    callback: F,
+) -> ExecStatus<X> {
+    let a = hart.read_int_register(rs1);
+    let b = hart.read_int_register(rs2);
+    let result = callback(a, b);
+    hart.write_int_register(rd, result);
+    ExecStatus::Running
+}
+
+pub(crate) fn exec_binary_op_imm<X: Xlen, Mem: Bus<X>, F: FnOnce(X, i32) -> X>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    imm: i32,
+    callback: F,
+) -> ExecStatus<X> {
+    let a = hart.read_int_register(rs1);
+    let result = callback(a, imm);
+    hart.write_int_register(rd, result);
+    ExecStatus::Running
+}
+
+pub(crate) fn exec_unary_op<X: Xlen, Mem: Bus<X>, F: FnOnce(X) -> X>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    callback: F,
+) -> ExecStatus<X> {
+    let a = hart.read_int_register(rs1);
+    let result = callback(a);
+    hart.write_int_register(rd, result);
+    ExecStatus::Running
+}
+
+pub(crate) fn exec_shift_op_imm<X: Xlen, Mem: Bus<X>, F: FnOnce(X, u32) -> X>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+    callback: F,
+) -> ExecStatus<X> {
+    let a = hart.read_int_register(rs1);
+    let result = callback(a, shamt);
+    hart.write_int_register(rd, result);
+    ExecStatus::Running
+}
+
+pub(crate) fn exec_branch_binary_cond<X: Xlen, Op: Operation, Mem: Bus<X>, F>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    inst: Instruction<Op, X>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+    callback: F,
+) -> ExecStatus<X>
+where
+    F: FnOnce(X, X) -> bool,
+{
+    let a = hart.read_int_register(rs1);
+    let b = hart.read_int_register(rs2);
+    if callback(a, b) {
+        let new_pc = inst.pc.wrapping_add(X::from_signed_word(simm));
+        if let Err(status) = jump(hart, inst.pc, new_pc) {
+            return status;
+        }
+    }
+    ExecStatus::Running
+}
+
+pub(crate) fn exec_load_mem<X: Xlen, Op: Operation, Mem: Bus<X>, F>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    inst: Instruction<Op, X>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+    callback: F,
+) -> ExecStatus<X>
+where
+    F: FnOnce(&mut Mem, X) -> Result<X, MemoryError>,
+{
+    let base_addr = hart.read_int_register(rs1);
+    let addr = base_addr.wrapping_add(X::from_signed_word(simm));
+    match hart.with_memory(|mem| callback(mem, addr)) {
+        Ok(v) => {
+            hart.write_int_register(rd, v);
+            ExecStatus::Running
+        }
+        Err(e) => raise(hart, inst.pc, e.cause(), X::from_error_addr(e.addr)),
+    }
+}
+
+pub(crate) fn exec_store_mem<X: Xlen, Op: Operation, Mem: Bus<X>, F>(
+    hart: &mut impl Hart<X, X, f64, Mem>,
+    inst: Instruction<Op, X>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+    callback: F,
+) -> ExecStatus<X>
+where
+    F: FnOnce(&mut Mem, X, X) -> Result<(), MemoryError>,
+{
+    let v = hart.read_int_register(rs2);
+    let base_addr = hart.read_int_register(rs1);
+    let addr = base_addr.wrapping_add(X::from_signed_word(simm));
+    match hart.with_memory(|mem| callback(mem, addr, v)) {
+        Ok(_) => ExecStatus::Running,
+        Err(e) => raise(hart, inst.pc, e.cause(), X::from_error_addr(e.addr)),
+    }
+}
@@ -0,0 +1,1517 @@
+use crate::data::Int;
+use crate::exception::ExceptionCause;
+use crate::exec::ExecStatus;
+use crate::hart::Hart;
+use crate::instruction::Instruction;
+use crate::instruction::OperationRV128;
+use crate::memory::Bus;
+use crate::register::{ControlStatusRegister, IntRegister};
+
+use super::common::{begin_execute, fetch, jump, raise};
+use super::common::{exec_binary_op, exec_binary_op_imm, exec_shift_op_imm};
+use super::common::{exec_branch_binary_cond, exec_load_mem, exec_store_mem};
+use super::common::{exec_csrrc, exec_csrrci, exec_csrrs, exec_csrrsi, exec_csrrw, exec_csrrwi};
+use super::common::{exec_ebreak, exec_ecall, exec_fence, exec_fence_i};
+
+type Op = OperationRV128;
+
+/// Performs a single execution step against the given RV128 hart.
+///
+/// An execution step is usually the execution of a single instruction, but
+/// it can also include handling exceptions that are raised in retrieving the
+/// next instruction from memory.
+///
+/// When this function returns, the state of the hart will have been modified
+/// to reflect the side-effects of the action.
+pub fn step_rv128<Mem: Bus<u128>>(hart: &mut impl Hart<u128, u128, f64, Mem>) -> ExecStatus<u128> {
+    let pc = hart.read_pc();
+    match fetch(hart, pc) {
+        Ok(raw_inst) => execute_rv128(hart, Instruction::decode_raw(raw_inst, pc)),
+        Err(e) => raise(hart, pc, e.cause(), e.addr),
+    }
+}
+
+// Executes an instruction that was decoded from the hart's current
+// program counter.
+fn execute_rv128<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+) -> ExecStatus<u128> {
+    if let Err(status) = begin_execute(hart, &inst) {
+        return status;
+    }
+    dispatch_instruction(inst, hart)
+}
+
+// The main instruction dispatch logic for RV128: selects a suitable
+// implementation function based on the specific operation in the instruction.
+fn dispatch_instruction<Mem: Bus<u128>>(
+    inst: Instruction<Op, u128>,
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+) -> ExecStatus<u128> {
+    match inst.op {
+        Op::Add { rd, rs1, rs2 } => exec_add(hart, inst, rd, rs1, rs2),
+        Op::Addd { rd, rs1, rs2 } => exec_addd(hart, inst, rd, rs1, rs2),
+        Op::Addi { rd, rs1, simm } => exec_addi(hart, inst, rd, rs1, simm),
+        Op::Addid { rd, rs1, simm } => exec_addid(hart, inst, rd, rs1, simm),
+        Op::Addiw { rd, rs1, simm } => exec_addiw(hart, inst, rd, rs1, simm),
+        Op::Addw { rd, rs1, rs2 } => exec_addw(hart, inst, rd, rs1, rs2),
+        Op::And { rd, rs1, rs2 } => exec_and(hart, inst, rd, rs1, rs2),
+        Op::Andi { rd, rs1, simm } => exec_andi(hart, inst, rd, rs1, simm),
+        Op::Auipc { rd, simm } => exec_auipc(hart, inst, rd, simm),
+        Op::Beq { rs1, rs2, simm } => exec_beq(hart, inst, rs1, rs2, simm),
+        Op::Bge { rs1, rs2, simm } => exec_bge(hart, inst, rs1, rs2, simm),
+        Op::Bgeu { rs1, rs2, simm } => exec_bgeu(hart, inst, rs1, rs2, simm),
+        Op::Blt { rs1, rs2, simm } => exec_blt(hart, inst, rs1, rs2, simm),
+        Op::Bltu { rs1, rs2, simm } => exec_bltu(hart, inst, rs1, rs2, simm),
+        Op::Bne { rs1, rs2, simm } => exec_bne(hart, inst, rs1, rs2, simm),
+        Op::Csrrc { rd, rs1, csr } => exec_csrrc(hart, inst, rd, rs1, csr),
+        Op::Csrrci { rd, uimm, csr } => exec_csrrci(hart, inst, rd, uimm, csr),
+        Op::Csrrs { rd, rs1, csr } => exec_csrrs(hart, inst, rd, rs1, csr),
+        Op::Csrrsi { rd, uimm, csr } => exec_csrrsi(hart, inst, rd, uimm, csr),
+        Op::Csrrw { rd, rs1, csr } => exec_csrrw(hart, inst, rd, rs1, csr),
+        Op::Csrrwi { rd, uimm, csr } => exec_csrrwi(hart, inst, rd, uimm, csr),
+        Op::Div { rd, rs1, rs2 } => exec_div(hart, inst, rd, rs1, rs2),
+        Op::Divd { rd, rs1, rs2 } => exec_divd(hart, inst, rd, rs1, rs2),
+        Op::Divu { rd, rs1, rs2 } => exec_divu(hart, inst, rd, rs1, rs2),
+        Op::Divud { rd, rs1, rs2 } => exec_divud(hart, inst, rd, rs1, rs2),
+        Op::Divuw { rd, rs1, rs2 } => exec_divuw(hart, inst, rd, rs1, rs2),
+        Op::Divw { rd, rs1, rs2 } => exec_divw(hart, inst, rd, rs1, rs2),
+        Op::Ebreak => exec_ebreak(hart, inst),
+        Op::Ecall => exec_ecall(hart, inst),
+        Op::Fence { pred, succ } => exec_fence(hart, inst, pred, succ),
+        Op::FenceI => exec_fence_i(hart, inst),
+        Op::Jal { rd, simm } => exec_jal(hart, inst, rd, simm),
+        Op::Jalr { rd, rs1, simm } => exec_jalr(hart, inst, rd, rs1, simm),
+        Op::Lb { rd, rs1, simm } => exec_lb(hart, inst, rd, rs1, simm),
+        Op::Lbu { rd, rs1, simm } => exec_lbu(hart, inst, rd, rs1, simm),
+        Op::Ld { rd, rs1, simm } => exec_ld(hart, inst, rd, rs1, simm),
+        Op::Ldu { rd, rs1, simm } => exec_ldu(hart, inst, rd, rs1, simm),
+        Op::Lh { rd, rs1, simm } => exec_lh(hart, inst, rd, rs1, simm),
+        Op::Lhu { rd, rs1, simm } => exec_lhu(hart, inst, rd, rs1, simm),
+        Op::Lq { rd, rs1, simm } => exec_lq(hart, inst, rd, rs1, simm),
+        Op::Lui { rd, simm } => exec_lui(hart, inst, rd, simm),
+        Op::Lw { rd, rs1, simm } => exec_lw(hart, inst, rd, rs1, simm),
+        Op::Lwu { rd, rs1, simm } => exec_lwu(hart, inst, rd, rs1, simm),
+        Op::Mul { rd, rs1, rs2 } => exec_mul(hart, inst, rd, rs1, rs2),
+        Op::Muld { rd, rs1, rs2 } => exec_muld(hart, inst, rd, rs1, rs2),
+        Op::Mulh { rd, rs1, rs2 } => exec_mulh(hart, inst, rd, rs1, rs2),
+        Op::Mulhsu { rd, rs1, rs2 } => exec_mulhsu(hart, inst, rd, rs1, rs2),
+        Op::Mulhu { rd, rs1, rs2 } => exec_mulhu(hart, inst, rd, rs1, rs2),
+        Op::Mulw { rd, rs1, rs2 } => exec_mulw(hart, inst, rd, rs1, rs2),
+        Op::Or { rd, rs1, rs2 } => exec_or(hart, inst, rd, rs1, rs2),
+        Op::Ori { rd, rs1, simm } => exec_ori(hart, inst, rd, rs1, simm),
+        Op::Rem { rd, rs1, rs2 } => exec_rem(hart, inst, rd, rs1, rs2),
+        Op::Remd { rd, rs1, rs2 } => exec_remd(hart, inst, rd, rs1, rs2),
+        Op::Remu { rd, rs1, rs2 } => exec_remu(hart, inst, rd, rs1, rs2),
+        Op::Remud { rd, rs1, rs2 } => exec_remud(hart, inst, rd, rs1, rs2),
+        Op::Remuw { rd, rs1, rs2 } => exec_remuw(hart, inst, rd, rs1, rs2),
+        Op::Remw { rd, rs1, rs2 } => exec_remw(hart, inst, rd, rs1, rs2),
+        Op::Sb { rs1, rs2, simm } => exec_sb(hart, inst, rs1, rs2, simm),
+        Op::Sd { rs1, rs2, simm } => exec_sd(hart, inst, rs1, rs2, simm),
+        Op::Sh { rs1, rs2, simm } => exec_sh(hart, inst, rs1, rs2, simm),
+        Op::Sll { rd, rs1, rs2 } => exec_sll(hart, inst, rd, rs1, rs2),
+        Op::Slld { rd, rs1, rs2 } => exec_slld(hart, inst, rd, rs1, rs2),
+        Op::Slli { rd, rs1, shamt } => exec_slli(hart, inst, rd, rs1, shamt),
+        Op::Sllid { rd, rs1, shamt } => exec_sllid(hart, inst, rd, rs1, shamt),
+        Op::Slliw { rd, rs1, shamt } => exec_slliw(hart, inst, rd, rs1, shamt),
+        Op::Sllw { rd, rs1, rs2 } => exec_sllw(hart, inst, rd, rs1, rs2),
+        Op::Slt { rd, rs1, rs2 } => exec_slt(hart, inst, rd, rs1, rs2),
+        Op::Slti { rd, rs1, simm } => exec_slti(hart, inst, rd, rs1, simm),
+        Op::Sltiu { rd, rs1, simm } => exec_sltiu(hart, inst, rd, rs1, simm),
+        Op::Sltu { rd, rs1, rs2 } => exec_sltu(hart, inst, rd, rs1, rs2),
+        Op::Sq { rs1, rs2, simm } => exec_sq(hart, inst, rs1, rs2, simm),
+        Op::Sra { rd, rs1, rs2 } => exec_sra(hart, inst, rd, rs1, rs2),
+        Op::Srad { rd, rs1, rs2 } => exec_srad(hart, inst, rd, rs1, rs2),
+        Op::Srai { rd, rs1, shamt } => exec_srai(hart, inst, rd, rs1, shamt),
+        Op::Sraid { rd, rs1, shamt } => exec_sraid(hart, inst, rd, rs1, shamt),
+        Op::Sraiw { rd, rs1, shamt } => exec_sraiw(hart, inst, rd, rs1, shamt),
+        Op::Sraw { rd, rs1, rs2 } => exec_sraw(hart, inst, rd, rs1, rs2),
+        Op::Srl { rd, rs1, rs2 } => exec_srl(hart, inst, rd, rs1, rs2),
+        Op::Srld { rd, rs1, rs2 } => exec_srld(hart, inst, rd, rs1, rs2),
+        Op::Srli { rd, rs1, shamt } => exec_srli(hart, inst, rd, rs1, shamt),
+        Op::Srlid { rd, rs1, shamt } => exec_srlid(hart, inst, rd, rs1, shamt),
+        Op::Srliw { rd, rs1, shamt } => exec_srliw(hart, inst, rd, rs1, shamt),
+        Op::Srlw { rd, rs1, rs2 } => exec_srlw(hart, inst, rd, rs1, rs2),
+        Op::Sub { rd, rs1, rs2 } => exec_sub(hart, inst, rd, rs1, rs2),
+        Op::Subd { rd, rs1, rs2 } => exec_subd(hart, inst, rd, rs1, rs2),
+        Op::Subw { rd, rs1, rs2 } => exec_subw(hart, inst, rd, rs1, rs2),
+        Op::Sw { rs1, rs2, simm } => exec_sw(hart, inst, rs1, rs2, simm),
+        Op::Uret => exec_uret(hart, inst),
+        Op::Wfi => exec_wfi(hart, inst),
+        Op::Xor { rd, rs1, rs2 } => exec_xor(hart, inst, rd, rs1, rs2),
+        Op::Xori { rd, rs1, simm } => exec_xori(hart, inst, rd, rs1, simm),
+        _ => raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0),
+    }
+}
+
+// Add: Add rs2 to rs1 and place the result into rd.
+//
+// > rd ← sx(rs1) + sx(rs2)
+fn exec_add<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a.wrapping_add(b))
+}
+
+// Add Double: Add 64-bit rs2 to 64-bit rs1 and place the sign-extended result into rd.
+//
+// > rd ← s64(rs1) + s64(rs2)
+fn exec_addd<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        from_double((a as i64).wrapping_add(b as i64))
+    })
+}
+
+// Add Immediate: Add sign-extended 12-bit immediate to register rs1 and place the result in rd.
+//
+// > rd ← rs1 + sx(imm)
+fn exec_addi<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| {
+        a.wrapping_add(u128::from_signed_word(b))
+    })
+}
+
+// Add Immediate Double: Add sign-extended 12-bit immediate to 64-bit register rs1 and place the sign-extended result in rd.
+//
+// > rd ← s64(rs1) + sx(imm)
+fn exec_addid<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| {
+        from_double((a as i64).wrapping_add(b as i64))
+    })
+}
+
+// Add Immediate Word: Add sign-extended 12-bit immediate to 32-bit register rs1 and place the sign-extended result in rd.
+//
+// > rd ← s32(rs1) + sx(imm)
+fn exec_addiw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| {
+        u128::from_signed_word(a.to_signed_word().wrapping_add(b))
+    })
+}
+
+// Add Word: Add 32-bit rs2 to 32-bit rs1 and place the sign-extended result into rd.
+//
+// > rd ← s32(rs1) + s32(rs2)
+fn exec_addw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u128::from_signed_word(a.to_signed_word().wrapping_add(b.to_signed_word()))
+    })
+}
+
+// And: Set rd to the bitwise and of rs1 and rs2.
+//
+// > rd ← ux(rs1) ∧ ux(rs2)
+fn exec_and<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a & b)
+}
+
+// And Immediate: Set rd to the bitwise and of rs1 with the sign-extended 12-bit immediate.
+//
+// > rd ← ux(rs1) ∧ ux(imm)
+fn exec_andi<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| a & u128::from_signed_word(b))
+}
+
+// Add Upper Immediate to PC: Place the PC plus the 20-bit signed immediate (shited 12 bits left) into rd (used before JALR).
+//
+// > rd ← pc + imm
+fn exec_auipc<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    let result = inst.pc.wrapping_add(u128::from_signed_word(simm));
+    hart.write_int_register(rd, result);
+    ExecStatus::Running
+}
+
+// Branch Equal: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 == rs2.
+//
+// > if rs1 = rs2 then pc ← pc + imm
+fn exec_beq<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| a == b)
+}
+
+// Branch Greater than Equal: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 >= rs2 (signed).
+//
+// > if rs1 ≥ rs2 then pc ← pc + imm
+fn exec_bge<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| {
+        a.to_signed() >= b.to_signed()
+    })
+}
+
+// Branch Greater than Equal Unsigned: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 >= rs2 (unsigned).
+//
+// > if rs1 ≥ rs2 then pc ← pc + imm
+fn exec_bgeu<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| a >= b)
+}
+
+// Branch Less Than: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 < rs2 (signed).
+//
+// > if rs1 < rs2 then pc ← pc + imm
+fn exec_blt<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| {
+        a.to_signed() < b.to_signed()
+    })
+}
+
+// Branch Less Than Unsigned: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 < rs2 (unsigned).
+//
+// > if rs1 < rs2 then pc ← pc + imm
+fn exec_bltu<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| a < b)
+}
+
+// Branch Not Equal: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 != rs2.
+//
+// > if rs1 ≠ rs2 then pc ← pc + imm
+fn exec_bne<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| a != b)
+}
+
+// Divide Signed: Divide rs1 (dividend) by rs2 (divisor) and place the quotient in rd (signed).
+//
+// > rd ← sx(rs1) ÷ sx(rs2)
+fn exec_div<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        let aw = a.to_signed();
+        let bw = b.to_signed();
+        if bw == 0 {
+            // Division by zero produces an all-ones result
+            return u128::MAX;
+        }
+        // The most negative value divided by -1 overflows back to itself.
+        u128::from_signed(aw.wrapping_div(bw))
+    })
+}
+
+// Divide Signed Double: Divide 64-bit rs1 (dividend) by 64-bit rs2 (divisor) and place the sign-extended quotient in rd (signed).
+//
+// > rd ← s64(rs1) ÷ s64(rs2)
+fn exec_divd<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        let aw = a as i64;
+        let bw = b as i64;
+        if bw == 0 {
+            return u128::MAX;
+        }
+        from_double(aw.wrapping_div(bw))
+    })
+}
+
+// Divide Unsigned: Divide rs1 (dividend) by rs2 (divisor) and place the quotient in rd (unsigned).
+//
+// > rd ← ux(rs1) ÷ ux(rs2)
+fn exec_divu<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b {
+        // Division by zero produces all-ones
+        0 => u128::MAX,
+        _ => a / b,
+    })
+}
+
+// Divide Unsigned Double: Divide 64-bit rs1 (dividend) by 64-bit rs2 (divisor) and place the sign-extended quotient in rd (unsigned).
+//
+// > rd ← u64(rs1) ÷ u64(rs2)
+fn exec_divud<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b as u64 {
+        0 => u128::MAX,
+        bw => from_double(((a as u64) / bw) as i64),
+    })
+}
+
+// Divide Unsigned Word: Divide 32-bit rs1 (dividend) by 32-bit rs2 (divisor) and place the sign-extended quotient in rd (unsigned).
+//
+// > rd ← u32(rs1) ÷ u32(rs2)
+fn exec_divuw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b.to_unsigned_word() {
+        0 => u128::MAX,
+        bw => u128::from_unsigned_word(a.to_unsigned_word() / bw),
+    })
+}
+
+// Divide Signed Word: Divide 32-bit rs1 (dividend) by 32-bit rs2 (divisor) and place the sign-extended quotient in rd (signed).
+//
+// > rd ← s32(rs1) ÷ s32(rs2)
+fn exec_divw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b.to_signed_word() {
+        0 => u128::MAX,
+        bw => u128::from_signed_word(a.to_signed_word().wrapping_div(bw)),
+    })
+}
+
+// Jump and Link: Jump to the PC plus 20-bit signed immediate while saving PC+4 into rd.
+//
+// > rd ← pc + length(inst) ; pc ← pc + imm
+fn exec_jal<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    let new_pc = inst.pc.wrapping_add(u128::from_signed_word(simm));
+    if let Err(status) = jump(hart, inst.pc, new_pc) {
+        return status;
+    }
+    let ret_pc = inst.pc.wrapping_add(inst.length as u128);
+    hart.write_int_register(rd, ret_pc);
+    ExecStatus::Running
+}
+
+// Jump and Link Register: Jump to rs1 plus the 12-bit signed immediate while saving PC+4 into rd.
+//
+// > rd ← pc + length(inst) ; pc ← (rs1 + imm) ∧ -2
+fn exec_jalr<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    let base_addr = hart.read_int_register(rs1);
+    let new_pc = base_addr.wrapping_add(u128::from_signed_word(simm)) & !1;
+    if let Err(status) = jump(hart, inst.pc, new_pc) {
+        return status;
+    }
+    let ret_pc = inst.pc.wrapping_add(inst.length as u128);
+    hart.write_int_register(rd, ret_pc);
+    ExecStatus::Running
+}
+
+// Load Byte: Load 8-bit value from addr in rs1 plus the 12-bit signed immediate and place sign-extended result into rd.
+//
+// > rd ← s8[rs1 + imm]
+fn exec_lb<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_byte(addr)?;
+        Ok(u128::from_signed(v as i8 as i128))
+    })
+}
+
+// Load Byte Unsigned: Load 8-bit value from addr in rs1 plus the 12-bit signed immediate and place zero-extended result into rd.
+//
+// > rd ← u8[rs1 + imm]
+fn exec_lbu<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_byte(addr)?;
+        Ok(v as u128)
+    })
+}
+
+// Load Double: Load 64-bit value from addr in rs1 plus the 12-bit signed immediate and place sign-extended result into rd.
+//
+// > rd ← s64[rs1 + imm]
+fn exec_ld<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_longword(addr)?;
+        Ok(from_double(v as i64))
+    })
+}
+
+// Load Double Unsigned: Load 64-bit value from addr in rs1 plus the 12-bit signed immediate and place zero-extended result into rd.
+//
+// > rd ← u64[rs1 + imm]
+fn exec_ldu<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_longword(addr)?;
+        Ok(v as u128)
+    })
+}
+
+// Load Half: Load 16-bit value from addr in rs1 plus the 12-bit signed immediate and place sign-extended result into rd.
+//
+// > rd ← s16[rs1 + imm]
+fn exec_lh<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_halfword(addr)?;
+        Ok(u128::from_signed(v as i16 as i128))
+    })
+}
+
+// Load Half Unsigned: Load 16-bit value from addr in rs1 plus the 12-bit signed immediate and place zero-extended result into rd.
+//
+// > rd ← u16[rs1 + imm]
+fn exec_lhu<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_halfword(addr)?;
+        Ok(v as u128)
+    })
+}
+
+// Load Quad: Load 128-bit value from addr in rs1 plus the 12-bit signed immediate and place the result into rd.
+//
+// > rd ← u128[rs1 + imm]
+fn exec_lq<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        mem.read_quadword(addr)
+    })
+}
+
+// Load Upper Immediate: Set and sign extend the 20-bit immediate (shited 12 bits left) and zero the bottom 12 bits into rd.
+//
+// > rd ← imm
+fn exec_lui<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    hart.write_int_register(rd, u128::from_signed_word(simm));
+    ExecStatus::Running
+}
+
+// Load Word: Load 32-bit value from addr in rs1 plus the 12-bit signed immediate and place sign-extended result into rd.
+//
+// > rd ← s32[rs1 + imm]
+fn exec_lw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_word(addr)?;
+        Ok(u128::from_unsigned_word(v))
+    })
+}
+
+// Load Word Unsigned: Load 32-bit value from addr in rs1 plus the 12-bit signed immediate and place zero-extended result into rd.
+//
+// > rd ← u32[rs1 + imm]
+fn exec_lwu<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_word(addr)?;
+        Ok(v as u128)
+    })
+}
+
+// Multiply: Multiply rs1 by rs2 and place the result in rd.
+//
+// > rd ← ux(rs1) × ux(rs2)
+fn exec_mul<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a.wrapping_mul(b))
+}
+
+// Multiply Double: Multiply 64-bit rs1 by 64-bit rs2 and place the sign-extended result in rd.
+//
+// > rd ← u64(rs1) × u64(rs2)
+fn exec_muld<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        from_double((a as i64).wrapping_mul(b as i64))
+    })
+}
+
+// Multiply High Signed Signed: Multiply signed rs1 by signed rs2 and place the high bits of the result in rd.
+//
+// > rd ← (sx(rs1) × sx(rs2)) » xlen
+fn exec_mulh<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        // There's no wider type to multiply in, so we take the unsigned high
+        // half and then correct it for each operand that's negative, whose
+        // unsigned value is 2^128 too large.
+        let mut result = mul_high_unsigned(a, b);
+        if a.to_signed() < 0 {
+            result = result.wrapping_sub(b);
+        }
+        if b.to_signed() < 0 {
+            result = result.wrapping_sub(a);
+        }
+        result
+    })
+}
+
+// Multiply High Signed Unsigned: Multiply signed rs1 by unsigned rs2 and place the high bits of the result in rd.
+//
+// > rd ← (sx(rs1) × ux(rs2)) » xlen
+fn exec_mulhsu<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        let result = mul_high_unsigned(a, b);
+        if a.to_signed() < 0 {
+            result.wrapping_sub(b)
+        } else {
+            result
+        }
+    })
+}
+
+// Multiply High Unsigned Unsigned: Multiply unsigned rs1 by unsigned rs2 and place the high bits of the result in rd.
+//
+// > rd ← (ux(rs1) × ux(rs2)) » xlen
+fn exec_mulhu<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, mul_high_unsigned)
+}
+
+// Multiply Word: Multiply 32-bit rs1 by 32-bit rs2 and place the sign-extended result in rd.
+//
+// > rd ← u32(rs1) × u32(rs2)
+fn exec_mulw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u128::from_signed_word(a.to_signed_word().wrapping_mul(b.to_signed_word()))
+    })
+}
+
+// Or: Set rd to the bitwise or of rs1 and rs2.
+//
+// > rd ← ux(rs1) ∨ ux(rs2)
+fn exec_or<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a | b)
+}
+
+// Or Immediate: Set rd to the bitwise or of rs1 with the sign-extended 12-bit immediate.
+//
+// > rd ← ux(rs1) ∨ ux(imm)
+fn exec_ori<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| a | u128::from_signed_word(b))
+}
+
+// Remainder Signed: Divide rs1 (dividend) by rs2 (divisor) and place the remainder in rd (signed).
+//
+// > rd ← sx(rs1) mod sx(rs2)
+fn exec_rem<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b.to_signed() {
+        // Division by zero produces the first operand
+        0 => a,
+        // The most negative value divided by -1 overflows, producing zero.
+        bw => u128::from_signed(a.to_signed().wrapping_rem(bw)),
+    })
+}
+
+// Remainder Signed Double: Divide 64-bit rs1 (dividend) by 64-bit rs2 (divisor) and place the sign-extended remainder in rd (signed).
+//
+// > rd ← s64(rs1) mod s64(rs2)
+fn exec_remd<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b as i64 {
+        0 => from_double(a as i64),
+        bw => from_double((a as i64).wrapping_rem(bw)),
+    })
+}
+
+// Remainder Unsigned: Divide rs1 (dividend) by rs2 (divisor) and place the remainder in rd (unsigned).
+//
+// > rd ← ux(rs1) mod ux(rs2)
+fn exec_remu<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b {
+        // Division by zero produces the first operand
+        0 => a,
+        _ => a % b,
+    })
+}
+
+// Remainder Unsigned Double: Divide 64-bit rs1 (dividend) by 64-bit rs2 (divisor) and place the sign-extended remainder in rd (unsigned).
+//
+// > rd ← u64(rs1) mod u64(rs2)
+fn exec_remud<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b as u64 {
+        0 => from_double(a as i64),
+        bw => from_double(((a as u64) % bw) as i64),
+    })
+}
+
+// Remainder Unsigned Word: Divide 32-bit rs1 (dividend) by 32-bit rs2 (divisor) and place the sign-extended remainder in rd (unsigned).
+//
+// > rd ← u32(rs1) mod u32(rs2)
+fn exec_remuw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b.to_unsigned_word() {
+        0 => u128::from_unsigned_word(a.to_unsigned_word()),
+        bw => u128::from_unsigned_word(a.to_unsigned_word() % bw),
+    })
+}
+
+// Remainder Signed Word: Divide 32-bit rs1 (dividend) by 32-bit rs2 (divisor) and place the sign-extended remainder in rd (signed).
+//
+// > rd ← s32(rs1) mod s32(rs2)
+fn exec_remw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b.to_signed_word() {
+        0 => u128::from_signed_word(a.to_signed_word()),
+        bw => u128::from_signed_word(a.to_signed_word().wrapping_rem(bw)),
+    })
+}
+
+// Store Byte: Store 8-bit value from the low bits of rs2 to addr in rs1 plus the 12-bit signed immediate.
+//
+// > u8[rs1 + imm] ← rs2
+fn exec_sb<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_store_mem(hart, inst, rs1, rs2, simm, |mem, addr, v| {
+        mem.write_byte(addr, v as u8)
+    })
+}
+
+// Store Double: Store 64-bit value from the low bits of rs2 to addr in rs1 plus the 12-bit signed immediate.
+//
+// > u64[rs1 + imm] ← rs2
+fn exec_sd<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_store_mem(hart, inst, rs1, rs2, simm, |mem, addr, v| {
+        mem.write_longword(addr, v as u64)
+    })
+}
+
+// Store Half: Store 16-bit value from the low bits of rs2 to addr in rs1 plus the 12-bit signed immediate.
+//
+// > u16[rs1 + imm] ← rs2
+fn exec_sh<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_store_mem(hart, inst, rs1, rs2, simm, |mem, addr, v| {
+        mem.write_halfword(addr, v as u16)
+    })
+}
+
+// Shift Left Logical: Shift rs1 left by the by the lower 7 bits in rs2 and place the result into rd.
+//
+// > rd ← ux(rs1) « rs2
+fn exec_sll<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a << (b & 0b1111111))
+}
+
+// Shift Left Logical Double: Shift 64-bit rs1 left by the lower 6 bits in rs2 and place the sign-extended result into rd.
+//
+// > rd ← s64(u64(rs1) « rs2)
+fn exec_slld<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        from_double((a as i64) << (b & 0b111111))
+    })
+}
+
+// Shift Left Logical Immediate: Shift rs1 left by the 7 bit immediate and place the result into rd.
+//
+// > rd ← ux(rs1) « ux(imm)
+fn exec_slli<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u128> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| a << shamt)
+}
+
+// Shift Left Logical Immediate Double: Shift 64-bit rs1 left by the 6 bit immediate and place the sign-extended result into rd.
+//
+// > rd ← s64(u64(rs1) « ux(imm))
+fn exec_sllid<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u128> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| {
+        from_double((a as i64) << shamt)
+    })
+}
+
+// Shift Left Logical Immediate Word: Shift 32-bit rs1 left by the 5 bit immediate and place the sign-extended result into rd.
+//
+// > rd ← s32(u32(rs1) « ux(imm))
+fn exec_slliw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u128> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| {
+        u128::from_unsigned_word(a.to_unsigned_word() << shamt)
+    })
+}
+
+// Shift Left Logical Word: Shift 32-bit rs1 left by the lower 5 bits in rs2 and place the sign-extended result into rd.
+//
+// > rd ← s32(u32(rs1) « rs2)
+fn exec_sllw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u128::from_unsigned_word(a.to_unsigned_word() << (b & 0b11111))
+    })
+}
+
+// Set Less Than: Set rd to 1 if rs1 is less than rs2, otherwise set rd to 0 (signed).
+//
+// > rd ← sx(rs1) < sx(rs2)
+fn exec_slt<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        (a.to_signed() < b.to_signed()) as u128
+    })
+}
+
+// Set Less Than Immediate: Set rd to 1 if rs1 is less than the sign-extended 12-bit immediate, otherwise set rd to 0 (signed).
+//
+// > rd ← sx(rs1) < sx(imm)
+fn exec_slti<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| {
+        (a.to_signed() < b as i128) as u128
+    })
+}
+
+// Set Less Than Immediate Unsigned: Set rd to 1 if rs1 is less than the sign-extended 12-bit immediate, otherwise set rd to 0 (unsigned).
+//
+// > rd ← ux(rs1) < ux(imm)
+fn exec_sltiu<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| {
+        (a < u128::from_signed_word(b)) as u128
+    })
+}
+
+// Set Less Than Unsigned: Set rd to 1 if rs1 is less than rs2, otherwise set rd to 0 (unsigned).
+//
+// > rd ← ux(rs1) < ux(rs2)
+fn exec_sltu<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| (a < b) as u128)
+}
+
+// Store Quad: Store 128-bit value from rs2 to addr in rs1 plus the 12-bit signed immediate.
+//
+// > u128[rs1 + imm] ← rs2
+fn exec_sq<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_store_mem(hart, inst, rs1, rs2, simm, |mem, addr, v| {
+        mem.write_quadword(addr, v)
+    })
+}
+
+// Shift Right Arithmetic: Shift rs1 right by the by the lower 7 bits in rs2 and place the result into rd while retaining the sign.
+//
+// > rd ← sx(rs1) » rs2
+fn exec_sra<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u128::from_signed(a.to_signed() >> (b & 0b1111111))
+    })
+}
+
+// Shift Right Arithmetic Double: Shift 64-bit rs1 right by the lower 6 bits in rs2 and place the sign-extended result into rd.
+//
+// > rd ← s64(rs1) » rs2
+fn exec_srad<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        from_double((a as i64) >> (b & 0b111111))
+    })
+}
+
+// Shift Right Arithmetic Immediate: Shift rs1 right by the 7 bit immediate and place the result into rd while retaining the sign.
+//
+// > rd ← sx(rs1) » ux(imm)
+fn exec_srai<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u128> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| {
+        u128::from_signed(a.to_signed() >> shamt)
+    })
+}
+
+// Shift Right Arithmetic Immediate Double: Shift 64-bit rs1 right by the 6 bit immediate and place the sign-extended result into rd.
+//
+// > rd ← s64(rs1) » ux(imm)
+fn exec_sraid<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u128> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| {
+        from_double((a as i64) >> shamt)
+    })
+}
+
+// Shift Right Arithmetic Immediate Word: Shift 32-bit rs1 right by the 5 bit immediate and place the sign-extended result into rd.
+//
+// > rd ← s32(rs1) » ux(imm)
+fn exec_sraiw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u128> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| {
+        u128::from_signed_word(a.to_signed_word() >> shamt)
+    })
+}
+
+// Shift Right Arithmetic Word: Shift 32-bit rs1 right by the lower 5 bits in rs2 and place the sign-extended result into rd.
+//
+// > rd ← s32(rs1) » rs2
+fn exec_sraw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u128::from_signed_word(a.to_signed_word() >> (b & 0b11111))
+    })
+}
+
+// Shift Right Logical: Shift rs1 right by the by the lower 7 bits in rs2 and place the result into rd.
+//
+// > rd ← ux(rs1) » rs2
+fn exec_srl<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a >> (b & 0b1111111))
+}
+
+// Shift Right Logical Double: Shift 64-bit rs1 right by the lower 6 bits in rs2 and place the sign-extended result into rd.
+//
+// > rd ← s64(u64(rs1) » rs2)
+fn exec_srld<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        from_double(((a as u64) >> (b & 0b111111)) as i64)
+    })
+}
+
+// Shift Right Logical Immediate: Shift rs1 right by the 7 bit immediate and place the result into rd.
+//
+// > rd ← ux(rs1) » ux(imm)
+fn exec_srli<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u128> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| a >> shamt)
+}
+
+// Shift Right Logical Immediate Double: Shift 64-bit rs1 right by the 6 bit immediate and place the sign-extended result into rd.
+//
+// > rd ← s64(u64(rs1) » ux(imm))
+fn exec_srlid<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u128> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| {
+        from_double(((a as u64) >> shamt) as i64)
+    })
+}
+
+// Shift Right Logical Immediate Word: Shift 32-bit rs1 right by the 5 bit immediate and place the sign-extended result into rd.
+//
+// > rd ← s32(u32(rs1) » ux(imm))
+fn exec_srliw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u128> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| {
+        u128::from_unsigned_word(a.to_unsigned_word() >> shamt)
+    })
+}
+
+// Shift Right Logical Word: Shift 32-bit rs1 right by the lower 5 bits in rs2 and place the sign-extended result into rd.
+//
+// > rd ← s32(u32(rs1) » rs2)
+fn exec_srlw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u128::from_unsigned_word(a.to_unsigned_word() >> (b & 0b11111))
+    })
+}
+
+// Subtract: Subtract rs2 from rs1 and place the result into rd.
+//
+// > rd ← sx(rs1) - sx(rs2)
+fn exec_sub<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a.wrapping_sub(b))
+}
+
+// Subtract Double: Subtract 64-bit rs2 from 64-bit rs1 and place the sign-extended result into rd.
+//
+// > rd ← s64(rs1) - s64(rs2)
+fn exec_subd<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        from_double((a as i64).wrapping_sub(b as i64))
+    })
+}
+
+// Subtract Word: Subtract 32-bit rs2 from 32-bit rs1 and place the sign-extended result into rd.
+//
+// > rd ← s32(rs1) - s32(rs2)
+fn exec_subw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u128::from_signed_word(a.to_signed_word().wrapping_sub(b.to_signed_word()))
+    })
+}
+
+// Store Word: Store 32-bit value from the low bits of rs2 to addr in rs1 plus the 12-bit signed immediate.
+//
+// > u32[rs1 + imm] ← rs2
+fn exec_sw<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_store_mem(hart, inst, rs1, rs2, simm, |mem, addr, v| {
+        mem.write_word(addr, v.to_unsigned_word())
+    })
+}
+
+// User Return: .
+//
+// >
+fn exec_uret<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    inst: Instruction<Op, u128>,
+) -> ExecStatus<u128> {
+    match hart.read_csr(ControlStatusRegister::numbered(0x041)) {
+        Ok(uepc) => {
+            hart.write_pc(uepc);
+            ExecStatus::Running
+        }
+        Err(_) => raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0),
+    }
+}
+
+// Wait For Interrupt: Wait for Interrupt indicates the hart can be stalled until an interrupt needs servicing.
+//
+// >
+fn exec_wfi<Mem: Bus<u128>>(
+    _hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+) -> ExecStatus<u128> {
+    ExecStatus::WaitingForInterrupt
+}
+
+// Xor: Set rd to the bitwise xor of rs1 and rs2.
+//
+// > rd ← ux(rs1) ⊻ ux(rs2)
+fn exec_xor<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u128> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a ^ b)
+}
+
+// Xor Immediate: Set rd to the bitwise xor of rs1 with the sign-extended 12-bit immediate.
+//
+// > rd ← ux(rs1) ⊻ ux(imm)
+fn exec_xori<Mem: Bus<u128>>(
+    hart: &mut impl Hart<u128, u128, f64, Mem>,
+    _inst: Instruction<Op, u128>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u128> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| a ^ u128::from_signed_word(b))
+}
+
+// Sign-extends the result of a doubleword operation to the full register.
+fn from_double(v: i64) -> u128 {
+    u128::from_signed(v as i128)
+}
+
+// Returns the high half of the 256-bit product of two unsigned values, by
+// multiplying their 64-bit halves as in long multiplication.
+fn mul_high_unsigned(a: u128, b: u128) -> u128 {
+    const LOW: u128 = u64::MAX as u128;
+    let (a_lo, a_hi) = (a & LOW, a >> 64);
+    let (b_lo, b_hi) = (b & LOW, b >> 64);
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+    let middle = (lo_lo >> 64) + (hi_lo & LOW) + (lo_hi & LOW);
+    hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (middle >> 64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mul_high_unsigned, step_rv128};
+    use crate::exception::ExceptionCause;
+    use crate::exec::ExecStatus;
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::RV128;
+    use crate::memory::{AddressConverter, Bus, Memory};
+    use crate::register::IntRegister;
+
+    #[test]
+    fn mul_high() {
+        assert_eq!(mul_high_unsigned(u128::MAX, u128::MAX), u128::MAX - 1);
+        assert_eq!(mul_high_unsigned(1 << 127, 4), 2);
+        assert_eq!(mul_high_unsigned(u64::MAX as u128, u64::MAX as u128), 0);
+    }
+
+    #[test]
+    fn quadword_program() {
+        let mut buf = [0u8; 128];
+        let mut hart: SingleThreadUserHart<RV128, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        hart.set_report_exceptions(true);
+        let program = [
+            0xfff00093, // addi x1, x0, -1
+            0x0400d113, // srli x2, x1, 64
+            0x04000193, // addi x3, x0, 64
+            0x0011c023, // sq x1, 0(x3)
+            0x0001f203, // ldu x4, 0(x3)
+            0x0001b283, // ld x5, 0(x3)
+            0x0001a30f, // lq x6, 0(x3)
+            0x001103db, // addid x7, x2, 1
+            0x03f1145b, // sllid x8, x2, 63
+            0x0210b4b3, // mulhu x9, x1, x1
+            0x00000073, // ecall
+            0x00000001, // c.nop, which RV128 doesn't decode
+        ];
+        hart.with_memory(|mem| {
+            for (i, word) in program.iter().enumerate() {
+                mem.write_word(i as u128 * 4, *word).unwrap();
+            }
+        });
+
+        for _ in 0..10 {
+            assert!(matches!(step_rv128(&mut hart), ExecStatus::Running));
+        }
+        assert!(matches!(
+            step_rv128(&mut hart),
+            ExecStatus::EnvironmentCall(40)
+        ));
+        let reg = |n| hart.read_int_register(IntRegister::numbered(n));
+        assert_eq!(reg(1), u128::MAX);
+        assert_eq!(reg(2), u64::MAX as u128);
+        assert_eq!(reg(4), u64::MAX as u128);
+        assert_eq!(reg(5), u128::MAX);
+        assert_eq!(reg(6), u128::MAX);
+        assert_eq!(reg(7), 0);
+        assert_eq!(reg(8), !0 << 63);
+        assert_eq!(reg(9), u128::MAX - 1);
+
+        assert!(matches!(
+            step_rv128(&mut hart),
+            ExecStatus::Exception {
+                cause: ExceptionCause::IllegalInstruction,
+                pc: 44,
+                tval: 0,
+            }
+        ));
+    }
+
+    #[test]
+    fn faults_above_64_bits() {
+        let mut buf = [0u8; 64];
+        let mut hart: SingleThreadUserHart<RV128, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        hart.set_report_exceptions(true);
+        let program = [
+            0x00100193, // addi x3, x0, 1
+            0x04019193, // slli x3, x3, 64
+            0x0081b203, // ld x4, 8(x3)
+            0x0041b823, // sd x4, 16(x3)
+            0x00018067, // jalr x0, 0(x3)
+        ];
+        hart.with_memory(|mem| {
+            for (i, word) in program.iter().enumerate() {
+                mem.write_word(i as u128 * 4, *word).unwrap();
+            }
+        });
+
+        // None of these addresses fit in the memory's address type, so they
+        // all fault, and the full 128-bit address is reported.
+        let base = 1u128 << 64;
+        for _ in 0..2 {
+            assert!(matches!(step_rv128(&mut hart), ExecStatus::Running));
+        }
+        assert!(matches!(
+            step_rv128(&mut hart),
+            ExecStatus::Exception {
+                cause: ExceptionCause::LoadPageFault,
+                pc,
+                tval,
+            } if pc == 8 && tval == base + 8
+        ));
+        hart.write_pc(12);
+        assert!(matches!(
+            step_rv128(&mut hart),
+            ExecStatus::Exception {
+                cause: ExceptionCause::StorePageFault,
+                pc,
+                tval,
+            } if pc == 12 && tval == base + 16
+        ));
+        hart.write_pc(16);
+        assert!(matches!(step_rv128(&mut hart), ExecStatus::Running));
+        assert!(matches!(
+            step_rv128(&mut hart),
+            ExecStatus::Exception {
+                cause: ExceptionCause::InstructionPageFault,
+                pc,
+                tval,
+            } if pc == base && tval == base
+        ));
+    }
+}
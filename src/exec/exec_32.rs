@@ -5,10 +5,16 @@ use crate::exec::ExecStatus;
 use crate::hart::Hart;
 use crate::instruction::Instruction;
 use crate::instruction::OperationRV32;
-use crate::memory::{Bus, MemoryError};
-use crate::raw_instruction::RawInstruction;
+use crate::memory::{AtomicOp, Bus, FenceSet};
 use crate::register::{ControlStatusRegister, FloatRegister, IntRegister};
 
+use super::common::{begin_execute, fetch, jump, raise};
+use super::common::{exec_binary_op, exec_binary_op_imm, exec_shift_op_imm, exec_unary_op};
+use super::common::{exec_branch_binary_cond, exec_load_mem, exec_store_mem};
+use super::common::{exec_csrrc, exec_csrrci, exec_csrrs, exec_csrrsi, exec_csrrw, exec_csrrwi};
+use super::common::{exec_ebreak, exec_ecall, exec_fence, exec_fence_i};
+use super::vector_32;
+
 type Op = OperationRV32;
 
 /// Performs a single execution step against the given RV32 hart.
@@ -21,35 +27,22 @@ type Op = OperationRV32;
 /// to reflect the side-effects of the action.
 pub fn step_rv32<Mem: Bus<u32>>(hart: &mut impl Hart<u32, u32, f64, Mem>) -> ExecStatus<u32> {
     let pc = hart.read_pc();
-    let raw_inst_result: Result<RawInstruction, MemoryError> =
-        hart.with_memory(|mem| match mem.read_word(pc) {
-            Err(e) => Err(e),
-            Ok(data) => Ok(RawInstruction::new(data)),
-        });
-
-    match raw_inst_result {
-        Ok(raw_inst) => {
-            let inst = Instruction::<Op, u32>::decode_raw(raw_inst, pc);
-
-            // We pre-increment the program counter to the default successor
-            // instruction here because we've already captured the current
-            // instruction's PC as part of inst above. Depending on which
-            // instruction we've detected, dispatch_instruction below might
-            // change the program counter again before it returns, overriding
-            // this default.
-            hart.write_pc(pc.wrapping_add(inst.length as u32));
-            return dispatch_instruction(inst, hart);
-        }
-        Err(e) => {
-            let cause: ExceptionCause = match e {
-                MemoryError::Misaligned => ExceptionCause::InstructionAddressMisaligned,
-                MemoryError::AccessFault => ExceptionCause::InstructionPageFault,
-                MemoryError::PageFault => ExceptionCause::InstructionPageFault,
-            };
-            hart.exception(cause);
-            return ExecStatus::Running;
-        }
+    match fetch(hart, pc) {
+        Ok(raw_inst) => execute_rv32(hart, Instruction::decode_raw(raw_inst, pc)),
+        Err(e) => raise(hart, pc, e.cause(), e.addr as u32),
+    }
+}
+
+// Executes an instruction that was decoded from the hart's current
+// program counter.
+pub(crate) fn execute_rv32<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+) -> ExecStatus<u32> {
+    if let Err(status) = begin_execute(hart, &inst) {
+        return status;
     }
+    return dispatch_instruction(inst, hart);
 }
 
 // The main instruction dispatch logic for RV32: selects a suitable
@@ -126,13 +119,22 @@ fn dispatch_instruction<Mem: Bus<u32>>(
         } => exec_amoxor_w(hart, inst, rd, rs1, rs2, aq, rl),
         Op::And { rd, rs1, rs2 } => exec_and(hart, inst, rd, rs1, rs2),
         Op::Andi { rd, rs1, simm } => exec_andi(hart, inst, rd, rs1, simm),
+        Op::Andn { rd, rs1, rs2 } => exec_andn(hart, inst, rd, rs1, rs2),
         Op::Auipc { rd, simm } => exec_auipc(hart, inst, rd, simm),
+        Op::Bclr { rd, rs1, rs2 } => exec_bclr(hart, inst, rd, rs1, rs2),
+        Op::Bclri { rd, rs1, shamt } => exec_bclri(hart, inst, rd, rs1, shamt),
         Op::Beq { rs1, rs2, simm } => exec_beq(hart, inst, rs1, rs2, simm),
+        Op::Bext { rd, rs1, rs2 } => exec_bext(hart, inst, rd, rs1, rs2),
+        Op::Bexti { rd, rs1, shamt } => exec_bexti(hart, inst, rd, rs1, shamt),
         Op::Bge { rs1, rs2, simm } => exec_bge(hart, inst, rs1, rs2, simm),
         Op::Bgeu { rs1, rs2, simm } => exec_bgeu(hart, inst, rs1, rs2, simm),
+        Op::Binv { rd, rs1, rs2 } => exec_binv(hart, inst, rd, rs1, rs2),
+        Op::Binvi { rd, rs1, shamt } => exec_binvi(hart, inst, rd, rs1, shamt),
         Op::Blt { rs1, rs2, simm } => exec_blt(hart, inst, rs1, rs2, simm),
         Op::Bltu { rs1, rs2, simm } => exec_bltu(hart, inst, rs1, rs2, simm),
         Op::Bne { rs1, rs2, simm } => exec_bne(hart, inst, rs1, rs2, simm),
+        Op::Bset { rd, rs1, rs2 } => exec_bset(hart, inst, rd, rs1, rs2),
+        Op::Bseti { rd, rs1, shamt } => exec_bseti(hart, inst, rd, rs1, shamt),
         Op::CAdd { rs1rd, rs2 } => exec_c_add(hart, inst, rs1rd, rs2),
         Op::CAddi { rs1rd, nzsimm } => exec_c_addi(hart, inst, rs1rd, nzsimm),
         Op::CAddi16Sp { rs1rd, nzsimm } => exec_c_addi16sp(hart, inst, rs1rd, nzsimm),
@@ -170,12 +172,18 @@ fn dispatch_instruction<Mem: Bus<u32>>(
         Op::CSw { rs1, rs2, uimm } => exec_c_sw(hart, inst, rs1, rs2, uimm),
         Op::CSwsp { rs2, uimm } => exec_c_swsp(hart, inst, rs2, uimm),
         Op::CXor { rs1rd, rs2 } => exec_c_xor(hart, inst, rs1rd, rs2),
+        Op::Clmul { rd, rs1, rs2 } => exec_clmul(hart, inst, rd, rs1, rs2),
+        Op::Clmulh { rd, rs1, rs2 } => exec_clmulh(hart, inst, rd, rs1, rs2),
+        Op::Clmulr { rd, rs1, rs2 } => exec_clmulr(hart, inst, rd, rs1, rs2),
+        Op::Clz { rd, rs1 } => exec_clz(hart, inst, rd, rs1),
+        Op::Cpop { rd, rs1 } => exec_cpop(hart, inst, rd, rs1),
         Op::Csrrc { rd, rs1, csr } => exec_csrrc(hart, inst, rd, rs1, csr),
         Op::Csrrci { rd, uimm, csr } => exec_csrrci(hart, inst, rd, uimm, csr),
         Op::Csrrs { rd, rs1, csr } => exec_csrrs(hart, inst, rd, rs1, csr),
         Op::Csrrsi { rd, uimm, csr } => exec_csrrsi(hart, inst, rd, uimm, csr),
         Op::Csrrw { rd, rs1, csr } => exec_csrrw(hart, inst, rd, rs1, csr),
         Op::Csrrwi { rd, uimm, csr } => exec_csrrwi(hart, inst, rd, uimm, csr),
+        Op::Ctz { rd, rs1 } => exec_ctz(hart, inst, rd, rs1),
         Op::Div { rd, rs1, rs2 } => exec_div(hart, inst, rd, rs1, rs2),
         Op::Divu { rd, rs1, rs2 } => exec_divu(hart, inst, rd, rs1, rs2),
         Op::Dret => exec_dret(hart, inst),
@@ -405,15 +413,25 @@ fn dispatch_instruction<Mem: Bus<u32>>(
         Op::LrW { rd, rs1, aq, rl } => exec_lr_w(hart, inst, rd, rs1, aq, rl),
         Op::Lui { rd, simm } => exec_lui(hart, inst, rd, simm),
         Op::Lw { rd, rs1, simm } => exec_lw(hart, inst, rd, rs1, simm),
+        Op::Max { rd, rs1, rs2 } => exec_max(hart, inst, rd, rs1, rs2),
+        Op::Maxu { rd, rs1, rs2 } => exec_maxu(hart, inst, rd, rs1, rs2),
+        Op::Min { rd, rs1, rs2 } => exec_min(hart, inst, rd, rs1, rs2),
+        Op::Minu { rd, rs1, rs2 } => exec_minu(hart, inst, rd, rs1, rs2),
         Op::Mret => exec_mret(hart, inst),
         Op::Mul { rd, rs1, rs2 } => exec_mul(hart, inst, rd, rs1, rs2),
         Op::Mulh { rd, rs1, rs2 } => exec_mulh(hart, inst, rd, rs1, rs2),
         Op::Mulhsu { rd, rs1, rs2 } => exec_mulhsu(hart, inst, rd, rs1, rs2),
         Op::Mulhu { rd, rs1, rs2 } => exec_mulhu(hart, inst, rd, rs1, rs2),
         Op::Or { rd, rs1, rs2 } => exec_or(hart, inst, rd, rs1, rs2),
+        Op::OrcB { rd, rs1 } => exec_orc_b(hart, inst, rd, rs1),
         Op::Ori { rd, rs1, simm } => exec_ori(hart, inst, rd, rs1, simm),
+        Op::Orn { rd, rs1, rs2 } => exec_orn(hart, inst, rd, rs1, rs2),
         Op::Rem { rd, rs1, rs2 } => exec_rem(hart, inst, rd, rs1, rs2),
         Op::Remu { rd, rs1, rs2 } => exec_remu(hart, inst, rd, rs1, rs2),
+        Op::Rev8 { rd, rs1 } => exec_rev8(hart, inst, rd, rs1),
+        Op::Rol { rd, rs1, rs2 } => exec_rol(hart, inst, rd, rs1, rs2),
+        Op::Ror { rd, rs1, rs2 } => exec_ror(hart, inst, rd, rs1, rs2),
+        Op::Rori { rd, rs1, shamt } => exec_rori(hart, inst, rd, rs1, shamt),
         Op::Sb { rs1, rs2, simm } => exec_sb(hart, inst, rs1, rs2, simm),
         Op::ScW {
             rd,
@@ -422,9 +440,14 @@ fn dispatch_instruction<Mem: Bus<u32>>(
             aq,
             rl,
         } => exec_sc_w(hart, inst, rd, rs1, rs2, aq, rl),
+        Op::SextB { rd, rs1 } => exec_sext_b(hart, inst, rd, rs1),
+        Op::SextH { rd, rs1 } => exec_sext_h(hart, inst, rd, rs1),
         Op::SfenceVm { rs1 } => exec_sfence_vm(hart, inst, rs1),
         Op::SfenceVma { rs1, rs2 } => exec_sfence_vma(hart, inst, rs1, rs2),
         Op::Sh { rs1, rs2, simm } => exec_sh(hart, inst, rs1, rs2, simm),
+        Op::Sh1add { rd, rs1, rs2 } => exec_sh1add(hart, inst, rd, rs1, rs2),
+        Op::Sh2add { rd, rs1, rs2 } => exec_sh2add(hart, inst, rd, rs1, rs2),
+        Op::Sh3add { rd, rs1, rs2 } => exec_sh3add(hart, inst, rd, rs1, rs2),
         Op::Sll { rd, rs1, rs2 } => exec_sll(hart, inst, rd, rs1, rs2),
         Op::Slli { rd, rs1, shamt } => exec_slli(hart, inst, rd, rs1, shamt),
         Op::Slt { rd, rs1, rs2 } => exec_slt(hart, inst, rd, rs1, rs2),
@@ -439,13 +462,345 @@ fn dispatch_instruction<Mem: Bus<u32>>(
         Op::Sub { rd, rs1, rs2 } => exec_sub(hart, inst, rd, rs1, rs2),
         Op::Sw { rs1, rs2, simm } => exec_sw(hart, inst, rs1, rs2, simm),
         Op::Uret => exec_uret(hart, inst),
+        Op::VaddVi { vd, vs2, simm, vm } => vector_32::exec_vadd_vi(hart, inst, vd, vs2, simm, vm),
+        Op::VaddVv { vd, vs2, vs1, vm } => vector_32::exec_vadd_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VaddVx { vd, vs2, rs1, vm } => vector_32::exec_vadd_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VandVi { vd, vs2, simm, vm } => vector_32::exec_vand_vi(hart, inst, vd, vs2, simm, vm),
+        Op::VandVv { vd, vs2, vs1, vm } => vector_32::exec_vand_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VandVx { vd, vs2, rs1, vm } => vector_32::exec_vand_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VcompressVm { vd, vs2, vs1 } => vector_32::exec_vcompress_vm(hart, inst, vd, vs2, vs1),
+        Op::VcpopM { rd, vs2, vm } => vector_32::exec_vcpop_m(hart, inst, rd, vs2, vm),
+        Op::VdivVv { vd, vs2, vs1, vm } => vector_32::exec_vdiv_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VdivVx { vd, vs2, rs1, vm } => vector_32::exec_vdiv_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VdivuVv { vd, vs2, vs1, vm } => vector_32::exec_vdivu_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VdivuVx { vd, vs2, rs1, vm } => vector_32::exec_vdivu_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VfaddVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vfadd_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VfaddVv { vd, vs2, vs1, vm } => vector_32::exec_vfadd_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VfdivVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vfdiv_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VfdivVv { vd, vs2, vs1, vm } => vector_32::exec_vfdiv_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VfirstM { rd, vs2, vm } => vector_32::exec_vfirst_m(hart, inst, rd, vs2, vm),
+        Op::VfmaxVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vfmax_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VfmaxVv { vd, vs2, vs1, vm } => vector_32::exec_vfmax_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VfmergeVfm { vd, vs2, frs1 } => vector_32::exec_vfmerge_vfm(hart, inst, vd, vs2, frs1),
+        Op::VfminVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vfmin_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VfminVv { vd, vs2, vs1, vm } => vector_32::exec_vfmin_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VfmulVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vfmul_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VfmulVv { vd, vs2, vs1, vm } => vector_32::exec_vfmul_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VfmvFS { frd, vs2 } => vector_32::exec_vfmv_f_s(hart, inst, frd, vs2),
+        Op::VfmvSF { vd, frs1 } => vector_32::exec_vfmv_s_f(hart, inst, vd, frs1),
+        Op::VfmvVF { vd, frs1 } => vector_32::exec_vfmv_v_f(hart, inst, vd, frs1),
+        Op::VfrdivVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vfrdiv_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VfredmaxVs { vd, vs2, vs1, vm } => {
+            vector_32::exec_vfredmax_vs(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VfredminVs { vd, vs2, vs1, vm } => {
+            vector_32::exec_vfredmin_vs(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VfredosumVs { vd, vs2, vs1, vm } => {
+            vector_32::exec_vfredosum_vs(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VfredusumVs { vd, vs2, vs1, vm } => {
+            vector_32::exec_vfredusum_vs(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VfrsubVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vfrsub_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VfsgnjVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vfsgnj_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VfsgnjVv { vd, vs2, vs1, vm } => {
+            vector_32::exec_vfsgnj_vv(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VfsgnjnVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vfsgnjn_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VfsgnjnVv { vd, vs2, vs1, vm } => {
+            vector_32::exec_vfsgnjn_vv(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VfsgnjxVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vfsgnjx_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VfsgnjxVv { vd, vs2, vs1, vm } => {
+            vector_32::exec_vfsgnjx_vv(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VfsubVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vfsub_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VfsubVv { vd, vs2, vs1, vm } => vector_32::exec_vfsub_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VidV { vd, vm } => vector_32::exec_vid_v(hart, inst, vd, vm),
+        Op::Vle16V { vd, rs1, vm } => vector_32::exec_vle16_v(hart, inst, vd, rs1, vm),
+        Op::Vle32V { vd, rs1, vm } => vector_32::exec_vle32_v(hart, inst, vd, rs1, vm),
+        Op::Vle64V { vd, rs1, vm } => vector_32::exec_vle64_v(hart, inst, vd, rs1, vm),
+        Op::Vle8V { vd, rs1, vm } => vector_32::exec_vle8_v(hart, inst, vd, rs1, vm),
+        Op::Vloxei16V { vd, rs1, vs2, vm } => {
+            vector_32::exec_vloxei16_v(hart, inst, vd, rs1, vs2, vm)
+        }
+        Op::Vloxei32V { vd, rs1, vs2, vm } => {
+            vector_32::exec_vloxei32_v(hart, inst, vd, rs1, vs2, vm)
+        }
+        Op::Vloxei64V { vd, rs1, vs2, vm } => {
+            vector_32::exec_vloxei64_v(hart, inst, vd, rs1, vs2, vm)
+        }
+        Op::Vloxei8V { vd, rs1, vs2, vm } => {
+            vector_32::exec_vloxei8_v(hart, inst, vd, rs1, vs2, vm)
+        }
+        Op::Vlse16V { vd, rs1, rs2, vm } => vector_32::exec_vlse16_v(hart, inst, vd, rs1, rs2, vm),
+        Op::Vlse32V { vd, rs1, rs2, vm } => vector_32::exec_vlse32_v(hart, inst, vd, rs1, rs2, vm),
+        Op::Vlse64V { vd, rs1, rs2, vm } => vector_32::exec_vlse64_v(hart, inst, vd, rs1, rs2, vm),
+        Op::Vlse8V { vd, rs1, rs2, vm } => vector_32::exec_vlse8_v(hart, inst, vd, rs1, rs2, vm),
+        Op::Vluxei16V { vd, rs1, vs2, vm } => {
+            vector_32::exec_vluxei16_v(hart, inst, vd, rs1, vs2, vm)
+        }
+        Op::Vluxei32V { vd, rs1, vs2, vm } => {
+            vector_32::exec_vluxei32_v(hart, inst, vd, rs1, vs2, vm)
+        }
+        Op::Vluxei64V { vd, rs1, vs2, vm } => {
+            vector_32::exec_vluxei64_v(hart, inst, vd, rs1, vs2, vm)
+        }
+        Op::Vluxei8V { vd, rs1, vs2, vm } => {
+            vector_32::exec_vluxei8_v(hart, inst, vd, rs1, vs2, vm)
+        }
+        Op::VmaccVv { vd, vs2, vs1, vm } => vector_32::exec_vmacc_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VmaccVx { vd, vs2, rs1, vm } => vector_32::exec_vmacc_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VmandMm { vd, vs2, vs1 } => vector_32::exec_vmand_mm(hart, inst, vd, vs2, vs1),
+        Op::VmandnMm { vd, vs2, vs1 } => vector_32::exec_vmandn_mm(hart, inst, vd, vs2, vs1),
+        Op::VmaxVv { vd, vs2, vs1, vm } => vector_32::exec_vmax_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VmaxVx { vd, vs2, rs1, vm } => vector_32::exec_vmax_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VmaxuVv { vd, vs2, vs1, vm } => vector_32::exec_vmaxu_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VmaxuVx { vd, vs2, rs1, vm } => vector_32::exec_vmaxu_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VmergeVim { vd, vs2, simm } => vector_32::exec_vmerge_vim(hart, inst, vd, vs2, simm),
+        Op::VmergeVvm { vd, vs2, vs1 } => vector_32::exec_vmerge_vvm(hart, inst, vd, vs2, vs1),
+        Op::VmergeVxm { vd, vs2, rs1 } => vector_32::exec_vmerge_vxm(hart, inst, vd, vs2, rs1),
+        Op::VmfeqVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vmfeq_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VmfeqVv { vd, vs2, vs1, vm } => vector_32::exec_vmfeq_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VmfgeVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vmfge_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VmfgtVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vmfgt_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VmfleVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vmfle_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VmfleVv { vd, vs2, vs1, vm } => vector_32::exec_vmfle_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VmfltVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vmflt_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VmfltVv { vd, vs2, vs1, vm } => vector_32::exec_vmflt_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VmfneVf { vd, vs2, frs1, vm } => {
+            vector_32::exec_vmfne_vf(hart, inst, vd, vs2, frs1, vm)
+        }
+        Op::VmfneVv { vd, vs2, vs1, vm } => vector_32::exec_vmfne_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VminVv { vd, vs2, vs1, vm } => vector_32::exec_vmin_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VminVx { vd, vs2, rs1, vm } => vector_32::exec_vmin_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VminuVv { vd, vs2, vs1, vm } => vector_32::exec_vminu_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VminuVx { vd, vs2, rs1, vm } => vector_32::exec_vminu_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VmnandMm { vd, vs2, vs1 } => vector_32::exec_vmnand_mm(hart, inst, vd, vs2, vs1),
+        Op::VmnorMm { vd, vs2, vs1 } => vector_32::exec_vmnor_mm(hart, inst, vd, vs2, vs1),
+        Op::VmorMm { vd, vs2, vs1 } => vector_32::exec_vmor_mm(hart, inst, vd, vs2, vs1),
+        Op::VmornMm { vd, vs2, vs1 } => vector_32::exec_vmorn_mm(hart, inst, vd, vs2, vs1),
+        Op::VmseqVi { vd, vs2, simm, vm } => {
+            vector_32::exec_vmseq_vi(hart, inst, vd, vs2, simm, vm)
+        }
+        Op::VmseqVv { vd, vs2, vs1, vm } => vector_32::exec_vmseq_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VmseqVx { vd, vs2, rs1, vm } => vector_32::exec_vmseq_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VmsgtVi { vd, vs2, simm, vm } => {
+            vector_32::exec_vmsgt_vi(hart, inst, vd, vs2, simm, vm)
+        }
+        Op::VmsgtVx { vd, vs2, rs1, vm } => vector_32::exec_vmsgt_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VmsgtuVi { vd, vs2, simm, vm } => {
+            vector_32::exec_vmsgtu_vi(hart, inst, vd, vs2, simm, vm)
+        }
+        Op::VmsgtuVx { vd, vs2, rs1, vm } => {
+            vector_32::exec_vmsgtu_vx(hart, inst, vd, vs2, rs1, vm)
+        }
+        Op::VmsleVi { vd, vs2, simm, vm } => {
+            vector_32::exec_vmsle_vi(hart, inst, vd, vs2, simm, vm)
+        }
+        Op::VmsleVv { vd, vs2, vs1, vm } => vector_32::exec_vmsle_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VmsleVx { vd, vs2, rs1, vm } => vector_32::exec_vmsle_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VmsleuVi { vd, vs2, simm, vm } => {
+            vector_32::exec_vmsleu_vi(hart, inst, vd, vs2, simm, vm)
+        }
+        Op::VmsleuVv { vd, vs2, vs1, vm } => {
+            vector_32::exec_vmsleu_vv(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VmsleuVx { vd, vs2, rs1, vm } => {
+            vector_32::exec_vmsleu_vx(hart, inst, vd, vs2, rs1, vm)
+        }
+        Op::VmsltVv { vd, vs2, vs1, vm } => vector_32::exec_vmslt_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VmsltVx { vd, vs2, rs1, vm } => vector_32::exec_vmslt_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VmsltuVv { vd, vs2, vs1, vm } => {
+            vector_32::exec_vmsltu_vv(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VmsltuVx { vd, vs2, rs1, vm } => {
+            vector_32::exec_vmsltu_vx(hart, inst, vd, vs2, rs1, vm)
+        }
+        Op::VmsneVi { vd, vs2, simm, vm } => {
+            vector_32::exec_vmsne_vi(hart, inst, vd, vs2, simm, vm)
+        }
+        Op::VmsneVv { vd, vs2, vs1, vm } => vector_32::exec_vmsne_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VmsneVx { vd, vs2, rs1, vm } => vector_32::exec_vmsne_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VmulVv { vd, vs2, vs1, vm } => vector_32::exec_vmul_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VmulVx { vd, vs2, rs1, vm } => vector_32::exec_vmul_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VmulhVv { vd, vs2, vs1, vm } => vector_32::exec_vmulh_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VmulhVx { vd, vs2, rs1, vm } => vector_32::exec_vmulh_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VmulhsuVv { vd, vs2, vs1, vm } => {
+            vector_32::exec_vmulhsu_vv(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VmulhsuVx { vd, vs2, rs1, vm } => {
+            vector_32::exec_vmulhsu_vx(hart, inst, vd, vs2, rs1, vm)
+        }
+        Op::VmulhuVv { vd, vs2, vs1, vm } => {
+            vector_32::exec_vmulhu_vv(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VmulhuVx { vd, vs2, rs1, vm } => {
+            vector_32::exec_vmulhu_vx(hart, inst, vd, vs2, rs1, vm)
+        }
+        Op::VmvSX { vd, rs1 } => vector_32::exec_vmv_s_x(hart, inst, vd, rs1),
+        Op::VmvVI { vd, simm } => vector_32::exec_vmv_v_i(hart, inst, vd, simm),
+        Op::VmvVV { vd, vs1 } => vector_32::exec_vmv_v_v(hart, inst, vd, vs1),
+        Op::VmvVX { vd, rs1 } => vector_32::exec_vmv_v_x(hart, inst, vd, rs1),
+        Op::VmvXS { rd, vs2 } => vector_32::exec_vmv_x_s(hart, inst, rd, vs2),
+        Op::VmxnorMm { vd, vs2, vs1 } => vector_32::exec_vmxnor_mm(hart, inst, vd, vs2, vs1),
+        Op::VmxorMm { vd, vs2, vs1 } => vector_32::exec_vmxor_mm(hart, inst, vd, vs2, vs1),
+        Op::VorVi { vd, vs2, simm, vm } => vector_32::exec_vor_vi(hart, inst, vd, vs2, simm, vm),
+        Op::VorVv { vd, vs2, vs1, vm } => vector_32::exec_vor_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VorVx { vd, vs2, rs1, vm } => vector_32::exec_vor_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VredandVs { vd, vs2, vs1, vm } => {
+            vector_32::exec_vredand_vs(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VredmaxVs { vd, vs2, vs1, vm } => {
+            vector_32::exec_vredmax_vs(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VredmaxuVs { vd, vs2, vs1, vm } => {
+            vector_32::exec_vredmaxu_vs(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VredminVs { vd, vs2, vs1, vm } => {
+            vector_32::exec_vredmin_vs(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VredminuVs { vd, vs2, vs1, vm } => {
+            vector_32::exec_vredminu_vs(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VredorVs { vd, vs2, vs1, vm } => {
+            vector_32::exec_vredor_vs(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VredsumVs { vd, vs2, vs1, vm } => {
+            vector_32::exec_vredsum_vs(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VredxorVs { vd, vs2, vs1, vm } => {
+            vector_32::exec_vredxor_vs(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VremVv { vd, vs2, vs1, vm } => vector_32::exec_vrem_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VremVx { vd, vs2, rs1, vm } => vector_32::exec_vrem_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VremuVv { vd, vs2, vs1, vm } => vector_32::exec_vremu_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VremuVx { vd, vs2, rs1, vm } => vector_32::exec_vremu_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VrgatherVi { vd, vs2, uimm, vm } => {
+            vector_32::exec_vrgather_vi(hart, inst, vd, vs2, uimm, vm)
+        }
+        Op::VrgatherVv { vd, vs2, vs1, vm } => {
+            vector_32::exec_vrgather_vv(hart, inst, vd, vs2, vs1, vm)
+        }
+        Op::VrgatherVx { vd, vs2, rs1, vm } => {
+            vector_32::exec_vrgather_vx(hart, inst, vd, vs2, rs1, vm)
+        }
+        Op::VrsubVi { vd, vs2, simm, vm } => {
+            vector_32::exec_vrsub_vi(hart, inst, vd, vs2, simm, vm)
+        }
+        Op::VrsubVx { vd, vs2, rs1, vm } => vector_32::exec_vrsub_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::Vse16V { vs3, rs1, vm } => vector_32::exec_vse16_v(hart, inst, vs3, rs1, vm),
+        Op::Vse32V { vs3, rs1, vm } => vector_32::exec_vse32_v(hart, inst, vs3, rs1, vm),
+        Op::Vse64V { vs3, rs1, vm } => vector_32::exec_vse64_v(hart, inst, vs3, rs1, vm),
+        Op::Vse8V { vs3, rs1, vm } => vector_32::exec_vse8_v(hart, inst, vs3, rs1, vm),
+        Op::Vsetivli { rd, uimm, vtypei } => vector_32::exec_vsetivli(hart, inst, rd, uimm, vtypei),
+        Op::Vsetvl { rd, rs1, rs2 } => vector_32::exec_vsetvl(hart, inst, rd, rs1, rs2),
+        Op::Vsetvli { rd, rs1, vtypei } => vector_32::exec_vsetvli(hart, inst, rd, rs1, vtypei),
+        Op::Vslide1downVx { vd, vs2, rs1, vm } => {
+            vector_32::exec_vslide1down_vx(hart, inst, vd, vs2, rs1, vm)
+        }
+        Op::Vslide1upVx { vd, vs2, rs1, vm } => {
+            vector_32::exec_vslide1up_vx(hart, inst, vd, vs2, rs1, vm)
+        }
+        Op::VslidedownVi { vd, vs2, uimm, vm } => {
+            vector_32::exec_vslidedown_vi(hart, inst, vd, vs2, uimm, vm)
+        }
+        Op::VslidedownVx { vd, vs2, rs1, vm } => {
+            vector_32::exec_vslidedown_vx(hart, inst, vd, vs2, rs1, vm)
+        }
+        Op::VslideupVi { vd, vs2, uimm, vm } => {
+            vector_32::exec_vslideup_vi(hart, inst, vd, vs2, uimm, vm)
+        }
+        Op::VslideupVx { vd, vs2, rs1, vm } => {
+            vector_32::exec_vslideup_vx(hart, inst, vd, vs2, rs1, vm)
+        }
+        Op::VsllVi { vd, vs2, uimm, vm } => vector_32::exec_vsll_vi(hart, inst, vd, vs2, uimm, vm),
+        Op::VsllVv { vd, vs2, vs1, vm } => vector_32::exec_vsll_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VsllVx { vd, vs2, rs1, vm } => vector_32::exec_vsll_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::Vsoxei16V { vs3, rs1, vs2, vm } => {
+            vector_32::exec_vsoxei16_v(hart, inst, vs3, rs1, vs2, vm)
+        }
+        Op::Vsoxei32V { vs3, rs1, vs2, vm } => {
+            vector_32::exec_vsoxei32_v(hart, inst, vs3, rs1, vs2, vm)
+        }
+        Op::Vsoxei64V { vs3, rs1, vs2, vm } => {
+            vector_32::exec_vsoxei64_v(hart, inst, vs3, rs1, vs2, vm)
+        }
+        Op::Vsoxei8V { vs3, rs1, vs2, vm } => {
+            vector_32::exec_vsoxei8_v(hart, inst, vs3, rs1, vs2, vm)
+        }
+        Op::VsraVi { vd, vs2, uimm, vm } => vector_32::exec_vsra_vi(hart, inst, vd, vs2, uimm, vm),
+        Op::VsraVv { vd, vs2, vs1, vm } => vector_32::exec_vsra_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VsraVx { vd, vs2, rs1, vm } => vector_32::exec_vsra_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::VsrlVi { vd, vs2, uimm, vm } => vector_32::exec_vsrl_vi(hart, inst, vd, vs2, uimm, vm),
+        Op::VsrlVv { vd, vs2, vs1, vm } => vector_32::exec_vsrl_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VsrlVx { vd, vs2, rs1, vm } => vector_32::exec_vsrl_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::Vsse16V { vs3, rs1, rs2, vm } => {
+            vector_32::exec_vsse16_v(hart, inst, vs3, rs1, rs2, vm)
+        }
+        Op::Vsse32V { vs3, rs1, rs2, vm } => {
+            vector_32::exec_vsse32_v(hart, inst, vs3, rs1, rs2, vm)
+        }
+        Op::Vsse64V { vs3, rs1, rs2, vm } => {
+            vector_32::exec_vsse64_v(hart, inst, vs3, rs1, rs2, vm)
+        }
+        Op::Vsse8V { vs3, rs1, rs2, vm } => vector_32::exec_vsse8_v(hart, inst, vs3, rs1, rs2, vm),
+        Op::VsubVv { vd, vs2, vs1, vm } => vector_32::exec_vsub_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VsubVx { vd, vs2, rs1, vm } => vector_32::exec_vsub_vx(hart, inst, vd, vs2, rs1, vm),
+        Op::Vsuxei16V { vs3, rs1, vs2, vm } => {
+            vector_32::exec_vsuxei16_v(hart, inst, vs3, rs1, vs2, vm)
+        }
+        Op::Vsuxei32V { vs3, rs1, vs2, vm } => {
+            vector_32::exec_vsuxei32_v(hart, inst, vs3, rs1, vs2, vm)
+        }
+        Op::Vsuxei64V { vs3, rs1, vs2, vm } => {
+            vector_32::exec_vsuxei64_v(hart, inst, vs3, rs1, vs2, vm)
+        }
+        Op::Vsuxei8V { vs3, rs1, vs2, vm } => {
+            vector_32::exec_vsuxei8_v(hart, inst, vs3, rs1, vs2, vm)
+        }
+        Op::VxorVi { vd, vs2, simm, vm } => vector_32::exec_vxor_vi(hart, inst, vd, vs2, simm, vm),
+        Op::VxorVv { vd, vs2, vs1, vm } => vector_32::exec_vxor_vv(hart, inst, vd, vs2, vs1, vm),
+        Op::VxorVx { vd, vs2, rs1, vm } => vector_32::exec_vxor_vx(hart, inst, vd, vs2, rs1, vm),
         Op::Wfi => exec_wfi(hart, inst),
+        Op::Xnor { rd, rs1, rs2 } => exec_xnor(hart, inst, rd, rs1, rs2),
         Op::Xor { rd, rs1, rs2 } => exec_xor(hart, inst, rd, rs1, rs2),
         Op::Xori { rd, rs1, simm } => exec_xori(hart, inst, rd, rs1, simm),
-        _ => {
-            hart.exception(ExceptionCause::IllegalInstruction);
-            ExecStatus::Running
-        }
+        Op::ZextH { rd, rs1 } => exec_zext_h(hart, inst, rd, rs1),
+        _ => raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0),
     }
 }
 
@@ -484,16 +839,14 @@ fn exec_addi<Mem: Bus<u32>>(
 // > rd ← s32[rs1] ∥ u32[rs1] ← s32(rs2) + s32[rs1]
 fn exec_amoadd_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     rs2: IntRegister,
     aq: bool,
     rl: bool,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_amo(hart, inst, rd, rs1, rs2, aq, rl, AtomicOp::Add)
 }
 
 // Atomic And Word: Load word from address in rs1 into rd, and rd and rs2, write the result to the address in rs1.
@@ -501,16 +854,14 @@ fn exec_amoadd_w<Mem: Bus<u32>>(
 // > rd ← s32[rs1] ∥ u32[rs1] ← s32(rs2) ∧ s32[rs1]
 fn exec_amoand_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     rs2: IntRegister,
     aq: bool,
     rl: bool,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_amo(hart, inst, rd, rs1, rs2, aq, rl, AtomicOp::And)
 }
 
 // Atomic Maximum Word: Load word from address in rs1 into rd, find maximum of rd and rs2, write the result to the address in rs1 (signed).
@@ -518,16 +869,14 @@ fn exec_amoand_w<Mem: Bus<u32>>(
 // > rd ← s32[rs1] ∥ u32[rs1] ← s32_max(s32(rs2), s32[rs1])
 fn exec_amomax_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     rs2: IntRegister,
     aq: bool,
     rl: bool,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_amo(hart, inst, rd, rs1, rs2, aq, rl, AtomicOp::Max)
 }
 
 // Atomic Maximum Unsigned Word: Load word from address in rs1 into rd, find maximum of rd and rs2, write the result to the address in rs1 (unsigned).
@@ -535,16 +884,14 @@ fn exec_amomax_w<Mem: Bus<u32>>(
 // > rd ← s32[rs1] ∥ u32[rs1] ← u32_max(u32(rs2), u32[rs1])
 fn exec_amomaxu_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     rs2: IntRegister,
     aq: bool,
     rl: bool,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_amo(hart, inst, rd, rs1, rs2, aq, rl, AtomicOp::MaxUnsigned)
 }
 
 // Atomic Minimum Word: Load word from address in rs1 into rd, find minimum of rd and rs2, write the result to the address in rs1 (signed).
@@ -552,16 +899,14 @@ fn exec_amomaxu_w<Mem: Bus<u32>>(
 // > rd ← s32[rs1] ∥ u32[rs1] ← s32_min(s32(rs2), s32[rs1])
 fn exec_amomin_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     rs2: IntRegister,
     aq: bool,
     rl: bool,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_amo(hart, inst, rd, rs1, rs2, aq, rl, AtomicOp::Min)
 }
 
 // Atomic Minimum Unsigned Word: Load word from address in rs1 into rd, find minimum of rd and rs2, write the result to the address in rs1 (unsigned).
@@ -569,16 +914,14 @@ fn exec_amomin_w<Mem: Bus<u32>>(
 // > rd ← s32[rs1] ∥ u32[rs1] ← u32_min(u32(rs2), u32[rs1])
 fn exec_amominu_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     rs2: IntRegister,
     aq: bool,
     rl: bool,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_amo(hart, inst, rd, rs1, rs2, aq, rl, AtomicOp::MinUnsigned)
 }
 
 // Atomic Or Word: Load word from address in rs1 into rd, or rd and rs2, write the result to the address in rs1.
@@ -586,16 +929,14 @@ fn exec_amominu_w<Mem: Bus<u32>>(
 // > rd ← s32[rs1] ∥ u32[rs1] ← s32(rs2) ∨ s32[rs1]
 fn exec_amoor_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     rs2: IntRegister,
     aq: bool,
     rl: bool,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_amo(hart, inst, rd, rs1, rs2, aq, rl, AtomicOp::Or)
 }
 
 // Atomic Swap Word: Load word from address in rs1 into rd, swap rd and rs2, write the result to the address in rs1.
@@ -603,16 +944,14 @@ fn exec_amoor_w<Mem: Bus<u32>>(
 // > rd ← s32[rs1] ∥ u32[rs1] ← s32(rs2)
 fn exec_amoswap_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     rs2: IntRegister,
     aq: bool,
     rl: bool,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_amo(hart, inst, rd, rs1, rs2, aq, rl, AtomicOp::Swap)
 }
 
 // Atomic Xor Word: Load word from address in rs1 into rd, xor rd and rs2, write the result to the address in rs1.
@@ -620,16 +959,14 @@ fn exec_amoswap_w<Mem: Bus<u32>>(
 // > rd ← s32[rs1] ∥ u32[rs1] ← s32(rs2) ⊻ s32[rs1]
 fn exec_amoxor_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     rs2: IntRegister,
     aq: bool,
     rl: bool,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_amo(hart, inst, rd, rs1, rs2, aq, rl, AtomicOp::Xor)
 }
 
 // And: Set rd to the bitwise and of rs1 and rs2.
@@ -658,6 +995,19 @@ fn exec_andi<Mem: Bus<u32>>(
     exec_binary_op_imm(hart, rd, rs1, simm, |a, b| a & u32::from_signed(b))
 }
 
+// And Inverted: Set rd to the bitwise and of rs1 and the inverse of rs2.
+//
+// > rd ← ux(rs1) ∧ ¬ux(rs2)
+fn exec_andn<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a & !b)
+}
+
 // Add Upper Immediate to PC: Place the PC plus the 20-bit signed immediate (shited 12 bits left) into rd (used before JALR).
 //
 // > rd ← pc + imm
@@ -672,6 +1022,32 @@ fn exec_auipc<Mem: Bus<u32>>(
     ExecStatus::Running
 }
 
+// Single-Bit Clear: Set rd to rs1 with the bit indexed by the lower 5 bits of rs2 cleared.
+//
+// > rd ← ux(rs1) ∧ ¬(1 « (rs2 ∧ 31))
+fn exec_bclr<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a & !(1 << (b & 0b11111)))
+}
+
+// Single-Bit Clear Immediate: Set rd to rs1 with the bit indexed by the immediate cleared.
+//
+// > rd ← ux(rs1) ∧ ¬(1 « ux(imm))
+fn exec_bclri<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u32> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| a & !(1 << shamt))
+}
+
 // Branch Equal: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 == rs2.
 //
 // > if rs1 = rs2 then pc ← pc + imm
@@ -685,6 +1061,32 @@ fn exec_beq<Mem: Bus<u32>>(
     exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| a == b)
 }
 
+// Single-Bit Extract: Set rd to the bit of rs1 indexed by the lower 5 bits of rs2.
+//
+// > rd ← (ux(rs1) » (rs2 ∧ 31)) ∧ 1
+fn exec_bext<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| (a >> (b & 0b11111)) & 1)
+}
+
+// Single-Bit Extract Immediate: Set rd to the bit of rs1 indexed by the immediate.
+//
+// > rd ← (ux(rs1) » ux(imm)) ∧ 1
+fn exec_bexti<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u32> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| (a >> shamt) & 1)
+}
+
 // Branch Greater than Equal: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 >= rs2 (signed).
 //
 // > if rs1 ≥ rs2 then pc ← pc + imm
@@ -715,6 +1117,32 @@ fn exec_bgeu<Mem: Bus<u32>>(
     })
 }
 
+// Single-Bit Invert: Set rd to rs1 with the bit indexed by the lower 5 bits of rs2 inverted.
+//
+// > rd ← ux(rs1) ⊕ (1 « (rs2 ∧ 31))
+fn exec_binv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a ^ (1 << (b & 0b11111)))
+}
+
+// Single-Bit Invert Immediate: Set rd to rs1 with the bit indexed by the immediate inverted.
+//
+// > rd ← ux(rs1) ⊕ (1 « ux(imm))
+fn exec_binvi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u32> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| a ^ (1 << shamt))
+}
+
 // Branch Less Than: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 < rs2 (signed).
 //
 // > if rs1 < rs2 then pc ← pc + imm
@@ -758,6 +1186,32 @@ fn exec_bne<Mem: Bus<u32>>(
     exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| a != b)
 }
 
+// Single-Bit Set: Set rd to rs1 with the bit indexed by the lower 5 bits of rs2 set.
+//
+// > rd ← ux(rs1) ∨ (1 « (rs2 ∧ 31))
+fn exec_bset<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a | (1 << (b & 0b11111)))
+}
+
+// Single-Bit Set Immediate: Set rd to rs1 with the bit indexed by the immediate set.
+//
+// > rd ← ux(rs1) ∨ (1 « ux(imm))
+fn exec_bseti<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u32> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| a | (1 << shamt))
+}
+
 // : .
 //
 // >
@@ -887,14 +1341,13 @@ fn exec_c_ebreak<Mem: Bus<u32>>(
 // >
 fn exec_c_fld<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: IntRegister,
     rs1: IntRegister,
     uimm: u32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // : .
@@ -902,13 +1355,12 @@ fn exec_c_fld<Mem: Bus<u32>>(
 // >
 fn exec_c_fldsp<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     uimm: u32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // : .
@@ -916,14 +1368,13 @@ fn exec_c_fldsp<Mem: Bus<u32>>(
 // >
 fn exec_c_flw<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: IntRegister,
     rs1: IntRegister,
     uimm: u32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // : .
@@ -931,13 +1382,12 @@ fn exec_c_flw<Mem: Bus<u32>>(
 // >
 fn exec_c_flwsp<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     uimm: u32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // : .
@@ -945,14 +1395,13 @@ fn exec_c_flwsp<Mem: Bus<u32>>(
 // >
 fn exec_c_fsd<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rs1: IntRegister,
     frs2: IntRegister,
     uimm: u32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // : .
@@ -960,13 +1409,12 @@ fn exec_c_fsd<Mem: Bus<u32>>(
 // >
 fn exec_c_fsdsp<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frs2: FloatRegister,
     uimm: u32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // : .
@@ -974,14 +1422,13 @@ fn exec_c_fsdsp<Mem: Bus<u32>>(
 // >
 fn exec_c_fsw<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rs1: IntRegister,
     frs2: IntRegister,
     uimm: u32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // : .
@@ -989,13 +1436,12 @@ fn exec_c_fsw<Mem: Bus<u32>>(
 // >
 fn exec_c_fswsp<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frs2: FloatRegister,
     uimm: u32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // : .
@@ -1237,105 +1683,83 @@ fn exec_c_xor<Mem: Bus<u32>>(
     exec_xor(hart, inst, rs1rd, rs1rd, rs2)
 }
 
-// CSR Atomic Clear Bit: CSR Atomic Clear Bit reads the CSR, clears CSR bits set in rs1, and writes previous value to rd.
+// Carry-less Multiply: Set rd to the lower half of the carry-less product of rs1 and rs2.
 //
-// >
-fn exec_csrrc<Mem: Bus<u32>>(
+// > rd ← ux(rs1) ⊗ ux(rs2)
+fn exec_clmul<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
     _inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
-    csr: u32,
+    rs2: IntRegister,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| carryless_mul(a, b) as u32)
 }
 
-// CSR Atomic Clear Bit Immediate: CSR Atomic Clear Bit Immediate reads the CSR, clears CSR bits set in the immediate, and writes previous value to rd.
+// Carry-less Multiply High: Set rd to the upper half of the carry-less product of rs1 and rs2.
 //
-// >
-fn exec_csrrci<Mem: Bus<u32>>(
+// > rd ← (ux(rs1) ⊗ ux(rs2)) » 32
+fn exec_clmulh<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
     _inst: Instruction<Op, u32>,
     rd: IntRegister,
-    uimm: u32,
-    csr: u32,
+    rs1: IntRegister,
+    rs2: IntRegister,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        (carryless_mul(a, b) >> 32) as u32
+    })
 }
 
-// CSR Atomic Set Bit: CSR Atomic Set Bit reads the CSR, sets CSR bits set in rs1, and writes previous value to rd.
+// Carry-less Multiply Reversed: Set rd to bits 62 to 31 of the carry-less product of rs1 and rs2.
 //
-// >
-fn exec_csrrs<Mem: Bus<u32>>(
+// > rd ← (ux(rs1) ⊗ ux(rs2)) » 31
+fn exec_clmulr<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
     _inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
-    csr: u32,
+    rs2: IntRegister,
 ) -> ExecStatus<u32> {
-    if rs1.num() == 0 {
-        match hart.read_csr(ControlStatusRegister::numbered(csr as usize)) {
-            Ok(result) => {
-                hart.write_int_register(rd, u32::from_unsigned(result));
-            }
-            Err(e) => {
-                hart.exception(ExceptionCause::IllegalInstruction);
-            }
-        };
-    } else {
-        // TODO: Implement the atomic read/or/write behavior for other rs1 registers
-        hart.exception(ExceptionCause::IllegalInstruction);
-    }
-    ExecStatus::Running
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        (carryless_mul(a, b) >> 31) as u32
+    })
 }
 
-// CSR Atomic Set Bit Immediate: CSR Atomic Set Bit Immediate reads the CSR, sets CSR bits set in the immediate, and writes previous value to rd.
+// Count Leading Zeros: Set rd to the number of 0 bits before the most significant 1 bit in rs1.
 //
-// >
-fn exec_csrrsi<Mem: Bus<u32>>(
+// > rd ← clz(ux(rs1))
+fn exec_clz<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
     _inst: Instruction<Op, u32>,
     rd: IntRegister,
-    uimm: u32,
-    csr: u32,
+    rs1: IntRegister,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_unary_op(hart, rd, rs1, u32::leading_zeros)
 }
 
-// CSR Atomic Read Write: CSR Atomic Read Write writes the value in rs1 to the CSR, and writes previous value to rd.
+// Count Set Bits: Set rd to the number of 1 bits in rs1.
 //
-// >
-fn exec_csrrw<Mem: Bus<u32>>(
+// > rd ← popcount(ux(rs1))
+fn exec_cpop<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
     _inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
-    csr: u32,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_unary_op(hart, rd, rs1, u32::count_ones)
 }
 
-// CSR Atomic Read Write Immediate: CSR Atomic Read Write Immediate writes the immediate value to the CSR, and writes previous value to rd.
+// Count Trailing Zeros: Set rd to the number of 0 bits after the least significant 1 bit in rs1.
 //
-// >
-fn exec_csrrwi<Mem: Bus<u32>>(
+// > rd ← ctz(ux(rs1))
+fn exec_ctz<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
     _inst: Instruction<Op, u32>,
     rd: IntRegister,
-    uimm: u32,
-    csr: u32,
+    rs1: IntRegister,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    exec_unary_op(hart, rd, rs1, u32::trailing_zeros)
 }
 
 // Divide Signed: Divide rs1 (dividend) by rs2 (divisor) and place the quotient in rd (signed).
@@ -1390,40 +1814,11 @@ fn exec_divu<Mem: Bus<u32>>(
 //
 // >
 fn exec_dret<Mem: Bus<u32>>(
-    hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
-) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
-}
-
-// Environment Break to Debugger: .
-//
-// >
-fn exec_ebreak<Mem: Bus<u32>>(
-    hart: &mut impl Hart<u32, u32, f64, Mem>,
-    inst: Instruction<Op, u32>,
-) -> ExecStatus<u32> {
-    if !hart.environment_break(inst.pc) {
-        ExecStatus::EnvironmentBreak(inst.pc)
-    } else {
-        ExecStatus::Running
-    }
-}
-
-// Environment Call: .
-//
-// >
-fn exec_ecall<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
     inst: Instruction<Op, u32>,
 ) -> ExecStatus<u32> {
-    if !hart.environment_call(inst.pc) {
-        ExecStatus::EnvironmentCall(inst.pc)
-    } else {
-        ExecStatus::Running
-    }
+    // TODO: Implement
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Add (DP): Add the double-precision values in frs1 and frs2, then write the result to frd.
@@ -1431,14 +1826,13 @@ fn exec_ecall<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f64(frs1) + f64(frs2)
 fn exec_fadd_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
-    hart.exception(ExceptionCause::IllegalInstruction);
-    return ExecStatus::Running;
+    return raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0);
 
     let a = hart.read_float_register(frs1).to_double();
     let b = hart.read_float_register(frs2).to_double();
@@ -1452,15 +1846,14 @@ fn exec_fadd_d<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f128(frs1) + f128(frs2)
 fn exec_fadd_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Add (SP): Add the single-precision values in frs1 and frs2, then write the result to frd.
@@ -1468,15 +1861,14 @@ fn exec_fadd_q<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f32(frs1) + f32(frs2)
 fn exec_fadd_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Classify (DP): Set rd to a 10-bit mask indicating the class of the double-precision value in frs1.
@@ -1484,13 +1876,12 @@ fn exec_fadd_s<Mem: Bus<u32>>(
 // > rd ← rd ← f64_classify(f64(frs1))
 fn exec_fclass_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Classify (QP): Set rd to a 10-bit mask indicating the class of the quadruple-precision value in frs1.
@@ -1498,13 +1889,12 @@ fn exec_fclass_d<Mem: Bus<u32>>(
 // > rd ← rd ← f128_classify(f128(frs1))
 fn exec_fclass_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Classify (SP): Set rd to a 10-bit mask indicating the class of the single-precision value in frs1.
@@ -1512,13 +1902,12 @@ fn exec_fclass_q<Mem: Bus<u32>>(
 // > rd ← f32_classify(f32(frs1))
 fn exec_fclass_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert QP to DP: Convert the quadruple-precision value in frs1 to double-precision, then write the result to frd.
@@ -1526,14 +1915,13 @@ fn exec_fclass_s<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f64(f128(frs1))
 fn exec_fcvt_d_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert SP to DP: Convert the single-precision value in frs1 to double-precision, then write the result to frd.
@@ -1541,14 +1929,13 @@ fn exec_fcvt_d_q<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f64(f32(frs1))
 fn exec_fcvt_d_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert Word to Float (DP): Convert the 64-bit signed integer in rs1 to a double-precision value, then write the result to frd.
@@ -1556,14 +1943,13 @@ fn exec_fcvt_d_s<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f64(s32(rs1))
 fn exec_fcvt_d_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     rs1: IntRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert Word Unsigned to Float (DP): Convert the 64-bit unsigned integer in rs1 to a double-precision value, then write the result to frd.
@@ -1571,14 +1957,13 @@ fn exec_fcvt_d_w<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f64(u32(rs1))
 fn exec_fcvt_d_wu<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     rs1: IntRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert DP to QP: Convert the double-precision value in frs1 to quadruple-precision, then write the result to frd.
@@ -1586,14 +1971,13 @@ fn exec_fcvt_d_wu<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f128(f64(frs1))
 fn exec_fcvt_q_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert SP to QP: Convert the single-precision value in frs1 to quadruple-precision, then write the result to frd.
@@ -1601,14 +1985,13 @@ fn exec_fcvt_q_d<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f128(f32(frs1))
 fn exec_fcvt_q_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert Word to Float (QP): Convert the 64-bit signed integer in rs1 to a quadruple-precision value, then write the result to frd.
@@ -1616,14 +1999,13 @@ fn exec_fcvt_q_s<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f128(s32(rs1))
 fn exec_fcvt_q_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     rs1: IntRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert Word Unsigned to Float (QP): Convert the 64-bit unsigned integer in rs1 to a quadruple-precision value, then write the result to frd.
@@ -1631,14 +2013,13 @@ fn exec_fcvt_q_w<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f128(u32(rs1))
 fn exec_fcvt_q_wu<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     rs1: IntRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert DP to SP: Convert the double-precision value in frs1 to single-precision, then write the result to frd.
@@ -1646,14 +2027,13 @@ fn exec_fcvt_q_wu<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f32(f64(frs1))
 fn exec_fcvt_s_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert QP to SP: Convert the quadruple-precision value in frs1 to single-precision, then write the result to frd.
@@ -1661,14 +2041,13 @@ fn exec_fcvt_s_d<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f32(f128(frs1))
 fn exec_fcvt_s_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert Word to Float (SP): Convert the 32-bit signed integer in rs1 to a single-precision value, then write the result to frd.
@@ -1676,14 +2055,13 @@ fn exec_fcvt_s_q<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f32(s32(rs1))
 fn exec_fcvt_s_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     rs1: IntRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert Word Unsigned to Float (SP): Convert the 32-bit unsigned integer in rs1 to a single-precision value, then write the result to frd.
@@ -1691,14 +2069,13 @@ fn exec_fcvt_s_w<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f32(u32(rs1))
 fn exec_fcvt_s_wu<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     rs1: IntRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert Float to Word (DP): Convert the double-precision value in frs1 to a 32-bit signed integer, then write the result to rd.
@@ -1706,14 +2083,13 @@ fn exec_fcvt_s_wu<Mem: Bus<u32>>(
 // > frm ← rm ; rd ← s32(f64(frs1))
 fn exec_fcvt_w_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert Float to Word (QP): Convert the quadruple-precision value in frs1 to a 32-bit signed integer, then write the result to rd.
@@ -1721,14 +2097,13 @@ fn exec_fcvt_w_d<Mem: Bus<u32>>(
 // > frm ← rm ; rd ← s32(f128(frs1))
 fn exec_fcvt_w_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert Float to Word (SP): Convert the single-precision value in frs1 to a 32-bit signed integer, then write the result to rd.
@@ -1736,14 +2111,13 @@ fn exec_fcvt_w_q<Mem: Bus<u32>>(
 // > frm ← rm ; rd ← s32(f32(frs1))
 fn exec_fcvt_w_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert Float to Word Unsigned (DP): Convert the double-precision value in frs1 to a 32-bit unsigned integer, then write the result to rd.
@@ -1751,14 +2125,13 @@ fn exec_fcvt_w_s<Mem: Bus<u32>>(
 // > frm ← rm ; if f64(frs1) > 0 then rd ← u32(f64(frs1) else rd ← 0
 fn exec_fcvt_wu_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert Float to Word Unsigned (QP): Convert the quadruple-precision value in frs1 to a 32-bit unsigned integer, then write the result to rd.
@@ -1766,14 +2139,13 @@ fn exec_fcvt_wu_d<Mem: Bus<u32>>(
 // > frm ← rm ; if f128(frs1) > 0 then rd ← u32(f128(frs1) else rd ← 0
 fn exec_fcvt_wu_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Convert Float to Word Unsigned (SP): Convert the single-precision value in frs1 to a 32-bit unsigned integer, then write the result to rd.
@@ -1781,14 +2153,13 @@ fn exec_fcvt_wu_q<Mem: Bus<u32>>(
 // > frm ← rm ; if f32(frs1) > 0 then rd ← u32(f32(frs1) else rd ← 0
 fn exec_fcvt_wu_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Divide (DP): Divide the double-precision value in frs1 into frs2, then write the result to frd.
@@ -1796,15 +2167,14 @@ fn exec_fcvt_wu_s<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f64(frs1) ÷ f64(frs2)
 fn exec_fdiv_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Divide (QP): Divide the quadruple-precision value in frs1 into frs2, then write the result to frd.
@@ -1812,15 +2182,14 @@ fn exec_fdiv_d<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f128(frs1) ÷ f128(frs2)
 fn exec_fdiv_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Divide (SP): Divide the single-precision value in frs1 into frs2, then write the result to frd.
@@ -1828,39 +2197,14 @@ fn exec_fdiv_q<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f32(frs1) ÷ f32(frs2)
 fn exec_fdiv_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
-}
-
-// Fence: Order device I/O and memory accesses viewed by other threads and devices.
-//
-// >
-fn exec_fence<Mem: Bus<u32>>(
-    hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
-    _pred: bool,
-    _succ: bool,
-) -> ExecStatus<u32> {
-    hart.fence_data();
-    ExecStatus::Running
-}
-
-// Fence Instruction: Synchronize the instruction and data streams.
-//
-// >
-fn exec_fence_i<Mem: Bus<u32>>(
-    hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
-) -> ExecStatus<u32> {
-    hart.fence_code();
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Equal (DP): Set rd to 1 if frs1 is equal to frs2, otherwise set rd to 0.
@@ -1868,14 +2212,13 @@ fn exec_fence_i<Mem: Bus<u32>>(
 // > if f64(frs1) = f64(frs2) then rd ← 1 else rd ← 0
 fn exec_feq_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Equal (QP): Set rd to 1 if frs1 is equal to frs2, otherwise set rd to 0.
@@ -1883,14 +2226,13 @@ fn exec_feq_d<Mem: Bus<u32>>(
 // > if f128(frs1) = f128(frs2) then rd ← 1 else rd ← 0
 fn exec_feq_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Equal (SP): Set rd to 1 if the single-precision value in frs1 is equal to frs2, otherwise set rd to 0.
@@ -1898,14 +2240,13 @@ fn exec_feq_q<Mem: Bus<u32>>(
 // > if f32(frs1) = f32(frs2) then rd ← 1 else rd ← 0
 fn exec_feq_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Load (DP): Loads a double-precision foating-point value from memory into foating-point register frd.
@@ -1913,14 +2254,13 @@ fn exec_feq_s<Mem: Bus<u32>>(
 // > frd ← f64[rs1 + imm]
 fn exec_fld<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     rs1: IntRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Less Than Equal (DP): Set rd to 1 if frs1 is less than or equal to frs2, otherwise set rd to 0.
@@ -1928,14 +2268,13 @@ fn exec_fld<Mem: Bus<u32>>(
 // > if f64(frs1) ≤ f64(frs2) then rd ← 1 else rd ← 0
 fn exec_fle_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Less Than Equal (QP): Set rd to 1 if frs1 is less than or equal to frs2, otherwise set rd to 0.
@@ -1943,14 +2282,13 @@ fn exec_fle_d<Mem: Bus<u32>>(
 // > if f128(frs1) ≤ f128(frs2) then rd ← 1 else rd ← 0
 fn exec_fle_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Less Than Equal (SP): Set rd to 1 if the single-precision value in frs1 is less than or equal to frs2, otherwise set rd to 0.
@@ -1958,14 +2296,13 @@ fn exec_fle_q<Mem: Bus<u32>>(
 // > if f32(frs1) ≤ f32(frs2) then rd ← 1 else rd ← 0
 fn exec_fle_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Load (QP): Loads a quadruple-precision foating-point value from memory into foating-point register frd.
@@ -1973,14 +2310,13 @@ fn exec_fle_s<Mem: Bus<u32>>(
 // > frd ← f128[rs1 + imm]
 fn exec_flq<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     rs1: IntRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Less Than (DP): Set rd to 1 if frs1 is less than frs2, otherwise set rd to 0.
@@ -1988,14 +2324,13 @@ fn exec_flq<Mem: Bus<u32>>(
 // > if f64(frs1) < f64(frs2) then rd ← 1 else rd ← 0
 fn exec_flt_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Less Than (QP): Set rd to 1 if frs1 is less than frs2, otherwise set rd to 0.
@@ -2003,14 +2338,13 @@ fn exec_flt_d<Mem: Bus<u32>>(
 // > if f128(frs1) < f128(frs2) then rd ← 1 else rd ← 0
 fn exec_flt_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Less Than (SP): Set rd to 1 if the single-precision value in frs1 is less than frs2, otherwise set rd to 0.
@@ -2018,14 +2352,13 @@ fn exec_flt_q<Mem: Bus<u32>>(
 // > if f32(frs1) < f32(frs2) then rd ← 1 else rd ← 0
 fn exec_flt_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Load (SP): Loads a single-precision foating-point value from memory into foating-point register frd.
@@ -2033,14 +2366,13 @@ fn exec_flt_s<Mem: Bus<u32>>(
 // > frd ← f32[rs1 + imm]
 fn exec_flw<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     rs1: IntRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Fused Multiply Add (DP): Multiply the double-precision values in frs1 and frs2, then add rs3 and write the result to frd.
@@ -2048,7 +2380,7 @@ fn exec_flw<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f64(frs1) × f64(frs2) + f64(frs3)
 fn exec_fmadd_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
@@ -2056,8 +2388,7 @@ fn exec_fmadd_d<Mem: Bus<u32>>(
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Fused Multiply Add (QP): Multiply the quadruple-precision values in frs1 and frs2, then add rs3 and write the result to frd.
@@ -2065,7 +2396,7 @@ fn exec_fmadd_d<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f128(frs1) × f128(frs2) + f128(frs3)
 fn exec_fmadd_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
@@ -2073,8 +2404,7 @@ fn exec_fmadd_q<Mem: Bus<u32>>(
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Fused Multiply Add (SP): Multiply the single-precision values in frs1 and frs2, then add rs3 and write the result to frd.
@@ -2082,7 +2412,7 @@ fn exec_fmadd_q<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f32(frs1) × f32(frs2) + f32(frs3)
 fn exec_fmadd_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
@@ -2090,8 +2420,7 @@ fn exec_fmadd_s<Mem: Bus<u32>>(
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Maximum (DP): .
@@ -2099,14 +2428,13 @@ fn exec_fmadd_s<Mem: Bus<u32>>(
 // > frd ← f64_max(f64(frs1), f64(frs2))
 fn exec_fmax_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Maximum (QP): .
@@ -2114,14 +2442,13 @@ fn exec_fmax_d<Mem: Bus<u32>>(
 // > frd ← f128_max(f128(frs1), f128(frs2))
 fn exec_fmax_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Maximum (SP): Take the larger quadruple-precision value from frs1 and frs2, then write the result to frd.
@@ -2129,14 +2456,13 @@ fn exec_fmax_q<Mem: Bus<u32>>(
 // > frd ← f32_max(f32(frs1), f32(frs2))
 fn exec_fmax_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Minimum (DP): .
@@ -2144,14 +2470,13 @@ fn exec_fmax_s<Mem: Bus<u32>>(
 // > frd ← f64_min(f64(frs1), f64(frs2))
 fn exec_fmin_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Minimum (QP): .
@@ -2159,14 +2484,13 @@ fn exec_fmin_d<Mem: Bus<u32>>(
 // > frd ← f128_min(f128(frs1), f128(frs2))
 fn exec_fmin_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Minimum (SP): Take the smaller quadruple-precision value from frs1 and frs2, then write the result to frd.
@@ -2174,14 +2498,13 @@ fn exec_fmin_q<Mem: Bus<u32>>(
 // > frd ← f32_min(f32(frs1), f32(frs2))
 fn exec_fmin_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Fused Multiply Subtract (DP): Multiply the double-precision values in frs1 and frs2, then subtract rs3 and write the result to frd.
@@ -2189,7 +2512,7 @@ fn exec_fmin_s<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f64(frs1) × f64(frs2) - f64(frs3)
 fn exec_fmsub_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
@@ -2197,8 +2520,7 @@ fn exec_fmsub_d<Mem: Bus<u32>>(
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Fused Multiply Subtract (QP): Multiply the quadruple-precision values in frs1 and frs2, then subtract rs3 and write the result to frd.
@@ -2206,7 +2528,7 @@ fn exec_fmsub_d<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f128(frs1) × f128(frs2) - f128(frs3)
 fn exec_fmsub_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
@@ -2214,8 +2536,7 @@ fn exec_fmsub_q<Mem: Bus<u32>>(
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Fused Multiply Subtract (SP): Multiply the single-precision values in frs1 and frs2, then subtract rs3 and write the result to frd.
@@ -2223,7 +2544,7 @@ fn exec_fmsub_q<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f32(frs1) × f32(frs2) - f32(frs3)
 fn exec_fmsub_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
@@ -2231,8 +2552,7 @@ fn exec_fmsub_s<Mem: Bus<u32>>(
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Multiply (DP): Multiply the double-precision values in frs1 and frs2, then write the result to frd.
@@ -2240,15 +2560,14 @@ fn exec_fmsub_s<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f64(frs1) × f64(frs2)
 fn exec_fmul_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Multiply (QP): Multiply the quadruple-precision values in frs1 and frs2, then write the result to frd.
@@ -2256,15 +2575,14 @@ fn exec_fmul_d<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f128(frs1) × f128(frs2)
 fn exec_fmul_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Multiply (SP): Multiply the single-precision values in frs1 and frs2, then write the result to frd.
@@ -2272,15 +2590,14 @@ fn exec_fmul_q<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f32(frs1) × f32(frs2)
 fn exec_fmul_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Move from Integer Register (SP): Write the lower 32-bits of the integer register rs1 into the single-precision register frd.
@@ -2288,13 +2605,12 @@ fn exec_fmul_s<Mem: Bus<u32>>(
 // > frd ← s32(rs1)
 fn exec_fmv_s_x<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     rs1: IntRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Move to Integer Register (SP): Write the sign extended single-precision value in frs1 into the integer register rd.
@@ -2302,13 +2618,12 @@ fn exec_fmv_s_x<Mem: Bus<u32>>(
 // > rd ← s32(frs1)
 fn exec_fmv_x_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     frs1: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Negate fused Multiply Add (DP): Multiply the double-precision value in frs1 with the negated value in frs2, then subtract rs3 and write the result to frd.
@@ -2316,7 +2631,7 @@ fn exec_fmv_x_s<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f64(frs1) × -f64(frs2) - f64(frs3)
 fn exec_fnmadd_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
@@ -2324,8 +2639,7 @@ fn exec_fnmadd_d<Mem: Bus<u32>>(
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Negate fused Multiply Add (QP): Multiply the quadruple-precision value in frs1 with the negated value in frs2, then subtract rs3 and write the result to frd.
@@ -2333,7 +2647,7 @@ fn exec_fnmadd_d<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f128(frs1) × -f128(frs2) - f128(frs3)
 fn exec_fnmadd_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
@@ -2341,8 +2655,7 @@ fn exec_fnmadd_q<Mem: Bus<u32>>(
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Negate fused Multiply Add (SP): Multiply the single-precision value in frs1 with the negated value in frs2, then subtract rs3 and write the result to frd.
@@ -2350,7 +2663,7 @@ fn exec_fnmadd_q<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f32(frs1) × -f32(frs2) - f32(frs3)
 fn exec_fnmadd_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
@@ -2358,8 +2671,7 @@ fn exec_fnmadd_s<Mem: Bus<u32>>(
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Negate fused Multiply Subtract (DP): Multiply the double-precision value in frs1 with the negated value in frs2, then add rs3 and write the result to frd.
@@ -2367,7 +2679,7 @@ fn exec_fnmadd_s<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f64(frs1) × -f64(frs2) + f64(frs3)
 fn exec_fnmsub_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
@@ -2375,8 +2687,7 @@ fn exec_fnmsub_d<Mem: Bus<u32>>(
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Negate fused Multiply Subtract (QP): Multiply the quadruple-precision value in frs1 with the negated value in frs2, then add rs3 and write the result to frd.
@@ -2384,7 +2695,7 @@ fn exec_fnmsub_d<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f128(frs1) × -f128(frs2) + f128(frs3)
 fn exec_fnmsub_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
@@ -2392,8 +2703,7 @@ fn exec_fnmsub_q<Mem: Bus<u32>>(
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Negate fused Multiply Subtract (SP): Multiply the single-precision value in frs1 with the negated value in frs2, then add rs3 and write the result to frd.
@@ -2401,7 +2711,7 @@ fn exec_fnmsub_q<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f32(frs1) × -f32(frs2) + f32(frs3)
 fn exec_fnmsub_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
@@ -2409,8 +2719,7 @@ fn exec_fnmsub_s<Mem: Bus<u32>>(
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Store (DP): Stores a double-precision foating-point value from foating-point register frs2 to memory.
@@ -2418,14 +2727,13 @@ fn exec_fnmsub_s<Mem: Bus<u32>>(
 // > f64[rs1 + imm] ← f64(frs2)
 fn exec_fsd<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rs1: IntRegister,
     frs2: FloatRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP to Sign-injection (DP): Take the double-precision value from frs1 and inject the sign bit from frs2, then write the result to frd.
@@ -2433,14 +2741,13 @@ fn exec_fsd<Mem: Bus<u32>>(
 // > frd ← f64_copysign(f64(frs1), f64(frs2))
 fn exec_fsgnj_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP to Sign-injection (QP): Take the quadruple-precision value from frs1 and inject the sign bit from frs2, then write the result to frd.
@@ -2448,14 +2755,13 @@ fn exec_fsgnj_d<Mem: Bus<u32>>(
 // > frd ← f128_copysign(f128(frs1), f128(frs2))
 fn exec_fsgnj_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Sign-injection (SP): Take the single-precision value from frs1 and inject the sign bit from frs2, then write the result to frd.
@@ -2463,14 +2769,13 @@ fn exec_fsgnj_q<Mem: Bus<u32>>(
 // > frd ← f32_copysign(f32(frs1), f32(frs2))
 fn exec_fsgnj_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP to Sign-injection Negate (DP): Take the double-precision value from frs1 and inject the negated sign bit from frs2, then write the result to frd.
@@ -2478,14 +2783,13 @@ fn exec_fsgnj_s<Mem: Bus<u32>>(
 // > frd ← f64_copysign(f64(frs1), -f64(frs2))
 fn exec_fsgnjn_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP to Sign-injection Negate (QP): Take the quadruple-precision value from frs1 and inject the negated sign bit from frs2, then write the result to frd.
@@ -2493,14 +2797,13 @@ fn exec_fsgnjn_d<Mem: Bus<u32>>(
 // > frd ← f128_copysign(f128(frs1), -f128(frs2))
 fn exec_fsgnjn_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Sign-injection Negate (SP): Take the single-precision value from frs1 and inject the negated sign bit from frs2, then write the result to frd.
@@ -2508,14 +2811,13 @@ fn exec_fsgnjn_q<Mem: Bus<u32>>(
 // > frd ← f32_copysign(f32(frs1), -f32(frs2))
 fn exec_fsgnjn_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP to Sign-injection Xor (DP): Take the double-precision value from frs1 and inject the xor of the sign bits frs1 and frs2, then write the result to frd.
@@ -2523,14 +2825,13 @@ fn exec_fsgnjn_s<Mem: Bus<u32>>(
 // > frd ← f64_xorsign(f64(frs1), f64(frs2))
 fn exec_fsgnjx_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP to Sign-injection Xor (QP): Take the quadruple-precision value from frs1 and inject the xor of the sign bits frs1 and frs2, then write the result to frd.
@@ -2538,14 +2839,13 @@ fn exec_fsgnjx_d<Mem: Bus<u32>>(
 // > frd ← f128_xorsign(f128(frs1), f128(frs2))
 fn exec_fsgnjx_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Sign-injection Xor (SP): Take the single-precision value from frs1 and inject the xor of the sign bits frs1 and frs2, then write the result to frd.
@@ -2553,14 +2853,13 @@ fn exec_fsgnjx_q<Mem: Bus<u32>>(
 // > frd ← f32_xorsign(f32(frs1), f32(frs2))
 fn exec_fsgnjx_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Store (QP): Stores a quadruple-precision foating-point value from foating-point register frs2 to memory.
@@ -2568,14 +2867,13 @@ fn exec_fsgnjx_s<Mem: Bus<u32>>(
 // > f128[rs1 + imm] ← f128(frs2)
 fn exec_fsq<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rs1: IntRegister,
     frs2: FloatRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // Floating Square Root (DP): Calculate the square root of the double-precision value in frs1, then write the result to frd.
@@ -2583,14 +2881,13 @@ fn exec_fsq<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f64_sqrt(f64(frs1))
 fn exec_fsqrt_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // Floating Square Root (QP): Calculate the square root of the quadruple-precision value in frs1, then write the result to frd.
@@ -2598,14 +2895,13 @@ fn exec_fsqrt_d<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f128_sqrt(f128(frs1))
 fn exec_fsqrt_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Square Root (SP): Calculate the square root of the single-precision value in frs1, then write the result to frd.
@@ -2613,14 +2909,13 @@ fn exec_fsqrt_q<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f32_sqrt(f32(frs1))
 fn exec_fsqrt_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Subtract (DP): Subtract the double-precision values in frs1 from frs2, then write the result to frd.
@@ -2628,15 +2923,14 @@ fn exec_fsqrt_s<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f64(frs1) - f64(frs2)
 fn exec_fsub_d<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Subtract (QP): Subtract the quadruple-precision values in frs1 from frs2, then write the result to frd.
@@ -2644,15 +2938,14 @@ fn exec_fsub_d<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f128(frs1) - f128(frs2)
 fn exec_fsub_q<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Subtract (SP): Subtract the single-precision values in frs1 from frs2, then write the result to frd.
@@ -2660,15 +2953,14 @@ fn exec_fsub_q<Mem: Bus<u32>>(
 // > frm ← rm ; frd ← f32(frs1) - f32(frs2)
 fn exec_fsub_s<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     frd: FloatRegister,
     frs1: FloatRegister,
     frs2: FloatRegister,
     rm: bool,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // FP Store (SP): Stores a single-precision foating-point value from foating-point register frs2 to memory.
@@ -2676,14 +2968,13 @@ fn exec_fsub_s<Mem: Bus<u32>>(
 // > f32[rs1 + imm] ← f32(frs2)
 fn exec_fsw<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rs1: IntRegister,
     frs2: FloatRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // Hypervisor Return: .
@@ -2691,11 +2982,10 @@ fn exec_fsw<Mem: Bus<u32>>(
 // >
 fn exec_hret<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // Jump and Link: Jump to the PC plus 20-bit signed immediate while saving PC+4 into rd.
@@ -2707,10 +2997,12 @@ fn exec_jal<Mem: Bus<u32>>(
     rd: IntRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
+    let new_pc = inst.pc.wrapping_add(u32::from_signed(simm).to_unsigned());
+    if let Err(status) = jump(hart, inst.pc, new_pc) {
+        return status;
+    }
     let ret_pc = inst.pc.wrapping_add(inst.length as u32);
     hart.write_int_register(rd, u32::from_unsigned(ret_pc));
-    let new_pc = inst.pc.wrapping_add(u32::from_signed(simm).to_unsigned());
-    hart.write_pc(new_pc);
     ExecStatus::Running
 }
 
@@ -2727,7 +3019,9 @@ fn exec_jalr<Mem: Bus<u32>>(
     let base_addr = hart.read_int_register(rs1).to_unsigned();
     let new_pc = base_addr.wrapping_add(u32::from_signed(simm).to_unsigned())
         & 0b11111111111111111111111111111110;
-    hart.write_pc(new_pc);
+    if let Err(status) = jump(hart, inst.pc, new_pc) {
+        return status;
+    }
 
     let ret_pc = inst.pc.wrapping_add(inst.length as u32);
     hart.write_int_register(rd, u32::from_unsigned(ret_pc));
@@ -2740,12 +3034,12 @@ fn exec_jalr<Mem: Bus<u32>>(
 // > rd ← s8[rs1 + imm]
 fn exec_lb<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
-    exec_load_mem(hart, rd, rs1, simm, |mem, addr| {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
         let v = mem.read_byte(addr)?;
         let sv = sign_extend(v as u32, 8);
         Ok(u32::from_signed(sv))
@@ -2757,12 +3051,12 @@ fn exec_lb<Mem: Bus<u32>>(
 // > rd ← u8[rs1 + imm]
 fn exec_lbu<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
-    exec_load_mem(hart, rd, rs1, simm, |mem, addr| {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
         let v = mem.read_byte(addr)?;
         Ok(u32::from_unsigned(v as u32))
     })
@@ -2773,12 +3067,12 @@ fn exec_lbu<Mem: Bus<u32>>(
 // > rd ← s16[rs1 + imm]
 fn exec_lh<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
-    exec_load_mem(hart, rd, rs1, simm, |mem, addr| {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
         let v = mem.read_halfword(addr)?;
         let sv = sign_extend(v as u32, 16);
         Ok(u32::from_signed(sv))
@@ -2790,12 +3084,12 @@ fn exec_lh<Mem: Bus<u32>>(
 // > rd ← u16[rs1 + imm]
 fn exec_lhu<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
-    exec_load_mem(hart, rd, rs1, simm, |mem, addr| {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
         let v = mem.read_halfword(addr)?;
         Ok(u32::from_unsigned(v as u32))
     })
@@ -2806,14 +3100,29 @@ fn exec_lhu<Mem: Bus<u32>>(
 // > lr ← rs1 ∥ rd ← sx(s32[rs1])
 fn exec_lr_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     aq: bool,
     rl: bool,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
+    let addr = hart.read_int_register(rs1).to_unsigned();
+    if addr % 4 != 0 {
+        return raise(hart, inst.pc, ExceptionCause::LoadAddressMisaligned, addr);
+    }
+    if rl {
+        hart.fence_data(FenceSet::MEMORY, FenceSet::MEMORY);
+    }
+    match hart.with_memory(|mem| mem.read_word(addr)) {
+        Ok(v) => {
+            hart.set_reservation(Some((addr, v)));
+            hart.write_int_register(rd, v);
+        }
+        Err(e) => return raise(hart, inst.pc, e.cause(), e.addr as u32),
+    }
+    if aq {
+        hart.fence_data(FenceSet::MEMORY, FenceSet::MEMORY);
+    }
     ExecStatus::Running
 }
 
@@ -2835,28 +3144,83 @@ fn exec_lui<Mem: Bus<u32>>(
 // > rd ← s32[rs1 + imm]
 fn exec_lw<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
-    exec_load_mem(hart, rd, rs1, simm, |mem, addr| {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
         let v = mem.read_word(addr)?;
         let sv = sign_extend(v as u32, 32);
         Ok(u32::from_signed(sv))
     })
 }
 
+// Maximum: Set rd to the larger of rs1 and rs2 (signed).
+//
+// > rd ← max(sx(rs1), sx(rs2))
+fn exec_max<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u32::from_signed(a.to_signed().max(b.to_signed()))
+    })
+}
+
+// Maximum Unsigned: Set rd to the larger of rs1 and rs2 (unsigned).
+//
+// > rd ← max(ux(rs1), ux(rs2))
+fn exec_maxu<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a.max(b))
+}
+
+// Minimum: Set rd to the smaller of rs1 and rs2 (signed).
+//
+// > rd ← min(sx(rs1), sx(rs2))
+fn exec_min<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u32::from_signed(a.to_signed().min(b.to_signed()))
+    })
+}
+
+// Minimum Unsigned: Set rd to the smaller of rs1 and rs2 (unsigned).
+//
+// > rd ← min(ux(rs1), ux(rs2))
+fn exec_minu<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a.min(b))
+}
+
 // Machine-Mode Return: .
 //
 // >
 fn exec_mret<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // Multiply: Multiply rs1 by rs2 and place the result in rd.
@@ -2950,6 +3314,20 @@ fn exec_or<Mem: Bus<u32>>(
     })
 }
 
+// Bitwise Or-Combine Byte: Set each byte of rd to all ones if the corresponding byte of rs1 is nonzero, or to zero otherwise.
+//
+// > rd ← orc.b(ux(rs1))
+fn exec_orc_b<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u32> {
+    exec_unary_op(hart, rd, rs1, |a| {
+        u32::from_le_bytes(a.to_le_bytes().map(|b| if b == 0 { 0 } else { 0xff }))
+    })
+}
+
 // Or Immediate: Set rd to the bitwise or of rs1 with the sign-extended 12-bit immediate.
 //
 // > rd ← ux(rs1) ∨ ux(imm)
@@ -2965,6 +3343,19 @@ fn exec_ori<Mem: Bus<u32>>(
     })
 }
 
+// Or Inverted: Set rd to the bitwise or of rs1 and the inverse of rs2.
+//
+// > rd ← ux(rs1) ∨ ¬ux(rs2)
+fn exec_orn<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a | !b)
+}
+
 // Remainder Signed: Divide rs1 (dividend) by rs2 (divisor) and place the remainder in rd (signed).
 //
 // > rd ← sx(rs1) mod sx(rs2)
@@ -3013,17 +3404,68 @@ fn exec_remu<Mem: Bus<u32>>(
     })
 }
 
+// Byte Reverse: Set rd to rs1 with the order of its bytes reversed.
+//
+// > rd ← rev8(ux(rs1))
+fn exec_rev8<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u32> {
+    exec_unary_op(hart, rd, rs1, u32::swap_bytes)
+}
+
+// Rotate Left: Rotate rs1 left by the lower 5 bits of rs2 and place the result into rd.
+//
+// > rd ← ux(rs1) «« rs2
+fn exec_rol<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a.rotate_left(b & 0b11111))
+}
+
+// Rotate Right: Rotate rs1 right by the lower 5 bits of rs2 and place the result into rd.
+//
+// > rd ← ux(rs1) »» rs2
+fn exec_ror<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a.rotate_right(b & 0b11111))
+}
+
+// Rotate Right Immediate: Rotate rs1 right by the immediate and place the result into rd.
+//
+// > rd ← ux(rs1) »» ux(imm)
+fn exec_rori<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u32> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, u32::rotate_right)
+}
+
 // Store Byte: Store 8-bit value from the low bits of rs2 to addr in rs1 plus the 12-bit signed immediate.
 //
 // > u8[rs1 + imm] ← rs2
 fn exec_sb<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rs1: IntRegister,
     rs2: IntRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
-    exec_store_mem(hart, rs1, rs2, simm, |mem, addr, v| {
+    exec_store_mem(hart, inst, rs1, rs2, simm, |mem, addr, v| {
         mem.write_byte(addr, v.to_unsigned() as u8)
     })
 }
@@ -3033,29 +3475,72 @@ fn exec_sb<Mem: Bus<u32>>(
 // > if lr = rs1 then u32[rs1] ← u32(rs2); rd ← 0 else rd ← 1
 fn exec_sc_w<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
     rs2: IntRegister,
     aq: bool,
     rl: bool,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
+    let addr = hart.read_int_register(rs1).to_unsigned();
+    if addr % 4 != 0 {
+        return raise(hart, inst.pc, ExceptionCause::StoreAddressMisaligned, addr);
+    }
+    let v = hart.read_int_register(rs2).to_unsigned();
+    let reservation = hart.take_reservation();
+    if rl {
+        hart.fence_data(FenceSet::MEMORY, FenceSet::MEMORY);
+    }
+    let result = match reservation {
+        Some((reserved, loaded)) if reserved == addr => hart
+            .with_memory(|mem| mem.compare_exchange_word(addr, loaded, v))
+            .map(|r| r.is_ok()),
+        _ => Ok(false),
+    };
+    match result {
+        Ok(stored) => hart.write_int_register(rd, if stored { 0 } else { 1 }),
+        Err(e) => return raise(hart, inst.pc, e.cause(), e.addr as u32),
+    }
+    if aq {
+        hart.fence_data(FenceSet::MEMORY, FenceSet::MEMORY);
+    }
     ExecStatus::Running
 }
 
+// Sign-Extend Byte: Set rd to the least significant byte of rs1, sign-extended.
+//
+// > rd ← s8(rs1)
+fn exec_sext_b<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u32> {
+    exec_unary_op(hart, rd, rs1, |a| a as i8 as u32)
+}
+
+// Sign-Extend Halfword: Set rd to the least significant halfword of rs1, sign-extended.
+//
+// > rd ← s16(rs1)
+fn exec_sext_h<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u32> {
+    exec_unary_op(hart, rd, rs1, |a| a as i16 as u32)
+}
+
 // Supervisor Memory Management Fence: Supervisor memory-management fence synchronizes updates to in-memory memory-management data structures.
 //
 // >
 fn exec_sfence_vm<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rs1: IntRegister,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // : .
@@ -3063,12 +3548,14 @@ fn exec_sfence_vm<Mem: Bus<u32>>(
 // >
 fn exec_sfence_vma<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rs1: IntRegister,
     rs2: IntRegister,
 ) -> ExecStatus<u32> {
-    hart.fence_virtual_memory_config(rs1, rs2);
-    ExecStatus::Running
+    match hart.fence_virtual_memory_config(rs1, rs2) {
+        Ok(()) => ExecStatus::Running,
+        Err(cause) => raise(hart, inst.pc, cause, 0),
+    }
 }
 
 // Store Half: Store 16-bit value from the low bits of rs2 to addr in rs1 plus the 12-bit signed immediate.
@@ -3076,16 +3563,55 @@ fn exec_sfence_vma<Mem: Bus<u32>>(
 // > u16[rs1 + imm] ← rs2
 fn exec_sh<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rs1: IntRegister,
     rs2: IntRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
-    exec_store_mem(hart, rs1, rs2, simm, |mem, addr, v| {
+    exec_store_mem(hart, inst, rs1, rs2, simm, |mem, addr, v| {
         mem.write_halfword(addr, v.to_unsigned() as u16)
     })
 }
 
+// Shift Left by 1 and Add: Shift rs1 left by 1 bit, add rs2 and place the result into rd.
+//
+// > rd ← ux(rs2) + (ux(rs1) « 1)
+fn exec_sh1add<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| b.wrapping_add(a << 1))
+}
+
+// Shift Left by 2 and Add: Shift rs1 left by 2 bits, add rs2 and place the result into rd.
+//
+// > rd ← ux(rs2) + (ux(rs1) « 2)
+fn exec_sh2add<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| b.wrapping_add(a << 2))
+}
+
+// Shift Left by 3 and Add: Shift rs1 left by 3 bits, add rs2 and place the result into rd.
+//
+// > rd ← ux(rs2) + (ux(rs1) « 3)
+fn exec_sh3add<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| b.wrapping_add(a << 3))
+}
+
 // Shift Left Logical: Shift rs1 left by the by the lower 5 or 6 (RV32/64) bits in rs2 and place the result into rd.
 //
 // > rd ← ux(rs1) « rs2
@@ -3235,11 +3761,10 @@ fn exec_srai<Mem: Bus<u32>>(
 // >
 fn exec_sret<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
 ) -> ExecStatus<u32> {
     // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0)
 }
 
 // Shift Right Logical: Shift rs1 right by the by the lower 5 or 6 (RV32/64) bits in rs2 and place the result into rd.
@@ -3295,12 +3820,12 @@ fn exec_sub<Mem: Bus<u32>>(
 // > u32[rs1 + imm] ← rs2
 fn exec_sw<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
     rs1: IntRegister,
     rs2: IntRegister,
     simm: i32,
 ) -> ExecStatus<u32> {
-    exec_store_mem(hart, rs1, rs2, simm, |mem, addr, v| {
+    exec_store_mem(hart, inst, rs1, rs2, simm, |mem, addr, v| {
         mem.write_word(addr, v.to_unsigned())
     })
 }
@@ -3310,11 +3835,17 @@ fn exec_sw<Mem: Bus<u32>>(
 // >
 fn exec_uret<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
-    _inst: Instruction<Op, u32>,
+    inst: Instruction<Op, u32>,
 ) -> ExecStatus<u32> {
-    // TODO: Implement
-    hart.exception(ExceptionCause::IllegalInstruction);
-    ExecStatus::Running
+    // TODO: Restore the interrupt enable bits in ustatus, once the hart
+    // supports interrupts.
+    match hart.read_csr(ControlStatusRegister::numbered(0x041)) {
+        Ok(uepc) => {
+            hart.write_pc(uepc.to_unsigned());
+            ExecStatus::Running
+        }
+        Err(_) => raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0),
+    }
 }
 
 // Wait For Interrupt: Wait for Interrupt indicates the hart can be stalled until an interrupt needs servicing.
@@ -3327,6 +3858,19 @@ fn exec_wfi<Mem: Bus<u32>>(
     ExecStatus::WaitingForInterrupt
 }
 
+// Exclusive Nor: Set rd to the inverse of the bitwise exclusive or of rs1 and rs2.
+//
+// > rd ← ¬(ux(rs1) ⊕ ux(rs2))
+fn exec_xnor<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| !(a ^ b))
+}
+
 // Xor: Set rd to the bitwise xor of rs1 and rs2.
 //
 // > rd ← ux(rs1) ⊻ ux(rs2)
@@ -3357,96 +3901,166 @@ fn exec_xori<Mem: Bus<u32>>(
     })
 }
 
-fn exec_binary_op<Mem: Bus<u32>, F: FnOnce(u32, u32) -> u32>(
+// Zero-Extend Halfword: Set rd to the least significant halfword of rs1, zero-extended.
+//
+// > rd ← u16(rs1)
+fn exec_zext_h<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
+    _inst: Instruction<Op, u32>,
     rd: IntRegister,
     rs1: IntRegister,
-    rs2: IntRegister,
-    callback: F,
 ) -> ExecStatus<u32> {
-    let a = hart.read_int_register(rs1);
-    let b = hart.read_int_register(rs2);
-    let result = callback(a, b);
-    hart.write_int_register(rd, result);
-    ExecStatus::Running
+    exec_unary_op(hart, rd, rs1, |a| a & 0xffff)
 }
 
-fn exec_binary_op_imm<Mem: Bus<u32>, F: FnOnce(u32, i32) -> u32>(
-    hart: &mut impl Hart<u32, u32, f64, Mem>,
-    rd: IntRegister,
-    rs1: IntRegister,
-    imm: i32,
-    callback: F,
-) -> ExecStatus<u32> {
-    let a = hart.read_int_register(rs1);
-    let b = imm;
-    let result = callback(a, b);
-    hart.write_int_register(rd, result);
-    ExecStatus::Running
+// Returns the 64-bit carry-less product of two values, which is the result of
+// long multiplication with exclusive or in place of addition.
+fn carryless_mul(a: u32, b: u32) -> u64 {
+    (0..32)
+        .filter(|i| (b >> i) & 1 != 0)
+        .fold(0, |acc, i| acc ^ ((a as u64) << i))
 }
 
-fn exec_shift_op_imm<Mem: Bus<u32>, F: FnOnce(u32, u32) -> u32>(
-    hart: &mut impl Hart<u32, u32, f64, Mem>,
-    rd: IntRegister,
-    rs1: IntRegister,
-    shamt: u32,
-    callback: F,
-) -> ExecStatus<u32> {
-    let a = hart.read_int_register(rs1);
-    let b = shamt;
-    let result = callback(a, b);
-    hart.write_int_register(rd, result);
-    ExecStatus::Running
-}
-
-fn exec_branch_binary_cond<Mem: Bus<u32>, F: FnOnce(u32, u32) -> bool>(
+// Performs an atomic memory operation on the word at the address in rs1,
+// treating the aq and rl bits as data fences after and before it.
+#[allow(clippy::too_many_arguments)]
+fn exec_amo<Mem: Bus<u32>>(
     hart: &mut impl Hart<u32, u32, f64, Mem>,
     inst: Instruction<Op, u32>,
+    rd: IntRegister,
     rs1: IntRegister,
     rs2: IntRegister,
-    simm: i32,
-    callback: F,
+    aq: bool,
+    rl: bool,
+    op: AtomicOp,
 ) -> ExecStatus<u32> {
-    let a = hart.read_int_register(rs1);
-    let b = hart.read_int_register(rs2);
-    if callback(a, b) {
-        let new_pc = inst.pc.wrapping_add(u32::from_signed(simm).to_unsigned());
-        hart.write_pc(new_pc);
+    let addr = hart.read_int_register(rs1).to_unsigned();
+    if addr % 4 != 0 {
+        // Misaligned atomics can't be emulated by splitting them up.
+        return raise(hart, inst.pc, ExceptionCause::StoreAddressMisaligned, addr);
+    }
+    let operand = hart.read_int_register(rs2).to_unsigned();
+    if rl {
+        hart.fence_data(FenceSet::MEMORY, FenceSet::MEMORY);
+    }
+    match hart.with_memory(|mem| mem.amo_word(addr, op, operand)) {
+        Ok(old) => hart.write_int_register(rd, old),
+        Err(e) => return raise(hart, inst.pc, e.cause(), e.addr as u32),
+    }
+    if aq {
+        hart.fence_data(FenceSet::MEMORY, FenceSet::MEMORY);
     }
     ExecStatus::Running
 }
 
-fn exec_load_mem<Mem: Bus<u32>, F: FnOnce(&mut Mem, u32) -> Result<u32, MemoryError>>(
-    hart: &mut impl Hart<u32, u32, f64, Mem>,
-    rd: IntRegister,
-    rs1: IntRegister,
-    simm: i32,
-    callback: F,
-) -> ExecStatus<u32> {
-    let base_addr = hart.read_int_register(rs1).to_unsigned();
-    let addr = base_addr.wrapping_add(u32::from_signed(simm).to_unsigned());
-    let result = hart.with_memory(|mem| callback(mem, addr));
-    match result {
-        Ok(v) => hart.write_int_register(rd, v),
-        Err(e) => hart.exception(e.as_data_load_cause()),
-    };
-    ExecStatus::Running
-}
+#[cfg(test)]
+mod tests {
+    use super::step_rv32;
+    use crate::exception::ExceptionCause;
+    use crate::exec::ExecStatus;
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::{Extensions, RV32};
+    use crate::memory::{AddressConverter, Bus, Memory};
+    use crate::register::IntRegister;
+
+    // Executes a single instruction with x1 and x2 set to the given values
+    // and returns the status along with the value it left in x3.
+    fn execute(inst: u32, x1: u32, x2: u32, extensions: Extensions) -> (ExecStatus<u32>, u32) {
+        let mut buf = [0u8; 64];
+        let mut hart: SingleThreadUserHart<RV32, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        hart.set_report_exceptions(true);
+        hart.set_extensions(extensions);
+        hart.with_memory(|mem| mem.write_word(0, inst).unwrap());
+        hart.write_int_register(IntRegister::numbered(1), x1);
+        hart.write_int_register(IntRegister::numbered(2), x2);
+        let status = step_rv32(&mut hart);
+        (status, hart.read_int_register(IntRegister::numbered(3)))
+    }
 
-fn exec_store_mem<Mem: Bus<u32>, F: FnOnce(&mut Mem, u32, u32) -> Result<(), MemoryError>>(
-    hart: &mut impl Hart<u32, u32, f64, Mem>,
-    rs1: IntRegister,
-    rs2: IntRegister,
-    simm: i32,
-    callback: F,
-) -> ExecStatus<u32> {
-    let v = hart.read_int_register(rs2);
-    let base_addr = hart.read_int_register(rs1).to_unsigned();
-    let addr = base_addr.wrapping_add(u32::from_signed(simm).to_unsigned());
-    let result = hart.with_memory(|mem| callback(mem, addr, v));
-    match result {
-        Ok(_) => {}
-        Err(e) => hart.exception(e.as_data_store_cause()),
-    };
-    ExecStatus::Running
+    #[test]
+    fn bit_manipulation() {
+        let cases: &[(u32, u32, u32, u32)] = &[
+            (0x4020f1b3, 0x1234_5678, 0xff, 0x1234_5600), // andn x3, x1, x2
+            (0x4020e1b3, 0x1234_5678, 0xff, 0xffff_ff78), // orn x3, x1, x2
+            (0x4020c1b3, 0x1234_5678, 0x8765_4321, 0x6aae_eaa6), // xnor x3, x1, x2
+            (0x60009193, 0x0, 0x0, 0x20),                 // clz x3, x1
+            (0x60009193, 0x1234_5678, 0x0, 0x3),          // clz x3, x1
+            (0x60009193, 0x8765_4321, 0x0, 0x0),          // clz x3, x1
+            (0x60109193, 0x0, 0x0, 0x20),                 // ctz x3, x1
+            (0x60109193, 0x1234_5678, 0x0, 0x3),          // ctz x3, x1
+            (0x60109193, 0x8000_0000, 0x0, 0x1f),         // ctz x3, x1
+            (0x60209193, 0x0, 0x0, 0x0),                  // cpop x3, x1
+            (0x60209193, 0xffff_ffff, 0x0, 0x20),         // cpop x3, x1
+            (0x0a20e1b3, 0xffff_ffff, 0x1, 0x1),          // max x3, x1, x2
+            (0x0a20f1b3, 0xffff_ffff, 0x1, 0xffff_ffff),  // maxu x3, x1, x2
+            (0x0a20c1b3, 0xffff_ffff, 0x1, 0xffff_ffff),  // min x3, x1, x2
+            (0x0a20d1b3, 0xffff_ffff, 0x1, 0x1),          // minu x3, x1, x2
+            (0x60409193, 0x7f, 0x0, 0x7f),                // sext.b x3, x1
+            (0x60409193, 0x180, 0x0, 0xffff_ff80),        // sext.b x3, x1
+            (0x60509193, 0x0001_8000, 0x0, 0xffff_8000),  // sext.h x3, x1
+            (0x0800c1b3, 0x8765_4321, 0x0, 0x4321),       // zext.h x3, x1
+            (0x602091b3, 0x1234_5678, 0x0, 0x1234_5678),  // rol x3, x1, x2
+            (0x602091b3, 0x1234_5678, 0x1f, 0x091a_2b3c), // rol x3, x1, x2
+            (0x602091b3, 0x1234_5678, 0x24, 0x2345_6781), // rol x3, x1, x2
+            (0x6020d1b3, 0x1234_5678, 0x0, 0x1234_5678),  // ror x3, x1, x2
+            (0x6020d1b3, 0x1234_5678, 0x1f, 0x2468_acf0), // ror x3, x1, x2
+            (0x6000d193, 0x1234_5678, 0x0, 0x1234_5678),  // rori x3, x1, 0
+            (0x61f0d193, 0x1234_5678, 0x0, 0x2468_acf0),  // rori x3, x1, 31
+            (0x2870d193, 0x0100_8000, 0x0, 0xff00_ff00),  // orc.b x3, x1
+            (0x6980d193, 0x1234_5678, 0x0, 0x7856_3412),  // rev8 x3, x1
+            (0x0a2091b3, 0x1234_5678, 0x8765_4321, 0x2b42_1178), // clmul x3, x1, x2
+            (0x0a2091b3, 0xffff_ffff, 0xffff_ffff, 0x5555_5555), // clmul x3, x1, x2
+            (0x0a20b1b3, 0x1234_5678, 0x8765_4321, 0x0962_335c), // clmulh x3, x1, x2
+            (0x0a20b1b3, 0xffff_ffff, 0xffff_ffff, 0x5555_5555), // clmulh x3, x1, x2
+            (0x0a20a1b3, 0x1234_5678, 0x8765_4321, 0x12c4_66b8), // clmulr x3, x1, x2
+            (0x0a20a1b3, 0xffff_ffff, 0xffff_ffff, 0xaaaa_aaaa), // clmulr x3, x1, x2
+            (0x482091b3, 0xffff_ffff, 0x1f, 0x7fff_ffff), // bclr x3, x1, x2
+            (0x482091b3, 0xffff_ffff, 0x21, 0xffff_fffd), // bclr x3, x1, x2
+            (0x48409193, 0xffff_ffff, 0x0, 0xffff_ffef),  // bclri x3, x1, 4
+            (0x4820d1b3, 0x8765_4321, 0x1f, 0x1),         // bext x3, x1, x2
+            (0x4820d1b3, 0x8765_4321, 0x23, 0x0),         // bext x3, x1, x2
+            (0x4810d193, 0x8765_4321, 0x0, 0x0),          // bexti x3, x1, 1
+            (0x682091b3, 0x1234_5678, 0x0, 0x1234_5679),  // binv x3, x1, x2
+            (0x682091b3, 0x1234_5678, 0x3f, 0x9234_5678), // binv x3, x1, x2
+            (0x69c09193, 0x1234_5678, 0x0, 0x0234_5678),  // binvi x3, x1, 28
+            (0x282091b3, 0x0, 0x1f, 0x8000_0000),         // bset x3, x1, x2
+            (0x28009193, 0x0, 0x0, 0x1),                  // bseti x3, x1, 0
+            (0x2020a1b3, 0x1234_5678, 0x3, 0x2468_acf3),  // sh1add x3, x1, x2
+            (0x2020a1b3, 0x8000_0001, 0x0, 0x2),          // sh1add x3, x1, x2
+            (0x2020c1b3, 0x1234_5678, 0x3, 0x48d1_59e3),  // sh2add x3, x1, x2
+            (0x2020e1b3, 0x1234_5678, 0x3, 0x91a2_b3c3),  // sh3add x3, x1, x2
+        ];
+        for &(inst, x1, x2, want) in cases {
+            let (status, got) = execute(inst, x1, x2, Extensions::ALL);
+            assert!(matches!(status, ExecStatus::Running), "{:#010x}", inst);
+            assert_eq!(got, want, "{:#010x} with {:#x}, {:#x}", inst, x1, x2);
+        }
+    }
+
+    #[test]
+    fn bit_manipulation_requires_extensions() {
+        let cases = [
+            (0x2020e1b3, Extensions::ZBA), // sh3add x3, x1, x2
+            (0x60009193, Extensions::ZBB), // clz x3, x1
+            (0x0a2091b3, Extensions::ZBC), // clmul x3, x1, x2
+            (0x282091b3, Extensions::ZBS), // bset x3, x1, x2
+        ];
+        for &(inst, extension) in cases.iter() {
+            let (status, got) = execute(inst, 1, 2, Extensions::ALL.without(extension));
+            assert!(
+                matches!(
+                    status,
+                    ExecStatus::Exception {
+                        cause: ExceptionCause::IllegalInstruction,
+                        pc: 0,
+                        tval: 0,
+                    }
+                ),
+                "{:#010x}",
+                inst
+            );
+            assert_eq!(got, 0);
+        }
+    }
 }
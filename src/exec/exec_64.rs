@@ -0,0 +1,1945 @@
+use crate::data::Int;
+use crate::exception::ExceptionCause;
+use crate::exec::ExecStatus;
+use crate::hart::Hart;
+use crate::instruction::Instruction;
+use crate::instruction::OperationRV64;
+use crate::memory::Bus;
+use crate::register::{ControlStatusRegister, IntRegister};
+
+use super::common::{begin_execute, fetch, jump, raise};
+use super::common::{exec_binary_op, exec_binary_op_imm, exec_shift_op_imm, exec_unary_op};
+use super::common::{exec_branch_binary_cond, exec_load_mem, exec_store_mem};
+use super::common::{exec_csrrc, exec_csrrci, exec_csrrs, exec_csrrsi, exec_csrrw, exec_csrrwi};
+use super::common::{exec_ebreak, exec_ecall, exec_fence, exec_fence_i};
+
+type Op = OperationRV64;
+
+/// Performs a single execution step against the given RV64 hart.
+///
+/// An execution step is usually the execution of a single instruction, but
+/// it can also include handling exceptions that are raised in retrieving the
+/// next instruction from memory.
+///
+/// When this function returns, the state of the hart will have been modified
+/// to reflect the side-effects of the action.
+pub fn step_rv64<Mem: Bus<u64>>(hart: &mut impl Hart<u64, u64, f64, Mem>) -> ExecStatus<u64> {
+    let pc = hart.read_pc();
+    match fetch(hart, pc) {
+        Ok(raw_inst) => execute_rv64(hart, Instruction::decode_raw(raw_inst, pc)),
+        Err(e) => raise(hart, pc, e.cause(), e.addr as u64),
+    }
+}
+
+// Executes an instruction that was decoded from the hart's current
+// program counter.
+fn execute_rv64<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+) -> ExecStatus<u64> {
+    if let Err(status) = begin_execute(hart, &inst) {
+        return status;
+    }
+    dispatch_instruction(inst, hart)
+}
+
+// The main instruction dispatch logic for RV64: selects a suitable
+// implementation function based on the specific operation in the instruction.
+fn dispatch_instruction<Mem: Bus<u64>>(
+    inst: Instruction<Op, u64>,
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+) -> ExecStatus<u64> {
+    match inst.op {
+        Op::Add { rd, rs1, rs2 } => exec_add(hart, inst, rd, rs1, rs2),
+        Op::AddUw { rd, rs1, rs2 } => exec_add_uw(hart, inst, rd, rs1, rs2),
+        Op::Addi { rd, rs1, simm } => exec_addi(hart, inst, rd, rs1, simm),
+        Op::Addiw { rd, rs1, simm } => exec_addiw(hart, inst, rd, rs1, simm),
+        Op::Addw { rd, rs1, rs2 } => exec_addw(hart, inst, rd, rs1, rs2),
+        Op::And { rd, rs1, rs2 } => exec_and(hart, inst, rd, rs1, rs2),
+        Op::Andi { rd, rs1, simm } => exec_andi(hart, inst, rd, rs1, simm),
+        Op::Andn { rd, rs1, rs2 } => exec_andn(hart, inst, rd, rs1, rs2),
+        Op::Auipc { rd, simm } => exec_auipc(hart, inst, rd, simm),
+        Op::Bclr { rd, rs1, rs2 } => exec_bclr(hart, inst, rd, rs1, rs2),
+        Op::Bclri { rd, rs1, shamt } => exec_bclri(hart, inst, rd, rs1, shamt),
+        Op::Beq { rs1, rs2, simm } => exec_beq(hart, inst, rs1, rs2, simm),
+        Op::Bext { rd, rs1, rs2 } => exec_bext(hart, inst, rd, rs1, rs2),
+        Op::Bexti { rd, rs1, shamt } => exec_bexti(hart, inst, rd, rs1, shamt),
+        Op::Bge { rs1, rs2, simm } => exec_bge(hart, inst, rs1, rs2, simm),
+        Op::Bgeu { rs1, rs2, simm } => exec_bgeu(hart, inst, rs1, rs2, simm),
+        Op::Binv { rd, rs1, rs2 } => exec_binv(hart, inst, rd, rs1, rs2),
+        Op::Binvi { rd, rs1, shamt } => exec_binvi(hart, inst, rd, rs1, shamt),
+        Op::Blt { rs1, rs2, simm } => exec_blt(hart, inst, rs1, rs2, simm),
+        Op::Bltu { rs1, rs2, simm } => exec_bltu(hart, inst, rs1, rs2, simm),
+        Op::Bne { rs1, rs2, simm } => exec_bne(hart, inst, rs1, rs2, simm),
+        Op::Bset { rd, rs1, rs2 } => exec_bset(hart, inst, rd, rs1, rs2),
+        Op::Bseti { rd, rs1, shamt } => exec_bseti(hart, inst, rd, rs1, shamt),
+        Op::Clmul { rd, rs1, rs2 } => exec_clmul(hart, inst, rd, rs1, rs2),
+        Op::Clmulh { rd, rs1, rs2 } => exec_clmulh(hart, inst, rd, rs1, rs2),
+        Op::Clmulr { rd, rs1, rs2 } => exec_clmulr(hart, inst, rd, rs1, rs2),
+        Op::Clz { rd, rs1 } => exec_clz(hart, inst, rd, rs1),
+        Op::Clzw { rd, rs1 } => exec_clzw(hart, inst, rd, rs1),
+        Op::Cpop { rd, rs1 } => exec_cpop(hart, inst, rd, rs1),
+        Op::Cpopw { rd, rs1 } => exec_cpopw(hart, inst, rd, rs1),
+        Op::Csrrc { rd, rs1, csr } => exec_csrrc(hart, inst, rd, rs1, csr),
+        Op::Csrrci { rd, uimm, csr } => exec_csrrci(hart, inst, rd, uimm, csr),
+        Op::Csrrs { rd, rs1, csr } => exec_csrrs(hart, inst, rd, rs1, csr),
+        Op::Csrrsi { rd, uimm, csr } => exec_csrrsi(hart, inst, rd, uimm, csr),
+        Op::Csrrw { rd, rs1, csr } => exec_csrrw(hart, inst, rd, rs1, csr),
+        Op::Csrrwi { rd, uimm, csr } => exec_csrrwi(hart, inst, rd, uimm, csr),
+        Op::Ctz { rd, rs1 } => exec_ctz(hart, inst, rd, rs1),
+        Op::Ctzw { rd, rs1 } => exec_ctzw(hart, inst, rd, rs1),
+        Op::Div { rd, rs1, rs2 } => exec_div(hart, inst, rd, rs1, rs2),
+        Op::Divu { rd, rs1, rs2 } => exec_divu(hart, inst, rd, rs1, rs2),
+        Op::Divuw { rd, rs1, rs2 } => exec_divuw(hart, inst, rd, rs1, rs2),
+        Op::Divw { rd, rs1, rs2 } => exec_divw(hart, inst, rd, rs1, rs2),
+        Op::Ebreak => exec_ebreak(hart, inst),
+        Op::Ecall => exec_ecall(hart, inst),
+        Op::Fence { pred, succ } => exec_fence(hart, inst, pred, succ),
+        Op::FenceI => exec_fence_i(hart, inst),
+        Op::Jal { rd, simm } => exec_jal(hart, inst, rd, simm),
+        Op::Jalr { rd, rs1, simm } => exec_jalr(hart, inst, rd, rs1, simm),
+        Op::Lb { rd, rs1, simm } => exec_lb(hart, inst, rd, rs1, simm),
+        Op::Lbu { rd, rs1, simm } => exec_lbu(hart, inst, rd, rs1, simm),
+        Op::Ld { rd, rs1, simm } => exec_ld(hart, inst, rd, rs1, simm),
+        Op::Lh { rd, rs1, simm } => exec_lh(hart, inst, rd, rs1, simm),
+        Op::Lhu { rd, rs1, simm } => exec_lhu(hart, inst, rd, rs1, simm),
+        Op::Lui { rd, simm } => exec_lui(hart, inst, rd, simm),
+        Op::Lw { rd, rs1, simm } => exec_lw(hart, inst, rd, rs1, simm),
+        Op::Lwu { rd, rs1, simm } => exec_lwu(hart, inst, rd, rs1, simm),
+        Op::Max { rd, rs1, rs2 } => exec_max(hart, inst, rd, rs1, rs2),
+        Op::Maxu { rd, rs1, rs2 } => exec_maxu(hart, inst, rd, rs1, rs2),
+        Op::Min { rd, rs1, rs2 } => exec_min(hart, inst, rd, rs1, rs2),
+        Op::Minu { rd, rs1, rs2 } => exec_minu(hart, inst, rd, rs1, rs2),
+        Op::Mul { rd, rs1, rs2 } => exec_mul(hart, inst, rd, rs1, rs2),
+        Op::Mulh { rd, rs1, rs2 } => exec_mulh(hart, inst, rd, rs1, rs2),
+        Op::Mulhsu { rd, rs1, rs2 } => exec_mulhsu(hart, inst, rd, rs1, rs2),
+        Op::Mulhu { rd, rs1, rs2 } => exec_mulhu(hart, inst, rd, rs1, rs2),
+        Op::Mulw { rd, rs1, rs2 } => exec_mulw(hart, inst, rd, rs1, rs2),
+        Op::Or { rd, rs1, rs2 } => exec_or(hart, inst, rd, rs1, rs2),
+        Op::OrcB { rd, rs1 } => exec_orc_b(hart, inst, rd, rs1),
+        Op::Ori { rd, rs1, simm } => exec_ori(hart, inst, rd, rs1, simm),
+        Op::Orn { rd, rs1, rs2 } => exec_orn(hart, inst, rd, rs1, rs2),
+        Op::Rem { rd, rs1, rs2 } => exec_rem(hart, inst, rd, rs1, rs2),
+        Op::Remu { rd, rs1, rs2 } => exec_remu(hart, inst, rd, rs1, rs2),
+        Op::Remuw { rd, rs1, rs2 } => exec_remuw(hart, inst, rd, rs1, rs2),
+        Op::Remw { rd, rs1, rs2 } => exec_remw(hart, inst, rd, rs1, rs2),
+        Op::Rev8 { rd, rs1 } => exec_rev8(hart, inst, rd, rs1),
+        Op::Rol { rd, rs1, rs2 } => exec_rol(hart, inst, rd, rs1, rs2),
+        Op::Rolw { rd, rs1, rs2 } => exec_rolw(hart, inst, rd, rs1, rs2),
+        Op::Ror { rd, rs1, rs2 } => exec_ror(hart, inst, rd, rs1, rs2),
+        Op::Rori { rd, rs1, shamt } => exec_rori(hart, inst, rd, rs1, shamt),
+        Op::Roriw { rd, rs1, shamt } => exec_roriw(hart, inst, rd, rs1, shamt),
+        Op::Rorw { rd, rs1, rs2 } => exec_rorw(hart, inst, rd, rs1, rs2),
+        Op::Sb { rs1, rs2, simm } => exec_sb(hart, inst, rs1, rs2, simm),
+        Op::Sd { rs1, rs2, simm } => exec_sd(hart, inst, rs1, rs2, simm),
+        Op::SextB { rd, rs1 } => exec_sext_b(hart, inst, rd, rs1),
+        Op::SextH { rd, rs1 } => exec_sext_h(hart, inst, rd, rs1),
+        Op::Sh { rs1, rs2, simm } => exec_sh(hart, inst, rs1, rs2, simm),
+        Op::Sh1add { rd, rs1, rs2 } => exec_sh1add(hart, inst, rd, rs1, rs2),
+        Op::Sh1addUw { rd, rs1, rs2 } => exec_sh1add_uw(hart, inst, rd, rs1, rs2),
+        Op::Sh2add { rd, rs1, rs2 } => exec_sh2add(hart, inst, rd, rs1, rs2),
+        Op::Sh2addUw { rd, rs1, rs2 } => exec_sh2add_uw(hart, inst, rd, rs1, rs2),
+        Op::Sh3add { rd, rs1, rs2 } => exec_sh3add(hart, inst, rd, rs1, rs2),
+        Op::Sh3addUw { rd, rs1, rs2 } => exec_sh3add_uw(hart, inst, rd, rs1, rs2),
+        Op::Sll { rd, rs1, rs2 } => exec_sll(hart, inst, rd, rs1, rs2),
+        Op::Slli { rd, rs1, shamt } => exec_slli(hart, inst, rd, rs1, shamt),
+        Op::SlliUw { rd, rs1, shamt } => exec_slli_uw(hart, inst, rd, rs1, shamt),
+        Op::Slliw { rd, rs1, shamt } => exec_slliw(hart, inst, rd, rs1, shamt),
+        Op::Sllw { rd, rs1, rs2 } => exec_sllw(hart, inst, rd, rs1, rs2),
+        Op::Slt { rd, rs1, rs2 } => exec_slt(hart, inst, rd, rs1, rs2),
+        Op::Slti { rd, rs1, simm } => exec_slti(hart, inst, rd, rs1, simm),
+        Op::Sltiu { rd, rs1, simm } => exec_sltiu(hart, inst, rd, rs1, simm),
+        Op::Sltu { rd, rs1, rs2 } => exec_sltu(hart, inst, rd, rs1, rs2),
+        Op::Sra { rd, rs1, rs2 } => exec_sra(hart, inst, rd, rs1, rs2),
+        Op::Srai { rd, rs1, shamt } => exec_srai(hart, inst, rd, rs1, shamt),
+        Op::Sraiw { rd, rs1, shamt } => exec_sraiw(hart, inst, rd, rs1, shamt),
+        Op::Sraw { rd, rs1, rs2 } => exec_sraw(hart, inst, rd, rs1, rs2),
+        Op::Srl { rd, rs1, rs2 } => exec_srl(hart, inst, rd, rs1, rs2),
+        Op::Srli { rd, rs1, shamt } => exec_srli(hart, inst, rd, rs1, shamt),
+        Op::Srliw { rd, rs1, shamt } => exec_srliw(hart, inst, rd, rs1, shamt),
+        Op::Srlw { rd, rs1, rs2 } => exec_srlw(hart, inst, rd, rs1, rs2),
+        Op::Sub { rd, rs1, rs2 } => exec_sub(hart, inst, rd, rs1, rs2),
+        Op::Subw { rd, rs1, rs2 } => exec_subw(hart, inst, rd, rs1, rs2),
+        Op::Sw { rs1, rs2, simm } => exec_sw(hart, inst, rs1, rs2, simm),
+        Op::Uret => exec_uret(hart, inst),
+        Op::Wfi => exec_wfi(hart, inst),
+        Op::Xnor { rd, rs1, rs2 } => exec_xnor(hart, inst, rd, rs1, rs2),
+        Op::Xor { rd, rs1, rs2 } => exec_xor(hart, inst, rd, rs1, rs2),
+        Op::Xori { rd, rs1, simm } => exec_xori(hart, inst, rd, rs1, simm),
+        Op::ZextH { rd, rs1 } => exec_zext_h(hart, inst, rd, rs1),
+        _ => raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0),
+    }
+}
+
+// Add: Add rs2 to rs1 and place the result into rd.
+//
+// > rd ← sx(rs1) + sx(rs2)
+fn exec_add<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a.wrapping_add(b))
+}
+
+// Add Unsigned Word: Add rs2 to the zero-extended low 32 bits of rs1 and place the result in rd.
+//
+// > rd ← ux(rs2) + u32(rs1)
+fn exec_add_uw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| b.wrapping_add(a & 0xffff_ffff))
+}
+
+// Add Immediate: Add sign-extended 12-bit immediate to register rs1 and place the result in rd.
+//
+// > rd ← rs1 + sx(imm)
+fn exec_addi<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| {
+        a.wrapping_add(u64::from_signed_word(b))
+    })
+}
+
+// Add Immediate Word: Add sign-extended 12-bit immediate to 32-bit register rs1 and place the sign-extended result in rd.
+//
+// > rd ← s32(rs1) + sx(imm)
+fn exec_addiw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| {
+        u64::from_signed_word(a.to_signed_word().wrapping_add(b))
+    })
+}
+
+// Add Word: Add 32-bit rs2 to 32-bit rs1 and place the sign-extended result into rd.
+//
+// > rd ← s32(rs1) + s32(rs2)
+fn exec_addw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u64::from_signed_word(a.to_signed_word().wrapping_add(b.to_signed_word()))
+    })
+}
+
+// And: Set rd to the bitwise and of rs1 and rs2.
+//
+// > rd ← ux(rs1) ∧ ux(rs2)
+fn exec_and<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a & b)
+}
+
+// And Immediate: Set rd to the bitwise and of rs1 with the sign-extended 12-bit immediate.
+//
+// > rd ← ux(rs1) ∧ ux(imm)
+fn exec_andi<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| a & u64::from_signed_word(b))
+}
+
+// And Inverted: Set rd to the bitwise and of rs1 and the inverse of rs2.
+//
+// > rd ← ux(rs1) ∧ ¬ux(rs2)
+fn exec_andn<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a & !b)
+}
+
+// Add Upper Immediate to PC: Place the PC plus the 20-bit signed immediate (shited 12 bits left) into rd (used before JALR).
+//
+// > rd ← pc + imm
+fn exec_auipc<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    let result = inst.pc.wrapping_add(u64::from_signed_word(simm));
+    hart.write_int_register(rd, result);
+    ExecStatus::Running
+}
+
+// Single-Bit Clear: Set rd to rs1 with the bit indexed by the lower 6 bits of rs2 cleared.
+//
+// > rd ← ux(rs1) ∧ ¬(1 « (rs2 ∧ 63))
+fn exec_bclr<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a & !(1 << (b & 0b111111)))
+}
+
+// Single-Bit Clear Immediate: Set rd to rs1 with the bit indexed by the immediate cleared.
+//
+// > rd ← ux(rs1) ∧ ¬(1 « ux(imm))
+fn exec_bclri<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u64> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| a & !(1 << shamt))
+}
+
+// Branch Equal: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 == rs2.
+//
+// > if rs1 = rs2 then pc ← pc + imm
+fn exec_beq<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| a == b)
+}
+
+// Single-Bit Extract: Set rd to the bit of rs1 indexed by the lower 6 bits of rs2.
+//
+// > rd ← (ux(rs1) » (rs2 ∧ 63)) ∧ 1
+fn exec_bext<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| (a >> (b & 0b111111)) & 1)
+}
+
+// Single-Bit Extract Immediate: Set rd to the bit of rs1 indexed by the immediate.
+//
+// > rd ← (ux(rs1) » ux(imm)) ∧ 1
+fn exec_bexti<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u64> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| (a >> shamt) & 1)
+}
+
+// Branch Greater than Equal: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 >= rs2 (signed).
+//
+// > if rs1 ≥ rs2 then pc ← pc + imm
+fn exec_bge<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| {
+        a.to_signed() >= b.to_signed()
+    })
+}
+
+// Branch Greater than Equal Unsigned: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 >= rs2 (unsigned).
+//
+// > if rs1 ≥ rs2 then pc ← pc + imm
+fn exec_bgeu<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| a >= b)
+}
+
+// Single-Bit Invert: Set rd to rs1 with the bit indexed by the lower 6 bits of rs2 inverted.
+//
+// > rd ← ux(rs1) ⊕ (1 « (rs2 ∧ 63))
+fn exec_binv<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a ^ (1 << (b & 0b111111)))
+}
+
+// Single-Bit Invert Immediate: Set rd to rs1 with the bit indexed by the immediate inverted.
+//
+// > rd ← ux(rs1) ⊕ (1 « ux(imm))
+fn exec_binvi<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u64> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| a ^ (1 << shamt))
+}
+
+// Branch Less Than: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 < rs2 (signed).
+//
+// > if rs1 < rs2 then pc ← pc + imm
+fn exec_blt<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| {
+        a.to_signed() < b.to_signed()
+    })
+}
+
+// Branch Less Than Unsigned: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 < rs2 (unsigned).
+//
+// > if rs1 < rs2 then pc ← pc + imm
+fn exec_bltu<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| a < b)
+}
+
+// Branch Not Equal: Branch to PC relative 12-bit signed immediate (shifted 1 bit left) if rs1 != rs2.
+//
+// > if rs1 ≠ rs2 then pc ← pc + imm
+fn exec_bne<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_branch_binary_cond(hart, inst, rs1, rs2, simm, |a, b| a != b)
+}
+
+// Single-Bit Set: Set rd to rs1 with the bit indexed by the lower 6 bits of rs2 set.
+//
+// > rd ← ux(rs1) ∨ (1 « (rs2 ∧ 63))
+fn exec_bset<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a | (1 << (b & 0b111111)))
+}
+
+// Single-Bit Set Immediate: Set rd to rs1 with the bit indexed by the immediate set.
+//
+// > rd ← ux(rs1) ∨ (1 « ux(imm))
+fn exec_bseti<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u64> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| a | (1 << shamt))
+}
+
+// Carry-less Multiply: Set rd to the lower half of the carry-less product of rs1 and rs2.
+//
+// > rd ← ux(rs1) ⊗ ux(rs2)
+fn exec_clmul<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| carryless_mul(a, b) as u64)
+}
+
+// Carry-less Multiply High: Set rd to the upper half of the carry-less product of rs1 and rs2.
+//
+// > rd ← (ux(rs1) ⊗ ux(rs2)) » 64
+fn exec_clmulh<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        (carryless_mul(a, b) >> 64) as u64
+    })
+}
+
+// Carry-less Multiply Reversed: Set rd to bits 126 to 63 of the carry-less product of rs1 and rs2.
+//
+// > rd ← (ux(rs1) ⊗ ux(rs2)) » 63
+fn exec_clmulr<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        (carryless_mul(a, b) >> 63) as u64
+    })
+}
+
+// Count Leading Zeros: Set rd to the number of 0 bits before the most significant 1 bit in rs1.
+//
+// > rd ← clz(ux(rs1))
+fn exec_clz<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u64> {
+    exec_unary_op(hart, rd, rs1, |a| a.leading_zeros() as u64)
+}
+
+// Count Leading Zeros Word: Set rd to the number of 0 bits before the most significant 1 bit in the low 32 bits of rs1.
+//
+// > rd ← clz(u32(rs1))
+fn exec_clzw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u64> {
+    exec_unary_op(hart, rd, rs1, |a| {
+        a.to_unsigned_word().leading_zeros() as u64
+    })
+}
+
+// Count Set Bits: Set rd to the number of 1 bits in rs1.
+//
+// > rd ← popcount(ux(rs1))
+fn exec_cpop<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u64> {
+    exec_unary_op(hart, rd, rs1, |a| a.count_ones() as u64)
+}
+
+// Count Set Bits Word: Set rd to the number of 1 bits in the low 32 bits of rs1.
+//
+// > rd ← popcount(u32(rs1))
+fn exec_cpopw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u64> {
+    exec_unary_op(hart, rd, rs1, |a| a.to_unsigned_word().count_ones() as u64)
+}
+
+// Count Trailing Zeros: Set rd to the number of 0 bits after the least significant 1 bit in rs1.
+//
+// > rd ← ctz(ux(rs1))
+fn exec_ctz<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u64> {
+    exec_unary_op(hart, rd, rs1, |a| a.trailing_zeros() as u64)
+}
+
+// Count Trailing Zeros Word: Set rd to the number of 0 bits after the least significant 1 bit in the low 32 bits of rs1.
+//
+// > rd ← ctz(u32(rs1))
+fn exec_ctzw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u64> {
+    exec_unary_op(hart, rd, rs1, |a| {
+        a.to_unsigned_word().trailing_zeros() as u64
+    })
+}
+
+// Divide Signed: Divide rs1 (dividend) by rs2 (divisor) and place the quotient in rd (signed).
+//
+// > rd ← sx(rs1) ÷ sx(rs2)
+fn exec_div<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        let aw = a.to_signed();
+        let bw = b.to_signed();
+        if bw == 0 {
+            // Division by zero produces an all-ones result
+            return u64::MAX;
+        }
+        // The most negative value divided by -1 overflows back to itself.
+        u64::from_signed(aw.wrapping_div(bw))
+    })
+}
+
+// Divide Unsigned: Divide rs1 (dividend) by rs2 (divisor) and place the quotient in rd (unsigned).
+//
+// > rd ← ux(rs1) ÷ ux(rs2)
+fn exec_divu<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b {
+        // Division by zero produces all-ones
+        0 => u64::MAX,
+        _ => a / b,
+    })
+}
+
+// Divide Unsigned Word: Divide 32-bit rs1 (dividend) by 32-bit rs2 (divisor) and place the sign-extended quotient in rd (unsigned).
+//
+// > rd ← u32(rs1) ÷ u32(rs2)
+fn exec_divuw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b.to_unsigned_word() {
+        0 => u64::MAX,
+        bw => u64::from_unsigned_word(a.to_unsigned_word() / bw),
+    })
+}
+
+// Divide Signed Word: Divide 32-bit rs1 (dividend) by 32-bit rs2 (divisor) and place the sign-extended quotient in rd (signed).
+//
+// > rd ← s32(rs1) ÷ s32(rs2)
+fn exec_divw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b.to_signed_word() {
+        0 => u64::MAX,
+        bw => u64::from_signed_word(a.to_signed_word().wrapping_div(bw)),
+    })
+}
+
+// Jump and Link: Jump to the PC plus 20-bit signed immediate while saving PC+4 into rd.
+//
+// > rd ← pc + length(inst) ; pc ← pc + imm
+fn exec_jal<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    let new_pc = inst.pc.wrapping_add(u64::from_signed_word(simm));
+    if let Err(status) = jump(hart, inst.pc, new_pc) {
+        return status;
+    }
+    let ret_pc = inst.pc.wrapping_add(inst.length as u64);
+    hart.write_int_register(rd, ret_pc);
+    ExecStatus::Running
+}
+
+// Jump and Link Register: Jump to rs1 plus the 12-bit signed immediate while saving PC+4 into rd.
+//
+// > rd ← pc + length(inst) ; pc ← (rs1 + imm) ∧ -2
+fn exec_jalr<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    let base_addr = hart.read_int_register(rs1);
+    let new_pc = base_addr.wrapping_add(u64::from_signed_word(simm)) & !1;
+    if let Err(status) = jump(hart, inst.pc, new_pc) {
+        return status;
+    }
+    let ret_pc = inst.pc.wrapping_add(inst.length as u64);
+    hart.write_int_register(rd, ret_pc);
+    ExecStatus::Running
+}
+
+// Load Byte: Load 8-bit value from addr in rs1 plus the 12-bit signed immediate and place sign-extended result into rd.
+//
+// > rd ← s8[rs1 + imm]
+fn exec_lb<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_byte(addr)?;
+        Ok(u64::from_signed(v as i8 as i64))
+    })
+}
+
+// Load Byte Unsigned: Load 8-bit value from addr in rs1 plus the 12-bit signed immediate and place zero-extended result into rd.
+//
+// > rd ← u8[rs1 + imm]
+fn exec_lbu<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_byte(addr)?;
+        Ok(v as u64)
+    })
+}
+
+// Load Double: Load 64-bit value from addr in rs1 plus the 12-bit signed immediate and place the result into rd.
+//
+// > rd ← u64[rs1 + imm]
+fn exec_ld<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        mem.read_longword(addr)
+    })
+}
+
+// Load Half: Load 16-bit value from addr in rs1 plus the 12-bit signed immediate and place sign-extended result into rd.
+//
+// > rd ← s16[rs1 + imm]
+fn exec_lh<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_halfword(addr)?;
+        Ok(u64::from_signed(v as i16 as i64))
+    })
+}
+
+// Load Half Unsigned: Load 16-bit value from addr in rs1 plus the 12-bit signed immediate and place zero-extended result into rd.
+//
+// > rd ← u16[rs1 + imm]
+fn exec_lhu<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_halfword(addr)?;
+        Ok(v as u64)
+    })
+}
+
+// Load Upper Immediate: Set and sign extend the 20-bit immediate (shited 12 bits left) and zero the bottom 12 bits into rd.
+//
+// > rd ← imm
+fn exec_lui<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    hart.write_int_register(rd, u64::from_signed_word(simm));
+    ExecStatus::Running
+}
+
+// Load Word: Load 32-bit value from addr in rs1 plus the 12-bit signed immediate and place sign-extended result into rd.
+//
+// > rd ← s32[rs1 + imm]
+fn exec_lw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_word(addr)?;
+        Ok(u64::from_unsigned_word(v))
+    })
+}
+
+// Load Word Unsigned: Load 32-bit value from addr in rs1 plus the 12-bit signed immediate and place zero-extended result into rd.
+//
+// > rd ← u32[rs1 + imm]
+fn exec_lwu<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_load_mem(hart, inst, rd, rs1, simm, |mem, addr| {
+        let v = mem.read_word(addr)?;
+        Ok(v as u64)
+    })
+}
+
+// Maximum: Set rd to the larger of rs1 and rs2 (signed).
+//
+// > rd ← max(sx(rs1), sx(rs2))
+fn exec_max<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u64::from_signed(a.to_signed().max(b.to_signed()))
+    })
+}
+
+// Maximum Unsigned: Set rd to the larger of rs1 and rs2 (unsigned).
+//
+// > rd ← max(ux(rs1), ux(rs2))
+fn exec_maxu<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a.max(b))
+}
+
+// Minimum: Set rd to the smaller of rs1 and rs2 (signed).
+//
+// > rd ← min(sx(rs1), sx(rs2))
+fn exec_min<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u64::from_signed(a.to_signed().min(b.to_signed()))
+    })
+}
+
+// Minimum Unsigned: Set rd to the smaller of rs1 and rs2 (unsigned).
+//
+// > rd ← min(ux(rs1), ux(rs2))
+fn exec_minu<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a.min(b))
+}
+
+// Multiply: Multiply rs1 by rs2 and place the result in rd.
+//
+// > rd ← ux(rs1) × ux(rs2)
+fn exec_mul<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a.wrapping_mul(b))
+}
+
+// Multiply High Signed Signed: Multiply signed rs1 by signed rs2 and place the high bits of the result in rd.
+//
+// > rd ← (sx(rs1) × sx(rs2)) » xlen
+fn exec_mulh<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        ((a.to_signed() as i128 * b.to_signed() as i128) >> 64) as u64
+    })
+}
+
+// Multiply High Signed Unsigned: Multiply signed rs1 by unsigned rs2 and place the high bits of the result in rd.
+//
+// > rd ← (sx(rs1) × ux(rs2)) » xlen
+fn exec_mulhsu<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        ((a.to_signed() as i128).wrapping_mul(b as i128) >> 64) as u64
+    })
+}
+
+// Multiply High Unsigned Unsigned: Multiply unsigned rs1 by unsigned rs2 and place the high bits of the result in rd.
+//
+// > rd ← (ux(rs1) × ux(rs2)) » xlen
+fn exec_mulhu<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        ((a as u128 * b as u128) >> 64) as u64
+    })
+}
+
+// Multiply Word: Multiply 32-bit rs1 by 32-bit rs2 and place the sign-extended result in rd.
+//
+// > rd ← u32(rs1) × u32(rs2)
+fn exec_mulw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u64::from_signed_word(a.to_signed_word().wrapping_mul(b.to_signed_word()))
+    })
+}
+
+// Or: Set rd to the bitwise or of rs1 and rs2.
+//
+// > rd ← ux(rs1) ∨ ux(rs2)
+fn exec_or<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a | b)
+}
+
+// Bitwise Or-Combine Byte: Set each byte of rd to all ones if the corresponding byte of rs1 is nonzero, or to zero otherwise.
+//
+// > rd ← orc.b(ux(rs1))
+fn exec_orc_b<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u64> {
+    exec_unary_op(hart, rd, rs1, |a| {
+        u64::from_le_bytes(a.to_le_bytes().map(|b| if b == 0 { 0 } else { 0xff }))
+    })
+}
+
+// Or Immediate: Set rd to the bitwise or of rs1 with the sign-extended 12-bit immediate.
+//
+// > rd ← ux(rs1) ∨ ux(imm)
+fn exec_ori<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| a | u64::from_signed_word(b))
+}
+
+// Or Inverted: Set rd to the bitwise or of rs1 and the inverse of rs2.
+//
+// > rd ← ux(rs1) ∨ ¬ux(rs2)
+fn exec_orn<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a | !b)
+}
+
+// Remainder Signed: Divide rs1 (dividend) by rs2 (divisor) and place the remainder in rd (signed).
+//
+// > rd ← sx(rs1) mod sx(rs2)
+fn exec_rem<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b.to_signed() {
+        // Division by zero produces the first operand
+        0 => a,
+        // The most negative value divided by -1 overflows, producing zero.
+        bw => u64::from_signed(a.to_signed().wrapping_rem(bw)),
+    })
+}
+
+// Remainder Unsigned: Divide rs1 (dividend) by rs2 (divisor) and place the remainder in rd (unsigned).
+//
+// > rd ← ux(rs1) mod ux(rs2)
+fn exec_remu<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b {
+        // Division by zero produces the first operand
+        0 => a,
+        _ => a % b,
+    })
+}
+
+// Remainder Unsigned Word: Divide 32-bit rs1 (dividend) by 32-bit rs2 (divisor) and place the sign-extended remainder in rd (unsigned).
+//
+// > rd ← u32(rs1) mod u32(rs2)
+fn exec_remuw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b.to_unsigned_word() {
+        0 => u64::from_unsigned_word(a.to_unsigned_word()),
+        bw => u64::from_unsigned_word(a.to_unsigned_word() % bw),
+    })
+}
+
+// Remainder Signed Word: Divide 32-bit rs1 (dividend) by 32-bit rs2 (divisor) and place the sign-extended remainder in rd (signed).
+//
+// > rd ← s32(rs1) mod s32(rs2)
+fn exec_remw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| match b.to_signed_word() {
+        0 => u64::from_signed_word(a.to_signed_word()),
+        bw => u64::from_signed_word(a.to_signed_word().wrapping_rem(bw)),
+    })
+}
+
+// Byte Reverse: Set rd to rs1 with the order of its bytes reversed.
+//
+// > rd ← rev8(ux(rs1))
+fn exec_rev8<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u64> {
+    exec_unary_op(hart, rd, rs1, u64::swap_bytes)
+}
+
+// Rotate Left: Rotate rs1 left by the lower 6 bits of rs2 and place the result into rd.
+//
+// > rd ← ux(rs1) «« rs2
+fn exec_rol<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        a.rotate_left((b & 0b111111) as u32)
+    })
+}
+
+// Rotate Left Word: Rotate the low 32 bits of rs1 left by the lower 5 bits of rs2 and place the sign-extended result in rd.
+//
+// > rd ← s32(u32(rs1) «« rs2)
+fn exec_rolw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u64::from_unsigned_word(
+            a.to_unsigned_word()
+                .rotate_left(b.to_unsigned_word() & 0b11111),
+        )
+    })
+}
+
+// Rotate Right: Rotate rs1 right by the lower 6 bits of rs2 and place the result into rd.
+//
+// > rd ← ux(rs1) »» rs2
+fn exec_ror<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        a.rotate_right((b & 0b111111) as u32)
+    })
+}
+
+// Rotate Right Immediate: Rotate rs1 right by the immediate and place the result into rd.
+//
+// > rd ← ux(rs1) »» ux(imm)
+fn exec_rori<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u64> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, u64::rotate_right)
+}
+
+// Rotate Right Immediate Word: Rotate the low 32 bits of rs1 right by the 5 bit immediate and place the sign-extended result in rd.
+//
+// > rd ← s32(u32(rs1) »» ux(imm))
+fn exec_roriw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u64> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| {
+        u64::from_unsigned_word(a.to_unsigned_word().rotate_right(shamt))
+    })
+}
+
+// Rotate Right Word: Rotate the low 32 bits of rs1 right by the lower 5 bits of rs2 and place the sign-extended result in rd.
+//
+// > rd ← s32(u32(rs1) »» rs2)
+fn exec_rorw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u64::from_unsigned_word(
+            a.to_unsigned_word()
+                .rotate_right(b.to_unsigned_word() & 0b11111),
+        )
+    })
+}
+
+// Store Byte: Store 8-bit value from the low bits of rs2 to addr in rs1 plus the 12-bit signed immediate.
+//
+// > u8[rs1 + imm] ← rs2
+fn exec_sb<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_store_mem(hart, inst, rs1, rs2, simm, |mem, addr, v| {
+        mem.write_byte(addr, v as u8)
+    })
+}
+
+// Store Double: Store 64-bit value from the low bits of rs2 to addr in rs1 plus the 12-bit signed immediate.
+//
+// > u64[rs1 + imm] ← rs2
+fn exec_sd<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_store_mem(hart, inst, rs1, rs2, simm, |mem, addr, v| {
+        mem.write_longword(addr, v)
+    })
+}
+
+// Sign-Extend Byte: Set rd to the least significant byte of rs1, sign-extended.
+//
+// > rd ← s8(rs1)
+fn exec_sext_b<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u64> {
+    exec_unary_op(hart, rd, rs1, |a| a as i8 as u64)
+}
+
+// Sign-Extend Halfword: Set rd to the least significant halfword of rs1, sign-extended.
+//
+// > rd ← s16(rs1)
+fn exec_sext_h<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u64> {
+    exec_unary_op(hart, rd, rs1, |a| a as i16 as u64)
+}
+
+// Store Half: Store 16-bit value from the low bits of rs2 to addr in rs1 plus the 12-bit signed immediate.
+//
+// > u16[rs1 + imm] ← rs2
+fn exec_sh<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_store_mem(hart, inst, rs1, rs2, simm, |mem, addr, v| {
+        mem.write_halfword(addr, v as u16)
+    })
+}
+
+// Shift Left by 1 and Add: Shift rs1 left by 1 bit, add rs2 and place the result into rd.
+//
+// > rd ← ux(rs2) + (ux(rs1) « 1)
+fn exec_sh1add<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| b.wrapping_add(a << 1))
+}
+
+// Shift Unsigned Word Left by 1 and Add: Shift the zero-extended low 32 bits of rs1 left by 1 bit, add it to rs2 and place the result in rd.
+//
+// > rd ← ux(rs2) + (u32(rs1) « 1)
+fn exec_sh1add_uw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        b.wrapping_add((a & 0xffff_ffff) << 1)
+    })
+}
+
+// Shift Left by 2 and Add: Shift rs1 left by 2 bits, add rs2 and place the result into rd.
+//
+// > rd ← ux(rs2) + (ux(rs1) « 2)
+fn exec_sh2add<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| b.wrapping_add(a << 2))
+}
+
+// Shift Unsigned Word Left by 2 and Add: Shift the zero-extended low 32 bits of rs1 left by 2 bits, add it to rs2 and place the result in rd.
+//
+// > rd ← ux(rs2) + (u32(rs1) « 2)
+fn exec_sh2add_uw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        b.wrapping_add((a & 0xffff_ffff) << 2)
+    })
+}
+
+// Shift Left by 3 and Add: Shift rs1 left by 3 bits, add rs2 and place the result into rd.
+//
+// > rd ← ux(rs2) + (ux(rs1) « 3)
+fn exec_sh3add<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| b.wrapping_add(a << 3))
+}
+
+// Shift Unsigned Word Left by 3 and Add: Shift the zero-extended low 32 bits of rs1 left by 3 bits, add it to rs2 and place the result in rd.
+//
+// > rd ← ux(rs2) + (u32(rs1) « 3)
+fn exec_sh3add_uw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        b.wrapping_add((a & 0xffff_ffff) << 3)
+    })
+}
+
+// Shift Left Logical: Shift rs1 left by the by the lower 6 bits in rs2 and place the result into rd.
+//
+// > rd ← ux(rs1) « rs2
+fn exec_sll<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a << (b & 0b111111))
+}
+
+// Shift Left Logical Immediate: Shift rs1 left by the 6 bit immediate and place the result into rd.
+//
+// > rd ← ux(rs1) « ux(imm)
+fn exec_slli<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u64> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| a << shamt)
+}
+
+// Shift Left Logical Immediate Unsigned Word: Shift the zero-extended low 32 bits of rs1 left by the 6 bit immediate and place the result in rd.
+//
+// > rd ← u32(rs1) « ux(imm)
+fn exec_slli_uw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u64> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| (a & 0xffff_ffff) << shamt)
+}
+
+// Shift Left Logical Immediate Word: Shift 32-bit rs1 left by the 5 bit immediate and place the sign-extended result into rd.
+//
+// > rd ← s32(u32(rs1) « ux(imm))
+fn exec_slliw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u64> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| {
+        u64::from_unsigned_word(a.to_unsigned_word() << shamt)
+    })
+}
+
+// Shift Left Logical Word: Shift 32-bit rs1 left by the lower 5 bits in rs2 and place the sign-extended result into rd.
+//
+// > rd ← s32(u32(rs1) « rs2)
+fn exec_sllw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u64::from_unsigned_word(a.to_unsigned_word() << (b & 0b11111))
+    })
+}
+
+// Set Less Than: Set rd to 1 if rs1 is less than rs2, otherwise set rd to 0 (signed).
+//
+// > rd ← sx(rs1) < sx(rs2)
+fn exec_slt<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        (a.to_signed() < b.to_signed()) as u64
+    })
+}
+
+// Set Less Than Immediate: Set rd to 1 if rs1 is less than the sign-extended 12-bit immediate, otherwise set rd to 0 (signed).
+//
+// > rd ← sx(rs1) < sx(imm)
+fn exec_slti<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| {
+        (a.to_signed() < b as i64) as u64
+    })
+}
+
+// Set Less Than Immediate Unsigned: Set rd to 1 if rs1 is less than the sign-extended 12-bit immediate, otherwise set rd to 0 (unsigned).
+//
+// > rd ← ux(rs1) < ux(imm)
+fn exec_sltiu<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| {
+        (a < u64::from_signed_word(b)) as u64
+    })
+}
+
+// Set Less Than Unsigned: Set rd to 1 if rs1 is less than rs2, otherwise set rd to 0 (unsigned).
+//
+// > rd ← ux(rs1) < ux(rs2)
+fn exec_sltu<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| (a < b) as u64)
+}
+
+// Shift Right Arithmetic: Shift rs1 right by the by the lower 6 bits in rs2 and place the result into rd while retaining the sign.
+//
+// > rd ← sx(rs1) » rs2
+fn exec_sra<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u64::from_signed(a.to_signed() >> (b & 0b111111))
+    })
+}
+
+// Shift Right Arithmetic Immediate: Shift rs1 right by the 6 bit immediate and place the result into rd while retaining the sign.
+//
+// > rd ← sx(rs1) » ux(imm)
+fn exec_srai<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u64> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| {
+        u64::from_signed(a.to_signed() >> shamt)
+    })
+}
+
+// Shift Right Arithmetic Immediate Word: Shift 32-bit rs1 right by the 5 bit immediate and place the sign-extended result into rd.
+//
+// > rd ← s32(rs1) » ux(imm)
+fn exec_sraiw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u64> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| {
+        u64::from_signed_word(a.to_signed_word() >> shamt)
+    })
+}
+
+// Shift Right Arithmetic Word: Shift 32-bit rs1 right by the lower 5 bits in rs2 and place the sign-extended result into rd.
+//
+// > rd ← s32(rs1) » rs2
+fn exec_sraw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u64::from_signed_word(a.to_signed_word() >> (b & 0b11111))
+    })
+}
+
+// Shift Right Logical: Shift rs1 right by the by the lower 6 bits in rs2 and place the result into rd.
+//
+// > rd ← ux(rs1) » rs2
+fn exec_srl<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a >> (b & 0b111111))
+}
+
+// Shift Right Logical Immediate: Shift rs1 right by the 6 bit immediate and place the result into rd.
+//
+// > rd ← ux(rs1) » ux(imm)
+fn exec_srli<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u64> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| a >> shamt)
+}
+
+// Shift Right Logical Immediate Word: Shift 32-bit rs1 right by the 5 bit immediate and place the sign-extended result into rd.
+//
+// > rd ← s32(u32(rs1) » ux(imm))
+fn exec_srliw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    shamt: u32,
+) -> ExecStatus<u64> {
+    exec_shift_op_imm(hart, rd, rs1, shamt, |a, shamt| {
+        u64::from_unsigned_word(a.to_unsigned_word() >> shamt)
+    })
+}
+
+// Shift Right Logical Word: Shift 32-bit rs1 right by the lower 5 bits in rs2 and place the sign-extended result into rd.
+//
+// > rd ← s32(u32(rs1) » rs2)
+fn exec_srlw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u64::from_unsigned_word(a.to_unsigned_word() >> (b & 0b11111))
+    })
+}
+
+// Subtract: Subtract rs2 from rs1 and place the result into rd.
+//
+// > rd ← sx(rs1) - sx(rs2)
+fn exec_sub<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a.wrapping_sub(b))
+}
+
+// Subtract Word: Subtract 32-bit rs2 from 32-bit rs1 and place the sign-extended result into rd.
+//
+// > rd ← s32(rs1) - s32(rs2)
+fn exec_subw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| {
+        u64::from_signed_word(a.to_signed_word().wrapping_sub(b.to_signed_word()))
+    })
+}
+
+// Store Word: Store 32-bit value from the low bits of rs2 to addr in rs1 plus the 12-bit signed immediate.
+//
+// > u32[rs1 + imm] ← rs2
+fn exec_sw<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_store_mem(hart, inst, rs1, rs2, simm, |mem, addr, v| {
+        mem.write_word(addr, v.to_unsigned_word())
+    })
+}
+
+// User Return: .
+//
+// >
+fn exec_uret<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    inst: Instruction<Op, u64>,
+) -> ExecStatus<u64> {
+    match hart.read_csr(ControlStatusRegister::numbered(0x041)) {
+        Ok(uepc) => {
+            hart.write_pc(uepc);
+            ExecStatus::Running
+        }
+        Err(_) => raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0),
+    }
+}
+
+// Wait For Interrupt: Wait for Interrupt indicates the hart can be stalled until an interrupt needs servicing.
+//
+// >
+fn exec_wfi<Mem: Bus<u64>>(
+    _hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+) -> ExecStatus<u64> {
+    ExecStatus::WaitingForInterrupt
+}
+
+// Exclusive Nor: Set rd to the inverse of the bitwise exclusive or of rs1 and rs2.
+//
+// > rd ← ¬(ux(rs1) ⊕ ux(rs2))
+fn exec_xnor<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| !(a ^ b))
+}
+
+// Xor: Set rd to the bitwise xor of rs1 and rs2.
+//
+// > rd ← ux(rs1) ⊻ ux(rs2)
+fn exec_xor<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u64> {
+    exec_binary_op(hart, rd, rs1, rs2, |a, b| a ^ b)
+}
+
+// Xor Immediate: Set rd to the bitwise xor of rs1 with the sign-extended 12-bit immediate.
+//
+// > rd ← ux(rs1) ⊻ ux(imm)
+fn exec_xori<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    simm: i32,
+) -> ExecStatus<u64> {
+    exec_binary_op_imm(hart, rd, rs1, simm, |a, b| a ^ u64::from_signed_word(b))
+}
+
+// Zero-Extend Halfword: Set rd to the least significant halfword of rs1, zero-extended.
+//
+// > rd ← u16(rs1)
+fn exec_zext_h<Mem: Bus<u64>>(
+    hart: &mut impl Hart<u64, u64, f64, Mem>,
+    _inst: Instruction<Op, u64>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u64> {
+    exec_unary_op(hart, rd, rs1, |a| a & 0xffff)
+}
+
+// Returns the 128-bit carry-less product of two values.
+fn carryless_mul(a: u64, b: u64) -> u128 {
+    (0..64)
+        .filter(|i| (b >> i) & 1 != 0)
+        .fold(0, |acc, i| acc ^ ((a as u128) << i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::step_rv64;
+    use crate::exception::ExceptionCause;
+    use crate::exec::ExecStatus;
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::RV64;
+    use crate::memory::{AddressConverter, Bus, Memory};
+    use crate::register::IntRegister;
+
+    // Executes a single instruction with x1 and x2 set to the given values
+    // and returns the value it left in x3.
+    fn execute(inst: u32, x1: u64, x2: u64) -> u64 {
+        let mut buf = [0u8; 64];
+        let mut hart: SingleThreadUserHart<RV64, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        hart.set_report_exceptions(true);
+        hart.with_memory(|mem| mem.write_word(0, inst).unwrap());
+        hart.write_int_register(IntRegister::numbered(1), x1);
+        hart.write_int_register(IntRegister::numbered(2), x2);
+        let status = step_rv64(&mut hart);
+        assert!(matches!(status, ExecStatus::Running), "{:#010x}", inst);
+        assert_eq!(hart.read_pc(), 4);
+        hart.read_int_register(IntRegister::numbered(3))
+    }
+
+    #[test]
+    fn single_instructions() {
+        let cases: &[(u32, u64, u64, u64)] = &[
+            (0x082081bb, 0xffff_ffff_8000_0000, 0x1, 0x8000_0001), // add.uw x3, x1, x2
+            (0x2020a1b3, 0x8000_0000_1234_5678, 0x3, 0x2468_acf3), // sh1add x3, x1, x2
+            (0x2020a1bb, 0xffff_ffff_8000_0001, 0x3, 0x0001_0000_0005), // sh1add.uw x3, x1, x2
+            (0x2020c1b3, 0x5, 0x7, 0x1b),                          // sh2add x3, x1, x2
+            (0x2020c1bb, 0xffff_ffff_ffff_ffff, 0x0, 0x0003_ffff_fffc), // sh2add.uw x3, x1, x2
+            (0x2020e1b3, 0x8000_0000_1234_5678, 0x3, 0x91a2_b3c3), // sh3add x3, x1, x2
+            (0x2020e1bb, 0x0001_0000_0002, 0x1, 0x11),             // sh3add.uw x3, x1, x2
+            (0x0840919b, 0xffff_ffff_8000_0001, 0x0, 0x0008_0000_0010), // slli.uw x3, x1, 4
+            (
+                0x4020f1b3,
+                0x8000_0000_1234_5678,
+                0xff,
+                0x8000_0000_1234_5600,
+            ), // andn x3, x1, x2
+            (
+                0x4020e1b3,
+                0x8000_0000_1234_5678,
+                0xff,
+                0xffff_ffff_ffff_ff78,
+            ), // orn x3, x1, x2
+            (
+                0x4020c1b3,
+                0x8000_0000_1234_5678,
+                0xff,
+                0x7fff_ffff_edcb_a978,
+            ), // xnor x3, x1, x2
+            (0x60009193, 0x0, 0x0, 0x40),                          // clz x3, x1
+            (0x60009193, 0x1234_5678, 0x0, 0x23),                  // clz x3, x1
+            (0x6000919b, 0xffff_ffff_0000_0000, 0x0, 0x20),        // clzw x3, x1
+            (0x6000919b, 0x0001_0000, 0x0, 0xf),                   // clzw x3, x1
+            (0x60109193, 0x0, 0x0, 0x40),                          // ctz x3, x1
+            (0x60109193, 0x8000_0000_0000_0000, 0x0, 0x3f),        // ctz x3, x1
+            (0x6010919b, 0x0001_0000_0000, 0x0, 0x20),             // ctzw x3, x1
+            (0x60209193, 0xffff_ffff_ffff_ffff, 0x0, 0x40),        // cpop x3, x1
+            (0x6020919b, 0xffff_ffff_ffff_ffff, 0x0, 0x20),        // cpopw x3, x1
+            (0x0a20e1b3, 0xffff_ffff_ffff_ffff, 0x1, 0x1),         // max x3, x1, x2
+            (
+                0x0a20f1b3,
+                0xffff_ffff_ffff_ffff,
+                0x1,
+                0xffff_ffff_ffff_ffff,
+            ), // maxu x3, x1, x2
+            (
+                0x0a20c1b3,
+                0xffff_ffff_ffff_ffff,
+                0x1,
+                0xffff_ffff_ffff_ffff,
+            ), // min x3, x1, x2
+            (0x0a20d1b3, 0xffff_ffff_ffff_ffff, 0x1, 0x1),         // minu x3, x1, x2
+            (0x60409193, 0x180, 0x0, 0xffff_ffff_ffff_ff80),       // sext.b x3, x1
+            (0x60509193, 0x0001_8000, 0x0, 0xffff_ffff_ffff_8000), // sext.h x3, x1
+            (0x0800c1bb, 0xffff_ffff_ffff_ffff, 0x0, 0xffff),      // zext.h x3, x1
+            (
+                0x602091b3,
+                0x8000_0000_1234_5678,
+                0x0,
+                0x8000_0000_1234_5678,
+            ), // rol x3, x1, x2
+            (0x602091b3, 0x8000_0000_1234_5678, 0x41, 0x2468_acf1), // rol x3, x1, x2
+            (0x602091bb, 0x1, 0x1f, 0xffff_ffff_8000_0000),        // rolw x3, x1, x2
+            (0x6020d1b3, 0x8000_0000_1234_5678, 0x3f, 0x2468_acf1), // ror x3, x1, x2
+            (0x6020d1bb, 0xffff_ffff_0000_0002, 0x1, 0x1),         // rorw x3, x1, x2
+            (
+                0x6000d193,
+                0x8000_0000_1234_5678,
+                0x0,
+                0x8000_0000_1234_5678,
+            ), // rori x3, x1, 0
+            (0x63f0d193, 0x8000_0000_1234_5678, 0x0, 0x2468_acf1), // rori x3, x1, 63
+            (0x61f0d19b, 0x1, 0x0, 0x2),                           // roriw x3, x1, 31
+            (
+                0x2870d193,
+                0x0100_0000_0080_0001,
+                0x0,
+                0xff00_0000_00ff_00ff,
+            ), // orc.b x3, x1
+            (
+                0x6b80d193,
+                0x0102_0304_0506_0708,
+                0x0,
+                0x0807_0605_0403_0201,
+            ), // rev8 x3, x1
+            (
+                0x0a2091b3,
+                0xffff_ffff_ffff_ffff,
+                0xffff_ffff_ffff_ffff,
+                0x5555_5555_5555_5555,
+            ), // clmul x3, x1, x2
+            (
+                0x0a20b1b3,
+                0xffff_ffff_ffff_ffff,
+                0xffff_ffff_ffff_ffff,
+                0x5555_5555_5555_5555,
+            ), // clmulh x3, x1, x2
+            (
+                0x0a20a1b3,
+                0xffff_ffff_ffff_ffff,
+                0xffff_ffff_ffff_ffff,
+                0xaaaa_aaaa_aaaa_aaaa,
+            ), // clmulr x3, x1, x2
+            (
+                0x482091b3,
+                0xffff_ffff_ffff_ffff,
+                0x7f,
+                0x7fff_ffff_ffff_ffff,
+            ), // bclr x3, x1, x2
+            (
+                0x4bf09193,
+                0xffff_ffff_ffff_ffff,
+                0x0,
+                0x7fff_ffff_ffff_ffff,
+            ), // bclri x3, x1, 63
+            (0x4820d1b3, 0x8000_0000_1234_5678, 0x3f, 0x1),        // bext x3, x1, x2
+            (0x4a80d193, 0x0100_0000_0000, 0x0, 0x1),              // bexti x3, x1, 40
+            (0x682091b3, 0x0, 0x20, 0x0001_0000_0000),             // binv x3, x1, x2
+            (
+                0x6a109193,
+                0xffff_ffff_ffff_ffff,
+                0x0,
+                0xffff_fffd_ffff_ffff,
+            ), // binvi x3, x1, 33
+            (0x282091b3, 0x0, 0x3f, 0x8000_0000_0000_0000),        // bset x3, x1, x2
+            (0x2bf09193, 0x0, 0x0, 0x8000_0000_0000_0000),         // bseti x3, x1, 63
+            (
+                0x022091b3,
+                0xffff_ffff_ffff_ffff,
+                0xffff_ffff_ffff_ffff,
+                0x0,
+            ), // mulh x3, x1, x2
+            (
+                0x0220a1b3,
+                0xffff_ffff_ffff_ffff,
+                0xffff_ffff_ffff_ffff,
+                0xffff_ffff_ffff_ffff,
+            ), // mulhsu x3, x1, x2
+            (
+                0x0220b1b3,
+                0xffff_ffff_ffff_ffff,
+                0xffff_ffff_ffff_ffff,
+                0xffff_ffff_ffff_fffe,
+            ), // mulhu x3, x1, x2
+            (0x002091b3, 0x1, 0x3f, 0x8000_0000_0000_0000),        // sll x3, x1, x2
+            (0x0020d1b3, 0x8000_0000_0000_0000, 0x3f, 0x1),        // srl x3, x1, x2
+            (
+                0x4020d1b3,
+                0x8000_0000_0000_0000,
+                0x3f,
+                0xffff_ffff_ffff_ffff,
+            ), // sra x3, x1, x2
+            (0x03f0d193, 0x8000_0000_0000_0000, 0x0, 0x1),         // srli x3, x1, 63
+        ];
+        for &(inst, x1, x2, want) in cases {
+            assert_eq!(execute(inst, x1, x2), want, "{:#010x}", inst);
+        }
+    }
+
+    #[test]
+    fn doubleword_program() {
+        let mut buf = [0u8; 128];
+        let mut hart: SingleThreadUserHart<RV64, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        hart.set_report_exceptions(true);
+        let program = [
+            0xfff00093, // addi x1, x0, -1
+            0x0200d113, // srli x2, x1, 32
+            0x04000193, // addi x3, x0, 64
+            0x0011b023, // sd x1, 0(x3)
+            0x0001e203, // lwu x4, 0(x3)
+            0x0001b283, // ld x5, 0(x3)
+            0x0011031b, // addiw x6, x2, 1
+            0x00000073, // ecall
+            0x0220f053, // fadd.d f0, f1, f2, which this executor doesn't implement
+        ];
+        hart.with_memory(|mem| {
+            for (i, word) in program.iter().enumerate() {
+                mem.write_word(i as u64 * 4, *word).unwrap();
+            }
+        });
+
+        for _ in 0..7 {
+            assert!(matches!(step_rv64(&mut hart), ExecStatus::Running));
+        }
+        assert!(matches!(
+            step_rv64(&mut hart),
+            ExecStatus::EnvironmentCall(28)
+        ));
+        let reg = |n| hart.read_int_register(IntRegister::numbered(n));
+        assert_eq!(reg(1), u64::MAX);
+        assert_eq!(reg(2), 0xffff_ffff);
+        assert_eq!(reg(4), 0xffff_ffff);
+        assert_eq!(reg(5), u64::MAX);
+        assert_eq!(reg(6), 0);
+
+        assert!(matches!(
+            step_rv64(&mut hart),
+            ExecStatus::Exception {
+                cause: ExceptionCause::IllegalInstruction,
+                pc: 32,
+                tval: 0,
+            }
+        ));
+    }
+}
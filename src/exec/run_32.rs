@@ -0,0 +1,292 @@
+use crate::exec::common::{fetch, raise};
+use crate::exec::exec_32::execute_rv32;
+use crate::exec::trace_32::{execute_rv32_traced, successors};
+use crate::exec::{ExecStatus, RunLimits, RunResult, StopReason, Tracer};
+use crate::hart::Hart;
+use crate::instruction::{Instruction, OperationRV32};
+use crate::memory::Bus;
+use core::sync::atomic::Ordering;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+type Op = OperationRV32;
+
+// The number of steps between checks of the deadline, which are much more
+// expensive than a typical step.
+#[cfg(feature = "std")]
+const DEADLINE_INTERVAL: u64 = 256;
+
+/// Repeatedly performs execution steps against the given RV32 hart, as with
+/// `step_rv32`, until it reaches an instruction that requires the caller's
+/// attention or one of the given limits is reached.
+///
+/// The run stops after an `ecall`, `ebreak` or `wfi` instruction has been
+/// executed, before an invalid instruction would be executed, and when an
+/// instruction raises an exception that the hart does not handle itself.
+pub fn run_rv32<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    limits: &RunLimits,
+) -> RunResult<u32> {
+    run(hart, limits, |hart, inst, _| execute_rv32(hart, inst))
+}
+
+/// Runs the given RV32 hart in the same way as `run_rv32`, reporting each
+/// executed instruction to the given tracer as with `step_rv32_traced`.
+pub fn run_rv32_traced<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    tracer: &mut impl Tracer<Op, u32>,
+    limits: &RunLimits,
+) -> RunResult<u32> {
+    run(hart, limits, |hart, inst, bits| {
+        execute_rv32_traced(hart, tracer, inst, bits)
+    })
+}
+
+fn run<Mem: Bus<u32>, H: Hart<u32, u32, f64, Mem>>(
+    hart: &mut H,
+    limits: &RunLimits,
+    mut execute: impl FnMut(&mut H, Instruction<Op, u32>, u32) -> ExecStatus<u32>,
+) -> RunResult<u32> {
+    let mut steps = 0;
+    let mut retired = 0;
+    let reason = loop {
+        if limits.max_steps.is_some_and(|max| steps >= max) {
+            break StopReason::StepLimit;
+        }
+        if let Some(cancel) = limits.cancel {
+            if cancel.load(Ordering::Relaxed) {
+                break StopReason::Cancelled;
+            }
+        }
+        #[cfg(feature = "std")]
+        if let Some(deadline) = limits.deadline {
+            if steps.is_multiple_of(DEADLINE_INTERVAL) && Instant::now() >= deadline {
+                break StopReason::Deadline;
+            }
+        }
+        steps += 1;
+
+        let pc = hart.read_pc();
+        let raw_inst = match fetch(hart, pc) {
+            Ok(raw_inst) => raw_inst,
+            Err(e) => match raise(hart, pc, e.cause(), e.addr as u32) {
+                ExecStatus::Exception { cause, pc, tval } => {
+                    break StopReason::Exception { cause, pc, tval }
+                }
+                _ => continue,
+            },
+        };
+        let bits = raw_inst.bits();
+        let inst = Instruction::<Op, u32>::decode_raw(raw_inst, pc);
+        if let Op::Invalid = inst.op {
+            break StopReason::InvalidInstruction { pc, bits };
+        }
+        let successors = successors(hart, &inst);
+        match execute(hart, inst, bits) {
+            ExecStatus::Running => {
+                // An exception that the hart handles itself leaves it
+                // running too, but at its trap vector rather than at one of
+                // the instruction's successors, and the instruction didn't
+                // retire.
+                let next = hart.read_pc();
+                if successors.is_none_or(|(a, b)| next == a || next == b) {
+                    retired += 1;
+                }
+            }
+            ExecStatus::WaitingForInterrupt => {
+                retired += 1;
+                break StopReason::WaitingForInterrupt;
+            }
+            ExecStatus::EnvironmentCall(pc) => {
+                retired += 1;
+                break StopReason::EnvironmentCall(pc);
+            }
+            ExecStatus::EnvironmentBreak(pc) => {
+                retired += 1;
+                break StopReason::EnvironmentBreak(pc);
+            }
+            ExecStatus::Exception { cause, pc, tval } => {
+                break StopReason::Exception { cause, pc, tval }
+            }
+        }
+    };
+    RunResult { reason, retired }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_rv32;
+    use crate::exec::{RunLimits, RunResult, StopReason};
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::RV32;
+    use crate::memory::{AddressConverter, Bus, Memory};
+    use crate::register::ControlStatusRegister;
+    use core::sync::atomic::AtomicBool;
+
+    #[test]
+    fn run_until_stopped() {
+        let mut buf = [0u8; 64];
+        let mut hart: SingleThreadUserHart<RV32, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        let program = [
+            0x00a00293, // addi x5, x0, 10
+            0xfff28293, // addi x5, x5, -1
+            0xfe029ee3, // bne x5, x0, -4
+            0x00000073, // ecall
+            0x10500073, // wfi
+            0x00100073, // ebreak
+            0xffffffff, // (invalid, with a reserved length encoding)
+        ];
+        hart.with_memory(|mem| {
+            for (i, word) in program.iter().enumerate() {
+                mem.write_word(i as u32 * 4, *word).unwrap();
+            }
+        });
+
+        let result = run_rv32(&mut hart, &RunLimits::default());
+        assert_eq!(
+            result,
+            RunResult {
+                reason: StopReason::EnvironmentCall(12),
+                retired: 22,
+            }
+        );
+        let result = run_rv32(&mut hart, &RunLimits::default());
+        assert_eq!(result.reason, StopReason::WaitingForInterrupt);
+        let result = run_rv32(&mut hart, &RunLimits::default());
+        assert_eq!(result.reason, StopReason::EnvironmentBreak(20));
+        let result = run_rv32(&mut hart, &RunLimits::default());
+        assert_eq!(
+            result,
+            RunResult {
+                reason: StopReason::InvalidInstruction {
+                    pc: 24,
+                    bits: 0xffff
+                },
+                retired: 0,
+            }
+        );
+        assert_eq!(hart.read_pc(), 24);
+
+        hart.write_pc(0);
+        let limits = RunLimits::new().with_max_steps(5);
+        assert_eq!(run_rv32(&mut hart, &limits).reason, StopReason::StepLimit);
+        assert_eq!(hart.read_pc(), 4);
+
+        let cancel = AtomicBool::new(true);
+        let limits = RunLimits::new().with_cancel(&cancel);
+        let result = run_rv32(&mut hart, &limits);
+        assert_eq!(result.reason, StopReason::Cancelled);
+        assert_eq!(result.retired, 0);
+
+        #[cfg(feature = "std")]
+        {
+            let limits = RunLimits::new().with_deadline(std::time::Instant::now());
+            assert_eq!(run_rv32(&mut hart, &limits).reason, StopReason::Deadline);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn compressed_at_end_of_memory() {
+        use crate::memory::AtomicMemory;
+        use crate::register::IntRegister;
+
+        // Unlike `Memory`, this faults on accesses beyond its end, so the
+        // final compressed instruction must be fetched without reading the
+        // two bytes after it.
+        let mut mem = AtomicMemory::new(8);
+        mem.write_word(0, 0x00500513).unwrap(); // li a0, 5
+        mem.write_halfword(4, 0x0505).unwrap(); // c.addi a0, 1
+        mem.write_halfword(6, 0x0505).unwrap(); // c.addi a0, 1
+        let mut hart: SingleThreadUserHart<RV32, _> = SingleThreadUserHart::new(mem);
+        hart.set_report_exceptions(true);
+
+        let limits = RunLimits::new().with_max_steps(3);
+        let result = run_rv32(&mut hart, &limits);
+        assert_eq!(result.reason, StopReason::StepLimit);
+        assert_eq!(hart.read_pc(), 8);
+        assert_eq!(hart.read_int_register(IntRegister::numbered(10)), 7);
+    }
+
+    #[test]
+    fn misaligned_jump() {
+        use crate::exception::ExceptionCause;
+        use crate::isa::Extensions;
+        use crate::register::IntRegister;
+
+        let mut buf = [0u8; 16];
+        let mut hart: SingleThreadUserHart<RV32, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        hart.with_memory(|mem| mem.write_word(0, 0x002000e7).unwrap()); // jalr ra, 2(zero)
+        hart.set_report_exceptions(true);
+        hart.set_extensions(Extensions::ALL.without(Extensions::C));
+
+        // Without compressed instructions the jump itself faults, before it
+        // writes the return address.
+        let limits = RunLimits::new().with_max_steps(1);
+        let result = run_rv32(&mut hart, &limits);
+        assert_eq!(
+            result.reason,
+            StopReason::Exception {
+                cause: ExceptionCause::InstructionAddressMisaligned,
+                pc: 0,
+                tval: 2,
+            }
+        );
+        assert_eq!(hart.read_pc(), 0);
+        assert_eq!(hart.read_int_register(IntRegister::numbered(1)), 0);
+
+        hart.set_extensions(Extensions::ALL);
+        assert_eq!(run_rv32(&mut hart, &limits).reason, StopReason::StepLimit);
+        assert_eq!(hart.read_pc(), 2);
+        assert_eq!(hart.read_int_register(IntRegister::numbered(1)), 4);
+    }
+
+    #[test]
+    fn faulting_instructions_not_retired() {
+        use crate::exception::ExceptionCause;
+        use crate::memory::{Alignment, MisalignedAccess};
+
+        let mut buf = [0u8; 64];
+        let mem = Alignment::new(
+            AddressConverter::new(Memory::new_ram(&mut buf)),
+            MisalignedAccess::Trap,
+        );
+        let mut hart: SingleThreadUserHart<RV32, _> = SingleThreadUserHart::new(mem);
+        hart.with_memory(|mem| {
+            mem.write_word(0, 0x02202503).unwrap(); // lw a0, 0x22(zero)
+            mem.write_word(8, 0x00000073).unwrap(); // ecall
+        });
+
+        // The load is misaligned, so it faults rather than retiring,
+        // whether the fault is reported to the caller...
+        hart.set_report_exceptions(true);
+        let result = run_rv32(&mut hart, &RunLimits::default());
+        assert_eq!(
+            result,
+            RunResult {
+                reason: StopReason::Exception {
+                    cause: ExceptionCause::LoadAddressMisaligned,
+                    pc: 0,
+                    tval: 0x22,
+                },
+                retired: 0,
+            }
+        );
+
+        // ...or the hart handles it by jumping to its trap vector, where
+        // only the ecall retires.
+        hart.set_report_exceptions(false);
+        hart.write_csr(ControlStatusRegister::numbered(0x005), 8)
+            .unwrap(); // utvec
+        let result = run_rv32(&mut hart, &RunLimits::default());
+        assert_eq!(
+            result,
+            RunResult {
+                reason: StopReason::EnvironmentCall(8),
+                retired: 1,
+            }
+        );
+    }
+}
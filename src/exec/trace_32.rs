@@ -0,0 +1,597 @@
+use crate::exec::common::{fetch, raise};
+use crate::exec::exec_32::execute_rv32;
+use crate::exec::{Commit, ExecStatus, MemoryAccess, Tracer};
+use crate::hart::Hart;
+use crate::instruction::{Instruction, OperationRV32};
+use crate::memory::Bus;
+use crate::register::{FloatRegister, IntRegister};
+use core::fmt;
+
+type Op = OperationRV32;
+
+/// Performs a single execution step against the given RV32 hart, in the
+/// same way as `step_rv32`, and then reports the executed instruction and
+/// its effects to the given tracer.
+///
+/// Nothing is reported if the instruction could not be fetched from memory.
+pub fn step_rv32_traced<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    tracer: &mut impl Tracer<Op, u32>,
+) -> ExecStatus<u32> {
+    let pc = hart.read_pc();
+    let raw_inst = match fetch(hart, pc) {
+        Ok(raw_inst) => raw_inst,
+        Err(e) => return raise(hart, pc, e.cause(), e.addr as u32),
+    };
+
+    let bits = raw_inst.bits();
+    let inst = Instruction::<Op, u32>::decode_raw(raw_inst, pc);
+    execute_rv32_traced(hart, tracer, inst, bits)
+}
+
+// Executes an instruction that was decoded from the hart's current program
+// counter, given with its raw encoding, and reports it to the tracer.
+pub(crate) fn execute_rv32_traced<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    tracer: &mut impl Tracer<Op, u32>,
+    inst: Instruction<Op, u32>,
+    bits: u32,
+) -> ExecStatus<u32> {
+    let bits = match inst.length {
+        2 => bits & 0xffff,
+        _ => bits,
+    };
+    let effects = Effects::of(&inst.op);
+
+    // Register operands must be captured before the instruction executes,
+    // because it may overwrite them.
+    let access = effects.memory.map(|m| {
+        let base = hart.read_int_register(m.base);
+        let addr = base.wrapping_add(m.offset as u32);
+        let value = match m.source {
+            Some(Source::Int(reg)) => hart.read_int_register(reg) as u64,
+            Some(Source::Float(reg)) => hart.read_float_register(reg).to_bits(),
+            None => 0,
+        };
+        (m, addr, value)
+    });
+    let successors = successors(hart, &inst);
+
+    let status = execute_rv32(hart, inst.clone());
+
+    // Environment calls and breaks raise exceptions too, even when the
+    // caller is going to handle them rather than the hart.
+    let trapped = match (&status, successors) {
+        (
+            ExecStatus::EnvironmentCall(_)
+            | ExecStatus::EnvironmentBreak(_)
+            | ExecStatus::Exception { .. },
+            _,
+        ) => true,
+        (_, Some((a, b))) => {
+            let next = hart.read_pc();
+            next != a && next != b
+        }
+        (_, None) => false,
+    };
+    let mut commit = Commit {
+        inst,
+        bits,
+        trapped,
+        int_write: None,
+        float_write: None,
+        load: None,
+        store: None,
+    };
+    if !trapped {
+        commit.int_write = effects
+            .int_rd
+            .filter(|rd| rd.num() != 0)
+            .map(|rd| (rd, hart.read_int_register(rd)));
+        commit.float_write = effects
+            .float_rd
+            .map(|rd| (rd, hart.read_float_register(rd).to_bits()));
+        if let Some((m, addr, value)) = access {
+            let loc = MemoryAccess { addr, size: m.size };
+            match m.source {
+                Some(_) => {
+                    let mask = match m.size {
+                        1 => 0xff,
+                        2 => 0xffff,
+                        4 => 0xffff_ffff,
+                        _ => !0,
+                    };
+                    commit.store = Some((loc, value & mask));
+                }
+                None => commit.load = Some(loc),
+            }
+        }
+        // A store-conditional only writes to memory if it succeeds, which
+        // it reports by writing zero to its destination register.
+        if let Op::ScW { rd, rs1, .. } = commit.inst.op {
+            if hart.read_int_register(rd) == 0 {
+                let addr = hart.read_int_register(rs1);
+                commit.store = access.map(|(_, _, value)| (MemoryAccess { addr, size: 4 }, value));
+            }
+            commit.load = None;
+        }
+    }
+
+    tracer.commit(&commit);
+    status
+}
+
+/// A `Tracer` that writes a log in the same format as the Spike simulator's
+/// `--log-commits` option, so that the two can be compared line by line.
+///
+/// Each retired instruction produces a line giving the privilege level, the
+/// program counter, the raw encoding and then any register writes and
+/// memory accesses. If disassembly is enabled, each instruction is also
+/// preceded by a line in the format of Spike's `-l` option, giving the
+/// disassembled instruction.
+///
+/// Errors returned by the underlying writer are ignored.
+pub struct SpikeLog<W: fmt::Write> {
+    out: W,
+    hart_id: u32,
+    privilege: u8,
+    disassemble: bool,
+}
+
+impl<W: fmt::Write> SpikeLog<W> {
+    /// Creates a log that writes to the given destination. The log initially
+    /// reports hart ID 0 and machine mode, and does not include disassembly.
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            hart_id: 0,
+            privilege: 3,
+            disassemble: false,
+        }
+    }
+
+    /// Sets the hart ID written at the start of each line.
+    pub fn set_hart_id(&mut self, id: u32) {
+        self.hart_id = id;
+    }
+
+    /// Sets the privilege level reported for subsequent instructions, using
+    /// the encoding from the `mstatus.MPP` field: 0 for user mode, 1 for
+    /// supervisor mode and 3 for machine mode.
+    pub fn set_privilege(&mut self, privilege: u8) {
+        self.privilege = privilege;
+    }
+
+    /// Enables or disables the disassembly line before each instruction.
+    pub fn set_disassembly(&mut self, enabled: bool) {
+        self.disassemble = enabled;
+    }
+
+    /// Borrows the destination the log is being written to.
+    pub fn output(&mut self) -> &mut W {
+        &mut self.out
+    }
+
+    /// Returns the destination the log was being written to.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn write_commit(&mut self, commit: &Commit<Op, u32>) -> fmt::Result {
+        let out = &mut self.out;
+        let pc = commit.inst.pc;
+        if self.disassemble {
+            writeln!(
+                out,
+                "core {:3}: 0x{:08x} (0x{:08x}) {}",
+                self.hart_id, pc, commit.bits, commit.inst.op
+            )?;
+        }
+        if commit.trapped {
+            return Ok(());
+        }
+        write!(
+            out,
+            "core {:3}: {} 0x{:08x} ",
+            self.hart_id, self.privilege, pc
+        )?;
+        match commit.inst.length {
+            2 => write!(out, "(0x{:04x})", commit.bits)?,
+            _ => write!(out, "(0x{:08x})", commit.bits)?,
+        }
+        if let Some((rd, v)) = commit.int_write {
+            write!(out, " x{:<2} 0x{:08x}", rd.num(), v)?;
+        }
+        if let Some((rd, v)) = commit.float_write {
+            write!(out, " f{:<2} 0x{:016x}", rd.num(), v)?;
+        }
+        if let Some(load) = commit.load {
+            write!(out, " mem 0x{:08x}", load.addr)?;
+        }
+        if let Some((store, v)) = commit.store {
+            write!(
+                out,
+                " mem 0x{:08x} 0x{:02$x}",
+                store.addr,
+                v,
+                store.size * 2
+            )?;
+        }
+        writeln!(out)
+    }
+}
+
+impl<W: fmt::Write> Tracer<Op, u32> for SpikeLog<W> {
+    fn commit(&mut self, commit: &Commit<Op, u32>) {
+        let _ = self.write_commit(commit);
+    }
+}
+
+// Returns the addresses where execution may continue after the given
+// instruction if it doesn't raise an exception, or `None` if the
+// instruction is one whose successor can't be predicted, such as `ecall`.
+pub(crate) fn successors<Mem: Bus<u32>>(
+    hart: &impl Hart<u32, u32, f64, Mem>,
+    inst: &Instruction<Op, u32>,
+) -> Option<(u32, u32)> {
+    let next = inst.pc.wrapping_add(inst.length as u32);
+    let relative = |simm: i32| inst.pc.wrapping_add(simm as u32);
+    let register =
+        |rs1: IntRegister, simm: i32| hart.read_int_register(rs1).wrapping_add(simm as u32) & !1;
+    Some(match inst.op {
+        Op::Jal { simm, .. } | Op::CJ { simm } | Op::CJal { simm } => {
+            (relative(simm), relative(simm))
+        }
+        Op::Jalr { rs1, simm, .. } => (register(rs1, simm), register(rs1, simm)),
+        Op::CJr { rs1, .. } | Op::CJalr { rs1, .. } => (register(rs1, 0), register(rs1, 0)),
+        Op::Beq { simm, .. }
+        | Op::Bne { simm, .. }
+        | Op::Blt { simm, .. }
+        | Op::Bge { simm, .. }
+        | Op::Bltu { simm, .. }
+        | Op::Bgeu { simm, .. }
+        | Op::CBeqz { simm, .. }
+        | Op::CBnez { simm, .. } => (next, relative(simm)),
+        Op::Ecall
+        | Op::Ebreak
+        | Op::CEbreak
+        | Op::Uret
+        | Op::Sret
+        | Op::Hret
+        | Op::Mret
+        | Op::Dret
+        | Op::Wfi => return None,
+        _ => (next, next),
+    })
+}
+
+// The register operand whose value is written to memory by a store.
+#[derive(Clone, Copy)]
+enum Source {
+    Int(IntRegister),
+    Float(FloatRegister),
+}
+
+// The location of an instruction's memory access, relative to a base
+// register, and the register to be stored there if the access is a store.
+#[derive(Clone, Copy)]
+struct Access {
+    base: IntRegister,
+    offset: i32,
+    size: usize,
+    source: Option<Source>,
+}
+
+// Summarizes which registers and memory an operation may write, for the
+// purpose of reporting the values it wrote once it has executed.
+struct Effects {
+    int_rd: Option<IntRegister>,
+    float_rd: Option<FloatRegister>,
+    memory: Option<Access>,
+}
+
+impl Effects {
+    fn of(op: &Op) -> Self {
+        let sp = IntRegister::numbered(2);
+        let mut effects = Effects {
+            int_rd: None,
+            float_rd: None,
+            memory: None,
+        };
+        let load = |base, offset, size| {
+            Some(Access {
+                base,
+                offset,
+                size,
+                source: None,
+            })
+        };
+        let store = |base, offset, size, source| {
+            Some(Access {
+                base,
+                offset,
+                size,
+                source: Some(source),
+            })
+        };
+        match *op {
+            Op::Lb { rd, rs1, simm } | Op::Lbu { rd, rs1, simm } => {
+                effects.int_rd = Some(rd);
+                effects.memory = load(rs1, simm, 1);
+            }
+            Op::Lh { rd, rs1, simm } | Op::Lhu { rd, rs1, simm } => {
+                effects.int_rd = Some(rd);
+                effects.memory = load(rs1, simm, 2);
+            }
+            Op::Lw { rd, rs1, simm } => {
+                effects.int_rd = Some(rd);
+                effects.memory = load(rs1, simm, 4);
+            }
+            Op::CLw { rd, rs1, uimm } => {
+                effects.int_rd = Some(rd);
+                effects.memory = load(rs1, uimm as i32, 4);
+            }
+            Op::CLwsp { rd, uimm } => {
+                effects.int_rd = Some(rd);
+                effects.memory = load(sp, uimm as i32, 4);
+            }
+            Op::Flw { frd, rs1, simm } => {
+                effects.float_rd = Some(frd);
+                effects.memory = load(rs1, simm, 4);
+            }
+            Op::Fld { frd, rs1, simm } => {
+                effects.float_rd = Some(frd);
+                effects.memory = load(rs1, simm, 8);
+            }
+            Op::CFlw { frd, rs1, uimm } => {
+                effects.float_rd = Some(FloatRegister::c_numbered(frd.num()));
+                effects.memory = load(rs1, uimm as i32, 4);
+            }
+            Op::CFld { frd, rs1, uimm } => {
+                effects.float_rd = Some(FloatRegister::c_numbered(frd.num()));
+                effects.memory = load(rs1, uimm as i32, 8);
+            }
+            Op::CFlwsp { frd, uimm } => {
+                effects.float_rd = Some(frd);
+                effects.memory = load(sp, uimm as i32, 4);
+            }
+            Op::CFldsp { frd, uimm } => {
+                effects.float_rd = Some(frd);
+                effects.memory = load(sp, uimm as i32, 8);
+            }
+
+            Op::Sb { rs1, rs2, simm } => effects.memory = store(rs1, simm, 1, Source::Int(rs2)),
+            Op::Sh { rs1, rs2, simm } => effects.memory = store(rs1, simm, 2, Source::Int(rs2)),
+            Op::Sw { rs1, rs2, simm } => effects.memory = store(rs1, simm, 4, Source::Int(rs2)),
+            Op::CSw { rs1, rs2, uimm } => {
+                effects.memory = store(rs1, uimm as i32, 4, Source::Int(rs2))
+            }
+            Op::CSwsp { rs2, uimm } => effects.memory = store(sp, uimm as i32, 4, Source::Int(rs2)),
+            Op::Fsw { rs1, frs2, simm } => {
+                effects.memory = store(rs1, simm, 4, Source::Float(frs2))
+            }
+            Op::Fsd { rs1, frs2, simm } => {
+                effects.memory = store(rs1, simm, 8, Source::Float(frs2))
+            }
+            Op::CFsw { rs1, frs2, uimm } => {
+                let frs2 = FloatRegister::c_numbered(frs2.num());
+                effects.memory = store(rs1, uimm as i32, 4, Source::Float(frs2))
+            }
+            Op::CFsd { rs1, frs2, uimm } => {
+                let frs2 = FloatRegister::c_numbered(frs2.num());
+                effects.memory = store(rs1, uimm as i32, 8, Source::Float(frs2))
+            }
+            Op::CFswsp { frs2, uimm } => {
+                effects.memory = store(sp, uimm as i32, 4, Source::Float(frs2))
+            }
+            Op::CFsdsp { frs2, uimm } => {
+                effects.memory = store(sp, uimm as i32, 8, Source::Float(frs2))
+            }
+
+            Op::LrW { rd, rs1, .. }
+            | Op::AmoaddW { rd, rs1, .. }
+            | Op::AmoandW { rd, rs1, .. }
+            | Op::AmomaxW { rd, rs1, .. }
+            | Op::AmomaxuW { rd, rs1, .. }
+            | Op::AmominW { rd, rs1, .. }
+            | Op::AmominuW { rd, rs1, .. }
+            | Op::AmoorW { rd, rs1, .. }
+            | Op::AmoswapW { rd, rs1, .. }
+            | Op::AmoxorW { rd, rs1, .. } => {
+                effects.int_rd = Some(rd);
+                effects.memory = load(rs1, 0, 4);
+            }
+            Op::ScW { rd, rs1, rs2, .. } => {
+                effects.int_rd = Some(rd);
+                effects.memory = store(rs1, 0, 4, Source::Int(rs2));
+            }
+
+            Op::Add { rd, .. }
+            | Op::Addi { rd, .. }
+            | Op::And { rd, .. }
+            | Op::Andi { rd, .. }
+            | Op::Auipc { rd, .. }
+            | Op::Jal { rd, .. }
+            | Op::Jalr { rd, .. }
+            | Op::Lui { rd, .. }
+            | Op::Or { rd, .. }
+            | Op::Ori { rd, .. }
+            | Op::Sll { rd, .. }
+            | Op::Slli { rd, .. }
+            | Op::Slt { rd, .. }
+            | Op::Slti { rd, .. }
+            | Op::Sltiu { rd, .. }
+            | Op::Sltu { rd, .. }
+            | Op::Sra { rd, .. }
+            | Op::Srai { rd, .. }
+            | Op::Srl { rd, .. }
+            | Op::Srli { rd, .. }
+            | Op::Sub { rd, .. }
+            | Op::Xor { rd, .. }
+            | Op::Xori { rd, .. }
+            | Op::Div { rd, .. }
+            | Op::Divu { rd, .. }
+            | Op::Mul { rd, .. }
+            | Op::Mulh { rd, .. }
+            | Op::Mulhsu { rd, .. }
+            | Op::Mulhu { rd, .. }
+            | Op::Rem { rd, .. }
+            | Op::Remu { rd, .. }
+            | Op::Csrrc { rd, .. }
+            | Op::Csrrci { rd, .. }
+            | Op::Csrrs { rd, .. }
+            | Op::Csrrsi { rd, .. }
+            | Op::Csrrw { rd, .. }
+            | Op::Csrrwi { rd, .. }
+            | Op::FclassS { rd, .. }
+            | Op::FcvtWS { rd, .. }
+            | Op::FcvtWuS { rd, .. }
+            | Op::FeqS { rd, .. }
+            | Op::FleS { rd, .. }
+            | Op::FltS { rd, .. }
+            | Op::FmvXS { rd, .. }
+            | Op::FclassD { rd, .. }
+            | Op::FcvtWD { rd, .. }
+            | Op::FcvtWuD { rd, .. }
+            | Op::FeqD { rd, .. }
+            | Op::FleD { rd, .. }
+            | Op::FltD { rd, .. }
+            | Op::FclassQ { rd, .. }
+            | Op::FcvtWQ { rd, .. }
+            | Op::FcvtWuQ { rd, .. }
+            | Op::FeqQ { rd, .. }
+            | Op::FleQ { rd, .. }
+            | Op::FltQ { rd, .. }
+            | Op::CAddi4Spn { rd, .. }
+            | Op::CJalr { rd, .. }
+            | Op::CJr { rd, .. }
+            | Op::CLui { rd, .. }
+            | Op::CMv { rd, .. } => effects.int_rd = Some(rd),
+            Op::CAdd { rs1rd, .. }
+            | Op::CAddi { rs1rd, .. }
+            | Op::CAddi16Sp { rs1rd, .. }
+            | Op::CAddw { rs1rd, .. }
+            | Op::CAnd { rs1rd, .. }
+            | Op::CAndi { rs1rd, .. }
+            | Op::CLi { rs1rd, .. }
+            | Op::COr { rs1rd, .. }
+            | Op::CSlli { rs1rd, .. }
+            | Op::CSrai { rs1rd, .. }
+            | Op::CSrli { rs1rd, .. }
+            | Op::CSub { rs1rd, .. }
+            | Op::CSubw { rs1rd, .. }
+            | Op::CXor { rs1rd, .. } => effects.int_rd = Some(rs1rd),
+            Op::CJal { .. } => effects.int_rd = Some(IntRegister::numbered(1)),
+
+            Op::FaddS { frd, .. }
+            | Op::FcvtSW { frd, .. }
+            | Op::FcvtSWu { frd, .. }
+            | Op::FdivS { frd, .. }
+            | Op::FmaddS { frd, .. }
+            | Op::FmaxS { frd, .. }
+            | Op::FminS { frd, .. }
+            | Op::FmsubS { frd, .. }
+            | Op::FmulS { frd, .. }
+            | Op::FmvSX { frd, .. }
+            | Op::FnmaddS { frd, .. }
+            | Op::FnmsubS { frd, .. }
+            | Op::FsgnjS { frd, .. }
+            | Op::FsgnjnS { frd, .. }
+            | Op::FsgnjxS { frd, .. }
+            | Op::FsqrtS { frd, .. }
+            | Op::FsubS { frd, .. }
+            | Op::FaddD { frd, .. }
+            | Op::FcvtDS { frd, .. }
+            | Op::FcvtDW { frd, .. }
+            | Op::FcvtDWu { frd, .. }
+            | Op::FcvtSD { frd, .. }
+            | Op::FdivD { frd, .. }
+            | Op::FmaddD { frd, .. }
+            | Op::FmaxD { frd, .. }
+            | Op::FminD { frd, .. }
+            | Op::FmsubD { frd, .. }
+            | Op::FmulD { frd, .. }
+            | Op::FnmaddD { frd, .. }
+            | Op::FnmsubD { frd, .. }
+            | Op::FsgnjD { frd, .. }
+            | Op::FsgnjnD { frd, .. }
+            | Op::FsgnjxD { frd, .. }
+            | Op::FsqrtD { frd, .. }
+            | Op::FsubD { frd, .. } => effects.float_rd = Some(frd),
+
+            // Quad-precision values don't fit in the 64-bit float registers
+            // this executor uses, so their effects aren't reported.
+            _ => {}
+        }
+        effects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{step_rv32_traced, SpikeLog};
+    use crate::exec::ExecStatus;
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::RV32;
+    use crate::memory::{AddressConverter, Bus, Memory};
+    use crate::register::IntRegister;
+    use std::string::String;
+
+    #[test]
+    fn spike_commit_log() {
+        let mut buf = [0u8; 64];
+        let mem = AddressConverter::<u32, usize, _>::new(Memory::new_ram(&mut buf));
+        let mut hart: SingleThreadUserHart<RV32, _> = SingleThreadUserHart::new(mem);
+        let program: [u32; 7] = [
+            0x02000593, // li      a1, 32
+            0x00b5a223, // sw      a1, 4(a1)
+            0x0045a603, // lw      a2, 4(a1)
+            0x00000013, // nop
+            0x00000073, // ecall
+            0x00000000, // (invalid)
+            0x00000505, // c.addi  a0, 1
+        ];
+        hart.with_memory(|mem| {
+            for (i, word) in program.iter().enumerate() {
+                mem.write_word(i as u32 * 4, *word).unwrap();
+            }
+        });
+
+        let mut log = SpikeLog::new(String::new());
+        log.set_disassembly(true);
+        for _ in 0..4 {
+            match step_rv32_traced(&mut hart, &mut log) {
+                ExecStatus::Running => {}
+                status => panic!("unexpected {:?}", status),
+            }
+        }
+        match step_rv32_traced(&mut hart, &mut log) {
+            ExecStatus::EnvironmentCall(16) => {}
+            status => panic!("unexpected {:?}", status),
+        }
+        log.set_disassembly(false);
+        hart.write_pc(24);
+        step_rv32_traced(&mut hart, &mut log);
+        assert_eq!(hart.read_int_register(IntRegister::numbered(10)), 1);
+
+        // An invalid instruction raises an exception instead of retiring.
+        log.set_disassembly(true);
+        hart.write_pc(20);
+        step_rv32_traced(&mut hart, &mut log);
+
+        assert_eq!(
+            log.into_inner(),
+            "core   0: 0x00000000 (0x02000593) li      a1, 32\n\
+             core   0: 3 0x00000000 (0x02000593) x11 0x00000020\n\
+             core   0: 0x00000004 (0x00b5a223) sw      a1, 4(a1)\n\
+             core   0: 3 0x00000004 (0x00b5a223) mem 0x00000024 0x00000020\n\
+             core   0: 0x00000008 (0x0045a603) lw      a2, 4(a1)\n\
+             core   0: 3 0x00000008 (0x0045a603) x12 0x00000020 mem 0x00000024\n\
+             core   0: 0x0000000c (0x00000013) nop\n\
+             core   0: 3 0x0000000c (0x00000013)\n\
+             core   0: 0x00000010 (0x00000073) ecall\n\
+             core   0: 3 0x00000018 (0x0505) x10 0x00000001\n\
+             core   0: 0x00000014 (0x00000000) unknown\n"
+        );
+    }
+}
@@ -0,0 +1,4326 @@
+use super::common::raise;
+use crate::exception::ExceptionCause;
+use crate::exec::ExecStatus;
+use crate::hart::Hart;
+use crate::instruction::Instruction;
+use crate::instruction::OperationRV32;
+use crate::isa::Extensions;
+use crate::memory::{Bus, MemoryError};
+use crate::register::{FloatRegister, IntRegister, VectorRegister};
+use crate::vector::{VectorRegisters, MAX_VLEN};
+
+type Op = OperationRV32;
+
+const CANONICAL_NAN_32: u64 = 0x7fc0_0000;
+const CANONICAL_NAN_64: u64 = 0x7ff8_0000_0000_0000;
+
+// The implementations of the RV32 vector instructions, which
+// exec_32::dispatch_instruction calls in the same way as its own.
+//
+// Masked-off elements and the tail elements past vl are always left
+// undisturbed, which the spec allows for both the undisturbed and the
+// agnostic policies.
+
+// Vector Add, Vector-Immediate: Add the elements of vs2 and the second operand.
+//
+// > vd[i] ← vs2[i] + simm
+pub(super) fn exec_vadd_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    simm: i32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(simm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a.wrapping_add(b))
+    })
+}
+
+// Vector Add, Vector-Vector: Add the elements of vs2 and the second operand.
+//
+// > vd[i] ← vs2[i] + vs1[i]
+pub(super) fn exec_vadd_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a.wrapping_add(b))
+    })
+}
+
+// Vector Add, Vector-Scalar: Add the elements of vs2 and the second operand.
+//
+// > vd[i] ← vs2[i] + x[rs1]
+pub(super) fn exec_vadd_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a.wrapping_add(b))
+    })
+}
+
+// Vector And, Vector-Immediate: Bitwise and the elements of vs2 with the second operand.
+//
+// > vd[i] ← vs2[i] ∧ simm
+pub(super) fn exec_vand_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    simm: i32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(simm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a & b)
+    })
+}
+
+// Vector And, Vector-Vector: Bitwise and the elements of vs2 with the second operand.
+//
+// > vd[i] ← vs2[i] ∧ vs1[i]
+pub(super) fn exec_vand_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a & b)
+    })
+}
+
+// Vector And, Vector-Scalar: Bitwise and the elements of vs2 with the second operand.
+//
+// > vd[i] ← vs2[i] ∧ x[rs1]
+pub(super) fn exec_vand_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a & b)
+    })
+}
+
+// Vector Compress: Pack the elements of vs2 whose bits are set in the mask in vs1 into the first elements of vd.
+//
+// > vd[j++] ← vs2[i] if vs1.mask[i]
+pub(super) fn exec_vcompress_vm<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| compress(regs, vd, vs2, vs1))
+}
+
+// Vector Count Population in Mask: Count the active elements of the mask in vs2 that are set, then write the count to rd.
+//
+// > rd ← Σ vs2.mask[i]
+pub(super) fn exec_vcpop_m<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let mut count = 0;
+    let status = exec_vector_op(hart, inst, |regs| match regs.vstart() {
+        0 => {
+            count = (0..regs.vl() as usize)
+                .filter(|&i| active(regs, vm, i) && regs.mask_bit(vs2, i))
+                .count() as u32;
+            true
+        }
+        _ => false,
+    });
+    if let ExecStatus::Running = status {
+        hart.write_int_register(rd, count);
+    }
+    status
+}
+
+// Vector Divide, Vector-Vector: Divide the elements of vs2 by the second operand (signed).
+//
+// > vd[i] ← sx(vs2[i]) ÷ sx(vs1[i])
+pub(super) fn exec_vdiv_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| match signed(b, sew) {
+            0 => u64::MAX,
+            b => signed(a, sew).wrapping_div(b) as u64,
+        })
+    })
+}
+
+// Vector Divide, Vector-Scalar: Divide the elements of vs2 by the second operand (signed).
+//
+// > vd[i] ← sx(vs2[i]) ÷ sx(x[rs1])
+pub(super) fn exec_vdiv_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| match signed(b, sew) {
+            0 => u64::MAX,
+            b => signed(a, sew).wrapping_div(b) as u64,
+        })
+    })
+}
+
+// Vector Divide Unsigned, Vector-Vector: Divide the elements of vs2 by the second operand (unsigned).
+//
+// > vd[i] ← ux(vs2[i]) ÷ ux(vs1[i])
+pub(super) fn exec_vdivu_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| {
+            a.checked_div(b).unwrap_or(u64::MAX)
+        })
+    })
+}
+
+// Vector Divide Unsigned, Vector-Scalar: Divide the elements of vs2 by the second operand (unsigned).
+//
+// > vd[i] ← ux(vs2[i]) ÷ ux(x[rs1])
+pub(super) fn exec_vdivu_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| {
+            a.checked_div(b).unwrap_or(u64::MAX)
+        })
+    })
+}
+
+// Vector FP Add, Vector-Scalar: Add the elements of vs2 and the second operand.
+//
+// > vd[i] ← vs2[i] + f[rs1]
+pub(super) fn exec_vfadd_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| a + b, |a, b| a + b)
+    })
+}
+
+// Vector FP Add, Vector-Vector: Add the elements of vs2 and the second operand.
+//
+// > vd[i] ← vs2[i] + vs1[i]
+pub(super) fn exec_vfadd_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| a + b, |a, b| a + b)
+    })
+}
+
+// Vector FP Divide, Vector-Scalar: Divide the elements of vs2 by the second operand.
+//
+// > vd[i] ← vs2[i] ÷ f[rs1]
+pub(super) fn exec_vfdiv_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| a / b, |a, b| a / b)
+    })
+}
+
+// Vector FP Divide, Vector-Vector: Divide the elements of vs2 by the second operand.
+//
+// > vd[i] ← vs2[i] ÷ vs1[i]
+pub(super) fn exec_vfdiv_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| a / b, |a, b| a / b)
+    })
+}
+
+// Vector Find First Set Mask Bit: Write the index of the first active element of the mask in vs2 that is set to rd, or -1 if there is none.
+//
+// > rd ← min({i | vs2.mask[i]} ∪ {-1})
+pub(super) fn exec_vfirst_m<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let mut first = u32::MAX;
+    let status = exec_vector_op(hart, inst, |regs| match regs.vstart() {
+        0 => {
+            if let Some(i) =
+                (0..regs.vl() as usize).find(|&i| active(regs, vm, i) && regs.mask_bit(vs2, i))
+            {
+                first = i as u32;
+            }
+            true
+        }
+        _ => false,
+    });
+    if let ExecStatus::Running = status {
+        hart.write_int_register(rd, first);
+    }
+    status
+}
+
+// Vector FP Maximum, Vector-Scalar: Select the larger of each element of vs2 and the second operand.
+//
+// > vd[i] ← max(vs2[i], f[rs1])
+pub(super) fn exec_vfmax_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| a.max(b), |a, b| a.max(b))
+    })
+}
+
+// Vector FP Maximum, Vector-Vector: Select the larger of each element of vs2 and the second operand.
+//
+// > vd[i] ← max(vs2[i], vs1[i])
+pub(super) fn exec_vfmax_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| a.max(b), |a, b| a.max(b))
+    })
+}
+
+// Vector FP Merge: Select f[rs1] for each element where the mask in v0 is set, and the element of vs2 otherwise.
+//
+// > vd[i] ← v0.mask[i] ? f[rs1] : vs2[i]
+pub(super) fn exec_vfmerge_vfm<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| merge(regs, vd, Some(vs2), src))
+}
+
+// Vector FP Minimum, Vector-Scalar: Select the smaller of each element of vs2 and the second operand.
+//
+// > vd[i] ← min(vs2[i], f[rs1])
+pub(super) fn exec_vfmin_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| a.min(b), |a, b| a.min(b))
+    })
+}
+
+// Vector FP Minimum, Vector-Vector: Select the smaller of each element of vs2 and the second operand.
+//
+// > vd[i] ← min(vs2[i], vs1[i])
+pub(super) fn exec_vfmin_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| a.min(b), |a, b| a.min(b))
+    })
+}
+
+// Vector FP Multiply, Vector-Scalar: Multiply the elements of vs2 by the second operand.
+//
+// > vd[i] ← vs2[i] × f[rs1]
+pub(super) fn exec_vfmul_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| a * b, |a, b| a * b)
+    })
+}
+
+// Vector FP Multiply, Vector-Vector: Multiply the elements of vs2 by the second operand.
+//
+// > vd[i] ← vs2[i] × vs1[i]
+pub(super) fn exec_vfmul_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| a * b, |a, b| a * b)
+    })
+}
+
+// Vector FP Move to Float: Write element 0 of vs2 to frd, NaN-boxing single-precision values.
+//
+// > frd ← vs2[0]
+pub(super) fn exec_vfmv_f_s<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    frd: FloatRegister,
+    vs2: VectorRegister,
+) -> ExecStatus<u32> {
+    let mut v = 0.0;
+    let status = exec_vector_float_op(hart, inst, |regs| {
+        v = match regs.sew() {
+            32 => f64::from_bits(regs.element(vs2, 0, 32) | 0xffff_ffff_0000_0000),
+            _ => f64::from_bits(regs.element(vs2, 0, 64)),
+        };
+        true
+    });
+    if let ExecStatus::Running = status {
+        hart.write_float_register(frd, v);
+    }
+    status
+}
+
+// Vector FP Move from Float: Write f[rs1] to element 0 of vd, unless vl is zero.
+//
+// > vd[0] ← f[rs1]
+pub(super) fn exec_vfmv_s_f<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    frs1: FloatRegister,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| move_to_first(regs, vd, src))
+}
+
+// Vector FP Move: Copy f[rs1] to every element of vd.
+//
+// > vd[i] ← f[rs1]
+pub(super) fn exec_vfmv_v_f<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    frs1: FloatRegister,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| merge(regs, vd, None, src))
+}
+
+// Vector FP Reverse Divide, Vector-Scalar: Divide the second operand by the elements of vs2.
+//
+// > vd[i] ← f[rs1] ÷ vs2[i]
+pub(super) fn exec_vfrdiv_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| b / a, |a, b| b / a)
+    })
+}
+
+// Vector FP Reduce Maximum: Combine element 0 of vs1 with the active elements of vs2, then write the result to element 0 of vd.
+//
+// > vd[0] ← max(vs1[0], vs2[0], …)
+pub(super) fn exec_vfredmax_vs<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_float_op(hart, inst, |regs| {
+        float_reduction(regs, vd, vs2, vs1, vm, |a, b| a.max(b), |a, b| a.max(b))
+    })
+}
+
+// Vector FP Reduce Minimum: Combine element 0 of vs1 with the active elements of vs2, then write the result to element 0 of vd.
+//
+// > vd[0] ← min(vs1[0], vs2[0], …)
+pub(super) fn exec_vfredmin_vs<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_float_op(hart, inst, |regs| {
+        float_reduction(regs, vd, vs2, vs1, vm, |a, b| a.min(b), |a, b| a.min(b))
+    })
+}
+
+// Vector FP Reduce Ordered Sum: Combine element 0 of vs1 with the active elements of vs2, then write the result to element 0 of vd.
+//
+// > vd[0] ← (vs1[0] + vs2[0]) + …
+pub(super) fn exec_vfredosum_vs<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_float_op(hart, inst, |regs| {
+        float_reduction(regs, vd, vs2, vs1, vm, |a, b| a + b, |a, b| a + b)
+    })
+}
+
+// Vector FP Reduce Unordered Sum: Combine element 0 of vs1 with the active elements of vs2, then write the result to element 0 of vd. Sums are always computed in element order.
+//
+// > vd[0] ← vs1[0] + Σ vs2[i]
+pub(super) fn exec_vfredusum_vs<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_float_op(hart, inst, |regs| {
+        float_reduction(regs, vd, vs2, vs1, vm, |a, b| a + b, |a, b| a + b)
+    })
+}
+
+// Vector FP Reverse Subtract, Vector-Scalar: Subtract the elements of vs2 from the second operand.
+//
+// > vd[i] ← f[rs1] - vs2[i]
+pub(super) fn exec_vfrsub_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| b - a, |a, b| b - a)
+    })
+}
+
+// Vector FP Sign-Injection, Vector-Scalar: Combine the sign of the second operand with the rest of each element of vs2.
+//
+// > vd[i] ← {sign(f[rs1]), vs2[i][SEW-2:0]}
+pub(super) fn exec_vfsgnj_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            let sign = 1 << (sew - 1);
+            (a & !sign) | (b & sign)
+        })
+    })
+}
+
+// Vector FP Sign-Injection, Vector-Vector: Combine the sign of the second operand with the rest of each element of vs2.
+//
+// > vd[i] ← {sign(vs1[i]), vs2[i][SEW-2:0]}
+pub(super) fn exec_vfsgnj_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            let sign = 1 << (sew - 1);
+            (a & !sign) | (b & sign)
+        })
+    })
+}
+
+// Vector FP Sign-Injection Negate, Vector-Scalar: Combine the opposite of the sign of the second operand with the rest of each element of vs2.
+//
+// > vd[i] ← {¬sign(f[rs1]), vs2[i][SEW-2:0]}
+pub(super) fn exec_vfsgnjn_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            let sign = 1 << (sew - 1);
+            (a & !sign) | (!b & sign)
+        })
+    })
+}
+
+// Vector FP Sign-Injection Negate, Vector-Vector: Combine the opposite of the sign of the second operand with the rest of each element of vs2.
+//
+// > vd[i] ← {¬sign(vs1[i]), vs2[i][SEW-2:0]}
+pub(super) fn exec_vfsgnjn_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            let sign = 1 << (sew - 1);
+            (a & !sign) | (!b & sign)
+        })
+    })
+}
+
+// Vector FP Sign-Injection Exclusive Or, Vector-Scalar: Flip the sign of each element of vs2 where the sign of the second operand is set.
+//
+// > vd[i] ← {sign(vs2[i]) ⊕ sign(f[rs1]), vs2[i][SEW-2:0]}
+pub(super) fn exec_vfsgnjx_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            let sign = 1 << (sew - 1);
+            a ^ (b & sign)
+        })
+    })
+}
+
+// Vector FP Sign-Injection Exclusive Or, Vector-Vector: Flip the sign of each element of vs2 where the sign of the second operand is set.
+//
+// > vd[i] ← {sign(vs2[i]) ⊕ sign(vs1[i]), vs2[i][SEW-2:0]}
+pub(super) fn exec_vfsgnjx_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            let sign = 1 << (sew - 1);
+            a ^ (b & sign)
+        })
+    })
+}
+
+// Vector FP Subtract, Vector-Scalar: Subtract the second operand from the elements of vs2.
+//
+// > vd[i] ← vs2[i] - f[rs1]
+pub(super) fn exec_vfsub_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| a - b, |a, b| a - b)
+    })
+}
+
+// Vector FP Subtract, Vector-Vector: Subtract the second operand from the elements of vs2.
+//
+// > vd[i] ← vs2[i] - vs1[i]
+pub(super) fn exec_vfsub_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_binary(regs, vd, vs2, src, vm, |a, b| a - b, |a, b| a - b)
+    })
+}
+
+// Vector Element Index: Write the index of each active element to that element of vd.
+//
+// > vd[i] ← i
+pub(super) fn exec_vid_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| element_index(regs, vd, vm))
+}
+
+// Vector Unit-Stride Load (16-bit): Load the active 16-bit consecutive elements from the address in rs1 into vd.
+//
+// > vd[i] ← M16[rs1 + i × 2]
+pub(super) fn exec_vle16_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Unit, vm, 16)
+}
+
+// Vector Unit-Stride Load (32-bit): Load the active 32-bit consecutive elements from the address in rs1 into vd.
+//
+// > vd[i] ← M32[rs1 + i × 4]
+pub(super) fn exec_vle32_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Unit, vm, 32)
+}
+
+// Vector Unit-Stride Load (64-bit): Load the active 64-bit consecutive elements from the address in rs1 into vd.
+//
+// > vd[i] ← M64[rs1 + i × 8]
+pub(super) fn exec_vle64_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Unit, vm, 64)
+}
+
+// Vector Unit-Stride Load (8-bit): Load the active 8-bit consecutive elements from the address in rs1 into vd.
+//
+// > vd[i] ← M8[rs1 + i × 1]
+pub(super) fn exec_vle8_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Unit, vm, 8)
+}
+
+// Vector Indexed Ordered Load (16-bit Indices): Load the active elements at the byte offsets in vs2 from the address in rs1, in element order into vd.
+//
+// > vd[i] ← M[rs1 + vs2[i]]
+pub(super) fn exec_vloxei16_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Indexed(vs2), vm, 16)
+}
+
+// Vector Indexed Ordered Load (32-bit Indices): Load the active elements at the byte offsets in vs2 from the address in rs1, in element order into vd.
+//
+// > vd[i] ← M[rs1 + vs2[i]]
+pub(super) fn exec_vloxei32_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Indexed(vs2), vm, 32)
+}
+
+// Vector Indexed Ordered Load (64-bit Indices): Load the active elements at the byte offsets in vs2 from the address in rs1, in element order into vd.
+//
+// > vd[i] ← M[rs1 + vs2[i]]
+pub(super) fn exec_vloxei64_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Indexed(vs2), vm, 64)
+}
+
+// Vector Indexed Ordered Load (8-bit Indices): Load the active elements at the byte offsets in vs2 from the address in rs1, in element order into vd.
+//
+// > vd[i] ← M[rs1 + vs2[i]]
+pub(super) fn exec_vloxei8_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Indexed(vs2), vm, 8)
+}
+
+// Vector Strided Load (16-bit): Load the active 16-bit elements spaced rs2 bytes apart starting at the address in rs1 into vd.
+//
+// > vd[i] ← M16[rs1 + i × rs2]
+pub(super) fn exec_vlse16_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let stride = hart.read_int_register(rs2);
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Strided(stride), vm, 16)
+}
+
+// Vector Strided Load (32-bit): Load the active 32-bit elements spaced rs2 bytes apart starting at the address in rs1 into vd.
+//
+// > vd[i] ← M32[rs1 + i × rs2]
+pub(super) fn exec_vlse32_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let stride = hart.read_int_register(rs2);
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Strided(stride), vm, 32)
+}
+
+// Vector Strided Load (64-bit): Load the active 64-bit elements spaced rs2 bytes apart starting at the address in rs1 into vd.
+//
+// > vd[i] ← M64[rs1 + i × rs2]
+pub(super) fn exec_vlse64_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let stride = hart.read_int_register(rs2);
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Strided(stride), vm, 64)
+}
+
+// Vector Strided Load (8-bit): Load the active 8-bit elements spaced rs2 bytes apart starting at the address in rs1 into vd.
+//
+// > vd[i] ← M8[rs1 + i × rs2]
+pub(super) fn exec_vlse8_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let stride = hart.read_int_register(rs2);
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Strided(stride), vm, 8)
+}
+
+// Vector Indexed Unordered Load (16-bit Indices): Load the active elements at the byte offsets in vs2 from the address in rs1 into vd.
+//
+// > vd[i] ← M[rs1 + vs2[i]]
+pub(super) fn exec_vluxei16_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Indexed(vs2), vm, 16)
+}
+
+// Vector Indexed Unordered Load (32-bit Indices): Load the active elements at the byte offsets in vs2 from the address in rs1 into vd.
+//
+// > vd[i] ← M[rs1 + vs2[i]]
+pub(super) fn exec_vluxei32_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Indexed(vs2), vm, 32)
+}
+
+// Vector Indexed Unordered Load (64-bit Indices): Load the active elements at the byte offsets in vs2 from the address in rs1 into vd.
+//
+// > vd[i] ← M[rs1 + vs2[i]]
+pub(super) fn exec_vluxei64_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Indexed(vs2), vm, 64)
+}
+
+// Vector Indexed Unordered Load (8-bit Indices): Load the active elements at the byte offsets in vs2 from the address in rs1 into vd.
+//
+// > vd[i] ← M[rs1 + vs2[i]]
+pub(super) fn exec_vluxei8_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_load(hart, inst, vd, rs1, Addressing::Indexed(vs2), vm, 8)
+}
+
+// Vector Multiply-Add, Vector-Vector: Multiply the elements of vs2 by the first operand, then add the product to vd.
+//
+// > vd[i] ← vs1[i] × vs2[i] + vd[i]
+pub(super) fn exec_vmacc_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        multiply_add(regs, vd, vs2, src, vm, |a, b, d, _| {
+            b.wrapping_mul(a).wrapping_add(d)
+        })
+    })
+}
+
+// Vector Multiply-Add, Vector-Scalar: Multiply the elements of vs2 by the first operand, then add the product to vd.
+//
+// > vd[i] ← x[rs1] × vs2[i] + vd[i]
+pub(super) fn exec_vmacc_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        multiply_add(regs, vd, vs2, src, vm, |a, b, d, _| {
+            b.wrapping_mul(a).wrapping_add(d)
+        })
+    })
+}
+
+// Vector Mask And: Combine the masks in vs2 and vs1 bit by bit, then write the result to the mask in vd.
+//
+// > vd.mask[i] ← vs2.mask[i] ∧ vs1.mask[i]
+pub(super) fn exec_vmand_mm<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        mask_logical(regs, vd, vs2, vs1, |a, b| a && b)
+    })
+}
+
+// Vector Mask And Not: Combine the masks in vs2 and vs1 bit by bit, then write the result to the mask in vd.
+//
+// > vd.mask[i] ← vs2.mask[i] ∧ ¬vs1.mask[i]
+pub(super) fn exec_vmandn_mm<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        mask_logical(regs, vd, vs2, vs1, |a, b| a && !b)
+    })
+}
+
+// Vector Maximum, Vector-Vector: Select the larger of each element of vs2 and the second operand (signed).
+//
+// > vd[i] ← max(sx(vs2[i]), sx(vs1[i]))
+pub(super) fn exec_vmax_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            signed(a, sew).max(signed(b, sew)) as u64
+        })
+    })
+}
+
+// Vector Maximum, Vector-Scalar: Select the larger of each element of vs2 and the second operand (signed).
+//
+// > vd[i] ← max(sx(vs2[i]), sx(x[rs1]))
+pub(super) fn exec_vmax_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            signed(a, sew).max(signed(b, sew)) as u64
+        })
+    })
+}
+
+// Vector Maximum Unsigned, Vector-Vector: Select the larger of each element of vs2 and the second operand (unsigned).
+//
+// > vd[i] ← max(vs2[i], vs1[i])
+pub(super) fn exec_vmaxu_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a.max(b))
+    })
+}
+
+// Vector Maximum Unsigned, Vector-Scalar: Select the larger of each element of vs2 and the second operand (unsigned).
+//
+// > vd[i] ← max(vs2[i], x[rs1])
+pub(super) fn exec_vmaxu_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a.max(b))
+    })
+}
+
+// Vector Merge, Vector-Immediate: Select the immediate for each element where the mask in v0 is set, and the element of vs2 otherwise.
+//
+// > vd[i] ← v0.mask[i] ? simm : vs2[i]
+pub(super) fn exec_vmerge_vim<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    simm: i32,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(simm as u64);
+    exec_vector_op(hart, inst, |regs| merge(regs, vd, Some(vs2), src))
+}
+
+// Vector Merge, Vector-Vector: Select each element from vs1 where the mask in v0 is set, and from vs2 otherwise.
+//
+// > vd[i] ← v0.mask[i] ? vs1[i] : vs2[i]
+pub(super) fn exec_vmerge_vvm<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        merge(regs, vd, Some(vs2), Operand::Vector(vs1))
+    })
+}
+
+// Vector Merge, Vector-Scalar: Select x[rs1] for each element where the mask in v0 is set, and the element of vs2 otherwise.
+//
+// > vd[i] ← v0.mask[i] ? x[rs1] : vs2[i]
+pub(super) fn exec_vmerge_vxm<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| merge(regs, vd, Some(vs2), src))
+}
+
+// Vector FP Set Mask If Equal, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is equal to the second operand.
+//
+// > vd.mask[i] ← vs2[i] = f[rs1]
+pub(super) fn exec_vmfeq_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_compare(regs, vd, vs2, src, vm, |a, b| a == b, |a, b| a == b)
+    })
+}
+
+// Vector FP Set Mask If Equal, Vector-Vector: Set each bit of the mask in vd if the element of vs2 is equal to the second operand.
+//
+// > vd.mask[i] ← vs2[i] = vs1[i]
+pub(super) fn exec_vmfeq_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_compare(regs, vd, vs2, src, vm, |a, b| a == b, |a, b| a == b)
+    })
+}
+
+// Vector FP Set Mask If Greater Than or Equal, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is greater than or equal to the second operand.
+//
+// > vd.mask[i] ← vs2[i] ≥ f[rs1]
+pub(super) fn exec_vmfge_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_compare(regs, vd, vs2, src, vm, |a, b| a >= b, |a, b| a >= b)
+    })
+}
+
+// Vector FP Set Mask If Greater Than, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is greater than the second operand.
+//
+// > vd.mask[i] ← vs2[i] > f[rs1]
+pub(super) fn exec_vmfgt_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_compare(regs, vd, vs2, src, vm, |a, b| a > b, |a, b| a > b)
+    })
+}
+
+// Vector FP Set Mask If Less Than or Equal, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is less than or equal to the second operand.
+//
+// > vd.mask[i] ← vs2[i] ≤ f[rs1]
+pub(super) fn exec_vmfle_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_compare(regs, vd, vs2, src, vm, |a, b| a <= b, |a, b| a <= b)
+    })
+}
+
+// Vector FP Set Mask If Less Than or Equal, Vector-Vector: Set each bit of the mask in vd if the element of vs2 is less than or equal to the second operand.
+//
+// > vd.mask[i] ← vs2[i] ≤ vs1[i]
+pub(super) fn exec_vmfle_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_compare(regs, vd, vs2, src, vm, |a, b| a <= b, |a, b| a <= b)
+    })
+}
+
+// Vector FP Set Mask If Less Than, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is less than the second operand.
+//
+// > vd.mask[i] ← vs2[i] < f[rs1]
+pub(super) fn exec_vmflt_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_compare(regs, vd, vs2, src, vm, |a, b| a < b, |a, b| a < b)
+    })
+}
+
+// Vector FP Set Mask If Less Than, Vector-Vector: Set each bit of the mask in vd if the element of vs2 is less than the second operand.
+//
+// > vd.mask[i] ← vs2[i] < vs1[i]
+pub(super) fn exec_vmflt_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_compare(regs, vd, vs2, src, vm, |a, b| a < b, |a, b| a < b)
+    })
+}
+
+// Vector FP Set Mask If Not Equal, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is not equal to the second operand.
+//
+// > vd.mask[i] ← vs2[i] ≠ f[rs1]
+pub(super) fn exec_vmfne_vf<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    frs1: FloatRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = float_operand(hart, frs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_compare(regs, vd, vs2, src, vm, |a, b| a != b, |a, b| a != b)
+    })
+}
+
+// Vector FP Set Mask If Not Equal, Vector-Vector: Set each bit of the mask in vd if the element of vs2 is not equal to the second operand.
+//
+// > vd.mask[i] ← vs2[i] ≠ vs1[i]
+pub(super) fn exec_vmfne_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_float_op(hart, inst, |regs| {
+        float_compare(regs, vd, vs2, src, vm, |a, b| a != b, |a, b| a != b)
+    })
+}
+
+// Vector Minimum, Vector-Vector: Select the smaller of each element of vs2 and the second operand (signed).
+//
+// > vd[i] ← min(sx(vs2[i]), sx(vs1[i]))
+pub(super) fn exec_vmin_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            signed(a, sew).min(signed(b, sew)) as u64
+        })
+    })
+}
+
+// Vector Minimum, Vector-Scalar: Select the smaller of each element of vs2 and the second operand (signed).
+//
+// > vd[i] ← min(sx(vs2[i]), sx(x[rs1]))
+pub(super) fn exec_vmin_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            signed(a, sew).min(signed(b, sew)) as u64
+        })
+    })
+}
+
+// Vector Minimum Unsigned, Vector-Vector: Select the smaller of each element of vs2 and the second operand (unsigned).
+//
+// > vd[i] ← min(vs2[i], vs1[i])
+pub(super) fn exec_vminu_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a.min(b))
+    })
+}
+
+// Vector Minimum Unsigned, Vector-Scalar: Select the smaller of each element of vs2 and the second operand (unsigned).
+//
+// > vd[i] ← min(vs2[i], x[rs1])
+pub(super) fn exec_vminu_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a.min(b))
+    })
+}
+
+// Vector Mask Not And: Combine the masks in vs2 and vs1 bit by bit, then write the result to the mask in vd.
+//
+// > vd.mask[i] ← ¬(vs2.mask[i]   vs1.mask[i])
+pub(super) fn exec_vmnand_mm<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        mask_logical(regs, vd, vs2, vs1, |a, b| !(a && b))
+    })
+}
+
+// Vector Mask Not Or: Combine the masks in vs2 and vs1 bit by bit, then write the result to the mask in vd.
+//
+// > vd.mask[i] ← ¬(vs2.mask[i]   vs1.mask[i])
+pub(super) fn exec_vmnor_mm<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        mask_logical(regs, vd, vs2, vs1, |a, b| !(a || b))
+    })
+}
+
+// Vector Mask Or: Combine the masks in vs2 and vs1 bit by bit, then write the result to the mask in vd.
+//
+// > vd.mask[i] ← vs2.mask[i] ∨ vs1.mask[i]
+pub(super) fn exec_vmor_mm<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        mask_logical(regs, vd, vs2, vs1, |a, b| a || b)
+    })
+}
+
+// Vector Mask Or Not: Combine the masks in vs2 and vs1 bit by bit, then write the result to the mask in vd.
+//
+// > vd.mask[i] ← vs2.mask[i] ∨ ¬vs1.mask[i]
+pub(super) fn exec_vmorn_mm<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        mask_logical(regs, vd, vs2, vs1, |a, b| a || !b)
+    })
+}
+
+// Vector Set Mask If Equal, Vector-Immediate: Set each bit of the mask in vd if the element of vs2 is equal to the second operand.
+//
+// > vd.mask[i] ← vs2[i] = simm
+pub(super) fn exec_vmseq_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    simm: i32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(simm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, _| a == b)
+    })
+}
+
+// Vector Set Mask If Equal, Vector-Vector: Set each bit of the mask in vd if the element of vs2 is equal to the second operand.
+//
+// > vd.mask[i] ← vs2[i] = vs1[i]
+pub(super) fn exec_vmseq_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, _| a == b)
+    })
+}
+
+// Vector Set Mask If Equal, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is equal to the second operand.
+//
+// > vd.mask[i] ← vs2[i] = x[rs1]
+pub(super) fn exec_vmseq_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, _| a == b)
+    })
+}
+
+// Vector Set Mask If Greater Than, Vector-Immediate: Set each bit of the mask in vd if the element of vs2 is greater than the second operand.
+//
+// > vd.mask[i] ← sx(vs2[i]) > sx(simm)
+pub(super) fn exec_vmsgt_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    simm: i32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(simm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, sew| {
+            signed(a, sew) > signed(b, sew)
+        })
+    })
+}
+
+// Vector Set Mask If Greater Than, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is greater than the second operand.
+//
+// > vd.mask[i] ← sx(vs2[i]) > sx(x[rs1])
+pub(super) fn exec_vmsgt_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, sew| {
+            signed(a, sew) > signed(b, sew)
+        })
+    })
+}
+
+// Vector Set Mask If Greater Than Unsigned, Vector-Immediate: Set each bit of the mask in vd if the element of vs2 is greater than (unsigned) the second operand.
+//
+// > vd.mask[i] ← vs2[i] > simm
+pub(super) fn exec_vmsgtu_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    simm: i32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(simm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, _| a > b)
+    })
+}
+
+// Vector Set Mask If Greater Than Unsigned, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is greater than (unsigned) the second operand.
+//
+// > vd.mask[i] ← vs2[i] > x[rs1]
+pub(super) fn exec_vmsgtu_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, _| a > b)
+    })
+}
+
+// Vector Set Mask If Less Than or Equal, Vector-Immediate: Set each bit of the mask in vd if the element of vs2 is less than or equal to the second operand.
+//
+// > vd.mask[i] ← sx(vs2[i]) ≤ sx(simm)
+pub(super) fn exec_vmsle_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    simm: i32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(simm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, sew| {
+            signed(a, sew) <= signed(b, sew)
+        })
+    })
+}
+
+// Vector Set Mask If Less Than or Equal, Vector-Vector: Set each bit of the mask in vd if the element of vs2 is less than or equal to the second operand.
+//
+// > vd.mask[i] ← sx(vs2[i]) ≤ sx(vs1[i])
+pub(super) fn exec_vmsle_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, sew| {
+            signed(a, sew) <= signed(b, sew)
+        })
+    })
+}
+
+// Vector Set Mask If Less Than or Equal, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is less than or equal to the second operand.
+//
+// > vd.mask[i] ← sx(vs2[i]) ≤ sx(x[rs1])
+pub(super) fn exec_vmsle_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, sew| {
+            signed(a, sew) <= signed(b, sew)
+        })
+    })
+}
+
+// Vector Set Mask If Less Than or Equal Unsigned, Vector-Immediate: Set each bit of the mask in vd if the element of vs2 is less than or equal to (unsigned) the second operand.
+//
+// > vd.mask[i] ← vs2[i] ≤ simm
+pub(super) fn exec_vmsleu_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    simm: i32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(simm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, _| a <= b)
+    })
+}
+
+// Vector Set Mask If Less Than or Equal Unsigned, Vector-Vector: Set each bit of the mask in vd if the element of vs2 is less than or equal to (unsigned) the second operand.
+//
+// > vd.mask[i] ← vs2[i] ≤ vs1[i]
+pub(super) fn exec_vmsleu_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, _| a <= b)
+    })
+}
+
+// Vector Set Mask If Less Than or Equal Unsigned, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is less than or equal to (unsigned) the second operand.
+//
+// > vd.mask[i] ← vs2[i] ≤ x[rs1]
+pub(super) fn exec_vmsleu_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, _| a <= b)
+    })
+}
+
+// Vector Set Mask If Less Than, Vector-Vector: Set each bit of the mask in vd if the element of vs2 is less than the second operand.
+//
+// > vd.mask[i] ← sx(vs2[i]) < sx(vs1[i])
+pub(super) fn exec_vmslt_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, sew| {
+            signed(a, sew) < signed(b, sew)
+        })
+    })
+}
+
+// Vector Set Mask If Less Than, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is less than the second operand.
+//
+// > vd.mask[i] ← sx(vs2[i]) < sx(x[rs1])
+pub(super) fn exec_vmslt_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, sew| {
+            signed(a, sew) < signed(b, sew)
+        })
+    })
+}
+
+// Vector Set Mask If Less Than Unsigned, Vector-Vector: Set each bit of the mask in vd if the element of vs2 is less than (unsigned) the second operand.
+//
+// > vd.mask[i] ← vs2[i] < vs1[i]
+pub(super) fn exec_vmsltu_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, _| a < b)
+    })
+}
+
+// Vector Set Mask If Less Than Unsigned, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is less than (unsigned) the second operand.
+//
+// > vd.mask[i] ← vs2[i] < x[rs1]
+pub(super) fn exec_vmsltu_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, _| a < b)
+    })
+}
+
+// Vector Set Mask If Not Equal, Vector-Immediate: Set each bit of the mask in vd if the element of vs2 is not equal to the second operand.
+//
+// > vd.mask[i] ← vs2[i] ≠ simm
+pub(super) fn exec_vmsne_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    simm: i32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(simm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, _| a != b)
+    })
+}
+
+// Vector Set Mask If Not Equal, Vector-Vector: Set each bit of the mask in vd if the element of vs2 is not equal to the second operand.
+//
+// > vd.mask[i] ← vs2[i] ≠ vs1[i]
+pub(super) fn exec_vmsne_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, _| a != b)
+    })
+}
+
+// Vector Set Mask If Not Equal, Vector-Scalar: Set each bit of the mask in vd if the element of vs2 is not equal to the second operand.
+//
+// > vd.mask[i] ← vs2[i] ≠ x[rs1]
+pub(super) fn exec_vmsne_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        compare(regs, vd, vs2, src, vm, |a, b, _| a != b)
+    })
+}
+
+// Vector Multiply, Vector-Vector: Multiply the elements of vs2 by the second operand, keeping the low half of each product.
+//
+// > vd[i] ← ux(vs2[i]) × ux(vs1[i])
+pub(super) fn exec_vmul_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a.wrapping_mul(b))
+    })
+}
+
+// Vector Multiply, Vector-Scalar: Multiply the elements of vs2 by the second operand, keeping the low half of each product.
+//
+// > vd[i] ← ux(vs2[i]) × ux(x[rs1])
+pub(super) fn exec_vmul_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a.wrapping_mul(b))
+    })
+}
+
+// Vector Multiply High, Vector-Vector: Multiply the elements of vs2 by the second operand (signed), keeping the high half of each product.
+//
+// > vd[i] ← (sx(vs2[i]) × sx(vs1[i])) » SEW
+pub(super) fn exec_vmulh_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            ((signed(a, sew) as i128 * signed(b, sew) as i128) >> sew) as u64
+        })
+    })
+}
+
+// Vector Multiply High, Vector-Scalar: Multiply the elements of vs2 by the second operand (signed), keeping the high half of each product.
+//
+// > vd[i] ← (sx(vs2[i]) × sx(x[rs1])) » SEW
+pub(super) fn exec_vmulh_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            ((signed(a, sew) as i128 * signed(b, sew) as i128) >> sew) as u64
+        })
+    })
+}
+
+// Vector Multiply High Signed Unsigned, Vector-Vector: Multiply the signed elements of vs2 by the unsigned second operand, keeping the high half of each product.
+//
+// > vd[i] ← (sx(vs2[i]) × ux(vs1[i])) » SEW
+pub(super) fn exec_vmulhsu_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            ((signed(a, sew) as i128 * b as i128) >> sew) as u64
+        })
+    })
+}
+
+// Vector Multiply High Signed Unsigned, Vector-Scalar: Multiply the signed elements of vs2 by the unsigned second operand, keeping the high half of each product.
+//
+// > vd[i] ← (sx(vs2[i]) × ux(x[rs1])) » SEW
+pub(super) fn exec_vmulhsu_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            ((signed(a, sew) as i128 * b as i128) >> sew) as u64
+        })
+    })
+}
+
+// Vector Multiply High Unsigned, Vector-Vector: Multiply the elements of vs2 by the second operand (unsigned), keeping the high half of each product.
+//
+// > vd[i] ← (ux(vs2[i]) × ux(vs1[i])) » SEW
+pub(super) fn exec_vmulhu_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            ((a as u128 * b as u128) >> sew) as u64
+        })
+    })
+}
+
+// Vector Multiply High Unsigned, Vector-Scalar: Multiply the elements of vs2 by the second operand (unsigned), keeping the high half of each product.
+//
+// > vd[i] ← (ux(vs2[i]) × ux(x[rs1])) » SEW
+pub(super) fn exec_vmulhu_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            ((a as u128 * b as u128) >> sew) as u64
+        })
+    })
+}
+
+// Vector Move from Integer: Write x[rs1] to element 0 of vd, unless vl is zero.
+//
+// > vd[0] ← x[rs1]
+pub(super) fn exec_vmv_s_x<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| move_to_first(regs, vd, src))
+}
+
+// Vector Move, Immediate: Copy the immediate to every element of vd.
+//
+// > vd[i] ← simm
+pub(super) fn exec_vmv_v_i<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    simm: i32,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(simm as u64);
+    exec_vector_op(hart, inst, |regs| merge(regs, vd, None, src))
+}
+
+// Vector Move, Vector: Copy the elements of vs1 to every element of vd.
+//
+// > vd[i] ← vs1[i]
+pub(super) fn exec_vmv_v_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs1: VectorRegister,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| merge(regs, vd, None, src))
+}
+
+// Vector Move, Scalar: Copy x[rs1] to every element of vd.
+//
+// > vd[i] ← x[rs1]
+pub(super) fn exec_vmv_v_x<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| merge(regs, vd, None, src))
+}
+
+// Vector Move to Integer: Sign-extend element 0 of vs2 to XLEN bits, then write it to rd.
+//
+// > rd ← sx(vs2[0])
+pub(super) fn exec_vmv_x_s<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    vs2: VectorRegister,
+) -> ExecStatus<u32> {
+    let mut v = 0;
+    let status = exec_vector_op(hart, inst, |regs| {
+        let sew = regs.sew();
+        v = signed(regs.element(vs2, 0, sew), sew) as u32;
+        true
+    });
+    if let ExecStatus::Running = status {
+        hart.write_int_register(rd, v);
+    }
+    status
+}
+
+// Vector Mask Exclusive Nor: Combine the masks in vs2 and vs1 bit by bit, then write the result to the mask in vd.
+//
+// > vd.mask[i] ← ¬(vs2.mask[i]   vs1.mask[i])
+pub(super) fn exec_vmxnor_mm<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        mask_logical(regs, vd, vs2, vs1, |a, b| a == b)
+    })
+}
+
+// Vector Mask Exclusive Or: Combine the masks in vs2 and vs1 bit by bit, then write the result to the mask in vd.
+//
+// > vd.mask[i] ← vs2.mask[i] ⊕ vs1.mask[i]
+pub(super) fn exec_vmxor_mm<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        mask_logical(regs, vd, vs2, vs1, |a, b| a != b)
+    })
+}
+
+// Vector Or, Vector-Immediate: Bitwise or the elements of vs2 with the second operand.
+//
+// > vd[i] ← vs2[i] ∨ simm
+pub(super) fn exec_vor_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    simm: i32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(simm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a | b)
+    })
+}
+
+// Vector Or, Vector-Vector: Bitwise or the elements of vs2 with the second operand.
+//
+// > vd[i] ← vs2[i] ∨ vs1[i]
+pub(super) fn exec_vor_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a | b)
+    })
+}
+
+// Vector Or, Vector-Scalar: Bitwise or the elements of vs2 with the second operand.
+//
+// > vd[i] ← vs2[i] ∨ x[rs1]
+pub(super) fn exec_vor_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a | b)
+    })
+}
+
+// Vector Reduce And: Combine element 0 of vs1 with the active elements of vs2 (and), then write the result to element 0 of vd.
+//
+// > vd[0] ← vs1[0] ∧ vs2[0] ∧ …
+pub(super) fn exec_vredand_vs<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        reduction(regs, vd, vs2, vs1, vm, |a, b, _| a & b)
+    })
+}
+
+// Vector Reduce Maximum: Combine element 0 of vs1 with the active elements of vs2 (maximum), then write the result to element 0 of vd.
+//
+// > vd[0] ← max(sx(vs1[0]), sx(vs2[0]), …)
+pub(super) fn exec_vredmax_vs<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        reduction(regs, vd, vs2, vs1, vm, |a, b, sew| {
+            signed(a, sew).max(signed(b, sew)) as u64
+        })
+    })
+}
+
+// Vector Reduce Maximum Unsigned: Combine element 0 of vs1 with the active elements of vs2 (maximum unsigned), then write the result to element 0 of vd.
+//
+// > vd[0] ← max(vs1[0], vs2[0], …)
+pub(super) fn exec_vredmaxu_vs<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        reduction(regs, vd, vs2, vs1, vm, |a, b, _| a.max(b))
+    })
+}
+
+// Vector Reduce Minimum: Combine element 0 of vs1 with the active elements of vs2 (minimum), then write the result to element 0 of vd.
+//
+// > vd[0] ← min(sx(vs1[0]), sx(vs2[0]), …)
+pub(super) fn exec_vredmin_vs<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        reduction(regs, vd, vs2, vs1, vm, |a, b, sew| {
+            signed(a, sew).min(signed(b, sew)) as u64
+        })
+    })
+}
+
+// Vector Reduce Minimum Unsigned: Combine element 0 of vs1 with the active elements of vs2 (minimum unsigned), then write the result to element 0 of vd.
+//
+// > vd[0] ← min(vs1[0], vs2[0], …)
+pub(super) fn exec_vredminu_vs<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        reduction(regs, vd, vs2, vs1, vm, |a, b, _| a.min(b))
+    })
+}
+
+// Vector Reduce Or: Combine element 0 of vs1 with the active elements of vs2 (or), then write the result to element 0 of vd.
+//
+// > vd[0] ← vs1[0] ∨ vs2[0] ∨ …
+pub(super) fn exec_vredor_vs<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        reduction(regs, vd, vs2, vs1, vm, |a, b, _| a | b)
+    })
+}
+
+// Vector Reduce Sum: Combine element 0 of vs1 with the active elements of vs2 (sum), then write the result to element 0 of vd.
+//
+// > vd[0] ← vs1[0] + Σ vs2[i]
+pub(super) fn exec_vredsum_vs<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        reduction(regs, vd, vs2, vs1, vm, |a, b, _| a.wrapping_add(b))
+    })
+}
+
+// Vector Reduce Exclusive Or: Combine element 0 of vs1 with the active elements of vs2 (exclusive or), then write the result to element 0 of vd.
+//
+// > vd[0] ← vs1[0] ⊕ vs2[0] ⊕ …
+pub(super) fn exec_vredxor_vs<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| {
+        reduction(regs, vd, vs2, vs1, vm, |a, b, _| a ^ b)
+    })
+}
+
+// Vector Remainder, Vector-Vector: Divide the elements of vs2 by the second operand (signed), keeping the remainder.
+//
+// > vd[i] ← sx(vs2[i]) mod sx(vs1[i])
+pub(super) fn exec_vrem_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| match signed(b, sew) {
+            0 => a,
+            b => signed(a, sew).wrapping_rem(b) as u64,
+        })
+    })
+}
+
+// Vector Remainder, Vector-Scalar: Divide the elements of vs2 by the second operand (signed), keeping the remainder.
+//
+// > vd[i] ← sx(vs2[i]) mod sx(x[rs1])
+pub(super) fn exec_vrem_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| match signed(b, sew) {
+            0 => a,
+            b => signed(a, sew).wrapping_rem(b) as u64,
+        })
+    })
+}
+
+// Vector Remainder Unsigned, Vector-Vector: Divide the elements of vs2 by the second operand (unsigned), keeping the remainder.
+//
+// > vd[i] ← ux(vs2[i]) mod ux(vs1[i])
+pub(super) fn exec_vremu_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| {
+            a.checked_rem(b).unwrap_or(a)
+        })
+    })
+}
+
+// Vector Remainder Unsigned, Vector-Scalar: Divide the elements of vs2 by the second operand (unsigned), keeping the remainder.
+//
+// > vd[i] ← ux(vs2[i]) mod ux(x[rs1])
+pub(super) fn exec_vremu_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| {
+            a.checked_rem(b).unwrap_or(a)
+        })
+    })
+}
+
+// Vector Register Gather, Vector-Immediate: Copy the element of vs2 selected by the index to every element of vd, or zero for an out-of-range index.
+//
+// > vd[i] ← uimm < VLMAX ? vs2[uimm] : 0
+pub(super) fn exec_vrgather_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    uimm: u32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let index = uimm as u64;
+    exec_vector_op(hart, inst, |regs| gather_scalar(regs, vd, vs2, index, vm))
+}
+
+// Vector Register Gather, Vector-Vector: Copy the elements of vs2 selected by the indices in vs1, or zero for out-of-range indices.
+//
+// > vd[i] ← vs1[i] < VLMAX ? vs2[vs1[i]] : 0
+pub(super) fn exec_vrgather_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_op(hart, inst, |regs| gather(regs, vd, vs2, vs1, vm))
+}
+
+// Vector Register Gather, Vector-Scalar: Copy the element of vs2 selected by the index to every element of vd, or zero for an out-of-range index.
+//
+// > vd[i] ← x[rs1] < VLMAX ? vs2[x[rs1]] : 0
+pub(super) fn exec_vrgather_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let index = hart.read_int_register(rs1) as u64;
+    exec_vector_op(hart, inst, |regs| gather_scalar(regs, vd, vs2, index, vm))
+}
+
+// Vector Reverse Subtract, Vector-Immediate: Subtract the elements of vs2 from the second operand.
+//
+// > vd[i] ← simm - vs2[i]
+pub(super) fn exec_vrsub_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    simm: i32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(simm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| b.wrapping_sub(a))
+    })
+}
+
+// Vector Reverse Subtract, Vector-Scalar: Subtract the elements of vs2 from the second operand.
+//
+// > vd[i] ← x[rs1] - vs2[i]
+pub(super) fn exec_vrsub_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| b.wrapping_sub(a))
+    })
+}
+
+// Vector Unit-Stride Store (16-bit): Store the active 16-bit consecutive elements from the address in rs1 from vs3.
+//
+// > M16[rs1 + i × 2] ← vs3[i]
+pub(super) fn exec_vse16_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Unit, vm, 16)
+}
+
+// Vector Unit-Stride Store (32-bit): Store the active 32-bit consecutive elements from the address in rs1 from vs3.
+//
+// > M32[rs1 + i × 4] ← vs3[i]
+pub(super) fn exec_vse32_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Unit, vm, 32)
+}
+
+// Vector Unit-Stride Store (64-bit): Store the active 64-bit consecutive elements from the address in rs1 from vs3.
+//
+// > M64[rs1 + i × 8] ← vs3[i]
+pub(super) fn exec_vse64_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Unit, vm, 64)
+}
+
+// Vector Unit-Stride Store (8-bit): Store the active 8-bit consecutive elements from the address in rs1 from vs3.
+//
+// > M8[rs1 + i × 1] ← vs3[i]
+pub(super) fn exec_vse8_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Unit, vm, 8)
+}
+
+// Vector Set Length Immediate AVL: Set vtype from the immediate and vl from the immediate application vector length, then write vl to rd.
+//
+// > vtype ← vtypei ; vl ← min(uimm, VLMAX) ; rd ← vl
+pub(super) fn exec_vsetivli<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    uimm: u32,
+    vtypei: u32,
+) -> ExecStatus<u32> {
+    exec_vector_config(hart, inst, rd, uimm, vtypei)
+}
+
+// Vector Set Length: Set vtype from rs2 and vl from the application vector length in rs1, then write vl to rd.
+//
+// > vtype ← rs2 ; vl ← min(avl, VLMAX) ; rd ← vl
+pub(super) fn exec_vsetvl<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+) -> ExecStatus<u32> {
+    let avl = register_avl(hart, rd, rs1);
+    let vtype = hart.read_int_register(rs2);
+    exec_vector_config(hart, inst, rd, avl, vtype)
+}
+
+// Vector Set Length Immediate: Set vtype from the immediate and vl from the application vector length in rs1, then write vl to rd.
+//
+// > vtype ← vtypei ; vl ← min(avl, VLMAX) ; rd ← vl
+pub(super) fn exec_vsetvli<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    rs1: IntRegister,
+    vtypei: u32,
+) -> ExecStatus<u32> {
+    let avl = register_avl(hart, rd, rs1);
+    exec_vector_config(hart, inst, rd, avl, vtypei)
+}
+
+// Vector Slide Down by One: Copy the elements of vs2 to the previous element of vd, and x[rs1] to the last element.
+//
+// > vd[i] ← vs2[i + 1] ; vd[vl - 1] ← x[rs1]
+pub(super) fn exec_vslide1down_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| slide_1down(regs, vd, vs2, src, vm))
+}
+
+// Vector Slide Up by One: Copy the elements of vs2 to the next element of vd, and x[rs1] to element 0.
+//
+// > vd[0] ← x[rs1] ; vd[i + 1] ← vs2[i]
+pub(super) fn exec_vslide1up_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| slide_1up(regs, vd, vs2, src, vm))
+}
+
+// Vector Slide Down, Vector-Immediate: Copy the elements of vs2 that are the offset higher to the elements of vd.
+//
+// > vd[i] ← vs2[i + uimm]
+pub(super) fn exec_vslidedown_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    uimm: u32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let offset = uimm as u64;
+    exec_vector_op(hart, inst, |regs| slide_down(regs, vd, vs2, offset, vm))
+}
+
+// Vector Slide Down, Vector-Scalar: Copy the elements of vs2 that are the offset higher to the elements of vd.
+//
+// > vd[i] ← vs2[i + x[rs1]]
+pub(super) fn exec_vslidedown_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let offset = hart.read_int_register(rs1) as u64;
+    exec_vector_op(hart, inst, |regs| slide_down(regs, vd, vs2, offset, vm))
+}
+
+// Vector Slide Up, Vector-Immediate: Copy the elements of vs2 to the elements of vd that are the offset higher.
+//
+// > vd[i + uimm] ← vs2[i]
+pub(super) fn exec_vslideup_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    uimm: u32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let offset = uimm as u64;
+    exec_vector_op(hart, inst, |regs| slide_up(regs, vd, vs2, offset, vm))
+}
+
+// Vector Slide Up, Vector-Scalar: Copy the elements of vs2 to the elements of vd that are the offset higher.
+//
+// > vd[i + x[rs1]] ← vs2[i]
+pub(super) fn exec_vslideup_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let offset = hart.read_int_register(rs1) as u64;
+    exec_vector_op(hart, inst, |regs| slide_up(regs, vd, vs2, offset, vm))
+}
+
+// Vector Shift Left Logical, Vector-Immediate: Shift the elements of vs2 left by the second operand.
+//
+// > vd[i] ← vs2[i] « (uimm mod SEW)
+pub(super) fn exec_vsll_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    uimm: u32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(uimm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            a << (b & (sew as u64 - 1))
+        })
+    })
+}
+
+// Vector Shift Left Logical, Vector-Vector: Shift the elements of vs2 left by the second operand.
+//
+// > vd[i] ← vs2[i] « (vs1[i] mod SEW)
+pub(super) fn exec_vsll_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            a << (b & (sew as u64 - 1))
+        })
+    })
+}
+
+// Vector Shift Left Logical, Vector-Scalar: Shift the elements of vs2 left by the second operand.
+//
+// > vd[i] ← vs2[i] « (x[rs1] mod SEW)
+pub(super) fn exec_vsll_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            a << (b & (sew as u64 - 1))
+        })
+    })
+}
+
+// Vector Indexed Ordered Store (16-bit Indices): Store the active elements at the byte offsets in vs2 from the address in rs1, in element order from vs3.
+//
+// > M[rs1 + vs2[i]] ← vs3[i]
+pub(super) fn exec_vsoxei16_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Indexed(vs2), vm, 16)
+}
+
+// Vector Indexed Ordered Store (32-bit Indices): Store the active elements at the byte offsets in vs2 from the address in rs1, in element order from vs3.
+//
+// > M[rs1 + vs2[i]] ← vs3[i]
+pub(super) fn exec_vsoxei32_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Indexed(vs2), vm, 32)
+}
+
+// Vector Indexed Ordered Store (64-bit Indices): Store the active elements at the byte offsets in vs2 from the address in rs1, in element order from vs3.
+//
+// > M[rs1 + vs2[i]] ← vs3[i]
+pub(super) fn exec_vsoxei64_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Indexed(vs2), vm, 64)
+}
+
+// Vector Indexed Ordered Store (8-bit Indices): Store the active elements at the byte offsets in vs2 from the address in rs1, in element order from vs3.
+//
+// > M[rs1 + vs2[i]] ← vs3[i]
+pub(super) fn exec_vsoxei8_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Indexed(vs2), vm, 8)
+}
+
+// Vector Shift Right Arithmetic, Vector-Immediate: Shift the elements of vs2 right by the second operand, shifting in copies of the sign bit.
+//
+// > vd[i] ← sx(vs2[i]) » (uimm mod SEW)
+pub(super) fn exec_vsra_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    uimm: u32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(uimm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            (signed(a, sew) >> (b & (sew as u64 - 1))) as u64
+        })
+    })
+}
+
+// Vector Shift Right Arithmetic, Vector-Vector: Shift the elements of vs2 right by the second operand, shifting in copies of the sign bit.
+//
+// > vd[i] ← sx(vs2[i]) » (vs1[i] mod SEW)
+pub(super) fn exec_vsra_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            (signed(a, sew) >> (b & (sew as u64 - 1))) as u64
+        })
+    })
+}
+
+// Vector Shift Right Arithmetic, Vector-Scalar: Shift the elements of vs2 right by the second operand, shifting in copies of the sign bit.
+//
+// > vd[i] ← sx(vs2[i]) » (x[rs1] mod SEW)
+pub(super) fn exec_vsra_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            (signed(a, sew) >> (b & (sew as u64 - 1))) as u64
+        })
+    })
+}
+
+// Vector Shift Right Logical, Vector-Immediate: Shift the elements of vs2 right by the second operand, shifting in zeros.
+//
+// > vd[i] ← vs2[i] » (uimm mod SEW)
+pub(super) fn exec_vsrl_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    uimm: u32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(uimm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            a >> (b & (sew as u64 - 1))
+        })
+    })
+}
+
+// Vector Shift Right Logical, Vector-Vector: Shift the elements of vs2 right by the second operand, shifting in zeros.
+//
+// > vd[i] ← vs2[i] » (vs1[i] mod SEW)
+pub(super) fn exec_vsrl_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            a >> (b & (sew as u64 - 1))
+        })
+    })
+}
+
+// Vector Shift Right Logical, Vector-Scalar: Shift the elements of vs2 right by the second operand, shifting in zeros.
+//
+// > vd[i] ← vs2[i] » (x[rs1] mod SEW)
+pub(super) fn exec_vsrl_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, sew| {
+            a >> (b & (sew as u64 - 1))
+        })
+    })
+}
+
+// Vector Strided Store (16-bit): Store the active 16-bit elements spaced rs2 bytes apart starting at the address in rs1 from vs3.
+//
+// > M16[rs1 + i × rs2] ← vs3[i]
+pub(super) fn exec_vsse16_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let stride = hart.read_int_register(rs2);
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Strided(stride), vm, 16)
+}
+
+// Vector Strided Store (32-bit): Store the active 32-bit elements spaced rs2 bytes apart starting at the address in rs1 from vs3.
+//
+// > M32[rs1 + i × rs2] ← vs3[i]
+pub(super) fn exec_vsse32_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let stride = hart.read_int_register(rs2);
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Strided(stride), vm, 32)
+}
+
+// Vector Strided Store (64-bit): Store the active 64-bit elements spaced rs2 bytes apart starting at the address in rs1 from vs3.
+//
+// > M64[rs1 + i × rs2] ← vs3[i]
+pub(super) fn exec_vsse64_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let stride = hart.read_int_register(rs2);
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Strided(stride), vm, 64)
+}
+
+// Vector Strided Store (8-bit): Store the active 8-bit elements spaced rs2 bytes apart starting at the address in rs1 from vs3.
+//
+// > M8[rs1 + i × rs2] ← vs3[i]
+pub(super) fn exec_vsse8_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let stride = hart.read_int_register(rs2);
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Strided(stride), vm, 8)
+}
+
+// Vector Subtract, Vector-Vector: Subtract the second operand from the elements of vs2.
+//
+// > vd[i] ← vs2[i] - vs1[i]
+pub(super) fn exec_vsub_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a.wrapping_sub(b))
+    })
+}
+
+// Vector Subtract, Vector-Scalar: Subtract the second operand from the elements of vs2.
+//
+// > vd[i] ← vs2[i] - x[rs1]
+pub(super) fn exec_vsub_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a.wrapping_sub(b))
+    })
+}
+
+// Vector Indexed Unordered Store (16-bit Indices): Store the active elements at the byte offsets in vs2 from the address in rs1 from vs3.
+//
+// > M[rs1 + vs2[i]] ← vs3[i]
+pub(super) fn exec_vsuxei16_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Indexed(vs2), vm, 16)
+}
+
+// Vector Indexed Unordered Store (32-bit Indices): Store the active elements at the byte offsets in vs2 from the address in rs1 from vs3.
+//
+// > M[rs1 + vs2[i]] ← vs3[i]
+pub(super) fn exec_vsuxei32_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Indexed(vs2), vm, 32)
+}
+
+// Vector Indexed Unordered Store (64-bit Indices): Store the active elements at the byte offsets in vs2 from the address in rs1 from vs3.
+//
+// > M[rs1 + vs2[i]] ← vs3[i]
+pub(super) fn exec_vsuxei64_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Indexed(vs2), vm, 64)
+}
+
+// Vector Indexed Unordered Store (8-bit Indices): Store the active elements at the byte offsets in vs2 from the address in rs1 from vs3.
+//
+// > M[rs1 + vs2[i]] ← vs3[i]
+pub(super) fn exec_vsuxei8_v<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    vs2: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    exec_vector_store(hart, inst, vs3, rs1, Addressing::Indexed(vs2), vm, 8)
+}
+
+// Vector Exclusive Or, Vector-Immediate: Bitwise exclusive or the elements of vs2 with the second operand.
+//
+// > vd[i] ← vs2[i] ⊕ simm
+pub(super) fn exec_vxor_vi<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    simm: i32,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Scalar(simm as u64);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a ^ b)
+    })
+}
+
+// Vector Exclusive Or, Vector-Vector: Bitwise exclusive or the elements of vs2 with the second operand.
+//
+// > vd[i] ← vs2[i] ⊕ vs1[i]
+pub(super) fn exec_vxor_vv<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = Operand::Vector(vs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a ^ b)
+    })
+}
+
+// Vector Exclusive Or, Vector-Scalar: Bitwise exclusive or the elements of vs2 with the second operand.
+//
+// > vd[i] ← vs2[i] ⊕ x[rs1]
+pub(super) fn exec_vxor_vx<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    rs1: IntRegister,
+    vm: bool,
+) -> ExecStatus<u32> {
+    let src = int_operand(hart, rs1);
+    exec_vector_op(hart, inst, |regs| {
+        binary(regs, vd, vs2, src, vm, |a, b, _| a ^ b)
+    })
+}
+
+// Runs an instruction that only touches the vector state. The callback
+// returns false if the instruction's operands are illegal under the
+// current vtype, in which case it must not have changed anything.
+//
+// Every vector instruction other than the vset{i}vl{i} family is illegal
+// when the hart has no vector state or when vtype is unsupported.
+fn exec_vector_op<Mem: Bus<u32>, F: FnOnce(&mut VectorRegisters) -> bool>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    callback: F,
+) -> ExecStatus<u32> {
+    let ok = match hart.vector_registers() {
+        Some(regs) if !regs.vill() => {
+            let ok = callback(regs);
+            if ok {
+                regs.set_vstart(0);
+            }
+            ok
+        }
+        _ => false,
+    };
+    match ok {
+        true => ExecStatus::Running,
+        false => raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0),
+    }
+}
+
+// As exec_vector_op, for instructions whose elements are floating point
+// values. SEW must be 32 or 64, with the matching scalar extension enabled.
+fn exec_vector_float_op<Mem: Bus<u32>, F: FnOnce(&mut VectorRegisters) -> bool>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    callback: F,
+) -> ExecStatus<u32> {
+    let extensions = hart.extensions();
+    exec_vector_op(hart, inst, |regs| {
+        let supported = match regs.sew() {
+            32 => extensions.contains(Extensions::F),
+            64 => extensions.contains(Extensions::D),
+            _ => false,
+        };
+        supported && callback(regs)
+    })
+}
+
+fn exec_vector_config<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    rd: IntRegister,
+    avl: u32,
+    vtype: u32,
+) -> ExecStatus<u32> {
+    match hart.vector_registers() {
+        Some(regs) => {
+            let vl = regs.configure(avl, vtype);
+            hart.write_int_register(rd, vl);
+            ExecStatus::Running
+        }
+        None => raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0),
+    }
+}
+
+// Returns the application vector length for vsetvli and vsetvl: rs1 if it
+// isn't x0, or else the largest possible vector length if rd isn't x0, or
+// else the current vl so that only vtype changes.
+fn register_avl<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    rd: IntRegister,
+    rs1: IntRegister,
+) -> u32 {
+    let zero = IntRegister::zero();
+    if rs1 != zero {
+        hart.read_int_register(rs1)
+    } else if rd != zero {
+        u32::MAX
+    } else {
+        hart.vector_registers().map_or(0, |regs| regs.vl())
+    }
+}
+
+// How a vector load or store finds the address of each element, relative
+// to the base address in rs1.
+#[derive(Clone, Copy)]
+enum Addressing {
+    Unit,
+    Strided(u32),
+    Indexed(VectorRegister),
+}
+
+// Loads the active elements of vd from memory. For the unit-stride and
+// strided forms the elements are eew bits wide, while for the indexed forms
+// eew is the width of the indices in vs2 and the elements are SEW bits wide.
+//
+// A fault on any element leaves vstart at that element, so that the
+// instruction resumes from there when it is retried.
+fn exec_vector_load<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vd: VectorRegister,
+    rs1: IntRegister,
+    addressing: Addressing,
+    vm: bool,
+    eew: usize,
+) -> ExecStatus<u32> {
+    let base = hart.read_int_register(rs1);
+    let width = match hart.vector_registers() {
+        Some(regs) if !regs.vill() && !overwrites_mask(vd, vm) => {
+            data_width(regs, vd, addressing, eew)
+        }
+        _ => None,
+    };
+    let width = match width {
+        Some(width) => width,
+        None => return raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0),
+    };
+    let (vstart, vl) = {
+        let regs = vector_state(hart);
+        (regs.vstart() as usize, regs.vl() as usize)
+    };
+    for i in vstart..vl {
+        let regs = vector_state(hart);
+        if !active(regs, vm, i) {
+            continue;
+        }
+        let addr = element_address(regs, base, addressing, i, width, eew);
+        match hart.with_memory(|mem| read_element(mem, addr, width)) {
+            Ok(v) => vector_state(hart).set_element(vd, i, width, v),
+            Err(e) => {
+                vector_state(hart).set_vstart(i as u32);
+                return raise(hart, inst.pc, e.cause(), e.addr as u32);
+            }
+        }
+    }
+    vector_state(hart).set_vstart(0);
+    ExecStatus::Running
+}
+
+// Stores the active elements of vs3 to memory, with the element widths
+// and fault handling described for exec_vector_load.
+fn exec_vector_store<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    inst: Instruction<Op, u32>,
+    vs3: VectorRegister,
+    rs1: IntRegister,
+    addressing: Addressing,
+    vm: bool,
+    eew: usize,
+) -> ExecStatus<u32> {
+    let base = hart.read_int_register(rs1);
+    let width = match hart.vector_registers() {
+        Some(regs) if !regs.vill() => data_width(regs, vs3, addressing, eew),
+        _ => None,
+    };
+    let width = match width {
+        Some(width) => width,
+        None => return raise(hart, inst.pc, ExceptionCause::IllegalInstruction, 0),
+    };
+    let (vstart, vl) = {
+        let regs = vector_state(hart);
+        (regs.vstart() as usize, regs.vl() as usize)
+    };
+    for i in vstart..vl {
+        let regs = vector_state(hart);
+        if !active(regs, vm, i) {
+            continue;
+        }
+        let addr = element_address(regs, base, addressing, i, width, eew);
+        let v = regs.element(vs3, i, width);
+        if let Err(e) = hart.with_memory(|mem| write_element(mem, addr, width, v)) {
+            vector_state(hart).set_vstart(i as u32);
+            return raise(hart, inst.pc, e.cause(), e.addr as u32);
+        }
+    }
+    vector_state(hart).set_vstart(0);
+    ExecStatus::Running
+}
+
+// Returns the hart's vector state, for use after an instruction has
+// already checked that it exists.
+fn vector_state<Mem: Bus<u32>>(hart: &mut impl Hart<u32, u32, f64, Mem>) -> &mut VectorRegisters {
+    hart.vector_registers()
+        .expect("vector state disappeared during an instruction")
+}
+
+// Returns the width of the data elements of a load or store, or None if
+// its register groups are illegal under the current vtype.
+fn data_width(
+    regs: &VectorRegisters,
+    data: VectorRegister,
+    addressing: Addressing,
+    eew: usize,
+) -> Option<usize> {
+    let width = match addressing {
+        Addressing::Indexed(vs2) => {
+            if !aligned(vs2, regs.group_size_for(eew)?) {
+                return None;
+            }
+            regs.sew()
+        }
+        _ => eew,
+    };
+    match aligned(data, regs.group_size_for(width)?) {
+        true => Some(width),
+        false => None,
+    }
+}
+
+fn element_address(
+    regs: &VectorRegisters,
+    base: u32,
+    addressing: Addressing,
+    i: usize,
+    width: usize,
+    eew: usize,
+) -> u32 {
+    let offset = match addressing {
+        Addressing::Unit => (i * width / 8) as u32,
+        Addressing::Strided(stride) => (i as u32).wrapping_mul(stride),
+        Addressing::Indexed(vs2) => regs.element(vs2, i, eew) as u32,
+    };
+    base.wrapping_add(offset)
+}
+
+fn read_element<Mem: Bus<u32>>(mem: &mut Mem, addr: u32, width: usize) -> Result<u64, MemoryError> {
+    Ok(match width {
+        8 => mem.read_byte(addr)? as u64,
+        16 => mem.read_halfword(addr)? as u64,
+        32 => mem.read_word(addr)? as u64,
+        _ => mem.read_longword(addr)?,
+    })
+}
+
+fn write_element<Mem: Bus<u32>>(
+    mem: &mut Mem,
+    addr: u32,
+    width: usize,
+    v: u64,
+) -> Result<(), MemoryError> {
+    match width {
+        8 => mem.write_byte(addr, v as u8),
+        16 => mem.write_halfword(addr, v as u16),
+        32 => mem.write_word(addr, v as u32),
+        _ => mem.write_longword(addr, v),
+    }
+}
+
+// The second source operand of an arithmetic instruction: either the
+// elements of a vector register group, or a scalar used for every element.
+#[derive(Clone, Copy)]
+enum Operand {
+    Vector(VectorRegister),
+    Scalar(u64),
+    // The raw bits of a floating point register, which hold single-precision
+    // values NaN-boxed in their low 32 bits.
+    Float(u64),
+}
+
+impl Operand {
+    fn element(self, regs: &VectorRegisters, i: usize, sew: usize) -> u64 {
+        match self {
+            Operand::Vector(reg) => regs.element(reg, i, sew),
+            Operand::Scalar(v) => v & (u64::MAX >> (64 - sew)),
+            Operand::Float(bits) if sew == 64 => bits,
+            Operand::Float(bits) if bits >> 32 == 0xffff_ffff => bits & 0xffff_ffff,
+            Operand::Float(_) => CANONICAL_NAN_32,
+        }
+    }
+
+    fn aligned(self, group: usize) -> bool {
+        match self {
+            Operand::Vector(reg) => aligned(reg, group),
+            _ => true,
+        }
+    }
+}
+
+// Integer scalars are sign-extended from XLEN bits when SEW is wider.
+fn int_operand<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    rs1: IntRegister,
+) -> Operand {
+    Operand::Scalar(hart.read_int_register(rs1) as i32 as u64)
+}
+
+fn float_operand<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    frs1: FloatRegister,
+) -> Operand {
+    Operand::Float(hart.read_float_register(frs1).to_bits())
+}
+
+// Interprets the low sew bits of v as a signed value.
+fn signed(v: u64, sew: usize) -> i64 {
+    ((v << (64 - sew)) as i64) >> (64 - sew)
+}
+
+fn active(regs: &VectorRegisters, vm: bool, i: usize) -> bool {
+    vm || regs.mask_bit(VectorRegister::numbered(0), i)
+}
+
+fn aligned(reg: VectorRegister, group: usize) -> bool {
+    reg.num().is_multiple_of(group)
+}
+
+// A masked instruction can't write its results to the register group that
+// holds the mask, unless the results are themselves a mask.
+fn overwrites_mask(vd: VectorRegister, vm: bool) -> bool {
+    !vm && vd.num() == 0
+}
+
+// Applies the callback to each active element of vs2 and the matching
+// element of the second operand, writing the results to vd.
+fn binary(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    src: Operand,
+    vm: bool,
+    callback: impl Fn(u64, u64, usize) -> u64,
+) -> bool {
+    let (sew, group) = (regs.sew(), regs.group_size());
+    if !aligned(vd, group) || !aligned(vs2, group) || !src.aligned(group) || overwrites_mask(vd, vm)
+    {
+        return false;
+    }
+    for i in regs.vstart() as usize..regs.vl() as usize {
+        if active(regs, vm, i) {
+            let v = callback(regs.element(vs2, i, sew), src.element(regs, i, sew), sew);
+            regs.set_element(vd, i, sew, v);
+        }
+    }
+    true
+}
+
+// As binary, except that the callback also receives the element of vd
+// that its result replaces.
+fn multiply_add(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    src: Operand,
+    vm: bool,
+    callback: impl Fn(u64, u64, u64, usize) -> u64,
+) -> bool {
+    let (sew, group) = (regs.sew(), regs.group_size());
+    if !aligned(vd, group) || !aligned(vs2, group) || !src.aligned(group) || overwrites_mask(vd, vm)
+    {
+        return false;
+    }
+    for i in regs.vstart() as usize..regs.vl() as usize {
+        if active(regs, vm, i) {
+            let a = regs.element(vs2, i, sew);
+            let b = src.element(regs, i, sew);
+            let v = callback(a, b, regs.element(vd, i, sew), sew);
+            regs.set_element(vd, i, sew, v);
+        }
+    }
+    true
+}
+
+// Applies the callback to each active element of vs2 and the matching
+// element of the second operand, writing the results to the mask in vd.
+fn compare(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    src: Operand,
+    vm: bool,
+    callback: impl Fn(u64, u64, usize) -> bool,
+) -> bool {
+    let (sew, group) = (regs.sew(), regs.group_size());
+    if !aligned(vs2, group) || !src.aligned(group) {
+        return false;
+    }
+    // The mask in vd may overlap the sources, so compute all of the
+    // results before writing any of them.
+    let mut results = [false; MAX_VLEN];
+    let body = regs.vstart() as usize..regs.vl() as usize;
+    for i in body.clone() {
+        results[i] = callback(regs.element(vs2, i, sew), src.element(regs, i, sew), sew);
+    }
+    for i in body {
+        if active(regs, vm, i) {
+            regs.set_mask_bit(vd, i, results[i]);
+        }
+    }
+    true
+}
+
+// Folds the active elements of vs2 into element 0 of vs1 using the
+// callback, then writes the result to element 0 of vd.
+fn reduction(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+    callback: impl Fn(u64, u64, usize) -> u64,
+) -> bool {
+    let sew = regs.sew();
+    if !aligned(vs2, regs.group_size()) || regs.vstart() != 0 {
+        return false;
+    }
+    let mut acc = regs.element(vs1, 0, sew);
+    for i in 0..regs.vl() as usize {
+        if active(regs, vm, i) {
+            acc = callback(acc, regs.element(vs2, i, sew), sew);
+        }
+    }
+    if regs.vl() > 0 {
+        regs.set_element(vd, 0, sew, acc);
+    }
+    true
+}
+
+fn mask_logical(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    callback: impl Fn(bool, bool) -> bool,
+) -> bool {
+    for i in regs.vstart() as usize..regs.vl() as usize {
+        let v = callback(regs.mask_bit(vs2, i), regs.mask_bit(vs1, i));
+        regs.set_mask_bit(vd, i, v);
+    }
+    true
+}
+
+// Copies the elements of src to vd, or for the merge forms with a vs2
+// copies only those selected by the mask in v0 and takes the rest from vs2.
+fn merge(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: Option<VectorRegister>,
+    src: Operand,
+) -> bool {
+    let (sew, group) = (regs.sew(), regs.group_size());
+    if !aligned(vd, group) || !src.aligned(group) {
+        return false;
+    }
+    if let Some(vs2) = vs2 {
+        if !aligned(vs2, group) || overwrites_mask(vd, false) {
+            return false;
+        }
+    }
+    for i in regs.vstart() as usize..regs.vl() as usize {
+        let v = match vs2 {
+            Some(vs2) if !active(regs, false, i) => regs.element(vs2, i, sew),
+            _ => src.element(regs, i, sew),
+        };
+        regs.set_element(vd, i, sew, v);
+    }
+    true
+}
+
+// Writes element 0 of vd, ignoring LMUL.
+fn move_to_first(regs: &mut VectorRegisters, vd: VectorRegister, src: Operand) -> bool {
+    let sew = regs.sew();
+    if regs.vstart() < regs.vl() {
+        let v = src.element(regs, 0, sew);
+        regs.set_element(vd, 0, sew, v);
+    }
+    true
+}
+
+fn element_index(regs: &mut VectorRegisters, vd: VectorRegister, vm: bool) -> bool {
+    let sew = regs.sew();
+    if !aligned(vd, regs.group_size()) || overwrites_mask(vd, vm) {
+        return false;
+    }
+    for i in regs.vstart() as usize..regs.vl() as usize {
+        if active(regs, vm, i) {
+            regs.set_element(vd, i, sew, i as u64);
+        }
+    }
+    true
+}
+
+// Checks the operands of the permutation instructions, whose destination
+// can't overlap any vector source.
+fn permutation_operands(
+    regs: &VectorRegisters,
+    vd: VectorRegister,
+    sources: &[VectorRegister],
+    vm: bool,
+) -> bool {
+    let group = regs.group_size();
+    aligned(vd, group)
+        && !overwrites_mask(vd, vm)
+        && sources.iter().all(|&vs| aligned(vs, group) && vs != vd)
+}
+
+fn slide_up(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    offset: u64,
+    vm: bool,
+) -> bool {
+    let sew = regs.sew();
+    if !permutation_operands(regs, vd, &[vs2], vm) {
+        return false;
+    }
+    let start = offset.max(regs.vstart() as u64);
+    for i in start..regs.vl() as u64 {
+        if active(regs, vm, i as usize) {
+            let v = regs.element(vs2, (i - offset) as usize, sew);
+            regs.set_element(vd, i as usize, sew, v);
+        }
+    }
+    true
+}
+
+fn slide_down(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    offset: u64,
+    vm: bool,
+) -> bool {
+    let sew = regs.sew();
+    if !permutation_operands(regs, vd, &[vs2], vm) {
+        return false;
+    }
+    let vlmax = regs.vlmax() as u64;
+    for i in regs.vstart() as usize..regs.vl() as usize {
+        if active(regs, vm, i) {
+            let v = match (i as u64).checked_add(offset) {
+                Some(j) if j < vlmax => regs.element(vs2, j as usize, sew),
+                _ => 0,
+            };
+            regs.set_element(vd, i, sew, v);
+        }
+    }
+    true
+}
+
+fn slide_1up(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    src: Operand,
+    vm: bool,
+) -> bool {
+    let sew = regs.sew();
+    if !permutation_operands(regs, vd, &[vs2], vm) {
+        return false;
+    }
+    for i in regs.vstart() as usize..regs.vl() as usize {
+        if active(regs, vm, i) {
+            let v = match i {
+                0 => src.element(regs, 0, sew),
+                _ => regs.element(vs2, i - 1, sew),
+            };
+            regs.set_element(vd, i, sew, v);
+        }
+    }
+    true
+}
+
+fn slide_1down(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    src: Operand,
+    vm: bool,
+) -> bool {
+    let (sew, group) = (regs.sew(), regs.group_size());
+    if !aligned(vd, group) || !aligned(vs2, group) || overwrites_mask(vd, vm) {
+        return false;
+    }
+    let vl = regs.vl() as usize;
+    for i in regs.vstart() as usize..vl {
+        if active(regs, vm, i) {
+            let v = match i + 1 {
+                j if j == vl => src.element(regs, 0, sew),
+                j => regs.element(vs2, j, sew),
+            };
+            regs.set_element(vd, i, sew, v);
+        }
+    }
+    true
+}
+
+fn gather(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+) -> bool {
+    let sew = regs.sew();
+    if !permutation_operands(regs, vd, &[vs2, vs1], vm) {
+        return false;
+    }
+    let vlmax = regs.vlmax() as u64;
+    for i in regs.vstart() as usize..regs.vl() as usize {
+        if active(regs, vm, i) {
+            let v = match regs.element(vs1, i, sew) {
+                j if j < vlmax => regs.element(vs2, j as usize, sew),
+                _ => 0,
+            };
+            regs.set_element(vd, i, sew, v);
+        }
+    }
+    true
+}
+
+fn gather_scalar(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    index: u64,
+    vm: bool,
+) -> bool {
+    let sew = regs.sew();
+    if !permutation_operands(regs, vd, &[vs2], vm) {
+        return false;
+    }
+    let v = match index < regs.vlmax() as u64 {
+        true => regs.element(vs2, index as usize, sew),
+        false => 0,
+    };
+    for i in regs.vstart() as usize..regs.vl() as usize {
+        if active(regs, vm, i) {
+            regs.set_element(vd, i, sew, v);
+        }
+    }
+    true
+}
+
+fn compress(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+) -> bool {
+    let (sew, group) = (regs.sew(), regs.group_size());
+    if !permutation_operands(regs, vd, &[vs2], true)
+        || vs1.num() / group == vd.num() / group
+        || regs.vstart() != 0
+    {
+        return false;
+    }
+    let mut j = 0;
+    for i in 0..regs.vl() as usize {
+        if regs.mask_bit(vs1, i) {
+            let v = regs.element(vs2, i, sew);
+            regs.set_element(vd, j, sew, v);
+            j += 1;
+        }
+    }
+    true
+}
+
+// Applies whichever of the callbacks matches SEW to the floating point
+// values in a and b, producing the canonical NaN for any NaN result.
+fn float_apply(
+    a: u64,
+    b: u64,
+    sew: usize,
+    single: &impl Fn(f32, f32) -> f32,
+    double: &impl Fn(f64, f64) -> f64,
+) -> u64 {
+    match sew {
+        32 => match single(f32::from_bits(a as u32), f32::from_bits(b as u32)) {
+            v if v.is_nan() => CANONICAL_NAN_32,
+            v => v.to_bits() as u64,
+        },
+        _ => match double(f64::from_bits(a), f64::from_bits(b)) {
+            v if v.is_nan() => CANONICAL_NAN_64,
+            v => v.to_bits(),
+        },
+    }
+}
+
+fn float_binary(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    src: Operand,
+    vm: bool,
+    single: impl Fn(f32, f32) -> f32,
+    double: impl Fn(f64, f64) -> f64,
+) -> bool {
+    binary(regs, vd, vs2, src, vm, |a, b, sew| {
+        float_apply(a, b, sew, &single, &double)
+    })
+}
+
+fn float_compare(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    src: Operand,
+    vm: bool,
+    single: impl Fn(f32, f32) -> bool,
+    double: impl Fn(f64, f64) -> bool,
+) -> bool {
+    compare(regs, vd, vs2, src, vm, |a, b, sew| match sew {
+        32 => single(f32::from_bits(a as u32), f32::from_bits(b as u32)),
+        _ => double(f64::from_bits(a), f64::from_bits(b)),
+    })
+}
+
+fn float_reduction(
+    regs: &mut VectorRegisters,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    vs1: VectorRegister,
+    vm: bool,
+    single: impl Fn(f32, f32) -> f32,
+    double: impl Fn(f64, f64) -> f64,
+) -> bool {
+    reduction(regs, vd, vs2, vs1, vm, |a, b, sew| {
+        float_apply(a, b, sew, &single, &double)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exception::ExceptionCause;
+    use crate::exec::{run_rv32, step_rv32, ExecStatus, RunLimits, StopReason};
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::{Extensions, RV32};
+    use crate::memory::{AddressConverter, Alignment, Bus, Memory, MisalignedAccess};
+    use crate::register::{ControlStatusRegister, IntRegister, VectorRegister};
+
+    // Writes a single instruction to address 0 and executes it.
+    fn execute<Mem: Bus<u32>>(
+        hart: &mut impl Hart<u32, u32, f64, Mem>,
+        inst: u32,
+    ) -> ExecStatus<u32> {
+        hart.with_memory(|mem| mem.write_word(0, inst).unwrap());
+        hart.write_pc(0);
+        step_rv32(hart)
+    }
+
+    fn illegal(status: ExecStatus<u32>) -> bool {
+        matches!(
+            status,
+            ExecStatus::Exception {
+                cause: ExceptionCause::IllegalInstruction,
+                ..
+            }
+        )
+    }
+
+    // Selects a vtype and vl directly, without going through vsetvl.
+    fn configure<Mem: Bus<u32>>(hart: &mut impl Hart<u32, u32, f64, Mem>, avl: u32, vtype: u32) {
+        hart.vector_registers().unwrap().configure(avl, vtype);
+    }
+
+    // Writes the first four elements of a register at the given SEW.
+    fn set_elements<Mem: Bus<u32>>(
+        hart: &mut impl Hart<u32, u32, f64, Mem>,
+        reg: usize,
+        sew: usize,
+        values: [u64; 4],
+    ) {
+        let regs = hart.vector_registers().unwrap();
+        for (i, v) in values.iter().enumerate() {
+            regs.set_element(VectorRegister::numbered(reg), i, sew, *v);
+        }
+    }
+
+    // Reads the first four elements of a register at the given SEW.
+    fn elements<Mem: Bus<u32>>(
+        hart: &mut impl Hart<u32, u32, f64, Mem>,
+        reg: usize,
+        sew: usize,
+    ) -> [u64; 4] {
+        let regs = hart.vector_registers().unwrap();
+        let mut values = [0; 4];
+        for (i, v) in values.iter_mut().enumerate() {
+            *v = regs.element(VectorRegister::numbered(reg), i, sew);
+        }
+        values
+    }
+
+    fn set_mask<Mem: Bus<u32>>(hart: &mut impl Hart<u32, u32, f64, Mem>, reg: usize, bits: u8) {
+        hart.vector_registers()
+            .unwrap()
+            .register_mut(VectorRegister::numbered(reg))[0] = bits;
+    }
+
+    fn write_words<Mem: Bus<u32>>(
+        hart: &mut impl Hart<u32, u32, f64, Mem>,
+        addr: u32,
+        words: &[u32],
+    ) {
+        hart.with_memory(|mem| {
+            for (i, word) in words.iter().enumerate() {
+                mem.write_word(addr + i as u32 * 4, *word).unwrap();
+            }
+        });
+    }
+
+    fn read_words<Mem: Bus<u32>>(hart: &mut impl Hart<u32, u32, f64, Mem>, addr: u32) -> [u32; 4] {
+        hart.with_memory(|mem| {
+            let mut words = [0; 4];
+            for (i, word) in words.iter_mut().enumerate() {
+                *word = mem.read_word(addr + i as u32 * 4).unwrap();
+            }
+            words
+        })
+    }
+
+    fn f32s(values: [f32; 4]) -> [u64; 4] {
+        let mut bits = [0; 4];
+        for (b, v) in bits.iter_mut().zip(values.iter()) {
+            *b = v.to_bits() as u64;
+        }
+        bits
+    }
+
+    #[test]
+    fn masking_and_tail_undisturbed() {
+        let mut buf = [0u8; 256];
+        let mut hart: SingleThreadUserHart<RV32, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        hart.set_report_exceptions(true);
+        hart.write_int_register(IntRegister::numbered(10), 0x80);
+        write_words(&mut hart, 0x80, &[100, 101, 102, 103]);
+
+        // e32, m1 with vl = 3, under both the undisturbed and the agnostic
+        // policies, which this implementation treats alike.
+        for &vtype in [0b0001_0000, 0b1101_0000].iter() {
+            configure(&mut hart, 3, vtype);
+            set_elements(&mut hart, 1, 32, [1, 2, 3, 4]);
+            set_elements(&mut hart, 2, 32, [10, 20, 30, 40]);
+            set_elements(&mut hart, 3, 32, [0xaa; 4]);
+            set_elements(&mut hart, 4, 32, [0xbb; 4]);
+            set_mask(&mut hart, 0, 0b0101);
+
+            // vadd.vv v3, v1, v2, v0.t
+            assert!(matches!(
+                execute(&mut hart, 0x001101d7),
+                ExecStatus::Running
+            ));
+            assert_eq!(elements(&mut hart, 3, 32), [11, 0xaa, 33, 0xaa]);
+
+            // vle32.v v4, (a0), v0.t
+            assert!(matches!(
+                execute(&mut hart, 0x00056207),
+                ExecStatus::Running
+            ));
+            assert_eq!(elements(&mut hart, 4, 32), [100, 0xbb, 102, 0xbb]);
+
+            // An unmasked vadd.vv v3, v1, v2 starting from vstart = 1 leaves
+            // element 0 alone as well as the tail, then clears vstart.
+            set_elements(&mut hart, 3, 32, [0xaa; 4]);
+            hart.vector_registers().unwrap().set_vstart(1);
+            assert!(matches!(
+                execute(&mut hart, 0x021101d7),
+                ExecStatus::Running
+            ));
+            assert_eq!(elements(&mut hart, 3, 32), [0xaa, 22, 33, 0xaa]);
+            assert_eq!(hart.vector_registers().unwrap().vstart(), 0);
+        }
+
+        // A masked instruction can't overwrite the mask: vadd.vv v0, v1, v2, v0.t
+        assert!(illegal(execute(&mut hart, 0x00110057)));
+        assert_eq!(elements(&mut hart, 0, 32)[0], 0b0101);
+    }
+
+    #[test]
+    fn vstart_resumes_after_fault() {
+        let mut buf = [0u8; 256];
+        let mem = Alignment::new(
+            AddressConverter::new(Memory::new_ram(&mut buf)),
+            MisalignedAccess::Trap,
+        );
+        let mut hart: SingleThreadUserHart<RV32, _> = SingleThreadUserHart::new(mem);
+        hart.set_report_exceptions(true);
+        configure(&mut hart, 4, 0b0001_0000);
+        write_words(&mut hart, 0x80, &[1, 2, 3, 4]);
+
+        // vluxei32.v v4, (a0), v5, where the third index is misaligned.
+        hart.write_int_register(IntRegister::numbered(10), 0x80);
+        set_elements(&mut hart, 5, 32, [0, 4, 6, 12]);
+        set_elements(&mut hart, 4, 32, [0; 4]);
+        assert!(matches!(
+            execute(&mut hart, 0x06556207),
+            ExecStatus::Exception {
+                cause: ExceptionCause::LoadAddressMisaligned,
+                pc: 0,
+                tval: 0x86,
+            }
+        ));
+        assert_eq!(hart.vector_registers().unwrap().vstart(), 2);
+        assert_eq!(elements(&mut hart, 4, 32), [1, 2, 0, 0]);
+
+        // Retrying resumes at element 2, so the elements already loaded
+        // aren't loaded again.
+        write_words(&mut hart, 0x80, &[99]);
+        set_elements(&mut hart, 5, 32, [0, 4, 8, 12]);
+        assert!(matches!(
+            execute(&mut hart, 0x06556207),
+            ExecStatus::Running
+        ));
+        assert_eq!(hart.vector_registers().unwrap().vstart(), 0);
+        assert_eq!(elements(&mut hart, 4, 32), [1, 2, 3, 4]);
+
+        // vsse32.v v1, (a0), a1 with a stride that misaligns element 1.
+        hart.write_int_register(IntRegister::numbered(10), 0xc0);
+        hart.write_int_register(IntRegister::numbered(11), 6);
+        set_elements(&mut hart, 1, 32, [5, 6, 7, 8]);
+        assert!(matches!(
+            execute(&mut hart, 0x0ab560a7),
+            ExecStatus::Exception {
+                cause: ExceptionCause::StoreAddressMisaligned,
+                pc: 0,
+                tval: 0xc6,
+            }
+        ));
+        assert_eq!(hart.vector_registers().unwrap().vstart(), 1);
+        assert_eq!(read_words(&mut hart, 0xc0), [5, 0, 0, 0]);
+
+        write_words(&mut hart, 0xc0, &[0]);
+        hart.write_int_register(IntRegister::numbered(11), 8);
+        assert!(matches!(
+            execute(&mut hart, 0x0ab560a7),
+            ExecStatus::Running
+        ));
+        assert_eq!(hart.vector_registers().unwrap().vstart(), 0);
+        assert_eq!(read_words(&mut hart, 0xc0), [0, 0, 6, 0]);
+        assert_eq!(read_words(&mut hart, 0xd0), [7, 0, 8, 0]);
+    }
+
+    #[test]
+    fn strided_and_indexed_accesses() {
+        let mut buf = [0u8; 256];
+        let mut hart: SingleThreadUserHart<RV32, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        hart.set_report_exceptions(true);
+        configure(&mut hart, 4, 0b0001_0000);
+        write_words(&mut hart, 0x80, &[1, 2, 3, 4, 5, 6, 7, 8]);
+        let (a0, a1) = (IntRegister::numbered(10), IntRegister::numbered(11));
+
+        // vlse32.v v4, (a0), a1 with positive, negative and zero strides.
+        hart.write_int_register(a0, 0x80);
+        hart.write_int_register(a1, 8);
+        assert!(matches!(
+            execute(&mut hart, 0x0ab56207),
+            ExecStatus::Running
+        ));
+        assert_eq!(elements(&mut hart, 4, 32), [1, 3, 5, 7]);
+        hart.write_int_register(a0, 0x8c);
+        hart.write_int_register(a1, -4i32 as u32);
+        assert!(matches!(
+            execute(&mut hart, 0x0ab56207),
+            ExecStatus::Running
+        ));
+        assert_eq!(elements(&mut hart, 4, 32), [4, 3, 2, 1]);
+        hart.write_int_register(a1, 0);
+        assert!(matches!(
+            execute(&mut hart, 0x0ab56207),
+            ExecStatus::Running
+        ));
+        assert_eq!(elements(&mut hart, 4, 32), [4; 4]);
+
+        // vluxei32.v v4, (a0), v5
+        hart.write_int_register(a0, 0x80);
+        set_elements(&mut hart, 5, 32, [12, 0, 8, 4]);
+        assert!(matches!(
+            execute(&mut hart, 0x06556207),
+            ExecStatus::Running
+        ));
+        assert_eq!(elements(&mut hart, 4, 32), [4, 1, 3, 2]);
+
+        // vloxei8.v v4, (a0), v5 reads 8-bit indices but loads SEW-wide
+        // elements.
+        set_elements(&mut hart, 5, 8, [4, 8, 0, 12]);
+        assert!(matches!(
+            execute(&mut hart, 0x0e550207),
+            ExecStatus::Running
+        ));
+        assert_eq!(elements(&mut hart, 4, 32), [2, 3, 1, 4]);
+
+        // vsse32.v v1, (a0), a1
+        set_elements(&mut hart, 1, 32, [5, 6, 7, 8]);
+        hart.write_int_register(a0, 0xc0);
+        hart.write_int_register(a1, 8);
+        assert!(matches!(
+            execute(&mut hart, 0x0ab560a7),
+            ExecStatus::Running
+        ));
+        assert_eq!(read_words(&mut hart, 0xc0), [5, 0, 6, 0]);
+        assert_eq!(read_words(&mut hart, 0xd0), [7, 0, 8, 0]);
+
+        // vsoxei32.v v1, (a0), v5
+        hart.write_int_register(a0, 0xe0);
+        set_elements(&mut hart, 5, 32, [12, 8, 4, 0]);
+        assert!(matches!(
+            execute(&mut hart, 0x0e5560a7),
+            ExecStatus::Running
+        ));
+        assert_eq!(read_words(&mut hart, 0xe0), [8, 7, 6, 5]);
+
+        // vsuxei32.v v1, (a0), v5
+        set_elements(&mut hart, 5, 32, [4, 0, 12, 8]);
+        assert!(matches!(
+            execute(&mut hart, 0x065560a7),
+            ExecStatus::Running
+        ));
+        assert_eq!(read_words(&mut hart, 0xe0), [6, 5, 8, 7]);
+    }
+
+    #[test]
+    fn reserved_vtype_sets_vill() {
+        let mut buf = [0u8; 64];
+        let mut hart: SingleThreadUserHart<RV32, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        hart.set_report_exceptions(true);
+        let (a1, a2, a3) = (
+            IntRegister::numbered(11),
+            IntRegister::numbered(12),
+            IntRegister::numbered(13),
+        );
+        let vtype = ControlStatusRegister::numbered(0xc21);
+        hart.write_int_register(a1, 4);
+
+        // vsetvli a2, a1, e32, m1, tu, mu
+        assert!(matches!(
+            execute(&mut hart, 0x0105f657),
+            ExecStatus::Running
+        ));
+        assert_eq!(hart.read_int_register(a2), 4);
+        assert_eq!(hart.read_csr(vtype).unwrap(), 0b0001_0000);
+
+        // vsetvli a2, a1 with the reserved LMUL encoding, and with e64 at
+        // mf8, which can't hold an element.
+        for &inst in [0x0145f657, 0x01d5f657].iter() {
+            assert!(matches!(execute(&mut hart, inst), ExecStatus::Running));
+            assert_eq!(hart.read_int_register(a2), 0);
+            assert!(hart.vector_registers().unwrap().vill());
+            assert_eq!(hart.read_csr(vtype).unwrap(), 0x8000_0000);
+            // vadd.vv v3, v1, v2
+            assert!(illegal(execute(&mut hart, 0x021101d7)));
+        }
+
+        // vsetvl a2, a1, a3 with vill or a reserved bit set in a3.
+        for &v in [0x8000_0010, 0x0000_0110].iter() {
+            hart.write_int_register(a3, 0b0001_0000);
+            assert!(matches!(
+                execute(&mut hart, 0x80d5f657),
+                ExecStatus::Running
+            ));
+            assert_eq!(hart.read_int_register(a2), 4);
+            hart.write_int_register(a3, v);
+            assert!(matches!(
+                execute(&mut hart, 0x80d5f657),
+                ExecStatus::Running
+            ));
+            assert_eq!(hart.read_int_register(a2), 0);
+            assert_eq!(hart.read_csr(vtype).unwrap(), 0x8000_0000);
+            assert!(illegal(execute(&mut hart, 0x021101d7)));
+        }
+    }
+
+    #[test]
+    fn vsetvl_with_x0_operands() {
+        let mut buf = [0u8; 64];
+        let mut hart: SingleThreadUserHart<RV32, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        hart.set_report_exceptions(true);
+        let (a1, a2, a3) = (
+            IntRegister::numbered(11),
+            IntRegister::numbered(12),
+            IntRegister::numbered(13),
+        );
+        let vl = ControlStatusRegister::numbered(0xc20);
+        let vtype = ControlStatusRegister::numbered(0xc21);
+
+        // vsetvl a2, a1, a3 with e32, m1 and an AVL of 3.
+        hart.write_int_register(a1, 3);
+        hart.write_int_register(a3, 0b0001_0000);
+        assert!(matches!(
+            execute(&mut hart, 0x80d5f657),
+            ExecStatus::Running
+        ));
+        assert_eq!(hart.read_int_register(a2), 3);
+
+        // vsetvl zero, zero, a3 changes vtype to e16 but keeps vl.
+        hart.write_int_register(a3, 0b0000_1000);
+        assert!(matches!(
+            execute(&mut hart, 0x80d07057),
+            ExecStatus::Running
+        ));
+        assert_eq!(hart.read_csr(vl).unwrap(), 3);
+        assert_eq!(hart.read_csr(vtype).unwrap(), 0b0000_1000);
+
+        // vsetvl a2, zero, a3 sets vl to VLMAX: e32 at m1 and m2.
+        hart.write_int_register(a3, 0b0001_0000);
+        assert!(matches!(
+            execute(&mut hart, 0x80d07657),
+            ExecStatus::Running
+        ));
+        assert_eq!(hart.read_int_register(a2), 4);
+        assert_eq!(hart.read_csr(vl).unwrap(), 4);
+        hart.write_int_register(a3, 0b0001_0001);
+        assert!(matches!(
+            execute(&mut hart, 0x80d07657),
+            ExecStatus::Running
+        ));
+        assert_eq!(hart.read_int_register(a2), 8);
+        assert_eq!(hart.read_csr(vl).unwrap(), 8);
+    }
+
+    #[test]
+    fn slides_gathers_and_compress() {
+        let mut buf = [0u8; 64];
+        let mut hart: SingleThreadUserHart<RV32, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        hart.set_report_exceptions(true);
+        let a1 = IntRegister::numbered(11);
+        configure(&mut hart, 4, 0b0001_0000);
+        set_elements(&mut hart, 1, 32, [1, 2, 3, 4]);
+
+        let check = |hart: &mut SingleThreadUserHart<RV32, _>, inst, x: u32, want| {
+            hart.write_int_register(a1, x);
+            set_elements(hart, 3, 32, [0xaa; 4]);
+            assert!(
+                matches!(execute(hart, inst), ExecStatus::Running),
+                "{:#010x}",
+                inst
+            );
+            assert_eq!(elements(hart, 3, 32), want, "{:#010x}", inst);
+        };
+        check(&mut hart, 0x3a10b1d7, 0, [0xaa, 1, 2, 3]); // vslideup.vi v3, v1, 1
+        check(&mut hart, 0x3a15c1d7, 2, [0xaa, 0xaa, 1, 2]); // vslideup.vx v3, v1, a1
+        check(&mut hart, 0x3e15c1d7, 2, [3, 4, 0, 0]); // vslidedown.vx v3, v1, a1
+        check(&mut hart, 0x3a15e1d7, 9, [9, 1, 2, 3]); // vslide1up.vx v3, v1, a1
+        check(&mut hart, 0x3e15e1d7, 9, [2, 3, 4, 9]); // vslide1down.vx v3, v1, a1
+        check(&mut hart, 0x3215c1d7, 2, [3; 4]); // vrgather.vx v3, v1, a1
+        check(&mut hart, 0x3215c1d7, 100, [0; 4]); // vrgather.vx v3, v1, a1
+        check(&mut hart, 0x321131d7, 0, [3; 4]); // vrgather.vi v3, v1, 2
+        set_elements(&mut hart, 2, 32, [3, 0, 7, 1]);
+        check(&mut hart, 0x321101d7, 0, [4, 1, 0, 2]); // vrgather.vv v3, v1, v2
+        set_mask(&mut hart, 0, 0b1010);
+        check(&mut hart, 0x5e1021d7, 0, [2, 4, 0xaa, 0xaa]); // vcompress.vm v3, v1, v0
+
+        // Slides down read past vl up to VLMAX, and leave the tail alone.
+        configure(&mut hart, 3, 0b0001_0000);
+        check(&mut hart, 0x3e15c1d7, 1, [2, 3, 4, 0xaa]); // vslidedown.vx v3, v1, a1
+        check(&mut hart, 0x3e15e1d7, 9, [2, 3, 9, 0xaa]); // vslide1down.vx v3, v1, a1
+
+        // The destination can't overlap the sources.
+        assert!(illegal(execute(&mut hart, 0x3a10b0d7))); // vslideup.vi v1, v1, 1
+        assert!(illegal(execute(&mut hart, 0x5e11a1d7))); // vcompress.vm v3, v1, v3
+        assert_eq!(elements(&mut hart, 1, 32), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn float_reductions() {
+        let mut buf = [0u8; 64];
+        let mut hart: SingleThreadUserHart<RV32, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        hart.set_report_exceptions(true);
+        configure(&mut hart, 4, 0b0001_0000);
+        set_elements(&mut hart, 1, 32, f32s([0.5, 1.5, 2.0, 4.0]));
+        set_elements(&mut hart, 2, 32, f32s([1.0, 0.0, 0.0, 0.0]));
+        set_mask(&mut hart, 0, 0b0101);
+
+        let check = |hart: &mut SingleThreadUserHart<RV32, _>, inst, want: f32| {
+            set_elements(hart, 3, 32, [0xaa; 4]);
+            assert!(
+                matches!(execute(hart, inst), ExecStatus::Running),
+                "{:#010x}",
+                inst
+            );
+            let result = elements(hart, 3, 32);
+            assert_eq!(result[0], want.to_bits() as u64, "{:#010x}", inst);
+            assert_eq!(result[1..], [0xaa; 3]);
+        };
+        check(&mut hart, 0x0e1111d7, 9.0); // vfredosum.vs v3, v1, v2
+        check(&mut hart, 0x061111d7, 9.0); // vfredusum.vs v3, v1, v2
+        check(&mut hart, 0x1e1111d7, 4.0); // vfredmax.vs v3, v1, v2
+        check(&mut hart, 0x161111d7, 0.5); // vfredmin.vs v3, v1, v2
+        check(&mut hart, 0x0c1111d7, 3.5); // vfredosum.vs v3, v1, v2, v0.t
+
+        // The ordered sum rounds after each element in turn.
+        set_elements(&mut hart, 1, 32, f32s([1.0e20, 1.0, -1.0e20, 0.0]));
+        set_elements(&mut hart, 2, 32, f32s([0.0; 4]));
+        check(&mut hart, 0x0e1111d7, 0.0);
+
+        // With vl = 0 the destination is left alone.
+        configure(&mut hart, 0, 0b0001_0000);
+        set_elements(&mut hart, 3, 32, [0xaa; 4]);
+        assert!(matches!(
+            execute(&mut hart, 0x0e1111d7),
+            ExecStatus::Running
+        ));
+        assert_eq!(elements(&mut hart, 3, 32), [0xaa; 4]);
+
+        // e64 reductions use double precision.
+        configure(&mut hart, 2, 0b0001_1000);
+        set_elements(
+            &mut hart,
+            1,
+            64,
+            [1.5f64.to_bits(), 2.25f64.to_bits(), 0, 0],
+        );
+        set_elements(&mut hart, 2, 64, [0.25f64.to_bits(), 0, 0, 0]);
+        assert!(matches!(
+            execute(&mut hart, 0x0e1111d7),
+            ExecStatus::Running
+        ));
+        assert_eq!(elements(&mut hart, 3, 64)[0], 4.0f64.to_bits());
+
+        // Without F, single-precision reductions are illegal.
+        configure(&mut hart, 4, 0b0001_0000);
+        hart.set_extensions(Extensions::ALL.without(Extensions::F));
+        assert!(illegal(execute(&mut hart, 0x0e1111d7)));
+    }
+
+    #[test]
+    fn program() {
+        let mut buf = [0u8; 256];
+        let mut hart: SingleThreadUserHart<RV32, _> =
+            SingleThreadUserHart::new(AddressConverter::new(Memory::new_ram(&mut buf)));
+        let program = [
+            0x08000513, // li a0, 128
+            0x00300593, // li a1, 3
+            0x00800793, // li a5, 8
+            0xcd0272d7, // vsetivli t0, 4, e32, m1, ta, ma
+            0x02056087, // vle32.v v1, (a0)
+            0x0212b157, // vadd.vi v2, v1, 5
+            0x9620a1d7, // vmul.vv v3, v2, v1
+            0x6e15c057, // vmslt.vx v0, v1, a1
+            0x5e003257, // vmv.v.i v4, 0
+            0x00318257, // vadd.vv v4, v3, v3, v0.t
+            0x4205e2d7, // vmv.s.x v5, a1
+            0x0232a357, // vredsum.vs v6, v3, v5
+            0x42602657, // vmv.x.s a2, v6
+            0x3e30b3d7, // vslidedown.vi v7, v3, 1
+            0x01050693, // addi a3, a0, 16
+            0x0206e227, // vse32.v v4, (a3)
+            0x42082757, // vcpop.m a4, v0
+            0x0af56407, // vlse32.v v8, (a0), a5
+            0x00000073, // ecall
+        ];
+        hart.with_memory(|mem| {
+            for (i, word) in program.iter().enumerate() {
+                mem.write_word(i as u32 * 4, *word).unwrap();
+            }
+            for i in 0..4 {
+                mem.write_word(128 + i * 4, i + 1).unwrap();
+            }
+        });
+
+        let result = run_rv32(&mut hart, &RunLimits::default());
+        assert_eq!(result.reason, StopReason::EnvironmentCall(72));
+        let reg = |n| hart.read_int_register(IntRegister::numbered(n));
+        assert_eq!(reg(5), 4);
+        assert_eq!(reg(12), 83);
+        assert_eq!(reg(14), 2);
+        let stored: [u32; 4] = hart.with_memory(|mem| {
+            let mut words = [0; 4];
+            for (i, word) in words.iter_mut().enumerate() {
+                *word = mem.read_word(144 + i as u32 * 4).unwrap();
+            }
+            words
+        });
+        assert_eq!(stored, [12, 28, 0, 0]);
+        let regs = hart.vector_registers().unwrap();
+        let elements = |regs: &crate::VectorRegisters, n| {
+            let mut elements = [0; 4];
+            for (i, element) in elements.iter_mut().enumerate() {
+                *element = regs.element(VectorRegister::numbered(n), i, 32);
+            }
+            elements
+        };
+        assert_eq!(elements(regs, 3), [6, 14, 24, 36]);
+        assert_eq!(elements(regs, 7), [14, 24, 36, 0]);
+        assert_eq!(elements(regs, 8), [1, 3, 12, 0]);
+
+        // Without V the same instructions are illegal.
+        hart.set_report_exceptions(true);
+        hart.set_extensions(Extensions::ALL.without(Extensions::V));
+        hart.write_pc(16);
+        let result = run_rv32(&mut hart, &RunLimits::default());
+        assert_eq!(
+            result.reason,
+            StopReason::Exception {
+                cause: ExceptionCause::IllegalInstruction,
+                pc: 16,
+                tval: 0,
+            }
+        );
+    }
+}
@@ -0,0 +1,991 @@
+use crate::exception::ExceptionCause;
+use crate::exec::{step_rv32, ExecStatus};
+use crate::hart::Hart;
+use crate::memory::{Bus, MemoryError};
+use crate::register::{ControlStatusRegister, FloatRegister, IntRegister};
+
+use std::io;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::string::String;
+use std::vec::Vec;
+
+// The register numbers GDB uses for RISC-V targets.
+const REG_PC: usize = 32;
+const REG_FIRST_FLOAT: usize = 33;
+const REG_FIRST_CSR: usize = 65;
+
+// The CSR numbers we list in the floating point feature of the target
+// description, so that GDB will show them alongside the float registers.
+const CSR_FFLAGS: usize = 0x001;
+const CSR_FRM: usize = 0x002;
+const CSR_FCSR: usize = 0x003;
+
+// The signal numbers used in stop replies.
+const SIGINT: u8 = 2;
+const SIGILL: u8 = 4;
+const SIGTRAP: u8 = 5;
+const SIGBUS: u8 = 7;
+const SIGSEGV: u8 = 11;
+
+// How many instructions to execute between checks for an interrupt request
+// from the debugger while continuing.
+const INTERRUPT_CHECK_INTERVAL: u32 = 4096;
+
+const EBREAK: u32 = 0x00100073;
+const C_EBREAK: u16 = 0x9002;
+
+/// A bidirectional byte stream that a `GdbStub` can communicate over.
+///
+/// In addition to reading and writing, a connection can optionally report
+/// whether the debugger has sent an interrupt request (a Ctrl+C) while the
+/// target is running, without blocking.
+pub trait Connection: Read + Write {
+    /// Returns true if the debugger has asked to interrupt execution,
+    /// consuming that request.
+    ///
+    /// The default implementation never reports an interrupt, which means
+    /// that a continuing target can only be stopped by a breakpoint.
+    fn interrupt_requested(&mut self) -> bool {
+        false
+    }
+}
+
+impl Connection for TcpStream {
+    fn interrupt_requested(&mut self) -> bool {
+        let mut buf = [0u8; 1];
+        if self.set_nonblocking(true).is_err() {
+            return false;
+        }
+        let result = self.peek(&mut buf);
+        let _ = self.set_nonblocking(false);
+        match result {
+            Ok(1) if buf[0] == 0x03 => self.read(&mut buf).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+/// A `Connection` made from a separate reader and writer, such as the
+/// standard input and output of a process started by GDB's
+/// `target remote | command` syntax.
+pub struct Pipe<R: Read, W: Write> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: Read, W: Write> Pipe<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    /// Consumes the pipe, returning its reader and writer.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+}
+
+impl<R: Read, W: Write> Read for Pipe<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+impl<R: Read, W: Write> Write for Pipe<R, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<R: Read, W: Write> Connection for Pipe<R, W> {}
+
+/// Describes why a debugging session driven by `GdbStub::run_rv32` ended.
+#[derive(Debug, PartialEq)]
+pub enum SessionEnd {
+    /// The debugger detached, or closed the connection, leaving the hart
+    /// ready to continue running without it.
+    Detached,
+
+    /// The debugger asked for the target to be killed.
+    Killed,
+
+    /// The guest program exited with the given status, as reported by the
+    /// environment call handler.
+    Exited(i32),
+}
+
+// The reason the target stopped, which determines the stop reply that the
+// stub sends to the debugger.
+enum Stop {
+    // The hart reached one of the stub's software breakpoints.
+    Breakpoint,
+
+    // The hart stopped for any other reason, including completing a single
+    // step, with the given signal.
+    Signal(u8),
+}
+
+// A software breakpoint that the stub has written into guest memory, along
+// with the original instruction bytes it replaced.
+struct Breakpoint {
+    addr: u32,
+    len: u32,
+    original: u32,
+}
+
+/// A server for the GDB remote serial protocol, allowing GDB (or any other
+/// compatible debugger) to control a hart and inspect its state.
+///
+/// The stub supports reading and writing the integer, floating point and
+/// control/status registers, reading and writing memory, software
+/// breakpoints, single-stepping, and continuing. It also provides a target
+/// description so that GDB knows the register layout without any further
+/// configuration.
+///
+/// Software breakpoints are implemented by writing an `ebreak` (or
+/// `c.ebreak`) instruction into guest memory, and recognizing the resulting
+/// `ExecStatus::EnvironmentBreak`. Memory reads through the stub return the
+/// original instructions rather than the breakpoints.
+pub struct GdbStub<C: Connection> {
+    conn: C,
+    no_ack: bool,
+    breakpoints: Vec<Breakpoint>,
+}
+
+impl GdbStub<TcpStream> {
+    /// Listens on the given address and waits for a single debugger to
+    /// connect, returning a stub that communicates with it.
+    pub fn listen(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let (stream, _) = listener.accept()?;
+        stream.set_nodelay(true)?;
+        Ok(Self::new(stream))
+    }
+}
+
+impl<C: Connection> GdbStub<C> {
+    /// Creates a stub that communicates over the given connection.
+    pub fn new(conn: C) -> Self {
+        Self {
+            conn,
+            no_ack: false,
+            breakpoints: Vec::new(),
+        }
+    }
+
+    /// Consumes the stub, returning its connection.
+    pub fn into_connection(self) -> C {
+        self.conn
+    }
+
+    /// Serves debugger requests for the given RV32 hart until the session
+    /// ends, executing instructions with `exec::step_rv32` whenever the
+    /// debugger asks the target to step or continue.
+    ///
+    /// Environment calls are passed to `on_ecall`, which can service them
+    /// (for example using `linux::SyscallEmulator`) and return `None` to
+    /// continue, or return `Some` with an exit status to end the session
+    /// because the guest program has exited.
+    ///
+    /// Any software breakpoints are removed from guest memory before this
+    /// function returns.
+    pub fn run_rv32<Mem, H>(
+        &mut self,
+        hart: &mut H,
+        mut on_ecall: impl FnMut(&mut H) -> Option<i32>,
+    ) -> io::Result<SessionEnd>
+    where
+        Mem: Bus<u32>,
+        H: Hart<u32, u32, f64, Mem>,
+    {
+        let result = self.serve(hart, &mut on_ecall);
+        self.remove_all_breakpoints(hart);
+        result
+    }
+
+    fn serve<Mem, H>(
+        &mut self,
+        hart: &mut H,
+        on_ecall: &mut impl FnMut(&mut H) -> Option<i32>,
+    ) -> io::Result<SessionEnd>
+    where
+        Mem: Bus<u32>,
+        H: Hart<u32, u32, f64, Mem>,
+    {
+        loop {
+            let packet = match self.read_packet()? {
+                Some(p) => p,
+                None => return Ok(SessionEnd::Detached),
+            };
+            let (cmd, args) = match packet.split_first() {
+                Some((cmd, args)) => (*cmd, args),
+                None => {
+                    self.send(b"")?;
+                    continue;
+                }
+            };
+            match cmd {
+                b'?' => self.send_stop(Stop::Signal(SIGTRAP))?,
+                b'g' => {
+                    let mut reply = String::new();
+                    for n in 0..32 {
+                        push_hex_u32(&mut reply, hart.read_int_register(IntRegister::numbered(n)));
+                    }
+                    push_hex_u32(&mut reply, hart.read_pc());
+                    self.send(reply.as_bytes())?;
+                }
+                b'G' => {
+                    let mut values = args.chunks(8).map(parse_hex_le);
+                    for n in 0..32 {
+                        if let Some(Some(v)) = values.next() {
+                            hart.write_int_register(IntRegister::numbered(n), v as u32);
+                        }
+                    }
+                    if let Some(Some(v)) = values.next() {
+                        hart.write_pc(v as u32);
+                    }
+                    self.send(b"OK")?;
+                }
+                b'p' => match parse_hex(args) {
+                    Some(n) => {
+                        let reply = read_register(hart, n as usize);
+                        self.send(reply.as_bytes())?;
+                    }
+                    None => self.send(b"E01")?,
+                },
+                b'P' => {
+                    let ok = match split_at_byte(args, b'=') {
+                        Some((n, v)) => match (parse_hex(n), parse_hex_le(v)) {
+                            (Some(n), Some(v)) => write_register(hart, n as usize, v),
+                            _ => false,
+                        },
+                        None => false,
+                    };
+                    self.send(if ok { b"OK" } else { b"E01" })?;
+                }
+                b'm' => {
+                    let reply = match parse_addr_len(args) {
+                        Some((addr, len)) => self.read_memory(hart, addr, len),
+                        None => None,
+                    };
+                    match reply {
+                        Some(r) => self.send(r.as_bytes())?,
+                        None => self.send(b"E14")?,
+                    }
+                }
+                b'M' => {
+                    let ok = match split_at_byte(args, b':') {
+                        Some((target, data)) => match parse_addr_len(target) {
+                            Some((addr, len)) => self.write_memory(hart, addr, len, data),
+                            None => false,
+                        },
+                        None => false,
+                    };
+                    self.send(if ok { b"OK" } else { b"E14" })?;
+                }
+                b'Z' | b'z' => {
+                    let reply: &[u8] = match parse_breakpoint(args) {
+                        Some((0, addr, kind)) if cmd == b'Z' => {
+                            if self.insert_breakpoint(hart, addr, kind) {
+                                b"OK"
+                            } else {
+                                b"E01"
+                            }
+                        }
+                        Some((0, addr, _)) => {
+                            self.remove_breakpoint(hart, addr);
+                            b"OK"
+                        }
+                        // Other breakpoint and watchpoint types are not
+                        // supported, which is reported with an empty reply.
+                        Some(_) => b"",
+                        None => b"E01",
+                    };
+                    self.send(reply)?;
+                }
+                b'c' | b's' => {
+                    if let Some(addr) = parse_hex(args) {
+                        hart.write_pc(addr as u32);
+                    }
+                    match self.resume(hart, on_ecall, cmd == b's') {
+                        Ok(stop) => self.send_stop(stop)?,
+                        Err(status) => {
+                            self.send(std::format!("W{:02x}", status as u8).as_bytes())?;
+                            return Ok(SessionEnd::Exited(status));
+                        }
+                    }
+                }
+                b'k' => return Ok(SessionEnd::Killed),
+                b'D' => {
+                    self.send(b"OK")?;
+                    return Ok(SessionEnd::Detached);
+                }
+                b'H' | b'T' => self.send(b"OK")?,
+                b'q' | b'Q' | b'v' => {
+                    if packet.as_slice() == b"vKill" || packet.starts_with(b"vKill;") {
+                        self.send(b"OK")?;
+                        return Ok(SessionEnd::Killed);
+                    }
+                    self.query(&packet)?;
+                }
+                _ => self.send(b"")?,
+            }
+        }
+    }
+
+    fn query(&mut self, packet: &[u8]) -> io::Result<()> {
+        const XFER_FEATURES: &[u8] = b"qXfer:features:read:target.xml:";
+        if packet.starts_with(b"qSupported") {
+            self.send(b"PacketSize=4000;qXfer:features:read+;swbreak+;QStartNoAckMode+")
+        } else if packet == b"QStartNoAckMode" {
+            self.send(b"OK")?;
+            self.no_ack = true;
+            Ok(())
+        } else if let Some(args) = packet.strip_prefix(XFER_FEATURES) {
+            let xml = target_xml();
+            let reply = match parse_addr_len(args) {
+                Some((offset, len)) => {
+                    let start = core::cmp::min(offset as usize, xml.len());
+                    let end = core::cmp::min(start + len as usize, xml.len());
+                    let mut reply = Vec::new();
+                    reply.push(if end == xml.len() { b'l' } else { b'm' });
+                    reply.extend_from_slice(&xml.as_bytes()[start..end]);
+                    reply
+                }
+                None => b"E01".to_vec(),
+            };
+            self.send(&reply)
+        } else if packet == b"qAttached" {
+            self.send(b"1")
+        } else if packet == b"qfThreadInfo" {
+            self.send(b"m1")
+        } else if packet == b"qsThreadInfo" {
+            self.send(b"l")
+        } else if packet == b"qC" {
+            self.send(b"QC1")
+        } else {
+            self.send(b"")
+        }
+    }
+
+    // Executes instructions until the hart reaches a breakpoint, the
+    // debugger interrupts, or a single instruction has executed if `step` is
+    // set. The result is the reason for the stop, or an error if the guest
+    // program exited.
+    fn resume<Mem, H>(
+        &mut self,
+        hart: &mut H,
+        on_ecall: &mut impl FnMut(&mut H) -> Option<i32>,
+        step: bool,
+    ) -> Result<Stop, i32>
+    where
+        Mem: Bus<u32>,
+        H: Hart<u32, u32, f64, Mem>,
+    {
+        let mut count: u32 = 0;
+        loop {
+            match step_rv32(hart) {
+                ExecStatus::Running | ExecStatus::WaitingForInterrupt => {}
+                ExecStatus::EnvironmentCall(_) => {
+                    if let Some(status) = on_ecall(hart) {
+                        return Err(status);
+                    }
+                }
+                ExecStatus::EnvironmentBreak(addr) => {
+                    // When we reach one of our own breakpoints, the program
+                    // counter should point at the breakpoint rather than
+                    // after it, so that GDB sees the stop at the right place.
+                    // An ebreak in the guest program itself is reported as a
+                    // plain trap.
+                    if self.breakpoints.iter().any(|bp| bp.addr == addr) {
+                        hart.write_pc(addr);
+                        return Ok(Stop::Breakpoint);
+                    }
+                    return Ok(Stop::Signal(SIGTRAP));
+                }
+                ExecStatus::Exception { cause, .. } => {
+                    return Ok(Stop::Signal(exception_signal(cause)))
+                }
+            }
+            if step {
+                return Ok(Stop::Signal(SIGTRAP));
+            }
+            count += 1;
+            if count.is_multiple_of(INTERRUPT_CHECK_INTERVAL) && self.conn.interrupt_requested() {
+                return Ok(Stop::Signal(SIGINT));
+            }
+        }
+    }
+
+    fn insert_breakpoint<Mem, H>(&mut self, hart: &mut H, addr: u32, kind: u32) -> bool
+    where
+        Mem: Bus<u32>,
+        H: Hart<u32, u32, f64, Mem>,
+    {
+        if self.breakpoints.iter().any(|bp| bp.addr == addr) {
+            return true;
+        }
+        let result: Result<u32, MemoryError> = hart.with_memory(|mem| match kind {
+            2 => {
+                let original = mem.read_halfword(addr)?;
+                mem.write_halfword(addr, C_EBREAK)?;
+                Ok(original as u32)
+            }
+            _ => {
+                let original = mem.read_word(addr)?;
+                mem.write_word(addr, EBREAK)?;
+                Ok(original)
+            }
+        });
+        match result {
+            Ok(original) => {
+                let len = if kind == 2 { 2 } else { 4 };
+                self.breakpoints.push(Breakpoint {
+                    addr,
+                    len,
+                    original,
+                });
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn remove_breakpoint<Mem, H>(&mut self, hart: &mut H, addr: u32)
+    where
+        Mem: Bus<u32>,
+        H: Hart<u32, u32, f64, Mem>,
+    {
+        if let Some(i) = self.breakpoints.iter().position(|bp| bp.addr == addr) {
+            let bp = self.breakpoints.remove(i);
+            hart.with_memory(|mem| restore_breakpoint(mem, &bp));
+        }
+    }
+
+    fn remove_all_breakpoints<Mem, H>(&mut self, hart: &mut H)
+    where
+        Mem: Bus<u32>,
+        H: Hart<u32, u32, f64, Mem>,
+    {
+        let breakpoints = core::mem::take(&mut self.breakpoints);
+        hart.with_memory(|mem| {
+            for bp in breakpoints.iter() {
+                restore_breakpoint(mem, bp);
+            }
+        });
+    }
+
+    fn read_memory<Mem, H>(&mut self, hart: &mut H, addr: u32, len: u32) -> Option<String>
+    where
+        Mem: Bus<u32>,
+        H: Hart<u32, u32, f64, Mem>,
+    {
+        let breakpoints = &self.breakpoints;
+        hart.with_memory(|mem| {
+            let mut reply = String::new();
+            for i in 0..len {
+                let a = addr.wrapping_add(i);
+                let b = match original_byte(breakpoints, a) {
+                    Some(b) => b,
+                    None => match mem.read_byte(a) {
+                        Ok(b) => b,
+                        // A partial read is acceptable, as long as at least
+                        // one byte was read.
+                        Err(_) if i > 0 => break,
+                        Err(_) => return None,
+                    },
+                };
+                push_hex_u8(&mut reply, b);
+            }
+            Some(reply)
+        })
+    }
+
+    fn write_memory<Mem, H>(&mut self, hart: &mut H, addr: u32, len: u32, data: &[u8]) -> bool
+    where
+        Mem: Bus<u32>,
+        H: Hart<u32, u32, f64, Mem>,
+    {
+        if data.len() != len as usize * 2 {
+            return false;
+        }
+        hart.with_memory(|mem| {
+            for (i, pair) in data.chunks(2).enumerate() {
+                let b = match parse_hex(pair) {
+                    Some(b) => b as u8,
+                    None => return false,
+                };
+                if mem.write_byte(addr.wrapping_add(i as u32), b).is_err() {
+                    return false;
+                }
+            }
+            true
+        })
+    }
+
+    // Sends a stop reply, which only reports a software breakpoint (with
+    // "swbreak") when the hart reached one of ours, since GDB may otherwise
+    // report a breakpoint hit that never happened.
+    fn send_stop(&mut self, stop: Stop) -> io::Result<()> {
+        match stop {
+            Stop::Breakpoint => self.send(std::format!("T{:02x}swbreak:;", SIGTRAP).as_bytes()),
+            Stop::Signal(signal) => self.send(std::format!("S{:02x}", signal).as_bytes()),
+        }
+    }
+
+    // Reads the next packet from the debugger, acknowledging it if
+    // necessary. Returns `None` if the connection has closed.
+    fn read_packet(&mut self) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            // Skip anything before the start of the packet, which includes
+            // acknowledgements and interrupt requests that arrived while
+            // the target was already stopped.
+            loop {
+                match self.read_byte()? {
+                    Some(b'$') => break,
+                    Some(_) => continue,
+                    None => return Ok(None),
+                }
+            }
+            let mut data = Vec::new();
+            let mut sum: u8 = 0;
+            loop {
+                match self.read_byte()? {
+                    Some(b'#') => break,
+                    Some(b) => {
+                        sum = sum.wrapping_add(b);
+                        data.push(b);
+                    }
+                    None => return Ok(None),
+                }
+            }
+            let mut checksum = [0u8; 2];
+            for c in checksum.iter_mut() {
+                match self.read_byte()? {
+                    Some(b) => *c = b,
+                    None => return Ok(None),
+                }
+            }
+            if self.no_ack {
+                return Ok(Some(unescape(data)));
+            }
+            if parse_hex(&checksum) == Some(sum as u64) {
+                self.conn.write_all(b"+")?;
+                return Ok(Some(unescape(data)));
+            }
+            self.conn.write_all(b"-")?;
+        }
+    }
+
+    fn send(&mut self, data: &[u8]) -> io::Result<()> {
+        let sum = data.iter().fold(0u8, |sum, b| sum.wrapping_add(*b));
+        let mut packet = Vec::with_capacity(data.len() + 4);
+        packet.push(b'$');
+        packet.extend_from_slice(data);
+        packet.extend_from_slice(std::format!("#{:02x}", sum).as_bytes());
+        loop {
+            self.conn.write_all(&packet)?;
+            self.conn.flush()?;
+            if self.no_ack {
+                return Ok(());
+            }
+            // Wait for the acknowledgement, retransmitting if the debugger
+            // reports that the packet was corrupted.
+            loop {
+                match self.read_byte()? {
+                    Some(b'+') | None => return Ok(()),
+                    Some(b'-') => break,
+                    Some(_) => continue,
+                }
+            }
+        }
+    }
+
+    fn read_byte(&mut self) -> io::Result<Option<u8>> {
+        let mut buf = [0u8; 1];
+        loop {
+            match self.conn.read(&mut buf) {
+                Ok(0) => return Ok(None),
+                Ok(_) => return Ok(Some(buf[0])),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+// Returns the signal to report when the guest program raises an exception
+// that its hart leaves for the caller to handle.
+fn exception_signal(cause: ExceptionCause) -> u8 {
+    match cause {
+        ExceptionCause::IllegalInstruction => SIGILL,
+        ExceptionCause::Breakpoint => SIGTRAP,
+        ExceptionCause::InstructionAddressMisaligned
+        | ExceptionCause::LoadAddressMisaligned
+        | ExceptionCause::StoreAddressMisaligned => SIGBUS,
+        _ => SIGSEGV,
+    }
+}
+
+fn restore_breakpoint<Mem: Bus<u32>>(mem: &mut Mem, bp: &Breakpoint) {
+    let _ = match bp.len {
+        2 => mem.write_halfword(bp.addr, bp.original as u16),
+        _ => mem.write_word(bp.addr, bp.original),
+    };
+}
+
+// Returns the original content of the given address if it is covered by a
+// software breakpoint.
+fn original_byte(breakpoints: &[Breakpoint], addr: u32) -> Option<u8> {
+    breakpoints.iter().find_map(|bp| {
+        let offset = addr.wrapping_sub(bp.addr);
+        if offset < bp.len {
+            Some((bp.original >> (offset * 8)) as u8)
+        } else {
+            None
+        }
+    })
+}
+
+fn read_register<Mem, H>(hart: &mut H, n: usize) -> String
+where
+    Mem: Bus<u32>,
+    H: Hart<u32, u32, f64, Mem>,
+{
+    let mut reply = String::new();
+    match n {
+        0..=31 => push_hex_u32(&mut reply, hart.read_int_register(IntRegister::numbered(n))),
+        REG_PC => push_hex_u32(&mut reply, hart.read_pc()),
+        REG_FIRST_FLOAT..=64 => {
+            let v = hart.read_float_register(FloatRegister::numbered(n - REG_FIRST_FLOAT));
+            for b in v.to_bits().to_le_bytes().iter() {
+                push_hex_u8(&mut reply, *b);
+            }
+        }
+        _ => match csr_numbered(n).map(|csr| hart.read_csr(csr)) {
+            Some(Ok(v)) => push_hex_u32(&mut reply, v),
+            // Registers the hart doesn't implement, and register numbers
+            // beyond the last CSR, are reported as unavailable rather than
+            // as an error, so that GDB can still show the others.
+            _ => reply.push_str("xxxxxxxx"),
+        },
+    }
+    reply
+}
+
+fn write_register<Mem, H>(hart: &mut H, n: usize, v: u64) -> bool
+where
+    Mem: Bus<u32>,
+    H: Hart<u32, u32, f64, Mem>,
+{
+    match n {
+        0..=31 => hart.write_int_register(IntRegister::numbered(n), v as u32),
+        REG_PC => hart.write_pc(v as u32),
+        REG_FIRST_FLOAT..=64 => hart.write_float_register(
+            FloatRegister::numbered(n - REG_FIRST_FLOAT),
+            f64::from_bits(v),
+        ),
+        _ => {
+            return match csr_numbered(n) {
+                Some(csr) => hart.write_csr(csr, v as u32).is_ok(),
+                None => false,
+            }
+        }
+    }
+    true
+}
+
+// Returns the CSR with the given GDB register number, which must be at least
+// `REG_FIRST_CSR`, or `None` if the number is beyond the last CSR.
+fn csr_numbered(n: usize) -> Option<ControlStatusRegister> {
+    let num = n - REG_FIRST_CSR;
+    if num < 4096 {
+        Some(ControlStatusRegister::numbered(num))
+    } else {
+        None
+    }
+}
+
+// Produces the target description XML, which tells GDB which registers
+// the target has and how they are numbered.
+fn target_xml() -> String {
+    const INT_NAMES: [&str; 32] = [
+        "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "fp", "s1", "a0", "a1", "a2", "a3", "a4",
+        "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+        "t5", "t6",
+    ];
+    const FLOAT_NAMES: [&str; 32] = [
+        "ft0", "ft1", "ft2", "ft3", "ft4", "ft5", "ft6", "ft7", "fs0", "fs1", "fa0", "fa1", "fa2",
+        "fa3", "fa4", "fa5", "fa6", "fa7", "fs2", "fs3", "fs4", "fs5", "fs6", "fs7", "fs8", "fs9",
+        "fs10", "fs11", "ft8", "ft9", "ft10", "ft11",
+    ];
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\"?>\
+         <!DOCTYPE target SYSTEM \"gdb-target.dtd\">\
+         <target version=\"1.0\">\
+         <architecture>riscv:rv32</architecture>\
+         <feature name=\"org.gnu.gdb.riscv.cpu\">",
+    );
+    for (i, name) in INT_NAMES.iter().enumerate() {
+        let ty = match i {
+            1 => "code_ptr",
+            2 | 8 => "data_ptr",
+            _ => "int",
+        };
+        xml.push_str(&std::format!(
+            "<reg name=\"{}\" bitsize=\"32\" type=\"{}\" regnum=\"{}\"/>",
+            name,
+            ty,
+            i
+        ));
+    }
+    xml.push_str(&std::format!(
+        "<reg name=\"pc\" bitsize=\"32\" type=\"code_ptr\" regnum=\"{}\"/>",
+        REG_PC
+    ));
+    xml.push_str("</feature><feature name=\"org.gnu.gdb.riscv.fpu\">");
+    for (i, name) in FLOAT_NAMES.iter().enumerate() {
+        xml.push_str(&std::format!(
+            "<reg name=\"{}\" bitsize=\"64\" type=\"ieee_double\" regnum=\"{}\"/>",
+            name,
+            REG_FIRST_FLOAT + i
+        ));
+    }
+    for (name, csr) in [("fflags", CSR_FFLAGS), ("frm", CSR_FRM), ("fcsr", CSR_FCSR)].iter() {
+        xml.push_str(&std::format!(
+            "<reg name=\"{}\" bitsize=\"32\" type=\"int\" regnum=\"{}\" group=\"float\"/>",
+            name,
+            REG_FIRST_CSR + csr
+        ));
+    }
+    xml.push_str("</feature></target>");
+    xml
+}
+
+// Removes the escaping that GDB applies to binary data in packets.
+fn unescape(data: Vec<u8>) -> Vec<u8> {
+    if !data.contains(&b'}') {
+        return data;
+    }
+    let mut result = Vec::with_capacity(data.len());
+    let mut bytes = data.into_iter();
+    while let Some(b) = bytes.next() {
+        if b == b'}' {
+            if let Some(next) = bytes.next() {
+                result.push(next ^ 0x20);
+            }
+        } else {
+            result.push(b);
+        }
+    }
+    result
+}
+
+fn split_at_byte(data: &[u8], sep: u8) -> Option<(&[u8], &[u8])> {
+    let i = data.iter().position(|b| *b == sep)?;
+    Some((&data[..i], &data[i + 1..]))
+}
+
+fn parse_addr_len(data: &[u8]) -> Option<(u32, u32)> {
+    let (addr, len) = split_at_byte(data, b',')?;
+    Some((parse_hex(addr)? as u32, parse_hex(len)? as u32))
+}
+
+// Parses the arguments of a Z or z packet, which are the breakpoint type,
+// address, and kind.
+fn parse_breakpoint(data: &[u8]) -> Option<(u32, u32, u32)> {
+    let (ty, rest) = split_at_byte(data, b',')?;
+    let (addr, kind) = split_at_byte(rest, b',')?;
+    // The kind may be followed by conditions, which we don't support.
+    let kind = match split_at_byte(kind, b';') {
+        Some((kind, _)) => kind,
+        None => kind,
+    };
+    Some((
+        parse_hex(ty)? as u32,
+        parse_hex(addr)? as u32,
+        parse_hex(kind)? as u32,
+    ))
+}
+
+fn parse_hex(data: &[u8]) -> Option<u64> {
+    if data.is_empty() || data.len() > 16 {
+        return None;
+    }
+    let mut v: u64 = 0;
+    for c in data.iter() {
+        v = (v << 4) | (*c as char).to_digit(16)? as u64;
+    }
+    Some(v)
+}
+
+// Parses a register value, which GDB sends as bytes in target (little
+// endian) order.
+fn parse_hex_le(data: &[u8]) -> Option<u64> {
+    if data.is_empty() || data.len() > 16 || !data.len().is_multiple_of(2) {
+        return None;
+    }
+    let mut v: u64 = 0;
+    for (i, pair) in data.chunks(2).enumerate() {
+        v |= parse_hex(pair)? << (i * 8);
+    }
+    Some(v)
+}
+
+fn push_hex_u8(s: &mut String, v: u8) {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    s.push(DIGITS[(v >> 4) as usize] as char);
+    s.push(DIGITS[(v & 0xf) as usize] as char);
+}
+
+fn push_hex_u32(s: &mut String, v: u32) {
+    for b in v.to_le_bytes().iter() {
+        push_hex_u8(s, *b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GdbStub, Pipe, SessionEnd};
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::RV32;
+    use crate::memory::{AddressConverter, Bus, Memory};
+    use crate::register::IntRegister;
+    use std::io::Cursor;
+    use std::string::String;
+    use std::vec::Vec;
+
+    fn packet(data: &str) -> String {
+        let sum = data.bytes().fold(0u8, |sum, b| sum.wrapping_add(b));
+        std::format!("${}#{:02x}", data, sum)
+    }
+
+    #[test]
+    fn session() {
+        let mut mem_buf = [0u8; 256];
+        let program: [u32; 5] = [
+            0x00500513, // li a0, 5
+            0x00150513, // addi a0, a0, 1
+            0x00150513, // addi a0, a0, 1
+            0x05d00893, // li a7, 93
+            0x00000073, // ecall
+        ];
+        for (i, word) in program.iter().enumerate() {
+            mem_buf[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+        }
+        let mem = Memory::new_ram(&mut mem_buf);
+        let mut hart: SingleThreadUserHart<RV32, AddressConverter<u32, usize, Memory>> =
+            SingleThreadUserHart::new(AddressConverter::new(mem));
+
+        // After the first packet we switch to no-ack mode, so only the reply
+        // to that one needs acknowledging.
+        let mut input = packet("QStartNoAckMode");
+        input.push('+');
+        for p in [
+            "qSupported:swbreak+",
+            "Z0,4,4",
+            "m4,4",
+            "c",
+            "p20",
+            "p0a",
+            "z0,4,4",
+            "s",
+            "g",
+            "P0a=29000000",
+            "c",
+        ]
+        .iter()
+        {
+            input.push_str(&packet(p));
+        }
+
+        let conn = Pipe::new(Cursor::new(input.into_bytes()), Vec::new());
+        let mut stub = GdbStub::new(conn);
+        let end = stub
+            .run_rv32(&mut hart, |hart| {
+                Some(hart.read_int_register(IntRegister::numbered(10)) as i32)
+            })
+            .unwrap();
+        assert_eq!(end, SessionEnd::Exited(42));
+
+        let (_, output) = stub.into_connection().into_inner();
+        let output = String::from_utf8(output).unwrap();
+        let mut expected = String::from("+");
+        for r in [
+            "OK",
+            "PacketSize=4000;qXfer:features:read+;swbreak+;QStartNoAckMode+",
+            "OK",
+            "13051500", // original instruction, not the breakpoint
+            "T05swbreak:;",
+            "04000000", // pc at the breakpoint
+            "05000000", // a0 before the breakpoint instruction
+            "OK",
+            "S05", // a single step, not a breakpoint
+        ]
+        .iter()
+        {
+            expected.push_str(&packet(r));
+        }
+        assert!(
+            output.starts_with(&expected),
+            "unexpected output\n got: {}\nwant: {}",
+            output,
+            expected
+        );
+
+        // The "g" reply has a0 = 6 and pc = 8 after the step, and then the
+        // program adds one to the value we wrote to a0 before exiting.
+        let rest = &output[expected.len()..];
+        let g_reply = &rest[1..rest.find('#').unwrap()];
+        assert_eq!(&g_reply[10 * 8..11 * 8], "06000000");
+        assert_eq!(&g_reply[32 * 8..], "08000000");
+        assert!(rest.ends_with(&std::format!("{}{}", packet("OK"), packet("W2a"))));
+
+        hart.with_memory(|mem| {
+            assert_eq!(mem.read_word(4).unwrap(), 0x00150513, "breakpoint removed");
+        });
+    }
+
+    #[test]
+    fn register_numbers_out_of_range() {
+        let mut mem_buf = [0u8; 16];
+        let mem = Memory::new_ram(&mut mem_buf);
+        let mut hart: SingleThreadUserHart<RV32, AddressConverter<u32, usize, Memory>> =
+            SingleThreadUserHart::new(AddressConverter::new(mem));
+
+        // Register 0x1040 is the last CSR, 0xfff, which the hart doesn't
+        // implement. The register numbers after it don't refer to any CSR.
+        let mut input = packet("QStartNoAckMode");
+        input.push('+');
+        for p in [
+            "p1040",
+            "p1041",
+            "pffffffff",
+            "P1041=01000000",
+            "Pffffffff=01000000",
+            "D",
+        ]
+        .iter()
+        {
+            input.push_str(&packet(p));
+        }
+
+        let conn = Pipe::new(Cursor::new(input.into_bytes()), Vec::new());
+        let mut stub = GdbStub::new(conn);
+        let end = stub.run_rv32(&mut hart, |_| None).unwrap();
+        assert_eq!(end, SessionEnd::Detached);
+
+        let (_, output) = stub.into_connection().into_inner();
+        let output = String::from_utf8(output).unwrap();
+        let mut expected = String::from("+");
+        for r in ["OK", "xxxxxxxx", "xxxxxxxx", "xxxxxxxx", "E01", "E01", "OK"].iter() {
+            expected.push_str(&packet(r));
+        }
+        assert_eq!(output, expected);
+    }
+}
@@ -1,8 +1,9 @@
 use crate::data::{Float, Int, Zero};
 use crate::exception::ExceptionCause;
-use crate::isa::BaseISA;
-use crate::memory::Bus;
+use crate::isa::{BaseISA, Extensions};
+use crate::memory::{Bus, FenceSet};
 use crate::register::{CSRError, ControlStatusRegister, FloatRegister, IntRegister};
+use crate::vector::{VectorRegisters, VectorSnapshot};
 
 /// Represents the mutable state for a RISC-V "hardware thread", and is
 /// responsible for providing the execution environment(s) for code running
@@ -31,6 +32,28 @@ where
     /// caller must handle that somehow itself.
     fn write_pc(&mut self, v: Addr);
 
+    /// Returns the set of ISA extensions that are currently enabled.
+    ///
+    /// The executor raises an illegal instruction exception for any
+    /// operation that requires an extension not in this set. The default
+    /// implementation enables every extension that the decoder supports.
+    fn extensions(&self) -> Extensions {
+        Extensions::ALL
+    }
+
+    /// Returns the instruction-address alignment (IALIGN) in bytes: 2 when
+    /// compressed instructions are enabled, or 4 otherwise.
+    ///
+    /// The executor raises an instruction-address-misaligned exception on any
+    /// jump or taken branch whose target isn't a multiple of this, rather
+    /// than writing that target to the program counter.
+    fn instruction_alignment(&self) -> usize {
+        match self.extensions().contains(Extensions::C) {
+            true => 2,
+            false => 4,
+        }
+    }
+
     /// Reads the current value of the given integer register.
     fn read_int_register(&self, reg: IntRegister) -> IntData;
 
@@ -43,6 +66,14 @@ where
     /// Writes a new value to the given floating point register.
     fn write_float_register(&mut self, reg: FloatRegister, v: FloatData);
 
+    /// Returns the hart's vector registers and the vector CSRs that
+    /// configure them, or `None` if the hart has none, in which case the
+    /// executor raises an illegal instruction exception for every vector
+    /// instruction.
+    fn vector_registers(&mut self) -> Option<&mut VectorRegisters> {
+        None
+    }
+
     /// Reads the current value of the given CSR.
     fn read_csr(&self, reg: ControlStatusRegister) -> Result<IntData, CSRError>;
 
@@ -70,7 +101,16 @@ where
     /// standard machine-level ISA specification, it is likely to update
     /// some CSRs related to trap handling and alter the program counter
     /// to refer to a trap handling function.
-    fn exception(&mut self, cause: ExceptionCause);
+    ///
+    /// When called by the executor, the program counter refers to the
+    /// instruction that raised the exception, and `tval` is the value
+    /// defined for the trap value CSR, such as the faulting address.
+    ///
+    /// Returns `true` to indicate that the hart handled the exception
+    /// itself, or `false` to indicate that it should be reported to the
+    /// calling Rust program as `ExecStatus::Exception` instead, in which
+    /// case the hart state must be left unchanged.
+    fn exception(&mut self, cause: ExceptionCause, tval: Addr) -> bool;
 
     /// Signals a data memory fence, as represented by the `fence`
     /// instruction in the RISC-V base integer ISAs.
@@ -79,13 +119,34 @@ where
     /// An implementation that includes per-hart data caches might flush
     /// those caches in response to a call to this method, for example.
     ///
-    /// The predecessor and successor fields from the `fence` instruction
-    /// are not currently exposed. The definition of this method may change
-    /// in future to include precessor and successor information.
-    fn fence_data(&mut self) {
+    /// `pred` and `succ` are the predecessor and successor sets from the
+    /// `fence` instruction. Atomic instructions with the `aq` or `rl` bits
+    /// set are treated as being preceded or followed by a fence ordering all
+    /// memory accesses.
+    fn fence_data(&mut self, _pred: FenceSet, _succ: FenceSet) {
+        // default implementation does nothing
+    }
+
+    /// Records the reservation made by a "load-reserved" instruction: the
+    /// address that was loaded and the value that was read from it. Passing
+    /// `None` clears any existing reservation.
+    ///
+    /// The default implementation does not keep a reservation, in which case
+    /// every "store-conditional" instruction fails.
+    fn set_reservation(&mut self, _reservation: Option<(Addr, IntData)>) {
         // default implementation does nothing
     }
 
+    /// Returns and clears the current reservation, if any, so that a
+    /// "store-conditional" instruction can attempt to use it.
+    ///
+    /// The executor implements store-conditional as an atomic compare and
+    /// exchange against the value that was loaded, so it succeeds if the
+    /// memory still holds that value even if it was overwritten in between.
+    fn take_reservation(&mut self) -> Option<(Addr, IntData)> {
+        None
+    }
+
     /// Signals an instruction memory fence, as represented by the `fence.i`
     /// instruction in the RISC-V base integer ISAs.
     ///
@@ -102,15 +163,19 @@ where
     /// The `rs1` and `rs2` arguments have the meaning defined for the
     /// `sfence.vma` instruction.
     ///
-    /// This method may trigger exception handling as a side-effect, as if
-    /// there had been a call to the method "exception", if the current
-    /// hart state does not permit virtual memory updates or if the
-    /// Supervisor-level ISA is not included in this implementation at all.
-    fn fence_virtual_memory_config(&mut self, rs1: IntRegister, rs2: IntRegister) {
+    /// Returns the cause of the exception that the instruction should raise
+    /// if the current hart state does not permit virtual memory updates or
+    /// if the Supervisor-level ISA is not included in this implementation
+    /// at all.
+    fn fence_virtual_memory_config(
+        &mut self,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    ) -> Result<(), ExceptionCause> {
         // default implementation raises an illegal instruction exception,
         // to suggest that the supervisor-level ISA is not implemented
         // at all.
-        self.exception(ExceptionCause::IllegalInstruction)
+        Err(ExceptionCause::IllegalInstruction)
     }
 
     /// Called when handling an "environment call" instruction, to give the
@@ -145,21 +210,26 @@ where
 ///
 /// This implementation cannot represent one of many Harts in a multi-core or
 /// hardware-multi-threaded system, because it takes exclusive ownership over
-/// the memory bus it will use. (A multi-thread-aware implementation would need
-/// to safely share access to a memory bus, e.g. via a mutex. No such
-/// implementation is provided in this crate.)
+/// the memory bus it will use. (A multi-thread-aware implementation needs to
+/// safely share access to a memory bus, e.g. via a mutex, as `smp::SmpHart`
+/// does.)
 ///
-/// This implementation does not support virtual memory, and treats fence
-/// instructions as no-op.
+/// This implementation does not support virtual memory. Data fences are
+/// passed on to the memory bus, and instruction fences are treated as no-op.
 pub struct SingleThreadUserHart<ISA, Mem>
 where
     ISA: BaseISA,
     Mem: Bus<<ISA::Int as Int>::Unsigned>,
 {
     pc: <ISA::Int as Int>::Unsigned,
-    int_regs: [ISA::Int; 32],
+    int_regs: ISA::IntRegisters,
     float_regs: [ISA::Float; 32],
-    csrs: SingleThreadUserHartCSRs<ISA>,
+    vector: VectorRegisters,
+    csrs: SingleThreadUserHartCSRs<ISA::Int>,
+    reservation: Option<(<ISA::Int as Int>::Unsigned, ISA::Int)>,
+    report_exceptions: bool,
+    extensions: Extensions,
+    misa_writable: bool,
     mem: Mem,
 }
 
@@ -173,50 +243,101 @@ where
             pc: Self::pc_at_reset(),
             int_regs: Self::int_registers_at_reset(),
             float_regs: Self::float_registers_at_reset(),
-            csrs: Self::csrs_at_reset(),
+            vector: VectorRegisters::new(128),
+            csrs: Self::csrs_at_reset(Self::with_base(Extensions::ALL)),
+            reservation: None,
+            report_exceptions: false,
+            extensions: Self::with_base(Extensions::ALL),
+            misa_writable: false,
             mem: mem,
         }
     }
 
+    /// Selects whether exceptions are reported to the caller of the
+    /// executor as `ExecStatus::Exception` rather than handled by the hart.
+    ///
+    /// By default, the hart handles an exception by recording it in its
+    /// trap-handling CSRs and jumping to the handler whose address is in
+    /// `utvec`. A program running without a trap handler would then jump to
+    /// address zero, so embedders that don't provide one should usually
+    /// enable reporting instead.
+    pub fn set_report_exceptions(&mut self, report: bool) {
+        self.report_exceptions = report;
+    }
+
+    /// Selects the ISA extensions that the hart implements, such as those
+    /// parsed from an ISA string by `Extensions::parse`, and enables all of
+    /// them. By default, the hart implements every extension the decoder
+    /// supports.
+    ///
+    /// The hart's own base integer ISA is always implemented, and replaces
+    /// any other base ISA in the set.
+    pub fn set_extensions(&mut self, extensions: Extensions) {
+        self.extensions = Self::with_base(extensions);
+        self.csrs.misa = Self::misa(self.extensions);
+    }
+
+    /// Selects whether writes to the `misa` CSR can disable and re-enable
+    /// the hart's extensions at runtime. By default, `misa` is read-only and
+    /// writes to it are ignored.
+    pub fn set_misa_writable(&mut self, writable: bool) {
+        self.misa_writable = writable;
+    }
+
+    /// Selects the length in bits of each of the hart's vector registers
+    /// (VLEN), which must be a power of two from 128 up to `MAX_VLEN`. The
+    /// default is 128.
+    ///
+    /// This returns the vector registers and CSRs to their reset state.
+    pub fn set_vector_length(&mut self, vlen: usize) {
+        self.vector = VectorRegisters::new(vlen);
+    }
+
+    /// Captures the hart's current register state.
+    pub fn snapshot(&self) -> HartSnapshot<ISA::Int, ISA::Float> {
+        let mut int_regs = [ISA::Int::zero(); 32];
+        let regs = self.int_regs.as_ref();
+        int_regs[..regs.len()].copy_from_slice(regs);
+        HartSnapshot {
+            pc: ISA::Int::from_unsigned(self.pc),
+            int_regs,
+            float_regs: self.float_regs,
+            csrs: self.csrs,
+            reservation: self
+                .reservation
+                .map(|(addr, v)| (ISA::Int::from_unsigned(addr), v)),
+            vector: self.vector.snapshot(),
+        }
+    }
+
+    /// Replaces the hart's register state with a previously-captured
+    /// snapshot. The memory bus is not affected.
+    ///
+    /// The vector register length also changes to the one in the snapshot,
+    /// so this panics if that length isn't one that `set_vector_length`
+    /// accepts.
+    pub fn restore(&mut self, snapshot: &HartSnapshot<ISA::Int, ISA::Float>) {
+        self.pc = snapshot.pc.to_unsigned();
+        let regs = self.int_regs.as_mut();
+        let count = regs.len();
+        regs.copy_from_slice(&snapshot.int_regs[..count]);
+        regs[0] = ISA::Int::zero();
+        self.float_regs = snapshot.float_regs;
+        self.csrs = snapshot.csrs;
+        self.reservation = snapshot
+            .reservation
+            .map(|(addr, v)| (addr.to_unsigned(), v));
+        self.vector = VectorRegisters::from_snapshot(&snapshot.vector);
+    }
+
     fn pc_at_reset() -> <ISA::Int as Int>::Unsigned {
         ISA::Int::from_unsigned_word(0).to_unsigned()
     }
 
-    fn int_registers_at_reset() -> [ISA::Int; 32] {
-        [
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-            ISA::Int::zero(),
-        ]
+    fn int_registers_at_reset() -> ISA::IntRegisters {
+        let mut regs = ISA::IntRegisters::default();
+        regs.as_mut().fill(ISA::Int::zero());
+        regs
     }
 
     fn float_registers_at_reset() -> [ISA::Float; 32] {
@@ -256,8 +377,9 @@ where
         ]
     }
 
-    fn csrs_at_reset() -> SingleThreadUserHartCSRs<ISA> {
+    fn csrs_at_reset(extensions: Extensions) -> SingleThreadUserHartCSRs<ISA::Int> {
         SingleThreadUserHartCSRs {
+            misa: Self::misa(extensions),
             ustatus: ISA::Int::zero(),
             uie: ISA::Int::zero(),
             utvec: ISA::Int::zero(),
@@ -270,6 +392,93 @@ where
             frm: ISA::Int::zero(),
         }
     }
+
+    // Returns the misa value describing the base ISA and the given
+    // extensions.
+    fn misa(extensions: Extensions) -> ISA::Int {
+        let mxl = ISA::Int::from_unsigned_word((ISA::XLEN as u32 / 32).ilog2() + 1);
+        let shift = ISA::Int::from_unsigned_word(ISA::XLEN as u32 - 2);
+        let letters = ISA::Int::from_unsigned_word(extensions.misa_letters());
+        ISA::Int::from_unsigned((mxl.to_unsigned() << shift.to_unsigned()) | letters.to_unsigned())
+    }
+
+    // Replaces the base ISA in the given set of extensions with the hart's
+    // own.
+    fn with_base(extensions: Extensions) -> Extensions {
+        extensions
+            .without(Extensions::I.union(Extensions::E))
+            .union(ISA::BASE)
+    }
+
+    // Updates the enabled extensions after a write to misa, which can only
+    // disable and re-enable the extensions that the hart implements.
+    fn write_misa(&mut self, letters: u32) {
+        let mut enabled = self
+            .extensions
+            .with_misa_letters(letters & self.extensions.misa_letters())
+            .union(ISA::BASE);
+        // An extension can't remain enabled without those it depends on.
+        if !enabled.contains(Extensions::F) {
+            enabled = enabled.without(Extensions::D);
+        }
+        if !enabled.contains(Extensions::D) {
+            enabled = enabled.without(Extensions::Q);
+        }
+        // The write is ignored if it would raise IALIGN while the next
+        // instruction is only aligned to two bytes.
+        let next_pc = ISA::Int::from_unsigned(self.pc).to_unsigned_word();
+        if !enabled.contains(Extensions::C) && !next_pc.is_multiple_of(4) {
+            return;
+        }
+        self.csrs.misa = Self::misa(enabled);
+    }
+
+    // Reads one of the vector CSRs, which exist only while the vector
+    // extension is enabled.
+    fn read_vector_csr(&self, num: usize) -> Option<ISA::Int> {
+        if !self.extensions().contains(Extensions::V) {
+            return None;
+        }
+        let v = &self.vector;
+        let word = |w: u32| ISA::Int::from_unsigned_word(w);
+        match num {
+            0x0008 => Some(word(v.vstart())),
+            0x0009 => Some(word(v.vxsat() as u32)),
+            0x000A => Some(word(v.vxrm())),
+            0x000F => Some(word((v.vxrm() << 1) | v.vxsat() as u32)),
+            0x0C20 => Some(word(v.vl())),
+            0x0C21 if v.vill() => {
+                // vill is the most significant bit, whatever the XLEN.
+                let shift = word(ISA::XLEN as u32 - 1);
+                Some(ISA::Int::from_unsigned(
+                    word(1).to_unsigned() << shift.to_unsigned(),
+                ))
+            }
+            0x0C21 => Some(word(v.vtype())),
+            0x0C22 => Some(word(v.vlenb() as u32)),
+            _ => None,
+        }
+    }
+
+    // Writes one of the writable vector CSRs, returning false if there is
+    // no such CSR.
+    fn write_vector_csr(&mut self, num: usize, v: ISA::Int) -> bool {
+        if !self.extensions().contains(Extensions::V) {
+            return false;
+        }
+        let v = v.to_unsigned_word();
+        match num {
+            0x0008 => self.vector.set_vstart(v),
+            0x0009 => self.vector.set_vxsat(v & 1 != 0),
+            0x000A => self.vector.set_vxrm(v),
+            0x000F => {
+                self.vector.set_vxsat(v & 1 != 0);
+                self.vector.set_vxrm(v >> 1);
+            }
+            _ => return false,
+        }
+        true
+    }
 }
 
 impl<ISA, Mem> Hart<<ISA::Int as Int>::Unsigned, ISA::Int, ISA::Float, Mem>
@@ -286,8 +495,19 @@ where
         self.pc = v
     }
 
+    fn extensions(&self) -> Extensions {
+        let letters = self.csrs.misa.to_unsigned_word() & self.extensions.misa_letters();
+        self.extensions.with_misa_letters(letters)
+    }
+
     fn read_int_register(&self, reg: IntRegister) -> ISA::Int {
-        self.int_regs[reg.num()]
+        // The embedded base ISAs have no registers above x15. The executor
+        // doesn't run instructions that refer to them, but a debugger might
+        // still ask, so they read as zero.
+        match self.int_regs.as_ref().get(reg.num()) {
+            Some(v) => *v,
+            None => ISA::Int::zero(),
+        }
     }
 
     fn write_int_register(&mut self, reg: IntRegister, v: ISA::Int) {
@@ -295,7 +515,9 @@ where
             // Register zero is always fixed at zero, so we ignore this request.
             return;
         }
-        self.int_regs[reg.num()] = v
+        if let Some(r) = self.int_regs.as_mut().get_mut(reg.num()) {
+            *r = v
+        }
     }
 
     fn read_float_register(&self, reg: FloatRegister) -> ISA::Float {
@@ -308,18 +530,54 @@ where
 
     fn read_csr(&self, reg: ControlStatusRegister) -> Result<ISA::Int, CSRError> {
         match reg.num() {
+            0x0000 => Ok(self.csrs.ustatus),
+            0x0001 => Ok(self.csrs.fflags),
+            0x0002 => Ok(self.csrs.frm),
+            0x0003 => Ok(ISA::Int::from_unsigned_word(
+                (self.csrs.frm.to_unsigned_word() << 5) | self.csrs.fflags.to_unsigned_word(),
+            )), // fcsr (frm and fflags combined)
+            0x0004 => Ok(self.csrs.uie),
+            0x0005 => Ok(self.csrs.utvec),
+            0x0040 => Ok(self.csrs.uscratch),
+            0x0041 => Ok(self.csrs.uepc),
+            0x0042 => Ok(self.csrs.ucause),
+            0x0043 => Ok(self.csrs.utval),
+            0x0044 => Ok(self.csrs.uip),
+            0x0301 => Ok(self.csrs.misa), // misa (readable so programs can discover the ISA)
             0x0F14 => Ok(ISA::Int::zero()), // mhartid (always zero for single-threaded)
-
-            // TODO: Implement the CSRs that we have in SingleThreadUserHartCSRs
-            _ => Err(CSRError::Unsupported),
+            n => self.read_vector_csr(n).ok_or(CSRError::Unsupported),
         }
     }
 
     fn write_csr(&mut self, reg: ControlStatusRegister, v: ISA::Int) -> Result<(), CSRError> {
+        // Only the low five bits of fflags and three bits of frm exist.
+        let masked = |bits: u32| {
+            ISA::Int::from_unsigned(
+                v.to_unsigned() & ISA::Int::from_unsigned_word(bits).to_unsigned(),
+            )
+        };
         match reg.num() {
-            // TODO: Implement the CSRs that we have in SingleThreadUserHartCSRs
-            _ => Err(CSRError::Unsupported),
+            0x0000 => self.csrs.ustatus = v,
+            0x0001 => self.csrs.fflags = masked(0b11111),
+            0x0002 => self.csrs.frm = masked(0b111),
+            0x0003 => {
+                let v = v.to_unsigned_word();
+                self.csrs.fflags = ISA::Int::from_unsigned_word(v & 0b11111);
+                self.csrs.frm = ISA::Int::from_unsigned_word((v >> 5) & 0b111);
+            }
+            0x0004 => self.csrs.uie = v,
+            0x0005 => self.csrs.utvec = v,
+            0x0040 => self.csrs.uscratch = v,
+            0x0041 => self.csrs.uepc = v,
+            0x0042 => self.csrs.ucause = v,
+            0x0043 => self.csrs.utval = v,
+            0x0044 => self.csrs.uip = v,
+            0x0301 if self.misa_writable => self.write_misa(v.to_unsigned_word()),
+            0x0301 => {} // misa (writes are ignored unless enabled)
+            n if self.write_vector_csr(n, v) => {}
+            _ => return Err(CSRError::Unsupported),
         }
+        Ok(())
     }
 
     fn with_memory<R>(&mut self, f: impl FnOnce(&mut Mem) -> R) -> R {
@@ -330,11 +588,16 @@ where
         self.pc = Self::pc_at_reset();
         self.int_regs = Self::int_registers_at_reset();
         self.float_regs = Self::float_registers_at_reset();
-        self.csrs = Self::csrs_at_reset();
+        self.vector.reset();
+        self.csrs = Self::csrs_at_reset(self.extensions);
         self.csrs.ucause = cause;
+        self.reservation = None;
     }
 
-    fn exception(&mut self, cause: ExceptionCause) {
+    fn exception(&mut self, cause: ExceptionCause, tval: <ISA::Int as Int>::Unsigned) -> bool {
+        if self.report_exceptions {
+            return false;
+        }
         let vec_raw = self.csrs.utvec.to_unsigned();
         let mask = ISA::Int::from_unsigned_word(0b11).to_unsigned();
         let vec_base = vec_raw & !mask;
@@ -344,36 +607,85 @@ where
         //let mode_vectored = ISA::Int::from_unsigned_word(1).to_unsigned();
 
         let new_pc: <ISA::Int as Int>::Unsigned = vec_base;
+        self.csrs.uepc = ISA::Int::from_unsigned(self.pc);
         self.write_pc(new_pc);
         self.csrs.ucause = ISA::Int::from_unsigned_word(cause as u32);
-        // TODO" Other exception-related CSRs
+        self.csrs.utval = ISA::Int::from_unsigned(tval);
+        true
+    }
+
+    fn fence_data(&mut self, pred: FenceSet, succ: FenceSet) {
+        self.mem.fence(pred, succ)
+    }
+
+    fn set_reservation(&mut self, reservation: Option<(<ISA::Int as Int>::Unsigned, ISA::Int)>) {
+        self.reservation = reservation;
+    }
+
+    fn take_reservation(&mut self) -> Option<(<ISA::Int as Int>::Unsigned, ISA::Int)> {
+        self.reservation.take()
+    }
+
+    fn vector_registers(&mut self) -> Option<&mut VectorRegisters> {
+        Some(&mut self.vector)
     }
 }
 
-struct SingleThreadUserHartCSRs<ISA>
-where
-    ISA: BaseISA,
-{
-    pub ustatus: ISA::Int,
-    pub uie: ISA::Int,
-    pub utvec: ISA::Int,
-    pub uscratch: ISA::Int,
-    pub uepc: ISA::Int,
-    pub ucause: ISA::Int,
-    pub utval: ISA::Int,
-    pub uip: ISA::Int,
-    pub fflags: ISA::Int,
-    pub frm: ISA::Int,
+/// The control and status registers of a `SingleThreadUserHart`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SingleThreadUserHartCSRs<Int> {
+    pub misa: Int,
+    pub ustatus: Int,
+    pub uie: Int,
+    pub utvec: Int,
+    pub uscratch: Int,
+    pub uepc: Int,
+    pub ucause: Int,
+    pub utval: Int,
+    pub uip: Int,
+    pub fflags: Int,
+    pub frm: Int,
+}
+
+/// A copy of the complete register state of a `SingleThreadUserHart`, which
+/// can be restored into that hart or another one later.
+///
+/// A snapshot does not include the contents of memory, which must be saved
+/// separately if needed.
+///
+/// When the `serde` feature is enabled, snapshots can be serialized so that
+/// they can be saved to a file.
+///
+/// A snapshot always has space for 32 integer registers. For the embedded
+/// base ISAs, x16 to x31 are zero and are ignored when it is restored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HartSnapshot<Int, Float> {
+    pub pc: Int,
+    pub int_regs: [Int; 32],
+    pub float_regs: [Float; 32],
+    pub csrs: SingleThreadUserHartCSRs<Int>,
+
+    /// The reservation made by the most recent "load-reserved" instruction,
+    /// as the address and the value loaded, if it hasn't yet been used.
+    pub reservation: Option<(Int, Int)>,
+
+    /// The vector registers and the vector CSRs, other than `vlenb`, which
+    /// follows from the vector register length.
+    pub vector: VectorSnapshot,
 }
 
 #[cfg(test)]
 mod tests {
     use super::{Hart, SingleThreadUserHart};
-    use crate::isa::RV32;
+    use crate::exception::ExceptionCause;
+    use crate::exec::{step_rv32, ExecStatus};
+    use crate::isa::{Extensions, RV32, RV32E, RV64E};
     use crate::memory::AddressConverter;
     use crate::memory::Bus;
     use crate::memory::Memory;
-    use crate::register::{FloatRegister, IntRegister};
+    use crate::register::{ControlStatusRegister, FloatRegister, IntRegister};
 
     #[test]
     fn single_thread_user_hart() {
@@ -412,4 +724,178 @@ mod tests {
             assert_eq!(v, 5, "was able to write to and then read from memory");
         })
     }
+
+    #[test]
+    fn snapshot_and_restore() {
+        let mut mem_buf = [0u8; 16];
+        let mem = Memory::new_ram(&mut mem_buf);
+        let mut hart: SingleThreadUserHart<RV32, AddressConverter<u32, usize, Memory>> =
+            SingleThreadUserHart::new(AddressConverter::new(mem));
+
+        let x5 = IntRegister::numbered(5);
+        let f3 = FloatRegister::numbered(3);
+        hart.write_pc(0x100);
+        hart.write_int_register(x5, 42);
+        hart.write_float_register(f3, 2.5);
+        let snapshot = hart.snapshot();
+        assert_eq!(snapshot.pc, 0x100);
+        assert_eq!(snapshot.int_regs[5], 42);
+
+        hart.reset(0);
+        assert_eq!(hart.read_int_register(x5), 0);
+        hart.restore(&snapshot);
+        assert_eq!(hart.read_pc(), 0x100);
+        assert_eq!(hart.read_int_register(x5), 42);
+        assert_eq!(hart.read_float_register(f3), 2.5);
+        assert_eq!(hart.snapshot(), snapshot);
+    }
+
+    #[test]
+    fn exceptions() {
+        let mut mem_buf = [0u8; 16];
+        mem_buf[4..8].copy_from_slice(&0x10002023u32.to_le_bytes()); // sw x0, 0x100(x0)
+        let mem = Memory::new_rom(&mut mem_buf);
+        let mut hart: SingleThreadUserHart<RV32, AddressConverter<u32, usize, Memory>> =
+            SingleThreadUserHart::new(AddressConverter::new(mem));
+
+        // By default, the hart jumps to its trap handler.
+        hart.write_pc(4);
+        assert!(matches!(step_rv32(&mut hart), ExecStatus::Running));
+        assert_eq!(hart.read_pc(), 0);
+        let csrs = hart.snapshot().csrs;
+        assert_eq!(csrs.uepc, 4);
+        assert_eq!(csrs.ucause, ExceptionCause::StoreAccessFault as u32);
+        assert_eq!(csrs.utval, 0x100);
+
+        // When reporting, it is left at the faulting instruction instead.
+        hart.set_report_exceptions(true);
+        hart.write_pc(4);
+        assert!(matches!(
+            step_rv32(&mut hart),
+            ExecStatus::Exception {
+                cause: ExceptionCause::StoreAccessFault,
+                pc: 4,
+                tval: 0x100,
+            }
+        ));
+        assert_eq!(hart.read_pc(), 4);
+    }
+
+    #[test]
+    fn extensions() {
+        let mut mem_buf = [0u8; 16];
+        mem_buf[0..4].copy_from_slice(&0x0000100fu32.to_le_bytes()); // fence.i
+        let mem = Memory::new_ram(&mut mem_buf);
+        let mut hart: SingleThreadUserHart<RV32, AddressConverter<u32, usize, Memory>> =
+            SingleThreadUserHart::new(AddressConverter::new(mem));
+        let misa = ControlStatusRegister::numbered(0x301);
+        assert_eq!(hart.extensions(), Extensions::ALL);
+
+        let imac = Extensions::parse::<RV32>("rv32imac_zicsr").unwrap();
+        hart.set_extensions(imac);
+        assert_eq!(hart.extensions(), imac);
+        assert_eq!(hart.read_csr(misa).ok(), Some(0x40001105));
+
+        // Without Zifencei, fence.i is an illegal instruction.
+        hart.set_report_exceptions(true);
+        assert!(matches!(
+            step_rv32(&mut hart),
+            ExecStatus::Exception {
+                cause: ExceptionCause::IllegalInstruction,
+                pc: 0,
+                tval: 0,
+            }
+        ));
+
+        // Writes are ignored until enabled, and can't add extensions that
+        // the hart doesn't implement.
+        hart.write_csr(misa, 0x40000100).unwrap();
+        assert_eq!(hart.read_csr(misa).ok(), Some(0x40001105));
+        hart.set_misa_writable(true);
+        hart.write_csr(misa, 0x40000120).unwrap();
+        assert_eq!(hart.read_csr(misa).ok(), Some(0x40000100));
+        assert_eq!(hart.extensions(), Extensions::I.union(Extensions::ZICSR));
+        assert_eq!(hart.instruction_alignment(), 4);
+
+        // Disabling C is ignored while the next instruction is misaligned.
+        hart.write_csr(misa, 0x40001105).unwrap();
+        hart.write_pc(6);
+        hart.write_csr(misa, 0x40001101).unwrap();
+        assert_eq!(hart.read_csr(misa).ok(), Some(0x40001105));
+        hart.write_pc(8);
+        hart.write_csr(misa, 0x40001101).unwrap();
+        assert_eq!(hart.read_csr(misa).ok(), Some(0x40001101));
+
+        hart.reset(0);
+        assert_eq!(hart.extensions(), imac);
+    }
+
+    #[test]
+    fn floating_point_csrs() {
+        let mut mem_buf = [0u8; 16];
+        let mem = Memory::new_ram(&mut mem_buf);
+        let mut hart: SingleThreadUserHart<RV32, AddressConverter<u32, usize, Memory>> =
+            SingleThreadUserHart::new(AddressConverter::new(mem));
+        let fflags = ControlStatusRegister::numbered(0x001);
+        let frm = ControlStatusRegister::numbered(0x002);
+        let fcsr = ControlStatusRegister::numbered(0x003);
+
+        // fcsr combines frm, in bits 7-5, with fflags, in bits 4-0.
+        hart.write_csr(fflags, 0b10101).unwrap();
+        hart.write_csr(frm, 0b011).unwrap();
+        assert_eq!(hart.read_csr(fcsr).ok(), Some(0b0111_0101));
+
+        // Writing fcsr writes both, ignoring the bits above them.
+        hart.write_csr(fcsr, 0xffff_ffca).unwrap();
+        assert_eq!(hart.read_csr(fflags).ok(), Some(0b01010));
+        assert_eq!(hart.read_csr(frm).ok(), Some(0b110));
+        assert_eq!(hart.read_csr(fcsr).ok(), Some(0b1100_1010));
+    }
+
+    #[test]
+    fn embedded_base_isa() {
+        let mut mem_buf = [0u8; 16];
+        mem_buf[0..4].copy_from_slice(&0x002087b3u32.to_le_bytes()); // add x15, x1, x2
+        mem_buf[4..8].copy_from_slice(&0x00208833u32.to_le_bytes()); // add x16, x1, x2
+        let mem = Memory::new_ram(&mut mem_buf);
+        let mut hart: SingleThreadUserHart<RV32E, AddressConverter<u32, usize, Memory>> =
+            SingleThreadUserHart::new(AddressConverter::new(mem));
+        hart.set_report_exceptions(true);
+        let misa = ControlStatusRegister::numbered(0x301);
+        assert_eq!(hart.read_csr(misa).ok(), Some(0x4021103d));
+
+        hart.write_int_register(IntRegister::numbered(1), 2);
+        hart.write_int_register(IntRegister::numbered(2), 3);
+        assert!(matches!(step_rv32(&mut hart), ExecStatus::Running));
+        assert_eq!(hart.read_int_register(IntRegister::numbered(15)), 5);
+        assert!(matches!(
+            step_rv32(&mut hart),
+            ExecStatus::Exception {
+                cause: ExceptionCause::IllegalInstruction,
+                pc: 4,
+                tval: 0,
+            }
+        ));
+
+        // The missing registers read as zero and ignore writes.
+        hart.write_int_register(IntRegister::numbered(16), 1);
+        assert_eq!(hart.read_int_register(IntRegister::numbered(16)), 0);
+        let snapshot = hart.snapshot();
+        assert_eq!(snapshot.int_regs[15], 5);
+        assert_eq!(snapshot.int_regs[16], 0);
+
+        // RV64E has the same restriction, with the wider registers.
+        let mut mem_buf = [0u8; 16];
+        let mem = Memory::new_ram(&mut mem_buf);
+        let mut hart: SingleThreadUserHart<RV64E, AddressConverter<u64, usize, Memory>> =
+            SingleThreadUserHart::new(AddressConverter::new(mem));
+        assert_eq!(hart.read_csr(misa).ok(), Some(0x800000000021103d));
+        for num in 1..32 {
+            hart.write_int_register(IntRegister::numbered(num), 1 << 40);
+        }
+        for num in 1..32 {
+            let want = if num < 16 { 1 << 40 } else { 0 };
+            assert_eq!(hart.read_int_register(IntRegister::numbered(num)), want);
+        }
+    }
 }
@@ -0,0 +1,422 @@
+use crate::data::Byte;
+use crate::data::HalfwordUnsigned as Halfword;
+use crate::data::LongwordUnsigned as Longword;
+use crate::data::QuadwordUnsigned as Quadword;
+use crate::data::WordUnsigned as Word;
+use crate::linux::{Errno, FileSystem};
+use crate::memory::{AtomicOp, Bus, FenceSet, MemoryError};
+
+// HTIF devices and their commands.
+const DEV_SYSCALL: u64 = 0;
+const DEV_CONSOLE: u64 = 1;
+const CMD_CONSOLE_GETCHAR: u64 = 0;
+const CMD_CONSOLE_PUTCHAR: u64 = 1;
+
+// The system calls that can be proxied through the syscall device, which
+// use the Linux RISC-V numbering.
+const SYS_CLOSE: u64 = 57;
+const SYS_READ: u64 = 63;
+const SYS_WRITE: u64 = 64;
+const SYS_EXIT: u64 = 93;
+
+const CHUNK_SIZE: usize = 512;
+
+/// Wraps another `Bus` to add the "host-target interface" (HTIF) device that
+/// the Spike simulator provides, through which the official `riscv-tests`
+/// and many other bare-metal test programs report their results.
+///
+/// The device consists of two 64-bit registers, `tohost` and `fromhost`,
+/// whose addresses are chosen by the program's linker script and so are
+/// usually found by looking up symbols of those names using
+/// `elf::Elf32::symbol`. Accesses to those addresses are handled by the
+/// device, while all other accesses pass through to the wrapped bus.
+///
+/// A write to `tohost` is decoded as a command when it writes the register's
+/// most significant byte, so that a 32-bit program writing the low word and
+/// then the high word is seen as a single 64-bit write. The following
+/// commands are supported:
+///
+/// * Device 0, command 0 with the least significant bit set requests that
+///   the simulation halt with the exit code in the remaining bits, where
+///   zero conventionally means that all tests passed. The exit code is then
+///   available from `halt_code`.
+/// * Device 0, command 0 with the least significant bit clear gives the
+///   address of a block of eight 64-bit words containing a system call
+///   number and its arguments. The `read`, `write`, `close` and `exit` calls
+///   are proxied to an implementation of `linux::FileSystem`, and the result
+///   is written back to the first word of the block.
+/// * Device 1, command 1 writes the character in the low byte to the
+///   console, which is file descriptor 1 of the filesystem.
+/// * Device 1, command 0 reads a character from the console, which is file
+///   descriptor 0 of the filesystem.
+///
+/// As with Spike, the device acknowledges each command by clearing `tohost`
+/// and, for commands that produce a response, writing it to `fromhost`.
+pub struct Htif<Wrapped: Bus<u32>, FS: FileSystem> {
+    wrapped: Wrapped,
+    fs: FS,
+    tohost_addr: u32,
+    fromhost_addr: u32,
+    tohost: u64,
+    fromhost: u64,
+    halt_code: Option<u64>,
+}
+
+impl<Wrapped: Bus<u32>, FS: FileSystem> Htif<Wrapped, FS> {
+    /// Consumes a bus and produces a wrapping `Htif` whose registers appear
+    /// at the given addresses, using the given filesystem for its console
+    /// and proxied system calls.
+    pub fn new(wrapped: Wrapped, fs: FS, tohost_addr: u32, fromhost_addr: u32) -> Self {
+        Self {
+            wrapped,
+            fs,
+            tohost_addr,
+            fromhost_addr,
+            tohost: 0,
+            fromhost: 0,
+            halt_code: None,
+        }
+    }
+
+    /// Returns the exit code the program requested if it has asked to halt,
+    /// or `None` if it should continue running.
+    ///
+    /// For `riscv-tests` programs an exit code of zero indicates success,
+    /// while any other value is the number of the first failing test.
+    pub fn halt_code(&self) -> Option<u64> {
+        self.halt_code
+    }
+
+    /// Borrows the filesystem the device is using.
+    pub fn file_system(&mut self) -> &mut FS {
+        &mut self.fs
+    }
+
+    /// Borrows the wrapped bus.
+    pub fn wrapped(&mut self) -> &mut Wrapped {
+        &mut self.wrapped
+    }
+
+    // If the given access falls entirely within one of the device registers,
+    // returns a reference to that register, whether it is `tohost`, and the
+    // bit offset of the access within it.
+    fn register(&mut self, addr: u32, size: u32) -> Option<(&mut u64, bool, u32)> {
+        let offset = addr.wrapping_sub(self.tohost_addr);
+        if offset < 8 && offset + size <= 8 {
+            return Some((&mut self.tohost, true, offset * 8));
+        }
+        let offset = addr.wrapping_sub(self.fromhost_addr);
+        if offset < 8 && offset + size <= 8 {
+            return Some((&mut self.fromhost, false, offset * 8));
+        }
+        None
+    }
+
+    // Reads from a device register, returning `None` if the given access
+    // does not belong to the device.
+    fn read_register(&mut self, addr: u32, size: u32) -> Option<u64> {
+        let (reg, _, shift) = self.register(addr, size)?;
+        let mask = u64::MAX >> (64 - size * 8);
+        Some((*reg >> shift) & mask)
+    }
+
+    // Writes to a device register, returning false if the given access does
+    // not belong to the device.
+    fn write_register(&mut self, addr: u32, size: u32, data: u64) -> bool {
+        let (reg, is_tohost, shift) = match self.register(addr, size) {
+            Some(r) => r,
+            None => return false,
+        };
+        let mask = (u64::MAX >> (64 - size * 8)) << shift;
+        *reg = (*reg & !mask) | ((data << shift) & mask);
+        if is_tohost && shift + size * 8 == 64 && self.tohost != 0 {
+            let cmd = self.tohost;
+            self.tohost = 0;
+            self.command(cmd);
+        }
+        true
+    }
+
+    fn command(&mut self, cmd: u64) {
+        let device = cmd >> 56;
+        let command = (cmd >> 48) & 0xff;
+        let payload = cmd & 0xffff_ffff_ffff;
+        match (device, command) {
+            (DEV_SYSCALL, 0) if payload & 1 != 0 => {
+                self.halt_code = Some(payload >> 1);
+            }
+            (DEV_SYSCALL, 0) => {
+                self.syscall(payload as u32);
+                self.fromhost = 1;
+            }
+            (DEV_CONSOLE, CMD_CONSOLE_PUTCHAR) => {
+                let _ = self.fs.write(1, &[payload as u8]);
+                self.fromhost = cmd & !0xffff_ffff_ffff;
+            }
+            (DEV_CONSOLE, CMD_CONSOLE_GETCHAR) => {
+                // If no input is available then there's no response, and
+                // the program is expected to try again later.
+                let mut c = [0u8; 1];
+                if let Ok(1) = self.fs.read(0, &mut c) {
+                    self.fromhost = (cmd & !0xffff_ffff_ffff) | c[0] as u64;
+                }
+            }
+            // Other commands are ignored, as they are in Spike.
+            _ => {}
+        }
+    }
+
+    // Services a proxied system call described by the block of words at
+    // the given address. Failing to access the block itself is a program
+    // error that we can't report, so it's ignored.
+    fn syscall(&mut self, block: u32) {
+        let mut args = [0u64; 8];
+        for (i, arg) in args.iter_mut().enumerate() {
+            match self.wrapped.read_longword(block.wrapping_add(i as u32 * 8)) {
+                Ok(v) => *arg = v,
+                Err(_) => return,
+            }
+        }
+        let result = match args[0] {
+            SYS_EXIT => {
+                self.halt_code = Some(args[1]);
+                Ok(0)
+            }
+            SYS_WRITE => self.sys_write(args[1] as i32, args[2] as u32, args[3]),
+            SYS_READ => self.sys_read(args[1] as i32, args[2] as u32, args[3]),
+            SYS_CLOSE => self.fs.close(args[1] as i32).map(|_| 0),
+            _ => Err(Errno::ENOSYS),
+        };
+        let v = match result {
+            Ok(v) => v,
+            Err(Errno(e)) => (-e) as u64,
+        };
+        let _ = self.wrapped.write_longword(block, v);
+    }
+
+    fn sys_write(&mut self, fd: i32, buf: u32, count: u64) -> Result<u64, Errno> {
+        let mut chunk = [0u8; CHUNK_SIZE];
+        let mut total: u64 = 0;
+        while total < count {
+            let want = core::cmp::min((count - total) as usize, CHUNK_SIZE);
+            for (i, b) in chunk[..want].iter_mut().enumerate() {
+                let addr = buf.wrapping_add(total as u32).wrapping_add(i as u32);
+                *b = self.wrapped.read_byte(addr).map_err(fault)?;
+            }
+            let n = self.fs.write(fd, &chunk[..want])?;
+            total += n as u64;
+            if n < want {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    fn sys_read(&mut self, fd: i32, buf: u32, count: u64) -> Result<u64, Errno> {
+        let mut chunk = [0u8; CHUNK_SIZE];
+        let mut total: u64 = 0;
+        while total < count {
+            let want = core::cmp::min((count - total) as usize, CHUNK_SIZE);
+            let n = self.fs.read(fd, &mut chunk[..want])?;
+            for (i, b) in chunk[..n].iter().enumerate() {
+                let addr = buf.wrapping_add(total as u32).wrapping_add(i as u32);
+                self.wrapped.write_byte(addr, *b).map_err(fault)?;
+            }
+            total += n as u64;
+            if n < want {
+                break;
+            }
+        }
+        Ok(total)
+    }
+}
+
+fn fault(_: MemoryError) -> Errno {
+    Errno::EFAULT
+}
+
+impl<Wrapped: Bus<u32>, FS: FileSystem> Bus<u32> for Htif<Wrapped, FS> {
+    fn read_byte(&mut self, addr: u32) -> Result<Byte, MemoryError> {
+        match self.read_register(addr, 1) {
+            Some(v) => Ok(v as Byte),
+            None => self.wrapped.read_byte(addr),
+        }
+    }
+
+    fn read_halfword(&mut self, addr: u32) -> Result<Halfword, MemoryError> {
+        match self.read_register(addr, 2) {
+            Some(v) => Ok(v as Halfword),
+            None => self.wrapped.read_halfword(addr),
+        }
+    }
+
+    fn read_word(&mut self, addr: u32) -> Result<Word, MemoryError> {
+        match self.read_register(addr, 4) {
+            Some(v) => Ok(v as Word),
+            None => self.wrapped.read_word(addr),
+        }
+    }
+
+    fn read_longword(&mut self, addr: u32) -> Result<Longword, MemoryError> {
+        match self.read_register(addr, 8) {
+            Some(v) => Ok(v as Longword),
+            None => self.wrapped.read_longword(addr),
+        }
+    }
+
+    fn read_quadword(&mut self, addr: u32) -> Result<Quadword, MemoryError> {
+        self.wrapped.read_quadword(addr)
+    }
+
+    fn write_byte(&mut self, addr: u32, data: Byte) -> Result<(), MemoryError> {
+        if self.write_register(addr, 1, data as u64) {
+            return Ok(());
+        }
+        self.wrapped.write_byte(addr, data)
+    }
+
+    fn write_halfword(&mut self, addr: u32, data: Halfword) -> Result<(), MemoryError> {
+        if self.write_register(addr, 2, data as u64) {
+            return Ok(());
+        }
+        self.wrapped.write_halfword(addr, data)
+    }
+
+    fn write_word(&mut self, addr: u32, data: Word) -> Result<(), MemoryError> {
+        if self.write_register(addr, 4, data as u64) {
+            return Ok(());
+        }
+        self.wrapped.write_word(addr, data)
+    }
+
+    fn write_longword(&mut self, addr: u32, data: Longword) -> Result<(), MemoryError> {
+        if self.write_register(addr, 8, data) {
+            return Ok(());
+        }
+        self.wrapped.write_longword(addr, data)
+    }
+
+    fn write_quadword(&mut self, addr: u32, data: Quadword) -> Result<(), MemoryError> {
+        self.wrapped.write_quadword(addr, data)
+    }
+
+    // The device's registers hold no instructions, so fetches always go
+    // straight to the wrapped bus rather than reading them.
+    fn fetch_halfword(&mut self, addr: u32) -> Result<Halfword, MemoryError> {
+        self.wrapped.fetch_halfword(addr)
+    }
+
+    // Nothing else can access the device's registers in the middle of an
+    // atomic operation, so one on a register is a separate read and write.
+    // Any other atomic operation must reach the wrapped bus intact.
+    fn amo_word(&mut self, addr: u32, op: AtomicOp, operand: Word) -> Result<Word, MemoryError> {
+        match self.read_register(addr, 4) {
+            Some(old) => {
+                let old = old as Word;
+                self.write_register(addr, 4, op.apply(old, operand) as u64);
+                Ok(old)
+            }
+            None => self.wrapped.amo_word(addr, op, operand),
+        }
+    }
+
+    fn compare_exchange_word(
+        &mut self,
+        addr: u32,
+        current: Word,
+        new: Word,
+    ) -> Result<Result<Word, Word>, MemoryError> {
+        match self.read_register(addr, 4) {
+            Some(old) if old as Word != current => Ok(Err(old as Word)),
+            Some(old) => {
+                self.write_register(addr, 4, new as u64);
+                Ok(Ok(old as Word))
+            }
+            None => self.wrapped.compare_exchange_word(addr, current, new),
+        }
+    }
+
+    fn fence(&mut self, pred: FenceSet, succ: FenceSet) {
+        self.wrapped.fence(pred, succ)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Htif;
+    use crate::elf::tests::{add_symbols, build_elf};
+    use crate::elf::Elf32;
+    use crate::exec::{step_rv32, ExecStatus};
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::RV32;
+    use crate::linux::tests::CaptureFileSystem;
+    use crate::memory::{AddressConverter, Bus, Memory};
+
+    #[test]
+    fn riscv_tests_protocol() {
+        // This mimics the way the riscv-tests environment reports its
+        // result, after first printing a message through the proxied
+        // write system call and a character through the console device.
+        let program: [u32; 14] = [
+            0x40000513, // li a0, 0x400 (syscall block)
+            0x30a02023, // sw a0, 0x300(x0) (tohost low)
+            0x30002223, // sw x0, 0x304(x0) (tohost high)
+            0x34002283, // lw t0, 0x340(x0) (fromhost)
+            0xfe028ee3, // beqz t0, -4
+            0x34002023, // sw x0, 0x340(x0)
+            0x02100513, // li a0, '!'
+            0x30a02023, // sw a0, 0x300(x0)
+            0x010105b7, // lui a1, 0x01010 (device 1, command 1)
+            0x30b02223, // sw a1, 0x304(x0)
+            0x00700513, // li a0, 7 (exit code 3, shifted left and with bit 0 set)
+            0x30a02023, // sw a0, 0x300(x0)
+            0x30002223, // sw x0, 0x304(x0)
+            0x0000006f, // j .
+        ];
+        let mut img = [0u8; 512];
+        let len = build_elf(&program, 0x0, 0, &mut img);
+        let len = add_symbols(&mut img, len, &[(b"tohost", 0x300), (b"fromhost", 0x340)]);
+        let elf = Elf32::parse(&img[..len]).unwrap();
+        let tohost = elf.symbol(b"tohost").unwrap();
+        let fromhost = elf.symbol(b"fromhost").unwrap();
+
+        let mut mem_buf = [0u8; 2048];
+        let mut mem: AddressConverter<u32, usize, Memory> =
+            AddressConverter::new(Memory::new_ram(&mut mem_buf));
+        elf.load(&mut mem).unwrap();
+        // write(1, 0x480, 6)
+        let block = [64u64, 1, 0x480, 6, 0, 0, 0, 0];
+        for (i, v) in block.iter().enumerate() {
+            mem.write_longword(0x400 + i as u32 * 8, *v).unwrap();
+        }
+        for (i, b) in b"hello ".iter().enumerate() {
+            mem.write_byte(0x480 + i as u32, *b).unwrap();
+        }
+
+        let fs = CaptureFileSystem::new();
+        let htif = Htif::new(mem, fs, tohost, fromhost);
+        let mut hart: SingleThreadUserHart<RV32, _> = SingleThreadUserHart::new(htif);
+        hart.write_pc(elf.entry());
+
+        let mut steps = 0;
+        let code = loop {
+            if let Some(code) = hart.with_memory(|mem| mem.halt_code()) {
+                break code;
+            }
+            match step_rv32(&mut hart) {
+                ExecStatus::Running => {}
+                status => panic!("unexpected status {:?}", status),
+            }
+            steps += 1;
+            assert!(steps < 100, "program did not halt");
+        };
+
+        assert_eq!(code, 3, "exit code");
+        hart.with_memory(|mem| {
+            assert_eq!(mem.read_longword(0x400).unwrap(), 6, "write result");
+            assert_eq!(mem.read_longword(0x300).unwrap(), 0, "tohost cleared");
+            let fs = mem.file_system();
+            assert_eq!(fs.stdout(), b"hello !");
+        });
+    }
+}
@@ -1,4 +1,8 @@
+use crate::isa::Extensions;
 use crate::raw_instruction::RawInstruction;
+use crate::register::IntRegister;
+
+mod disasm_32;
 
 mod instruction_32;
 pub use instruction_32::OperationRV32;
@@ -6,6 +10,9 @@ pub use instruction_32::OperationRV32;
 mod instruction_64;
 pub use instruction_64::OperationRV64;
 
+mod instruction_128;
+pub use instruction_128::OperationRV128;
+
 //mod instruction_64;
 //pub use instruction_64::OperationRV64;
 
@@ -13,6 +20,14 @@ pub use instruction_64::OperationRV64;
 /// decoding a `RawInstruction` value.
 pub trait Operation {
     fn decode_raw(raw: RawInstruction) -> Self;
+
+    /// Returns the set of extensions that a hart must implement for this
+    /// operation to be valid.
+    fn required_extensions(&self) -> Extensions;
+
+    /// Returns the integer registers that this operation names as its
+    /// operands or destination.
+    fn int_registers(&self) -> [Option<IntRegister>; 3];
 }
 
 /// The RV32 implementation of `Operation` supports the operating encodings
@@ -21,6 +36,14 @@ impl Operation for OperationRV32 {
     fn decode_raw(raw: RawInstruction) -> Self {
         OperationRV32::decode_from_raw(raw)
     }
+
+    fn required_extensions(&self) -> Extensions {
+        OperationRV32::required_extensions(self)
+    }
+
+    fn int_registers(&self) -> [Option<IntRegister>; 3] {
+        OperationRV32::int_registers(self)
+    }
 }
 
 /// The RV64 implementation of `Operation` supports the operating encodings
@@ -29,6 +52,30 @@ impl Operation for OperationRV64 {
     fn decode_raw(raw: RawInstruction) -> Self {
         OperationRV64::decode_from_raw(raw)
     }
+
+    fn required_extensions(&self) -> Extensions {
+        OperationRV64::required_extensions(self)
+    }
+
+    fn int_registers(&self) -> [Option<IntRegister>; 3] {
+        OperationRV64::int_registers(self)
+    }
+}
+
+/// The RV128 implementation of `Operation` supports the operating encodings
+/// from the draft RV128 base ISA and its multiply and divide extension.
+impl Operation for OperationRV128 {
+    fn decode_raw(raw: RawInstruction) -> Self {
+        OperationRV128::decode_from_raw(raw)
+    }
+
+    fn required_extensions(&self) -> Extensions {
+        OperationRV128::required_extensions(self)
+    }
+
+    fn int_registers(&self) -> [Option<IntRegister>; 3] {
+        OperationRV128::int_registers(self)
+    }
 }
 
 /// Represents a decoded instruction ready to execute.
@@ -70,12 +117,13 @@ impl<Op: Operation, Addr> Instruction<Op, Addr> {
 
 #[cfg(test)]
 mod tests {
-    use super::{Instruction, OperationRV32, OperationRV64, RawInstruction};
+    use super::{Instruction, OperationRV128, OperationRV32, OperationRV64, RawInstruction};
     use crate::register::IntRegister;
     type Inst32 = Instruction<OperationRV32, u32>;
     type Op32 = OperationRV32;
     type Inst64 = Instruction<OperationRV64, u32>;
     type Op64 = OperationRV64;
+    type Op128 = OperationRV128;
 
     #[test]
     fn instruction_rv32() {
@@ -204,4 +252,286 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn instruction_rv128() {
+        fn mkop(raw: u32) -> Op128 {
+            Op128::decode_from_raw(RawInstruction::new(raw))
+        }
+        let x = IntRegister::numbered;
+
+        assert_eq!(
+            mkop(0b0000_0000_0000_0001_1010_0011_0000_1111),
+            Op128::Lq {
+                rd: x(6),
+                rs1: x(3),
+                simm: 0,
+            }
+        );
+        assert_eq!(
+            mkop(0b0000_0000_0001_0001_1100_0100_0010_0011),
+            Op128::Sq {
+                rs1: x(3),
+                rs2: x(1),
+                simm: 8,
+            }
+        );
+        assert_eq!(
+            mkop(0b0000_0100_0000_0000_1101_0001_0001_0011),
+            Op128::Srli {
+                rd: x(2),
+                rs1: x(1),
+                shamt: 64,
+            }
+        );
+        assert_eq!(
+            mkop(0b0100_0011_1111_0001_0101_0100_0101_1011),
+            Op128::Sraid {
+                rd: x(8),
+                rs1: x(2),
+                shamt: 63,
+            }
+        );
+        assert_eq!(
+            mkop(0b0000_0010_0011_0001_0100_0000_1111_1011),
+            Op128::Divd {
+                rd: x(1),
+                rs1: x(2),
+                rs2: x(3),
+            }
+        );
+        assert_eq!(mkop(0x0001), Op128::Invalid); // c.nop
+    }
+
+    // The executor for the embedded base ISAs rejects any operation that
+    // names x16 to x31, which relies on the operation reporting its
+    // registers in every position, including in the RV64-only operations.
+    #[test]
+    fn embedded_registers() {
+        for r in 16..32 {
+            let raws = [
+                (2 << 20) | (1 << 15) | (r << 7) | 0x3b,  // addw xr, x1, x2
+                (1 << 20) | (r << 15) | (1 << 7) | 0x3b,  // addw x1, xr, x1
+                (r << 15) | (3 << 12) | (1 << 7) | 0x03,  // ld x1, 0(xr)
+                (r << 20) | (1 << 15) | (3 << 12) | 0x23, // sd xr, 0(x1)
+                0x6002 | (r << 7),                        // c.ldsp xr, 0(sp)
+                0xe002 | (r << 2),                        // c.sdsp xr, 0(sp)
+            ];
+            for &raw in raws.iter() {
+                let op = Inst64::decode_raw(RawInstruction::new(raw), 0).op;
+                assert!(
+                    op.int_registers()
+                        .iter()
+                        .flatten()
+                        .any(|reg| reg.num() == r as usize),
+                    "{:?} doesn't report x{}",
+                    op,
+                    r
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bit_manipulation() {
+        use crate::isa::Extensions;
+        let x = IntRegister::numbered;
+        let op32 = |raw| Op32::decode_from_raw(RawInstruction::new(raw));
+        let op64 = |raw| Op64::decode_from_raw(RawInstruction::new(raw));
+
+        assert_eq!(
+            op32(0b0010_0000_0011_0001_0100_0000_1011_0011),
+            Op32::Sh2add {
+                rd: x(1),
+                rs1: x(2),
+                rs2: x(3),
+            }
+        );
+        assert_eq!(
+            op32(0b0110_0000_0000_0001_0001_0000_1001_0011),
+            Op32::Clz {
+                rd: x(1),
+                rs1: x(2)
+            }
+        );
+        assert_eq!(
+            op32(0b0110_1001_1000_0001_0101_0000_1001_0011),
+            Op32::Rev8 {
+                rd: x(1),
+                rs1: x(2)
+            }
+        );
+        assert_eq!(
+            op32(0b0010_1001_1111_0001_0001_0000_1001_0011),
+            Op32::Bseti {
+                rd: x(1),
+                rs1: x(2),
+                shamt: 31,
+            }
+        );
+        assert_eq!(
+            op32(0b0000_1000_0000_0001_0100_0000_1011_0011),
+            Op32::ZextH {
+                rd: x(1),
+                rs1: x(2)
+            }
+        );
+        assert_eq!(
+            op32(0b0000_1010_0011_0001_0011_0000_1011_0011).required_extensions(),
+            Extensions::ZBC
+        );
+        // The 64-bit forms of rev8 and the shift-immediates are reserved.
+        assert_eq!(
+            op32(0b0110_1011_1000_0001_0101_0000_1001_0011),
+            Op32::Invalid
+        );
+        assert_eq!(
+            op32(0b0010_1011_1111_0001_0001_0000_1001_0011),
+            Op32::Invalid
+        );
+
+        assert_eq!(
+            op64(0b0110_1011_1000_0001_0101_0000_1001_0011),
+            Op64::Rev8 {
+                rd: x(1),
+                rs1: x(2)
+            }
+        );
+        assert_eq!(
+            op64(0b0010_1011_1111_0001_0001_0000_1001_0011),
+            Op64::Bseti {
+                rd: x(1),
+                rs1: x(2),
+                shamt: 63,
+            }
+        );
+        assert_eq!(
+            op64(0b0000_1000_0011_0001_0001_0000_1001_1011),
+            Op64::SlliUw {
+                rd: x(1),
+                rs1: x(2),
+                shamt: 3,
+            }
+        );
+        assert_eq!(
+            op64(0b0000_1000_0011_0001_0000_0000_1011_1011),
+            Op64::AddUw {
+                rd: x(1),
+                rs1: x(2),
+                rs2: x(3),
+            }
+        );
+        assert_eq!(
+            op64(0b0000_1000_0000_0001_0100_0000_1011_1011),
+            Op64::ZextH {
+                rd: x(1),
+                rs1: x(2)
+            }
+        );
+        assert_eq!(
+            op64(0b0110_0000_0010_0001_0001_0000_1001_1011),
+            Op64::Cpopw {
+                rd: x(1),
+                rs1: x(2)
+            }
+        );
+    }
+
+    #[test]
+    fn vector() {
+        use crate::isa::Extensions;
+        use crate::register::{FloatRegister, VectorRegister};
+        let x = IntRegister::numbered;
+        let v = VectorRegister::numbered;
+        let op32 = |raw| Op32::decode_from_raw(RawInstruction::new(raw));
+
+        assert_eq!(
+            op32(0b0000_1101_0000_0001_0111_0000_1101_0111),
+            Op32::Vsetvli {
+                rd: x(1),
+                rs1: x(2),
+                vtypei: 0b00011010000,
+            }
+        );
+        assert_eq!(
+            op32(0b1100_1101_0000_0010_0111_0000_1101_0111),
+            Op32::Vsetivli {
+                rd: x(1),
+                uimm: 4,
+                vtypei: 0b0011010000,
+            }
+        );
+        assert_eq!(
+            op32(0b0000_1010_0011_0001_0110_0000_1000_0111),
+            Op32::Vlse32V {
+                vd: v(1),
+                rs1: x(2),
+                rs2: x(3),
+                vm: true,
+            }
+        );
+        assert_eq!(
+            op32(0b0000_0100_0011_0001_0000_0000_1010_0111),
+            Op32::Vsuxei8V {
+                vs3: v(1),
+                rs1: x(2),
+                vs2: v(3),
+                vm: false,
+            }
+        );
+        assert_eq!(
+            op32(0b0000_0010_0011_1110_1011_0000_1101_0111),
+            Op32::VaddVi {
+                vd: v(1),
+                vs2: v(3),
+                simm: -3,
+                vm: true,
+            }
+        );
+        assert_eq!(
+            op32(0b0101_1100_0011_0001_0100_0000_1101_0111),
+            Op32::VmergeVxm {
+                vd: v(1),
+                vs2: v(3),
+                rs1: x(2),
+            }
+        );
+        assert_eq!(
+            op32(0b0101_1110_0000_0001_0100_0000_1101_0111),
+            Op32::VmvVX {
+                vd: v(1),
+                rs1: x(2),
+            }
+        );
+        assert_eq!(
+            op32(0b0100_0010_0011_1000_1010_0000_1101_0111),
+            Op32::VfirstM {
+                rd: x(1),
+                vs2: v(3),
+                vm: true,
+            }
+        );
+        assert_eq!(
+            op32(0b0000_0010_0011_0001_0101_0000_1101_0111),
+            Op32::VfaddVf {
+                vd: v(1),
+                vs2: v(3),
+                frs1: FloatRegister::numbered(2),
+                vm: true,
+            }
+        );
+        assert_eq!(
+            op32(0b0000_0010_0011_0001_0101_0000_1101_0111).required_extensions(),
+            Extensions::V
+        );
+        // Whole-register and segment loads aren't supported.
+        assert_eq!(
+            op32(0b0000_0010_1000_0001_0110_0000_1000_0111),
+            Op32::Invalid
+        );
+        assert_eq!(
+            op32(0b0010_0010_0000_0001_0110_0000_1000_0111),
+            Op32::Invalid
+        );
+    }
 }
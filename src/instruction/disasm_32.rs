@@ -0,0 +1,1098 @@
+use crate::instruction::OperationRV32;
+use crate::register::{FloatRegister, IntRegister, VectorRegister};
+use core::fmt;
+
+type Op = OperationRV32;
+
+/// Formats the operation as assembly language, using the same mnemonics,
+/// operand syntax and pseudo-instructions as Spike's disassembler so that
+/// traces from the two can be compared directly.
+///
+/// Because an operation does not know its own address, branch and jump
+/// targets are written relative to the program counter, as in `pc + 8`.
+impl fmt::Display for OperationRV32 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let zero = IntRegister::zero();
+        let ra = IntRegister::numbered(1);
+        let sp = IntRegister::numbered(2);
+        match *self {
+            Op::Invalid => f.write_str("unknown"),
+
+            Op::Addi { rd, rs1, simm } if rd == zero && rs1 == zero && simm == 0 => {
+                f.write_str("nop")
+            }
+            Op::Addi { rd, rs1, simm } if rs1 == zero => {
+                write!(f, "{}{}, {}", Mnemonic("li"), rd, simm)
+            }
+            Op::Addi { rd, rs1, simm: 0 } => {
+                write!(f, "{}{}, {}", Mnemonic("mv"), rd, rs1)
+            }
+            Op::Xori { rd, rs1, simm: -1 } => {
+                write!(f, "{}{}, {}", Mnemonic("not"), rd, rs1)
+            }
+            Op::Sltiu { rd, rs1, simm: 1 } => {
+                write!(f, "{}{}, {}", Mnemonic("seqz"), rd, rs1)
+            }
+            Op::Sub { rd, rs1, rs2 } if rs1 == zero => {
+                write!(f, "{}{}, {}", Mnemonic("neg"), rd, rs2)
+            }
+            Op::Sltu { rd, rs1, rs2 } if rs1 == zero => {
+                write!(f, "{}{}, {}", Mnemonic("snez"), rd, rs2)
+            }
+            Op::Addi { rd, rs1, simm } => reg_reg_imm(f, "addi", rd, rs1, simm),
+            Op::Andi { rd, rs1, simm } => reg_reg_imm(f, "andi", rd, rs1, simm),
+            Op::Ori { rd, rs1, simm } => reg_reg_imm(f, "ori", rd, rs1, simm),
+            Op::Xori { rd, rs1, simm } => reg_reg_imm(f, "xori", rd, rs1, simm),
+            Op::Slti { rd, rs1, simm } => reg_reg_imm(f, "slti", rd, rs1, simm),
+            Op::Sltiu { rd, rs1, simm } => reg_reg_imm(f, "sltiu", rd, rs1, simm),
+            Op::Slli { rd, rs1, shamt } => reg_reg_imm(f, "slli", rd, rs1, shamt),
+            Op::Srli { rd, rs1, shamt } => reg_reg_imm(f, "srli", rd, rs1, shamt),
+            Op::Srai { rd, rs1, shamt } => reg_reg_imm(f, "srai", rd, rs1, shamt),
+
+            Op::Add { rd, rs1, rs2 } => reg_reg_reg(f, "add", rd, rs1, rs2),
+            Op::And { rd, rs1, rs2 } => reg_reg_reg(f, "and", rd, rs1, rs2),
+            Op::Or { rd, rs1, rs2 } => reg_reg_reg(f, "or", rd, rs1, rs2),
+            Op::Xor { rd, rs1, rs2 } => reg_reg_reg(f, "xor", rd, rs1, rs2),
+            Op::Sll { rd, rs1, rs2 } => reg_reg_reg(f, "sll", rd, rs1, rs2),
+            Op::Srl { rd, rs1, rs2 } => reg_reg_reg(f, "srl", rd, rs1, rs2),
+            Op::Sra { rd, rs1, rs2 } => reg_reg_reg(f, "sra", rd, rs1, rs2),
+            Op::Slt { rd, rs1, rs2 } => reg_reg_reg(f, "slt", rd, rs1, rs2),
+            Op::Sltu { rd, rs1, rs2 } => reg_reg_reg(f, "sltu", rd, rs1, rs2),
+            Op::Sub { rd, rs1, rs2 } => reg_reg_reg(f, "sub", rd, rs1, rs2),
+            Op::Mul { rd, rs1, rs2 } => reg_reg_reg(f, "mul", rd, rs1, rs2),
+            Op::Mulh { rd, rs1, rs2 } => reg_reg_reg(f, "mulh", rd, rs1, rs2),
+            Op::Mulhsu { rd, rs1, rs2 } => reg_reg_reg(f, "mulhsu", rd, rs1, rs2),
+            Op::Mulhu { rd, rs1, rs2 } => reg_reg_reg(f, "mulhu", rd, rs1, rs2),
+            Op::Div { rd, rs1, rs2 } => reg_reg_reg(f, "div", rd, rs1, rs2),
+            Op::Divu { rd, rs1, rs2 } => reg_reg_reg(f, "divu", rd, rs1, rs2),
+            Op::Rem { rd, rs1, rs2 } => reg_reg_reg(f, "rem", rd, rs1, rs2),
+            Op::Remu { rd, rs1, rs2 } => reg_reg_reg(f, "remu", rd, rs1, rs2),
+
+            Op::Lui { rd, simm } => write!(f, "{}{}, {}", Mnemonic("lui"), rd, UpperImm(simm)),
+            Op::Auipc { rd, simm } => {
+                write!(f, "{}{}, {}", Mnemonic("auipc"), rd, UpperImm(simm))
+            }
+
+            Op::Jal { rd, simm } if rd == zero => write!(f, "{}{}", Mnemonic("j"), Target(simm)),
+            Op::Jal { rd, simm } if rd == ra => write!(f, "{}{}", Mnemonic("jal"), Target(simm)),
+            Op::Jal { rd, simm } => write!(f, "{}{}, {}", Mnemonic("jal"), rd, Target(simm)),
+            Op::Jalr { rd, rs1, simm } if rd == zero && rs1 == ra && simm == 0 => {
+                f.write_str("ret")
+            }
+            Op::Jalr { rd, rs1, simm } if rd == zero && simm == 0 => {
+                write!(f, "{}{}", Mnemonic("jr"), rs1)
+            }
+            Op::Jalr { rd, rs1, simm } if rd == ra && simm == 0 => {
+                write!(f, "{}{}", Mnemonic("jalr"), rs1)
+            }
+            Op::Jalr { rd, rs1, simm } => {
+                write!(f, "{}{}, {}({})", Mnemonic("jalr"), rd, simm, rs1)
+            }
+
+            Op::Beq { rs1, rs2, simm } if rs2 == zero => branch_zero(f, "beqz", rs1, simm),
+            Op::Bne { rs1, rs2, simm } if rs2 == zero => branch_zero(f, "bnez", rs1, simm),
+            Op::Blt { rs1, rs2, simm } if rs2 == zero => branch_zero(f, "bltz", rs1, simm),
+            Op::Bge { rs1, rs2, simm } if rs2 == zero => branch_zero(f, "bgez", rs1, simm),
+            Op::Blt { rs1, rs2, simm } if rs1 == zero => branch_zero(f, "bgtz", rs2, simm),
+            Op::Bge { rs1, rs2, simm } if rs1 == zero => branch_zero(f, "blez", rs2, simm),
+            Op::Beq { rs1, rs2, simm } => branch(f, "beq", rs1, rs2, simm),
+            Op::Bne { rs1, rs2, simm } => branch(f, "bne", rs1, rs2, simm),
+            Op::Blt { rs1, rs2, simm } => branch(f, "blt", rs1, rs2, simm),
+            Op::Bge { rs1, rs2, simm } => branch(f, "bge", rs1, rs2, simm),
+            Op::Bltu { rs1, rs2, simm } => branch(f, "bltu", rs1, rs2, simm),
+            Op::Bgeu { rs1, rs2, simm } => branch(f, "bgeu", rs1, rs2, simm),
+
+            Op::Lb { rd, rs1, simm } => load_store(f, "lb", rd, rs1, simm),
+            Op::Lbu { rd, rs1, simm } => load_store(f, "lbu", rd, rs1, simm),
+            Op::Lh { rd, rs1, simm } => load_store(f, "lh", rd, rs1, simm),
+            Op::Lhu { rd, rs1, simm } => load_store(f, "lhu", rd, rs1, simm),
+            Op::Lw { rd, rs1, simm } => load_store(f, "lw", rd, rs1, simm),
+            Op::Sb { rs1, rs2, simm } => load_store(f, "sb", rs2, rs1, simm),
+            Op::Sh { rs1, rs2, simm } => load_store(f, "sh", rs2, rs1, simm),
+            Op::Sw { rs1, rs2, simm } => load_store(f, "sw", rs2, rs1, simm),
+            Op::Flw { frd, rs1, simm } => load_store(f, "flw", frd, rs1, simm),
+            Op::Fld { frd, rs1, simm } => load_store(f, "fld", frd, rs1, simm),
+            Op::Flq { frd, rs1, simm } => load_store(f, "flq", frd, rs1, simm),
+            Op::Fsw { rs1, frs2, simm } => load_store(f, "fsw", frs2, rs1, simm),
+            Op::Fsd { rs1, frs2, simm } => load_store(f, "fsd", frs2, rs1, simm),
+            Op::Fsq { rs1, frs2, simm } => load_store(f, "fsq", frs2, rs1, simm),
+
+            Op::Fence { .. } => f.write_str("fence"),
+            Op::FenceI => f.write_str("fence.i"),
+            Op::Ecall => f.write_str("ecall"),
+            Op::Ebreak => f.write_str("ebreak"),
+            Op::Uret => f.write_str("uret"),
+            Op::Sret => f.write_str("sret"),
+            Op::Hret => f.write_str("hret"),
+            Op::Mret => f.write_str("mret"),
+            Op::Dret => f.write_str("dret"),
+            Op::Wfi => f.write_str("wfi"),
+            Op::SfenceVm { rs1 } => write!(f, "{}{}", Mnemonic("sfence.vm"), rs1),
+            Op::SfenceVma { rs1, rs2 } => {
+                write!(f, "{}{}, {}", Mnemonic("sfence.vma"), rs1, rs2)
+            }
+
+            Op::LrW { rd, rs1, aq, rl } => {
+                write!(f, "{}{}, ({})", Mnemonic(Ordered("lr.w", aq, rl)), rd, rs1)
+            }
+            Op::ScW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => amo(f, "sc.w", rd, rs1, rs2, aq, rl),
+            Op::AmoaddW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => amo(f, "amoadd.w", rd, rs1, rs2, aq, rl),
+            Op::AmoandW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => amo(f, "amoand.w", rd, rs1, rs2, aq, rl),
+            Op::AmomaxW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => amo(f, "amomax.w", rd, rs1, rs2, aq, rl),
+            Op::AmomaxuW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => amo(f, "amomaxu.w", rd, rs1, rs2, aq, rl),
+            Op::AmominW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => amo(f, "amomin.w", rd, rs1, rs2, aq, rl),
+            Op::AmominuW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => amo(f, "amominu.w", rd, rs1, rs2, aq, rl),
+            Op::AmoorW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => amo(f, "amoor.w", rd, rs1, rs2, aq, rl),
+            Op::AmoswapW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => amo(f, "amoswap.w", rd, rs1, rs2, aq, rl),
+            Op::AmoxorW {
+                rd,
+                rs1,
+                rs2,
+                aq,
+                rl,
+            } => amo(f, "amoxor.w", rd, rs1, rs2, aq, rl),
+
+            Op::Csrrs { rd, rs1, csr } if rs1 == zero => {
+                write!(f, "{}{}, {}", Mnemonic("csrr"), rd, Csr(csr))
+            }
+            Op::Csrrw { rd, rs1, csr } if rd == zero => {
+                write!(f, "{}{}, {}", Mnemonic("csrw"), Csr(csr), rs1)
+            }
+            Op::Csrrs { rd, rs1, csr } if rd == zero => {
+                write!(f, "{}{}, {}", Mnemonic("csrs"), Csr(csr), rs1)
+            }
+            Op::Csrrc { rd, rs1, csr } if rd == zero => {
+                write!(f, "{}{}, {}", Mnemonic("csrc"), Csr(csr), rs1)
+            }
+            Op::Csrrwi { rd, uimm, csr } if rd == zero => {
+                write!(f, "{}{}, {}", Mnemonic("csrwi"), Csr(csr), uimm)
+            }
+            Op::Csrrsi { rd, uimm, csr } if rd == zero => {
+                write!(f, "{}{}, {}", Mnemonic("csrsi"), Csr(csr), uimm)
+            }
+            Op::Csrrci { rd, uimm, csr } if rd == zero => {
+                write!(f, "{}{}, {}", Mnemonic("csrci"), Csr(csr), uimm)
+            }
+            Op::Csrrw { rd, rs1, csr } => csr_op(f, "csrrw", rd, csr, rs1),
+            Op::Csrrs { rd, rs1, csr } => csr_op(f, "csrrs", rd, csr, rs1),
+            Op::Csrrc { rd, rs1, csr } => csr_op(f, "csrrc", rd, csr, rs1),
+            Op::Csrrwi { rd, uimm, csr } => csr_op(f, "csrrwi", rd, csr, uimm),
+            Op::Csrrsi { rd, uimm, csr } => csr_op(f, "csrrsi", rd, csr, uimm),
+            Op::Csrrci { rd, uimm, csr } => csr_op(f, "csrrci", rd, csr, uimm),
+
+            Op::FsgnjS { frd, frs1, frs2 } if frs1 == frs2 => float_move(f, "fmv.s", frd, frs1),
+            Op::FsgnjnS { frd, frs1, frs2 } if frs1 == frs2 => float_move(f, "fneg.s", frd, frs1),
+            Op::FsgnjxS { frd, frs1, frs2 } if frs1 == frs2 => float_move(f, "fabs.s", frd, frs1),
+            Op::FsgnjD { frd, frs1, frs2 } if frs1 == frs2 => float_move(f, "fmv.d", frd, frs1),
+            Op::FsgnjnD { frd, frs1, frs2 } if frs1 == frs2 => float_move(f, "fneg.d", frd, frs1),
+            Op::FsgnjxD { frd, frs1, frs2 } if frs1 == frs2 => float_move(f, "fabs.d", frd, frs1),
+
+            Op::FaddS {
+                frd, frs1, frs2, ..
+            } => reg_reg_reg(f, "fadd.s", frd, frs1, frs2),
+            Op::FsubS {
+                frd, frs1, frs2, ..
+            } => reg_reg_reg(f, "fsub.s", frd, frs1, frs2),
+            Op::FmulS {
+                frd, frs1, frs2, ..
+            } => reg_reg_reg(f, "fmul.s", frd, frs1, frs2),
+            Op::FdivS {
+                frd, frs1, frs2, ..
+            } => reg_reg_reg(f, "fdiv.s", frd, frs1, frs2),
+            Op::FminS { frd, frs1, frs2 } => reg_reg_reg(f, "fmin.s", frd, frs1, frs2),
+            Op::FmaxS { frd, frs1, frs2 } => reg_reg_reg(f, "fmax.s", frd, frs1, frs2),
+            Op::FsgnjS { frd, frs1, frs2 } => reg_reg_reg(f, "fsgnj.s", frd, frs1, frs2),
+            Op::FsgnjnS { frd, frs1, frs2 } => reg_reg_reg(f, "fsgnjn.s", frd, frs1, frs2),
+            Op::FsgnjxS { frd, frs1, frs2 } => reg_reg_reg(f, "fsgnjx.s", frd, frs1, frs2),
+            Op::FeqS { rd, frs1, frs2 } => reg_reg_reg(f, "feq.s", rd, frs1, frs2),
+            Op::FltS { rd, frs1, frs2 } => reg_reg_reg(f, "flt.s", rd, frs1, frs2),
+            Op::FleS { rd, frs1, frs2 } => reg_reg_reg(f, "fle.s", rd, frs1, frs2),
+            Op::FsqrtS { frd, frs1, .. } => float_move(f, "fsqrt.s", frd, frs1),
+            Op::FclassS { rd, frs1 } => float_move(f, "fclass.s", rd, frs1),
+            Op::FcvtSW { frd, rs1, .. } => float_move(f, "fcvt.s.w", frd, rs1),
+            Op::FcvtSWu { frd, rs1, .. } => float_move(f, "fcvt.s.wu", frd, rs1),
+            Op::FcvtWS { rd, frs1, .. } => float_move(f, "fcvt.w.s", rd, frs1),
+            Op::FcvtWuS { rd, frs1, .. } => float_move(f, "fcvt.wu.s", rd, frs1),
+            Op::FmvSX { frd, rs1 } => float_move(f, "fmv.w.x", frd, rs1),
+            Op::FmvXS { rd, frs1 } => float_move(f, "fmv.x.w", rd, frs1),
+            Op::FmaddS {
+                frd,
+                frs1,
+                frs2,
+                frs3,
+                ..
+            } => fused(f, "fmadd.s", frd, frs1, frs2, frs3),
+            Op::FmsubS {
+                frd,
+                frs1,
+                frs2,
+                frs3,
+                ..
+            } => fused(f, "fmsub.s", frd, frs1, frs2, frs3),
+            Op::FnmaddS {
+                frd,
+                frs1,
+                frs2,
+                frs3,
+                ..
+            } => fused(f, "fnmadd.s", frd, frs1, frs2, frs3),
+            Op::FnmsubS {
+                frd,
+                frs1,
+                frs2,
+                frs3,
+                ..
+            } => fused(f, "fnmsub.s", frd, frs1, frs2, frs3),
+
+            Op::FaddD {
+                frd, frs1, frs2, ..
+            } => reg_reg_reg(f, "fadd.d", frd, frs1, frs2),
+            Op::FsubD {
+                frd, frs1, frs2, ..
+            } => reg_reg_reg(f, "fsub.d", frd, frs1, frs2),
+            Op::FmulD {
+                frd, frs1, frs2, ..
+            } => reg_reg_reg(f, "fmul.d", frd, frs1, frs2),
+            Op::FdivD {
+                frd, frs1, frs2, ..
+            } => reg_reg_reg(f, "fdiv.d", frd, frs1, frs2),
+            Op::FminD { frd, frs1, frs2 } => reg_reg_reg(f, "fmin.d", frd, frs1, frs2),
+            Op::FmaxD { frd, frs1, frs2 } => reg_reg_reg(f, "fmax.d", frd, frs1, frs2),
+            Op::FsgnjD { frd, frs1, frs2 } => reg_reg_reg(f, "fsgnj.d", frd, frs1, frs2),
+            Op::FsgnjnD { frd, frs1, frs2 } => reg_reg_reg(f, "fsgnjn.d", frd, frs1, frs2),
+            Op::FsgnjxD { frd, frs1, frs2 } => reg_reg_reg(f, "fsgnjx.d", frd, frs1, frs2),
+            Op::FeqD { rd, frs1, frs2 } => reg_reg_reg(f, "feq.d", rd, frs1, frs2),
+            Op::FltD { rd, frs1, frs2 } => reg_reg_reg(f, "flt.d", rd, frs1, frs2),
+            Op::FleD { rd, frs1, frs2 } => reg_reg_reg(f, "fle.d", rd, frs1, frs2),
+            Op::FsqrtD { frd, frs1, .. } => float_move(f, "fsqrt.d", frd, frs1),
+            Op::FclassD { rd, frs1 } => float_move(f, "fclass.d", rd, frs1),
+            Op::FcvtDS { frd, frs1, .. } => float_move(f, "fcvt.d.s", frd, frs1),
+            Op::FcvtSD { frd, frs1, .. } => float_move(f, "fcvt.s.d", frd, frs1),
+            Op::FcvtDW { frd, rs1, .. } => float_move(f, "fcvt.d.w", frd, rs1),
+            Op::FcvtDWu { frd, rs1, .. } => float_move(f, "fcvt.d.wu", frd, rs1),
+            Op::FcvtWD { rd, frs1, .. } => float_move(f, "fcvt.w.d", rd, frs1),
+            Op::FcvtWuD { rd, frs1, .. } => float_move(f, "fcvt.wu.d", rd, frs1),
+            Op::FmaddD {
+                frd,
+                frs1,
+                frs2,
+                frs3,
+                ..
+            } => fused(f, "fmadd.d", frd, frs1, frs2, frs3),
+            Op::FmsubD {
+                frd,
+                frs1,
+                frs2,
+                frs3,
+                ..
+            } => fused(f, "fmsub.d", frd, frs1, frs2, frs3),
+            Op::FnmaddD {
+                frd,
+                frs1,
+                frs2,
+                frs3,
+                ..
+            } => fused(f, "fnmadd.d", frd, frs1, frs2, frs3),
+            Op::FnmsubD {
+                frd,
+                frs1,
+                frs2,
+                frs3,
+                ..
+            } => fused(f, "fnmsub.d", frd, frs1, frs2, frs3),
+
+            Op::FaddQ {
+                frd, frs1, frs2, ..
+            } => reg_reg_reg(f, "fadd.q", frd, frs1, frs2),
+            Op::FsubQ {
+                frd, frs1, frs2, ..
+            } => reg_reg_reg(f, "fsub.q", frd, frs1, frs2),
+            Op::FmulQ {
+                frd, frs1, frs2, ..
+            } => reg_reg_reg(f, "fmul.q", frd, frs1, frs2),
+            Op::FdivQ {
+                frd, frs1, frs2, ..
+            } => reg_reg_reg(f, "fdiv.q", frd, frs1, frs2),
+            Op::FminQ { frd, frs1, frs2 } => reg_reg_reg(f, "fmin.q", frd, frs1, frs2),
+            Op::FmaxQ { frd, frs1, frs2 } => reg_reg_reg(f, "fmax.q", frd, frs1, frs2),
+            Op::FsgnjQ { frd, frs1, frs2 } => reg_reg_reg(f, "fsgnj.q", frd, frs1, frs2),
+            Op::FsgnjnQ { frd, frs1, frs2 } => reg_reg_reg(f, "fsgnjn.q", frd, frs1, frs2),
+            Op::FsgnjxQ { frd, frs1, frs2 } => reg_reg_reg(f, "fsgnjx.q", frd, frs1, frs2),
+            Op::FeqQ { rd, frs1, frs2 } => reg_reg_reg(f, "feq.q", rd, frs1, frs2),
+            Op::FltQ { rd, frs1, frs2 } => reg_reg_reg(f, "flt.q", rd, frs1, frs2),
+            Op::FleQ { rd, frs1, frs2 } => reg_reg_reg(f, "fle.q", rd, frs1, frs2),
+            Op::FsqrtQ { frd, frs1, .. } => float_move(f, "fsqrt.q", frd, frs1),
+            Op::FclassQ { rd, frs1 } => float_move(f, "fclass.q", rd, frs1),
+            Op::FcvtDQ { frd, frs1, .. } => float_move(f, "fcvt.d.q", frd, frs1),
+            Op::FcvtQD { frd, frs1, .. } => float_move(f, "fcvt.q.d", frd, frs1),
+            Op::FcvtQS { frd, frs1, .. } => float_move(f, "fcvt.q.s", frd, frs1),
+            Op::FcvtSQ { frd, frs1, .. } => float_move(f, "fcvt.s.q", frd, frs1),
+            Op::FcvtQW { frd, rs1, .. } => float_move(f, "fcvt.q.w", frd, rs1),
+            Op::FcvtQWu { frd, rs1, .. } => float_move(f, "fcvt.q.wu", frd, rs1),
+            Op::FcvtWQ { rd, frs1, .. } => float_move(f, "fcvt.w.q", rd, frs1),
+            Op::FcvtWuQ { rd, frs1, .. } => float_move(f, "fcvt.wu.q", rd, frs1),
+            Op::FmaddQ {
+                frd,
+                frs1,
+                frs2,
+                frs3,
+                ..
+            } => fused(f, "fmadd.q", frd, frs1, frs2, frs3),
+            Op::FmsubQ {
+                frd,
+                frs1,
+                frs2,
+                frs3,
+                ..
+            } => fused(f, "fmsub.q", frd, frs1, frs2, frs3),
+            Op::FnmaddQ {
+                frd,
+                frs1,
+                frs2,
+                frs3,
+                ..
+            } => fused(f, "fnmadd.q", frd, frs1, frs2, frs3),
+            Op::FnmsubQ {
+                frd,
+                frs1,
+                frs2,
+                frs3,
+                ..
+            } => fused(f, "fnmsub.q", frd, frs1, frs2, frs3),
+
+            Op::CNop => f.write_str("c.nop"),
+            Op::CEbreak => f.write_str("c.ebreak"),
+            Op::CAddi { rs1rd, nzsimm } => reg_imm(f, "c.addi", rs1rd, nzsimm),
+            Op::CAddi16Sp { rs1rd, nzsimm } => reg_imm(f, "c.addi16sp", rs1rd, nzsimm),
+            Op::CAddi4Spn { rd, nzuimm } => reg_reg_imm(f, "c.addi4spn", rd, sp, nzuimm),
+            Op::CAndi { rs1rd, nzsimm } => reg_imm(f, "c.andi", rs1rd, nzsimm),
+            Op::CLi { rs1rd, simm } => reg_imm(f, "c.li", rs1rd, simm),
+            Op::CLui { rd, nzsimm } => {
+                write!(f, "{}{}, {}", Mnemonic("c.lui"), rd, UpperImm(nzsimm))
+            }
+            Op::CSlli { rs1rd, nzuimm } => reg_imm(f, "c.slli", rs1rd, nzuimm),
+            Op::CSrli { rs1rd, nzuimm } => reg_imm(f, "c.srli", rs1rd, nzuimm),
+            Op::CSrai { rs1rd, nzuimm } => reg_imm(f, "c.srai", rs1rd, nzuimm),
+            Op::CMv { rd, rs2 } => write!(f, "{}{}, {}", Mnemonic("c.mv"), rd, rs2),
+            Op::CAdd { rs1rd, rs2 } => write!(f, "{}{}, {}", Mnemonic("c.add"), rs1rd, rs2),
+            Op::CAddw { rs1rd, rs2 } => write!(f, "{}{}, {}", Mnemonic("c.addw"), rs1rd, rs2),
+            Op::CAnd { rs1rd, rs2 } => write!(f, "{}{}, {}", Mnemonic("c.and"), rs1rd, rs2),
+            Op::COr { rs1rd, rs2 } => write!(f, "{}{}, {}", Mnemonic("c.or"), rs1rd, rs2),
+            Op::CXor { rs1rd, rs2 } => write!(f, "{}{}, {}", Mnemonic("c.xor"), rs1rd, rs2),
+            Op::CSub { rs1rd, rs2 } => write!(f, "{}{}, {}", Mnemonic("c.sub"), rs1rd, rs2),
+            Op::CSubw { rs1rd, rs2 } => write!(f, "{}{}, {}", Mnemonic("c.subw"), rs1rd, rs2),
+            Op::CJ { simm } => write!(f, "{}{}", Mnemonic("c.j"), Target(simm)),
+            Op::CJal { simm } => write!(f, "{}{}", Mnemonic("c.jal"), Target(simm)),
+            Op::CJr { rs1, .. } => write!(f, "{}{}", Mnemonic("c.jr"), rs1),
+            Op::CJalr { rs1, .. } => write!(f, "{}{}", Mnemonic("c.jalr"), rs1),
+            Op::CBeqz { rs1, simm } => branch_zero(f, "c.beqz", rs1, simm),
+            Op::CBnez { rs1, simm } => branch_zero(f, "c.bnez", rs1, simm),
+            Op::CLw { rd, rs1, uimm } => load_store(f, "c.lw", rd, rs1, uimm),
+            Op::CSw { rs1, rs2, uimm } => load_store(f, "c.sw", rs2, rs1, uimm),
+            Op::CLwsp { rd, uimm } => load_store(f, "c.lwsp", rd, sp, uimm),
+            Op::CSwsp { rs2, uimm } => load_store(f, "c.swsp", rs2, sp, uimm),
+            Op::CFlw { frd, rs1, uimm } => load_store(f, "c.flw", compressed(frd), rs1, uimm),
+            Op::CFld { frd, rs1, uimm } => load_store(f, "c.fld", compressed(frd), rs1, uimm),
+            Op::CFsw { rs1, frs2, uimm } => load_store(f, "c.fsw", compressed(frs2), rs1, uimm),
+            Op::CFsd { rs1, frs2, uimm } => load_store(f, "c.fsd", compressed(frs2), rs1, uimm),
+            Op::CFlwsp { frd, uimm } => load_store(f, "c.flwsp", frd, sp, uimm),
+            Op::CFldsp { frd, uimm } => load_store(f, "c.fldsp", frd, sp, uimm),
+            Op::CFswsp { frs2, uimm } => load_store(f, "c.fswsp", frs2, sp, uimm),
+            Op::CFsdsp { frs2, uimm } => load_store(f, "c.fsdsp", frs2, sp, uimm),
+
+            Op::Sh1add { rd, rs1, rs2 } => reg_reg_reg(f, "sh1add", rd, rs1, rs2),
+            Op::Sh2add { rd, rs1, rs2 } => reg_reg_reg(f, "sh2add", rd, rs1, rs2),
+            Op::Sh3add { rd, rs1, rs2 } => reg_reg_reg(f, "sh3add", rd, rs1, rs2),
+
+            Op::Andn { rd, rs1, rs2 } => reg_reg_reg(f, "andn", rd, rs1, rs2),
+            Op::Orn { rd, rs1, rs2 } => reg_reg_reg(f, "orn", rd, rs1, rs2),
+            Op::Xnor { rd, rs1, rs2 } => reg_reg_reg(f, "xnor", rd, rs1, rs2),
+            Op::Max { rd, rs1, rs2 } => reg_reg_reg(f, "max", rd, rs1, rs2),
+            Op::Maxu { rd, rs1, rs2 } => reg_reg_reg(f, "maxu", rd, rs1, rs2),
+            Op::Min { rd, rs1, rs2 } => reg_reg_reg(f, "min", rd, rs1, rs2),
+            Op::Minu { rd, rs1, rs2 } => reg_reg_reg(f, "minu", rd, rs1, rs2),
+            Op::Rol { rd, rs1, rs2 } => reg_reg_reg(f, "rol", rd, rs1, rs2),
+            Op::Ror { rd, rs1, rs2 } => reg_reg_reg(f, "ror", rd, rs1, rs2),
+            Op::Clz { rd, rs1 } => write!(f, "{}{}, {}", Mnemonic("clz"), rd, rs1),
+            Op::Ctz { rd, rs1 } => write!(f, "{}{}, {}", Mnemonic("ctz"), rd, rs1),
+            Op::Cpop { rd, rs1 } => write!(f, "{}{}, {}", Mnemonic("cpop"), rd, rs1),
+            Op::SextB { rd, rs1 } => write!(f, "{}{}, {}", Mnemonic("sext.b"), rd, rs1),
+            Op::SextH { rd, rs1 } => write!(f, "{}{}, {}", Mnemonic("sext.h"), rd, rs1),
+            Op::ZextH { rd, rs1 } => write!(f, "{}{}, {}", Mnemonic("zext.h"), rd, rs1),
+            Op::OrcB { rd, rs1 } => write!(f, "{}{}, {}", Mnemonic("orc.b"), rd, rs1),
+            Op::Rev8 { rd, rs1 } => write!(f, "{}{}, {}", Mnemonic("rev8"), rd, rs1),
+            Op::Rori { rd, rs1, shamt } => reg_reg_imm(f, "rori", rd, rs1, shamt),
+
+            Op::Clmul { rd, rs1, rs2 } => reg_reg_reg(f, "clmul", rd, rs1, rs2),
+            Op::Clmulh { rd, rs1, rs2 } => reg_reg_reg(f, "clmulh", rd, rs1, rs2),
+            Op::Clmulr { rd, rs1, rs2 } => reg_reg_reg(f, "clmulr", rd, rs1, rs2),
+
+            Op::Bclr { rd, rs1, rs2 } => reg_reg_reg(f, "bclr", rd, rs1, rs2),
+            Op::Bext { rd, rs1, rs2 } => reg_reg_reg(f, "bext", rd, rs1, rs2),
+            Op::Binv { rd, rs1, rs2 } => reg_reg_reg(f, "binv", rd, rs1, rs2),
+            Op::Bset { rd, rs1, rs2 } => reg_reg_reg(f, "bset", rd, rs1, rs2),
+            Op::Bclri { rd, rs1, shamt } => reg_reg_imm(f, "bclri", rd, rs1, shamt),
+            Op::Bexti { rd, rs1, shamt } => reg_reg_imm(f, "bexti", rd, rs1, shamt),
+            Op::Binvi { rd, rs1, shamt } => reg_reg_imm(f, "binvi", rd, rs1, shamt),
+            Op::Bseti { rd, rs1, shamt } => reg_reg_imm(f, "bseti", rd, rs1, shamt),
+
+            Op::Vsetvli { rd, rs1, vtypei } => reg_reg_imm(f, "vsetvli", rd, rs1, VType(vtypei)),
+            Op::Vsetivli { rd, uimm, vtypei } => {
+                reg_reg_imm(f, "vsetivli", rd, uimm, VType(vtypei))
+            }
+            Op::Vsetvl { rd, rs1, rs2 } => reg_reg_reg(f, "vsetvl", rd, rs1, rs2),
+            Op::Vle8V { vd, rs1, vm } => vector_mem(f, "vle8.v", vd, rs1, vm),
+            Op::Vse8V { vs3, rs1, vm } => vector_mem(f, "vse8.v", vs3, rs1, vm),
+            Op::Vlse8V { vd, rs1, rs2, vm } => vector_mem_offset(f, "vlse8.v", vd, rs1, rs2, vm),
+            Op::Vsse8V { vs3, rs1, rs2, vm } => vector_mem_offset(f, "vsse8.v", vs3, rs1, rs2, vm),
+            Op::Vluxei8V { vd, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vluxei8.v", vd, rs1, vs2, vm)
+            }
+            Op::Vsuxei8V { vs3, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vsuxei8.v", vs3, rs1, vs2, vm)
+            }
+            Op::Vloxei8V { vd, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vloxei8.v", vd, rs1, vs2, vm)
+            }
+            Op::Vsoxei8V { vs3, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vsoxei8.v", vs3, rs1, vs2, vm)
+            }
+            Op::Vle16V { vd, rs1, vm } => vector_mem(f, "vle16.v", vd, rs1, vm),
+            Op::Vse16V { vs3, rs1, vm } => vector_mem(f, "vse16.v", vs3, rs1, vm),
+            Op::Vlse16V { vd, rs1, rs2, vm } => vector_mem_offset(f, "vlse16.v", vd, rs1, rs2, vm),
+            Op::Vsse16V { vs3, rs1, rs2, vm } => {
+                vector_mem_offset(f, "vsse16.v", vs3, rs1, rs2, vm)
+            }
+            Op::Vluxei16V { vd, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vluxei16.v", vd, rs1, vs2, vm)
+            }
+            Op::Vsuxei16V { vs3, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vsuxei16.v", vs3, rs1, vs2, vm)
+            }
+            Op::Vloxei16V { vd, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vloxei16.v", vd, rs1, vs2, vm)
+            }
+            Op::Vsoxei16V { vs3, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vsoxei16.v", vs3, rs1, vs2, vm)
+            }
+            Op::Vle32V { vd, rs1, vm } => vector_mem(f, "vle32.v", vd, rs1, vm),
+            Op::Vse32V { vs3, rs1, vm } => vector_mem(f, "vse32.v", vs3, rs1, vm),
+            Op::Vlse32V { vd, rs1, rs2, vm } => vector_mem_offset(f, "vlse32.v", vd, rs1, rs2, vm),
+            Op::Vsse32V { vs3, rs1, rs2, vm } => {
+                vector_mem_offset(f, "vsse32.v", vs3, rs1, rs2, vm)
+            }
+            Op::Vluxei32V { vd, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vluxei32.v", vd, rs1, vs2, vm)
+            }
+            Op::Vsuxei32V { vs3, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vsuxei32.v", vs3, rs1, vs2, vm)
+            }
+            Op::Vloxei32V { vd, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vloxei32.v", vd, rs1, vs2, vm)
+            }
+            Op::Vsoxei32V { vs3, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vsoxei32.v", vs3, rs1, vs2, vm)
+            }
+            Op::Vle64V { vd, rs1, vm } => vector_mem(f, "vle64.v", vd, rs1, vm),
+            Op::Vse64V { vs3, rs1, vm } => vector_mem(f, "vse64.v", vs3, rs1, vm),
+            Op::Vlse64V { vd, rs1, rs2, vm } => vector_mem_offset(f, "vlse64.v", vd, rs1, rs2, vm),
+            Op::Vsse64V { vs3, rs1, rs2, vm } => {
+                vector_mem_offset(f, "vsse64.v", vs3, rs1, rs2, vm)
+            }
+            Op::Vluxei64V { vd, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vluxei64.v", vd, rs1, vs2, vm)
+            }
+            Op::Vsuxei64V { vs3, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vsuxei64.v", vs3, rs1, vs2, vm)
+            }
+            Op::Vloxei64V { vd, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vloxei64.v", vd, rs1, vs2, vm)
+            }
+            Op::Vsoxei64V { vs3, rs1, vs2, vm } => {
+                vector_mem_offset(f, "vsoxei64.v", vs3, rs1, vs2, vm)
+            }
+            Op::VaddVv { vd, vs2, vs1, vm } => vector_op(f, "vadd.vv", vd, vs2, vs1, vm),
+            Op::VaddVx { vd, vs2, rs1, vm } => vector_op(f, "vadd.vx", vd, vs2, rs1, vm),
+            Op::VaddVi { vd, vs2, simm, vm } => vector_op(f, "vadd.vi", vd, vs2, simm, vm),
+            Op::VsubVv { vd, vs2, vs1, vm } => vector_op(f, "vsub.vv", vd, vs2, vs1, vm),
+            Op::VsubVx { vd, vs2, rs1, vm } => vector_op(f, "vsub.vx", vd, vs2, rs1, vm),
+            Op::VrsubVx { vd, vs2, rs1, vm } => vector_op(f, "vrsub.vx", vd, vs2, rs1, vm),
+            Op::VrsubVi { vd, vs2, simm, vm } => vector_op(f, "vrsub.vi", vd, vs2, simm, vm),
+            Op::VminuVv { vd, vs2, vs1, vm } => vector_op(f, "vminu.vv", vd, vs2, vs1, vm),
+            Op::VminuVx { vd, vs2, rs1, vm } => vector_op(f, "vminu.vx", vd, vs2, rs1, vm),
+            Op::VminVv { vd, vs2, vs1, vm } => vector_op(f, "vmin.vv", vd, vs2, vs1, vm),
+            Op::VminVx { vd, vs2, rs1, vm } => vector_op(f, "vmin.vx", vd, vs2, rs1, vm),
+            Op::VmaxuVv { vd, vs2, vs1, vm } => vector_op(f, "vmaxu.vv", vd, vs2, vs1, vm),
+            Op::VmaxuVx { vd, vs2, rs1, vm } => vector_op(f, "vmaxu.vx", vd, vs2, rs1, vm),
+            Op::VmaxVv { vd, vs2, vs1, vm } => vector_op(f, "vmax.vv", vd, vs2, vs1, vm),
+            Op::VmaxVx { vd, vs2, rs1, vm } => vector_op(f, "vmax.vx", vd, vs2, rs1, vm),
+            Op::VandVv { vd, vs2, vs1, vm } => vector_op(f, "vand.vv", vd, vs2, vs1, vm),
+            Op::VandVx { vd, vs2, rs1, vm } => vector_op(f, "vand.vx", vd, vs2, rs1, vm),
+            Op::VandVi { vd, vs2, simm, vm } => vector_op(f, "vand.vi", vd, vs2, simm, vm),
+            Op::VorVv { vd, vs2, vs1, vm } => vector_op(f, "vor.vv", vd, vs2, vs1, vm),
+            Op::VorVx { vd, vs2, rs1, vm } => vector_op(f, "vor.vx", vd, vs2, rs1, vm),
+            Op::VorVi { vd, vs2, simm, vm } => vector_op(f, "vor.vi", vd, vs2, simm, vm),
+            Op::VxorVv { vd, vs2, vs1, vm } => vector_op(f, "vxor.vv", vd, vs2, vs1, vm),
+            Op::VxorVx { vd, vs2, rs1, vm } => vector_op(f, "vxor.vx", vd, vs2, rs1, vm),
+            Op::VxorVi { vd, vs2, simm, vm } => vector_op(f, "vxor.vi", vd, vs2, simm, vm),
+            Op::VsllVv { vd, vs2, vs1, vm } => vector_op(f, "vsll.vv", vd, vs2, vs1, vm),
+            Op::VsllVx { vd, vs2, rs1, vm } => vector_op(f, "vsll.vx", vd, vs2, rs1, vm),
+            Op::VsllVi { vd, vs2, uimm, vm } => vector_op(f, "vsll.vi", vd, vs2, uimm, vm),
+            Op::VsrlVv { vd, vs2, vs1, vm } => vector_op(f, "vsrl.vv", vd, vs2, vs1, vm),
+            Op::VsrlVx { vd, vs2, rs1, vm } => vector_op(f, "vsrl.vx", vd, vs2, rs1, vm),
+            Op::VsrlVi { vd, vs2, uimm, vm } => vector_op(f, "vsrl.vi", vd, vs2, uimm, vm),
+            Op::VsraVv { vd, vs2, vs1, vm } => vector_op(f, "vsra.vv", vd, vs2, vs1, vm),
+            Op::VsraVx { vd, vs2, rs1, vm } => vector_op(f, "vsra.vx", vd, vs2, rs1, vm),
+            Op::VsraVi { vd, vs2, uimm, vm } => vector_op(f, "vsra.vi", vd, vs2, uimm, vm),
+            Op::VmulVv { vd, vs2, vs1, vm } => vector_op(f, "vmul.vv", vd, vs2, vs1, vm),
+            Op::VmulVx { vd, vs2, rs1, vm } => vector_op(f, "vmul.vx", vd, vs2, rs1, vm),
+            Op::VmulhVv { vd, vs2, vs1, vm } => vector_op(f, "vmulh.vv", vd, vs2, vs1, vm),
+            Op::VmulhVx { vd, vs2, rs1, vm } => vector_op(f, "vmulh.vx", vd, vs2, rs1, vm),
+            Op::VmulhuVv { vd, vs2, vs1, vm } => vector_op(f, "vmulhu.vv", vd, vs2, vs1, vm),
+            Op::VmulhuVx { vd, vs2, rs1, vm } => vector_op(f, "vmulhu.vx", vd, vs2, rs1, vm),
+            Op::VmulhsuVv { vd, vs2, vs1, vm } => vector_op(f, "vmulhsu.vv", vd, vs2, vs1, vm),
+            Op::VmulhsuVx { vd, vs2, rs1, vm } => vector_op(f, "vmulhsu.vx", vd, vs2, rs1, vm),
+            Op::VdivuVv { vd, vs2, vs1, vm } => vector_op(f, "vdivu.vv", vd, vs2, vs1, vm),
+            Op::VdivuVx { vd, vs2, rs1, vm } => vector_op(f, "vdivu.vx", vd, vs2, rs1, vm),
+            Op::VdivVv { vd, vs2, vs1, vm } => vector_op(f, "vdiv.vv", vd, vs2, vs1, vm),
+            Op::VdivVx { vd, vs2, rs1, vm } => vector_op(f, "vdiv.vx", vd, vs2, rs1, vm),
+            Op::VremuVv { vd, vs2, vs1, vm } => vector_op(f, "vremu.vv", vd, vs2, vs1, vm),
+            Op::VremuVx { vd, vs2, rs1, vm } => vector_op(f, "vremu.vx", vd, vs2, rs1, vm),
+            Op::VremVv { vd, vs2, vs1, vm } => vector_op(f, "vrem.vv", vd, vs2, vs1, vm),
+            Op::VremVx { vd, vs2, rs1, vm } => vector_op(f, "vrem.vx", vd, vs2, rs1, vm),
+            Op::VmaccVv { vd, vs2, vs1, vm } => vector_op(f, "vmacc.vv", vd, vs2, vs1, vm),
+            Op::VmaccVx { vd, vs2, rs1, vm } => vector_op(f, "vmacc.vx", vd, vs2, rs1, vm),
+            Op::VmseqVv { vd, vs2, vs1, vm } => vector_op(f, "vmseq.vv", vd, vs2, vs1, vm),
+            Op::VmseqVx { vd, vs2, rs1, vm } => vector_op(f, "vmseq.vx", vd, vs2, rs1, vm),
+            Op::VmseqVi { vd, vs2, simm, vm } => vector_op(f, "vmseq.vi", vd, vs2, simm, vm),
+            Op::VmsneVv { vd, vs2, vs1, vm } => vector_op(f, "vmsne.vv", vd, vs2, vs1, vm),
+            Op::VmsneVx { vd, vs2, rs1, vm } => vector_op(f, "vmsne.vx", vd, vs2, rs1, vm),
+            Op::VmsneVi { vd, vs2, simm, vm } => vector_op(f, "vmsne.vi", vd, vs2, simm, vm),
+            Op::VmsltuVv { vd, vs2, vs1, vm } => vector_op(f, "vmsltu.vv", vd, vs2, vs1, vm),
+            Op::VmsltuVx { vd, vs2, rs1, vm } => vector_op(f, "vmsltu.vx", vd, vs2, rs1, vm),
+            Op::VmsltVv { vd, vs2, vs1, vm } => vector_op(f, "vmslt.vv", vd, vs2, vs1, vm),
+            Op::VmsltVx { vd, vs2, rs1, vm } => vector_op(f, "vmslt.vx", vd, vs2, rs1, vm),
+            Op::VmsleuVv { vd, vs2, vs1, vm } => vector_op(f, "vmsleu.vv", vd, vs2, vs1, vm),
+            Op::VmsleuVx { vd, vs2, rs1, vm } => vector_op(f, "vmsleu.vx", vd, vs2, rs1, vm),
+            Op::VmsleuVi { vd, vs2, simm, vm } => vector_op(f, "vmsleu.vi", vd, vs2, simm, vm),
+            Op::VmsleVv { vd, vs2, vs1, vm } => vector_op(f, "vmsle.vv", vd, vs2, vs1, vm),
+            Op::VmsleVx { vd, vs2, rs1, vm } => vector_op(f, "vmsle.vx", vd, vs2, rs1, vm),
+            Op::VmsleVi { vd, vs2, simm, vm } => vector_op(f, "vmsle.vi", vd, vs2, simm, vm),
+            Op::VmsgtuVx { vd, vs2, rs1, vm } => vector_op(f, "vmsgtu.vx", vd, vs2, rs1, vm),
+            Op::VmsgtuVi { vd, vs2, simm, vm } => vector_op(f, "vmsgtu.vi", vd, vs2, simm, vm),
+            Op::VmsgtVx { vd, vs2, rs1, vm } => vector_op(f, "vmsgt.vx", vd, vs2, rs1, vm),
+            Op::VmsgtVi { vd, vs2, simm, vm } => vector_op(f, "vmsgt.vi", vd, vs2, simm, vm),
+            Op::VredsumVs { vd, vs2, vs1, vm } => vector_op(f, "vredsum.vs", vd, vs2, vs1, vm),
+            Op::VredandVs { vd, vs2, vs1, vm } => vector_op(f, "vredand.vs", vd, vs2, vs1, vm),
+            Op::VredorVs { vd, vs2, vs1, vm } => vector_op(f, "vredor.vs", vd, vs2, vs1, vm),
+            Op::VredxorVs { vd, vs2, vs1, vm } => vector_op(f, "vredxor.vs", vd, vs2, vs1, vm),
+            Op::VredminuVs { vd, vs2, vs1, vm } => vector_op(f, "vredminu.vs", vd, vs2, vs1, vm),
+            Op::VredminVs { vd, vs2, vs1, vm } => vector_op(f, "vredmin.vs", vd, vs2, vs1, vm),
+            Op::VredmaxuVs { vd, vs2, vs1, vm } => vector_op(f, "vredmaxu.vs", vd, vs2, vs1, vm),
+            Op::VredmaxVs { vd, vs2, vs1, vm } => vector_op(f, "vredmax.vs", vd, vs2, vs1, vm),
+            Op::VmandMm { vd, vs2, vs1 } => reg_reg_reg(f, "vmand.mm", vd, vs2, vs1),
+            Op::VmnandMm { vd, vs2, vs1 } => reg_reg_reg(f, "vmnand.mm", vd, vs2, vs1),
+            Op::VmandnMm { vd, vs2, vs1 } => reg_reg_reg(f, "vmandn.mm", vd, vs2, vs1),
+            Op::VmxorMm { vd, vs2, vs1 } => reg_reg_reg(f, "vmxor.mm", vd, vs2, vs1),
+            Op::VmorMm { vd, vs2, vs1 } => reg_reg_reg(f, "vmor.mm", vd, vs2, vs1),
+            Op::VmnorMm { vd, vs2, vs1 } => reg_reg_reg(f, "vmnor.mm", vd, vs2, vs1),
+            Op::VmornMm { vd, vs2, vs1 } => reg_reg_reg(f, "vmorn.mm", vd, vs2, vs1),
+            Op::VmxnorMm { vd, vs2, vs1 } => reg_reg_reg(f, "vmxnor.mm", vd, vs2, vs1),
+            Op::VcpopM { rd, vs2, vm } => {
+                write!(f, "{}{}, {}{}", Mnemonic("vcpop.m"), rd, vs2, Mask(vm))
+            }
+            Op::VfirstM { rd, vs2, vm } => {
+                write!(f, "{}{}, {}{}", Mnemonic("vfirst.m"), rd, vs2, Mask(vm))
+            }
+            Op::VidV { vd, vm } => write!(f, "{}{}{}", Mnemonic("vid.v"), vd, Mask(vm)),
+            Op::VmergeVvm { vd, vs2, vs1 } => {
+                write!(f, "{}{}, {}, {}, v0", Mnemonic("vmerge.vvm"), vd, vs2, vs1)
+            }
+            Op::VmergeVxm { vd, vs2, rs1 } => {
+                write!(f, "{}{}, {}, {}, v0", Mnemonic("vmerge.vxm"), vd, vs2, rs1)
+            }
+            Op::VmergeVim { vd, vs2, simm } => {
+                write!(f, "{}{}, {}, {}, v0", Mnemonic("vmerge.vim"), vd, vs2, simm)
+            }
+            Op::VmvVV { vd, vs1 } => reg_imm(f, "vmv.v.v", vd, vs1),
+            Op::VmvVX { vd, rs1 } => reg_imm(f, "vmv.v.x", vd, rs1),
+            Op::VmvVI { vd, simm } => reg_imm(f, "vmv.v.i", vd, simm),
+            Op::VmvXS { rd, vs2 } => reg_imm(f, "vmv.x.s", rd, vs2),
+            Op::VmvSX { vd, rs1 } => reg_imm(f, "vmv.s.x", vd, rs1),
+            Op::VslideupVx { vd, vs2, rs1, vm } => vector_op(f, "vslideup.vx", vd, vs2, rs1, vm),
+            Op::VslideupVi { vd, vs2, uimm, vm } => vector_op(f, "vslideup.vi", vd, vs2, uimm, vm),
+            Op::VslidedownVx { vd, vs2, rs1, vm } => {
+                vector_op(f, "vslidedown.vx", vd, vs2, rs1, vm)
+            }
+            Op::VslidedownVi { vd, vs2, uimm, vm } => {
+                vector_op(f, "vslidedown.vi", vd, vs2, uimm, vm)
+            }
+            Op::Vslide1upVx { vd, vs2, rs1, vm } => vector_op(f, "vslide1up.vx", vd, vs2, rs1, vm),
+            Op::Vslide1downVx { vd, vs2, rs1, vm } => {
+                vector_op(f, "vslide1down.vx", vd, vs2, rs1, vm)
+            }
+            Op::VrgatherVv { vd, vs2, vs1, vm } => vector_op(f, "vrgather.vv", vd, vs2, vs1, vm),
+            Op::VrgatherVx { vd, vs2, rs1, vm } => vector_op(f, "vrgather.vx", vd, vs2, rs1, vm),
+            Op::VrgatherVi { vd, vs2, uimm, vm } => vector_op(f, "vrgather.vi", vd, vs2, uimm, vm),
+            Op::VcompressVm { vd, vs2, vs1 } => reg_reg_reg(f, "vcompress.vm", vd, vs2, vs1),
+            Op::VfaddVv { vd, vs2, vs1, vm } => vector_op(f, "vfadd.vv", vd, vs2, vs1, vm),
+            Op::VfaddVf { vd, vs2, frs1, vm } => vector_op(f, "vfadd.vf", vd, vs2, frs1, vm),
+            Op::VfsubVv { vd, vs2, vs1, vm } => vector_op(f, "vfsub.vv", vd, vs2, vs1, vm),
+            Op::VfsubVf { vd, vs2, frs1, vm } => vector_op(f, "vfsub.vf", vd, vs2, frs1, vm),
+            Op::VfrsubVf { vd, vs2, frs1, vm } => vector_op(f, "vfrsub.vf", vd, vs2, frs1, vm),
+            Op::VfmulVv { vd, vs2, vs1, vm } => vector_op(f, "vfmul.vv", vd, vs2, vs1, vm),
+            Op::VfmulVf { vd, vs2, frs1, vm } => vector_op(f, "vfmul.vf", vd, vs2, frs1, vm),
+            Op::VfdivVv { vd, vs2, vs1, vm } => vector_op(f, "vfdiv.vv", vd, vs2, vs1, vm),
+            Op::VfdivVf { vd, vs2, frs1, vm } => vector_op(f, "vfdiv.vf", vd, vs2, frs1, vm),
+            Op::VfrdivVf { vd, vs2, frs1, vm } => vector_op(f, "vfrdiv.vf", vd, vs2, frs1, vm),
+            Op::VfminVv { vd, vs2, vs1, vm } => vector_op(f, "vfmin.vv", vd, vs2, vs1, vm),
+            Op::VfminVf { vd, vs2, frs1, vm } => vector_op(f, "vfmin.vf", vd, vs2, frs1, vm),
+            Op::VfmaxVv { vd, vs2, vs1, vm } => vector_op(f, "vfmax.vv", vd, vs2, vs1, vm),
+            Op::VfmaxVf { vd, vs2, frs1, vm } => vector_op(f, "vfmax.vf", vd, vs2, frs1, vm),
+            Op::VfsgnjVv { vd, vs2, vs1, vm } => vector_op(f, "vfsgnj.vv", vd, vs2, vs1, vm),
+            Op::VfsgnjVf { vd, vs2, frs1, vm } => vector_op(f, "vfsgnj.vf", vd, vs2, frs1, vm),
+            Op::VfsgnjnVv { vd, vs2, vs1, vm } => vector_op(f, "vfsgnjn.vv", vd, vs2, vs1, vm),
+            Op::VfsgnjnVf { vd, vs2, frs1, vm } => vector_op(f, "vfsgnjn.vf", vd, vs2, frs1, vm),
+            Op::VfsgnjxVv { vd, vs2, vs1, vm } => vector_op(f, "vfsgnjx.vv", vd, vs2, vs1, vm),
+            Op::VfsgnjxVf { vd, vs2, frs1, vm } => vector_op(f, "vfsgnjx.vf", vd, vs2, frs1, vm),
+            Op::VmfeqVv { vd, vs2, vs1, vm } => vector_op(f, "vmfeq.vv", vd, vs2, vs1, vm),
+            Op::VmfeqVf { vd, vs2, frs1, vm } => vector_op(f, "vmfeq.vf", vd, vs2, frs1, vm),
+            Op::VmfneVv { vd, vs2, vs1, vm } => vector_op(f, "vmfne.vv", vd, vs2, vs1, vm),
+            Op::VmfneVf { vd, vs2, frs1, vm } => vector_op(f, "vmfne.vf", vd, vs2, frs1, vm),
+            Op::VmfltVv { vd, vs2, vs1, vm } => vector_op(f, "vmflt.vv", vd, vs2, vs1, vm),
+            Op::VmfltVf { vd, vs2, frs1, vm } => vector_op(f, "vmflt.vf", vd, vs2, frs1, vm),
+            Op::VmfleVv { vd, vs2, vs1, vm } => vector_op(f, "vmfle.vv", vd, vs2, vs1, vm),
+            Op::VmfleVf { vd, vs2, frs1, vm } => vector_op(f, "vmfle.vf", vd, vs2, frs1, vm),
+            Op::VmfgtVf { vd, vs2, frs1, vm } => vector_op(f, "vmfgt.vf", vd, vs2, frs1, vm),
+            Op::VmfgeVf { vd, vs2, frs1, vm } => vector_op(f, "vmfge.vf", vd, vs2, frs1, vm),
+            Op::VfredusumVs { vd, vs2, vs1, vm } => vector_op(f, "vfredusum.vs", vd, vs2, vs1, vm),
+            Op::VfredosumVs { vd, vs2, vs1, vm } => vector_op(f, "vfredosum.vs", vd, vs2, vs1, vm),
+            Op::VfredminVs { vd, vs2, vs1, vm } => vector_op(f, "vfredmin.vs", vd, vs2, vs1, vm),
+            Op::VfredmaxVs { vd, vs2, vs1, vm } => vector_op(f, "vfredmax.vs", vd, vs2, vs1, vm),
+            Op::VfmergeVfm { vd, vs2, frs1 } => write!(
+                f,
+                "{}{}, {}, {}, v0",
+                Mnemonic("vfmerge.vfm"),
+                vd,
+                vs2,
+                frs1
+            ),
+            Op::VfmvVF { vd, frs1 } => reg_imm(f, "vfmv.v.f", vd, frs1),
+            Op::VfmvFS { frd, vs2 } => reg_imm(f, "vfmv.f.s", frd, vs2),
+            Op::VfmvSF { vd, frs1 } => reg_imm(f, "vfmv.s.f", vd, frs1),
+        }
+    }
+}
+
+// Writes an instruction mnemonic followed by the padding that separates it
+// from its operands: Spike aligns operands to the ninth column, but always
+// leaves at least one space.
+struct Mnemonic<T: fmt::Display>(T);
+
+impl<T: fmt::Display> fmt::Display for Mnemonic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<7} ", self.0)
+    }
+}
+
+// A mnemonic with the memory ordering suffix of an atomic operation.
+struct Ordered(&'static str, bool, bool);
+
+impl fmt::Display for Ordered {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suffix = match (self.1, self.2) {
+            (false, false) => "",
+            (true, false) => ".aq",
+            (false, true) => ".rl",
+            (true, true) => ".aqrl",
+        };
+        // Padding is applied by the caller, so this must write the whole
+        // mnemonic through the formatter in one piece.
+        let mut buf = [0u8; 16];
+        let len = self.0.len() + suffix.len();
+        buf[..self.0.len()].copy_from_slice(self.0.as_bytes());
+        buf[self.0.len()..len].copy_from_slice(suffix.as_bytes());
+        f.pad(core::str::from_utf8(&buf[..len]).unwrap())
+    }
+}
+
+// The operand of `lui` and `auipc`, which is written as the hexadecimal value
+// of the upper 20 bits rather than as the full 32-bit immediate.
+struct UpperImm(i32);
+
+impl fmt::Display for UpperImm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:x}", (self.0 as u32) >> 12)
+    }
+}
+
+// A branch or jump target relative to the program counter.
+struct Target(i32);
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 < 0 {
+            write!(f, "pc - {}", self.0.unsigned_abs())
+        } else {
+            write!(f, "pc + {}", self.0)
+        }
+    }
+}
+
+// A CSR number, written by name when it is one of the standard CSRs.
+struct Csr(u32);
+
+impl fmt::Display for Csr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match csr_name(self.0) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "unknown_{:03x}", self.0),
+        }
+    }
+}
+
+fn csr_name(csr: u32) -> Option<&'static str> {
+    Some(match csr {
+        0x000 => "ustatus",
+        0x001 => "fflags",
+        0x002 => "frm",
+        0x003 => "fcsr",
+        0x004 => "uie",
+        0x005 => "utvec",
+        0x040 => "uscratch",
+        0x041 => "uepc",
+        0x042 => "ucause",
+        0x043 => "utval",
+        0x044 => "uip",
+        0x100 => "sstatus",
+        0x104 => "sie",
+        0x105 => "stvec",
+        0x106 => "scounteren",
+        0x140 => "sscratch",
+        0x141 => "sepc",
+        0x142 => "scause",
+        0x143 => "stval",
+        0x144 => "sip",
+        0x180 => "satp",
+        0x300 => "mstatus",
+        0x301 => "misa",
+        0x302 => "medeleg",
+        0x303 => "mideleg",
+        0x304 => "mie",
+        0x305 => "mtvec",
+        0x306 => "mcounteren",
+        0x310 => "mstatush",
+        0x340 => "mscratch",
+        0x341 => "mepc",
+        0x342 => "mcause",
+        0x343 => "mtval",
+        0x344 => "mip",
+        0x3a0 => "pmpcfg0",
+        0x3b0 => "pmpaddr0",
+        0x7a0 => "tselect",
+        0x7a1 => "tdata1",
+        0x7b0 => "dcsr",
+        0x7b1 => "dpc",
+        0xb00 => "mcycle",
+        0xb02 => "minstret",
+        0xc00 => "cycle",
+        0xc01 => "time",
+        0xc02 => "instret",
+        0xc80 => "cycleh",
+        0xc81 => "timeh",
+        0xc82 => "instreth",
+        0xf11 => "mvendorid",
+        0xf12 => "marchid",
+        0xf13 => "mimpid",
+        0xf14 => "mhartid",
+        _ => return None,
+    })
+}
+
+// The suffix of a vector instruction that is masked by v0, which is empty
+// when the instruction is unmasked.
+struct Mask(bool);
+
+impl fmt::Display for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            true => Ok(()),
+            false => f.write_str(", v0.t"),
+        }
+    }
+}
+
+// The vtype immediate of vsetvli and vsetivli, written as its fields
+// unless it uses a reserved encoding.
+struct VType(u32);
+
+impl fmt::Display for VType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (vlmul, vsew) = (self.0 & 0b111, (self.0 >> 3) & 0b111);
+        if vlmul == 0b100 || vsew > 0b011 || self.0 >> 8 != 0 {
+            return write!(f, "{}", self.0);
+        }
+        write!(f, "e{}, ", 8 << vsew)?;
+        match vlmul {
+            0..=3 => write!(f, "m{}", 1 << vlmul)?,
+            _ => write!(f, "mf{}", 1 << (8 - vlmul))?,
+        }
+        let vta = self.0 & (1 << 6) != 0;
+        let vma = self.0 & (1 << 7) != 0;
+        write!(
+            f,
+            ", {}, {}",
+            if vta { "ta" } else { "tu" },
+            if vma { "ma" } else { "mu" }
+        )
+    }
+}
+
+// The compressed floating point loads and stores decode their register
+// selection as a raw three-bit number, which selects from f8 through f15.
+fn compressed(reg: IntRegister) -> FloatRegister {
+    FloatRegister::c_numbered(reg.num())
+}
+
+fn reg_imm(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    rd: impl fmt::Display,
+    imm: impl fmt::Display,
+) -> fmt::Result {
+    write!(f, "{}{}, {}", Mnemonic(name), rd, imm)
+}
+
+fn reg_reg_imm(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    rd: impl fmt::Display,
+    rs1: impl fmt::Display,
+    imm: impl fmt::Display,
+) -> fmt::Result {
+    write!(f, "{}{}, {}, {}", Mnemonic(name), rd, rs1, imm)
+}
+
+fn reg_reg_reg(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    rd: impl fmt::Display,
+    rs1: impl fmt::Display,
+    rs2: impl fmt::Display,
+) -> fmt::Result {
+    write!(f, "{}{}, {}, {}", Mnemonic(name), rd, rs1, rs2)
+}
+
+fn float_move(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    rd: impl fmt::Display,
+    rs1: impl fmt::Display,
+) -> fmt::Result {
+    write!(f, "{}{}, {}", Mnemonic(name), rd, rs1)
+}
+
+fn fused(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    frd: FloatRegister,
+    frs1: FloatRegister,
+    frs2: FloatRegister,
+    frs3: FloatRegister,
+) -> fmt::Result {
+    write!(f, "{}{}, {}, {}, {}", Mnemonic(name), frd, frs1, frs2, frs3)
+}
+
+fn branch(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    simm: i32,
+) -> fmt::Result {
+    write!(f, "{}{}, {}, {}", Mnemonic(name), rs1, rs2, Target(simm))
+}
+
+fn branch_zero(f: &mut fmt::Formatter<'_>, name: &str, rs1: IntRegister, simm: i32) -> fmt::Result {
+    write!(f, "{}{}, {}", Mnemonic(name), rs1, Target(simm))
+}
+
+fn load_store(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    reg: impl fmt::Display,
+    base: IntRegister,
+    offset: impl fmt::Display,
+) -> fmt::Result {
+    write!(f, "{}{}, {}({})", Mnemonic(name), reg, offset, base)
+}
+
+fn vector_op(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    vd: VectorRegister,
+    vs2: VectorRegister,
+    src: impl fmt::Display,
+    vm: bool,
+) -> fmt::Result {
+    write!(f, "{}{}, {}, {}{}", Mnemonic(name), vd, vs2, src, Mask(vm))
+}
+
+fn vector_mem(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    reg: VectorRegister,
+    base: IntRegister,
+    vm: bool,
+) -> fmt::Result {
+    write!(f, "{}{}, ({}){}", Mnemonic(name), reg, base, Mask(vm))
+}
+
+fn vector_mem_offset(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    reg: VectorRegister,
+    base: IntRegister,
+    offset: impl fmt::Display,
+    vm: bool,
+) -> fmt::Result {
+    write!(
+        f,
+        "{}{}, ({}), {}{}",
+        Mnemonic(name),
+        reg,
+        base,
+        offset,
+        Mask(vm)
+    )
+}
+
+fn amo(
+    f: &mut fmt::Formatter<'_>,
+    name: &'static str,
+    rd: IntRegister,
+    rs1: IntRegister,
+    rs2: IntRegister,
+    aq: bool,
+    rl: bool,
+) -> fmt::Result {
+    write!(
+        f,
+        "{}{}, {}, ({})",
+        Mnemonic(Ordered(name, aq, rl)),
+        rd,
+        rs2,
+        rs1
+    )
+}
+
+fn csr_op(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    rd: IntRegister,
+    csr: u32,
+    src: impl fmt::Display,
+) -> fmt::Result {
+    write!(f, "{}{}, {}, {}", Mnemonic(name), rd, Csr(csr), src)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::instruction::OperationRV32;
+    use crate::raw_instruction::RawInstruction;
+    use std::format;
+
+    fn disasm(word: u32) -> std::string::String {
+        format!(
+            "{}",
+            OperationRV32::decode_from_raw(RawInstruction::new(word))
+        )
+    }
+
+    #[test]
+    fn spike_syntax() {
+        // Expected results are from Spike's disassembler.
+        assert_eq!(disasm(0x00000297), "auipc   t0, 0x0");
+        assert_eq!(disasm(0x02028593), "addi    a1, t0, 32");
+        assert_eq!(disasm(0x00000013), "nop");
+        assert_eq!(disasm(0x00500513), "li      a0, 5");
+        assert_eq!(disasm(0x00058513), "mv      a0, a1");
+        assert_eq!(disasm(0xffc12583), "lw      a1, -4(sp)");
+        assert_eq!(disasm(0x00b12223), "sw      a1, 4(sp)");
+        assert_eq!(disasm(0xfe051ee3), "bnez    a0, pc - 4");
+        assert_eq!(disasm(0x00b50463), "beq     a0, a1, pc + 8");
+        assert_eq!(disasm(0x0080006f), "j       pc + 8");
+        assert_eq!(disasm(0x00008067), "ret");
+        assert_eq!(disasm(0x123455b7), "lui     a1, 0x12345");
+        assert_eq!(disasm(0xf1402573), "csrr    a0, mhartid");
+        assert_eq!(disasm(0x0c05a52f), "amoswap.w.aq a0, zero, (a1)");
+        assert_eq!(disasm(0x00000073), "ecall");
+        assert_eq!(disasm(0x0000_0505), "c.addi  a0, 1");
+        assert_eq!(disasm(0x0000_4188), "c.lw    a0, 0(a1)");
+        assert_eq!(disasm(0x0000_a001), "c.j     pc + 0");
+        assert_eq!(disasm(0x20b54533), "sh2add  a0, a0, a1");
+        assert_eq!(disasm(0x60051513), "clz     a0, a0");
+        assert_eq!(disasm(0x28755513), "orc.b   a0, a0");
+        assert_eq!(disasm(0x0ab51533), "clmul   a0, a0, a1");
+        assert_eq!(disasm(0x28b51533), "bset    a0, a0, a1");
+        assert_eq!(disasm(0xcd0272d7), "vsetivli t0, 4, e32, m1, ta, ma");
+        assert_eq!(disasm(0x02056087), "vle32.v v1, (a0)");
+        assert_eq!(disasm(0x0af56407), "vlse32.v v8, (a0), a5");
+        assert_eq!(disasm(0x00318257), "vadd.vv v4, v3, v3, v0.t");
+        assert_eq!(disasm(0x42602657), "vmv.x.s a2, v6");
+    }
+}
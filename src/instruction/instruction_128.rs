@@ -0,0 +1,1554 @@
+use crate::isa::Extensions;
+use crate::raw_instruction::{Opcode, RawInstruction};
+use crate::register::IntRegister;
+
+/// Enumeration of all operations from the RV128 ISA.
+///
+/// RV128I is not yet frozen, so this covers only the integer base ISA as
+/// currently drafted, along with its multiply and divide extension and the
+/// system instructions. Compressed, atomic and floating point instructions
+/// decode as `Invalid`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OperationRV128 {
+    /// The result of decoding an instruction that isn't valid at all,
+    /// according to the current decoder implementation.
+    Invalid,
+
+    // RV128I: Base Integer Instruction Set
+    /// Add (RV128I)
+    Add {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Add Immediate (RV128I)
+    Addi {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Add Immediate Double (RV128I)
+    Addid {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Add Immediate Word (RV128I)
+    Addiw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Add Double (RV128I)
+    Addd {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Add Word (RV128I)
+    Addw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// And (RV128I)
+    And {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// And Immediate (RV128I)
+    Andi {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Add Upper Immediate to PC (RV128I)
+    Auipc { rd: IntRegister, simm: i32 },
+    /// Branch Equal (RV128I)
+    Beq {
+        rs1: IntRegister,
+        rs2: IntRegister,
+        simm: i32,
+    },
+    /// Branch Greater than Equal (RV128I)
+    Bge {
+        rs1: IntRegister,
+        rs2: IntRegister,
+        simm: i32,
+    },
+    /// Branch Greater than Equal Unsigned (RV128I)
+    Bgeu {
+        rs1: IntRegister,
+        rs2: IntRegister,
+        simm: i32,
+    },
+    /// Branch Less Than (RV128I)
+    Blt {
+        rs1: IntRegister,
+        rs2: IntRegister,
+        simm: i32,
+    },
+    /// Branch Less Than Unsigned (RV128I)
+    Bltu {
+        rs1: IntRegister,
+        rs2: IntRegister,
+        simm: i32,
+    },
+    /// Branch Not Equal (RV128I)
+    Bne {
+        rs1: IntRegister,
+        rs2: IntRegister,
+        simm: i32,
+    },
+    /// Fence (RV128I)
+    Fence { pred: u32, succ: u32 },
+    /// Fence Instruction (RV128I)
+    FenceI,
+    /// Jump and Link (RV128I)
+    Jal { rd: IntRegister, simm: i32 },
+    /// Jump and Link Register (RV128I)
+    Jalr {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Load Byte (RV128I)
+    Lb {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Load Byte Unsigned (RV128I)
+    Lbu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Load Double (RV128I)
+    Ld {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Load Double Unsigned (RV128I)
+    Ldu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Load Half (RV128I)
+    Lh {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Load Half Unsigned (RV128I)
+    Lhu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Load Quad (RV128I)
+    Lq {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Load Upper Immediate (RV128I)
+    Lui { rd: IntRegister, simm: i32 },
+    /// Load Word (RV128I)
+    Lw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Load Word Unsigned (RV128I)
+    Lwu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Or (RV128I)
+    Or {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Or Immediate (RV128I)
+    Ori {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Store Byte (RV128I)
+    Sb {
+        rs1: IntRegister,
+        rs2: IntRegister,
+        simm: i32,
+    },
+    /// Store Double (RV128I)
+    Sd {
+        rs1: IntRegister,
+        rs2: IntRegister,
+        simm: i32,
+    },
+    /// Store Half (RV128I)
+    Sh {
+        rs1: IntRegister,
+        rs2: IntRegister,
+        simm: i32,
+    },
+    /// Shift Left Logical (RV128I)
+    Sll {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Left Logical Double (RV128I)
+    Slld {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Left Logical Immediate (RV128I)
+    Slli {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Shift Left Logical Immediate Double (RV128I)
+    Sllid {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Shift Left Logical Immediate Word (RV128I)
+    Slliw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Shift Left Logical Word (RV128I)
+    Sllw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Set Less Than (RV128I)
+    Slt {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Set Less Than Immediate (RV128I)
+    Slti {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Set Less Than Immediate Unsigned (RV128I)
+    Sltiu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+    /// Set Less Than Unsigned (RV128I)
+    Sltu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Store Quad (RV128I)
+    Sq {
+        rs1: IntRegister,
+        rs2: IntRegister,
+        simm: i32,
+    },
+    /// Shift Right Arithmetic (RV128I)
+    Sra {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Right Arithmetic Double (RV128I)
+    Srad {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Right Arithmetic Immediate (RV128I)
+    Srai {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Shift Right Arithmetic Immediate Double (RV128I)
+    Sraid {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Shift Right Arithmetic Immediate Word (RV128I)
+    Sraiw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Shift Right Arithmetic Word (RV128I)
+    Sraw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Right Logical (RV128I)
+    Srl {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Right Logical Double (RV128I)
+    Srld {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Right Logical Immediate (RV128I)
+    Srli {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Shift Right Logical Immediate Double (RV128I)
+    Srlid {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Shift Right Logical Immediate Word (RV128I)
+    Srliw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Shift Right Logical Word (RV128I)
+    Srlw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Subtract (RV128I)
+    Sub {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Subtract Double (RV128I)
+    Subd {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Subtract Word (RV128I)
+    Subw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Store Word (RV128I)
+    Sw {
+        rs1: IntRegister,
+        rs2: IntRegister,
+        simm: i32,
+    },
+    /// Xor (RV128I)
+    Xor {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Xor Immediate (RV128I)
+    Xori {
+        rd: IntRegister,
+        rs1: IntRegister,
+        simm: i32,
+    },
+
+    // RV128M: Integer Multiply and Divide
+    /// Divide Signed (RV128M)
+    Div {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Divide Signed Double (RV128M)
+    Divd {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Divide Unsigned (RV128M)
+    Divu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Divide Unsigned Double (RV128M)
+    Divud {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Divide Unsigned Word (RV128M)
+    Divuw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Divide Signed Word (RV128M)
+    Divw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Multiply (RV128M)
+    Mul {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Multiply Double (RV128M)
+    Muld {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Multiply High Signed Signed (RV128M)
+    Mulh {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Multiply High Signed Unsigned (RV128M)
+    Mulhsu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Multiply High Unsigned Unsigned (RV128M)
+    Mulhu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Multiply Word (RV128M)
+    Mulw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Remainder Signed (RV128M)
+    Rem {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Remainder Signed Double (RV128M)
+    Remd {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Remainder Unsigned (RV128M)
+    Remu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Remainder Unsigned Double (RV128M)
+    Remud {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Remainder Unsigned Word (RV128M)
+    Remuw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Remainder Signed Word (RV128M)
+    Remw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+
+    // RV128S: Supervisor-level Instructions
+    /// CSR Atomic Clear Bit (RV128S)
+    Csrrc {
+        rd: IntRegister,
+        rs1: IntRegister,
+        csr: u32,
+    },
+    /// CSR Atomic Clear Bit Immediate (RV128S)
+    Csrrci {
+        rd: IntRegister,
+        uimm: u32,
+        csr: u32,
+    },
+    /// CSR Atomic Set Bit (RV128S)
+    Csrrs {
+        rd: IntRegister,
+        rs1: IntRegister,
+        csr: u32,
+    },
+    /// CSR Atomic Set Bit Immediate (RV128S)
+    Csrrsi {
+        rd: IntRegister,
+        uimm: u32,
+        csr: u32,
+    },
+    /// CSR Atomic Read Write (RV128S)
+    Csrrw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        csr: u32,
+    },
+    /// CSR Atomic Read Write Immediate (RV128S)
+    Csrrwi {
+        rd: IntRegister,
+        uimm: u32,
+        csr: u32,
+    },
+    /// Debug-Mode Return (RV128S)
+    Dret,
+    /// Environment Break to Debugger (RV128S)
+    Ebreak,
+    /// Environment Call (RV128S)
+    Ecall,
+    /// Machine-Mode Return (RV128S)
+    Mret,
+    /// System Return (RV128S)
+    Sret,
+    /// User Return (RV128S)
+    Uret,
+    /// Wait For Interrupt (RV128S)
+    Wfi,
+    /// Supervisor Memory Management Fence (RV128S)
+    SfenceVma { rs1: IntRegister, rs2: IntRegister },
+}
+
+impl OperationRV128 {
+    /// Returns the extensions that a hart must implement for this operation
+    /// to be valid. Operations from the base integer ISA don't require any.
+    pub fn required_extensions(&self) -> Extensions {
+        match self {
+            Self::Csrrc { .. }
+            | Self::Csrrci { .. }
+            | Self::Csrrs { .. }
+            | Self::Csrrsi { .. }
+            | Self::Csrrw { .. }
+            | Self::Csrrwi { .. } => Extensions::ZICSR,
+            Self::FenceI => Extensions::ZIFENCEI,
+            Self::Div { .. }
+            | Self::Divd { .. }
+            | Self::Divu { .. }
+            | Self::Divud { .. }
+            | Self::Divuw { .. }
+            | Self::Divw { .. }
+            | Self::Mul { .. }
+            | Self::Muld { .. }
+            | Self::Mulh { .. }
+            | Self::Mulhsu { .. }
+            | Self::Mulhu { .. }
+            | Self::Mulw { .. }
+            | Self::Rem { .. }
+            | Self::Remd { .. }
+            | Self::Remu { .. }
+            | Self::Remud { .. }
+            | Self::Remuw { .. }
+            | Self::Remw { .. } => Extensions::M,
+            _ => Extensions::NONE,
+        }
+    }
+
+    /// Returns the integer registers that this operation names as its
+    /// operands or destination.
+    pub fn int_registers(&self) -> [Option<IntRegister>; 3] {
+        match *self {
+            Self::Add { rd, rs1, rs2 }
+            | Self::Addd { rd, rs1, rs2 }
+            | Self::Addw { rd, rs1, rs2 }
+            | Self::And { rd, rs1, rs2 }
+            | Self::Or { rd, rs1, rs2 }
+            | Self::Sll { rd, rs1, rs2 }
+            | Self::Slld { rd, rs1, rs2 }
+            | Self::Sllw { rd, rs1, rs2 }
+            | Self::Slt { rd, rs1, rs2 }
+            | Self::Sltu { rd, rs1, rs2 }
+            | Self::Sra { rd, rs1, rs2 }
+            | Self::Srad { rd, rs1, rs2 }
+            | Self::Sraw { rd, rs1, rs2 }
+            | Self::Srl { rd, rs1, rs2 }
+            | Self::Srld { rd, rs1, rs2 }
+            | Self::Srlw { rd, rs1, rs2 }
+            | Self::Sub { rd, rs1, rs2 }
+            | Self::Subd { rd, rs1, rs2 }
+            | Self::Subw { rd, rs1, rs2 }
+            | Self::Xor { rd, rs1, rs2 }
+            | Self::Div { rd, rs1, rs2 }
+            | Self::Divd { rd, rs1, rs2 }
+            | Self::Divu { rd, rs1, rs2 }
+            | Self::Divud { rd, rs1, rs2 }
+            | Self::Divuw { rd, rs1, rs2 }
+            | Self::Divw { rd, rs1, rs2 }
+            | Self::Mul { rd, rs1, rs2 }
+            | Self::Muld { rd, rs1, rs2 }
+            | Self::Mulh { rd, rs1, rs2 }
+            | Self::Mulhsu { rd, rs1, rs2 }
+            | Self::Mulhu { rd, rs1, rs2 }
+            | Self::Mulw { rd, rs1, rs2 }
+            | Self::Rem { rd, rs1, rs2 }
+            | Self::Remd { rd, rs1, rs2 }
+            | Self::Remu { rd, rs1, rs2 }
+            | Self::Remud { rd, rs1, rs2 }
+            | Self::Remuw { rd, rs1, rs2 }
+            | Self::Remw { rd, rs1, rs2 } => [Some(rd), Some(rs1), Some(rs2)],
+            Self::Addi { rd, rs1, .. }
+            | Self::Addid { rd, rs1, .. }
+            | Self::Addiw { rd, rs1, .. }
+            | Self::Andi { rd, rs1, .. }
+            | Self::Jalr { rd, rs1, .. }
+            | Self::Lb { rd, rs1, .. }
+            | Self::Lbu { rd, rs1, .. }
+            | Self::Ld { rd, rs1, .. }
+            | Self::Ldu { rd, rs1, .. }
+            | Self::Lh { rd, rs1, .. }
+            | Self::Lhu { rd, rs1, .. }
+            | Self::Lq { rd, rs1, .. }
+            | Self::Lw { rd, rs1, .. }
+            | Self::Lwu { rd, rs1, .. }
+            | Self::Ori { rd, rs1, .. }
+            | Self::Slli { rd, rs1, .. }
+            | Self::Sllid { rd, rs1, .. }
+            | Self::Slliw { rd, rs1, .. }
+            | Self::Slti { rd, rs1, .. }
+            | Self::Sltiu { rd, rs1, .. }
+            | Self::Srai { rd, rs1, .. }
+            | Self::Sraid { rd, rs1, .. }
+            | Self::Sraiw { rd, rs1, .. }
+            | Self::Srli { rd, rs1, .. }
+            | Self::Srlid { rd, rs1, .. }
+            | Self::Srliw { rd, rs1, .. }
+            | Self::Xori { rd, rs1, .. }
+            | Self::Csrrc { rd, rs1, .. }
+            | Self::Csrrs { rd, rs1, .. }
+            | Self::Csrrw { rd, rs1, .. } => [Some(rd), Some(rs1), None],
+            Self::Auipc { rd, .. }
+            | Self::Jal { rd, .. }
+            | Self::Lui { rd, .. }
+            | Self::Csrrci { rd, .. }
+            | Self::Csrrsi { rd, .. }
+            | Self::Csrrwi { rd, .. } => [Some(rd), None, None],
+            Self::Beq { rs1, rs2, .. }
+            | Self::Bge { rs1, rs2, .. }
+            | Self::Bgeu { rs1, rs2, .. }
+            | Self::Blt { rs1, rs2, .. }
+            | Self::Bltu { rs1, rs2, .. }
+            | Self::Bne { rs1, rs2, .. }
+            | Self::Sb { rs1, rs2, .. }
+            | Self::Sd { rs1, rs2, .. }
+            | Self::Sh { rs1, rs2, .. }
+            | Self::Sq { rs1, rs2, .. }
+            | Self::Sw { rs1, rs2, .. }
+            | Self::SfenceVma { rs1, rs2 } => [Some(rs1), Some(rs2), None],
+            _ => [None; 3],
+        }
+    }
+
+    pub fn decode_from_raw(raw: RawInstruction) -> Self {
+        let opcode = raw.opcode();
+        if opcode == (Opcode::Auipc as u8) {
+            if raw.matches(
+                0b00000000000000000000000001111111,
+                0b00000000000000000000000000010111,
+            ) {
+                Self::Auipc {
+                    rd: raw.rd(),
+                    simm: raw.oimm20(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Branch as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000000000001100011,
+            ) {
+                Self::Beq {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.sbimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000101000001100011,
+            ) {
+                Self::Bge {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.sbimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000111000001100011,
+            ) {
+                Self::Bgeu {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.sbimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000100000001100011,
+            ) {
+                Self::Blt {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.sbimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000110000001100011,
+            ) {
+                Self::Bltu {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.sbimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000001000001100011,
+            ) {
+                Self::Bne {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.sbimm12(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Jal as u8) {
+            if raw.matches(
+                0b00000000000000000000000001111111,
+                0b00000000000000000000000001101111,
+            ) {
+                Self::Jal {
+                    rd: raw.rd(),
+                    simm: raw.jimm20(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Jalr as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000000000001100111,
+            ) {
+                Self::Jalr {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Load as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000000000000000011,
+            ) {
+                Self::Lb {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000100000000000011,
+            ) {
+                Self::Lbu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000011000000000011,
+            ) {
+                Self::Ld {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000111000000000011,
+            ) {
+                Self::Ldu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000001000000000011,
+            ) {
+                Self::Lh {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000101000000000011,
+            ) {
+                Self::Lhu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000010000000000011,
+            ) {
+                Self::Lw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000110000000000011,
+            ) {
+                Self::Lwu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Lui as u8) {
+            if raw.matches(
+                0b00000000000000000000000001111111,
+                0b00000000000000000000000000110111,
+            ) {
+                Self::Lui {
+                    rd: raw.rd(),
+                    simm: raw.imm20(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::MiscMem as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000000000000001111,
+            ) {
+                Self::Fence {
+                    pred: raw.pred(),
+                    succ: raw.succ(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000001000000001111,
+            ) {
+                Self::FenceI
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000010000000001111,
+            ) {
+                Self::Lq {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Op as u8) {
+            if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000000000000110011,
+            ) {
+                Self::Add {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000111000000110011,
+            ) {
+                Self::And {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000100000000110011,
+            ) {
+                Self::Div {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000101000000110011,
+            ) {
+                Self::Divu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000000000000110011,
+            ) {
+                Self::Mul {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000001000000110011,
+            ) {
+                Self::Mulh {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000010000000110011,
+            ) {
+                Self::Mulhsu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000011000000110011,
+            ) {
+                Self::Mulhu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000110000000110011,
+            ) {
+                Self::Or {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000110000000110011,
+            ) {
+                Self::Rem {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000111000000110011,
+            ) {
+                Self::Remu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000001000000110011,
+            ) {
+                Self::Sll {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000010000000110011,
+            ) {
+                Self::Slt {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000011000000110011,
+            ) {
+                Self::Sltu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000101000000110011,
+            ) {
+                Self::Sra {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000101000000110011,
+            ) {
+                Self::Srl {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000000000000110011,
+            ) {
+                Self::Sub {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000100000000110011,
+            ) {
+                Self::Xor {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Op32 as u8) {
+            if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000000000000111011,
+            ) {
+                Self::Addw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000101000000111011,
+            ) {
+                Self::Divuw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000100000000111011,
+            ) {
+                Self::Divw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000000000000111011,
+            ) {
+                Self::Mulw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000111000000111011,
+            ) {
+                Self::Remuw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000110000000111011,
+            ) {
+                Self::Remw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000001000000111011,
+            ) {
+                Self::Sllw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000101000000111011,
+            ) {
+                Self::Sraw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000101000000111011,
+            ) {
+                Self::Srlw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000000000000111011,
+            ) {
+                Self::Subw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Op64 as u8) {
+            if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000000000001111011,
+            ) {
+                Self::Addd {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000100000001111011,
+            ) {
+                Self::Divd {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000101000001111011,
+            ) {
+                Self::Divud {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000000000001111011,
+            ) {
+                Self::Muld {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000110000001111011,
+            ) {
+                Self::Remd {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000111000001111011,
+            ) {
+                Self::Remud {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000001000001111011,
+            ) {
+                Self::Slld {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000101000001111011,
+            ) {
+                Self::Srad {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000101000001111011,
+            ) {
+                Self::Srld {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000000000001111011,
+            ) {
+                Self::Subd {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::OpImm as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000000000000010011,
+            ) {
+                Self::Addi {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000111000000010011,
+            ) {
+                Self::Andi {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000110000000010011,
+            ) {
+                Self::Ori {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else if raw.matches(
+                0b11111000000000000111000001111111,
+                0b00000000000000000001000000010011,
+            ) {
+                Self::Slli {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt7(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000010000000010011,
+            ) {
+                Self::Slti {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000011000000010011,
+            ) {
+                Self::Sltiu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else if raw.matches(
+                0b11111000000000000111000001111111,
+                0b01000000000000000101000000010011,
+            ) {
+                Self::Srai {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt7(),
+                }
+            } else if raw.matches(
+                0b11111000000000000111000001111111,
+                0b00000000000000000101000000010011,
+            ) {
+                Self::Srli {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt7(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000100000000010011,
+            ) {
+                Self::Xori {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::OpImm32 as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000000000000011011,
+            ) {
+                Self::Addiw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000001000000011011,
+            ) {
+                Self::Slliw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt5(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000101000000011011,
+            ) {
+                Self::Sraiw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt5(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000101000000011011,
+            ) {
+                Self::Srliw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt5(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::OpImm64 as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000000000001011011,
+            ) {
+                Self::Addid {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000000000000000001000001011011,
+            ) {
+                Self::Sllid {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt6(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b01000000000000000101000001011011,
+            ) {
+                Self::Sraid {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt6(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000000000000000101000001011011,
+            ) {
+                Self::Srlid {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt6(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Store as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000000000000100011,
+            ) {
+                Self::Sb {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.simm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000011000000100011,
+            ) {
+                Self::Sd {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.simm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000001000000100011,
+            ) {
+                Self::Sh {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.simm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000100000000100011,
+            ) {
+                Self::Sq {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.simm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000010000000100011,
+            ) {
+                Self::Sw {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.simm12(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::System as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000011000001110011,
+            ) {
+                Self::Csrrc {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    csr: raw.csr12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000111000001110011,
+            ) {
+                Self::Csrrci {
+                    rd: raw.rd(),
+                    uimm: raw.zimm(),
+                    csr: raw.csr12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000010000001110011,
+            ) {
+                Self::Csrrs {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    csr: raw.csr12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000110000001110011,
+            ) {
+                Self::Csrrsi {
+                    rd: raw.rd(),
+                    uimm: raw.zimm(),
+                    csr: raw.csr12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000001000001110011,
+            ) {
+                Self::Csrrw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    csr: raw.csr12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000101000001110011,
+            ) {
+                Self::Csrrwi {
+                    rd: raw.rd(),
+                    uimm: raw.zimm(),
+                    csr: raw.csr12(),
+                }
+            } else if raw.matches(
+                0b11111111111111111111111111111111,
+                0b01111011001000000000000001110011,
+            ) {
+                Self::Dret
+            } else if raw.matches(
+                0b11111111111111111111111111111111,
+                0b00000000000100000000000001110011,
+            ) {
+                Self::Ebreak
+            } else if raw.matches(
+                0b11111111111111111111111111111111,
+                0b00000000000000000000000001110011,
+            ) {
+                Self::Ecall
+            } else if raw.matches(
+                0b11111111111111111111111111111111,
+                0b00110000001000000000000001110011,
+            ) {
+                Self::Mret
+            } else if raw.matches(
+                0b11111110000000000111111111111111,
+                0b00010010000000000000000001110011,
+            ) {
+                Self::SfenceVma {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111111111111111111111111111111,
+                0b00010000001000000000000001110011,
+            ) {
+                Self::Sret
+            } else if raw.matches(
+                0b11111111111111111111111111111111,
+                0b00000000001000000000000001110011,
+            ) {
+                Self::Uret
+            } else if raw.matches(
+                0b11111111111111111111111111111111,
+                0b00010000010100000000000001110011,
+            ) {
+                Self::Wfi
+            } else {
+                Self::Invalid
+            }
+        } else {
+            Self::Invalid
+        }
+    }
+}
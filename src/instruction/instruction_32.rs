@@ -1,8 +1,9 @@
+use crate::isa::Extensions;
 use crate::raw_instruction::{Opcode, RawInstruction};
-use crate::register::{FloatRegister, IntRegister};
+use crate::register::{FloatRegister, IntRegister, VectorRegister};
 
 /// Enumeration of all operations from the RV32 ISA.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum OperationRV32 {
     /// The result of decoding an instruction that isn't valid at all,
     /// according to the current decoder implementation.
@@ -72,7 +73,7 @@ pub enum OperationRV32 {
         simm: i32,
     },
     /// Fence (RV32I)
-    Fence { pred: bool, succ: bool },
+    Fence { pred: u32, succ: u32 },
     /// Fence Instruction (RV32I)
     FenceI,
     /// Jump and Link (RV32I)
@@ -1058,1317 +1059,5199 @@ pub enum OperationRV32 {
         rs1rd: IntRegister,
         rs2: IntRegister,
     },
-}
 
-impl OperationRV32 {
-    pub fn decode_from_raw(raw: RawInstruction) -> Self {
-        let opcode = raw.opcode();
-        if raw.matches(0xffffffff, 0xffffffff) || raw.matches(0xffffffff, 0x00000000) {
-            // All-zeros and all-ones are reserved as invalid instructions in
-            // the RISC-V spec, regardless of whether they might match any
-            // patterns below.
-            return Self::Invalid;
-        }
-        if opcode == (Opcode::Amo as u8) {
+    // RV32Zba: Address Generation
+    /// Shift Left by 1 and Add (RV32Zba)
+    Sh1add {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Left by 2 and Add (RV32Zba)
+    Sh2add {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Left by 3 and Add (RV32Zba)
+    Sh3add {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+
+    // RV32Zbb: Basic Bit Manipulation
+    /// And Inverted (RV32Zbb)
+    Andn {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Count Leading Zeros (RV32Zbb)
+    Clz { rd: IntRegister, rs1: IntRegister },
+    /// Count Set Bits (RV32Zbb)
+    Cpop { rd: IntRegister, rs1: IntRegister },
+    /// Count Trailing Zeros (RV32Zbb)
+    Ctz { rd: IntRegister, rs1: IntRegister },
+    /// Maximum (RV32Zbb)
+    Max {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Maximum Unsigned (RV32Zbb)
+    Maxu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Minimum (RV32Zbb)
+    Min {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Minimum Unsigned (RV32Zbb)
+    Minu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Bitwise Or-Combine Byte (RV32Zbb)
+    OrcB { rd: IntRegister, rs1: IntRegister },
+    /// Or Inverted (RV32Zbb)
+    Orn {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Byte Reverse (RV32Zbb)
+    Rev8 { rd: IntRegister, rs1: IntRegister },
+    /// Rotate Left (RV32Zbb)
+    Rol {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Rotate Right (RV32Zbb)
+    Ror {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Rotate Right Immediate (RV32Zbb)
+    Rori {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Sign-Extend Byte (RV32Zbb)
+    SextB { rd: IntRegister, rs1: IntRegister },
+    /// Sign-Extend Halfword (RV32Zbb)
+    SextH { rd: IntRegister, rs1: IntRegister },
+    /// Exclusive Nor (RV32Zbb)
+    Xnor {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Zero-Extend Halfword (RV32Zbb)
+    ZextH { rd: IntRegister, rs1: IntRegister },
+
+    // RV32Zbc: Carry-less Multiplication
+    /// Carry-less Multiply (RV32Zbc)
+    Clmul {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Carry-less Multiply High (RV32Zbc)
+    Clmulh {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Carry-less Multiply Reversed (RV32Zbc)
+    Clmulr {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+
+    // RV32Zbs: Single-bit Instructions
+    /// Single-Bit Clear (RV32Zbs)
+    Bclr {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Single-Bit Clear Immediate (RV32Zbs)
+    Bclri {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Single-Bit Extract (RV32Zbs)
+    Bext {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Single-Bit Extract Immediate (RV32Zbs)
+    Bexti {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Single-Bit Invert (RV32Zbs)
+    Binv {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Single-Bit Invert Immediate (RV32Zbs)
+    Binvi {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Single-Bit Set (RV32Zbs)
+    Bset {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Single-Bit Set Immediate (RV32Zbs)
+    Bseti {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+
+    // RV32V: Vector Operations
+    /// Vector Add, Vector-Immediate (RV32V)
+    VaddVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        simm: i32,
+        vm: bool,
+    },
+    /// Vector Add, Vector-Vector (RV32V)
+    VaddVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Add, Vector-Scalar (RV32V)
+    VaddVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector And, Vector-Immediate (RV32V)
+    VandVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        simm: i32,
+        vm: bool,
+    },
+    /// Vector And, Vector-Vector (RV32V)
+    VandVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector And, Vector-Scalar (RV32V)
+    VandVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Compress (RV32V)
+    VcompressVm {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+    },
+    /// Vector Count Population in Mask (RV32V)
+    VcpopM {
+        rd: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Divide, Vector-Vector (RV32V)
+    VdivVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Divide, Vector-Scalar (RV32V)
+    VdivVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Divide Unsigned, Vector-Vector (RV32V)
+    VdivuVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Divide Unsigned, Vector-Scalar (RV32V)
+    VdivuVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector FP Add, Vector-Scalar (RV32V)
+    VfaddVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Add, Vector-Vector (RV32V)
+    VfaddVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Divide, Vector-Scalar (RV32V)
+    VfdivVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Divide, Vector-Vector (RV32V)
+    VfdivVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Find First Set Mask Bit (RV32V)
+    VfirstM {
+        rd: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Maximum, Vector-Scalar (RV32V)
+    VfmaxVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Maximum, Vector-Vector (RV32V)
+    VfmaxVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Merge (RV32V)
+    VfmergeVfm {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+    },
+    /// Vector FP Minimum, Vector-Scalar (RV32V)
+    VfminVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Minimum, Vector-Vector (RV32V)
+    VfminVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Multiply, Vector-Scalar (RV32V)
+    VfmulVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Multiply, Vector-Vector (RV32V)
+    VfmulVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Move to Float (RV32V)
+    VfmvFS {
+        frd: FloatRegister,
+        vs2: VectorRegister,
+    },
+    /// Vector FP Move from Float (RV32V)
+    VfmvSF {
+        vd: VectorRegister,
+        frs1: FloatRegister,
+    },
+    /// Vector FP Move (RV32V)
+    VfmvVF {
+        vd: VectorRegister,
+        frs1: FloatRegister,
+    },
+    /// Vector FP Reverse Divide, Vector-Scalar (RV32V)
+    VfrdivVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Reduce Maximum (RV32V)
+    VfredmaxVs {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Reduce Minimum (RV32V)
+    VfredminVs {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Reduce Ordered Sum (RV32V)
+    VfredosumVs {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Reduce Unordered Sum (RV32V)
+    VfredusumVs {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Reverse Subtract, Vector-Scalar (RV32V)
+    VfrsubVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Sign-Injection, Vector-Scalar (RV32V)
+    VfsgnjVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Sign-Injection, Vector-Vector (RV32V)
+    VfsgnjVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Sign-Injection Negate, Vector-Scalar (RV32V)
+    VfsgnjnVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Sign-Injection Negate, Vector-Vector (RV32V)
+    VfsgnjnVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Sign-Injection Exclusive Or, Vector-Scalar (RV32V)
+    VfsgnjxVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Sign-Injection Exclusive Or, Vector-Vector (RV32V)
+    VfsgnjxVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Subtract, Vector-Scalar (RV32V)
+    VfsubVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Subtract, Vector-Vector (RV32V)
+    VfsubVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Element Index (RV32V)
+    VidV { vd: VectorRegister, vm: bool },
+    /// Vector Unit-Stride Load (16-bit) (RV32V)
+    Vle16V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Unit-Stride Load (32-bit) (RV32V)
+    Vle32V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Unit-Stride Load (64-bit) (RV32V)
+    Vle64V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Unit-Stride Load (8-bit) (RV32V)
+    Vle8V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Ordered Load (16-bit Indices) (RV32V)
+    Vloxei16V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Ordered Load (32-bit Indices) (RV32V)
+    Vloxei32V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Ordered Load (64-bit Indices) (RV32V)
+    Vloxei64V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Ordered Load (8-bit Indices) (RV32V)
+    Vloxei8V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Strided Load (16-bit) (RV32V)
+    Vlse16V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+        vm: bool,
+    },
+    /// Vector Strided Load (32-bit) (RV32V)
+    Vlse32V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+        vm: bool,
+    },
+    /// Vector Strided Load (64-bit) (RV32V)
+    Vlse64V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+        vm: bool,
+    },
+    /// Vector Strided Load (8-bit) (RV32V)
+    Vlse8V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Unordered Load (16-bit Indices) (RV32V)
+    Vluxei16V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Unordered Load (32-bit Indices) (RV32V)
+    Vluxei32V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Unordered Load (64-bit Indices) (RV32V)
+    Vluxei64V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Unordered Load (8-bit Indices) (RV32V)
+    Vluxei8V {
+        vd: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Multiply-Add, Vector-Vector (RV32V)
+    VmaccVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Multiply-Add, Vector-Scalar (RV32V)
+    VmaccVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Mask And (RV32V)
+    VmandMm {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+    },
+    /// Vector Mask And Not (RV32V)
+    VmandnMm {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+    },
+    /// Vector Maximum, Vector-Vector (RV32V)
+    VmaxVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Maximum, Vector-Scalar (RV32V)
+    VmaxVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Maximum Unsigned, Vector-Vector (RV32V)
+    VmaxuVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Maximum Unsigned, Vector-Scalar (RV32V)
+    VmaxuVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Merge, Vector-Immediate (RV32V)
+    VmergeVim {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        simm: i32,
+    },
+    /// Vector Merge, Vector-Vector (RV32V)
+    VmergeVvm {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+    },
+    /// Vector Merge, Vector-Scalar (RV32V)
+    VmergeVxm {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+    },
+    /// Vector FP Set Mask If Equal, Vector-Scalar (RV32V)
+    VmfeqVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Set Mask If Equal, Vector-Vector (RV32V)
+    VmfeqVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Set Mask If Greater Than or Equal, Vector-Scalar (RV32V)
+    VmfgeVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Set Mask If Greater Than, Vector-Scalar (RV32V)
+    VmfgtVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Set Mask If Less Than or Equal, Vector-Scalar (RV32V)
+    VmfleVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Set Mask If Less Than or Equal, Vector-Vector (RV32V)
+    VmfleVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Set Mask If Less Than, Vector-Scalar (RV32V)
+    VmfltVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Set Mask If Less Than, Vector-Vector (RV32V)
+    VmfltVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector FP Set Mask If Not Equal, Vector-Scalar (RV32V)
+    VmfneVf {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        frs1: FloatRegister,
+        vm: bool,
+    },
+    /// Vector FP Set Mask If Not Equal, Vector-Vector (RV32V)
+    VmfneVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Minimum, Vector-Vector (RV32V)
+    VminVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Minimum, Vector-Scalar (RV32V)
+    VminVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Minimum Unsigned, Vector-Vector (RV32V)
+    VminuVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Minimum Unsigned, Vector-Scalar (RV32V)
+    VminuVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Mask Not And (RV32V)
+    VmnandMm {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+    },
+    /// Vector Mask Not Or (RV32V)
+    VmnorMm {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+    },
+    /// Vector Mask Or (RV32V)
+    VmorMm {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+    },
+    /// Vector Mask Or Not (RV32V)
+    VmornMm {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+    },
+    /// Vector Set Mask If Equal, Vector-Immediate (RV32V)
+    VmseqVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        simm: i32,
+        vm: bool,
+    },
+    /// Vector Set Mask If Equal, Vector-Vector (RV32V)
+    VmseqVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Set Mask If Equal, Vector-Scalar (RV32V)
+    VmseqVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Set Mask If Greater Than, Vector-Immediate (RV32V)
+    VmsgtVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        simm: i32,
+        vm: bool,
+    },
+    /// Vector Set Mask If Greater Than, Vector-Scalar (RV32V)
+    VmsgtVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Set Mask If Greater Than Unsigned, Vector-Immediate (RV32V)
+    VmsgtuVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        simm: i32,
+        vm: bool,
+    },
+    /// Vector Set Mask If Greater Than Unsigned, Vector-Scalar (RV32V)
+    VmsgtuVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Set Mask If Less Than or Equal, Vector-Immediate (RV32V)
+    VmsleVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        simm: i32,
+        vm: bool,
+    },
+    /// Vector Set Mask If Less Than or Equal, Vector-Vector (RV32V)
+    VmsleVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Set Mask If Less Than or Equal, Vector-Scalar (RV32V)
+    VmsleVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Set Mask If Less Than or Equal Unsigned, Vector-Immediate (RV32V)
+    VmsleuVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        simm: i32,
+        vm: bool,
+    },
+    /// Vector Set Mask If Less Than or Equal Unsigned, Vector-Vector (RV32V)
+    VmsleuVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Set Mask If Less Than or Equal Unsigned, Vector-Scalar (RV32V)
+    VmsleuVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Set Mask If Less Than, Vector-Vector (RV32V)
+    VmsltVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Set Mask If Less Than, Vector-Scalar (RV32V)
+    VmsltVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Set Mask If Less Than Unsigned, Vector-Vector (RV32V)
+    VmsltuVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Set Mask If Less Than Unsigned, Vector-Scalar (RV32V)
+    VmsltuVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Set Mask If Not Equal, Vector-Immediate (RV32V)
+    VmsneVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        simm: i32,
+        vm: bool,
+    },
+    /// Vector Set Mask If Not Equal, Vector-Vector (RV32V)
+    VmsneVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Set Mask If Not Equal, Vector-Scalar (RV32V)
+    VmsneVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Multiply, Vector-Vector (RV32V)
+    VmulVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Multiply, Vector-Scalar (RV32V)
+    VmulVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Multiply High, Vector-Vector (RV32V)
+    VmulhVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Multiply High, Vector-Scalar (RV32V)
+    VmulhVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Multiply High Signed Unsigned, Vector-Vector (RV32V)
+    VmulhsuVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Multiply High Signed Unsigned, Vector-Scalar (RV32V)
+    VmulhsuVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Multiply High Unsigned, Vector-Vector (RV32V)
+    VmulhuVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Multiply High Unsigned, Vector-Scalar (RV32V)
+    VmulhuVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Move from Integer (RV32V)
+    VmvSX {
+        vd: VectorRegister,
+        rs1: IntRegister,
+    },
+    /// Vector Move, Immediate (RV32V)
+    VmvVI { vd: VectorRegister, simm: i32 },
+    /// Vector Move, Vector (RV32V)
+    VmvVV {
+        vd: VectorRegister,
+        vs1: VectorRegister,
+    },
+    /// Vector Move, Scalar (RV32V)
+    VmvVX {
+        vd: VectorRegister,
+        rs1: IntRegister,
+    },
+    /// Vector Move to Integer (RV32V)
+    VmvXS {
+        rd: IntRegister,
+        vs2: VectorRegister,
+    },
+    /// Vector Mask Exclusive Nor (RV32V)
+    VmxnorMm {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+    },
+    /// Vector Mask Exclusive Or (RV32V)
+    VmxorMm {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+    },
+    /// Vector Or, Vector-Immediate (RV32V)
+    VorVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        simm: i32,
+        vm: bool,
+    },
+    /// Vector Or, Vector-Vector (RV32V)
+    VorVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Or, Vector-Scalar (RV32V)
+    VorVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Reduce And (RV32V)
+    VredandVs {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Reduce Maximum (RV32V)
+    VredmaxVs {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Reduce Maximum Unsigned (RV32V)
+    VredmaxuVs {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Reduce Minimum (RV32V)
+    VredminVs {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Reduce Minimum Unsigned (RV32V)
+    VredminuVs {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Reduce Or (RV32V)
+    VredorVs {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Reduce Sum (RV32V)
+    VredsumVs {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Reduce Exclusive Or (RV32V)
+    VredxorVs {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Remainder, Vector-Vector (RV32V)
+    VremVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Remainder, Vector-Scalar (RV32V)
+    VremVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Remainder Unsigned, Vector-Vector (RV32V)
+    VremuVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Remainder Unsigned, Vector-Scalar (RV32V)
+    VremuVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Register Gather, Vector-Immediate (RV32V)
+    VrgatherVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        uimm: u32,
+        vm: bool,
+    },
+    /// Vector Register Gather, Vector-Vector (RV32V)
+    VrgatherVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Register Gather, Vector-Scalar (RV32V)
+    VrgatherVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Reverse Subtract, Vector-Immediate (RV32V)
+    VrsubVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        simm: i32,
+        vm: bool,
+    },
+    /// Vector Reverse Subtract, Vector-Scalar (RV32V)
+    VrsubVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Unit-Stride Store (16-bit) (RV32V)
+    Vse16V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Unit-Stride Store (32-bit) (RV32V)
+    Vse32V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Unit-Stride Store (64-bit) (RV32V)
+    Vse64V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Unit-Stride Store (8-bit) (RV32V)
+    Vse8V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Set Length Immediate AVL (RV32V)
+    Vsetivli {
+        rd: IntRegister,
+        uimm: u32,
+        vtypei: u32,
+    },
+    /// Vector Set Length (RV32V)
+    Vsetvl {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Vector Set Length Immediate (RV32V)
+    Vsetvli {
+        rd: IntRegister,
+        rs1: IntRegister,
+        vtypei: u32,
+    },
+    /// Vector Slide Down by One (RV32V)
+    Vslide1downVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Slide Up by One (RV32V)
+    Vslide1upVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Slide Down, Vector-Immediate (RV32V)
+    VslidedownVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        uimm: u32,
+        vm: bool,
+    },
+    /// Vector Slide Down, Vector-Scalar (RV32V)
+    VslidedownVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Slide Up, Vector-Immediate (RV32V)
+    VslideupVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        uimm: u32,
+        vm: bool,
+    },
+    /// Vector Slide Up, Vector-Scalar (RV32V)
+    VslideupVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Shift Left Logical, Vector-Immediate (RV32V)
+    VsllVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        uimm: u32,
+        vm: bool,
+    },
+    /// Vector Shift Left Logical, Vector-Vector (RV32V)
+    VsllVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Shift Left Logical, Vector-Scalar (RV32V)
+    VsllVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Ordered Store (16-bit Indices) (RV32V)
+    Vsoxei16V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Ordered Store (32-bit Indices) (RV32V)
+    Vsoxei32V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Ordered Store (64-bit Indices) (RV32V)
+    Vsoxei64V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Ordered Store (8-bit Indices) (RV32V)
+    Vsoxei8V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Shift Right Arithmetic, Vector-Immediate (RV32V)
+    VsraVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        uimm: u32,
+        vm: bool,
+    },
+    /// Vector Shift Right Arithmetic, Vector-Vector (RV32V)
+    VsraVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Shift Right Arithmetic, Vector-Scalar (RV32V)
+    VsraVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Shift Right Logical, Vector-Immediate (RV32V)
+    VsrlVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        uimm: u32,
+        vm: bool,
+    },
+    /// Vector Shift Right Logical, Vector-Vector (RV32V)
+    VsrlVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Shift Right Logical, Vector-Scalar (RV32V)
+    VsrlVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Strided Store (16-bit) (RV32V)
+    Vsse16V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+        vm: bool,
+    },
+    /// Vector Strided Store (32-bit) (RV32V)
+    Vsse32V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+        vm: bool,
+    },
+    /// Vector Strided Store (64-bit) (RV32V)
+    Vsse64V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+        vm: bool,
+    },
+    /// Vector Strided Store (8-bit) (RV32V)
+    Vsse8V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+        vm: bool,
+    },
+    /// Vector Subtract, Vector-Vector (RV32V)
+    VsubVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Subtract, Vector-Scalar (RV32V)
+    VsubVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Unordered Store (16-bit Indices) (RV32V)
+    Vsuxei16V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Unordered Store (32-bit Indices) (RV32V)
+    Vsuxei32V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Unordered Store (64-bit Indices) (RV32V)
+    Vsuxei64V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Indexed Unordered Store (8-bit Indices) (RV32V)
+    Vsuxei8V {
+        vs3: VectorRegister,
+        rs1: IntRegister,
+        vs2: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Exclusive Or, Vector-Immediate (RV32V)
+    VxorVi {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        simm: i32,
+        vm: bool,
+    },
+    /// Vector Exclusive Or, Vector-Vector (RV32V)
+    VxorVv {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        vs1: VectorRegister,
+        vm: bool,
+    },
+    /// Vector Exclusive Or, Vector-Scalar (RV32V)
+    VxorVx {
+        vd: VectorRegister,
+        vs2: VectorRegister,
+        rs1: IntRegister,
+        vm: bool,
+    },
+}
+
+impl OperationRV32 {
+    /// Returns the extensions that a hart must implement for this operation
+    /// to be valid. Operations from the base integer ISA don't require any.
+    pub fn required_extensions(&self) -> Extensions {
+        match self {
+            Self::Csrrc { .. }
+            | Self::Csrrci { .. }
+            | Self::Csrrs { .. }
+            | Self::Csrrsi { .. }
+            | Self::Csrrw { .. }
+            | Self::Csrrwi { .. } => Extensions::ZICSR,
+            Self::FenceI => Extensions::ZIFENCEI,
+            Self::Div { .. }
+            | Self::Divu { .. }
+            | Self::Mul { .. }
+            | Self::Mulh { .. }
+            | Self::Mulhsu { .. }
+            | Self::Mulhu { .. }
+            | Self::Rem { .. }
+            | Self::Remu { .. } => Extensions::M,
+            Self::AmoaddW { .. }
+            | Self::AmoandW { .. }
+            | Self::AmomaxW { .. }
+            | Self::AmomaxuW { .. }
+            | Self::AmominW { .. }
+            | Self::AmominuW { .. }
+            | Self::AmoorW { .. }
+            | Self::AmoswapW { .. }
+            | Self::AmoxorW { .. }
+            | Self::LrW { .. }
+            | Self::ScW { .. } => Extensions::A,
+            Self::FaddS { .. }
+            | Self::FclassS { .. }
+            | Self::FcvtSW { .. }
+            | Self::FcvtSWu { .. }
+            | Self::FcvtWS { .. }
+            | Self::FcvtWuS { .. }
+            | Self::FdivS { .. }
+            | Self::FeqS { .. }
+            | Self::FleS { .. }
+            | Self::FltS { .. }
+            | Self::Flw { .. }
+            | Self::FmaddS { .. }
+            | Self::FmaxS { .. }
+            | Self::FminS { .. }
+            | Self::FmsubS { .. }
+            | Self::FmulS { .. }
+            | Self::FmvSX { .. }
+            | Self::FmvXS { .. }
+            | Self::FnmaddS { .. }
+            | Self::FnmsubS { .. }
+            | Self::FsgnjS { .. }
+            | Self::FsgnjnS { .. }
+            | Self::FsgnjxS { .. }
+            | Self::FsqrtS { .. }
+            | Self::FsubS { .. }
+            | Self::Fsw { .. } => Extensions::F,
+            Self::FaddD { .. }
+            | Self::FclassD { .. }
+            | Self::FcvtDS { .. }
+            | Self::FcvtDW { .. }
+            | Self::FcvtDWu { .. }
+            | Self::FcvtSD { .. }
+            | Self::FcvtWD { .. }
+            | Self::FcvtWuD { .. }
+            | Self::FdivD { .. }
+            | Self::FeqD { .. }
+            | Self::Fld { .. }
+            | Self::FleD { .. }
+            | Self::FltD { .. }
+            | Self::FmaddD { .. }
+            | Self::FmaxD { .. }
+            | Self::FminD { .. }
+            | Self::FmsubD { .. }
+            | Self::FmulD { .. }
+            | Self::FnmaddD { .. }
+            | Self::FnmsubD { .. }
+            | Self::Fsd { .. }
+            | Self::FsgnjD { .. }
+            | Self::FsgnjnD { .. }
+            | Self::FsgnjxD { .. }
+            | Self::FsqrtD { .. }
+            | Self::FsubD { .. } => Extensions::D,
+            Self::FaddQ { .. }
+            | Self::FclassQ { .. }
+            | Self::FcvtDQ { .. }
+            | Self::FcvtQD { .. }
+            | Self::FcvtQS { .. }
+            | Self::FcvtQW { .. }
+            | Self::FcvtQWu { .. }
+            | Self::FcvtSQ { .. }
+            | Self::FcvtWQ { .. }
+            | Self::FcvtWuQ { .. }
+            | Self::FdivQ { .. }
+            | Self::FeqQ { .. }
+            | Self::FleQ { .. }
+            | Self::Flq { .. }
+            | Self::FltQ { .. }
+            | Self::FmaddQ { .. }
+            | Self::FmaxQ { .. }
+            | Self::FminQ { .. }
+            | Self::FmsubQ { .. }
+            | Self::FmulQ { .. }
+            | Self::FnmaddQ { .. }
+            | Self::FnmsubQ { .. }
+            | Self::FsgnjQ { .. }
+            | Self::FsgnjnQ { .. }
+            | Self::FsgnjxQ { .. }
+            | Self::Fsq { .. }
+            | Self::FsqrtQ { .. }
+            | Self::FsubQ { .. } => Extensions::Q,
+            Self::CFld { .. } | Self::CFldsp { .. } | Self::CFsd { .. } | Self::CFsdsp { .. } => {
+                Extensions::C.union(Extensions::D)
+            }
+            Self::CFlw { .. } | Self::CFlwsp { .. } | Self::CFsw { .. } | Self::CFswsp { .. } => {
+                Extensions::C.union(Extensions::F)
+            }
+            Self::CAdd { .. }
+            | Self::CAddi { .. }
+            | Self::CAddi16Sp { .. }
+            | Self::CAddi4Spn { .. }
+            | Self::CAddw { .. }
+            | Self::CAnd { .. }
+            | Self::CAndi { .. }
+            | Self::CBeqz { .. }
+            | Self::CBnez { .. }
+            | Self::CEbreak
+            | Self::CJ { .. }
+            | Self::CJal { .. }
+            | Self::CJalr { .. }
+            | Self::CJr { .. }
+            | Self::CLi { .. }
+            | Self::CLui { .. }
+            | Self::CLw { .. }
+            | Self::CLwsp { .. }
+            | Self::CMv { .. }
+            | Self::CNop
+            | Self::COr { .. }
+            | Self::CSlli { .. }
+            | Self::CSrai { .. }
+            | Self::CSrli { .. }
+            | Self::CSub { .. }
+            | Self::CSubw { .. }
+            | Self::CSw { .. }
+            | Self::CSwsp { .. }
+            | Self::CXor { .. } => Extensions::C,
+            Self::Sh1add { .. } | Self::Sh2add { .. } | Self::Sh3add { .. } => Extensions::ZBA,
+            Self::Andn { .. }
+            | Self::Clz { .. }
+            | Self::Cpop { .. }
+            | Self::Ctz { .. }
+            | Self::Max { .. }
+            | Self::Maxu { .. }
+            | Self::Min { .. }
+            | Self::Minu { .. }
+            | Self::OrcB { .. }
+            | Self::Orn { .. }
+            | Self::Rev8 { .. }
+            | Self::Rol { .. }
+            | Self::Ror { .. }
+            | Self::Rori { .. }
+            | Self::SextB { .. }
+            | Self::SextH { .. }
+            | Self::Xnor { .. }
+            | Self::ZextH { .. } => Extensions::ZBB,
+            Self::Clmul { .. } | Self::Clmulh { .. } | Self::Clmulr { .. } => Extensions::ZBC,
+            Self::Bclr { .. }
+            | Self::Bclri { .. }
+            | Self::Bext { .. }
+            | Self::Bexti { .. }
+            | Self::Binv { .. }
+            | Self::Binvi { .. }
+            | Self::Bset { .. }
+            | Self::Bseti { .. } => Extensions::ZBS,
+            Self::VaddVi { .. }
+            | Self::VaddVv { .. }
+            | Self::VaddVx { .. }
+            | Self::VandVi { .. }
+            | Self::VandVv { .. }
+            | Self::VandVx { .. }
+            | Self::VcompressVm { .. }
+            | Self::VcpopM { .. }
+            | Self::VdivVv { .. }
+            | Self::VdivVx { .. }
+            | Self::VdivuVv { .. }
+            | Self::VdivuVx { .. }
+            | Self::VfaddVf { .. }
+            | Self::VfaddVv { .. }
+            | Self::VfdivVf { .. }
+            | Self::VfdivVv { .. }
+            | Self::VfirstM { .. }
+            | Self::VfmaxVf { .. }
+            | Self::VfmaxVv { .. }
+            | Self::VfmergeVfm { .. }
+            | Self::VfminVf { .. }
+            | Self::VfminVv { .. }
+            | Self::VfmulVf { .. }
+            | Self::VfmulVv { .. }
+            | Self::VfmvFS { .. }
+            | Self::VfmvSF { .. }
+            | Self::VfmvVF { .. }
+            | Self::VfrdivVf { .. }
+            | Self::VfredmaxVs { .. }
+            | Self::VfredminVs { .. }
+            | Self::VfredosumVs { .. }
+            | Self::VfredusumVs { .. }
+            | Self::VfrsubVf { .. }
+            | Self::VfsgnjVf { .. }
+            | Self::VfsgnjVv { .. }
+            | Self::VfsgnjnVf { .. }
+            | Self::VfsgnjnVv { .. }
+            | Self::VfsgnjxVf { .. }
+            | Self::VfsgnjxVv { .. }
+            | Self::VfsubVf { .. }
+            | Self::VfsubVv { .. }
+            | Self::VidV { .. }
+            | Self::Vle16V { .. }
+            | Self::Vle32V { .. }
+            | Self::Vle64V { .. }
+            | Self::Vle8V { .. }
+            | Self::Vloxei16V { .. }
+            | Self::Vloxei32V { .. }
+            | Self::Vloxei64V { .. }
+            | Self::Vloxei8V { .. }
+            | Self::Vlse16V { .. }
+            | Self::Vlse32V { .. }
+            | Self::Vlse64V { .. }
+            | Self::Vlse8V { .. }
+            | Self::Vluxei16V { .. }
+            | Self::Vluxei32V { .. }
+            | Self::Vluxei64V { .. }
+            | Self::Vluxei8V { .. }
+            | Self::VmaccVv { .. }
+            | Self::VmaccVx { .. }
+            | Self::VmandMm { .. }
+            | Self::VmandnMm { .. }
+            | Self::VmaxVv { .. }
+            | Self::VmaxVx { .. }
+            | Self::VmaxuVv { .. }
+            | Self::VmaxuVx { .. }
+            | Self::VmergeVim { .. }
+            | Self::VmergeVvm { .. }
+            | Self::VmergeVxm { .. }
+            | Self::VmfeqVf { .. }
+            | Self::VmfeqVv { .. }
+            | Self::VmfgeVf { .. }
+            | Self::VmfgtVf { .. }
+            | Self::VmfleVf { .. }
+            | Self::VmfleVv { .. }
+            | Self::VmfltVf { .. }
+            | Self::VmfltVv { .. }
+            | Self::VmfneVf { .. }
+            | Self::VmfneVv { .. }
+            | Self::VminVv { .. }
+            | Self::VminVx { .. }
+            | Self::VminuVv { .. }
+            | Self::VminuVx { .. }
+            | Self::VmnandMm { .. }
+            | Self::VmnorMm { .. }
+            | Self::VmorMm { .. }
+            | Self::VmornMm { .. }
+            | Self::VmseqVi { .. }
+            | Self::VmseqVv { .. }
+            | Self::VmseqVx { .. }
+            | Self::VmsgtVi { .. }
+            | Self::VmsgtVx { .. }
+            | Self::VmsgtuVi { .. }
+            | Self::VmsgtuVx { .. }
+            | Self::VmsleVi { .. }
+            | Self::VmsleVv { .. }
+            | Self::VmsleVx { .. }
+            | Self::VmsleuVi { .. }
+            | Self::VmsleuVv { .. }
+            | Self::VmsleuVx { .. }
+            | Self::VmsltVv { .. }
+            | Self::VmsltVx { .. }
+            | Self::VmsltuVv { .. }
+            | Self::VmsltuVx { .. }
+            | Self::VmsneVi { .. }
+            | Self::VmsneVv { .. }
+            | Self::VmsneVx { .. }
+            | Self::VmulVv { .. }
+            | Self::VmulVx { .. }
+            | Self::VmulhVv { .. }
+            | Self::VmulhVx { .. }
+            | Self::VmulhsuVv { .. }
+            | Self::VmulhsuVx { .. }
+            | Self::VmulhuVv { .. }
+            | Self::VmulhuVx { .. }
+            | Self::VmvSX { .. }
+            | Self::VmvVI { .. }
+            | Self::VmvVV { .. }
+            | Self::VmvVX { .. }
+            | Self::VmvXS { .. }
+            | Self::VmxnorMm { .. }
+            | Self::VmxorMm { .. }
+            | Self::VorVi { .. }
+            | Self::VorVv { .. }
+            | Self::VorVx { .. }
+            | Self::VredandVs { .. }
+            | Self::VredmaxVs { .. }
+            | Self::VredmaxuVs { .. }
+            | Self::VredminVs { .. }
+            | Self::VredminuVs { .. }
+            | Self::VredorVs { .. }
+            | Self::VredsumVs { .. }
+            | Self::VredxorVs { .. }
+            | Self::VremVv { .. }
+            | Self::VremVx { .. }
+            | Self::VremuVv { .. }
+            | Self::VremuVx { .. }
+            | Self::VrgatherVi { .. }
+            | Self::VrgatherVv { .. }
+            | Self::VrgatherVx { .. }
+            | Self::VrsubVi { .. }
+            | Self::VrsubVx { .. }
+            | Self::Vse16V { .. }
+            | Self::Vse32V { .. }
+            | Self::Vse64V { .. }
+            | Self::Vse8V { .. }
+            | Self::Vsetivli { .. }
+            | Self::Vsetvl { .. }
+            | Self::Vsetvli { .. }
+            | Self::Vslide1downVx { .. }
+            | Self::Vslide1upVx { .. }
+            | Self::VslidedownVi { .. }
+            | Self::VslidedownVx { .. }
+            | Self::VslideupVi { .. }
+            | Self::VslideupVx { .. }
+            | Self::VsllVi { .. }
+            | Self::VsllVv { .. }
+            | Self::VsllVx { .. }
+            | Self::Vsoxei16V { .. }
+            | Self::Vsoxei32V { .. }
+            | Self::Vsoxei64V { .. }
+            | Self::Vsoxei8V { .. }
+            | Self::VsraVi { .. }
+            | Self::VsraVv { .. }
+            | Self::VsraVx { .. }
+            | Self::VsrlVi { .. }
+            | Self::VsrlVv { .. }
+            | Self::VsrlVx { .. }
+            | Self::Vsse16V { .. }
+            | Self::Vsse32V { .. }
+            | Self::Vsse64V { .. }
+            | Self::Vsse8V { .. }
+            | Self::VsubVv { .. }
+            | Self::VsubVx { .. }
+            | Self::Vsuxei16V { .. }
+            | Self::Vsuxei32V { .. }
+            | Self::Vsuxei64V { .. }
+            | Self::Vsuxei8V { .. }
+            | Self::VxorVi { .. }
+            | Self::VxorVv { .. }
+            | Self::VxorVx { .. } => Extensions::V,
+            _ => Extensions::NONE,
+        }
+    }
+
+    /// Returns the integer registers that this operation names as its
+    /// operands or destination. Registers that an operation uses implicitly,
+    /// such as the stack pointer in the compressed stack-relative loads, are
+    /// not included.
+    pub fn int_registers(&self) -> [Option<IntRegister>; 3] {
+        match *self {
+            Self::Add { rd, rs1, rs2 }
+            | Self::And { rd, rs1, rs2 }
+            | Self::Or { rd, rs1, rs2 }
+            | Self::Sll { rd, rs1, rs2 }
+            | Self::Slt { rd, rs1, rs2 }
+            | Self::Sltu { rd, rs1, rs2 }
+            | Self::Sra { rd, rs1, rs2 }
+            | Self::Srl { rd, rs1, rs2 }
+            | Self::Sub { rd, rs1, rs2 }
+            | Self::Xor { rd, rs1, rs2 }
+            | Self::Div { rd, rs1, rs2 }
+            | Self::Divu { rd, rs1, rs2 }
+            | Self::Mul { rd, rs1, rs2 }
+            | Self::Mulh { rd, rs1, rs2 }
+            | Self::Mulhsu { rd, rs1, rs2 }
+            | Self::Mulhu { rd, rs1, rs2 }
+            | Self::Rem { rd, rs1, rs2 }
+            | Self::Remu { rd, rs1, rs2 }
+            | Self::AmoaddW { rd, rs1, rs2, .. }
+            | Self::AmoandW { rd, rs1, rs2, .. }
+            | Self::AmomaxW { rd, rs1, rs2, .. }
+            | Self::AmomaxuW { rd, rs1, rs2, .. }
+            | Self::AmominW { rd, rs1, rs2, .. }
+            | Self::AmominuW { rd, rs1, rs2, .. }
+            | Self::AmoorW { rd, rs1, rs2, .. }
+            | Self::AmoswapW { rd, rs1, rs2, .. }
+            | Self::AmoxorW { rd, rs1, rs2, .. }
+            | Self::ScW { rd, rs1, rs2, .. }
+            | Self::Sh1add { rd, rs1, rs2 }
+            | Self::Sh2add { rd, rs1, rs2 }
+            | Self::Sh3add { rd, rs1, rs2 }
+            | Self::Andn { rd, rs1, rs2 }
+            | Self::Max { rd, rs1, rs2 }
+            | Self::Maxu { rd, rs1, rs2 }
+            | Self::Min { rd, rs1, rs2 }
+            | Self::Minu { rd, rs1, rs2 }
+            | Self::Orn { rd, rs1, rs2 }
+            | Self::Rol { rd, rs1, rs2 }
+            | Self::Ror { rd, rs1, rs2 }
+            | Self::Xnor { rd, rs1, rs2 }
+            | Self::Clmul { rd, rs1, rs2 }
+            | Self::Clmulh { rd, rs1, rs2 }
+            | Self::Clmulr { rd, rs1, rs2 }
+            | Self::Bclr { rd, rs1, rs2 }
+            | Self::Bext { rd, rs1, rs2 }
+            | Self::Binv { rd, rs1, rs2 }
+            | Self::Bset { rd, rs1, rs2 }
+            | Self::Vsetvl { rd, rs1, rs2 } => [Some(rd), Some(rs1), Some(rs2)],
+            Self::Addi { rd, rs1, .. }
+            | Self::Andi { rd, rs1, .. }
+            | Self::Jalr { rd, rs1, .. }
+            | Self::Lb { rd, rs1, .. }
+            | Self::Lbu { rd, rs1, .. }
+            | Self::Lh { rd, rs1, .. }
+            | Self::Lhu { rd, rs1, .. }
+            | Self::Lw { rd, rs1, .. }
+            | Self::Ori { rd, rs1, .. }
+            | Self::Slli { rd, rs1, .. }
+            | Self::Slti { rd, rs1, .. }
+            | Self::Sltiu { rd, rs1, .. }
+            | Self::Srai { rd, rs1, .. }
+            | Self::Srli { rd, rs1, .. }
+            | Self::Xori { rd, rs1, .. }
+            | Self::LrW { rd, rs1, .. }
+            | Self::Csrrc { rd, rs1, .. }
+            | Self::Csrrs { rd, rs1, .. }
+            | Self::Csrrw { rd, rs1, .. }
+            | Self::CJalr { rd, rs1 }
+            | Self::CJr { rd, rs1 }
+            | Self::CLw { rd, rs1, .. }
+            | Self::Clz { rd, rs1 }
+            | Self::Cpop { rd, rs1 }
+            | Self::Ctz { rd, rs1 }
+            | Self::OrcB { rd, rs1 }
+            | Self::Rev8 { rd, rs1 }
+            | Self::Rori { rd, rs1, .. }
+            | Self::SextB { rd, rs1 }
+            | Self::SextH { rd, rs1 }
+            | Self::ZextH { rd, rs1 }
+            | Self::Bclri { rd, rs1, .. }
+            | Self::Bexti { rd, rs1, .. }
+            | Self::Binvi { rd, rs1, .. }
+            | Self::Bseti { rd, rs1, .. }
+            | Self::Vsetvli { rd, rs1, .. } => [Some(rd), Some(rs1), None],
+            Self::Auipc { rd, .. }
+            | Self::Jal { rd, .. }
+            | Self::Lui { rd, .. }
+            | Self::Csrrci { rd, .. }
+            | Self::Csrrsi { rd, .. }
+            | Self::Csrrwi { rd, .. }
+            | Self::FclassS { rd, .. }
+            | Self::FcvtWS { rd, .. }
+            | Self::FcvtWuS { rd, .. }
+            | Self::FeqS { rd, .. }
+            | Self::FleS { rd, .. }
+            | Self::FltS { rd, .. }
+            | Self::FmvXS { rd, .. }
+            | Self::FclassD { rd, .. }
+            | Self::FcvtWD { rd, .. }
+            | Self::FcvtWuD { rd, .. }
+            | Self::FeqD { rd, .. }
+            | Self::FleD { rd, .. }
+            | Self::FltD { rd, .. }
+            | Self::FclassQ { rd, .. }
+            | Self::FcvtWQ { rd, .. }
+            | Self::FcvtWuQ { rd, .. }
+            | Self::FeqQ { rd, .. }
+            | Self::FleQ { rd, .. }
+            | Self::FltQ { rd, .. }
+            | Self::CAddi4Spn { rd, .. }
+            | Self::CLui { rd, .. }
+            | Self::CLwsp { rd, .. }
+            | Self::VcpopM { rd, .. }
+            | Self::VfirstM { rd, .. }
+            | Self::VmvXS { rd, .. }
+            | Self::Vsetivli { rd, .. } => [Some(rd), None, None],
+            Self::Beq { rs1, rs2, .. }
+            | Self::Bge { rs1, rs2, .. }
+            | Self::Bgeu { rs1, rs2, .. }
+            | Self::Blt { rs1, rs2, .. }
+            | Self::Bltu { rs1, rs2, .. }
+            | Self::Bne { rs1, rs2, .. }
+            | Self::Sb { rs1, rs2, .. }
+            | Self::Sh { rs1, rs2, .. }
+            | Self::Sw { rs1, rs2, .. }
+            | Self::SfenceVma { rs1, rs2 }
+            | Self::CSw { rs1, rs2, .. }
+            | Self::Vlse16V { rs1, rs2, .. }
+            | Self::Vlse32V { rs1, rs2, .. }
+            | Self::Vlse64V { rs1, rs2, .. }
+            | Self::Vlse8V { rs1, rs2, .. }
+            | Self::Vsse16V { rs1, rs2, .. }
+            | Self::Vsse32V { rs1, rs2, .. }
+            | Self::Vsse64V { rs1, rs2, .. }
+            | Self::Vsse8V { rs1, rs2, .. } => [Some(rs1), Some(rs2), None],
+            Self::SfenceVm { rs1 }
+            | Self::FcvtSW { rs1, .. }
+            | Self::FcvtSWu { rs1, .. }
+            | Self::Flw { rs1, .. }
+            | Self::FmvSX { rs1, .. }
+            | Self::Fsw { rs1, .. }
+            | Self::FcvtDW { rs1, .. }
+            | Self::FcvtDWu { rs1, .. }
+            | Self::Fld { rs1, .. }
+            | Self::Fsd { rs1, .. }
+            | Self::FcvtQW { rs1, .. }
+            | Self::FcvtQWu { rs1, .. }
+            | Self::Flq { rs1, .. }
+            | Self::Fsq { rs1, .. }
+            | Self::CBeqz { rs1, .. }
+            | Self::CBnez { rs1, .. }
+            | Self::CFld { rs1, .. }
+            | Self::CFlw { rs1, .. }
+            | Self::CFsd { rs1, .. }
+            | Self::CFsw { rs1, .. }
+            | Self::VaddVx { rs1, .. }
+            | Self::VandVx { rs1, .. }
+            | Self::VdivVx { rs1, .. }
+            | Self::VdivuVx { rs1, .. }
+            | Self::Vle16V { rs1, .. }
+            | Self::Vle32V { rs1, .. }
+            | Self::Vle64V { rs1, .. }
+            | Self::Vle8V { rs1, .. }
+            | Self::Vloxei16V { rs1, .. }
+            | Self::Vloxei32V { rs1, .. }
+            | Self::Vloxei64V { rs1, .. }
+            | Self::Vloxei8V { rs1, .. }
+            | Self::Vluxei16V { rs1, .. }
+            | Self::Vluxei32V { rs1, .. }
+            | Self::Vluxei64V { rs1, .. }
+            | Self::Vluxei8V { rs1, .. }
+            | Self::VmaccVx { rs1, .. }
+            | Self::VmaxVx { rs1, .. }
+            | Self::VmaxuVx { rs1, .. }
+            | Self::VmergeVxm { rs1, .. }
+            | Self::VminVx { rs1, .. }
+            | Self::VminuVx { rs1, .. }
+            | Self::VmseqVx { rs1, .. }
+            | Self::VmsgtVx { rs1, .. }
+            | Self::VmsgtuVx { rs1, .. }
+            | Self::VmsleVx { rs1, .. }
+            | Self::VmsleuVx { rs1, .. }
+            | Self::VmsltVx { rs1, .. }
+            | Self::VmsltuVx { rs1, .. }
+            | Self::VmsneVx { rs1, .. }
+            | Self::VmulVx { rs1, .. }
+            | Self::VmulhVx { rs1, .. }
+            | Self::VmulhsuVx { rs1, .. }
+            | Self::VmulhuVx { rs1, .. }
+            | Self::VmvSX { rs1, .. }
+            | Self::VmvVX { rs1, .. }
+            | Self::VorVx { rs1, .. }
+            | Self::VremVx { rs1, .. }
+            | Self::VremuVx { rs1, .. }
+            | Self::VrgatherVx { rs1, .. }
+            | Self::VrsubVx { rs1, .. }
+            | Self::Vse16V { rs1, .. }
+            | Self::Vse32V { rs1, .. }
+            | Self::Vse64V { rs1, .. }
+            | Self::Vse8V { rs1, .. }
+            | Self::Vslide1downVx { rs1, .. }
+            | Self::Vslide1upVx { rs1, .. }
+            | Self::VslidedownVx { rs1, .. }
+            | Self::VslideupVx { rs1, .. }
+            | Self::VsllVx { rs1, .. }
+            | Self::Vsoxei16V { rs1, .. }
+            | Self::Vsoxei32V { rs1, .. }
+            | Self::Vsoxei64V { rs1, .. }
+            | Self::Vsoxei8V { rs1, .. }
+            | Self::VsraVx { rs1, .. }
+            | Self::VsrlVx { rs1, .. }
+            | Self::VsubVx { rs1, .. }
+            | Self::Vsuxei16V { rs1, .. }
+            | Self::Vsuxei32V { rs1, .. }
+            | Self::Vsuxei64V { rs1, .. }
+            | Self::Vsuxei8V { rs1, .. }
+            | Self::VxorVx { rs1, .. } => [Some(rs1), None, None],
+            Self::CAdd { rs1rd, rs2 }
+            | Self::CAddw { rs1rd, rs2 }
+            | Self::CAnd { rs1rd, rs2 }
+            | Self::COr { rs1rd, rs2 }
+            | Self::CSub { rs1rd, rs2 }
+            | Self::CSubw { rs1rd, rs2 }
+            | Self::CXor { rs1rd, rs2 } => [Some(rs1rd), Some(rs2), None],
+            Self::CAddi { rs1rd, .. }
+            | Self::CAddi16Sp { rs1rd, .. }
+            | Self::CAndi { rs1rd, .. }
+            | Self::CLi { rs1rd, .. }
+            | Self::CSlli { rs1rd, .. }
+            | Self::CSrai { rs1rd, .. }
+            | Self::CSrli { rs1rd, .. } => [Some(rs1rd), None, None],
+            Self::CMv { rd, rs2 } => [Some(rd), Some(rs2), None],
+            Self::CSwsp { rs2, .. } => [Some(rs2), None, None],
+            _ => [None; 3],
+        }
+    }
+
+    pub fn decode_from_raw(raw: RawInstruction) -> Self {
+        let opcode = raw.opcode();
+        if raw.matches(0xffffffff, 0xffffffff) || raw.matches(0xffffffff, 0x00000000) {
+            // All-zeros and all-ones are reserved as invalid instructions in
+            // the RISC-V spec, regardless of whether they might match any
+            // patterns below.
+            return Self::Invalid;
+        }
+        if opcode == (Opcode::Amo as u8) {
+            if raw.matches(
+                0b11111000000000000111000001111111,
+                0b00000000000000000010000000101111,
+            ) {
+                Self::AmoaddW {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    aq: raw.aq(),
+                    rl: raw.rl(),
+                }
+            } else if raw.matches(
+                0b11111000000000000111000001111111,
+                0b01100000000000000010000000101111,
+            ) {
+                Self::AmoandW {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    aq: raw.aq(),
+                    rl: raw.rl(),
+                }
+            } else if raw.matches(
+                0b11111000000000000111000001111111,
+                0b10100000000000000010000000101111,
+            ) {
+                Self::AmomaxW {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    aq: raw.aq(),
+                    rl: raw.rl(),
+                }
+            } else if raw.matches(
+                0b11111000000000000111000001111111,
+                0b11100000000000000010000000101111,
+            ) {
+                Self::AmomaxuW {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    aq: raw.aq(),
+                    rl: raw.rl(),
+                }
+            } else if raw.matches(
+                0b11111000000000000111000001111111,
+                0b10000000000000000010000000101111,
+            ) {
+                Self::AmominW {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    aq: raw.aq(),
+                    rl: raw.rl(),
+                }
+            } else if raw.matches(
+                0b11111000000000000111000001111111,
+                0b11000000000000000010000000101111,
+            ) {
+                Self::AmominuW {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    aq: raw.aq(),
+                    rl: raw.rl(),
+                }
+            } else if raw.matches(
+                0b11111000000000000111000001111111,
+                0b01000000000000000010000000101111,
+            ) {
+                Self::AmoorW {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    aq: raw.aq(),
+                    rl: raw.rl(),
+                }
+            } else if raw.matches(
+                0b11111000000000000111000001111111,
+                0b00001000000000000010000000101111,
+            ) {
+                Self::AmoswapW {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    aq: raw.aq(),
+                    rl: raw.rl(),
+                }
+            } else if raw.matches(
+                0b11111000000000000111000001111111,
+                0b00100000000000000010000000101111,
+            ) {
+                Self::AmoxorW {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    aq: raw.aq(),
+                    rl: raw.rl(),
+                }
+            } else if raw.matches(
+                0b11111001111100000111000001111111,
+                0b00010000000000000010000000101111,
+            ) {
+                Self::LrW {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    aq: raw.aq(),
+                    rl: raw.rl(),
+                }
+            } else if raw.matches(
+                0b11111000000000000111000001111111,
+                0b00011000000000000010000000101111,
+            ) {
+                Self::ScW {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    aq: raw.aq(),
+                    rl: raw.rl(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Auipc as u8) {
+            if raw.matches(
+                0b00000000000000000000000001111111,
+                0b00000000000000000000000000010111,
+            ) {
+                Self::Auipc {
+                    rd: raw.rd(),
+                    simm: raw.oimm20(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Branch as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000000000001100011,
+            ) {
+                Self::Beq {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.sbimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000101000001100011,
+            ) {
+                Self::Bge {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.sbimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000111000001100011,
+            ) {
+                Self::Bgeu {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.sbimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000100000001100011,
+            ) {
+                Self::Blt {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.sbimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000110000001100011,
+            ) {
+                Self::Bltu {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.sbimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000001000001100011,
+            ) {
+                Self::Bne {
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    simm: raw.sbimm12(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Jal as u8) {
+            if raw.matches(
+                0b00000000000000000000000001111111,
+                0b00000000000000000000000001101111,
+            ) {
+                Self::Jal {
+                    rd: raw.rd(),
+                    simm: raw.jimm20(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Jalr as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000000000001100111,
+            ) {
+                Self::Jalr {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Load as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000000000000000011,
+            ) {
+                Self::Lb {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000100000000000011,
+            ) {
+                Self::Lbu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000001000000000011,
+            ) {
+                Self::Lh {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000101000000000011,
+            ) {
+                Self::Lhu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000010000000000011,
+            ) {
+                Self::Lw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::LoadFp as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000011000000000111,
+            ) {
+                Self::Fld {
+                    frd: raw.frd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000100000000000111,
+            ) {
+                Self::Flq {
+                    frd: raw.frd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000010000000000111,
+            ) {
+                Self::Flw {
+                    frd: raw.frd(),
+                    rs1: raw.rs1(),
+                    simm: raw.oimm12(),
+                }
+            } else if raw.matches(
+                0b11111101111100000111000001111111,
+                0b00000000000000000101000000000111,
+            ) {
+                Self::Vle16V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111101111100000111000001111111,
+                0b00000000000000000110000000000111,
+            ) {
+                Self::Vle32V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111101111100000111000001111111,
+                0b00000000000000000111000000000111,
+            ) {
+                Self::Vle64V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111101111100000111000001111111,
+                0b00000000000000000000000000000111,
+            ) {
+                Self::Vle8V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001100000000000101000000000111,
+            ) {
+                Self::Vloxei16V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001100000000000110000000000111,
+            ) {
+                Self::Vloxei32V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001100000000000111000000000111,
+            ) {
+                Self::Vloxei64V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001100000000000000000000000111,
+            ) {
+                Self::Vloxei8V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001000000000000101000000000111,
+            ) {
+                Self::Vlse16V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001000000000000110000000000111,
+            ) {
+                Self::Vlse32V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001000000000000111000000000111,
+            ) {
+                Self::Vlse64V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001000000000000000000000000111,
+            ) {
+                Self::Vlse8V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000100000000000101000000000111,
+            ) {
+                Self::Vluxei16V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000100000000000110000000000111,
+            ) {
+                Self::Vluxei32V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000100000000000111000000000111,
+            ) {
+                Self::Vluxei64V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000100000000000000000000000111,
+            ) {
+                Self::Vluxei8V {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Lui as u8) {
+            if raw.matches(
+                0b00000000000000000000000001111111,
+                0b00000000000000000000000000110111,
+            ) {
+                Self::Lui {
+                    rd: raw.rd(),
+                    simm: raw.imm20(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Madd as u8) {
+            if raw.matches(
+                0b00000110000000000000000001111111,
+                0b00000010000000000000000001000011,
+            ) {
+                Self::FmaddD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    frs3: raw.frs3(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b00000110000000000000000001111111,
+                0b00000110000000000000000001000011,
+            ) {
+                Self::FmaddQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    frs3: raw.frs3(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b00000110000000000000000001111111,
+                0b00000000000000000000000001000011,
+            ) {
+                Self::FmaddS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    frs3: raw.frs3(),
+                    rm: raw.rm(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::MiscMem as u8) {
+            if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000000000000001111,
+            ) {
+                Self::Fence {
+                    pred: raw.pred(),
+                    succ: raw.succ(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000001000000001111,
+            ) {
+                Self::FenceI
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Msub as u8) {
+            if raw.matches(
+                0b00000110000000000000000001111111,
+                0b00000010000000000000000001000111,
+            ) {
+                Self::FmsubD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    frs3: raw.frs3(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b00000110000000000000000001111111,
+                0b00000110000000000000000001000111,
+            ) {
+                Self::FmsubQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    frs3: raw.frs3(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b00000110000000000000000001111111,
+                0b00000000000000000000000001000111,
+            ) {
+                Self::FmsubS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    frs3: raw.frs3(),
+                    rm: raw.rm(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Nmadd as u8) {
+            if raw.matches(
+                0b00000110000000000000000001111111,
+                0b00000010000000000000000001001111,
+            ) {
+                Self::FnmaddD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    frs3: raw.frs3(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b00000110000000000000000001111111,
+                0b00000110000000000000000001001111,
+            ) {
+                Self::FnmaddQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    frs3: raw.frs3(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b00000110000000000000000001111111,
+                0b00000000000000000000000001001111,
+            ) {
+                Self::FnmaddS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    frs3: raw.frs3(),
+                    rm: raw.rm(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Nmsub as u8) {
+            if raw.matches(
+                0b00000110000000000000000001111111,
+                0b00000010000000000000000001001011,
+            ) {
+                Self::FnmsubD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    frs3: raw.frs3(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b00000110000000000000000001111111,
+                0b00000110000000000000000001001011,
+            ) {
+                Self::FnmsubQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    frs3: raw.frs3(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b00000110000000000000000001111111,
+                0b00000000000000000000000001001011,
+            ) {
+                Self::FnmsubS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    frs3: raw.frs3(),
+                    rm: raw.rm(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Op as u8) {
+            if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000000000000110011,
+            ) {
+                Self::Add {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000111000000110011,
+            ) {
+                Self::And {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000111000000110011,
+            ) {
+                Self::Andn {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01001000000000000001000000110011,
+            ) {
+                Self::Bclr {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01001000000000000101000000110011,
+            ) {
+                Self::Bext {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01101000000000000001000000110011,
+            ) {
+                Self::Binv {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00101000000000000001000000110011,
+            ) {
+                Self::Bset {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000001000000110011,
+            ) {
+                Self::Clmul {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000011000000110011,
+            ) {
+                Self::Clmulh {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000010000000110011,
+            ) {
+                Self::Clmulr {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000100000000110011,
+            ) {
+                Self::Div {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000101000000110011,
+            ) {
+                Self::Divu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000110000000110011,
+            ) {
+                Self::Max {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000111000000110011,
+            ) {
+                Self::Maxu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000100000000110011,
+            ) {
+                Self::Min {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000101000000110011,
+            ) {
+                Self::Minu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000000000000110011,
+            ) {
+                Self::Mul {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000001000000110011,
+            ) {
+                Self::Mulh {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000010000000110011,
+            ) {
+                Self::Mulhsu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000011000000110011,
+            ) {
+                Self::Mulhu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000110000000110011,
+            ) {
+                Self::Or {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000110000000110011,
+            ) {
+                Self::Orn {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000110000000110011,
+            ) {
+                Self::Rem {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000010000000000111000000110011,
+            ) {
+                Self::Remu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01100000000000000001000000110011,
+            ) {
+                Self::Rol {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01100000000000000101000000110011,
+            ) {
+                Self::Ror {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100000000000000010000000110011,
+            ) {
+                Self::Sh1add {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100000000000000100000000110011,
+            ) {
+                Self::Sh2add {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100000000000000110000000110011,
+            ) {
+                Self::Sh3add {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000001000000110011,
+            ) {
+                Self::Sll {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000010000000110011,
+            ) {
+                Self::Slt {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000011000000110011,
+            ) {
+                Self::Sltu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000101000000110011,
+            ) {
+                Self::Sra {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000101000000110011,
+            ) {
+                Self::Srl {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000000000000110011,
+            ) {
+                Self::Sub {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000100000000110011,
+            ) {
+                Self::Xnor {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00000000000000000100000000110011,
+            ) {
+                Self::Xor {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b00001000000000000100000000110011,
+            ) {
+                Self::ZextH {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::Op32 as u8) {
+            Self::Invalid
+        } else if opcode == (Opcode::OpFp as u8) {
+            if raw.matches(
+                0b11111110000000000000000001111111,
+                0b00000010000000000000000001010011,
+            ) {
+                Self::FaddD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111110000000000000000001111111,
+                0b00000110000000000000000001010011,
+            ) {
+                Self::FaddQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111110000000000000000001111111,
+                0b00000000000000000000000001010011,
+            ) {
+                Self::FaddS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b11100010000000000001000001010011,
+            ) {
+                Self::FclassD {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b11100110000000000001000001010011,
+            ) {
+                Self::FclassQ {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b11100000000000000001000001010011,
+            ) {
+                Self::FclassS {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b01000010001100000000000001010011,
+            ) {
+                Self::FcvtDQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b01000010000000000000000001010011,
+            ) {
+                Self::FcvtDS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b11010010000000000000000001010011,
+            ) {
+                Self::FcvtDW {
+                    frd: raw.frd(),
+                    rs1: raw.rs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b11010010000100000000000001010011,
+            ) {
+                Self::FcvtDWu {
+                    frd: raw.frd(),
+                    rs1: raw.rs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b01000110000100000000000001010011,
+            ) {
+                Self::FcvtQD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b01000110000000000000000001010011,
+            ) {
+                Self::FcvtQS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b11010110000000000000000001010011,
+            ) {
+                Self::FcvtQW {
+                    frd: raw.frd(),
+                    rs1: raw.rs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b11010110000100000000000001010011,
+            ) {
+                Self::FcvtQWu {
+                    frd: raw.frd(),
+                    rs1: raw.rs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b01000000000100000000000001010011,
+            ) {
+                Self::FcvtSD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b01000000001100000000000001010011,
+            ) {
+                Self::FcvtSQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b11010000000000000000000001010011,
+            ) {
+                Self::FcvtSW {
+                    frd: raw.frd(),
+                    rs1: raw.rs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b11010000000100000000000001010011,
+            ) {
+                Self::FcvtSWu {
+                    frd: raw.frd(),
+                    rs1: raw.rs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b11000010000000000000000001010011,
+            ) {
+                Self::FcvtWD {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b11000110000000000000000001010011,
+            ) {
+                Self::FcvtWQ {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b11000000000000000000000001010011,
+            ) {
+                Self::FcvtWS {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b11000010000100000000000001010011,
+            ) {
+                Self::FcvtWuD {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b11000110000100000000000001010011,
+            ) {
+                Self::FcvtWuQ {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b11000000000100000000000001010011,
+            ) {
+                Self::FcvtWuS {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111110000000000000000001111111,
+                0b00011010000000000000000001010011,
+            ) {
+                Self::FdivD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111110000000000000000001111111,
+                0b00011110000000000000000001010011,
+            ) {
+                Self::FdivQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111110000000000000000001111111,
+                0b00011000000000000000000001010011,
+            ) {
+                Self::FdivS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b10100010000000000010000001010011,
+            ) {
+                Self::FeqD {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b10100110000000000010000001010011,
+            ) {
+                Self::FeqQ {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b10100000000000000010000001010011,
+            ) {
+                Self::FeqS {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b10100010000000000000000001010011,
+            ) {
+                Self::FleD {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b10100110000000000000000001010011,
+            ) {
+                Self::FleQ {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b10100000000000000000000001010011,
+            ) {
+                Self::FleS {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b10100010000000000001000001010011,
+            ) {
+                Self::FltD {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b10100110000000000001000001010011,
+            ) {
+                Self::FltQ {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b10100000000000000001000001010011,
+            ) {
+                Self::FltS {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00101010000000000001000001010011,
+            ) {
+                Self::FmaxD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00101110000000000001000001010011,
+            ) {
+                Self::FmaxQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00101000000000000001000001010011,
+            ) {
+                Self::FmaxS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00101010000000000000000001010011,
+            ) {
+                Self::FminD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00101110000000000000000001010011,
+            ) {
+                Self::FminQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00101000000000000000000001010011,
+            ) {
+                Self::FminS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000000000001111111,
+                0b00010010000000000000000001010011,
+            ) {
+                Self::FmulD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111110000000000000000001111111,
+                0b00010110000000000000000001010011,
+            ) {
+                Self::FmulQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111110000000000000000001111111,
+                0b00010000000000000000000001010011,
+            ) {
+                Self::FmulS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b11110000000000000000000001010011,
+            ) {
+                Self::FmvSX {
+                    frd: raw.frd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b11100000000000000000000001010011,
+            ) {
+                Self::FmvXS {
+                    rd: raw.rd(),
+                    frs1: raw.frs1(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100010000000000000000001010011,
+            ) {
+                Self::FsgnjD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100110000000000000000001010011,
+            ) {
+                Self::FsgnjQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100000000000000000000001010011,
+            ) {
+                Self::FsgnjS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100010000000000001000001010011,
+            ) {
+                Self::FsgnjnD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100110000000000001000001010011,
+            ) {
+                Self::FsgnjnQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100000000000000001000001010011,
+            ) {
+                Self::FsgnjnS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100010000000000010000001010011,
+            ) {
+                Self::FsgnjxD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100110000000000010000001010011,
+            ) {
+                Self::FsgnjxQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100000000000000010000001010011,
+            ) {
+                Self::FsgnjxS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b01011010000000000000000001010011,
+            ) {
+                Self::FsqrtD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b01011110000000000000000001010011,
+            ) {
+                Self::FsqrtQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111111111100000000000001111111,
+                0b01011000000000000000000001010011,
+            ) {
+                Self::FsqrtS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111110000000000000000001111111,
+                0b00001010000000000000000001010011,
+            ) {
+                Self::FsubD {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111110000000000000000001111111,
+                0b00001110000000000000000001010011,
+            ) {
+                Self::FsubQ {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    rm: raw.rm(),
+                }
+            } else if raw.matches(
+                0b11111110000000000000000001111111,
+                0b00001000000000000000000001010011,
+            ) {
+                Self::FsubS {
+                    frd: raw.frd(),
+                    frs1: raw.frs1(),
+                    frs2: raw.frs2(),
+                    rm: raw.rm(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::OpImm as u8) {
             if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000000000000010011,
+            ) {
+                Self::Addi {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000111000000010011,
+            ) {
+                Self::Andi {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01001000000000000001000000010011,
+            ) {
+                Self::Bclri {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt5(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01001000000000000101000000010011,
+            ) {
+                Self::Bexti {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt5(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01101000000000000001000000010011,
+            ) {
+                Self::Binvi {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt5(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00101000000000000001000000010011,
+            ) {
+                Self::Bseti {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt5(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01100000000000000001000000010011,
+            ) {
+                Self::Clz {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01100000001000000001000000010011,
+            ) {
+                Self::Cpop {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01100000000100000001000000010011,
+            ) {
+                Self::Ctz {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b00101000011100000101000000010011,
+            ) {
+                Self::OrcB {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000110000000010011,
+            ) {
+                Self::Ori {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01101001100000000101000000010011,
+            ) {
+                Self::Rev8 {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01100000000000000101000000010011,
+            ) {
+                Self::Rori {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt5(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01100000010000000001000000010011,
+            ) {
+                Self::SextB {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01100000010100000001000000010011,
+            ) {
+                Self::SextH {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
                 0b11111000000000000111000001111111,
-                0b00000000000000000010000000101111,
+                0b00000000000000000001000000010011,
             ) {
-                Self::AmoaddW {
+                Self::Slli {
                     rd: raw.rd(),
                     rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    aq: raw.aq(),
-                    rl: raw.rl(),
+                    shamt: raw.shamt5(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000010000000010011,
+            ) {
+                Self::Slti {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000011000000010011,
+            ) {
+                Self::Sltiu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
                 }
             } else if raw.matches(
                 0b11111000000000000111000001111111,
-                0b01100000000000000010000000101111,
+                0b01000000000000000101000000010011,
             ) {
-                Self::AmoandW {
+                Self::Srai {
                     rd: raw.rd(),
                     rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    aq: raw.aq(),
-                    rl: raw.rl(),
+                    shamt: raw.shamt5(),
                 }
             } else if raw.matches(
                 0b11111000000000000111000001111111,
-                0b10100000000000000010000000101111,
+                0b00000000000000000101000000010011,
+            ) {
+                Self::Srli {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt5(),
+                }
+            } else if raw.matches(
+                0b00000000000000000111000001111111,
+                0b00000000000000000100000000010011,
+            ) {
+                Self::Xori {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    simm: raw.imm12(),
+                }
+            } else {
+                Self::Invalid
+            }
+        } else if opcode == (Opcode::OpImm32 as u8) {
+            Self::Invalid
+        } else if opcode == (Opcode::OpV as u8) {
+            if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000000000000000011000001010111,
+            ) {
+                Self::VaddVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    simm: raw.simm5(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000000000000000000000001010111,
+            ) {
+                Self::VaddVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000000000000000100000001010111,
+            ) {
+                Self::VaddVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00100100000000000011000001010111,
+            ) {
+                Self::VandVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    simm: raw.simm5(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00100100000000000000000001010111,
+            ) {
+                Self::VandVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00100100000000000100000001010111,
+            ) {
+                Self::VandVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01011110000000000010000001010111,
+            ) {
+                Self::VcompressVm {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                }
+            } else if raw.matches(
+                0b11111100000011111111000001111111,
+                0b01000000000010000010000001010111,
+            ) {
+                Self::VcpopM {
+                    rd: raw.rd(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b10000100000000000010000001010111,
+            ) {
+                Self::VdivVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b10000100000000000110000001010111,
+            ) {
+                Self::VdivVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b10000000000000000010000001010111,
+            ) {
+                Self::VdivuVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b10000000000000000110000001010111,
+            ) {
+                Self::VdivuVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000000000000000101000001010111,
+            ) {
+                Self::VfaddVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    frs1: raw.frs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000000000000000001000001010111,
+            ) {
+                Self::VfaddVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b10000000000000000101000001010111,
+            ) {
+                Self::VfdivVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    frs1: raw.frs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b10000000000000000001000001010111,
+            ) {
+                Self::VfdivVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000011111111000001111111,
+                0b01000000000010001010000001010111,
+            ) {
+                Self::VfirstM {
+                    rd: raw.rd(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00011000000000000101000001010111,
+            ) {
+                Self::VfmaxVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    frs1: raw.frs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00011000000000000001000001010111,
+            ) {
+                Self::VfmaxVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01011100000000000101000001010111,
             ) {
-                Self::AmomaxW {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    aq: raw.aq(),
-                    rl: raw.rl(),
+                Self::VfmergeVfm {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    frs1: raw.frs1(),
                 }
             } else if raw.matches(
-                0b11111000000000000111000001111111,
-                0b11100000000000000010000000101111,
+                0b11111100000000000111000001111111,
+                0b00010000000000000101000001010111,
             ) {
-                Self::AmomaxuW {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    aq: raw.aq(),
-                    rl: raw.rl(),
+                Self::VfminVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    frs1: raw.frs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111000000000000111000001111111,
-                0b10000000000000000010000000101111,
+                0b11111100000000000111000001111111,
+                0b00010000000000000001000001010111,
             ) {
-                Self::AmominW {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    aq: raw.aq(),
-                    rl: raw.rl(),
+                Self::VfminVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111000000000000111000001111111,
-                0b11000000000000000010000000101111,
+                0b11111100000000000111000001111111,
+                0b10010000000000000101000001010111,
             ) {
-                Self::AmominuW {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    aq: raw.aq(),
-                    rl: raw.rl(),
+                Self::VfmulVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    frs1: raw.frs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111000000000000111000001111111,
-                0b01000000000000000010000000101111,
+                0b11111100000000000111000001111111,
+                0b10010000000000000001000001010111,
             ) {
-                Self::AmoorW {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    aq: raw.aq(),
-                    rl: raw.rl(),
+                Self::VfmulVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111000000000000111000001111111,
-                0b00001000000000000010000000101111,
+                0b11111110000011111111000001111111,
+                0b01000010000000000001000001010111,
             ) {
-                Self::AmoswapW {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    aq: raw.aq(),
-                    rl: raw.rl(),
+                Self::VfmvFS {
+                    frd: raw.frd(),
+                    vs2: raw.vs2(),
                 }
             } else if raw.matches(
-                0b11111000000000000111000001111111,
-                0b00100000000000000010000000101111,
+                0b11111111111100000111000001111111,
+                0b01000010000000000101000001010111,
             ) {
-                Self::AmoxorW {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    aq: raw.aq(),
-                    rl: raw.rl(),
+                Self::VfmvSF {
+                    vd: raw.vd(),
+                    frs1: raw.frs1(),
                 }
             } else if raw.matches(
-                0b11111001111100000111000001111111,
-                0b00010000000000000010000000101111,
+                0b11111111111100000111000001111111,
+                0b01011110000000000101000001010111,
             ) {
-                Self::LrW {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    aq: raw.aq(),
-                    rl: raw.rl(),
+                Self::VfmvVF {
+                    vd: raw.vd(),
+                    frs1: raw.frs1(),
                 }
             } else if raw.matches(
-                0b11111000000000000111000001111111,
-                0b00011000000000000010000000101111,
+                0b11111100000000000111000001111111,
+                0b10000100000000000101000001010111,
             ) {
-                Self::ScW {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    aq: raw.aq(),
-                    rl: raw.rl(),
+                Self::VfrdivVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    frs1: raw.frs1(),
+                    vm: raw.vm(),
                 }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::Auipc as u8) {
-            if raw.matches(
-                0b00000000000000000000000001111111,
-                0b00000000000000000000000000010111,
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00011100000000000001000001010111,
             ) {
-                Self::Auipc {
-                    rd: raw.rd(),
-                    simm: raw.oimm20(),
+                Self::VfredmaxVs {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::Branch as u8) {
-            if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000000000001100011,
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00010100000000000001000001010111,
             ) {
-                Self::Beq {
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    simm: raw.sbimm12(),
+                Self::VfredminVs {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000101000001100011,
+                0b11111100000000000111000001111111,
+                0b00001100000000000001000001010111,
             ) {
-                Self::Bge {
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    simm: raw.sbimm12(),
+                Self::VfredosumVs {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000111000001100011,
+                0b11111100000000000111000001111111,
+                0b00000100000000000001000001010111,
             ) {
-                Self::Bgeu {
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    simm: raw.sbimm12(),
+                Self::VfredusumVs {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000100000001100011,
+                0b11111100000000000111000001111111,
+                0b10011100000000000101000001010111,
             ) {
-                Self::Blt {
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    simm: raw.sbimm12(),
+                Self::VfrsubVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    frs1: raw.frs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000110000001100011,
+                0b11111100000000000111000001111111,
+                0b00100000000000000101000001010111,
             ) {
-                Self::Bltu {
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    simm: raw.sbimm12(),
+                Self::VfsgnjVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    frs1: raw.frs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000001000001100011,
+                0b11111100000000000111000001111111,
+                0b00100000000000000001000001010111,
             ) {
-                Self::Bne {
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                    simm: raw.sbimm12(),
+                Self::VfsgnjVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::Jal as u8) {
-            if raw.matches(
-                0b00000000000000000000000001111111,
-                0b00000000000000000000000001101111,
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00100100000000000101000001010111,
             ) {
-                Self::Jal {
-                    rd: raw.rd(),
-                    simm: raw.jimm20(),
+                Self::VfsgnjnVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    frs1: raw.frs1(),
+                    vm: raw.vm(),
                 }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::Jalr as u8) {
-            if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000000000001100111,
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00100100000000000001000001010111,
             ) {
-                Self::Jalr {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    simm: raw.imm12(),
+                Self::VfsgnjnVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::Load as u8) {
-            if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000000000000000011,
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00101000000000000101000001010111,
             ) {
-                Self::Lb {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    simm: raw.oimm12(),
+                Self::VfsgnjxVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    frs1: raw.frs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000100000000000011,
+                0b11111100000000000111000001111111,
+                0b00101000000000000001000001010111,
             ) {
-                Self::Lbu {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    simm: raw.oimm12(),
+                Self::VfsgnjxVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000001000000000011,
+                0b11111100000000000111000001111111,
+                0b00001000000000000101000001010111,
             ) {
-                Self::Lh {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    simm: raw.oimm12(),
+                Self::VfsubVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    frs1: raw.frs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000101000000000011,
+                0b11111100000000000111000001111111,
+                0b00001000000000000001000001010111,
             ) {
-                Self::Lhu {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    simm: raw.oimm12(),
+                Self::VfsubVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000010000000000011,
+                0b11111101111111111111000001111111,
+                0b01010000000010001010000001010111,
             ) {
-                Self::Lw {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    simm: raw.oimm12(),
+                Self::VidV {
+                    vd: raw.vd(),
+                    vm: raw.vm(),
                 }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::LoadFp as u8) {
-            if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000011000000000111,
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b10110100000000000010000001010111,
             ) {
-                Self::Fld {
-                    frd: raw.frd(),
-                    rs1: raw.rs1(),
-                    simm: raw.oimm12(),
+                Self::VmaccVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000100000000000111,
+                0b11111100000000000111000001111111,
+                0b10110100000000000110000001010111,
             ) {
-                Self::Flq {
-                    frd: raw.frd(),
+                Self::VmaccVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
-                    simm: raw.oimm12(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000010000000000111,
+                0b11111110000000000111000001111111,
+                0b01100110000000000010000001010111,
             ) {
-                Self::Flw {
-                    frd: raw.frd(),
-                    rs1: raw.rs1(),
-                    simm: raw.oimm12(),
+                Self::VmandMm {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
                 }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::Lui as u8) {
-            if raw.matches(
-                0b00000000000000000000000001111111,
-                0b00000000000000000000000000110111,
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01100010000000000010000001010111,
             ) {
-                Self::Lui {
-                    rd: raw.rd(),
-                    simm: raw.imm20(),
+                Self::VmandnMm {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
                 }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::Madd as u8) {
-            if raw.matches(
-                0b00000110000000000000000001111111,
-                0b00000010000000000000000001000011,
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00011100000000000000000001010111,
             ) {
-                Self::FmaddD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    frs3: raw.frs3(),
-                    rm: raw.rm(),
+                Self::VmaxVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000110000000000000000001111111,
-                0b00000110000000000000000001000011,
+                0b11111100000000000111000001111111,
+                0b00011100000000000100000001010111,
             ) {
-                Self::FmaddQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    frs3: raw.frs3(),
-                    rm: raw.rm(),
+                Self::VmaxVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000110000000000000000001111111,
-                0b00000000000000000000000001000011,
+                0b11111100000000000111000001111111,
+                0b00011000000000000000000001010111,
             ) {
-                Self::FmaddS {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    frs3: raw.frs3(),
-                    rm: raw.rm(),
+                Self::VmaxuVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::MiscMem as u8) {
-            if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000000000000001111,
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00011000000000000100000001010111,
             ) {
-                Self::Fence {
-                    pred: raw.pred(),
-                    succ: raw.succ(),
+                Self::VmaxuVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000001000000001111,
+                0b11111110000000000111000001111111,
+                0b01011100000000000011000001010111,
             ) {
-                Self::FenceI
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::Msub as u8) {
-            if raw.matches(
-                0b00000110000000000000000001111111,
-                0b00000010000000000000000001000111,
+                Self::VmergeVim {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    simm: raw.simm5(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01011100000000000000000001010111,
             ) {
-                Self::FmsubD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    frs3: raw.frs3(),
-                    rm: raw.rm(),
+                Self::VmergeVvm {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
                 }
             } else if raw.matches(
-                0b00000110000000000000000001111111,
-                0b00000110000000000000000001000111,
+                0b11111110000000000111000001111111,
+                0b01011100000000000100000001010111,
             ) {
-                Self::FmsubQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    frs3: raw.frs3(),
-                    rm: raw.rm(),
+                Self::VmergeVxm {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
                 }
             } else if raw.matches(
-                0b00000110000000000000000001111111,
-                0b00000000000000000000000001000111,
+                0b11111100000000000111000001111111,
+                0b01100000000000000101000001010111,
             ) {
-                Self::FmsubS {
-                    frd: raw.frd(),
+                Self::VmfeqVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    frs3: raw.frs3(),
-                    rm: raw.rm(),
+                    vm: raw.vm(),
                 }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::Nmadd as u8) {
-            if raw.matches(
-                0b00000110000000000000000001111111,
-                0b00000010000000000000000001001111,
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b01100000000000000001000001010111,
             ) {
-                Self::FnmaddD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    frs3: raw.frs3(),
-                    rm: raw.rm(),
+                Self::VmfeqVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000110000000000000000001111111,
-                0b00000110000000000000000001001111,
+                0b11111100000000000111000001111111,
+                0b01111100000000000101000001010111,
             ) {
-                Self::FnmaddQ {
-                    frd: raw.frd(),
+                Self::VmfgeVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    frs3: raw.frs3(),
-                    rm: raw.rm(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000110000000000000000001111111,
-                0b00000000000000000000000001001111,
+                0b11111100000000000111000001111111,
+                0b01110100000000000101000001010111,
             ) {
-                Self::FnmaddS {
-                    frd: raw.frd(),
+                Self::VmfgtVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    frs3: raw.frs3(),
-                    rm: raw.rm(),
+                    vm: raw.vm(),
                 }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::Nmsub as u8) {
-            if raw.matches(
-                0b00000110000000000000000001111111,
-                0b00000010000000000000000001001011,
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b01100100000000000101000001010111,
             ) {
-                Self::FnmsubD {
-                    frd: raw.frd(),
+                Self::VmfleVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    frs3: raw.frs3(),
-                    rm: raw.rm(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000110000000000000000001111111,
-                0b00000110000000000000000001001011,
+                0b11111100000000000111000001111111,
+                0b01100100000000000001000001010111,
             ) {
-                Self::FnmsubQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    frs3: raw.frs3(),
-                    rm: raw.rm(),
+                Self::VmfleVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000110000000000000000001111111,
-                0b00000000000000000000000001001011,
+                0b11111100000000000111000001111111,
+                0b01101100000000000101000001010111,
             ) {
-                Self::FnmsubS {
-                    frd: raw.frd(),
+                Self::VmfltVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    frs3: raw.frs3(),
-                    rm: raw.rm(),
+                    vm: raw.vm(),
                 }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::Op as u8) {
-            if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00000000000000000000000000110011,
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b01101100000000000001000001010111,
             ) {
-                Self::Add {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                Self::VmfltVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00000000000000000111000000110011,
+                0b11111100000000000111000001111111,
+                0b01110000000000000101000001010111,
             ) {
-                Self::And {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                Self::VmfneVf {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    frs1: raw.frs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00000010000000000100000000110011,
+                0b11111100000000000111000001111111,
+                0b01110000000000000001000001010111,
             ) {
-                Self::Div {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                Self::VmfneVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00000010000000000101000000110011,
+                0b11111100000000000111000001111111,
+                0b00010100000000000000000001010111,
             ) {
-                Self::Divu {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                Self::VminVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00000010000000000000000000110011,
+                0b11111100000000000111000001111111,
+                0b00010100000000000100000001010111,
             ) {
-                Self::Mul {
-                    rd: raw.rd(),
+                Self::VminVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00000010000000000001000000110011,
+                0b11111100000000000111000001111111,
+                0b00010000000000000000000001010111,
             ) {
-                Self::Mulh {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                Self::VminuVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00000010000000000010000000110011,
+                0b11111100000000000111000001111111,
+                0b00010000000000000100000001010111,
             ) {
-                Self::Mulhsu {
-                    rd: raw.rd(),
+                Self::VminuVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
-                0b00000010000000000011000000110011,
+                0b01110110000000000010000001010111,
             ) {
-                Self::Mulhu {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                Self::VmnandMm {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
                 }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
-                0b00000000000000000110000000110011,
+                0b01111010000000000010000001010111,
             ) {
-                Self::Or {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                Self::VmnorMm {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
                 }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
-                0b00000010000000000110000000110011,
+                0b01101010000000000010000001010111,
             ) {
-                Self::Rem {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                Self::VmorMm {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
                 }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
-                0b00000010000000000111000000110011,
+                0b01110010000000000010000001010111,
             ) {
-                Self::Remu {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                Self::VmornMm {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00000000000000000001000000110011,
+                0b11111100000000000111000001111111,
+                0b01100000000000000011000001010111,
             ) {
-                Self::Sll {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                Self::VmseqVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    simm: raw.simm5(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00000000000000000010000000110011,
+                0b11111100000000000111000001111111,
+                0b01100000000000000000000001010111,
             ) {
-                Self::Slt {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                Self::VmseqVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00000000000000000011000000110011,
+                0b11111100000000000111000001111111,
+                0b01100000000000000100000001010111,
             ) {
-                Self::Sltu {
-                    rd: raw.rd(),
+                Self::VmseqVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b01000000000000000101000000110011,
+                0b11111100000000000111000001111111,
+                0b01111100000000000011000001010111,
             ) {
-                Self::Sra {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                Self::VmsgtVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    simm: raw.simm5(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00000000000000000101000000110011,
+                0b11111100000000000111000001111111,
+                0b01111100000000000100000001010111,
             ) {
-                Self::Srl {
-                    rd: raw.rd(),
+                Self::VmsgtVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b01000000000000000000000000110011,
+                0b11111100000000000111000001111111,
+                0b01111000000000000011000001010111,
             ) {
-                Self::Sub {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    rs2: raw.rs2(),
+                Self::VmsgtuVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    simm: raw.simm5(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00000000000000000100000000110011,
+                0b11111100000000000111000001111111,
+                0b01111000000000000100000001010111,
             ) {
-                Self::Xor {
-                    rd: raw.rd(),
+                Self::VmsgtuVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
-                    rs2: raw.rs2(),
-                }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::Op32 as u8) {
-            Self::Invalid
-        } else if opcode == (Opcode::OpFp as u8) {
-            if raw.matches(
-                0b11111110000000000000000001111111,
-                0b00000010000000000000000001010011,
-            ) {
-                Self::FaddD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    rm: raw.rm(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000000000001111111,
-                0b00000110000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b01110100000000000011000001010111,
             ) {
-                Self::FaddQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    rm: raw.rm(),
+                Self::VmsleVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    simm: raw.simm5(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000000000001111111,
-                0b00000000000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b01110100000000000000000001010111,
             ) {
-                Self::FaddS {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    rm: raw.rm(),
+                Self::VmsleVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
-            } else if raw.matches(
-                0b11111111111100000111000001111111,
-                0b11100010000000000001000001010011,
-            ) {
-                Self::FclassD {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b01110100000000000100000001010111,
+            ) {
+                Self::VmsleVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000111000001111111,
-                0b11100110000000000001000001010011,
+                0b11111100000000000111000001111111,
+                0b01110000000000000011000001010111,
             ) {
-                Self::FclassQ {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
+                Self::VmsleuVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    simm: raw.simm5(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000111000001111111,
-                0b11100000000000000001000001010011,
+                0b11111100000000000111000001111111,
+                0b01110000000000000000000001010111,
             ) {
-                Self::FclassS {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
+                Self::VmsleuVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b01000010001100000000000001010011,
+                0b11111100000000000111000001111111,
+                0b01110000000000000100000001010111,
             ) {
-                Self::FcvtDQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VmsleuVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b01000010000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b01101100000000000000000001010111,
             ) {
-                Self::FcvtDS {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VmsltVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b11010010000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b01101100000000000100000001010111,
             ) {
-                Self::FcvtDW {
-                    frd: raw.frd(),
+                Self::VmsltVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
-                    rm: raw.rm(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b11010010000100000000000001010011,
+                0b11111100000000000111000001111111,
+                0b01101000000000000000000001010111,
             ) {
-                Self::FcvtDWu {
-                    frd: raw.frd(),
-                    rs1: raw.rs1(),
-                    rm: raw.rm(),
+                Self::VmsltuVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b01000110000100000000000001010011,
+                0b11111100000000000111000001111111,
+                0b01101000000000000100000001010111,
             ) {
-                Self::FcvtQD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VmsltuVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b01000110000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b01100100000000000011000001010111,
             ) {
-                Self::FcvtQS {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VmsneVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    simm: raw.simm5(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b11010110000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b01100100000000000000000001010111,
             ) {
-                Self::FcvtQW {
-                    frd: raw.frd(),
-                    rs1: raw.rs1(),
-                    rm: raw.rm(),
+                Self::VmsneVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b11010110000100000000000001010011,
+                0b11111100000000000111000001111111,
+                0b01100100000000000100000001010111,
             ) {
-                Self::FcvtQWu {
-                    frd: raw.frd(),
+                Self::VmsneVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
-                    rm: raw.rm(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b01000000000100000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10010100000000000010000001010111,
             ) {
-                Self::FcvtSD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VmulVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b01000000001100000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10010100000000000110000001010111,
             ) {
-                Self::FcvtSQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VmulVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b11010000000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10011100000000000010000001010111,
             ) {
-                Self::FcvtSW {
-                    frd: raw.frd(),
-                    rs1: raw.rs1(),
-                    rm: raw.rm(),
+                Self::VmulhVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b11010000000100000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10011100000000000110000001010111,
             ) {
-                Self::FcvtSWu {
-                    frd: raw.frd(),
+                Self::VmulhVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
-                    rm: raw.rm(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b11000010000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10011000000000000010000001010111,
             ) {
-                Self::FcvtWD {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VmulhsuVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b11000110000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10011000000000000110000001010111,
             ) {
-                Self::FcvtWQ {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VmulhsuVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b11000000000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10010000000000000010000001010111,
             ) {
-                Self::FcvtWS {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VmulhuVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b11000010000100000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10010000000000000110000001010111,
             ) {
-                Self::FcvtWuD {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VmulhuVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b11000110000100000000000001010011,
+                0b11111111111100000111000001111111,
+                0b01000010000000000110000001010111,
             ) {
-                Self::FcvtWuQ {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VmvSX {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b11000000000100000000000001010011,
+                0b11111111111100000111000001111111,
+                0b01011110000000000011000001010111,
             ) {
-                Self::FcvtWuS {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VmvVI {
+                    vd: raw.vd(),
+                    simm: raw.simm5(),
                 }
             } else if raw.matches(
-                0b11111110000000000000000001111111,
-                0b00011010000000000000000001010011,
+                0b11111111111100000111000001111111,
+                0b01011110000000000000000001010111,
             ) {
-                Self::FdivD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    rm: raw.rm(),
+                Self::VmvVV {
+                    vd: raw.vd(),
+                    vs1: raw.vs1(),
                 }
             } else if raw.matches(
-                0b11111110000000000000000001111111,
-                0b00011110000000000000000001010011,
+                0b11111111111100000111000001111111,
+                0b01011110000000000100000001010111,
             ) {
-                Self::FdivQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    rm: raw.rm(),
+                Self::VmvVX {
+                    vd: raw.vd(),
+                    rs1: raw.rs1(),
                 }
             } else if raw.matches(
-                0b11111110000000000000000001111111,
-                0b00011000000000000000000001010011,
+                0b11111110000011111111000001111111,
+                0b01000010000000000010000001010111,
             ) {
-                Self::FdivS {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    rm: raw.rm(),
+                Self::VmvXS {
+                    rd: raw.rd(),
+                    vs2: raw.vs2(),
                 }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
-                0b10100010000000000010000001010011,
+                0b01111110000000000010000001010111,
             ) {
-                Self::FeqD {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VmxnorMm {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
                 }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
-                0b10100110000000000010000001010011,
+                0b01101110000000000010000001010111,
             ) {
-                Self::FeqQ {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VmxorMm {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b10100000000000000010000001010011,
+                0b11111100000000000111000001111111,
+                0b00101000000000000011000001010111,
             ) {
-                Self::FeqS {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VorVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    simm: raw.simm5(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b10100010000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b00101000000000000000000001010111,
             ) {
-                Self::FleD {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VorVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b10100110000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b00101000000000000100000001010111,
             ) {
-                Self::FleQ {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VorVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b10100000000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b00000100000000000010000001010111,
             ) {
-                Self::FleS {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VredandVs {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b10100010000000000001000001010011,
+                0b11111100000000000111000001111111,
+                0b00011100000000000010000001010111,
             ) {
-                Self::FltD {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VredmaxVs {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b10100110000000000001000001010011,
+                0b11111100000000000111000001111111,
+                0b00011000000000000010000001010111,
             ) {
-                Self::FltQ {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VredmaxuVs {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b10100000000000000001000001010011,
+                0b11111100000000000111000001111111,
+                0b00010100000000000010000001010111,
             ) {
-                Self::FltS {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VredminVs {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00101010000000000001000001010011,
+                0b11111100000000000111000001111111,
+                0b00010000000000000010000001010111,
             ) {
-                Self::FmaxD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VredminuVs {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00101110000000000001000001010011,
+                0b11111100000000000111000001111111,
+                0b00001000000000000010000001010111,
             ) {
-                Self::FmaxQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VredorVs {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00101000000000000001000001010011,
+                0b11111100000000000111000001111111,
+                0b00000000000000000010000001010111,
             ) {
-                Self::FmaxS {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VredsumVs {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00101010000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b00001100000000000010000001010111,
             ) {
-                Self::FminD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VredxorVs {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00101110000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10001100000000000010000001010111,
             ) {
-                Self::FminQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VremVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00101000000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10001100000000000110000001010111,
             ) {
-                Self::FminS {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VremVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000000000001111111,
-                0b00010010000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10001000000000000010000001010111,
             ) {
-                Self::FmulD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    rm: raw.rm(),
+                Self::VremuVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000000000001111111,
-                0b00010110000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10001000000000000110000001010111,
             ) {
-                Self::FmulQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    rm: raw.rm(),
+                Self::VremuVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000000000001111111,
-                0b00010000000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b00110000000000000011000001010111,
             ) {
-                Self::FmulS {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    rm: raw.rm(),
+                Self::VrgatherVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    uimm: raw.zimm(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000111000001111111,
-                0b11110000000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b00110000000000000000000001010111,
             ) {
-                Self::FmvSX {
-                    frd: raw.frd(),
+                Self::VrgatherVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00110000000000000100000001010111,
+            ) {
+                Self::VrgatherVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000111000001111111,
-                0b11100000000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b00001100000000000011000001010111,
             ) {
-                Self::FmvXS {
-                    rd: raw.rd(),
-                    frs1: raw.frs1(),
+                Self::VrsubVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    simm: raw.simm5(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00100010000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b00001100000000000100000001010111,
             ) {
-                Self::FsgnjD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VrsubVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00100110000000000000000001010011,
+                0b11000000000000000111000001111111,
+                0b11000000000000000111000001010111,
             ) {
-                Self::FsgnjQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::Vsetivli {
+                    rd: raw.rd(),
+                    uimm: raw.zimm(),
+                    vtypei: raw.vtypei10(),
                 }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
-                0b00100000000000000000000001010011,
+                0b10000000000000000111000001010111,
             ) {
-                Self::FsgnjS {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::Vsetvl {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00100010000000000001000001010011,
+                0b10000000000000000111000001111111,
+                0b00000000000000000111000001010111,
             ) {
-                Self::FsgnjnD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::Vsetvli {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    vtypei: raw.vtypei11(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00100110000000000001000001010011,
+                0b11111100000000000111000001111111,
+                0b00111100000000000110000001010111,
             ) {
-                Self::FsgnjnQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::Vslide1downVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00100000000000000001000001010011,
+                0b11111100000000000111000001111111,
+                0b00111000000000000110000001010111,
             ) {
-                Self::FsgnjnS {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::Vslide1upVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00100010000000000010000001010011,
+                0b11111100000000000111000001111111,
+                0b00111100000000000011000001010111,
             ) {
-                Self::FsgnjxD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VslidedownVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    uimm: raw.zimm(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00100110000000000010000001010011,
+                0b11111100000000000111000001111111,
+                0b00111100000000000100000001010111,
             ) {
-                Self::FsgnjxQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VslidedownVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000111000001111111,
-                0b00100000000000000010000001010011,
+                0b11111100000000000111000001111111,
+                0b00111000000000000011000001010111,
             ) {
-                Self::FsgnjxS {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
+                Self::VslideupVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    uimm: raw.zimm(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b01011010000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b00111000000000000100000001010111,
             ) {
-                Self::FsqrtD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VslideupVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b01011110000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10010100000000000011000001010111,
             ) {
-                Self::FsqrtQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VsllVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    uimm: raw.zimm(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111111111100000000000001111111,
-                0b01011000000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10010100000000000000000001010111,
             ) {
-                Self::FsqrtS {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    rm: raw.rm(),
+                Self::VsllVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000000000001111111,
-                0b00001010000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10010100000000000100000001010111,
             ) {
-                Self::FsubD {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    rm: raw.rm(),
+                Self::VsllVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000000000001111111,
-                0b00001110000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10100100000000000011000001010111,
             ) {
-                Self::FsubQ {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    rm: raw.rm(),
+                Self::VsraVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    uimm: raw.zimm(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111110000000000000000001111111,
-                0b00001000000000000000000001010011,
+                0b11111100000000000111000001111111,
+                0b10100100000000000000000001010111,
             ) {
-                Self::FsubS {
-                    frd: raw.frd(),
-                    frs1: raw.frs1(),
-                    frs2: raw.frs2(),
-                    rm: raw.rm(),
+                Self::VsraVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
-            } else {
-                Self::Invalid
-            }
-        } else if opcode == (Opcode::OpImm as u8) {
-            if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000000000000010011,
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b10100100000000000100000001010111,
             ) {
-                Self::Addi {
-                    rd: raw.rd(),
+                Self::VsraVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
-                    simm: raw.imm12(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000111000000010011,
+                0b11111100000000000111000001111111,
+                0b10100000000000000011000001010111,
             ) {
-                Self::Andi {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    simm: raw.imm12(),
+                Self::VsrlVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    uimm: raw.zimm(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000110000000010011,
+                0b11111100000000000111000001111111,
+                0b10100000000000000000000001010111,
             ) {
-                Self::Ori {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    simm: raw.imm12(),
+                Self::VsrlVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111000000000000111000001111111,
-                0b00000000000000000001000000010011,
+                0b11111100000000000111000001111111,
+                0b10100000000000000100000001010111,
             ) {
-                Self::Slli {
-                    rd: raw.rd(),
+                Self::VsrlVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
-                    shamt: raw.shamt5(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000010000000010011,
+                0b11111100000000000111000001111111,
+                0b00001000000000000000000001010111,
             ) {
-                Self::Slti {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    simm: raw.imm12(),
+                Self::VsubVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000011000000010011,
+                0b11111100000000000111000001111111,
+                0b00001000000000000100000001010111,
             ) {
-                Self::Sltiu {
-                    rd: raw.rd(),
+                Self::VsubVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
-                    simm: raw.imm12(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111000000000000111000001111111,
-                0b01000000000000000101000000010011,
+                0b11111100000000000111000001111111,
+                0b00101100000000000011000001010111,
             ) {
-                Self::Srai {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    shamt: raw.shamt5(),
+                Self::VxorVi {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    simm: raw.simm5(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b11111000000000000111000001111111,
-                0b00000000000000000101000000010011,
+                0b11111100000000000111000001111111,
+                0b00101100000000000000000001010111,
             ) {
-                Self::Srli {
-                    rd: raw.rd(),
-                    rs1: raw.rs1(),
-                    shamt: raw.shamt5(),
+                Self::VxorVv {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
+                    vs1: raw.vs1(),
+                    vm: raw.vm(),
                 }
             } else if raw.matches(
-                0b00000000000000000111000001111111,
-                0b00000000000000000100000000010011,
+                0b11111100000000000111000001111111,
+                0b00101100000000000100000001010111,
             ) {
-                Self::Xori {
-                    rd: raw.rd(),
+                Self::VxorVx {
+                    vd: raw.vd(),
+                    vs2: raw.vs2(),
                     rs1: raw.rs1(),
-                    simm: raw.imm12(),
+                    vm: raw.vm(),
                 }
             } else {
                 Self::Invalid
             }
-        } else if opcode == (Opcode::OpImm32 as u8) {
-            Self::Invalid
         } else if opcode == (Opcode::Store as u8) {
             if raw.matches(
                 0b00000000000000000111000001111111,
@@ -2428,6 +6311,162 @@ impl OperationRV32 {
                     frs2: raw.frs2(),
                     simm: raw.simm12(),
                 }
+            } else if raw.matches(
+                0b11111101111100000111000001111111,
+                0b00000000000000000101000000100111,
+            ) {
+                Self::Vse16V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111101111100000111000001111111,
+                0b00000000000000000110000000100111,
+            ) {
+                Self::Vse32V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111101111100000111000001111111,
+                0b00000000000000000111000000100111,
+            ) {
+                Self::Vse64V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111101111100000111000001111111,
+                0b00000000000000000000000000100111,
+            ) {
+                Self::Vse8V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001100000000000101000000100111,
+            ) {
+                Self::Vsoxei16V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001100000000000110000000100111,
+            ) {
+                Self::Vsoxei32V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001100000000000111000000100111,
+            ) {
+                Self::Vsoxei64V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001100000000000000000000100111,
+            ) {
+                Self::Vsoxei8V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001000000000000101000000100111,
+            ) {
+                Self::Vsse16V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001000000000000110000000100111,
+            ) {
+                Self::Vsse32V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001000000000000111000000100111,
+            ) {
+                Self::Vsse64V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001000000000000000000000100111,
+            ) {
+                Self::Vsse8V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000100000000000101000000100111,
+            ) {
+                Self::Vsuxei16V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000100000000000110000000100111,
+            ) {
+                Self::Vsuxei32V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000100000000000111000000100111,
+            ) {
+                Self::Vsuxei64V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00000100000000000000000000100111,
+            ) {
+                Self::Vsuxei8V {
+                    vs3: raw.vd(),
+                    rs1: raw.rs1(),
+                    vs2: raw.vs2(),
+                    vm: raw.vm(),
+                }
             } else {
                 Self::Invalid
             }
@@ -1,8 +1,9 @@
+use crate::isa::Extensions;
 use crate::raw_instruction::{Opcode, RawInstruction};
 use crate::register::{FloatRegister, IntRegister};
 
 /// Enumeration of all operations from the RV64 ISA.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum OperationRV64 {
     /// The result of decoding an instruction that isn't valid at all,
     /// according to the current decoder implementation.
@@ -84,7 +85,7 @@ pub enum OperationRV64 {
         simm: i32,
     },
     /// Fence (RV64I)
-    Fence { pred: bool, succ: bool },
+    Fence { pred: u32, succ: u32 },
     /// Fence Instruction (RV64I)
     FenceI,
     /// Jump and Link (RV64I)
@@ -1339,9 +1340,678 @@ pub enum OperationRV64 {
         rs1rd: IntRegister,
         rs2: IntRegister,
     },
+
+    // RV64Zba: Address Generation
+    /// Add Unsigned Word (RV64Zba)
+    AddUw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Left by 1 and Add (RV64Zba)
+    Sh1add {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Unsigned Word Left by 1 and Add (RV64Zba)
+    Sh1addUw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Left by 2 and Add (RV64Zba)
+    Sh2add {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Unsigned Word Left by 2 and Add (RV64Zba)
+    Sh2addUw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Left by 3 and Add (RV64Zba)
+    Sh3add {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Unsigned Word Left by 3 and Add (RV64Zba)
+    Sh3addUw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Shift Left Logical Immediate Unsigned Word (RV64Zba)
+    SlliUw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+
+    // RV64Zbb: Basic Bit Manipulation
+    /// And Inverted (RV64Zbb)
+    Andn {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Count Leading Zeros (RV64Zbb)
+    Clz { rd: IntRegister, rs1: IntRegister },
+    /// Count Leading Zeros Word (RV64Zbb)
+    Clzw { rd: IntRegister, rs1: IntRegister },
+    /// Count Set Bits (RV64Zbb)
+    Cpop { rd: IntRegister, rs1: IntRegister },
+    /// Count Set Bits Word (RV64Zbb)
+    Cpopw { rd: IntRegister, rs1: IntRegister },
+    /// Count Trailing Zeros (RV64Zbb)
+    Ctz { rd: IntRegister, rs1: IntRegister },
+    /// Count Trailing Zeros Word (RV64Zbb)
+    Ctzw { rd: IntRegister, rs1: IntRegister },
+    /// Maximum (RV64Zbb)
+    Max {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Maximum Unsigned (RV64Zbb)
+    Maxu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Minimum (RV64Zbb)
+    Min {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Minimum Unsigned (RV64Zbb)
+    Minu {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Bitwise Or-Combine Byte (RV64Zbb)
+    OrcB { rd: IntRegister, rs1: IntRegister },
+    /// Or Inverted (RV64Zbb)
+    Orn {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Byte Reverse (RV64Zbb)
+    Rev8 { rd: IntRegister, rs1: IntRegister },
+    /// Rotate Left (RV64Zbb)
+    Rol {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Rotate Left Word (RV64Zbb)
+    Rolw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Rotate Right (RV64Zbb)
+    Ror {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Rotate Right Immediate (RV64Zbb)
+    Rori {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Rotate Right Immediate Word (RV64Zbb)
+    Roriw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Rotate Right Word (RV64Zbb)
+    Rorw {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Sign-Extend Byte (RV64Zbb)
+    SextB { rd: IntRegister, rs1: IntRegister },
+    /// Sign-Extend Halfword (RV64Zbb)
+    SextH { rd: IntRegister, rs1: IntRegister },
+    /// Exclusive Nor (RV64Zbb)
+    Xnor {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Zero-Extend Halfword (RV64Zbb)
+    ZextH { rd: IntRegister, rs1: IntRegister },
+
+    // RV64Zbc: Carry-less Multiplication
+    /// Carry-less Multiply (RV64Zbc)
+    Clmul {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Carry-less Multiply High (RV64Zbc)
+    Clmulh {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Carry-less Multiply Reversed (RV64Zbc)
+    Clmulr {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+
+    // RV64Zbs: Single-bit Instructions
+    /// Single-Bit Clear (RV64Zbs)
+    Bclr {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Single-Bit Clear Immediate (RV64Zbs)
+    Bclri {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Single-Bit Extract (RV64Zbs)
+    Bext {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Single-Bit Extract Immediate (RV64Zbs)
+    Bexti {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Single-Bit Invert (RV64Zbs)
+    Binv {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Single-Bit Invert Immediate (RV64Zbs)
+    Binvi {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
+    /// Single-Bit Set (RV64Zbs)
+    Bset {
+        rd: IntRegister,
+        rs1: IntRegister,
+        rs2: IntRegister,
+    },
+    /// Single-Bit Set Immediate (RV64Zbs)
+    Bseti {
+        rd: IntRegister,
+        rs1: IntRegister,
+        shamt: u32,
+    },
 }
 
 impl OperationRV64 {
+    /// Returns the extensions that a hart must implement for this operation
+    /// to be valid. Operations from the base integer ISA don't require any.
+    pub fn required_extensions(&self) -> Extensions {
+        match self {
+            Self::Csrrc { .. }
+            | Self::Csrrci { .. }
+            | Self::Csrrs { .. }
+            | Self::Csrrsi { .. }
+            | Self::Csrrw { .. }
+            | Self::Csrrwi { .. } => Extensions::ZICSR,
+            Self::FenceI => Extensions::ZIFENCEI,
+            Self::Div { .. }
+            | Self::Divu { .. }
+            | Self::Divuw { .. }
+            | Self::Divw { .. }
+            | Self::Mul { .. }
+            | Self::Mulh { .. }
+            | Self::Mulhsu { .. }
+            | Self::Mulhu { .. }
+            | Self::Mulw { .. }
+            | Self::Rem { .. }
+            | Self::Remu { .. }
+            | Self::Remuw { .. }
+            | Self::Remw { .. } => Extensions::M,
+            Self::AmoaddD { .. }
+            | Self::AmoaddW { .. }
+            | Self::AmoandD { .. }
+            | Self::AmoandW { .. }
+            | Self::AmomaxD { .. }
+            | Self::AmomaxW { .. }
+            | Self::AmomaxuD { .. }
+            | Self::AmomaxuW { .. }
+            | Self::AmominD { .. }
+            | Self::AmominW { .. }
+            | Self::AmominuD { .. }
+            | Self::AmominuW { .. }
+            | Self::AmoorD { .. }
+            | Self::AmoorW { .. }
+            | Self::AmoswapD { .. }
+            | Self::AmoswapW { .. }
+            | Self::AmoxorD { .. }
+            | Self::AmoxorW { .. }
+            | Self::LrD { .. }
+            | Self::LrW { .. }
+            | Self::ScD { .. }
+            | Self::ScW { .. } => Extensions::A,
+            Self::FaddS { .. }
+            | Self::FclassS { .. }
+            | Self::FcvtLS { .. }
+            | Self::FcvtLuS { .. }
+            | Self::FcvtSL { .. }
+            | Self::FcvtSLu { .. }
+            | Self::FcvtSW { .. }
+            | Self::FcvtSWu { .. }
+            | Self::FcvtWS { .. }
+            | Self::FcvtWuS { .. }
+            | Self::FdivS { .. }
+            | Self::FeqS { .. }
+            | Self::FleS { .. }
+            | Self::FltS { .. }
+            | Self::Flw { .. }
+            | Self::FmaddS { .. }
+            | Self::FmaxS { .. }
+            | Self::FminS { .. }
+            | Self::FmsubS { .. }
+            | Self::FmulS { .. }
+            | Self::FmvSX { .. }
+            | Self::FmvXS { .. }
+            | Self::FnmaddS { .. }
+            | Self::FnmsubS { .. }
+            | Self::FsgnjS { .. }
+            | Self::FsgnjnS { .. }
+            | Self::FsgnjxS { .. }
+            | Self::FsqrtS { .. }
+            | Self::FsubS { .. }
+            | Self::Fsw { .. } => Extensions::F,
+            Self::FaddD { .. }
+            | Self::FclassD { .. }
+            | Self::FcvtDL { .. }
+            | Self::FcvtDLu { .. }
+            | Self::FcvtDS { .. }
+            | Self::FcvtDW { .. }
+            | Self::FcvtDWu { .. }
+            | Self::FcvtLD { .. }
+            | Self::FcvtLuD { .. }
+            | Self::FcvtSD { .. }
+            | Self::FcvtWD { .. }
+            | Self::FcvtWuD { .. }
+            | Self::FdivD { .. }
+            | Self::FeqD { .. }
+            | Self::Fld { .. }
+            | Self::FleD { .. }
+            | Self::FltD { .. }
+            | Self::FmaddD { .. }
+            | Self::FmaxD { .. }
+            | Self::FminD { .. }
+            | Self::FmsubD { .. }
+            | Self::FmulD { .. }
+            | Self::FmvDX { .. }
+            | Self::FmvXD { .. }
+            | Self::FnmaddD { .. }
+            | Self::FnmsubD { .. }
+            | Self::Fsd { .. }
+            | Self::FsgnjD { .. }
+            | Self::FsgnjnD { .. }
+            | Self::FsgnjxD { .. }
+            | Self::FsqrtD { .. }
+            | Self::FsubD { .. } => Extensions::D,
+            Self::FaddQ { .. }
+            | Self::FclassQ { .. }
+            | Self::FcvtDQ { .. }
+            | Self::FcvtLQ { .. }
+            | Self::FcvtLuQ { .. }
+            | Self::FcvtQD { .. }
+            | Self::FcvtQL { .. }
+            | Self::FcvtQLu { .. }
+            | Self::FcvtQS { .. }
+            | Self::FcvtQW { .. }
+            | Self::FcvtQWu { .. }
+            | Self::FcvtSQ { .. }
+            | Self::FcvtWQ { .. }
+            | Self::FcvtWuQ { .. }
+            | Self::FdivQ { .. }
+            | Self::FeqQ { .. }
+            | Self::FleQ { .. }
+            | Self::Flq { .. }
+            | Self::FltQ { .. }
+            | Self::FmaddQ { .. }
+            | Self::FmaxQ { .. }
+            | Self::FminQ { .. }
+            | Self::FmsubQ { .. }
+            | Self::FmulQ { .. }
+            | Self::FmvQX { .. }
+            | Self::FmvXQ { .. }
+            | Self::FnmaddQ { .. }
+            | Self::FnmsubQ { .. }
+            | Self::FsgnjQ { .. }
+            | Self::FsgnjnQ { .. }
+            | Self::FsgnjxQ { .. }
+            | Self::Fsq { .. }
+            | Self::FsqrtQ { .. }
+            | Self::FsubQ { .. } => Extensions::Q,
+            Self::CFld { .. } | Self::CFldsp { .. } | Self::CFsd { .. } | Self::CFsdsp { .. } => {
+                Extensions::C.union(Extensions::D)
+            }
+            Self::CAdd { .. }
+            | Self::CAddi { .. }
+            | Self::CAddi16Sp { .. }
+            | Self::CAddi4Spn { .. }
+            | Self::CAddiw { .. }
+            | Self::CAddw { .. }
+            | Self::CAnd { .. }
+            | Self::CAndi { .. }
+            | Self::CBeqz { .. }
+            | Self::CBnez { .. }
+            | Self::CEbreak
+            | Self::CJ { .. }
+            | Self::CJalr { .. }
+            | Self::CJr { .. }
+            | Self::CLd { .. }
+            | Self::CLdsp { .. }
+            | Self::CLi { .. }
+            | Self::CLui { .. }
+            | Self::CLw { .. }
+            | Self::CLwsp { .. }
+            | Self::CMv { .. }
+            | Self::CNop
+            | Self::COr { .. }
+            | Self::CSd { .. }
+            | Self::CSdsp { .. }
+            | Self::CSlli { .. }
+            | Self::CSrai { .. }
+            | Self::CSrli { .. }
+            | Self::CSub { .. }
+            | Self::CSubw { .. }
+            | Self::CSw { .. }
+            | Self::CSwsp { .. }
+            | Self::CXor { .. } => Extensions::C,
+            Self::AddUw { .. }
+            | Self::Sh1add { .. }
+            | Self::Sh1addUw { .. }
+            | Self::Sh2add { .. }
+            | Self::Sh2addUw { .. }
+            | Self::Sh3add { .. }
+            | Self::Sh3addUw { .. }
+            | Self::SlliUw { .. } => Extensions::ZBA,
+            Self::Andn { .. }
+            | Self::Clz { .. }
+            | Self::Clzw { .. }
+            | Self::Cpop { .. }
+            | Self::Cpopw { .. }
+            | Self::Ctz { .. }
+            | Self::Ctzw { .. }
+            | Self::Max { .. }
+            | Self::Maxu { .. }
+            | Self::Min { .. }
+            | Self::Minu { .. }
+            | Self::OrcB { .. }
+            | Self::Orn { .. }
+            | Self::Rev8 { .. }
+            | Self::Rol { .. }
+            | Self::Rolw { .. }
+            | Self::Ror { .. }
+            | Self::Rori { .. }
+            | Self::Roriw { .. }
+            | Self::Rorw { .. }
+            | Self::SextB { .. }
+            | Self::SextH { .. }
+            | Self::Xnor { .. }
+            | Self::ZextH { .. } => Extensions::ZBB,
+            Self::Clmul { .. } | Self::Clmulh { .. } | Self::Clmulr { .. } => Extensions::ZBC,
+            Self::Bclr { .. }
+            | Self::Bclri { .. }
+            | Self::Bext { .. }
+            | Self::Bexti { .. }
+            | Self::Binv { .. }
+            | Self::Binvi { .. }
+            | Self::Bset { .. }
+            | Self::Bseti { .. } => Extensions::ZBS,
+            _ => Extensions::NONE,
+        }
+    }
+
+    /// Returns the integer registers that this operation names as its
+    /// operands or destination. Registers that an operation uses implicitly,
+    /// such as the stack pointer in the compressed stack-relative loads, are
+    /// not included.
+    pub fn int_registers(&self) -> [Option<IntRegister>; 3] {
+        match *self {
+            Self::Add { rd, rs1, rs2 }
+            | Self::Addw { rd, rs1, rs2 }
+            | Self::And { rd, rs1, rs2 }
+            | Self::Or { rd, rs1, rs2 }
+            | Self::Sll { rd, rs1, rs2 }
+            | Self::Sllw { rd, rs1, rs2 }
+            | Self::Slt { rd, rs1, rs2 }
+            | Self::Sltu { rd, rs1, rs2 }
+            | Self::Sra { rd, rs1, rs2 }
+            | Self::Sraw { rd, rs1, rs2 }
+            | Self::Srl { rd, rs1, rs2 }
+            | Self::Srlw { rd, rs1, rs2 }
+            | Self::Sub { rd, rs1, rs2 }
+            | Self::Subw { rd, rs1, rs2 }
+            | Self::Xor { rd, rs1, rs2 }
+            | Self::Div { rd, rs1, rs2 }
+            | Self::Divu { rd, rs1, rs2 }
+            | Self::Divuw { rd, rs1, rs2 }
+            | Self::Divw { rd, rs1, rs2 }
+            | Self::Mul { rd, rs1, rs2 }
+            | Self::Mulh { rd, rs1, rs2 }
+            | Self::Mulhsu { rd, rs1, rs2 }
+            | Self::Mulhu { rd, rs1, rs2 }
+            | Self::Mulw { rd, rs1, rs2 }
+            | Self::Rem { rd, rs1, rs2 }
+            | Self::Remu { rd, rs1, rs2 }
+            | Self::Remuw { rd, rs1, rs2 }
+            | Self::Remw { rd, rs1, rs2 }
+            | Self::AmoaddD { rd, rs1, rs2, .. }
+            | Self::AmoaddW { rd, rs1, rs2, .. }
+            | Self::AmoandD { rd, rs1, rs2, .. }
+            | Self::AmoandW { rd, rs1, rs2, .. }
+            | Self::AmomaxD { rd, rs1, rs2, .. }
+            | Self::AmomaxW { rd, rs1, rs2, .. }
+            | Self::AmomaxuD { rd, rs1, rs2, .. }
+            | Self::AmomaxuW { rd, rs1, rs2, .. }
+            | Self::AmominD { rd, rs1, rs2, .. }
+            | Self::AmominW { rd, rs1, rs2, .. }
+            | Self::AmominuD { rd, rs1, rs2, .. }
+            | Self::AmominuW { rd, rs1, rs2, .. }
+            | Self::AmoorD { rd, rs1, rs2, .. }
+            | Self::AmoorW { rd, rs1, rs2, .. }
+            | Self::AmoswapD { rd, rs1, rs2, .. }
+            | Self::AmoswapW { rd, rs1, rs2, .. }
+            | Self::AmoxorD { rd, rs1, rs2, .. }
+            | Self::AmoxorW { rd, rs1, rs2, .. }
+            | Self::ScD { rd, rs1, rs2, .. }
+            | Self::ScW { rd, rs1, rs2, .. }
+            | Self::AddUw { rd, rs1, rs2 }
+            | Self::Sh1add { rd, rs1, rs2 }
+            | Self::Sh1addUw { rd, rs1, rs2 }
+            | Self::Sh2add { rd, rs1, rs2 }
+            | Self::Sh2addUw { rd, rs1, rs2 }
+            | Self::Sh3add { rd, rs1, rs2 }
+            | Self::Sh3addUw { rd, rs1, rs2 }
+            | Self::Andn { rd, rs1, rs2 }
+            | Self::Max { rd, rs1, rs2 }
+            | Self::Maxu { rd, rs1, rs2 }
+            | Self::Min { rd, rs1, rs2 }
+            | Self::Minu { rd, rs1, rs2 }
+            | Self::Orn { rd, rs1, rs2 }
+            | Self::Rol { rd, rs1, rs2 }
+            | Self::Rolw { rd, rs1, rs2 }
+            | Self::Ror { rd, rs1, rs2 }
+            | Self::Rorw { rd, rs1, rs2 }
+            | Self::Xnor { rd, rs1, rs2 }
+            | Self::Clmul { rd, rs1, rs2 }
+            | Self::Clmulh { rd, rs1, rs2 }
+            | Self::Clmulr { rd, rs1, rs2 }
+            | Self::Bclr { rd, rs1, rs2 }
+            | Self::Bext { rd, rs1, rs2 }
+            | Self::Binv { rd, rs1, rs2 }
+            | Self::Bset { rd, rs1, rs2 } => [Some(rd), Some(rs1), Some(rs2)],
+            Self::Addi { rd, rs1, .. }
+            | Self::Addiw { rd, rs1, .. }
+            | Self::Andi { rd, rs1, .. }
+            | Self::Jalr { rd, rs1, .. }
+            | Self::Lb { rd, rs1, .. }
+            | Self::Lbu { rd, rs1, .. }
+            | Self::Ld { rd, rs1, .. }
+            | Self::Lh { rd, rs1, .. }
+            | Self::Lhu { rd, rs1, .. }
+            | Self::Lw { rd, rs1, .. }
+            | Self::Lwu { rd, rs1, .. }
+            | Self::Ori { rd, rs1, .. }
+            | Self::Slli { rd, rs1, .. }
+            | Self::Slliw { rd, rs1, .. }
+            | Self::Slti { rd, rs1, .. }
+            | Self::Sltiu { rd, rs1, .. }
+            | Self::Srai { rd, rs1, .. }
+            | Self::Sraiw { rd, rs1, .. }
+            | Self::Srli { rd, rs1, .. }
+            | Self::Srliw { rd, rs1, .. }
+            | Self::Xori { rd, rs1, .. }
+            | Self::LrD { rd, rs1, .. }
+            | Self::LrW { rd, rs1, .. }
+            | Self::Csrrc { rd, rs1, .. }
+            | Self::Csrrs { rd, rs1, .. }
+            | Self::Csrrw { rd, rs1, .. }
+            | Self::CJalr { rd, rs1 }
+            | Self::CJr { rd, rs1 }
+            | Self::CLd { rd, rs1, .. }
+            | Self::CLw { rd, rs1, .. }
+            | Self::SlliUw { rd, rs1, .. }
+            | Self::Clz { rd, rs1 }
+            | Self::Clzw { rd, rs1 }
+            | Self::Cpop { rd, rs1 }
+            | Self::Cpopw { rd, rs1 }
+            | Self::Ctz { rd, rs1 }
+            | Self::Ctzw { rd, rs1 }
+            | Self::OrcB { rd, rs1 }
+            | Self::Rev8 { rd, rs1 }
+            | Self::Rori { rd, rs1, .. }
+            | Self::Roriw { rd, rs1, .. }
+            | Self::SextB { rd, rs1 }
+            | Self::SextH { rd, rs1 }
+            | Self::ZextH { rd, rs1 }
+            | Self::Bclri { rd, rs1, .. }
+            | Self::Bexti { rd, rs1, .. }
+            | Self::Binvi { rd, rs1, .. }
+            | Self::Bseti { rd, rs1, .. } => [Some(rd), Some(rs1), None],
+            Self::Auipc { rd, .. }
+            | Self::Jal { rd, .. }
+            | Self::Lui { rd, .. }
+            | Self::Csrrci { rd, .. }
+            | Self::Csrrsi { rd, .. }
+            | Self::Csrrwi { rd, .. }
+            | Self::FclassS { rd, .. }
+            | Self::FcvtLS { rd, .. }
+            | Self::FcvtLuS { rd, .. }
+            | Self::FcvtWS { rd, .. }
+            | Self::FcvtWuS { rd, .. }
+            | Self::FeqS { rd, .. }
+            | Self::FleS { rd, .. }
+            | Self::FltS { rd, .. }
+            | Self::FmvXS { rd, .. }
+            | Self::FclassD { rd, .. }
+            | Self::FcvtLD { rd, .. }
+            | Self::FcvtLuD { rd, .. }
+            | Self::FcvtWD { rd, .. }
+            | Self::FcvtWuD { rd, .. }
+            | Self::FeqD { rd, .. }
+            | Self::FleD { rd, .. }
+            | Self::FltD { rd, .. }
+            | Self::FmvXD { rd, .. }
+            | Self::FclassQ { rd, .. }
+            | Self::FcvtLQ { rd, .. }
+            | Self::FcvtLuQ { rd, .. }
+            | Self::FcvtWQ { rd, .. }
+            | Self::FcvtWuQ { rd, .. }
+            | Self::FeqQ { rd, .. }
+            | Self::FleQ { rd, .. }
+            | Self::FltQ { rd, .. }
+            | Self::FmvXQ { rd, .. }
+            | Self::CAddi4Spn { rd, .. }
+            | Self::CLdsp { rd, .. }
+            | Self::CLui { rd, .. }
+            | Self::CLwsp { rd, .. } => [Some(rd), None, None],
+            Self::Beq { rs1, rs2, .. }
+            | Self::Bge { rs1, rs2, .. }
+            | Self::Bgeu { rs1, rs2, .. }
+            | Self::Blt { rs1, rs2, .. }
+            | Self::Bltu { rs1, rs2, .. }
+            | Self::Bne { rs1, rs2, .. }
+            | Self::Sb { rs1, rs2, .. }
+            | Self::Sd { rs1, rs2, .. }
+            | Self::Sh { rs1, rs2, .. }
+            | Self::Sw { rs1, rs2, .. }
+            | Self::SfenceVma { rs1, rs2 }
+            | Self::CSd { rs1, rs2, .. }
+            | Self::CSw { rs1, rs2, .. } => [Some(rs1), Some(rs2), None],
+            Self::SfenceVm { rs1 }
+            | Self::FcvtSL { rs1, .. }
+            | Self::FcvtSLu { rs1, .. }
+            | Self::FcvtSW { rs1, .. }
+            | Self::FcvtSWu { rs1, .. }
+            | Self::Flw { rs1, .. }
+            | Self::FmvSX { rs1, .. }
+            | Self::Fsw { rs1, .. }
+            | Self::FcvtDL { rs1, .. }
+            | Self::FcvtDLu { rs1, .. }
+            | Self::FcvtDW { rs1, .. }
+            | Self::FcvtDWu { rs1, .. }
+            | Self::Fld { rs1, .. }
+            | Self::FmvDX { rs1, .. }
+            | Self::Fsd { rs1, .. }
+            | Self::FcvtQL { rs1, .. }
+            | Self::FcvtQLu { rs1, .. }
+            | Self::FcvtQW { rs1, .. }
+            | Self::FcvtQWu { rs1, .. }
+            | Self::Flq { rs1, .. }
+            | Self::FmvQX { rs1, .. }
+            | Self::Fsq { rs1, .. }
+            | Self::CBeqz { rs1, .. }
+            | Self::CBnez { rs1, .. }
+            | Self::CFld { rs1, .. }
+            | Self::CFsd { rs1, .. } => [Some(rs1), None, None],
+            Self::CAdd { rs1rd, rs2 }
+            | Self::CAddw { rs1rd, rs2 }
+            | Self::CAnd { rs1rd, rs2 }
+            | Self::COr { rs1rd, rs2 }
+            | Self::CSub { rs1rd, rs2 }
+            | Self::CSubw { rs1rd, rs2 }
+            | Self::CXor { rs1rd, rs2 } => [Some(rs1rd), Some(rs2), None],
+            Self::CAddi { rs1rd, .. }
+            | Self::CAddi16Sp { rs1rd, .. }
+            | Self::CAddiw { rs1rd, .. }
+            | Self::CAndi { rs1rd, .. }
+            | Self::CLi { rs1rd, .. }
+            | Self::CSlli { rs1rd, .. }
+            | Self::CSrai { rs1rd, .. }
+            | Self::CSrli { rs1rd, .. } => [Some(rs1rd), None, None],
+            Self::CMv { rd, rs2 } => [Some(rd), Some(rs2), None],
+            Self::CSdsp { rs2, .. } | Self::CSwsp { rs2, .. } => [Some(rs2), None, None],
+            _ => [None; 3],
+        }
+    }
+
     pub fn decode_from_raw(raw: RawInstruction) -> Self {
         let opcode = raw.opcode();
         if opcode == (Opcode::Amo as u8) {
@@ -1977,6 +2647,78 @@ impl OperationRV64 {
                     rs1: raw.rs1(),
                     rs2: raw.rs2(),
                 }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000111000000110011,
+            ) {
+                Self::Andn {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01001000000000000001000000110011,
+            ) {
+                Self::Bclr {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01001000000000000101000000110011,
+            ) {
+                Self::Bext {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01101000000000000001000000110011,
+            ) {
+                Self::Binv {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00101000000000000001000000110011,
+            ) {
+                Self::Bset {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000001000000110011,
+            ) {
+                Self::Clmul {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000011000000110011,
+            ) {
+                Self::Clmulh {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000010000000110011,
+            ) {
+                Self::Clmulr {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
                 0b00000010000000000100000000110011,
@@ -1995,6 +2737,42 @@ impl OperationRV64 {
                     rs1: raw.rs1(),
                     rs2: raw.rs2(),
                 }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000110000000110011,
+            ) {
+                Self::Max {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000111000000110011,
+            ) {
+                Self::Maxu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000100000000110011,
+            ) {
+                Self::Min {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001010000000000101000000110011,
+            ) {
+                Self::Minu {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
                 0b00000010000000000000000000110011,
@@ -2040,6 +2818,15 @@ impl OperationRV64 {
                     rs1: raw.rs1(),
                     rs2: raw.rs2(),
                 }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000110000000110011,
+            ) {
+                Self::Orn {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
                 0b00000010000000000110000000110011,
@@ -2058,6 +2845,51 @@ impl OperationRV64 {
                     rs1: raw.rs1(),
                     rs2: raw.rs2(),
                 }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01100000000000000001000000110011,
+            ) {
+                Self::Rol {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01100000000000000101000000110011,
+            ) {
+                Self::Ror {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100000000000000010000000110011,
+            ) {
+                Self::Sh1add {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100000000000000100000000110011,
+            ) {
+                Self::Sh2add {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100000000000000110000000110011,
+            ) {
+                Self::Sh3add {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
                 0b00000000000000000001000000110011,
@@ -2112,6 +2944,15 @@ impl OperationRV64 {
                     rs1: raw.rs1(),
                     rs2: raw.rs2(),
                 }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01000000000000000100000000110011,
+            ) {
+                Self::Xnor {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
                 0b00000000000000000100000000110011,
@@ -2126,6 +2967,15 @@ impl OperationRV64 {
             }
         } else if opcode == (Opcode::Op32 as u8) {
             if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00001000000000000000000000111011,
+            ) {
+                Self::AddUw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
                 0b11111110000000000111000001111111,
                 0b00000000000000000000000000111011,
             ) {
@@ -2179,6 +3029,51 @@ impl OperationRV64 {
                     rs1: raw.rs1(),
                     rs2: raw.rs2(),
                 }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01100000000000000001000000111011,
+            ) {
+                Self::Rolw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01100000000000000101000000111011,
+            ) {
+                Self::Rorw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100000000000000010000000111011,
+            ) {
+                Self::Sh1addUw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100000000000000100000000111011,
+            ) {
+                Self::Sh2addUw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b00100000000000000110000000111011,
+            ) {
+                Self::Sh3addUw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    rs2: raw.rs2(),
+                }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
                 0b00000000000000000001000000111011,
@@ -2215,6 +3110,14 @@ impl OperationRV64 {
                     rs1: raw.rs1(),
                     rs2: raw.rs2(),
                 }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b00001000000000000100000000111011,
+            ) {
+                Self::ZextH {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
             } else {
                 Self::Invalid
             }
@@ -2946,6 +3849,74 @@ impl OperationRV64 {
                     rs1: raw.rs1(),
                     simm: raw.imm12(),
                 }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b01001000000000000001000000010011,
+            ) {
+                Self::Bclri {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt6(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b01001000000000000101000000010011,
+            ) {
+                Self::Bexti {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt6(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b01101000000000000001000000010011,
+            ) {
+                Self::Binvi {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt6(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00101000000000000001000000010011,
+            ) {
+                Self::Bseti {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt6(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01100000000000000001000000010011,
+            ) {
+                Self::Clz {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01100000001000000001000000010011,
+            ) {
+                Self::Cpop {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01100000000100000001000000010011,
+            ) {
+                Self::Ctz {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b00101000011100000101000000010011,
+            ) {
+                Self::OrcB {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
             } else if raw.matches(
                 0b00000000000000000111000001111111,
                 0b00000000000000000110000000010011,
@@ -2955,6 +3926,39 @@ impl OperationRV64 {
                     rs1: raw.rs1(),
                     simm: raw.imm12(),
                 }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01101011100000000101000000010011,
+            ) {
+                Self::Rev8 {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b01100000000000000101000000010011,
+            ) {
+                Self::Rori {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt6(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01100000010000000001000000010011,
+            ) {
+                Self::SextB {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01100000010100000001000000010011,
+            ) {
+                Self::SextH {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
             } else if raw.matches(
                 0b11111000000000000111000001111111,
                 0b00000000000000000001000000010011,
@@ -3022,6 +4026,48 @@ impl OperationRV64 {
                     rs1: raw.rs1(),
                     simm: raw.imm12(),
                 }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01100000000000000001000000011011,
+            ) {
+                Self::Clzw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01100000001000000001000000011011,
+            ) {
+                Self::Cpopw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111111111100000111000001111111,
+                0b01100000000100000001000000011011,
+            ) {
+                Self::Ctzw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                }
+            } else if raw.matches(
+                0b11111110000000000111000001111111,
+                0b01100000000000000101000000011011,
+            ) {
+                Self::Roriw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt5(),
+                }
+            } else if raw.matches(
+                0b11111100000000000111000001111111,
+                0b00001000000000000001000000011011,
+            ) {
+                Self::SlliUw {
+                    rd: raw.rd(),
+                    rs1: raw.rs1(),
+                    shamt: raw.shamt6(),
+                }
             } else if raw.matches(
                 0b11111110000000000111000001111111,
                 0b00000000000000000001000000011011,
@@ -1,14 +1,25 @@
-use crate::data::{Float, Int, LongwordUnsigned, WordUnsigned, Zero};
-use crate::instruction::{Operation, OperationRV32, OperationRV64};
+use crate::data::{Float, Int, LongwordUnsigned, QuadwordUnsigned, WordUnsigned, Zero};
+use crate::instruction::{Operation, OperationRV128, OperationRV32, OperationRV64};
 
 /// Represents a RISC-V base ISA, collecting its integer data type and
 /// operation type together as associated types for more convenient
 /// use as a single type parameter on types that are generic over entire base
 /// ISAs, rather than individual aspects thereof.
 pub trait BaseISA {
+    /// The width of the integer registers in bits.
+    const XLEN: usize;
+
+    /// The base ISA's own entry in the `misa` CSR: `Extensions::I`, or
+    /// `Extensions::E` for the embedded base ISAs.
+    const BASE: Extensions;
+
     type Int: Int;
     type Float: Float;
     type Operation: Operation;
+
+    /// The storage for the integer register file, which is an array of 32
+    /// registers or of 16 for the embedded base ISAs.
+    type IntRegisters: AsRef<[Self::Int]> + AsMut<[Self::Int]> + Copy + Default;
 }
 
 /// A compile-time-only type that represents the RISC-V 32-bit base ISA in
@@ -16,9 +27,26 @@ pub trait BaseISA {
 pub enum RV32 {}
 
 impl BaseISA for RV32 {
+    const XLEN: usize = 32;
+    const BASE: Extensions = Extensions::I;
     type Int = WordUnsigned;
     type Float = f64;
     type Operation = OperationRV32;
+    type IntRegisters = [WordUnsigned; 32];
+}
+
+/// A compile-time-only type that represents the RISC-V 32-bit embedded base
+/// ISA, RV32E, which is the same as RV32 except that it has only the integer
+/// registers x0 to x15.
+pub enum RV32E {}
+
+impl BaseISA for RV32E {
+    const XLEN: usize = 32;
+    const BASE: Extensions = Extensions::E;
+    type Int = WordUnsigned;
+    type Float = f64;
+    type Operation = OperationRV32;
+    type IntRegisters = [WordUnsigned; 16];
 }
 
 /// A compile-time-only type that represents the RISC-V 64-bit base ISA in
@@ -26,7 +54,307 @@ impl BaseISA for RV32 {
 pub enum RV64 {}
 
 impl BaseISA for RV64 {
+    const XLEN: usize = 64;
+    const BASE: Extensions = Extensions::I;
+    type Int = LongwordUnsigned;
+    type Float = f64;
+    type Operation = OperationRV64;
+    type IntRegisters = [LongwordUnsigned; 32];
+}
+
+/// A compile-time-only type that represents the RISC-V 64-bit embedded base
+/// ISA, RV64E, which is the same as RV64 except that it has only the integer
+/// registers x0 to x15.
+pub enum RV64E {}
+
+impl BaseISA for RV64E {
+    const XLEN: usize = 64;
+    const BASE: Extensions = Extensions::E;
     type Int = LongwordUnsigned;
     type Float = f64;
     type Operation = OperationRV64;
+    type IntRegisters = [LongwordUnsigned; 16];
+}
+
+/// A compile-time-only type that represents the draft RISC-V 128-bit base ISA
+/// in type parameters that require a `BaseISA` implementation.
+pub enum RV128 {}
+
+impl BaseISA for RV128 {
+    const XLEN: usize = 128;
+    const BASE: Extensions = Extensions::I;
+    type Int = QuadwordUnsigned;
+    type Float = f64;
+    type Operation = OperationRV128;
+    type IntRegisters = [QuadwordUnsigned; 32];
+}
+
+/// A set of standard ISA extensions that a hart implements, as could be
+/// described by an ISA string such as "rv32imac_zicsr".
+///
+/// The single-letter extensions are represented by the same bits as in the
+/// `misa` CSR, with bit 0 for "A" through to bit 25 for "Z". The base integer
+/// ISA itself counts as the "I" extension, or as "E" for the embedded base
+/// ISAs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extensions(u32);
+
+impl Extensions {
+    /// No extensions at all, not even the base integer ISA.
+    pub const NONE: Self = Self(0);
+    /// The base integer ISA.
+    pub const I: Self = Self::letter(b'i');
+    /// The embedded base integer ISA, which has only 16 integer registers.
+    pub const E: Self = Self::letter(b'e');
+    /// Integer multiplication and division.
+    pub const M: Self = Self::letter(b'm');
+    /// Atomic instructions.
+    pub const A: Self = Self::letter(b'a');
+    /// Single-precision floating point.
+    pub const F: Self = Self::letter(b'f');
+    /// Double-precision floating point.
+    pub const D: Self = Self::letter(b'd');
+    /// Quad-precision floating point.
+    pub const Q: Self = Self::letter(b'q');
+    /// Compressed instructions.
+    pub const C: Self = Self::letter(b'c');
+    /// Vector instructions.
+    pub const V: Self = Self::letter(b'v');
+    /// The control and status register instructions.
+    pub const ZICSR: Self = Self(1 << 26);
+    /// The `fence.i` instruction.
+    pub const ZIFENCEI: Self = Self(1 << 27);
+    /// Address generation: the shift-and-add instructions.
+    pub const ZBA: Self = Self(1 << 28);
+    /// Basic bit manipulation, such as `andn`, `clz` and `rev8`.
+    pub const ZBB: Self = Self(1 << 29);
+    /// Carry-less multiplication.
+    pub const ZBC: Self = Self(1 << 30);
+    /// Single-bit instructions, such as `bset` and `bclr`.
+    pub const ZBS: Self = Self(1 << 31);
+
+    /// The general-purpose set abbreviated as "G" in ISA strings.
+    pub const G: Self = Self::I
+        .union(Self::M)
+        .union(Self::A)
+        .union(Self::F)
+        .union(Self::D)
+        .union(Self::ZICSR)
+        .union(Self::ZIFENCEI);
+
+    /// Every extension that the instruction decoder supports.
+    pub const ALL: Self = Self::G
+        .union(Self::Q)
+        .union(Self::C)
+        .union(Self::V)
+        .union(Self::ZBA)
+        .union(Self::ZBB)
+        .union(Self::ZBC)
+        .union(Self::ZBS);
+
+    // Bits that can appear in misa: one for each letter of the alphabet.
+    const LETTERS: u32 = (1 << 26) - 1;
+
+    const fn letter(c: u8) -> Self {
+        Self(1 << (c - b'a'))
+    }
+
+    /// Returns the set containing the extensions from both sets.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns the set containing the extensions from this set that are not
+    /// in the other.
+    pub const fn without(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Returns true if every extension in the other set is also in this one.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the single-letter extensions as they would appear in the
+    /// "Extensions" field of the `misa` CSR.
+    pub const fn misa_letters(self) -> u32 {
+        self.0 & Self::LETTERS
+    }
+
+    /// Returns a copy of this set with its single-letter extensions replaced
+    /// by the ones in the given value of the `misa` "Extensions" field. The
+    /// multi-letter extensions are unchanged.
+    pub const fn with_misa_letters(self, letters: u32) -> Self {
+        Self((self.0 & !Self::LETTERS) | (letters & Self::LETTERS))
+    }
+
+    /// Parses an ISA string as used by toolchains and in device trees, such
+    /// as "rv32imac_zicsr" or "RV64GC", for a hart with the given base ISA.
+    ///
+    /// The string must start with "rv", the XLEN of the base ISA and then
+    /// either "i" or "g", or "e" for the embedded base ISAs. Version numbers
+    /// such as the "2p0" in "m2p0" are accepted and ignored. As required by
+    /// the ISA manual's dependency rules, "d" implies "f" and "q" implies "d"
+    /// even if they aren't named.
+    pub fn parse<ISA: BaseISA>(isa: &str) -> Result<Self, IsaStringError> {
+        let isa = isa.as_bytes();
+        if isa.len() < 2 || !isa[..2].eq_ignore_ascii_case(b"rv") {
+            return Err(IsaStringError::Base);
+        }
+        let digits = isa[2..].iter().take_while(|c| c.is_ascii_digit()).count();
+        let xlen = isa[2..2 + digits].iter().fold(0usize, |n, c| {
+            n.saturating_mul(10).saturating_add((c - b'0') as usize)
+        });
+        if xlen != ISA::XLEN {
+            return Err(IsaStringError::Base);
+        }
+        let rest = &isa[2 + digits..];
+        let mut ext = match (rest.first().map(u8::to_ascii_lowercase), ISA::BASE) {
+            (Some(b'i'), Self::I) => Self::I,
+            (Some(b'g'), Self::I) => Self::G,
+            (Some(b'e'), Self::E) => Self::E,
+            _ => return Err(IsaStringError::Base),
+        };
+
+        for (i, part) in skip_version(&rest[1..]).split(|c| *c == b'_').enumerate() {
+            match part.first().map(u8::to_ascii_lowercase) {
+                // The base ISA letter may be followed directly by an
+                // underscore, but no other part may be empty.
+                None if i == 0 => {}
+                None => return Err(IsaStringError::Malformed),
+                Some(b'z' | b's' | b'x') => ext = ext.union(Self::named(part)?),
+                Some(_) => {
+                    let mut letters = part;
+                    while let Some((c, tail)) = letters.split_first() {
+                        ext = ext.union(match c.to_ascii_lowercase() {
+                            c @ (b'm' | b'a' | b'f' | b'd' | b'q' | b'c' | b'v') => Self::letter(c),
+                            b'g' => Self::G,
+                            c if c.is_ascii_lowercase() => return Err(IsaStringError::Unsupported),
+                            _ => return Err(IsaStringError::Malformed),
+                        });
+                        letters = skip_version(tail);
+                    }
+                }
+            }
+        }
+
+        if ext.contains(Self::Q) {
+            ext = ext.union(Self::D);
+        }
+        if ext.contains(Self::D) {
+            ext = ext.union(Self::F);
+        }
+        Ok(ext)
+    }
+
+    // Returns the multi-letter extension with the given name, which may be
+    // followed by a version number.
+    fn named(part: &[u8]) -> Result<Self, IsaStringError> {
+        let len = part
+            .iter()
+            .position(u8::is_ascii_digit)
+            .unwrap_or(part.len());
+        if !skip_version(&part[len..]).is_empty() {
+            return Err(IsaStringError::Malformed);
+        }
+        let name = &part[..len];
+        if name.eq_ignore_ascii_case(b"zicsr") {
+            Ok(Self::ZICSR)
+        } else if name.eq_ignore_ascii_case(b"zifencei") {
+            Ok(Self::ZIFENCEI)
+        } else if name.eq_ignore_ascii_case(b"zba") {
+            Ok(Self::ZBA)
+        } else if name.eq_ignore_ascii_case(b"zbb") {
+            Ok(Self::ZBB)
+        } else if name.eq_ignore_ascii_case(b"zbc") {
+            Ok(Self::ZBC)
+        } else if name.eq_ignore_ascii_case(b"zbs") {
+            Ok(Self::ZBS)
+        } else if name.iter().all(u8::is_ascii_alphabetic) {
+            Err(IsaStringError::Unsupported)
+        } else {
+            Err(IsaStringError::Malformed)
+        }
+    }
+}
+
+// Skips a version number such as "2" or "2p1" at the start of the given
+// string, if there is one.
+fn skip_version(s: &[u8]) -> &[u8] {
+    let digits = |s: &[u8]| s.iter().take_while(|c| c.is_ascii_digit()).count();
+    let major = digits(s);
+    if major == 0 {
+        return s;
+    }
+    let s = &s[major..];
+    match s.split_first() {
+        Some((b'p' | b'P', tail)) if digits(tail) > 0 => &tail[digits(tail)..],
+        _ => s,
+    }
+}
+
+/// The reasons that `Extensions::parse` can reject an ISA string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IsaStringError {
+    /// The string doesn't start with "rv" and the XLEN of the expected base
+    /// ISA, followed by the letter for a base integer ISA.
+    Base,
+    /// The string names an extension that this implementation doesn't
+    /// support.
+    Unsupported,
+    /// The string isn't a well-formed ISA string.
+    Malformed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Extensions, IsaStringError, RV32, RV32E, RV64};
+
+    #[test]
+    fn parse() {
+        let parse32 = Extensions::parse::<RV32>;
+        let imac = Extensions::I
+            .union(Extensions::M)
+            .union(Extensions::A)
+            .union(Extensions::C);
+        assert_eq!(parse32("rv32imac"), Ok(imac));
+        assert_eq!(parse32("rv32imac_zicsr"), Ok(imac.union(Extensions::ZICSR)));
+        assert_eq!(
+            parse32("RV32I2p1M2_A_C_Zicsr2p0_zifencei"),
+            Ok(imac.union(Extensions::ZICSR).union(Extensions::ZIFENCEI))
+        );
+        assert_eq!(
+            Extensions::parse::<RV64>("rv64gc"),
+            Ok(Extensions::G.union(Extensions::C))
+        );
+        assert_eq!(
+            parse32("rv32iq"),
+            Ok(Extensions::I
+                .union(Extensions::F)
+                .union(Extensions::D)
+                .union(Extensions::Q))
+        );
+        assert_eq!(
+            parse32("rv32gqcv_zba_zbb_zbc1p0_zbs"),
+            Ok(Extensions::ALL)
+        );
+        assert_eq!(
+            Extensions::parse::<RV32E>("rv32emc"),
+            Ok(Extensions::E.union(Extensions::M).union(Extensions::C))
+        );
+        assert_eq!(imac.misa_letters(), 0b1_0001_0000_0101);
+
+        assert_eq!(parse32("rv64i"), Err(IsaStringError::Base));
+        assert_eq!(parse32("rv32"), Err(IsaStringError::Base));
+        assert_eq!(parse32("rv32mac"), Err(IsaStringError::Base));
+        assert_eq!(parse32("rv32e"), Err(IsaStringError::Base));
+        assert_eq!(
+            Extensions::parse::<RV32E>("rv32g"),
+            Err(IsaStringError::Base)
+        );
+        assert_eq!(parse32("rv32imh"), Err(IsaStringError::Unsupported));
+        assert_eq!(parse32("rv32i_zbkb"), Err(IsaStringError::Unsupported));
+        assert_eq!(parse32("rv32i__m"), Err(IsaStringError::Malformed));
+        assert_eq!(parse32("rv32im-c"), Err(IsaStringError::Malformed));
+    }
 }
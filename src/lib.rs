@@ -1,5 +1,8 @@
 #![no_std]
 
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
 mod cpu;
 mod data;
 mod exception;
@@ -9,6 +12,7 @@ mod isa;
 mod memory;
 mod raw_instruction;
 mod register;
+mod vector;
 
 pub use cpu::CPU;
 pub use data::Byte;
@@ -16,23 +20,63 @@ pub use data::{Float, Int, Zero};
 pub use data::{HalfwordSigned, LongwordSigned, QuadwordSigned, WordSigned};
 pub use data::{HalfwordUnsigned, LongwordUnsigned, QuadwordUnsigned, WordUnsigned};
 pub use exception::{Cause, ExceptionCause, InterruptCause};
-pub use hart::{Hart, SingleThreadUserHart};
+pub use hart::{Hart, HartSnapshot, SingleThreadUserHart, SingleThreadUserHartCSRs};
 pub use instruction::{Instruction, Operation};
-pub use isa::BaseISA;
-pub use memory::{AddressConverter, AddressTransformer, Bus, Memory, MemoryError};
-pub use raw_instruction::RawInstruction;
-pub use register::{ControlStatusRegister, FloatRegister, IntRegister, Register};
+pub use isa::{BaseISA, Extensions, IsaStringError};
+pub use memory::{AccessKind, Fault, Memory, MemoryError, MisalignedAccess};
+pub use memory::{AddressConverter, AddressTransformer, Alignment, AtomicOp, Bus, FenceSet};
+#[cfg(feature = "std")]
+pub use memory::{AtomicMemory, MemorySnapshot, SnapshotMemory, SparseMemory, PAGE_SIZE};
+pub use raw_instruction::{RawInstruction, MAX_INSTRUCTION_LENGTH};
+pub use register::{ControlStatusRegister, FloatRegister, IntRegister, Register, VectorRegister};
+pub use vector::{VectorRegisters, VectorSnapshot, ELEN, MAX_VLEN};
+
+/// Lockstep comparison of execution against a reference simulator's log.
+pub mod cosim;
+
+/// Host-side breakpoints and watchpoints for debugging guest programs.
+#[cfg(feature = "std")]
+pub mod debug;
+
+/// Parsing and loading of ELF executable images.
+pub mod elf;
 
 /// Instruction execution engines.
 pub mod exec;
 
+/// A GDB remote serial protocol server for debugging guest programs.
+#[cfg(feature = "std")]
+pub mod gdb;
+
+/// The host-target interface device used by Spike and the riscv-tests suite.
+pub mod htif;
+
+/// Emulation of the Linux user-mode execution environment.
+pub mod linux;
+
+/// Running memory-model litmus tests under many interleavings of their harts.
+#[cfg(feature = "std")]
+pub mod litmus;
+
+/// Deterministic recording and replay of execution, with reverse stepping.
+#[cfg(feature = "std")]
+pub mod replay;
+
+/// Support for the RISC-V semihosting interface used by bare-metal programs.
+pub mod semihosting;
+
+/// Multiple harts sharing a memory bus, with deterministic scheduling.
+#[cfg(feature = "std")]
+pub mod smp;
+
 /// Contains the instruction enum types for each base ISA. (Implementations of `Operation`.)
 pub mod ops {
     pub use crate::instruction::OperationRV32 as RV32;
     pub use crate::instruction::OperationRV64 as RV64;
+    pub use crate::instruction::OperationRV128 as RV128;
 }
 
 /// Contains the marker types representing the base ISAs. (Implementations of `BaseISA`.)
 pub mod isas {
-    pub use crate::isa::{RV32, RV64};
+    pub use crate::isa::{RV128, RV32, RV32E, RV64, RV64E};
 }
@@ -0,0 +1,273 @@
+mod process_32;
+mod syscall_32;
+
+#[cfg(feature = "std")]
+mod host_fs;
+
+pub use process_32::init_process_rv32;
+
+#[cfg(feature = "std")]
+pub use host_fs::{HostFileSystem, ReadWrite};
+
+use core::ops::Range;
+
+/// Represents a Linux error number, as would be returned (negated) in the
+/// `a0` register from a failing system call.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Errno(pub i32);
+
+impl Errno {
+    pub const EPERM: Errno = Errno(1);
+    pub const ENOENT: Errno = Errno(2);
+    pub const EIO: Errno = Errno(5);
+    pub const EBADF: Errno = Errno(9);
+    pub const ENOMEM: Errno = Errno(12);
+    pub const EACCES: Errno = Errno(13);
+    pub const EFAULT: Errno = Errno(14);
+    pub const EEXIST: Errno = Errno(17);
+    pub const ENOTDIR: Errno = Errno(20);
+    pub const EISDIR: Errno = Errno(21);
+    pub const EINVAL: Errno = Errno(22);
+    pub const EMFILE: Errno = Errno(24);
+    pub const ENOTTY: Errno = Errno(25);
+    pub const ESPIPE: Errno = Errno(29);
+    pub const ENAMETOOLONG: Errno = Errno(36);
+    pub const ENOSYS: Errno = Errno(38);
+}
+
+/// Flag bits for the `flags` argument to `FileSystem::open`, using the
+/// values from the Linux RISC-V ABI.
+pub mod open_flags {
+    pub const O_RDONLY: u32 = 0o0;
+    pub const O_WRONLY: u32 = 0o1;
+    pub const O_RDWR: u32 = 0o2;
+    pub const O_ACCMODE: u32 = 0o3;
+    pub const O_CREAT: u32 = 0o100;
+    pub const O_EXCL: u32 = 0o200;
+    pub const O_TRUNC: u32 = 0o1000;
+    pub const O_APPEND: u32 = 0o2000;
+    pub const O_DIRECTORY: u32 = 0o200000;
+    pub const O_CLOEXEC: u32 = 0o2000000;
+}
+
+/// A time value split into whole seconds and nanoseconds, as used by the
+/// Linux `struct timespec`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Timespec {
+    pub sec: i64,
+    pub nsec: i64,
+}
+
+/// Describes a file in the terms of the Linux `struct stat`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FileStat {
+    pub dev: u64,
+    pub ino: u64,
+    pub mode: u32,
+    pub nlink: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub rdev: u64,
+    pub size: i64,
+    pub blksize: i32,
+    pub blocks: i64,
+    pub atime: Timespec,
+    pub mtime: Timespec,
+    pub ctime: Timespec,
+}
+
+/// The origin for a `FileSystem::seek` call, matching the `whence` argument
+/// of the Linux `lseek` system call.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SeekFrom {
+    Start,
+    Current,
+    End,
+}
+
+/// Provides the file-related services for a `SyscallEmulator`.
+///
+/// File descriptors are owned by the implementation, so the guest program's
+/// file descriptor numbers are passed through verbatim. An implementation
+/// will typically pre-populate descriptors 0, 1 and 2 with the standard
+/// input, output and error streams.
+///
+/// Paths are passed as raw bytes, exactly as the guest program provided them,
+/// without the terminating NUL.
+pub trait FileSystem {
+    /// Opens the file at the given path, returning a new file descriptor.
+    fn open(&mut self, path: &[u8], flags: u32, mode: u32) -> Result<i32, Errno>;
+
+    /// Closes the given file descriptor.
+    fn close(&mut self, fd: i32) -> Result<(), Errno>;
+
+    /// Reads from the given file descriptor into the given buffer, returning
+    /// the number of bytes read. Zero indicates end of file.
+    fn read(&mut self, fd: i32, buf: &mut [u8]) -> Result<usize, Errno>;
+
+    /// Writes the given buffer to the given file descriptor, returning the
+    /// number of bytes written.
+    fn write(&mut self, fd: i32, buf: &[u8]) -> Result<usize, Errno>;
+
+    /// Moves the file offset of the given file descriptor, returning the
+    /// new offset from the start of the file.
+    fn seek(&mut self, fd: i32, offset: i64, whence: SeekFrom) -> Result<u64, Errno>;
+
+    /// Returns metadata about the file open as the given file descriptor.
+    fn stat(&mut self, fd: i32) -> Result<FileStat, Errno>;
+
+    /// Returns true if the given file descriptor refers to a terminal.
+    ///
+    /// The default implementation reports that no descriptors are terminals.
+    fn is_terminal(&mut self, _fd: i32) -> bool {
+        false
+    }
+}
+
+/// Represents the outcome of `SyscallEmulator` handling an environment call.
+#[derive(Debug, PartialEq)]
+pub enum SyscallStatus {
+    /// The system call was handled and its result written to the hart's
+    /// registers, so execution can continue.
+    Handled,
+
+    /// The guest program requested termination with the given exit status,
+    /// via either `exit` or `exit_group`.
+    Exited(i32),
+
+    /// The system call number given in the argument is not supported by
+    /// this emulator. `-ENOSYS` has already been written as the result, so
+    /// the caller may either continue execution or stop.
+    Unsupported(u32),
+}
+
+/// Emulates a subset of the Linux system call interface for a user-mode
+/// program, servicing the `ecall` instructions reported by an executor as
+/// `ExecStatus::EnvironmentCall`.
+///
+/// File operations are delegated to an implementation of `FileSystem`.
+/// Memory management calls (`brk`, `mmap` and `munmap`) are served from
+/// address ranges in the hart's memory that the caller reserves when
+/// constructing the emulator: the program break grows upwards through the
+/// heap range, while anonymous and file mappings are allocated downwards
+/// from the end of the mapping range.
+pub struct SyscallEmulator<FS: FileSystem> {
+    fs: FS,
+    heap: Range<u64>,
+    brk: u64,
+    mmap: Range<u64>,
+    mmap_next: u64,
+    clock: fn() -> Timespec,
+    random_state: u64,
+}
+
+impl<FS: FileSystem> SyscallEmulator<FS> {
+    /// Creates an emulator that will use the given filesystem, with the
+    /// program break starting at the beginning of `heap` and mappings being
+    /// allocated from the end of `mmap`.
+    pub fn new(fs: FS, heap: Range<u64>, mmap: Range<u64>) -> Self {
+        Self {
+            fs,
+            brk: heap.start,
+            heap,
+            mmap_next: mmap.end,
+            mmap,
+            clock: default_clock,
+            random_state: 0x853c49e6748fea9b,
+        }
+    }
+
+    /// Replaces the function used to answer `clock_gettime` and similar
+    /// calls. The default clock uses the host system time when the `std`
+    /// feature is enabled, or always returns zero otherwise.
+    pub fn set_clock(&mut self, clock: fn() -> Timespec) {
+        self.clock = clock;
+    }
+
+    /// Borrows the filesystem the emulator is using.
+    pub fn file_system(&mut self) -> &mut FS {
+        &mut self.fs
+    }
+
+    /// Returns the current location of the program break.
+    pub fn program_break(&self) -> u64 {
+        self.brk
+    }
+
+    // Produces pseudo-random bytes for getrandom and similar calls. These
+    // are deterministic across runs, which is more useful than real
+    // randomness for a test environment.
+    fn next_random(&mut self) -> u8 {
+        self.random_state ^= self.random_state << 13;
+        self.random_state ^= self.random_state >> 7;
+        self.random_state ^= self.random_state << 17;
+        (self.random_state >> 24) as u8
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn default_clock() -> Timespec {
+    match std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+        Ok(d) => Timespec {
+            sec: d.as_secs() as i64,
+            nsec: d.subsec_nanos() as i64,
+        },
+        Err(_) => Timespec::default(),
+    }
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn default_clock() -> Timespec {
+    Timespec::default()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{Errno, FileStat, FileSystem, SeekFrom};
+
+    // A FileSystem that just records what's written to standard output,
+    // shared by the tests of the guest environments that use FileSystem.
+    pub(crate) struct CaptureFileSystem {
+        stdout: [u8; 64],
+        stdout_len: usize,
+    }
+
+    impl CaptureFileSystem {
+        pub(crate) fn new() -> Self {
+            Self {
+                stdout: [0; 64],
+                stdout_len: 0,
+            }
+        }
+
+        pub(crate) fn stdout(&self) -> &[u8] {
+            &self.stdout[..self.stdout_len]
+        }
+    }
+
+    impl FileSystem for CaptureFileSystem {
+        fn open(&mut self, _path: &[u8], _flags: u32, _mode: u32) -> Result<i32, Errno> {
+            Err(Errno::ENOENT)
+        }
+        fn close(&mut self, _fd: i32) -> Result<(), Errno> {
+            Ok(())
+        }
+        fn read(&mut self, _fd: i32, _buf: &mut [u8]) -> Result<usize, Errno> {
+            Ok(0)
+        }
+        fn write(&mut self, fd: i32, buf: &[u8]) -> Result<usize, Errno> {
+            if fd != 1 {
+                return Err(Errno::EBADF);
+            }
+            self.stdout[self.stdout_len..self.stdout_len + buf.len()].copy_from_slice(buf);
+            self.stdout_len += buf.len();
+            Ok(buf.len())
+        }
+        fn seek(&mut self, _fd: i32, _offset: i64, _whence: SeekFrom) -> Result<u64, Errno> {
+            Err(Errno::ESPIPE)
+        }
+        fn stat(&mut self, _fd: i32) -> Result<FileStat, Errno> {
+            Ok(FileStat::default())
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use super::open_flags::*;
+use super::{Errno, FileStat, FileSystem, SeekFrom, Timespec};
+
+use std::boxed::Box;
+use std::fs;
+use std::io;
+use std::io::{IsTerminal, Read, Seek, Write};
+use std::path::PathBuf;
+use std::vec::Vec;
+
+/// An implementation of `FileSystem` that passes file operations through to
+/// the host's filesystem, in the same way as a user-mode emulator like
+/// `qemu-user` would.
+///
+/// Guest paths are resolved relative to a root directory chosen when the
+/// filesystem is created, so that an absolute guest path `/etc/hosts` refers
+/// to `etc/hosts` inside that root. Relative guest paths are resolved
+/// relative to the root too, because the guest always believes its working
+/// directory to be `/`. A guest path whose `..` components would climb above
+/// the root can't be opened.
+///
+/// Descriptors 0, 1 and 2 initially refer to the host process's own standard
+/// input, output and error streams.
+pub struct HostFileSystem {
+    root: PathBuf,
+    files: Vec<Option<HostFile>>,
+}
+
+enum HostFile {
+    Stdin,
+    Stdout,
+    Stderr,
+    File(fs::File),
+    Custom(Box<dyn ReadWrite>),
+}
+
+/// Combined trait for the streams that can be attached to a `HostFileSystem`
+/// descriptor with `HostFileSystem::attach`.
+pub trait ReadWrite: Read + Write {}
+
+impl<T: Read + Write> ReadWrite for T {}
+
+impl HostFileSystem {
+    /// Creates a filesystem that resolves guest paths beneath the given
+    /// host directory.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            files: std::vec![
+                Some(HostFile::Stdin),
+                Some(HostFile::Stdout),
+                Some(HostFile::Stderr),
+            ],
+        }
+    }
+
+    /// Replaces the stream behind the given descriptor, which is useful for
+    /// capturing the standard output of a guest program.
+    pub fn attach(&mut self, fd: i32, stream: impl ReadWrite + 'static) {
+        let idx = fd as usize;
+        if self.files.len() <= idx {
+            self.files.resize_with(idx + 1, || None);
+        }
+        self.files[idx] = Some(HostFile::Custom(Box::new(stream)));
+    }
+
+    // Resolves a guest path to a path beneath the root directory. The `.`
+    // and `..` components are resolved here rather than by the host, and a
+    // path that would climb above the root is rejected.
+    fn host_path(&self, path: &[u8]) -> Result<PathBuf, Errno> {
+        let path = std::str::from_utf8(path).map_err(|_| Errno::ENOENT)?;
+        let mut components: Vec<&str> = Vec::new();
+        for component in path.split('/') {
+            match component {
+                "" | "." => {}
+                ".." => {
+                    if components.pop().is_none() {
+                        return Err(Errno::EACCES);
+                    }
+                }
+                _ => components.push(component),
+            }
+        }
+        let mut host_path = self.root.clone();
+        host_path.extend(components);
+        Ok(host_path)
+    }
+
+    fn file(&mut self, fd: i32) -> Result<&mut HostFile, Errno> {
+        if fd < 0 {
+            return Err(Errno::EBADF);
+        }
+        match self.files.get_mut(fd as usize) {
+            Some(Some(f)) => Ok(f),
+            _ => Err(Errno::EBADF),
+        }
+    }
+}
+
+impl FileSystem for HostFileSystem {
+    fn open(&mut self, path: &[u8], flags: u32, mode: u32) -> Result<i32, Errno> {
+        let host_path = self.host_path(path)?;
+        let mut opts = fs::OpenOptions::new();
+        match flags & O_ACCMODE {
+            O_RDONLY => opts.read(true),
+            O_WRONLY => opts.write(true),
+            _ => opts.read(true).write(true),
+        };
+        opts.append((flags & O_APPEND) != 0);
+        opts.truncate((flags & O_TRUNC) != 0);
+        if (flags & O_CREAT) != 0 {
+            if (flags & O_EXCL) != 0 {
+                opts.create_new(true);
+            } else {
+                opts.create(true);
+            }
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            opts.mode(mode);
+        }
+        #[cfg(not(unix))]
+        let _ = mode;
+
+        let file = opts.open(&host_path).map_err(errno_from_io)?;
+        let is_dir = file.metadata().map(|m| m.is_dir()).unwrap_or(false);
+        if (flags & O_DIRECTORY) != 0 && !is_dir {
+            return Err(Errno::ENOTDIR);
+        }
+
+        let fd = match self.files.iter().position(|f| f.is_none()) {
+            Some(idx) => idx,
+            None => {
+                self.files.push(None);
+                self.files.len() - 1
+            }
+        };
+        self.files[fd] = Some(HostFile::File(file));
+        Ok(fd as i32)
+    }
+
+    fn close(&mut self, fd: i32) -> Result<(), Errno> {
+        self.file(fd)?;
+        self.files[fd as usize] = None;
+        Ok(())
+    }
+
+    fn read(&mut self, fd: i32, buf: &mut [u8]) -> Result<usize, Errno> {
+        let result = match self.file(fd)? {
+            HostFile::Stdin => io::stdin().read(buf),
+            HostFile::Stdout | HostFile::Stderr => return Err(Errno::EBADF),
+            HostFile::File(f) => f.read(buf),
+            HostFile::Custom(s) => s.read(buf),
+        };
+        result.map_err(errno_from_io)
+    }
+
+    fn write(&mut self, fd: i32, buf: &[u8]) -> Result<usize, Errno> {
+        let result = match self.file(fd)? {
+            HostFile::Stdin => return Err(Errno::EBADF),
+            HostFile::Stdout => io::stdout().write(buf),
+            HostFile::Stderr => io::stderr().write(buf),
+            HostFile::File(f) => f.write(buf),
+            HostFile::Custom(s) => s.write(buf),
+        };
+        result.map_err(errno_from_io)
+    }
+
+    fn seek(&mut self, fd: i32, offset: i64, whence: SeekFrom) -> Result<u64, Errno> {
+        let pos = match whence {
+            SeekFrom::Start => io::SeekFrom::Start(offset as u64),
+            SeekFrom::Current => io::SeekFrom::Current(offset),
+            SeekFrom::End => io::SeekFrom::End(offset),
+        };
+        match self.file(fd)? {
+            HostFile::File(f) => f.seek(pos).map_err(errno_from_io),
+            _ => Err(Errno::ESPIPE),
+        }
+    }
+
+    fn stat(&mut self, fd: i32) -> Result<FileStat, Errno> {
+        let meta = match self.file(fd)? {
+            HostFile::File(f) => f.metadata().map_err(errno_from_io)?,
+            // Streams are reported as character devices.
+            _ => {
+                return Ok(FileStat {
+                    mode: 0o020620,
+                    nlink: 1,
+                    blksize: 1024,
+                    ..FileStat::default()
+                })
+            }
+        };
+        Ok(stat_from_metadata(&meta))
+    }
+
+    fn is_terminal(&mut self, fd: i32) -> bool {
+        match self.file(fd) {
+            Ok(HostFile::Stdin) => io::stdin().is_terminal(),
+            Ok(HostFile::Stdout) => io::stdout().is_terminal(),
+            Ok(HostFile::Stderr) => io::stderr().is_terminal(),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(unix)]
+fn stat_from_metadata(meta: &fs::Metadata) -> FileStat {
+    use std::os::unix::fs::MetadataExt;
+    FileStat {
+        dev: meta.dev(),
+        ino: meta.ino(),
+        mode: meta.mode(),
+        nlink: meta.nlink() as u32,
+        uid: meta.uid(),
+        gid: meta.gid(),
+        rdev: meta.rdev(),
+        size: meta.size() as i64,
+        blksize: meta.blksize() as i32,
+        blocks: meta.blocks() as i64,
+        atime: Timespec {
+            sec: meta.atime(),
+            nsec: meta.atime_nsec(),
+        },
+        mtime: Timespec {
+            sec: meta.mtime(),
+            nsec: meta.mtime_nsec(),
+        },
+        ctime: Timespec {
+            sec: meta.ctime(),
+            nsec: meta.ctime_nsec(),
+        },
+    }
+}
+
+#[cfg(not(unix))]
+fn stat_from_metadata(meta: &fs::Metadata) -> FileStat {
+    let mode = if meta.is_dir() { 0o040755 } else { 0o100644 };
+    FileStat {
+        mode,
+        nlink: 1,
+        size: meta.len() as i64,
+        blksize: 4096,
+        blocks: ((meta.len() + 511) / 512) as i64,
+        ..FileStat::default()
+    }
+}
+
+fn errno_from_io(err: io::Error) -> Errno {
+    if let Some(code) = err.raw_os_error() {
+        // Linux error numbers are the same on every architecture for the
+        // codes we're likely to encounter, so a Linux host can pass them
+        // through directly.
+        if cfg!(target_os = "linux") {
+            return Errno(code);
+        }
+    }
+    match err.kind() {
+        io::ErrorKind::NotFound => Errno::ENOENT,
+        io::ErrorKind::PermissionDenied => Errno::EACCES,
+        io::ErrorKind::AlreadyExists => Errno::EEXIST,
+        io::ErrorKind::InvalidInput => Errno::EINVAL,
+        _ => Errno::EIO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HostFileSystem;
+    use crate::linux::Errno;
+    use std::path::PathBuf;
+
+    #[test]
+    fn host_path() {
+        let fs = HostFileSystem::new("/guest");
+        let host_path = |path: &str| fs.host_path(path.as_bytes());
+        assert_eq!(
+            host_path("/etc/hosts"),
+            Ok(PathBuf::from("/guest/etc/hosts"))
+        );
+        assert_eq!(
+            host_path("etc//./hosts"),
+            Ok(PathBuf::from("/guest/etc/hosts"))
+        );
+        assert_eq!(
+            host_path("/etc/../tmp/x"),
+            Ok(PathBuf::from("/guest/tmp/x"))
+        );
+        assert_eq!(host_path("/"), Ok(PathBuf::from("/guest")));
+        assert_eq!(host_path("../../etc/passwd"), Err(Errno::EACCES));
+        assert_eq!(host_path("/tmp/../../etc/passwd"), Err(Errno::EACCES));
+    }
+}
@@ -0,0 +1,276 @@
+use crate::elf::LoadedImage32;
+use crate::hart::Hart;
+use crate::memory::{AccessKind, Bus, Fault, MemoryError};
+use crate::register::IntRegister;
+use core::convert::TryFrom;
+
+// Auxiliary vector entry types, from the Linux "auxvec.h".
+const AT_NULL: u32 = 0;
+const AT_PHDR: u32 = 3;
+const AT_PHENT: u32 = 4;
+const AT_PHNUM: u32 = 5;
+const AT_PAGESZ: u32 = 6;
+const AT_BASE: u32 = 7;
+const AT_FLAGS: u32 = 8;
+const AT_ENTRY: u32 = 9;
+const AT_UID: u32 = 11;
+const AT_EUID: u32 = 12;
+const AT_GID: u32 = 13;
+const AT_EGID: u32 = 14;
+const AT_HWCAP: u32 = 16;
+const AT_CLKTCK: u32 = 17;
+const AT_SECURE: u32 = 23;
+const AT_RANDOM: u32 = 25;
+const AT_EXECFN: u32 = 31;
+
+// The RISC-V AT_HWCAP value has one bit per single-letter extension, with
+// bit zero representing "A". We advertise the extensions the executor
+// implements.
+const HWCAP_RV32IMAC: u32 = 1 | (1 << (b'C' - b'A')) | (1 << (b'I' - b'A')) | (1 << (b'M' - b'A'));
+
+// The number of entries written to the auxiliary vector, including the
+// terminating AT_NULL.
+const AUXV_LEN: u32 = 17;
+
+// The bytes we present through AT_RANDOM. The C library uses these to seed
+// its stack protector and pointer guard, so a fixed value keeps runs
+// reproducible.
+const RANDOM_BYTES: [u8; 16] = *b"riscv-emu random";
+
+/// Prepares an RV32 hart to begin executing a Linux user-mode program that
+/// has already been loaded into its memory, building the initial process
+/// stack described by the RISC-V psABI.
+///
+/// The stack is built downwards from `stack_top` and contains, from the
+/// final stack pointer upwards: the argument count, the `argv` and `envp`
+/// pointer arrays (each NULL-terminated), the auxiliary vector, and then the
+/// strings and other data those point to. The given strings must not include
+/// their NUL terminators.
+///
+/// On success, `sp` points at the argument count, `a0` is zero (meaning no
+/// termination function is registered by a dynamic linker), the program
+/// counter is set to the image entry point, and the resulting stack pointer
+/// is returned.
+///
+/// If the stack doesn't fit between address zero and `stack_top`, this
+/// returns an access fault for a store to address zero without changing the
+/// hart's registers.
+pub fn init_process_rv32<Mem: Bus<u32>>(
+    hart: &mut impl Hart<u32, u32, f64, Mem>,
+    image: &LoadedImage32,
+    stack_top: u32,
+    argv: &[&[u8]],
+    envp: &[&[u8]],
+) -> Result<u32, MemoryError> {
+    let sp = hart.with_memory(|mem| build_stack(mem, image, stack_top, argv, envp))?;
+    hart.write_int_register(IntRegister::numbered(2), sp);
+    hart.write_int_register(IntRegister::numbered(10), 0);
+    hart.write_pc(image.entry);
+    Ok(sp)
+}
+
+fn build_stack<Mem: Bus<u32>>(
+    mem: &mut Mem,
+    image: &LoadedImage32,
+    stack_top: u32,
+    argv: &[&[u8]],
+    envp: &[&[u8]],
+) -> Result<u32, MemoryError> {
+    let random_addr = stack_below(stack_top, RANDOM_BYTES.len())? & !0xf;
+    write_bytes(mem, random_addr, &RANDOM_BYTES)?;
+
+    // The strings are packed immediately below the random bytes, with the
+    // argument strings first so that argv[0] is also usable for AT_EXECFN.
+    let strings_len: usize = argv.iter().chain(envp.iter()).map(|s| s.len() + 1).sum();
+    let strings_addr = stack_below(random_addr, strings_len)?;
+    let mut next_string = strings_addr;
+    for s in argv.iter().chain(envp.iter()) {
+        write_bytes(mem, next_string, s)?;
+        mem.write_byte(next_string + s.len() as u32, 0)?;
+        next_string += s.len() as u32 + 1;
+    }
+
+    let words = 1 + (argv.len() + 1) + (envp.len() + 1) + AUXV_LEN as usize * 2;
+    let sp = stack_below(strings_addr, words * 4)? & !0xf;
+
+    let mut w = StackWriter { addr: sp };
+    w.push(mem, argv.len() as u32)?;
+    let mut string_addr = strings_addr;
+    for s in argv.iter() {
+        w.push(mem, string_addr)?;
+        string_addr += s.len() as u32 + 1;
+    }
+    w.push(mem, 0)?;
+    for s in envp.iter() {
+        w.push(mem, string_addr)?;
+        string_addr += s.len() as u32 + 1;
+    }
+    w.push(mem, 0)?;
+
+    let execfn = if argv.is_empty() { 0 } else { strings_addr };
+    let auxv: [(u32, u32); AUXV_LEN as usize] = [
+        (AT_PHDR, image.phdr),
+        (AT_PHENT, image.phent),
+        (AT_PHNUM, image.phnum),
+        (AT_PAGESZ, 4096),
+        (AT_BASE, 0),
+        (AT_FLAGS, 0),
+        (AT_ENTRY, image.entry),
+        (AT_UID, 0),
+        (AT_EUID, 0),
+        (AT_GID, 0),
+        (AT_EGID, 0),
+        (AT_HWCAP, HWCAP_RV32IMAC),
+        (AT_CLKTCK, 100),
+        (AT_SECURE, 0),
+        (AT_RANDOM, random_addr),
+        (AT_EXECFN, execfn),
+        (AT_NULL, 0),
+    ];
+    for (key, value) in auxv.iter() {
+        w.push(mem, *key)?;
+        w.push(mem, *value)?;
+    }
+
+    Ok(sp)
+}
+
+// Returns the address `len` bytes below `addr`, or the error that writing
+// below address zero would cause if the stack doesn't fit there.
+fn stack_below(addr: u32, len: usize) -> Result<u32, MemoryError> {
+    u32::try_from(len)
+        .ok()
+        .and_then(|len| addr.checked_sub(len))
+        .ok_or(MemoryError::new(
+            Fault::AccessFault,
+            AccessKind::Store,
+            0,
+            len,
+        ))
+}
+
+struct StackWriter {
+    addr: u32,
+}
+
+impl StackWriter {
+    fn push<Mem: Bus<u32>>(&mut self, mem: &mut Mem, v: u32) -> Result<(), MemoryError> {
+        mem.write_word(self.addr, v)?;
+        self.addr += 4;
+        Ok(())
+    }
+}
+
+fn write_bytes<Mem: Bus<u32>>(mem: &mut Mem, addr: u32, data: &[u8]) -> Result<(), MemoryError> {
+    for (i, b) in data.iter().enumerate() {
+        mem.write_byte(addr + i as u32, *b)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::init_process_rv32;
+    use crate::elf::LoadedImage32;
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::RV32;
+    use crate::memory::{AccessKind, AddressConverter, Bus, Fault, Memory};
+    use crate::register::IntRegister;
+
+    #[test]
+    fn initial_stack() {
+        let mut mem_buf = [0u8; 1024];
+        let mem = Memory::new_ram(&mut mem_buf);
+        let mut hart: SingleThreadUserHart<RV32, AddressConverter<u32, usize, Memory>> =
+            SingleThreadUserHart::new(AddressConverter::new(mem));
+        let image = LoadedImage32 {
+            entry: 0x54,
+            phdr: 0x34,
+            phent: 32,
+            phnum: 1,
+            end: 0x100,
+        };
+
+        let sp =
+            init_process_rv32(&mut hart, &image, 0x400, &[b"prog", b"arg"], &[b"A=B"]).unwrap();
+        assert_eq!(sp % 16, 0, "stack pointer is 16-byte aligned");
+        assert_eq!(hart.read_int_register(IntRegister::numbered(2)), sp);
+        assert_eq!(hart.read_pc(), 0x54);
+
+        hart.with_memory(|mem| {
+            let word = |mem: &mut AddressConverter<u32, usize, Memory>, i: u32| {
+                mem.read_word(sp + i * 4).unwrap()
+            };
+            let string = |mem: &mut AddressConverter<u32, usize, Memory>, mut addr: u32| {
+                let mut buf = [0u8; 16];
+                let mut len = 0;
+                loop {
+                    let b = mem.read_byte(addr).unwrap();
+                    if b == 0 {
+                        break;
+                    }
+                    buf[len] = b;
+                    len += 1;
+                    addr += 1;
+                }
+                (buf, len)
+            };
+
+            assert_eq!(word(mem, 0), 2, "argc");
+            let addr = word(mem, 1);
+            let (s, l) = string(mem, addr);
+            assert_eq!(&s[..l], b"prog");
+            let addr = word(mem, 2);
+            let (s, l) = string(mem, addr);
+            assert_eq!(&s[..l], b"arg");
+            assert_eq!(word(mem, 3), 0, "argv terminator");
+            let addr = word(mem, 4);
+            let (s, l) = string(mem, addr);
+            assert_eq!(&s[..l], b"A=B");
+            assert_eq!(word(mem, 5), 0, "envp terminator");
+
+            // Scan the auxiliary vector for a few interesting entries.
+            let mut i = 6;
+            let mut found = 0;
+            loop {
+                let (key, value) = (word(mem, i), word(mem, i + 1));
+                match key {
+                    0 => break,
+                    3 => assert_eq!(value, 0x34, "AT_PHDR"),
+                    6 => assert_eq!(value, 4096, "AT_PAGESZ"),
+                    9 => assert_eq!(value, 0x54, "AT_ENTRY"),
+                    25 => assert!(value > sp && value < 0x400, "AT_RANDOM"),
+                    31 => assert_eq!(value, word(mem, 1), "AT_EXECFN"),
+                    _ => {
+                        i += 2;
+                        continue;
+                    }
+                }
+                found += 1;
+                i += 2;
+            }
+            assert_eq!(found, 5, "all expected auxv entries present");
+        });
+    }
+
+    #[test]
+    fn stack_too_small() {
+        let mut mem_buf = [0u8; 64];
+        let mem = Memory::new_ram(&mut mem_buf);
+        let mut hart: SingleThreadUserHart<RV32, AddressConverter<u32, usize, Memory>> =
+            SingleThreadUserHart::new(AddressConverter::new(mem));
+        let image = LoadedImage32 {
+            entry: 0,
+            phdr: 0,
+            phent: 32,
+            phnum: 0,
+            end: 0,
+        };
+
+        // The auxiliary vector alone doesn't fit below 0x40.
+        let err = init_process_rv32(&mut hart, &image, 0x40, &[b"prog"], &[]).unwrap_err();
+        assert_eq!(err.fault, Fault::AccessFault);
+        assert_eq!(err.access, AccessKind::Store);
+        assert_eq!(hart.read_pc(), 0);
+    }
+}
@@ -0,0 +1,658 @@
+use super::{Errno, FileStat, FileSystem, SeekFrom, SyscallEmulator, SyscallStatus, Timespec};
+use crate::hart::Hart;
+use crate::memory::{Bus, MemoryError};
+use crate::register::IntRegister;
+
+// System call numbers from the Linux "asm-generic" table, which RISC-V uses.
+const SYS_GETCWD: u32 = 17;
+const SYS_DUP: u32 = 23;
+const SYS_FCNTL: u32 = 25;
+const SYS_IOCTL: u32 = 29;
+const SYS_FACCESSAT: u32 = 48;
+const SYS_OPENAT: u32 = 56;
+const SYS_CLOSE: u32 = 57;
+const SYS_LLSEEK: u32 = 62;
+const SYS_READ: u32 = 63;
+const SYS_WRITE: u32 = 64;
+const SYS_READV: u32 = 65;
+const SYS_WRITEV: u32 = 66;
+const SYS_READLINKAT: u32 = 78;
+const SYS_FSTATAT: u32 = 79;
+const SYS_FSTAT: u32 = 80;
+const SYS_EXIT: u32 = 93;
+const SYS_EXIT_GROUP: u32 = 94;
+const SYS_SET_TID_ADDRESS: u32 = 96;
+const SYS_SET_ROBUST_LIST: u32 = 99;
+const SYS_CLOCK_GETTIME: u32 = 113;
+const SYS_RT_SIGACTION: u32 = 134;
+const SYS_RT_SIGPROCMASK: u32 = 135;
+const SYS_UNAME: u32 = 160;
+const SYS_GETPID: u32 = 172;
+const SYS_GETPPID: u32 = 173;
+const SYS_GETUID: u32 = 174;
+const SYS_GETEUID: u32 = 175;
+const SYS_GETGID: u32 = 176;
+const SYS_GETEGID: u32 = 177;
+const SYS_GETTID: u32 = 178;
+const SYS_BRK: u32 = 214;
+const SYS_MUNMAP: u32 = 215;
+const SYS_MMAP2: u32 = 222;
+const SYS_MPROTECT: u32 = 226;
+const SYS_PRLIMIT64: u32 = 261;
+const SYS_GETRANDOM: u32 = 278;
+const SYS_CLOCK_GETTIME64: u32 = 403;
+
+const AT_FDCWD: i32 = -100;
+const AT_EMPTY_PATH: u32 = 0x1000;
+
+const MAP_FIXED: u32 = 0x10;
+const MAP_ANONYMOUS: u32 = 0x20;
+
+const TIOCGWINSZ: u32 = 0x5413;
+
+const PAGE_SIZE: u32 = 4096;
+const PATH_MAX: usize = 4096;
+
+// The size of the host-side buffer used to move data between guest memory
+// and the filesystem. Larger transfers are split into multiple calls.
+const CHUNK_SIZE: usize = 512;
+
+type Result<T> = core::result::Result<T, Errno>;
+
+impl<FS: FileSystem> SyscallEmulator<FS> {
+    /// Services the system call requested by an RV32 hart that has just
+    /// executed an `ecall`, as reported by `ExecStatus::EnvironmentCall`.
+    ///
+    /// The system call number is taken from `a7` and the arguments from `a0`
+    /// through `a5`, per the Linux RISC-V calling convention. The result, or
+    /// the negated error number on failure, is written to `a0`.
+    pub fn handle_rv32<Mem: Bus<u32>>(
+        &mut self,
+        hart: &mut impl Hart<u32, u32, f64, Mem>,
+    ) -> SyscallStatus {
+        let num = hart.read_int_register(IntRegister::numbered(17));
+        let args = [
+            hart.read_int_register(IntRegister::numbered(10)),
+            hart.read_int_register(IntRegister::numbered(11)),
+            hart.read_int_register(IntRegister::numbered(12)),
+            hart.read_int_register(IntRegister::numbered(13)),
+            hart.read_int_register(IntRegister::numbered(14)),
+            hart.read_int_register(IntRegister::numbered(15)),
+        ];
+
+        let result = match num {
+            SYS_EXIT | SYS_EXIT_GROUP => return SyscallStatus::Exited(args[0] as i32),
+            SYS_GETCWD => hart.with_memory(|mem| self.sys_getcwd(mem, args[0], args[1])),
+            SYS_DUP | SYS_FCNTL | SYS_READLINKAT => Err(Errno::EINVAL),
+            SYS_IOCTL => self.sys_ioctl(args[0] as i32, args[1]),
+            SYS_FACCESSAT => {
+                hart.with_memory(|mem| self.sys_faccessat(mem, args[0] as i32, args[1]))
+            }
+            SYS_OPENAT => hart
+                .with_memory(|mem| self.sys_openat(mem, args[0] as i32, args[1], args[2], args[3])),
+            SYS_CLOSE => self.fs.close(args[0] as i32).map(|_| 0),
+            SYS_LLSEEK => hart.with_memory(|mem| {
+                self.sys_llseek(mem, args[0] as i32, args[1], args[2], args[3], args[4])
+            }),
+            SYS_READ => {
+                hart.with_memory(|mem| self.sys_read(mem, args[0] as i32, args[1], args[2]))
+            }
+            SYS_WRITE => {
+                hart.with_memory(|mem| self.sys_write(mem, args[0] as i32, args[1], args[2]))
+            }
+            SYS_READV => {
+                hart.with_memory(|mem| self.sys_readv(mem, args[0] as i32, args[1], args[2]))
+            }
+            SYS_WRITEV => {
+                hart.with_memory(|mem| self.sys_writev(mem, args[0] as i32, args[1], args[2]))
+            }
+            SYS_FSTATAT => hart.with_memory(|mem| {
+                self.sys_fstatat(mem, args[0] as i32, args[1], args[2], args[3])
+            }),
+            SYS_FSTAT => hart.with_memory(|mem| self.sys_fstat(mem, args[0] as i32, args[1])),
+            SYS_SET_TID_ADDRESS | SYS_GETPID | SYS_GETTID => Ok(1),
+            SYS_GETPPID | SYS_GETUID | SYS_GETEUID | SYS_GETGID | SYS_GETEGID => Ok(0),
+            SYS_SET_ROBUST_LIST | SYS_RT_SIGACTION | SYS_RT_SIGPROCMASK | SYS_MPROTECT => Ok(0),
+            SYS_CLOCK_GETTIME => {
+                hart.with_memory(|mem| self.sys_clock_gettime(mem, args[1], false))
+            }
+            SYS_CLOCK_GETTIME64 => {
+                hart.with_memory(|mem| self.sys_clock_gettime(mem, args[1], true))
+            }
+            SYS_UNAME => hart.with_memory(|mem| self.sys_uname(mem, args[0])),
+            SYS_BRK => hart.with_memory(|mem| self.sys_brk(mem, args[0])),
+            SYS_MUNMAP => self.sys_munmap(args[0], args[1]),
+            SYS_MMAP2 => hart.with_memory(|mem| {
+                self.sys_mmap2(mem, args[0], args[1], args[3], args[4] as i32, args[5])
+            }),
+            SYS_PRLIMIT64 => Err(Errno::EPERM),
+            SYS_GETRANDOM => hart.with_memory(|mem| self.sys_getrandom(mem, args[0], args[1])),
+            _ => {
+                write_result(hart, Err(Errno::ENOSYS));
+                return SyscallStatus::Unsupported(num);
+            }
+        };
+        write_result(hart, result);
+        SyscallStatus::Handled
+    }
+
+    fn sys_getcwd<Mem: Bus<u32>>(&mut self, mem: &mut Mem, buf: u32, size: u32) -> Result<u32> {
+        // The emulated process always runs in the root directory, because
+        // paths are passed to the FileSystem as given.
+        let cwd = b"/\0";
+        if (size as usize) < cwd.len() {
+            return Err(Errno::EINVAL);
+        }
+        write_guest(mem, buf, cwd)?;
+        Ok(cwd.len() as u32)
+    }
+
+    fn sys_ioctl(&mut self, fd: i32, req: u32) -> Result<u32> {
+        // We don't emulate any terminal control, but we must answer
+        // TIOCGWINSZ in a way that allows the C library to decide whether
+        // its standard streams are line-buffered.
+        match req {
+            TIOCGWINSZ if self.fs.is_terminal(fd) => Ok(0),
+            _ => Err(Errno::ENOTTY),
+        }
+    }
+
+    fn sys_faccessat<Mem: Bus<u32>>(
+        &mut self,
+        mem: &mut Mem,
+        dirfd: i32,
+        path: u32,
+    ) -> Result<u32> {
+        let fd = self.sys_openat(mem, dirfd, path, super::open_flags::O_RDONLY, 0)?;
+        self.fs.close(fd as i32)?;
+        Ok(0)
+    }
+
+    fn sys_openat<Mem: Bus<u32>>(
+        &mut self,
+        mem: &mut Mem,
+        dirfd: i32,
+        path: u32,
+        flags: u32,
+        mode: u32,
+    ) -> Result<u32> {
+        let mut buf = [0u8; PATH_MAX];
+        let path = read_guest_str(mem, path, &mut buf)?;
+        if dirfd != AT_FDCWD && path.first() != Some(&b'/') {
+            // Paths relative to a directory descriptor are not supported.
+            return Err(Errno::EBADF);
+        }
+        self.fs.open(path, flags, mode).map(|fd| fd as u32)
+    }
+
+    fn sys_llseek<Mem: Bus<u32>>(
+        &mut self,
+        mem: &mut Mem,
+        fd: i32,
+        offset_high: u32,
+        offset_low: u32,
+        result: u32,
+        whence: u32,
+    ) -> Result<u32> {
+        let offset = (((offset_high as u64) << 32) | (offset_low as u64)) as i64;
+        let whence = match whence {
+            0 => SeekFrom::Start,
+            1 => SeekFrom::Current,
+            2 => SeekFrom::End,
+            _ => return Err(Errno::EINVAL),
+        };
+        let pos = self.fs.seek(fd, offset, whence)?;
+        write_guest(mem, result, &pos.to_le_bytes())?;
+        Ok(0)
+    }
+
+    fn sys_read<Mem: Bus<u32>>(
+        &mut self,
+        mem: &mut Mem,
+        fd: i32,
+        buf: u32,
+        count: u32,
+    ) -> Result<u32> {
+        let mut chunk = [0u8; CHUNK_SIZE];
+        let mut total: u32 = 0;
+        while total < count {
+            let want = core::cmp::min((count - total) as usize, CHUNK_SIZE);
+            let n = match self.fs.read(fd, &mut chunk[..want]) {
+                Ok(n) => n,
+                // An error after a partial read is reported as a short read.
+                Err(e) if total == 0 => return Err(e),
+                Err(_) => break,
+            };
+            write_guest(mem, buf.wrapping_add(total), &chunk[..n])?;
+            total += n as u32;
+            if n < want {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    fn sys_write<Mem: Bus<u32>>(
+        &mut self,
+        mem: &mut Mem,
+        fd: i32,
+        buf: u32,
+        count: u32,
+    ) -> Result<u32> {
+        let mut chunk = [0u8; CHUNK_SIZE];
+        let mut total: u32 = 0;
+        while total < count {
+            let want = core::cmp::min((count - total) as usize, CHUNK_SIZE);
+            read_guest(mem, buf.wrapping_add(total), &mut chunk[..want])?;
+            let n = match self.fs.write(fd, &chunk[..want]) {
+                Ok(n) => n,
+                Err(e) if total == 0 => return Err(e),
+                Err(_) => break,
+            };
+            total += n as u32;
+            if n < want {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    fn sys_readv<Mem: Bus<u32>>(
+        &mut self,
+        mem: &mut Mem,
+        fd: i32,
+        iov: u32,
+        iovcnt: u32,
+    ) -> Result<u32> {
+        let mut total: u32 = 0;
+        for i in 0..iovcnt {
+            let (base, len) = read_iovec(mem, iov, i)?;
+            let n = self.sys_read(mem, fd, base, len)?;
+            total += n;
+            if n < len {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    fn sys_writev<Mem: Bus<u32>>(
+        &mut self,
+        mem: &mut Mem,
+        fd: i32,
+        iov: u32,
+        iovcnt: u32,
+    ) -> Result<u32> {
+        let mut total: u32 = 0;
+        for i in 0..iovcnt {
+            let (base, len) = read_iovec(mem, iov, i)?;
+            let n = self.sys_write(mem, fd, base, len)?;
+            total += n;
+            if n < len {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    fn sys_fstatat<Mem: Bus<u32>>(
+        &mut self,
+        mem: &mut Mem,
+        dirfd: i32,
+        path: u32,
+        statbuf: u32,
+        flags: u32,
+    ) -> Result<u32> {
+        let mut buf = [0u8; PATH_MAX];
+        let path_bytes = read_guest_str(mem, path, &mut buf)?;
+        if path_bytes.is_empty() && (flags & AT_EMPTY_PATH) != 0 {
+            return self.sys_fstat(mem, dirfd, statbuf);
+        }
+        let fd = self.sys_openat(mem, dirfd, path, super::open_flags::O_RDONLY, 0)? as i32;
+        let result = self.sys_fstat(mem, fd, statbuf);
+        self.fs.close(fd)?;
+        result
+    }
+
+    fn sys_fstat<Mem: Bus<u32>>(&mut self, mem: &mut Mem, fd: i32, statbuf: u32) -> Result<u32> {
+        let stat = self.fs.stat(fd)?;
+        write_guest(mem, statbuf, &encode_stat_rv32(&stat))?;
+        Ok(0)
+    }
+
+    fn sys_clock_gettime<Mem: Bus<u32>>(
+        &mut self,
+        mem: &mut Mem,
+        tp: u32,
+        time64: bool,
+    ) -> Result<u32> {
+        let clock = self.clock;
+        let now = clock();
+        if time64 {
+            let mut raw = [0u8; 16];
+            raw[0..8].copy_from_slice(&now.sec.to_le_bytes());
+            raw[8..16].copy_from_slice(&now.nsec.to_le_bytes());
+            write_guest(mem, tp, &raw)?;
+        } else {
+            let mut raw = [0u8; 8];
+            raw[0..4].copy_from_slice(&(now.sec as i32).to_le_bytes());
+            raw[4..8].copy_from_slice(&(now.nsec as i32).to_le_bytes());
+            write_guest(mem, tp, &raw)?;
+        }
+        Ok(0)
+    }
+
+    fn sys_uname<Mem: Bus<u32>>(&mut self, mem: &mut Mem, buf: u32) -> Result<u32> {
+        // struct utsname is six fixed-size NUL-terminated strings. Some C
+        // libraries check the release field against a minimum kernel
+        // version, so we claim a reasonably modern one.
+        const FIELD_LEN: u32 = 65;
+        let fields: [&[u8]; 6] = [b"Linux", b"riscv-emu", b"5.15.0", b"#1", b"riscv32", b""];
+        for (i, field) in fields.iter().enumerate() {
+            let mut raw = [0u8; FIELD_LEN as usize];
+            raw[..field.len()].copy_from_slice(field);
+            write_guest(mem, buf.wrapping_add(i as u32 * FIELD_LEN), &raw)?;
+        }
+        Ok(0)
+    }
+
+    fn sys_brk<Mem: Bus<u32>>(&mut self, mem: &mut Mem, addr: u32) -> Result<u32> {
+        // brk reports failure by returning the unchanged break, rather than
+        // by returning an error number.
+        let addr = addr as u64;
+        if addr < self.heap.start || addr > self.heap.end {
+            return Ok(self.brk as u32);
+        }
+        if addr > self.brk {
+            // Memory newly exposed by the break must read as zero, even if
+            // the program previously wrote to it before shrinking the break.
+            zero_guest(mem, self.brk as u32, (addr - self.brk) as u32)?;
+        }
+        self.brk = addr;
+        Ok(self.brk as u32)
+    }
+
+    fn sys_munmap(&mut self, addr: u32, len: u32) -> Result<u32> {
+        if !addr.is_multiple_of(PAGE_SIZE) {
+            return Err(Errno::EINVAL);
+        }
+        // Mappings are allocated as a stack, so we can only actually reclaim
+        // space when the most recent mapping is released.
+        let len = round_up_page(len).ok_or(Errno::ENOMEM)?;
+        if addr as u64 == self.mmap_next {
+            let len = len as u64;
+            self.mmap_next = core::cmp::min(self.mmap_next + len, self.mmap.end);
+        }
+        Ok(0)
+    }
+
+    fn sys_mmap2<Mem: Bus<u32>>(
+        &mut self,
+        mem: &mut Mem,
+        addr: u32,
+        len: u32,
+        flags: u32,
+        fd: i32,
+        pgoffset: u32,
+    ) -> Result<u32> {
+        if len == 0 {
+            return Err(Errno::EINVAL);
+        }
+        let len = round_up_page(len).ok_or(Errno::ENOMEM)?;
+        let start = if (flags & MAP_FIXED) != 0 {
+            if !addr.is_multiple_of(PAGE_SIZE) {
+                return Err(Errno::EINVAL);
+            }
+            addr.checked_add(len).ok_or(Errno::ENOMEM)?;
+            addr
+        } else {
+            let start = self
+                .mmap_next
+                .checked_sub(len as u64)
+                .ok_or(Errno::ENOMEM)?;
+            if start < self.mmap.start {
+                return Err(Errno::ENOMEM);
+            }
+            self.mmap_next = start;
+            start as u32
+        };
+
+        zero_guest(mem, start, len)?;
+        if (flags & MAP_ANONYMOUS) == 0 {
+            // File mappings are private copies of the file content, so later
+            // writes to the file are not visible through the mapping.
+            let offset = (pgoffset as i64) * (PAGE_SIZE as i64);
+            self.fs.seek(fd, offset, SeekFrom::Start)?;
+            self.sys_read(mem, fd, start, len)?;
+        }
+        Ok(start)
+    }
+
+    fn sys_getrandom<Mem: Bus<u32>>(&mut self, mem: &mut Mem, buf: u32, len: u32) -> Result<u32> {
+        for i in 0..len {
+            let v = self.next_random();
+            write_guest(mem, buf.wrapping_add(i), &[v])?;
+        }
+        Ok(len)
+    }
+}
+
+fn write_result<Mem: Bus<u32>>(hart: &mut impl Hart<u32, u32, f64, Mem>, result: Result<u32>) {
+    let v = match result {
+        Ok(v) => v,
+        Err(Errno(e)) => (-e) as u32,
+    };
+    hart.write_int_register(IntRegister::numbered(10), v);
+}
+
+// Rounds the given length up to a whole number of pages, or returns `None` if
+// that doesn't fit in the guest's address space.
+fn round_up_page(len: u32) -> Option<u32> {
+    Some(len.checked_add(PAGE_SIZE - 1)? & !(PAGE_SIZE - 1))
+}
+
+fn read_guest<Mem: Bus<u32>>(mem: &mut Mem, addr: u32, buf: &mut [u8]) -> Result<()> {
+    for (i, b) in buf.iter_mut().enumerate() {
+        *b = mem.read_byte(addr.wrapping_add(i as u32)).map_err(fault)?;
+    }
+    Ok(())
+}
+
+fn write_guest<Mem: Bus<u32>>(mem: &mut Mem, addr: u32, buf: &[u8]) -> Result<()> {
+    for (i, b) in buf.iter().enumerate() {
+        mem.write_byte(addr.wrapping_add(i as u32), *b)
+            .map_err(fault)?;
+    }
+    Ok(())
+}
+
+fn zero_guest<Mem: Bus<u32>>(mem: &mut Mem, addr: u32, len: u32) -> Result<()> {
+    for i in 0..len {
+        mem.write_byte(addr.wrapping_add(i), 0).map_err(fault)?;
+    }
+    Ok(())
+}
+
+// Reads a NUL-terminated string from guest memory into the given buffer,
+// returning the portion of the buffer that the string occupies.
+fn read_guest_str<'a, Mem: Bus<u32>>(
+    mem: &mut Mem,
+    addr: u32,
+    buf: &'a mut [u8],
+) -> Result<&'a [u8]> {
+    for i in 0..buf.len() {
+        let b = mem.read_byte(addr.wrapping_add(i as u32)).map_err(fault)?;
+        if b == 0 {
+            return Ok(&buf[..i]);
+        }
+        buf[i] = b;
+    }
+    Err(Errno::ENAMETOOLONG)
+}
+
+fn read_iovec<Mem: Bus<u32>>(mem: &mut Mem, iov: u32, i: u32) -> Result<(u32, u32)> {
+    let entry = iov.wrapping_add(i * 8);
+    let base = mem.read_word(entry).map_err(fault)?;
+    let len = mem.read_word(entry.wrapping_add(4)).map_err(fault)?;
+    Ok((base, len))
+}
+
+fn fault(_: MemoryError) -> Errno {
+    Errno::EFAULT
+}
+
+// Encodes a FileStat using the layout of the asm-generic `struct stat` for
+// a 32-bit target, where `long` fields are four bytes.
+fn encode_stat_rv32(stat: &FileStat) -> [u8; 104] {
+    let mut raw = [0u8; 104];
+    raw[0..8].copy_from_slice(&stat.dev.to_le_bytes());
+    raw[8..16].copy_from_slice(&stat.ino.to_le_bytes());
+    raw[16..20].copy_from_slice(&stat.mode.to_le_bytes());
+    raw[20..24].copy_from_slice(&stat.nlink.to_le_bytes());
+    raw[24..28].copy_from_slice(&stat.uid.to_le_bytes());
+    raw[28..32].copy_from_slice(&stat.gid.to_le_bytes());
+    raw[32..40].copy_from_slice(&stat.rdev.to_le_bytes());
+    raw[48..56].copy_from_slice(&stat.size.to_le_bytes());
+    raw[56..60].copy_from_slice(&stat.blksize.to_le_bytes());
+    raw[64..72].copy_from_slice(&stat.blocks.to_le_bytes());
+    encode_timespec_rv32(&mut raw[72..80], &stat.atime);
+    encode_timespec_rv32(&mut raw[80..88], &stat.mtime);
+    encode_timespec_rv32(&mut raw[88..96], &stat.ctime);
+    raw
+}
+
+fn encode_timespec_rv32(raw: &mut [u8], ts: &Timespec) {
+    raw[0..4].copy_from_slice(&(ts.sec as i32).to_le_bytes());
+    raw[4..8].copy_from_slice(&(ts.nsec as u32).to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::CaptureFileSystem;
+    use super::super::{SyscallEmulator, SyscallStatus};
+    use crate::exec::{step_rv32, ExecStatus};
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::RV32;
+    use crate::memory::{AddressConverter, Bus, Memory};
+    use crate::register::IntRegister;
+
+    #[test]
+    fn write_and_exit() {
+        let mut mem_buf = [0u8; 1024];
+        let program: [u32; 11] = [
+            0x04000893, // li a7, 64 (write)
+            0x00100513, // li a0, 1
+            0x10000593, // li a1, 0x100
+            0x00600613, // li a2, 6
+            0x00000073, // ecall
+            0x0d600893, // li a7, 214 (brk)
+            0x00000513, // li a0, 0
+            0x00000073, // ecall
+            0x05e00893, // li a7, 94 (exit_group)
+            0x02a00513, // li a0, 42
+            0x00000073, // ecall
+        ];
+        for (i, word) in program.iter().enumerate() {
+            mem_buf[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+        }
+        mem_buf[0x100..0x106].copy_from_slice(b"hello\n");
+
+        let mem = Memory::new_ram(&mut mem_buf);
+        let mut hart: SingleThreadUserHart<RV32, AddressConverter<u32, usize, Memory>> =
+            SingleThreadUserHart::new(AddressConverter::new(mem));
+        let fs = CaptureFileSystem::new();
+        let mut sys = SyscallEmulator::new(fs, 0x200..0x300, 0x300..0x400);
+
+        let mut results = [0u32; 2];
+        let mut calls = 0;
+        let exit_code = loop {
+            match step_rv32(&mut hart) {
+                ExecStatus::EnvironmentCall(_) => match sys.handle_rv32(&mut hart) {
+                    SyscallStatus::Handled => {
+                        results[calls] = hart.read_int_register(IntRegister::numbered(10));
+                        calls += 1;
+                    }
+                    SyscallStatus::Exited(code) => break code,
+                    SyscallStatus::Unsupported(num) => panic!("unsupported syscall {}", num),
+                },
+                ExecStatus::Running => {}
+                status => panic!("unexpected status {:?}", status),
+            }
+        };
+
+        assert_eq!(exit_code, 42, "exit code");
+        assert_eq!(results[0], 6, "write result");
+        assert_eq!(results[1], 0x200, "initial program break");
+        let fs = sys.file_system();
+        assert_eq!(fs.stdout(), b"hello\n");
+    }
+
+    #[test]
+    fn mmap_and_brk() {
+        let mut mem_buf = [0xffu8; 0x4000];
+        let mem = Memory::new_ram(&mut mem_buf);
+        let mut hart: SingleThreadUserHart<RV32, AddressConverter<u32, usize, Memory>> =
+            SingleThreadUserHart::new(AddressConverter::new(mem));
+        let fs = CaptureFileSystem::new();
+        let mut sys = SyscallEmulator::new(fs, 0x1000..0x2000, 0x2000..0x4000);
+
+        let mut syscall = |hart: &mut SingleThreadUserHart<RV32, _>, num: u32, args: &[u32]| {
+            hart.write_int_register(IntRegister::numbered(17), num);
+            for (i, arg) in args.iter().enumerate() {
+                hart.write_int_register(IntRegister::numbered(10 + i), *arg);
+            }
+            assert_eq!(sys.handle_rv32(hart), SyscallStatus::Handled);
+            hart.read_int_register(IntRegister::numbered(10))
+        };
+
+        assert_eq!(syscall(&mut hart, 214, &[0x1100]), 0x1100, "brk grow");
+        assert_eq!(
+            syscall(&mut hart, 214, &[0x3000]),
+            0x1100,
+            "brk beyond heap"
+        );
+        hart.with_memory(|mem| {
+            assert_eq!(mem.read_word(0x1000).unwrap(), 0, "brk zeroes new memory");
+            assert_eq!(mem.read_word(0x1100).unwrap(), 0xffffffff, "beyond brk");
+        });
+
+        // mmap2(NULL, 100, PROT_READ|PROT_WRITE, MAP_PRIVATE|MAP_ANONYMOUS, -1, 0)
+        let anon = syscall(&mut hart, 222, &[0, 100, 3, 0x22, 0xffffffff, 0]);
+        assert_eq!(anon, 0x3000, "first mapping at top of range");
+        let anon2 = syscall(&mut hart, 222, &[0, 0x1000, 3, 0x22, 0xffffffff, 0]);
+        assert_eq!(anon2, 0x2000, "second mapping below first");
+        let fail = syscall(&mut hart, 222, &[0, 0x1000, 3, 0x22, 0xffffffff, 0]);
+        assert_eq!(fail, -12i32 as u32, "mapping range exhausted");
+        hart.with_memory(|mem| {
+            assert_eq!(mem.read_word(0x3000).unwrap(), 0, "mapping is zeroed");
+        });
+        assert_eq!(syscall(&mut hart, 215, &[anon2, 0x1000]), 0, "munmap");
+        let anon3 = syscall(&mut hart, 222, &[0, 0x1000, 3, 0x22, 0xffffffff, 0]);
+        assert_eq!(anon3, 0x2000, "munmap released the space");
+
+        // Lengths that can't be rounded up to whole pages, and fixed ranges
+        // that extend past the end of the address space, fail rather than
+        // wrapping around.
+        let huge = syscall(&mut hart, 222, &[0, 0xfffff001, 3, 0x22, 0xffffffff, 0]);
+        assert_eq!(huge, -12i32 as u32, "mmap2 length overflows");
+        let fixed = syscall(
+            &mut hart,
+            222,
+            &[0xfffff000, 0x2000, 3, 0x32, 0xffffffff, 0],
+        );
+        assert_eq!(fixed, -12i32 as u32, "MAP_FIXED range overflows");
+        let unmap = syscall(&mut hart, 215, &[anon3, 0xffffffff]);
+        assert_eq!(unmap, -12i32 as u32, "munmap length overflows");
+        assert_eq!(syscall(&mut hart, 215, &[anon3, 0x1000]), 0, "munmap");
+        let anon4 = syscall(&mut hart, 222, &[0, 0x1000, 3, 0x22, 0xffffffff, 0]);
+        assert_eq!(anon4, 0x2000, "failed calls left the mappings unchanged");
+
+        hart.write_int_register(IntRegister::numbered(17), 4000);
+        assert_eq!(sys.handle_rv32(&mut hart), SyscallStatus::Unsupported(4000));
+        assert_eq!(
+            hart.read_int_register(IntRegister::numbered(10)),
+            -38i32 as u32,
+            "unsupported call returns -ENOSYS"
+        );
+    }
+}
@@ -0,0 +1,710 @@
+mod asm;
+mod parse;
+
+pub use parse::ParseError;
+
+use crate::data::Byte;
+use crate::data::HalfwordUnsigned as Halfword;
+use crate::data::LongwordUnsigned as Longword;
+use crate::data::QuadwordUnsigned as Quadword;
+use crate::data::WordUnsigned as Word;
+use crate::exec::{fetch, step_rv32, ExecStatus};
+use crate::hart::{Hart, SingleThreadUserHart};
+use crate::instruction::{Operation, OperationRV32};
+use crate::isa::RV32;
+use crate::memory::{AtomicOp, Bus, FenceSet, MemoryError, SparseMemory};
+use crate::register::IntRegister;
+use crate::smp::SharedBus;
+
+use core::fmt;
+use std::boxed::Box;
+use std::collections::BTreeMap;
+use std::string::String;
+use std::vec;
+use std::vec::Vec;
+
+type Op = OperationRV32;
+
+// Where each thread's code and each shared location are placed in memory.
+const CODE_BASE: u32 = 0x1000;
+const CODE_STRIDE: u32 = 0x1000;
+const LOCATION_BASE: u32 = 0x10_0000;
+const LOCATION_STRIDE: u32 = 0x40;
+
+// The number of instructions a thread may execute before it is assumed to
+// be stuck in a loop, and is stopped.
+const MAX_THREAD_STEPS: usize = 1000;
+
+/// A small multi-hart program together with a condition on its final state,
+/// as used to describe the behaviour permitted by a memory model.
+///
+/// Tests are usually written in the litmus format of the `herd` tool and
+/// loaded with `LitmusTest::parse`, which supports the subset of RV32
+/// instructions used by the official RVWMO litmus suite.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LitmusTest {
+    pub name: String,
+
+    /// The names of the shared memory locations.
+    pub locations: Vec<String>,
+
+    /// The machine code for each thread, which runs on its own hart.
+    pub threads: Vec<Vec<u32>>,
+
+    /// The initial values of integer registers, given as a thread index, a
+    /// register number and a value. Registers not listed start as zero.
+    pub registers: Vec<(usize, usize, u32)>,
+
+    /// The initial values of memory locations, given as an index into
+    /// `locations` and a value. Locations not listed start as zero.
+    pub memory: Vec<(usize, u32)>,
+
+    pub condition: Condition,
+}
+
+impl LitmusTest {
+    /// Parses a test written in the litmus format of the `herd` tool.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        parse::parse(text)
+    }
+
+    /// Returns the address at which the location with the given index is
+    /// placed.
+    pub fn location_address(index: usize) -> u32 {
+        LOCATION_BASE + index as u32 * LOCATION_STRIDE
+    }
+}
+
+/// Something in the final state of a litmus test that a condition can refer
+/// to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Observable {
+    /// An integer register of a thread, given as the thread index and the
+    /// register number.
+    Register(usize, usize),
+
+    /// A shared memory location, given as an index into the test's
+    /// `locations`.
+    Location(usize),
+}
+
+/// A proposition about the final state of a litmus test.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Prop {
+    True,
+    Equal(Observable, u32),
+    Not(Box<Prop>),
+    And(Box<Prop>, Box<Prop>),
+    Or(Box<Prop>, Box<Prop>),
+}
+
+impl Prop {
+    /// Evaluates the proposition, looking up each observable's value with
+    /// the given function.
+    pub fn eval(&self, value: &impl Fn(Observable) -> u32) -> bool {
+        match self {
+            Prop::True => true,
+            Prop::Equal(o, v) => value(*o) == *v,
+            Prop::Not(p) => !p.eval(value),
+            Prop::And(a, b) => a.eval(value) && b.eval(value),
+            Prop::Or(a, b) => a.eval(value) || b.eval(value),
+        }
+    }
+
+    fn observables(&self, into: &mut Vec<Observable>) {
+        match self {
+            Prop::True => {}
+            Prop::Equal(o, _) => {
+                if !into.contains(o) {
+                    into.push(*o);
+                }
+            }
+            Prop::Not(p) => p.observables(into),
+            Prop::And(a, b) | Prop::Or(a, b) => {
+                a.observables(into);
+                b.observables(into);
+            }
+        }
+    }
+}
+
+/// How a litmus test's condition applies to its possible final states.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantifier {
+    /// Some final state satisfies the proposition.
+    Exists,
+    /// No final state satisfies the proposition.
+    NotExists,
+    /// Every final state satisfies the proposition.
+    Forall,
+}
+
+/// The final condition of a litmus test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    pub quantifier: Quantifier,
+    pub prop: Prop,
+}
+
+impl Condition {
+    /// Returns true if the given final state is a witness for the
+    /// condition's interesting case: one that satisfies the proposition of
+    /// an `exists` or `~exists` condition, or violates that of a `forall`.
+    pub fn is_witness(&self, value: &impl Fn(Observable) -> u32) -> bool {
+        match self.quantifier {
+            Quantifier::Exists | Quantifier::NotExists => self.prop.eval(value),
+            Quantifier::Forall => !self.prop.eval(value),
+        }
+    }
+}
+
+/// Whether a memory model permits the witness of a litmus test's condition
+/// to be observed, as listed in the results published alongside a litmus
+/// suite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Expectation {
+    Allowed,
+    Forbidden,
+}
+
+/// Parses a list of expected results with one test per line, each giving
+/// the test name followed by `Allowed` or `Forbidden`. Blank lines and
+/// lines beginning with `#` are ignored.
+pub fn parse_expectations(text: &str) -> Result<Vec<(String, Expectation)>, ParseError> {
+    let mut expectations = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let expectation = match words.next() {
+            Some("Allowed") => Expectation::Allowed,
+            Some("Forbidden") => Expectation::Forbidden,
+            _ => return Err(ParseError::new(i + 1, "expected Allowed or Forbidden")),
+        };
+        expectations.push((String::from(name), expectation));
+    }
+    Ok(expectations)
+}
+
+/// The values of a litmus test's observables in one final state, in the
+/// order they first appear in its condition.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Outcome(pub Vec<(Observable, u32)>);
+
+impl Outcome {
+    fn value(&self, o: Observable) -> u32 {
+        self.0.iter().find(|(k, _)| *k == o).map_or(0, |(_, v)| *v)
+    }
+}
+
+/// Selects which interleavings of a litmus test are run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Exploration {
+    /// Runs every interleaving, unless there are more than `max_runs` of
+    /// them.
+    Exhaustive { max_runs: usize },
+
+    /// Runs the given number of interleavings, chosen pseudo-randomly from
+    /// the given seed.
+    Random { runs: usize, seed: u64 },
+}
+
+/// The final states observed by running a litmus test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LitmusResult {
+    /// Each distinct final state, with the number of runs that reached it.
+    pub outcomes: BTreeMap<Outcome, usize>,
+
+    /// The number of interleavings that were run.
+    pub runs: usize,
+
+    /// True if an exhaustive exploration ran every interleaving.
+    pub complete: bool,
+
+    /// True if any of the final states is a witness for the condition.
+    pub witnessed: bool,
+}
+
+impl LitmusResult {
+    /// Returns false if the memory model forbids the witness of the test's
+    /// condition and yet it was observed.
+    ///
+    /// An allowed witness that was never observed is not a disagreement,
+    /// because the interleavings explored are only a subset of the
+    /// behaviours that RVWMO permits.
+    pub fn agrees_with(&self, expected: Expectation) -> bool {
+        !(expected == Expectation::Forbidden && self.witnessed)
+    }
+}
+
+/// Runs a litmus test under many interleavings of its threads, collecting
+/// the final states that result.
+///
+/// Each thread runs on its own hart, with a store buffer between the hart
+/// and the shared memory. Stores wait in the buffer until the exploration
+/// chooses to write them to memory, and may be written in any order except
+/// that stores to overlapping addresses, or separated by a fence ordering
+/// writes, stay in order. Loads see the hart's own buffered stores. A fence
+/// ordering earlier writes before later reads empties the buffer, as does
+/// any atomic instruction. Loads are performed in program order.
+///
+/// This produces a subset of the behaviours that RVWMO allows, including
+/// those of the classic store buffering and message passing tests, so any
+/// witness it observes for a condition that RVWMO forbids indicates a bug
+/// in the emulator.
+pub fn run_litmus(test: &LitmusTest, exploration: Exploration) -> LitmusResult {
+    let mut observables = Vec::new();
+    test.condition.prop.observables(&mut observables);
+    let mut result = LitmusResult {
+        outcomes: BTreeMap::new(),
+        runs: 0,
+        complete: false,
+        witnessed: false,
+    };
+    let record = |result: &mut LitmusResult, outcome: Outcome| {
+        result.witnessed |= test.condition.is_witness(&|o| outcome.value(o));
+        *result.outcomes.entry(outcome).or_insert(0) += 1;
+        result.runs += 1;
+    };
+    match exploration {
+        Exploration::Exhaustive { max_runs } => {
+            // Each entry is the choice made at that point in the previous
+            // run and the number of choices there were.
+            let mut path: Vec<(usize, usize)> = Vec::new();
+            loop {
+                let mut depth = 0;
+                let outcome = run_once(test, &observables, |n| {
+                    if depth == path.len() {
+                        path.push((0, n));
+                    }
+                    depth += 1;
+                    path[depth - 1].0
+                });
+                record(&mut result, outcome);
+                while let Some((chosen, n)) = path.pop() {
+                    if chosen + 1 < n {
+                        path.push((chosen + 1, n));
+                        break;
+                    }
+                }
+                if path.is_empty() {
+                    result.complete = true;
+                    break;
+                }
+                if result.runs >= max_runs {
+                    break;
+                }
+            }
+        }
+        Exploration::Random { runs, seed } => {
+            let mut state = seed | 1;
+            for _ in 0..runs {
+                let outcome = run_once(test, &observables, |n| {
+                    // xorshift64
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    (state % n as u64) as usize
+                });
+                record(&mut result, outcome);
+            }
+        }
+    }
+    result
+}
+
+// A choice the exploration can make about what happens next.
+#[derive(Debug, Clone, Copy)]
+enum Event {
+    // Execute the given thread's next memory instruction.
+    Step(usize),
+    // Write the given entry in the given thread's store buffer to memory.
+    Drain(usize, usize),
+}
+
+// Runs a test once, calling `choose` with the number of possible events
+// whenever there is more than one, to select one of them.
+fn run_once(
+    test: &LitmusTest,
+    observables: &[Observable],
+    mut choose: impl FnMut(usize) -> usize,
+) -> Outcome {
+    let mut mem = SparseMemory::new();
+    for (index, value) in &test.memory {
+        let addr = LitmusTest::location_address(*index) as usize;
+        // A sparse memory accepts any address.
+        let _ = mem.write_word(addr, *value);
+    }
+    for (t, code) in test.threads.iter().enumerate() {
+        let base = (CODE_BASE + t as u32 * CODE_STRIDE) as usize;
+        for (i, word) in code.iter().chain([0x00000073].iter()).enumerate() {
+            let _ = mem.write_word(base + i * 4, *word); // ..., ecall
+        }
+    }
+    let shared = SharedBus::new(mem);
+    let mut harts: Vec<SingleThreadUserHart<RV32, StoreBuffer>> = (0..test.threads.len())
+        .map(|t| {
+            let mut hart = SingleThreadUserHart::new(StoreBuffer::new(shared.clone()));
+            hart.write_pc(CODE_BASE + t as u32 * CODE_STRIDE);
+            hart
+        })
+        .collect();
+    for (t, reg, value) in &test.registers {
+        harts[*t].write_int_register(IntRegister::numbered(*reg), *value);
+    }
+    let mut steps = vec![0; harts.len()];
+    let mut done = vec![false; harts.len()];
+    for t in 0..harts.len() {
+        run_local(&mut harts[t], &mut steps[t], &mut done[t]);
+    }
+
+    let mut events = Vec::new();
+    loop {
+        events.clear();
+        for (t, hart) in harts.iter_mut().enumerate() {
+            if !done[t] {
+                events.push(Event::Step(t));
+            }
+            hart.with_memory(|buf| {
+                for i in 0..buf.pending.len() {
+                    if buf.can_drain(i) {
+                        events.push(Event::Drain(t, i));
+                    }
+                }
+            });
+        }
+        let event = match events.len() {
+            0 => break,
+            1 => events[0],
+            n => events[choose(n)],
+        };
+        match event {
+            Event::Step(t) => {
+                let hart = &mut harts[t];
+                if let ExecStatus::Running = step_rv32(hart) {
+                    steps[t] += 1;
+                    run_local(hart, &mut steps[t], &mut done[t]);
+                } else {
+                    done[t] = true;
+                }
+            }
+            Event::Drain(t, i) => harts[t].with_memory(|buf| buf.drain(i)),
+        }
+    }
+
+    let mut mem = shared.lock();
+    Outcome(
+        observables
+            .iter()
+            .map(|o| {
+                let v = match *o {
+                    Observable::Register(t, r) => harts
+                        .get(t)
+                        .map_or(0, |h| h.read_int_register(IntRegister::numbered(r))),
+                    Observable::Location(i) => mem
+                        .read_word(LitmusTest::location_address(i) as usize)
+                        .unwrap_or(0),
+                };
+                (*o, v)
+            })
+            .collect(),
+    )
+}
+
+// Runs the given hart until its next instruction is one whose effects
+// could be observed by another hart, since the order in which the others
+// execute relative to those makes no difference.
+fn run_local(
+    hart: &mut SingleThreadUserHart<RV32, StoreBuffer>,
+    steps: &mut usize,
+    done: &mut bool,
+) {
+    while !*done {
+        if *steps >= MAX_THREAD_STEPS {
+            *done = true;
+            return;
+        }
+        let pc = hart.read_pc();
+        let op = match fetch(hart, pc) {
+            Ok(raw) => Op::decode_raw(raw),
+            Err(_) => return,
+        };
+        if is_shared(&op) {
+            return;
+        }
+        match step_rv32(hart) {
+            ExecStatus::Running => *steps += 1,
+            _ => *done = true,
+        }
+    }
+}
+
+fn is_shared(op: &Op) -> bool {
+    matches!(
+        op,
+        Op::Lb { .. }
+            | Op::Lbu { .. }
+            | Op::Lh { .. }
+            | Op::Lhu { .. }
+            | Op::Lw { .. }
+            | Op::Sb { .. }
+            | Op::Sh { .. }
+            | Op::Sw { .. }
+            | Op::Flw { .. }
+            | Op::Fsw { .. }
+            | Op::Fld { .. }
+            | Op::Fsd { .. }
+            | Op::LrW { .. }
+            | Op::ScW { .. }
+            | Op::AmoaddW { .. }
+            | Op::AmoandW { .. }
+            | Op::AmomaxW { .. }
+            | Op::AmomaxuW { .. }
+            | Op::AmominW { .. }
+            | Op::AmominuW { .. }
+            | Op::AmoorW { .. }
+            | Op::AmoswapW { .. }
+            | Op::AmoxorW { .. }
+            | Op::Fence { .. }
+    )
+}
+
+struct Store {
+    addr: u32,
+    bytes: Vec<u8>,
+    // Stores in different epochs were separated by a fence ordering writes.
+    epoch: usize,
+}
+
+impl Store {
+    fn overlaps(&self, other: &Store) -> bool {
+        let end = |s: &Store| s.addr as u64 + s.bytes.len() as u64;
+        (self.addr as u64) < end(other) && (other.addr as u64) < end(self)
+    }
+}
+
+// A per-hart buffer of stores that have executed but are not yet visible
+// to the other harts.
+struct StoreBuffer {
+    shared: SharedBus<SparseMemory>,
+    pending: Vec<Store>,
+    epoch: usize,
+}
+
+impl StoreBuffer {
+    fn new(shared: SharedBus<SparseMemory>) -> Self {
+        Self {
+            shared,
+            pending: Vec::new(),
+            epoch: 0,
+        }
+    }
+
+    fn can_drain(&self, i: usize) -> bool {
+        let store = &self.pending[i];
+        self.pending[..i]
+            .iter()
+            .all(|older| older.epoch == store.epoch && !older.overlaps(store))
+    }
+
+    fn drain(&mut self, i: usize) {
+        let store = self.pending.remove(i);
+        let mut mem = self.shared.lock();
+        for (n, b) in store.bytes.iter().enumerate() {
+            let _ = mem.write_byte(store.addr.wrapping_add(n as u32) as usize, *b);
+        }
+    }
+
+    fn drain_all(&mut self) {
+        while !self.pending.is_empty() {
+            self.drain(0);
+        }
+    }
+
+    fn read_le(&mut self, addr: u32, size: usize) -> Result<u128, MemoryError> {
+        let mut bytes = [0u8; 16];
+        {
+            let mut mem = self.shared.lock();
+            for (n, b) in bytes[..size].iter_mut().enumerate() {
+                *b = mem.read_byte(addr.wrapping_add(n as u32) as usize)?;
+            }
+        }
+        // Later stores take priority over earlier ones.
+        for store in &self.pending {
+            for (n, b) in store.bytes.iter().enumerate() {
+                let offset = store.addr.wrapping_add(n as u32).wrapping_sub(addr) as usize;
+                if offset < size {
+                    bytes[offset] = *b;
+                }
+            }
+        }
+        Ok(u128::from_le_bytes(bytes))
+    }
+
+    fn write_le(&mut self, addr: u32, size: usize, v: u128) -> Result<(), MemoryError> {
+        self.pending.push(Store {
+            addr,
+            bytes: v.to_le_bytes()[..size].to_vec(),
+            epoch: self.epoch,
+        });
+        Ok(())
+    }
+}
+
+impl Bus<u32> for StoreBuffer {
+    fn read_byte(&mut self, addr: u32) -> Result<Byte, MemoryError> {
+        Ok(self.read_le(addr, 1)? as Byte)
+    }
+
+    fn write_byte(&mut self, addr: u32, data: Byte) -> Result<(), MemoryError> {
+        self.write_le(addr, 1, data as u128)
+    }
+
+    fn read_halfword(&mut self, addr: u32) -> Result<Halfword, MemoryError> {
+        Ok(self.read_le(addr, 2)? as Halfword)
+    }
+
+    fn write_halfword(&mut self, addr: u32, data: Halfword) -> Result<(), MemoryError> {
+        self.write_le(addr, 2, data as u128)
+    }
+
+    fn read_word(&mut self, addr: u32) -> Result<Word, MemoryError> {
+        Ok(self.read_le(addr, 4)? as Word)
+    }
+
+    fn write_word(&mut self, addr: u32, data: Word) -> Result<(), MemoryError> {
+        self.write_le(addr, 4, data as u128)
+    }
+
+    fn read_longword(&mut self, addr: u32) -> Result<Longword, MemoryError> {
+        Ok(self.read_le(addr, 8)? as Longword)
+    }
+
+    fn write_longword(&mut self, addr: u32, data: Longword) -> Result<(), MemoryError> {
+        self.write_le(addr, 8, data as u128)
+    }
+
+    fn read_quadword(&mut self, addr: u32) -> Result<Quadword, MemoryError> {
+        self.read_le(addr, 16)
+    }
+
+    fn write_quadword(&mut self, addr: u32, data: Quadword) -> Result<(), MemoryError> {
+        self.write_le(addr, 16, data)
+    }
+
+    fn amo_word(&mut self, addr: u32, op: AtomicOp, operand: Word) -> Result<Word, MemoryError> {
+        self.drain_all();
+        let old = self.read_word(addr)?;
+        self.drain_write(addr, op.apply(old, operand));
+        Ok(old)
+    }
+
+    fn compare_exchange_word(
+        &mut self,
+        addr: u32,
+        current: Word,
+        new: Word,
+    ) -> Result<Result<Word, Word>, MemoryError> {
+        self.drain_all();
+        let old = self.read_word(addr)?;
+        if old != current {
+            return Ok(Err(old));
+        }
+        self.drain_write(addr, new);
+        Ok(Ok(old))
+    }
+
+    fn fence(&mut self, pred: FenceSet, succ: FenceSet) {
+        if pred.write && succ.read {
+            self.drain_all();
+        } else if pred.write && succ.write {
+            self.epoch += 1;
+        }
+    }
+}
+
+impl StoreBuffer {
+    // Writes a word straight to memory, as the store half of an atomic
+    // read-modify-write.
+    fn drain_write(&mut self, addr: u32, v: Word) {
+        let _ = self.write_word(addr, v);
+        self.drain_all();
+    }
+}
+
+impl fmt::Display for Observable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Observable::Register(t, r) => write!(f, "{}:x{}", t, r),
+            Observable::Location(i) => write!(f, "[{}]", i),
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (o, v) in &self.0 {
+            write!(f, "{}={}; ", o, *v as i32)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_litmus, Exploration, LitmusTest, Observable, Quantifier};
+
+    #[test]
+    fn store_buffering() {
+        let sb = "\
+RISCV SB
+\"PodWR Fre PodWR Fre\"
+{
+0:x5=1; 0:x6=x; 0:x8=y;
+1:x5=1; 1:x6=y; 1:x8=x;
+}
+ P0          | P1          ;
+ sw x5,0(x6) | sw x5,0(x6) ;
+ lw x7,0(x8) | lw x7,0(x8) ;
+exists
+(0:x7=0 /\\ 1:x7=0)
+";
+        let test = LitmusTest::parse(sb).unwrap();
+        assert_eq!(test.name, "SB");
+        assert_eq!(test.locations, ["x", "y"]);
+        assert_eq!(
+            test.threads,
+            [[0x00532023, 0x00042383], [0x00532023, 0x00042383]]
+        );
+        assert_eq!(test.condition.quantifier, Quantifier::Exists);
+        let unknown_thread = sb.replace("1:x8=x;", "1:x8=x; 2:x5=1;");
+        assert_eq!(
+            LitmusTest::parse(&unknown_thread).map_err(|e| e.line),
+            Err(3),
+            "initial state for a thread with no program"
+        );
+
+        let result = run_litmus(&test, Exploration::Exhaustive { max_runs: 10_000 });
+        assert!(result.complete);
+        assert!(result.witnessed, "stores can be delayed past later loads");
+        assert_eq!(result.outcomes.len(), 4);
+
+        // With a fence between them, they can't.
+        let fenced = sb.replace(
+            " lw x7,0(x8) | lw x7,0(x8) ;",
+            " fence rw,rw | fence rw,rw ;\n lw x7,0(x8) | lw x7,0(x8) ;",
+        );
+        let test = LitmusTest::parse(&fenced).unwrap();
+        let result = run_litmus(&test, Exploration::Random { runs: 500, seed: 1 });
+        assert_eq!(result.runs, 500);
+        assert!(!result.witnessed);
+        assert!(result
+            .outcomes
+            .keys()
+            .all(|o| o.value(Observable::Register(0, 7)) == 1
+                || o.value(Observable::Register(1, 7)) == 1));
+    }
+}
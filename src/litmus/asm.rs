@@ -0,0 +1,461 @@
+use super::parse::{parse_int_register, parse_value};
+use super::ParseError;
+
+use std::format;
+use std::vec::Vec;
+
+// An assembler for the instructions that appear in litmus tests: the RV32I
+// integer and control transfer instructions, loads and stores, fences, and
+// the A extension. Pseudo-instructions are limited to `li`, `mv`, `nop` and
+// `j`.
+
+const OP: u32 = 0x33;
+const OP_IMM: u32 = 0x13;
+const LOAD: u32 = 0x03;
+const STORE: u32 = 0x23;
+const BRANCH: u32 = 0x63;
+const JAL: u32 = 0x6f;
+const LUI: u32 = 0x37;
+const MISC_MEM: u32 = 0x0f;
+const AMO: u32 = 0x2f;
+
+// Assembles the lines of one thread, given with their line numbers. A line
+// may begin with a label, which branches on other lines can refer to.
+pub(super) fn assemble(lines: &[(usize, &str)]) -> Result<Vec<u32>, ParseError> {
+    // The first pass finds the address of each label. Every instruction is
+    // one word except `li` with a large value, which is two.
+    let mut labels = Vec::new();
+    let mut statements = Vec::new();
+    let mut offset = 0;
+    for &(n, line) in lines {
+        let mut line = line;
+        while let Some((label, rest)) = line.split_once(':') {
+            if label.contains(char::is_whitespace) {
+                break;
+            }
+            labels.push((label.trim(), offset));
+            line = rest.trim();
+        }
+        if line.is_empty() {
+            continue;
+        }
+        let (mnemonic, operands) = match line.split_once(char::is_whitespace) {
+            Some((mnemonic, operands)) => (mnemonic, operands.trim()),
+            None => (line, ""),
+        };
+        let operands: Vec<&str> = if operands.is_empty() {
+            Vec::new()
+        } else {
+            operands.split(',').map(str::trim).collect()
+        };
+        let size = match (mnemonic, operands.get(1).and_then(|v| parse_value(v))) {
+            ("li", Some(v)) if !fits_signed(v, 12) => 8,
+            _ => 4,
+        };
+        statements.push((n, offset, mnemonic, operands));
+        offset += size;
+    }
+
+    let mut code = Vec::new();
+    for (n, offset, mnemonic, operands) in statements {
+        let asm = Assembler {
+            line: n,
+            offset,
+            operands: &operands,
+            labels: &labels,
+        };
+        asm.instruction(mnemonic, &mut code)?;
+    }
+    Ok(code)
+}
+
+struct Assembler<'a> {
+    line: usize,
+    offset: i32,
+    operands: &'a [&'a str],
+    labels: &'a [(&'a str, i32)],
+}
+
+impl Assembler<'_> {
+    fn error(&self, message: &str) -> ParseError {
+        ParseError::new(self.line, message)
+    }
+
+    fn operand(&self, i: usize) -> Result<&str, ParseError> {
+        self.operands
+            .get(i)
+            .copied()
+            .ok_or_else(|| self.error("missing operand"))
+    }
+
+    fn expect_operands(&self, count: usize) -> Result<(), ParseError> {
+        if self.operands.len() != count {
+            return Err(self.error(&format!("expected {} operands", count)));
+        }
+        Ok(())
+    }
+
+    fn reg(&self, i: usize) -> Result<u32, ParseError> {
+        let s = self.operand(i)?;
+        parse_int_register(s)
+            .map(|r| r.num() as u32)
+            .ok_or_else(|| self.error(&format!("unknown register {}", s)))
+    }
+
+    fn imm(&self, i: usize, bits: u32) -> Result<u32, ParseError> {
+        let s = self.operand(i)?;
+        match parse_value(s) {
+            Some(v) if fits_signed(v, bits) => Ok(v),
+            Some(_) => Err(self.error(&format!("{} is out of range", s))),
+            None => Err(self.error(&format!("invalid number {}", s))),
+        }
+    }
+
+    // Parses an address operand `offset(reg)`, in which the offset may be
+    // omitted, returning the register and offset.
+    fn address(&self, i: usize) -> Result<(u32, u32), ParseError> {
+        let s = self.operand(i)?;
+        let (offset, reg) = s
+            .strip_suffix(')')
+            .and_then(|s| s.split_once('('))
+            .ok_or_else(|| self.error("expected an address"))?;
+        let reg = parse_int_register(reg.trim())
+            .ok_or_else(|| self.error(&format!("unknown register {}", reg)))?;
+        let offset = match offset.trim() {
+            "" => 0,
+            offset => match parse_value(offset) {
+                Some(v) if fits_signed(v, 12) => v,
+                _ => return Err(self.error(&format!("invalid offset {}", offset))),
+            },
+        };
+        Ok((reg.num() as u32, offset))
+    }
+
+    // Returns the distance from this instruction to a label.
+    fn target(&self, i: usize, bits: u32) -> Result<u32, ParseError> {
+        let s = self.operand(i)?;
+        let distance = match self.labels.iter().find(|(label, _)| *label == s) {
+            Some((_, offset)) => offset.wrapping_sub(self.offset) as u32,
+            None => parse_value(s).ok_or_else(|| self.error(&format!("unknown label {}", s)))?,
+        };
+        if !fits_signed(distance, bits) {
+            return Err(self.error("branch target is too far away"));
+        }
+        Ok(distance)
+    }
+
+    fn instruction(&self, mnemonic: &str, code: &mut Vec<u32>) -> Result<(), ParseError> {
+        if let Some(funct) = op_funct(mnemonic) {
+            self.expect_operands(3)?;
+            let (funct7, funct3) = funct;
+            code.push(r_type(
+                funct7,
+                self.reg(2)?,
+                self.reg(1)?,
+                funct3,
+                self.reg(0)?,
+                OP,
+            ));
+        } else if let Some(funct3) = op_imm_funct(mnemonic) {
+            self.expect_operands(3)?;
+            let imm = match mnemonic {
+                "slli" | "srli" | "srai" => {
+                    let shamt = self.imm(2, 12)?;
+                    if shamt > 31 {
+                        return Err(self.error("shift amount is out of range"));
+                    }
+                    if mnemonic == "srai" {
+                        shamt | 0x400
+                    } else {
+                        shamt
+                    }
+                }
+                _ => self.imm(2, 12)?,
+            };
+            code.push(i_type(imm, self.reg(1)?, funct3, self.reg(0)?, OP_IMM));
+        } else if let Some(funct3) = load_funct(mnemonic) {
+            self.expect_operands(2)?;
+            let (rs1, imm) = self.address(1)?;
+            code.push(i_type(imm, rs1, funct3, self.reg(0)?, LOAD));
+        } else if let Some(funct3) = store_funct(mnemonic) {
+            self.expect_operands(2)?;
+            let (rs1, imm) = self.address(1)?;
+            code.push(s_type(imm, self.reg(0)?, rs1, funct3));
+        } else if let Some(funct3) = branch_funct(mnemonic) {
+            self.expect_operands(3)?;
+            code.push(b_type(
+                self.target(2, 13)?,
+                self.reg(1)?,
+                self.reg(0)?,
+                funct3,
+            ));
+        } else if let Some((funct5, aq, rl)) = amo_funct(mnemonic) {
+            let (rd, rs2, addr) = match funct5 {
+                // lr.w rd, (rs1)
+                2 => {
+                    self.expect_operands(2)?;
+                    (self.reg(0)?, 0, 1)
+                }
+                _ => {
+                    self.expect_operands(3)?;
+                    (self.reg(0)?, self.reg(1)?, 2)
+                }
+            };
+            let (rs1, offset) = self.address(addr)?;
+            if offset != 0 {
+                return Err(self.error("atomic instructions take no offset"));
+            }
+            code.push(
+                funct5 << 27
+                    | (aq as u32) << 26
+                    | (rl as u32) << 25
+                    | rs2 << 20
+                    | rs1 << 15
+                    | 2 << 12
+                    | rd << 7
+                    | AMO,
+            );
+        } else {
+            match mnemonic {
+                "li" => {
+                    self.expect_operands(2)?;
+                    let rd = self.reg(0)?;
+                    let v = self.imm(1, 32)?;
+                    if fits_signed(v, 12) {
+                        code.push(i_type(v, 0, 0, rd, OP_IMM));
+                    } else {
+                        // The addi sign-extends its immediate, so round the
+                        // upper part to compensate.
+                        let upper = v.wrapping_add(0x800) & 0xfffff000;
+                        code.push(upper | rd << 7 | LUI);
+                        code.push(i_type(v.wrapping_sub(upper) & 0xfff, rd, 0, rd, OP_IMM));
+                    }
+                }
+                "mv" => {
+                    self.expect_operands(2)?;
+                    code.push(i_type(0, self.reg(1)?, 0, self.reg(0)?, OP_IMM));
+                }
+                "nop" => {
+                    self.expect_operands(0)?;
+                    code.push(i_type(0, 0, 0, 0, OP_IMM));
+                }
+                "j" => {
+                    self.expect_operands(1)?;
+                    code.push(j_type(self.target(0, 21)?, 0));
+                }
+                "jal" => {
+                    self.expect_operands(2)?;
+                    code.push(j_type(self.target(1, 21)?, self.reg(0)?));
+                }
+                "fence" => {
+                    let (pred, succ) = match self.operands {
+                        [] => (0xf, 0xf),
+                        [pred, succ] => (self.fence_set(pred)?, self.fence_set(succ)?),
+                        _ => return Err(self.error("expected a predecessor and successor set")),
+                    };
+                    code.push(pred << 24 | succ << 20 | MISC_MEM);
+                }
+                "fence.tso" => {
+                    self.expect_operands(0)?;
+                    code.push(0x8330_0000 | MISC_MEM);
+                }
+                "fence.i" => {
+                    self.expect_operands(0)?;
+                    code.push(1 << 12 | MISC_MEM);
+                }
+                _ => return Err(self.error(&format!("unsupported instruction {}", mnemonic))),
+            }
+        }
+        Ok(())
+    }
+
+    // Parses a fence's predecessor or successor set, such as `rw`.
+    fn fence_set(&self, s: &str) -> Result<u32, ParseError> {
+        let mut bits = 0;
+        for c in s.chars() {
+            bits |= match c {
+                'i' => 8,
+                'o' => 4,
+                'r' => 2,
+                'w' => 1,
+                _ => return Err(self.error(&format!("invalid fence set {}", s))),
+            };
+        }
+        Ok(bits)
+    }
+}
+
+fn fits_signed(v: u32, bits: u32) -> bool {
+    if bits >= 32 {
+        return true;
+    }
+    let v = v as i32;
+    let limit = 1 << (bits - 1);
+    -limit <= v && v < limit
+}
+
+fn op_funct(mnemonic: &str) -> Option<(u32, u32)> {
+    Some(match mnemonic {
+        "add" => (0x00, 0),
+        "sub" => (0x20, 0),
+        "sll" => (0x00, 1),
+        "slt" => (0x00, 2),
+        "sltu" => (0x00, 3),
+        "xor" => (0x00, 4),
+        "srl" => (0x00, 5),
+        "sra" => (0x20, 5),
+        "or" => (0x00, 6),
+        "and" => (0x00, 7),
+        _ => return None,
+    })
+}
+
+fn op_imm_funct(mnemonic: &str) -> Option<u32> {
+    Some(match mnemonic {
+        "addi" => 0,
+        "slli" => 1,
+        "slti" => 2,
+        "sltiu" => 3,
+        "xori" => 4,
+        "srli" | "srai" => 5,
+        "ori" => 6,
+        "andi" => 7,
+        _ => return None,
+    })
+}
+
+fn load_funct(mnemonic: &str) -> Option<u32> {
+    Some(match mnemonic {
+        "lb" => 0,
+        "lh" => 1,
+        "lw" => 2,
+        "lbu" => 4,
+        "lhu" => 5,
+        _ => return None,
+    })
+}
+
+fn store_funct(mnemonic: &str) -> Option<u32> {
+    Some(match mnemonic {
+        "sb" => 0,
+        "sh" => 1,
+        "sw" => 2,
+        _ => return None,
+    })
+}
+
+fn branch_funct(mnemonic: &str) -> Option<u32> {
+    Some(match mnemonic {
+        "beq" => 0,
+        "bne" => 1,
+        "blt" => 4,
+        "bge" => 5,
+        "bltu" => 6,
+        "bgeu" => 7,
+        _ => return None,
+    })
+}
+
+// Returns the funct5 field and the aq and rl bits of a word-sized atomic
+// instruction, such as `amoswap.w.aqrl`.
+fn amo_funct(mnemonic: &str) -> Option<(u32, bool, bool)> {
+    let (base, ordering) = match mnemonic.find(".w") {
+        Some(i) => (&mnemonic[..i], &mnemonic[i + 2..]),
+        None => return None,
+    };
+    let funct5 = match base {
+        "amoadd" => 0,
+        "amoswap" => 1,
+        "lr" => 2,
+        "sc" => 3,
+        "amoxor" => 4,
+        "amoor" => 8,
+        "amoand" => 12,
+        "amomin" => 16,
+        "amomax" => 20,
+        "amominu" => 24,
+        "amomaxu" => 28,
+        _ => return None,
+    };
+    let (aq, rl) = match ordering {
+        "" => (false, false),
+        ".aq" => (true, false),
+        ".rl" => (false, true),
+        ".aqrl" | ".aq.rl" => (true, true),
+        _ => return None,
+    };
+    Some((funct5, aq, rl))
+}
+
+fn r_type(funct7: u32, rs2: u32, rs1: u32, funct3: u32, rd: u32, opcode: u32) -> u32 {
+    funct7 << 25 | rs2 << 20 | rs1 << 15 | funct3 << 12 | rd << 7 | opcode
+}
+
+fn i_type(imm: u32, rs1: u32, funct3: u32, rd: u32, opcode: u32) -> u32 {
+    (imm & 0xfff) << 20 | rs1 << 15 | funct3 << 12 | rd << 7 | opcode
+}
+
+fn s_type(imm: u32, rs2: u32, rs1: u32, funct3: u32) -> u32 {
+    (imm >> 5 & 0x7f) << 25 | rs2 << 20 | rs1 << 15 | funct3 << 12 | (imm & 0x1f) << 7 | STORE
+}
+
+fn b_type(imm: u32, rs2: u32, rs1: u32, funct3: u32) -> u32 {
+    (imm >> 12 & 1) << 31
+        | (imm >> 5 & 0x3f) << 25
+        | rs2 << 20
+        | rs1 << 15
+        | funct3 << 12
+        | (imm >> 1 & 0xf) << 8
+        | (imm >> 11 & 1) << 7
+        | BRANCH
+}
+
+fn j_type(imm: u32, rd: u32) -> u32 {
+    (imm >> 20 & 1) << 31
+        | (imm >> 1 & 0x3ff) << 21
+        | (imm >> 11 & 1) << 20
+        | (imm >> 12 & 0xff) << 12
+        | rd << 7
+        | JAL
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assemble;
+    use std::vec::Vec;
+
+    #[test]
+    fn encodings() {
+        // Checked against the output of llvm-mc.
+        let lines = [
+            "LC00:",
+            "add a0,t1,s2",
+            "sra x5,x6,x7",
+            "srai x5,x6,3",
+            "addi x5,x6,-1",
+            "li x7,0x12345fff",
+            "lhu x5,-2(x6)",
+            "sb x5,2047(x6)",
+            "bne x5,x0,LC00",
+            "j LC01",
+            "amoswap.w.aqrl x5,x6,(x7)",
+            "lr.w.aq x5,0(x6)",
+            "sc.w.rl x7,x8,(x6)",
+            "amomaxu.w x5,x6,(x7)",
+            "LC01: fence rw,w",
+            "fence.tso",
+            "fence.i",
+        ];
+        let lines: Vec<(usize, &str)> = lines.iter().enumerate().map(|(i, l)| (i, *l)).collect();
+        assert_eq!(
+            assemble(&lines).unwrap(),
+            [
+                0x01230533, 0x407352b3, 0x40335293, 0xfff30293, 0x123463b7, 0xfff38393, 0xffe35283,
+                0x7e530fa3, 0xfe0290e3, 0x0140006f, 0x0e63a2af, 0x140322af, 0x1a8323af, 0xe063a2af,
+                0x0310000f, 0x8330000f, 0x0000100f,
+            ]
+        );
+        assert!(assemble(&[(7, "addi x5,x6,2048")]).is_err());
+        assert_eq!(assemble(&[(7, "frob x5")]).unwrap_err().line, 7);
+    }
+}
@@ -0,0 +1,362 @@
+use super::asm::assemble;
+use super::{Condition, LitmusTest, Observable, Prop, Quantifier};
+use crate::register::IntRegister;
+
+use core::fmt;
+use std::boxed::Box;
+use std::format;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+/// Describes why a litmus test could not be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// The line on which the problem was found, counting from one.
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+// Parses a test in herd's litmus format, which consists of a header line
+// naming the architecture and test, some optional metadata, the initial
+// state in braces, a table of instructions with a column for each thread,
+// and the final condition.
+pub(super) fn parse(text: &str) -> Result<LitmusTest, ParseError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    let (n, header) = lines
+        .next()
+        .ok_or_else(|| ParseError::new(1, "empty test"))?;
+    let mut words = header.split_whitespace();
+    if words.next() != Some("RISCV") {
+        return Err(ParseError::new(n, "expected a RISCV test"));
+    }
+    let name = words
+        .next()
+        .ok_or_else(|| ParseError::new(n, "missing test name"))?;
+    let mut test = LitmusTest {
+        name: String::from(name),
+        locations: Vec::new(),
+        threads: Vec::new(),
+        registers: Vec::new(),
+        memory: Vec::new(),
+        condition: Condition {
+            quantifier: Quantifier::Exists,
+            prop: Prop::True,
+        },
+    };
+
+    // Skip the metadata, and gather the initial state up to the closing
+    // brace, which may be spread over several lines.
+    let mut init = String::new();
+    let mut init_line = n;
+    for (n, line) in lines.by_ref() {
+        if let Some(rest) = line.strip_prefix('{') {
+            init_line = n;
+            init.push_str(rest);
+            break;
+        }
+    }
+    if !init.contains('}') {
+        for (_, line) in lines.by_ref() {
+            init.push(' ');
+            init.push_str(line);
+            if line.contains('}') {
+                break;
+            }
+        }
+    }
+    let init = init
+        .split('}')
+        .next()
+        .ok_or_else(|| ParseError::new(init_line, "missing initial state"))?;
+    for entry in init.split(';') {
+        parse_init(&mut test, init_line, entry)?;
+    }
+
+    // The program table, whose first row names the threads.
+    let (n, heading) = lines
+        .next()
+        .ok_or_else(|| ParseError::new(init_line, "missing program"))?;
+    let threads = cells(heading).len();
+    if threads == 0 {
+        return Err(ParseError::new(n, "expected thread names"));
+    }
+    let mut code: Vec<Vec<(usize, &str)>> = (0..threads).map(|_| Vec::new()).collect();
+    let mut condition = String::new();
+    let mut condition_line = n;
+    for (n, line) in lines.by_ref() {
+        if !line.contains('|') && !line.ends_with(';') {
+            condition_line = n;
+            condition.push_str(line);
+            break;
+        }
+        let row = cells(line);
+        if row.len() > threads {
+            return Err(ParseError::new(n, "too many columns"));
+        }
+        for (t, cell) in row.into_iter().enumerate() {
+            if !cell.is_empty() {
+                code[t].push((n, cell));
+            }
+        }
+    }
+    for thread in code {
+        test.threads.push(assemble(&thread)?);
+    }
+    if let Some((thread, _, _)) = test.registers.iter().find(|(t, _, _)| *t >= threads) {
+        return Err(ParseError::new(
+            init_line,
+            format!(
+                "initial state refers to thread {}, which has no program",
+                thread
+            ),
+        ));
+    }
+
+    // The condition, which may also be spread over several lines.
+    for (_, line) in lines {
+        condition.push(' ');
+        condition.push_str(line);
+    }
+    let condition = condition.trim();
+    let (quantifier, rest) = if let Some(rest) = condition.strip_prefix("~exists") {
+        (Quantifier::NotExists, rest)
+    } else if let Some(rest) = condition.strip_prefix("exists") {
+        (Quantifier::Exists, rest)
+    } else if let Some(rest) = condition.strip_prefix("forall") {
+        (Quantifier::Forall, rest)
+    } else {
+        return Err(ParseError::new(
+            condition_line,
+            "expected exists, ~exists or forall",
+        ));
+    };
+    let mut parser = PropParser {
+        test: &mut test,
+        line: condition_line,
+        tokens: tokenize(rest),
+        pos: 0,
+    };
+    let prop = parser.or()?;
+    if parser.pos != parser.tokens.len() {
+        return Err(ParseError::new(
+            condition_line,
+            "unexpected text after condition",
+        ));
+    }
+    test.condition = Condition { quantifier, prop };
+    Ok(test)
+}
+
+// Splits a row of the program table into the cell for each thread.
+fn cells(row: &str) -> Vec<&str> {
+    let row = row.trim().trim_end_matches(';');
+    row.split('|').map(str::trim).collect()
+}
+
+// Parses one entry of the initial state, such as `0:x5=1`, `0:x6=x`, `x=1`
+// or `int x=1`.
+fn parse_init(test: &mut LitmusTest, line: usize, entry: &str) -> Result<(), ParseError> {
+    let entry = entry.trim();
+    if entry.is_empty() {
+        return Ok(());
+    }
+    let (lhs, rhs) = match entry.split_once('=') {
+        Some((lhs, rhs)) => (lhs.trim(), rhs.trim()),
+        // A declaration without a value, such as `uint64_t x;`
+        None => (entry, "0"),
+    };
+    // Drop any type name.
+    let lhs = lhs.rsplit(' ').next().unwrap_or(lhs);
+    let value = match parse_value(rhs) {
+        Some(v) => v,
+        None => LitmusTest::location_address(test.location(rhs)),
+    };
+    if let Some((thread, reg)) = lhs.split_once(':') {
+        let thread = thread
+            .parse()
+            .map_err(|_| ParseError::new(line, "invalid thread number"))?;
+        let reg = parse_int_register(reg)
+            .ok_or_else(|| ParseError::new(line, format!("unknown register {}", reg)))?;
+        test.registers.push((thread, reg.num(), value));
+    } else {
+        let location = test.location(lhs.trim_start_matches('[').trim_end_matches(']'));
+        test.memory.push((location, value));
+    }
+    Ok(())
+}
+
+impl LitmusTest {
+    // Returns the index of the named location, adding it if it is new.
+    fn location(&mut self, name: &str) -> usize {
+        match self.locations.iter().position(|l| l == name) {
+            Some(i) => i,
+            None => {
+                self.locations.push(String::from(name));
+                self.locations.len() - 1
+            }
+        }
+    }
+}
+
+/// Parses a decimal or `0x`-prefixed hexadecimal number, which may be
+/// negative.
+pub(super) fn parse_value(s: &str) -> Option<u32> {
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s),
+    };
+    let v = match digits.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+        None => digits.parse::<u32>().ok()?,
+    };
+    Some(if negative { v.wrapping_neg() } else { v })
+}
+
+/// Parses an integer register given by number, such as `x5`, or by its ABI
+/// name, such as `t0`.
+pub(super) fn parse_int_register(s: &str) -> Option<IntRegister> {
+    if s == "fp" {
+        return Some(IntRegister::numbered(8));
+    }
+    (0..32).map(IntRegister::numbered).find(|r| {
+        r.abi_name() == s || s.strip_prefix('x').and_then(|n| n.parse().ok()) == Some(r.num())
+    })
+}
+
+fn tokenize(s: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut rest = s.trim();
+    while !rest.is_empty() {
+        let len = if rest.starts_with("/\\") || rest.starts_with("\\/") {
+            2
+        } else if rest.starts_with(|c| "()~=".contains(c)) {
+            1
+        } else {
+            rest.find(|c: char| c.is_whitespace() || "()~=/\\".contains(c))
+                .unwrap_or(rest.len())
+        };
+        tokens.push(rest[..len].to_string());
+        rest = rest[len..].trim_start();
+    }
+    tokens
+}
+
+// A recursive descent parser for conditions, in which `~` binds tightest,
+// then `/\`, then `\/`.
+struct PropParser<'a> {
+    test: &'a mut LitmusTest,
+    line: usize,
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl PropParser<'_> {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Result<String, ParseError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| ParseError::new(self.line, "unexpected end of condition"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), ParseError> {
+        let token = self.next()?;
+        if token != expected {
+            return Err(ParseError::new(
+                self.line,
+                format!("expected {} but found {}", expected, token),
+            ));
+        }
+        Ok(())
+    }
+
+    fn or(&mut self) -> Result<Prop, ParseError> {
+        let mut prop = self.and()?;
+        while self.peek() == Some("\\/") {
+            self.pos += 1;
+            prop = Prop::Or(Box::new(prop), Box::new(self.and()?));
+        }
+        Ok(prop)
+    }
+
+    fn and(&mut self) -> Result<Prop, ParseError> {
+        let mut prop = self.not()?;
+        while self.peek() == Some("/\\") {
+            self.pos += 1;
+            prop = Prop::And(Box::new(prop), Box::new(self.not()?));
+        }
+        Ok(prop)
+    }
+
+    fn not(&mut self) -> Result<Prop, ParseError> {
+        match self.peek() {
+            Some("~") => {
+                self.pos += 1;
+                Ok(Prop::Not(Box::new(self.not()?)))
+            }
+            Some("(") => {
+                self.pos += 1;
+                let prop = self.or()?;
+                self.expect(")")?;
+                Ok(prop)
+            }
+            Some("true") => {
+                self.pos += 1;
+                Ok(Prop::True)
+            }
+            _ => self.equal(),
+        }
+    }
+
+    fn equal(&mut self) -> Result<Prop, ParseError> {
+        let lhs = self.next()?;
+        self.expect("=")?;
+        let rhs = self.next()?;
+        let observable = match lhs.split_once(':') {
+            Some((thread, reg)) => {
+                let thread = thread
+                    .parse()
+                    .map_err(|_| ParseError::new(self.line, "invalid thread number"))?;
+                let reg = parse_int_register(reg).ok_or_else(|| {
+                    ParseError::new(self.line, format!("unknown register {}", reg))
+                })?;
+                Observable::Register(thread, reg.num())
+            }
+            None => Observable::Location(
+                self.test
+                    .location(lhs.trim_start_matches('[').trim_end_matches(']')),
+            ),
+        };
+        let value = match parse_value(&rhs) {
+            Some(v) => v,
+            None => LitmusTest::location_address(self.test.location(&rhs)),
+        };
+        Ok(Prop::Equal(observable, value))
+    }
+}
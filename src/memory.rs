@@ -3,6 +3,20 @@ use crate::data::HalfwordUnsigned as Halfword;
 use crate::data::LongwordUnsigned as Longword;
 use crate::data::QuadwordUnsigned as Quadword;
 use crate::data::WordUnsigned as Word;
+use crate::exception::ExceptionCause;
+use core::convert::TryInto;
+
+mod alignment;
+#[cfg(feature = "std")]
+mod atomic;
+#[cfg(feature = "std")]
+mod sparse;
+
+pub use alignment::{Alignment, MisalignedAccess};
+#[cfg(feature = "std")]
+pub use atomic::AtomicMemory;
+#[cfg(feature = "std")]
+pub use sparse::{MemorySnapshot, SparseMemory, PAGE_SIZE};
 
 /// Represents the external memory bus of the CPU.
 ///
@@ -24,44 +38,250 @@ pub trait Bus<Addr> {
     fn write_longword(&mut self, addr: Addr, data: Longword) -> Result<(), MemoryError>;
     fn read_quadword(&mut self, addr: Addr) -> Result<Quadword, MemoryError>;
     fn write_quadword(&mut self, addr: Addr, data: Quadword) -> Result<(), MemoryError>;
+
+    /// Reads a 16-bit parcel of an instruction for execution, rather than as
+    /// data. A bus can override this to forbid execution from some regions,
+    /// or to keep instruction fetches from reaching devices that react to
+    /// reads.
+    ///
+    /// The default implementation is `read_halfword`, with any error
+    /// reported as a failed fetch.
+    fn fetch_halfword(&mut self, addr: Addr) -> Result<Halfword, MemoryError> {
+        self.read_halfword(addr)
+            .map_err(|e| e.with_access(AccessKind::Fetch))
+    }
+
+    /// Atomically reads the word at the given address, combines it with
+    /// `operand` using the given operation, and writes the result back,
+    /// returning the word that was originally read.
+    ///
+    /// The default implementation is a separate read and write, which is
+    /// atomic only if nothing else can access the bus in between. A bus
+    /// shared between harts running concurrently must override it.
+    fn amo_word(&mut self, addr: Addr, op: AtomicOp, operand: Word) -> Result<Word, MemoryError>
+    where
+        Addr: Copy,
+    {
+        let old = self
+            .read_word(addr)
+            .map_err(|e| e.with_access(AccessKind::Amo))?;
+        self.write_word(addr, op.apply(old, operand))
+            .map_err(|e| e.with_access(AccessKind::Amo))?;
+        Ok(old)
+    }
+
+    /// Atomically writes `new` to the word at the given address if it
+    /// currently contains `current`. Returns `Ok` with the previous value if
+    /// the write happened, or `Err` with the actual value otherwise.
+    ///
+    /// As with `amo_word`, the default implementation is only atomic if
+    /// nothing else can access the bus in between its read and its write.
+    fn compare_exchange_word(
+        &mut self,
+        addr: Addr,
+        current: Word,
+        new: Word,
+    ) -> Result<Result<Word, Word>, MemoryError>
+    where
+        Addr: Copy,
+    {
+        let old = self
+            .read_word(addr)
+            .map_err(|e| e.with_access(AccessKind::Amo))?;
+        if old != current {
+            return Ok(Err(old));
+        }
+        self.write_word(addr, new)
+            .map_err(|e| e.with_access(AccessKind::Amo))?;
+        Ok(Ok(old))
+    }
+
+    /// Orders the accesses of the kinds in `pred` that were made through
+    /// this bus before the fence with the accesses of the kinds in `succ`
+    /// made after it, as observed by other harts and devices.
+    ///
+    /// The default implementation does nothing, which is appropriate for a
+    /// bus that completes each access before returning from it.
+    fn fence(&mut self, _pred: FenceSet, _succ: FenceSet) {
+        // default implementation does nothing
+    }
 }
 
-/// Represents the ways in which a memory access can fail. These map indirectly
-/// onto the processor's exception codes, but the exact mapping depends on
-/// what exactly the CPU was aiming to achieve with the particular memory access.
-#[derive(Debug)]
-pub enum MemoryError {
+/// A set of kinds of access ordered by a fence, as given by the predecessor
+/// or successor field of a `fence` instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FenceSet {
+    /// Device input, or reads from I/O regions.
+    pub input: bool,
+    /// Device output, or writes to I/O regions.
+    pub output: bool,
+    /// Reads from memory.
+    pub read: bool,
+    /// Writes to memory.
+    pub write: bool,
+}
+
+impl FenceSet {
+    /// All kinds of access, as in `fence iorw, iorw`.
+    pub const ALL: Self = Self {
+        input: true,
+        output: true,
+        read: true,
+        write: true,
+    };
+
+    /// Reads and writes to memory, as implied by the `aq` and `rl` bits of
+    /// the atomic instructions.
+    pub const MEMORY: Self = Self {
+        input: false,
+        output: false,
+        read: true,
+        write: true,
+    };
+
+    /// Decodes the four-bit field used in the `fence` instruction encoding,
+    /// whose bits from most to least significant are I, O, R and W.
+    pub fn from_bits(bits: u32) -> Self {
+        Self {
+            input: bits & 0b1000 != 0,
+            output: bits & 0b0100 != 0,
+            read: bits & 0b0010 != 0,
+            write: bits & 0b0001 != 0,
+        }
+    }
+}
+
+/// The read-modify-write operations that can be performed atomically on a
+/// word of memory, as used by the atomic memory operation instructions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AtomicOp {
+    Swap,
+    Add,
+    Xor,
+    And,
+    Or,
+    Min,
+    Max,
+    MinUnsigned,
+    MaxUnsigned,
+}
+
+impl AtomicOp {
+    /// Returns the value that the operation writes to memory, given the value
+    /// originally in memory and the operand from the instruction.
+    pub fn apply(self, old: Word, operand: Word) -> Word {
+        match self {
+            AtomicOp::Swap => operand,
+            AtomicOp::Add => old.wrapping_add(operand),
+            AtomicOp::Xor => old ^ operand,
+            AtomicOp::And => old & operand,
+            AtomicOp::Or => old | operand,
+            AtomicOp::Min => (old as i32).min(operand as i32) as Word,
+            AtomicOp::Max => (old as i32).max(operand as i32) as Word,
+            AtomicOp::MinUnsigned => old.min(operand),
+            AtomicOp::MaxUnsigned => old.max(operand),
+        }
+    }
+}
+
+/// Describes a memory access that failed. The kind of fault maps indirectly
+/// onto the processor's exception codes, with the exact mapping depending on
+/// the kind of access, as given by `cause`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryError {
+    pub fault: Fault,
+    pub access: AccessKind,
+
+    /// The address that the failed access was made to. This is the address
+    /// given to the outermost bus, even if wrappers such as
+    /// `AddressTransformer` passed a different address on to the memory that
+    /// raised the error. It is wide enough for any of the address types used
+    /// with `Bus`, including those of RV128.
+    pub addr: u128,
+
+    /// The size of the failed access, in bytes.
+    pub size: usize,
+}
+
+/// The ways in which a memory access can fail.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fault {
     Misaligned,
     AccessFault,
     PageFault,
 }
 
+/// The kinds of memory access, which determine the exception that a failed
+/// access raises.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccessKind {
+    /// Reading an instruction to execute it.
+    Fetch,
+    Load,
+    Store,
+    /// A read-modify-write by an atomic instruction, which raises the same
+    /// exceptions as a store.
+    Amo,
+}
+
 impl MemoryError {
-    pub fn as_code_load_cause(self) -> crate::exception::ExceptionCause {
-        match self {
-            MemoryError::Misaligned => {
-                crate::exception::ExceptionCause::InstructionAddressMisaligned
-            }
-            MemoryError::AccessFault => crate::exception::ExceptionCause::InstructionAccessFault,
-            MemoryError::PageFault => crate::exception::ExceptionCause::InstructionPageFault,
+    pub fn new(fault: Fault, access: AccessKind, addr: u128, size: usize) -> Self {
+        Self {
+            fault,
+            access,
+            addr,
+            size,
         }
     }
-    pub fn as_data_load_cause(self) -> crate::exception::ExceptionCause {
-        match self {
-            MemoryError::Misaligned => crate::exception::ExceptionCause::LoadAddressMisaligned,
-            MemoryError::AccessFault => crate::exception::ExceptionCause::LoadAccessFault,
-            MemoryError::PageFault => crate::exception::ExceptionCause::LoadPageFault,
+
+    /// Returns the exception that an instruction raises when it makes this
+    /// failed access.
+    pub fn cause(&self) -> ExceptionCause {
+        match (self.access, self.fault) {
+            (AccessKind::Fetch, Fault::Misaligned) => ExceptionCause::InstructionAddressMisaligned,
+            (AccessKind::Fetch, Fault::AccessFault) => ExceptionCause::InstructionAccessFault,
+            (AccessKind::Fetch, Fault::PageFault) => ExceptionCause::InstructionPageFault,
+            (AccessKind::Load, Fault::Misaligned) => ExceptionCause::LoadAddressMisaligned,
+            (AccessKind::Load, Fault::AccessFault) => ExceptionCause::LoadAccessFault,
+            (AccessKind::Load, Fault::PageFault) => ExceptionCause::LoadPageFault,
+            (AccessKind::Store | AccessKind::Amo, Fault::Misaligned) => {
+                ExceptionCause::StoreAddressMisaligned
+            }
+            (AccessKind::Store | AccessKind::Amo, Fault::AccessFault) => {
+                ExceptionCause::StoreAccessFault
+            }
+            (AccessKind::Store | AccessKind::Amo, Fault::PageFault) => {
+                ExceptionCause::StorePageFault
+            }
         }
     }
-    pub fn as_data_store_cause(self) -> crate::exception::ExceptionCause {
-        match self {
-            MemoryError::Misaligned => crate::exception::ExceptionCause::StoreAddressMisaligned,
-            MemoryError::AccessFault => crate::exception::ExceptionCause::StoreAccessFault,
-            MemoryError::PageFault => crate::exception::ExceptionCause::StorePageFault,
-        }
+
+    /// Returns the same error for a different kind of access, such as when
+    /// an atomic memory operation is made up of a load and a store.
+    pub fn with_access(self, access: AccessKind) -> Self {
+        Self { access, ..self }
     }
 }
 
+// Converts an address of any of the types used with `Bus` for reporting in
+// a `MemoryError`.
+fn error_addr<Addr: TryInto<u128>>(addr: Addr) -> u128 {
+    addr.try_into().unwrap_or(u128::MAX)
+}
+
+/// Implemented by memories whose entire contents can be captured and later
+/// restored, and by bus wrappers around such memories, so that generic code
+/// such as a replay engine can take checkpoints of a whole system.
+#[cfg(feature = "std")]
+pub trait SnapshotMemory {
+    /// Captures the current contents of the memory.
+    fn snapshot_memory(&self) -> MemorySnapshot;
+
+    /// Replaces the contents of the memory with a previously-captured
+    /// snapshot.
+    fn restore_memory(&mut self, snapshot: &MemorySnapshot) -> Result<(), MemoryError>;
+}
+
 pub struct Memory<'b> {
     buf: &'b mut [u8],
     writable: bool,
@@ -81,6 +301,48 @@ impl<'b> Memory<'b> {
             writable: false,
         }
     }
+
+    /// Returns the memory's backing buffer, so that its contents can be
+    /// saved.
+    pub fn contents(&self) -> &[u8] {
+        self.buf
+    }
+
+    /// Returns the memory's backing buffer for modification, so that its
+    /// contents can be replaced. This is permitted even for ROM.
+    pub fn contents_mut(&mut self) -> &mut [u8] {
+        self.buf
+    }
+
+    /// Captures the current contents of the memory.
+    #[cfg(feature = "std")]
+    pub fn snapshot(&self) -> MemorySnapshot {
+        MemorySnapshot::from_bytes(self.buf)
+    }
+
+    /// Replaces the contents of the memory with a previously-captured
+    /// snapshot. Any location not included in the snapshot becomes zero.
+    ///
+    /// Returns an access fault without changing the memory if
+    /// the snapshot includes data beyond the end of the buffer.
+    #[cfg(feature = "std")]
+    pub fn restore(&mut self, snapshot: &MemorySnapshot) -> Result<(), MemoryError> {
+        for (base, data) in &snapshot.pages {
+            if base + data.len() > self.buf.len() {
+                return Err(MemoryError::new(
+                    Fault::AccessFault,
+                    AccessKind::Store,
+                    *base as u128,
+                    data.len(),
+                ));
+            }
+        }
+        self.buf.fill(0);
+        for (base, data) in &snapshot.pages {
+            self.buf[*base..*base + data.len()].copy_from_slice(data);
+        }
+        Ok(())
+    }
 }
 
 impl<'b> Bus<usize> for Memory<'b> {
@@ -90,7 +352,12 @@ impl<'b> Bus<usize> for Memory<'b> {
 
     fn write_byte(&mut self, addr: usize, data: Byte) -> Result<(), MemoryError> {
         if !self.writable {
-            return Err(MemoryError::AccessFault);
+            return Err(MemoryError::new(
+                Fault::AccessFault,
+                AccessKind::Store,
+                addr as u128,
+                1,
+            ));
         }
         let l: usize;
         {
@@ -110,7 +377,12 @@ impl<'b> Bus<usize> for Memory<'b> {
 
     fn write_word(&mut self, addr: usize, data: Word) -> Result<(), MemoryError> {
         if !self.writable {
-            return Err(MemoryError::AccessFault);
+            return Err(MemoryError::new(
+                Fault::AccessFault,
+                AccessKind::Store,
+                addr as u128,
+                4,
+            ));
         }
         let l: usize;
         {
@@ -132,7 +404,12 @@ impl<'b> Bus<usize> for Memory<'b> {
 
     fn write_halfword(&mut self, addr: usize, data: Halfword) -> Result<(), MemoryError> {
         if !self.writable {
-            return Err(MemoryError::AccessFault);
+            return Err(MemoryError::new(
+                Fault::AccessFault,
+                AccessKind::Store,
+                addr as u128,
+                2,
+            ));
         }
         let l: usize;
         {
@@ -154,7 +431,12 @@ impl<'b> Bus<usize> for Memory<'b> {
 
     fn write_longword(&mut self, addr: usize, data: Longword) -> Result<(), MemoryError> {
         if !self.writable {
-            return Err(MemoryError::AccessFault);
+            return Err(MemoryError::new(
+                Fault::AccessFault,
+                AccessKind::Store,
+                addr as u128,
+                8,
+            ));
         }
         let l: usize;
         {
@@ -176,7 +458,12 @@ impl<'b> Bus<usize> for Memory<'b> {
 
     fn write_quadword(&mut self, addr: usize, data: Quadword) -> Result<(), MemoryError> {
         if !self.writable {
-            return Err(MemoryError::AccessFault);
+            return Err(MemoryError::new(
+                Fault::AccessFault,
+                AccessKind::Store,
+                addr as u128,
+                16,
+            ));
         }
         let l: usize;
         {
@@ -189,6 +476,17 @@ impl<'b> Bus<usize> for Memory<'b> {
     }
 }
 
+#[cfg(feature = "std")]
+impl<'b> SnapshotMemory for Memory<'b> {
+    fn snapshot_memory(&self) -> MemorySnapshot {
+        self.snapshot()
+    }
+
+    fn restore_memory(&mut self, snapshot: &MemorySnapshot) -> Result<(), MemoryError> {
+        self.restore(snapshot)
+    }
+}
+
 /// Wraps another `Bus` and uses a provided function to adjust incoming
 /// addresses before calling the wrapped bus.
 pub struct AddressTransformer<Addr, Wrapped, Callback>
@@ -224,81 +522,150 @@ where
         let callback = &self.callback;
         callback(addr)
     }
+
+    // Translates an address for an access of the given kind and size. The
+    // callback doesn't know what the access is, so an error it returns is
+    // adjusted to describe the access that was actually attempted.
+    fn translate_access(
+        &self,
+        addr: Addr,
+        access: AccessKind,
+        size: usize,
+    ) -> Result<Addr, MemoryError> {
+        self.translate_address(addr)
+            .map_err(|e| MemoryError { access, size, ..e })
+    }
 }
 
 impl<Addr, Wrapped, Callback> Bus<Addr> for AddressTransformer<Addr, Wrapped, Callback>
 where
+    Addr: Copy + TryInto<u128>,
     Wrapped: Bus<Addr>,
     Callback: Fn(Addr) -> Result<Addr, MemoryError>,
 {
     fn read_byte(&mut self, addr: Addr) -> Result<Byte, MemoryError> {
-        match self.translate_address(addr) {
-            Ok(addr) => self.wrapped.read_byte(addr),
-            Err(e) => Err(e),
-        }
+        let inner = self.translate_access(addr, AccessKind::Load, 1)?;
+        self.wrapped
+            .read_byte(inner)
+            .map_err(|e| untranslate(e, addr, inner))
     }
 
     fn read_halfword(&mut self, addr: Addr) -> Result<Halfword, MemoryError> {
-        match self.translate_address(addr) {
-            Ok(addr) => self.wrapped.read_halfword(addr),
-            Err(e) => Err(e),
-        }
+        let inner = self.translate_access(addr, AccessKind::Load, 2)?;
+        self.wrapped
+            .read_halfword(inner)
+            .map_err(|e| untranslate(e, addr, inner))
     }
 
     fn read_word(&mut self, addr: Addr) -> Result<Word, MemoryError> {
-        match self.translate_address(addr) {
-            Ok(addr) => self.wrapped.read_word(addr),
-            Err(e) => Err(e),
-        }
+        let inner = self.translate_access(addr, AccessKind::Load, 4)?;
+        self.wrapped
+            .read_word(inner)
+            .map_err(|e| untranslate(e, addr, inner))
     }
 
     fn read_longword(&mut self, addr: Addr) -> Result<Longword, MemoryError> {
-        match self.translate_address(addr) {
-            Ok(addr) => self.wrapped.read_longword(addr),
-            Err(e) => Err(e),
-        }
+        let inner = self.translate_access(addr, AccessKind::Load, 8)?;
+        self.wrapped
+            .read_longword(inner)
+            .map_err(|e| untranslate(e, addr, inner))
     }
 
     fn read_quadword(&mut self, addr: Addr) -> Result<Quadword, MemoryError> {
-        match self.translate_address(addr) {
-            Ok(addr) => self.wrapped.read_quadword(addr),
-            Err(e) => Err(e),
-        }
+        let inner = self.translate_access(addr, AccessKind::Load, 16)?;
+        self.wrapped
+            .read_quadword(inner)
+            .map_err(|e| untranslate(e, addr, inner))
     }
 
     fn write_byte(&mut self, addr: Addr, data: Byte) -> Result<(), MemoryError> {
-        match self.translate_address(addr) {
-            Ok(addr) => self.wrapped.write_byte(addr, data),
-            Err(e) => Err(e),
-        }
+        let inner = self.translate_access(addr, AccessKind::Store, 1)?;
+        self.wrapped
+            .write_byte(inner, data)
+            .map_err(|e| untranslate(e, addr, inner))
     }
 
     fn write_halfword(&mut self, addr: Addr, data: Halfword) -> Result<(), MemoryError> {
-        match self.translate_address(addr) {
-            Ok(addr) => self.wrapped.write_halfword(addr, data),
-            Err(e) => Err(e),
-        }
+        let inner = self.translate_access(addr, AccessKind::Store, 2)?;
+        self.wrapped
+            .write_halfword(inner, data)
+            .map_err(|e| untranslate(e, addr, inner))
     }
 
     fn write_word(&mut self, addr: Addr, data: Word) -> Result<(), MemoryError> {
-        match self.translate_address(addr) {
-            Ok(addr) => self.wrapped.write_word(addr, data),
-            Err(e) => Err(e),
-        }
+        let inner = self.translate_access(addr, AccessKind::Store, 4)?;
+        self.wrapped
+            .write_word(inner, data)
+            .map_err(|e| untranslate(e, addr, inner))
     }
 
     fn write_longword(&mut self, addr: Addr, data: Longword) -> Result<(), MemoryError> {
-        match self.translate_address(addr) {
-            Ok(addr) => self.wrapped.write_longword(addr, data),
-            Err(e) => Err(e),
-        }
+        let inner = self.translate_access(addr, AccessKind::Store, 8)?;
+        self.wrapped
+            .write_longword(inner, data)
+            .map_err(|e| untranslate(e, addr, inner))
     }
 
     fn write_quadword(&mut self, addr: Addr, data: Quadword) -> Result<(), MemoryError> {
-        match self.translate_address(addr) {
-            Ok(addr) => self.wrapped.write_quadword(addr, data),
-            Err(e) => Err(e),
-        }
+        let inner = self.translate_access(addr, AccessKind::Store, 16)?;
+        self.wrapped
+            .write_quadword(inner, data)
+            .map_err(|e| untranslate(e, addr, inner))
+    }
+    fn fetch_halfword(&mut self, addr: Addr) -> Result<Halfword, MemoryError> {
+        let inner = self.translate_access(addr, AccessKind::Fetch, 2)?;
+        self.wrapped
+            .fetch_halfword(inner)
+            .map_err(|e| untranslate(e, addr, inner))
+    }
+
+    fn amo_word(&mut self, addr: Addr, op: AtomicOp, operand: Word) -> Result<Word, MemoryError> {
+        let inner = self.translate_access(addr, AccessKind::Amo, 4)?;
+        self.wrapped
+            .amo_word(inner, op, operand)
+            .map_err(|e| untranslate(e, addr, inner))
+    }
+
+    fn compare_exchange_word(
+        &mut self,
+        addr: Addr,
+        current: Word,
+        new: Word,
+    ) -> Result<Result<Word, Word>, MemoryError> {
+        let inner = self.translate_access(addr, AccessKind::Amo, 4)?;
+        self.wrapped
+            .compare_exchange_word(inner, current, new)
+            .map_err(|e| untranslate(e, addr, inner))
+    }
+
+    fn fence(&mut self, pred: FenceSet, succ: FenceSet) {
+        self.wrapped.fence(pred, succ)
+    }
+}
+
+// Adjusts an error from a wrapped bus to refer to the address that was
+// given to the wrapper rather than the one it passed on, keeping the offset
+// of the failing byte within the access.
+fn untranslate<Addr: TryInto<u128>>(e: MemoryError, outer: Addr, inner: Addr) -> MemoryError {
+    let offset = e.addr.wrapping_sub(error_addr(inner));
+    MemoryError {
+        addr: error_addr(outer).wrapping_add(offset),
+        ..e
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Addr, Wrapped, Callback> SnapshotMemory for AddressTransformer<Addr, Wrapped, Callback>
+where
+    Wrapped: Bus<Addr> + SnapshotMemory,
+    Callback: Fn(Addr) -> Result<Addr, MemoryError>,
+{
+    fn snapshot_memory(&self) -> MemorySnapshot {
+        self.wrapped.snapshot_memory()
+    }
+
+    fn restore_memory(&mut self, snapshot: &MemorySnapshot) -> Result<(), MemoryError> {
+        self.wrapped.restore_memory(snapshot)
     }
 }
 
@@ -348,83 +715,146 @@ where
 
 impl<Outside, Inside, Wrapped> Bus<Outside> for AddressConverter<Outside, Inside, Wrapped>
 where
-    Outside: core::convert::TryInto<Inside>,
+    Outside: Copy + core::convert::TryInto<Inside> + TryInto<u128>,
+    Inside: Copy,
     Wrapped: Bus<Inside>,
 {
     fn read_byte(&mut self, addr: Outside) -> Result<Byte, MemoryError> {
         match self.convert_address(addr) {
             Some(addr) => self.wrapped.read_byte(addr),
-            None => Err(MemoryError::PageFault),
+            None => Err(unconvertible(addr, AccessKind::Load, 1)),
         }
     }
 
     fn read_halfword(&mut self, addr: Outside) -> Result<Halfword, MemoryError> {
         match self.convert_address(addr) {
             Some(addr) => self.wrapped.read_halfword(addr),
-            None => Err(MemoryError::PageFault),
+            None => Err(unconvertible(addr, AccessKind::Load, 2)),
         }
     }
 
     fn read_word(&mut self, addr: Outside) -> Result<Word, MemoryError> {
         match self.convert_address(addr) {
             Some(addr) => self.wrapped.read_word(addr),
-            None => Err(MemoryError::PageFault),
+            None => Err(unconvertible(addr, AccessKind::Load, 4)),
         }
     }
 
     fn read_longword(&mut self, addr: Outside) -> Result<Longword, MemoryError> {
         match self.convert_address(addr) {
             Some(addr) => self.wrapped.read_longword(addr),
-            None => Err(MemoryError::PageFault),
+            None => Err(unconvertible(addr, AccessKind::Load, 8)),
         }
     }
 
     fn read_quadword(&mut self, addr: Outside) -> Result<Quadword, MemoryError> {
         match self.convert_address(addr) {
             Some(addr) => self.wrapped.read_quadword(addr),
-            None => Err(MemoryError::PageFault),
+            None => Err(unconvertible(addr, AccessKind::Load, 16)),
         }
     }
 
     fn write_byte(&mut self, addr: Outside, data: Byte) -> Result<(), MemoryError> {
         match self.convert_address(addr) {
             Some(addr) => self.wrapped.write_byte(addr, data),
-            None => Err(MemoryError::PageFault),
+            None => Err(unconvertible(addr, AccessKind::Store, 1)),
         }
     }
 
     fn write_halfword(&mut self, addr: Outside, data: Halfword) -> Result<(), MemoryError> {
         match self.convert_address(addr) {
             Some(addr) => self.wrapped.write_halfword(addr, data),
-            None => Err(MemoryError::PageFault),
+            None => Err(unconvertible(addr, AccessKind::Store, 2)),
         }
     }
 
     fn write_word(&mut self, addr: Outside, data: Word) -> Result<(), MemoryError> {
         match self.convert_address(addr) {
             Some(addr) => self.wrapped.write_word(addr, data),
-            None => Err(MemoryError::PageFault),
+            None => Err(unconvertible(addr, AccessKind::Store, 4)),
         }
     }
 
     fn write_longword(&mut self, addr: Outside, data: Longword) -> Result<(), MemoryError> {
         match self.convert_address(addr) {
             Some(addr) => self.wrapped.write_longword(addr, data),
-            None => Err(MemoryError::PageFault),
+            None => Err(unconvertible(addr, AccessKind::Store, 8)),
         }
     }
 
     fn write_quadword(&mut self, addr: Outside, data: Quadword) -> Result<(), MemoryError> {
         match self.convert_address(addr) {
             Some(addr) => self.wrapped.write_quadword(addr, data),
-            None => Err(MemoryError::PageFault),
+            None => Err(unconvertible(addr, AccessKind::Store, 16)),
+        }
+    }
+    fn fetch_halfword(&mut self, addr: Outside) -> Result<Halfword, MemoryError> {
+        match self.convert_address(addr) {
+            Some(addr) => self.wrapped.fetch_halfword(addr),
+            None => Err(unconvertible(addr, AccessKind::Fetch, 2)),
+        }
+    }
+
+    fn amo_word(
+        &mut self,
+        addr: Outside,
+        op: AtomicOp,
+        operand: Word,
+    ) -> Result<Word, MemoryError> {
+        match self.convert_address(addr) {
+            Some(addr) => self.wrapped.amo_word(addr, op, operand),
+            None => Err(unconvertible(addr, AccessKind::Amo, 4)),
+        }
+    }
+
+    fn compare_exchange_word(
+        &mut self,
+        addr: Outside,
+        current: Word,
+        new: Word,
+    ) -> Result<Result<Word, Word>, MemoryError> {
+        match self.convert_address(addr) {
+            Some(addr) => self.wrapped.compare_exchange_word(addr, current, new),
+            None => Err(unconvertible(addr, AccessKind::Amo, 4)),
         }
     }
+
+    fn fence(&mut self, pred: FenceSet, succ: FenceSet) {
+        self.wrapped.fence(pred, succ)
+    }
+}
+
+// Returns the error for an address that can't be represented by the wrapped
+// bus's address type.
+fn unconvertible<Addr: TryInto<u128>>(addr: Addr, access: AccessKind, size: usize) -> MemoryError {
+    MemoryError::new(Fault::PageFault, access, error_addr(addr), size)
+}
+
+#[cfg(feature = "std")]
+impl<Outside, Inside, Wrapped> SnapshotMemory for AddressConverter<Outside, Inside, Wrapped>
+where
+    Outside: core::convert::TryInto<Inside>,
+    Wrapped: Bus<Inside> + SnapshotMemory,
+{
+    fn snapshot_memory(&self) -> MemorySnapshot {
+        self.wrapped.snapshot_memory()
+    }
+
+    fn restore_memory(&mut self, snapshot: &MemorySnapshot) -> Result<(), MemoryError> {
+        self.wrapped.restore_memory(snapshot)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{AddressConverter, Bus, Memory};
+    use super::{
+        AccessKind, AddressConverter, AddressTransformer, AtomicOp, Bus, Fault, Memory, MemoryError,
+    };
+    use crate::data::Byte;
+    use crate::data::HalfwordUnsigned as Halfword;
+    use crate::data::LongwordUnsigned as Longword;
+    use crate::data::QuadwordUnsigned as Quadword;
+    use crate::data::WordUnsigned as Word;
 
     #[test]
     fn memory_writable() {
@@ -538,4 +968,149 @@ mod tests {
         bus.write_byte(1, 0xfe).unwrap();
         assert_eq!(bus.read_byte(1).unwrap(), 0xfe as u8);
     }
+
+    #[test]
+    fn error_details() {
+        let mut buf: [u8; 32] = [0; 32];
+        let rom = Memory::new_rom(&mut buf);
+        let bus = AddressConverter::<u32, usize, Memory>::new(rom);
+        let mut bus = AddressTransformer::new(bus, |addr: u32| Ok(addr.wrapping_sub(0x1000)));
+
+        // The error reports the address that was given to the outer bus,
+        // along with the size and kind of the access.
+        assert_eq!(
+            bus.write_halfword(0x1006, 0),
+            Err(MemoryError::new(
+                Fault::AccessFault,
+                AccessKind::Store,
+                0x1006,
+                2
+            ))
+        );
+        assert_eq!(
+            bus.compare_exchange_word(0x1008, 0, 1),
+            Err(MemoryError::new(
+                Fault::AccessFault,
+                AccessKind::Amo,
+                0x1008,
+                4
+            ))
+        );
+
+        // An address that the callback refuses to translate is reported in
+        // the same way, even though the callback doesn't know what kind of
+        // access is being made.
+        let mut buf: [u8; 32] = [0; 32];
+        let ram = Memory::new_ram(&mut buf);
+        let bus = AddressConverter::<u32, usize, Memory>::new(ram);
+        let mut bus = AddressTransformer::new(bus, |addr: u32| match addr {
+            0x1000..=0x101f => Ok(addr - 0x1000),
+            _ => Err(MemoryError::new(
+                Fault::PageFault,
+                AccessKind::Load,
+                addr as u128,
+                1,
+            )),
+        });
+        assert_eq!(
+            bus.write_word(0x2004, 0),
+            Err(MemoryError::new(
+                Fault::PageFault,
+                AccessKind::Store,
+                0x2004,
+                4
+            ))
+        );
+        assert_eq!(
+            bus.read_longword(0x2008),
+            Err(MemoryError::new(
+                Fault::PageFault,
+                AccessKind::Load,
+                0x2008,
+                8
+            ))
+        );
+        assert_eq!(
+            bus.write_word(0x2004, 0).unwrap_err().cause(),
+            crate::exception::ExceptionCause::StorePageFault
+        );
+
+        assert_eq!(
+            MemoryError::new(Fault::AccessFault, AccessKind::Fetch, 0, 4).cause(),
+            crate::exception::ExceptionCause::InstructionAccessFault
+        );
+    }
+
+    // A bus holding a single word that can only be accessed by the atomic
+    // operations, to show that wrappers pass those on intact rather than
+    // falling back to a separate read and write.
+    struct AtomicsOnly(Word);
+
+    impl Bus<usize> for AtomicsOnly {
+        fn read_byte(&mut self, _addr: usize) -> Result<Byte, MemoryError> {
+            unimplemented!()
+        }
+        fn read_halfword(&mut self, _addr: usize) -> Result<Halfword, MemoryError> {
+            unimplemented!()
+        }
+        fn read_word(&mut self, _addr: usize) -> Result<Word, MemoryError> {
+            unimplemented!()
+        }
+        fn read_longword(&mut self, _addr: usize) -> Result<Longword, MemoryError> {
+            unimplemented!()
+        }
+        fn read_quadword(&mut self, _addr: usize) -> Result<Quadword, MemoryError> {
+            unimplemented!()
+        }
+        fn write_byte(&mut self, _addr: usize, _data: Byte) -> Result<(), MemoryError> {
+            unimplemented!()
+        }
+        fn write_halfword(&mut self, _addr: usize, _data: Halfword) -> Result<(), MemoryError> {
+            unimplemented!()
+        }
+        fn write_word(&mut self, _addr: usize, _data: Word) -> Result<(), MemoryError> {
+            unimplemented!()
+        }
+        fn write_longword(&mut self, _addr: usize, _data: Longword) -> Result<(), MemoryError> {
+            unimplemented!()
+        }
+        fn write_quadword(&mut self, _addr: usize, _data: Quadword) -> Result<(), MemoryError> {
+            unimplemented!()
+        }
+
+        fn amo_word(
+            &mut self,
+            addr: usize,
+            op: AtomicOp,
+            operand: Word,
+        ) -> Result<Word, MemoryError> {
+            assert_eq!(addr, 0);
+            let old = self.0;
+            self.0 = op.apply(old, operand);
+            Ok(old)
+        }
+
+        fn compare_exchange_word(
+            &mut self,
+            addr: usize,
+            current: Word,
+            new: Word,
+        ) -> Result<Result<Word, Word>, MemoryError> {
+            assert_eq!(addr, 0);
+            if self.0 != current {
+                return Ok(Err(self.0));
+            }
+            self.0 = new;
+            Ok(Ok(current))
+        }
+    }
+
+    #[test]
+    fn atomics_forwarded() {
+        let bus = AddressConverter::<u32, usize, AtomicsOnly>::new(AtomicsOnly(5));
+        let mut bus = AddressTransformer::new(bus, |addr: u32| Ok(addr.wrapping_sub(0x1000)));
+        assert_eq!(bus.amo_word(0x1000, AtomicOp::Add, 2), Ok(5));
+        assert_eq!(bus.compare_exchange_word(0x1000, 7, 1), Ok(Ok(7)));
+        assert_eq!(bus.compare_exchange_word(0x1000, 7, 3), Ok(Err(1)));
+    }
 }
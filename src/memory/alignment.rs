@@ -0,0 +1,258 @@
+use crate::data::Byte;
+use crate::data::HalfwordUnsigned as Halfword;
+use crate::data::LongwordUnsigned as Longword;
+use crate::data::QuadwordUnsigned as Quadword;
+use crate::data::WordUnsigned as Word;
+use crate::memory::{AccessKind, AtomicOp, Bus, Fault, FenceSet, MemoryError};
+
+/// Selects how an `Alignment` bus handles a data access whose address is not
+/// a multiple of its size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MisalignedAccess {
+    /// Fail the access, so that the instruction raises an address-misaligned
+    /// exception, as many hardware implementations do.
+    Trap,
+
+    /// Split the access into naturally-aligned accesses to the wrapped bus,
+    /// as a trap handler emulating misaligned accesses would. The pieces are
+    /// not atomic as a whole, and a store that fails part way through may
+    /// have already written some of its bytes.
+    Split,
+
+    /// Pass the access on to the wrapped bus unchanged.
+    Allow,
+}
+
+/// A bus that applies a `MisalignedAccess` policy to the data accesses made
+/// through it before passing them on to a wrapped bus.
+///
+/// Atomic memory operations must always be aligned, so they fail when
+/// misaligned regardless of the policy. Instruction fetches are not checked,
+/// because their alignment requirement depends on the instruction set rather
+/// than the access size.
+pub struct Alignment<Wrapped: Bus<u32>> {
+    wrapped: Wrapped,
+    policy: MisalignedAccess,
+}
+
+impl<Wrapped: Bus<u32>> Alignment<Wrapped> {
+    /// Consumes a bus and produces a wrapping `Alignment` that handles
+    /// misaligned accesses as the given policy says.
+    pub fn new(wrapped: Wrapped, policy: MisalignedAccess) -> Self {
+        Self { wrapped, policy }
+    }
+
+    /// Changes the policy for subsequent accesses.
+    pub fn set_policy(&mut self, policy: MisalignedAccess) {
+        self.policy = policy;
+    }
+
+    /// Borrows the wrapped bus.
+    pub fn wrapped(&mut self) -> &mut Wrapped {
+        &mut self.wrapped
+    }
+
+    // Returns true if an access of the given size needs the policy applied,
+    // or an error if the policy is to reject it.
+    fn misaligned(&self, addr: u32, size: usize, access: AccessKind) -> Result<bool, MemoryError> {
+        if addr.is_multiple_of(size as u32) || self.policy == MisalignedAccess::Allow {
+            return Ok(false);
+        }
+        match self.policy {
+            MisalignedAccess::Trap => Err(MemoryError::new(
+                Fault::Misaligned,
+                access,
+                addr as u128,
+                size,
+            )),
+            _ => Ok(true),
+        }
+    }
+
+    fn read(&mut self, addr: u32, size: usize) -> Result<u128, MemoryError> {
+        let mut v = 0;
+        let mut done = 0;
+        while done < size {
+            let addr = addr.wrapping_add(done as u32);
+            let n = piece_size(addr, size - done);
+            let piece = match n {
+                1 => self.wrapped.read_byte(addr)? as u128,
+                2 => self.wrapped.read_halfword(addr)? as u128,
+                4 => self.wrapped.read_word(addr)? as u128,
+                _ => self.wrapped.read_longword(addr)? as u128,
+            };
+            v |= piece << (done * 8);
+            done += n;
+        }
+        Ok(v)
+    }
+
+    fn write(&mut self, addr: u32, size: usize, v: u128) -> Result<(), MemoryError> {
+        let mut done = 0;
+        while done < size {
+            let addr = addr.wrapping_add(done as u32);
+            let n = piece_size(addr, size - done);
+            let piece = v >> (done * 8);
+            match n {
+                1 => self.wrapped.write_byte(addr, piece as Byte)?,
+                2 => self.wrapped.write_halfword(addr, piece as Halfword)?,
+                4 => self.wrapped.write_word(addr, piece as Word)?,
+                _ => self.wrapped.write_longword(addr, piece as Longword)?,
+            }
+            done += n;
+        }
+        Ok(())
+    }
+}
+
+// Returns the size of the largest naturally-aligned access that can be made
+// at the given address without exceeding the given number of bytes. A
+// misaligned access never needs pieces larger than half its own size.
+fn piece_size(addr: u32, remaining: usize) -> usize {
+    let mut n = 8;
+    while n > 1 && (n > remaining || !addr.is_multiple_of(n as u32)) {
+        n /= 2;
+    }
+    n
+}
+
+impl<Wrapped: Bus<u32>> Bus<u32> for Alignment<Wrapped> {
+    fn read_byte(&mut self, addr: u32) -> Result<Byte, MemoryError> {
+        self.wrapped.read_byte(addr)
+    }
+
+    fn read_halfword(&mut self, addr: u32) -> Result<Halfword, MemoryError> {
+        match self.misaligned(addr, 2, AccessKind::Load)? {
+            true => Ok(self.read(addr, 2)? as Halfword),
+            false => self.wrapped.read_halfword(addr),
+        }
+    }
+
+    fn read_word(&mut self, addr: u32) -> Result<Word, MemoryError> {
+        match self.misaligned(addr, 4, AccessKind::Load)? {
+            true => Ok(self.read(addr, 4)? as Word),
+            false => self.wrapped.read_word(addr),
+        }
+    }
+
+    fn read_longword(&mut self, addr: u32) -> Result<Longword, MemoryError> {
+        match self.misaligned(addr, 8, AccessKind::Load)? {
+            true => Ok(self.read(addr, 8)? as Longword),
+            false => self.wrapped.read_longword(addr),
+        }
+    }
+
+    fn read_quadword(&mut self, addr: u32) -> Result<Quadword, MemoryError> {
+        match self.misaligned(addr, 16, AccessKind::Load)? {
+            true => self.read(addr, 16),
+            false => self.wrapped.read_quadword(addr),
+        }
+    }
+
+    fn write_byte(&mut self, addr: u32, data: Byte) -> Result<(), MemoryError> {
+        self.wrapped.write_byte(addr, data)
+    }
+
+    fn write_halfword(&mut self, addr: u32, data: Halfword) -> Result<(), MemoryError> {
+        match self.misaligned(addr, 2, AccessKind::Store)? {
+            true => self.write(addr, 2, data as u128),
+            false => self.wrapped.write_halfword(addr, data),
+        }
+    }
+
+    fn write_word(&mut self, addr: u32, data: Word) -> Result<(), MemoryError> {
+        match self.misaligned(addr, 4, AccessKind::Store)? {
+            true => self.write(addr, 4, data as u128),
+            false => self.wrapped.write_word(addr, data),
+        }
+    }
+
+    fn write_longword(&mut self, addr: u32, data: Longword) -> Result<(), MemoryError> {
+        match self.misaligned(addr, 8, AccessKind::Store)? {
+            true => self.write(addr, 8, data as u128),
+            false => self.wrapped.write_longword(addr, data),
+        }
+    }
+
+    fn write_quadword(&mut self, addr: u32, data: Quadword) -> Result<(), MemoryError> {
+        match self.misaligned(addr, 16, AccessKind::Store)? {
+            true => self.write(addr, 16, data),
+            false => self.wrapped.write_quadword(addr, data),
+        }
+    }
+
+    fn fetch_halfword(&mut self, addr: u32) -> Result<Halfword, MemoryError> {
+        self.wrapped.fetch_halfword(addr)
+    }
+
+    fn amo_word(&mut self, addr: u32, op: AtomicOp, operand: Word) -> Result<Word, MemoryError> {
+        check_atomic(addr)?;
+        self.wrapped.amo_word(addr, op, operand)
+    }
+
+    fn compare_exchange_word(
+        &mut self,
+        addr: u32,
+        current: Word,
+        new: Word,
+    ) -> Result<Result<Word, Word>, MemoryError> {
+        check_atomic(addr)?;
+        self.wrapped.compare_exchange_word(addr, current, new)
+    }
+
+    fn fence(&mut self, pred: FenceSet, succ: FenceSet) {
+        self.wrapped.fence(pred, succ)
+    }
+}
+
+fn check_atomic(addr: u32) -> Result<(), MemoryError> {
+    match addr.is_multiple_of(4) {
+        true => Ok(()),
+        false => Err(MemoryError::new(
+            Fault::Misaligned,
+            AccessKind::Amo,
+            addr as u128,
+            4,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Alignment, MisalignedAccess};
+    use crate::memory::{AccessKind, AddressConverter, AtomicOp, Bus, Fault, Memory, MemoryError};
+
+    #[test]
+    fn policies() {
+        let mut buf = [0u8; 32];
+        let ram = AddressConverter::<u32, usize, Memory>::new(Memory::new_ram(&mut buf));
+        let mut bus = Alignment::new(ram, MisalignedAccess::Trap);
+
+        bus.write_word(4, 0x44332211).unwrap();
+        assert_eq!(bus.read_halfword(6).unwrap(), 0x4433);
+        assert_eq!(
+            bus.read_word(5),
+            Err(MemoryError::new(Fault::Misaligned, AccessKind::Load, 5, 4))
+        );
+        assert_eq!(
+            bus.write_halfword(3, 0),
+            Err(MemoryError::new(Fault::Misaligned, AccessKind::Store, 3, 2))
+        );
+
+        bus.set_policy(MisalignedAccess::Split);
+        bus.write_longword(9, 0x0807060504030201).unwrap();
+        assert_eq!(bus.read_word(9).unwrap(), 0x04030201);
+        assert_eq!(bus.read_longword(9).unwrap(), 0x0807060504030201);
+        assert_eq!(bus.wrapped().read_byte(16).unwrap(), 0x08);
+
+        // Atomic operations can't be split, so they fail even when other
+        // accesses are allowed to be misaligned.
+        bus.set_policy(MisalignedAccess::Allow);
+        assert_eq!(bus.read_word(10).unwrap(), 0x05040302);
+        assert_eq!(
+            bus.amo_word(10, AtomicOp::Add, 1),
+            Err(MemoryError::new(Fault::Misaligned, AccessKind::Amo, 10, 4))
+        );
+        assert_eq!(bus.amo_word(4, AtomicOp::Add, 1).unwrap(), 0x44332211);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::data::Byte;
+use crate::data::HalfwordUnsigned as Halfword;
+use crate::data::LongwordUnsigned as Longword;
+use crate::data::QuadwordUnsigned as Quadword;
+use crate::data::WordUnsigned as Word;
+use crate::memory::{AccessKind, AtomicOp, Bus, Fault, MemoryError};
+use core::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// A RAM whose contents are stored in host atomic words, so that it can be
+/// accessed by harts running concurrently on several host threads.
+///
+/// Cloning an `AtomicMemory` produces another handle to the same memory,
+/// which can be given to a hart on another thread. No locking is involved:
+/// each naturally-aligned access of up to four bytes is a single host
+/// atomic access, atomic memory operations map onto the corresponding host
+/// read-modify-write operations, and other accesses are split into several
+/// such accesses, as the ISA permits for misaligned and wider accesses.
+///
+/// Loads have acquire ordering and stores have release ordering, which is
+/// stronger than RVWMO requires, so `fence` only needs to order stores
+/// before later loads. Atomic memory operations are sequentially
+/// consistent.
+pub struct AtomicMemory {
+    words: Arc<[AtomicU32]>,
+}
+
+impl AtomicMemory {
+    /// Creates a memory of at least the given number of bytes, rounded up to
+    /// a whole number of words, initially filled with zeros.
+    pub fn new(size: usize) -> Self {
+        Self {
+            words: (0..size.div_ceil(4)).map(|_| AtomicU32::new(0)).collect(),
+        }
+    }
+
+    /// Returns the size of the memory in bytes.
+    pub fn len(&self) -> usize {
+        self.words.len() * 4
+    }
+
+    /// Returns true if the memory has no bytes at all.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    fn check(&self, addr: u32, size: usize, access: AccessKind) -> Result<usize, MemoryError> {
+        let fault = MemoryError::new(Fault::AccessFault, access, addr as u128, size);
+        let addr = addr as usize;
+        match addr.checked_add(size) {
+            Some(end) if end <= self.len() => Ok(addr),
+            _ => Err(fault),
+        }
+    }
+
+    fn aligned_word(&self, addr: u32) -> Result<&AtomicU32, MemoryError> {
+        if !addr.is_multiple_of(4) {
+            return Err(MemoryError::new(
+                Fault::Misaligned,
+                AccessKind::Amo,
+                addr as u128,
+                4,
+            ));
+        }
+        let addr = self.check(addr, 4, AccessKind::Amo)?;
+        Ok(&self.words[addr / 4])
+    }
+
+    // Reads `size` bytes as a little-endian value, using one host access for
+    // each of the words that the bytes fall in.
+    fn read_le(&self, addr: u32, size: usize) -> Result<u128, MemoryError> {
+        let addr = self.check(addr, size, AccessKind::Load)?;
+        let mut v = 0;
+        let mut done = 0;
+        while done < size {
+            let (word, offset) = ((addr + done) / 4, (addr + done) % 4);
+            let n = (4 - offset).min(size - done);
+            let bits = self.words[word].load(Ordering::Acquire) >> (offset * 8);
+            v |= ((bits as u64 & mask(n)) as u128) << (done * 8);
+            done += n;
+        }
+        Ok(v)
+    }
+
+    // Writes `size` bytes of a little-endian value, using one host access
+    // for each of the words that the bytes fall in.
+    fn write_le(&self, addr: u32, size: usize, v: u128) -> Result<(), MemoryError> {
+        let addr = self.check(addr, size, AccessKind::Store)?;
+        let mut done = 0;
+        while done < size {
+            let (word, offset) = ((addr + done) / 4, (addr + done) % 4);
+            let n = (4 - offset).min(size - done);
+            let bits = ((v >> (done * 8)) as u64 & mask(n)) as u32;
+            if n == 4 {
+                self.words[word].store(bits, Ordering::Release);
+            } else {
+                let keep = !((mask(n) as u32) << (offset * 8));
+                let new = bits << (offset * 8);
+                // The closure always returns Some, so this can't fail.
+                let _ = self.words[word].fetch_update(Ordering::AcqRel, Ordering::Acquire, |w| {
+                    Some(w & keep | new)
+                });
+            }
+            done += n;
+        }
+        Ok(())
+    }
+}
+
+fn mask(bytes: usize) -> u64 {
+    (1 << (bytes * 8)) - 1
+}
+
+impl Clone for AtomicMemory {
+    fn clone(&self) -> Self {
+        Self {
+            words: Arc::clone(&self.words),
+        }
+    }
+}
+
+impl Bus<u32> for AtomicMemory {
+    fn read_byte(&mut self, addr: u32) -> Result<Byte, MemoryError> {
+        Ok(self.read_le(addr, 1)? as Byte)
+    }
+
+    fn write_byte(&mut self, addr: u32, data: Byte) -> Result<(), MemoryError> {
+        self.write_le(addr, 1, data as u128)
+    }
+
+    fn read_halfword(&mut self, addr: u32) -> Result<Halfword, MemoryError> {
+        Ok(self.read_le(addr, 2)? as Halfword)
+    }
+
+    fn write_halfword(&mut self, addr: u32, data: Halfword) -> Result<(), MemoryError> {
+        self.write_le(addr, 2, data as u128)
+    }
+
+    fn read_word(&mut self, addr: u32) -> Result<Word, MemoryError> {
+        Ok(self.read_le(addr, 4)? as Word)
+    }
+
+    fn write_word(&mut self, addr: u32, data: Word) -> Result<(), MemoryError> {
+        self.write_le(addr, 4, data as u128)
+    }
+
+    fn read_longword(&mut self, addr: u32) -> Result<Longword, MemoryError> {
+        Ok(self.read_le(addr, 8)? as Longword)
+    }
+
+    fn write_longword(&mut self, addr: u32, data: Longword) -> Result<(), MemoryError> {
+        self.write_le(addr, 8, data as u128)
+    }
+
+    fn read_quadword(&mut self, addr: u32) -> Result<Quadword, MemoryError> {
+        self.read_le(addr, 16)
+    }
+
+    fn write_quadword(&mut self, addr: u32, data: Quadword) -> Result<(), MemoryError> {
+        self.write_le(addr, 16, data)
+    }
+
+    fn amo_word(&mut self, addr: u32, op: AtomicOp, operand: Word) -> Result<Word, MemoryError> {
+        let word = self.aligned_word(addr)?;
+        let order = Ordering::SeqCst;
+        Ok(match op {
+            AtomicOp::Swap => word.swap(operand, order),
+            AtomicOp::Add => word.fetch_add(operand, order),
+            AtomicOp::Xor => word.fetch_xor(operand, order),
+            AtomicOp::And => word.fetch_and(operand, order),
+            AtomicOp::Or => word.fetch_or(operand, order),
+            AtomicOp::MinUnsigned => word.fetch_min(operand, order),
+            AtomicOp::MaxUnsigned => word.fetch_max(operand, order),
+            AtomicOp::Min | AtomicOp::Max => {
+                match word.fetch_update(order, order, |old| Some(op.apply(old, operand))) {
+                    Ok(old) | Err(old) => old,
+                }
+            }
+        })
+    }
+
+    fn compare_exchange_word(
+        &mut self,
+        addr: u32,
+        current: Word,
+        new: Word,
+    ) -> Result<Result<Word, Word>, MemoryError> {
+        let word = self.aligned_word(addr)?;
+        Ok(word.compare_exchange(current, new, Ordering::SeqCst, Ordering::SeqCst))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AtomicMemory;
+    use crate::memory::{AccessKind, AtomicOp, Bus, Fault, MemoryError};
+
+    #[test]
+    fn atomic_memory() {
+        let mut mem = AtomicMemory::new(14);
+        assert_eq!(mem.len(), 16);
+        mem.write_word(0, 0x44332211).unwrap();
+        mem.write_byte(1, 0xaa).unwrap();
+        assert_eq!(mem.read_word(0).unwrap(), 0x4433aa11);
+
+        // Accesses that span words are split, but read back the same.
+        mem.write_word(3, 0xddccbbaa).unwrap();
+        assert_eq!(mem.read_word(3).unwrap(), 0xddccbbaa);
+        assert_eq!(mem.read_word(0).unwrap(), 0xaa33aa11);
+        mem.write_longword(8, 0x0807060504030201).unwrap();
+        assert_eq!(mem.read_halfword(11).unwrap(), 0x0504);
+        assert_eq!(
+            mem.read_word(13),
+            Err(MemoryError::new(
+                Fault::AccessFault,
+                AccessKind::Load,
+                13,
+                4
+            ))
+        );
+
+        // Another handle sees the same memory.
+        let mut other = mem.clone();
+        assert_eq!(other.amo_word(8, AtomicOp::Add, 1).unwrap(), 0x04030201);
+        assert_eq!(
+            mem.amo_word(8, AtomicOp::Max, 0xffffffff).unwrap(),
+            0x04030202
+        );
+        assert_eq!(
+            mem.amo_word(8, AtomicOp::Min, 0xffffffff).unwrap(),
+            0x04030202
+        );
+        assert_eq!(mem.read_word(8).unwrap(), 0xffffffff);
+        assert_eq!(mem.compare_exchange_word(8, 0, 5).unwrap(), Err(0xffffffff));
+        assert_eq!(
+            mem.compare_exchange_word(8, 0xffffffff, 5).unwrap(),
+            Ok(0xffffffff)
+        );
+        assert_eq!(other.read_word(8).unwrap(), 5);
+        assert_eq!(
+            mem.amo_word(2, AtomicOp::Swap, 0),
+            Err(MemoryError::new(Fault::Misaligned, AccessKind::Amo, 2, 4))
+        );
+    }
+}
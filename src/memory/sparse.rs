@@ -0,0 +1,233 @@
+use crate::data::Byte;
+use crate::data::HalfwordUnsigned as Halfword;
+use crate::data::LongwordUnsigned as Longword;
+use crate::data::QuadwordUnsigned as Quadword;
+use crate::data::WordUnsigned as Word;
+use crate::memory::{Bus, MemoryError, SnapshotMemory};
+use std::boxed::Box;
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+/// The size of the pages that `SparseMemory` allocates, and that a
+/// `MemorySnapshot` is divided into.
+pub const PAGE_SIZE: usize = 4096;
+
+/// A copy of the contents of a memory, which can be restored into that
+/// memory or another one later.
+///
+/// Only the pages that contain at least one non-zero byte are included, so a
+/// snapshot of a large and mostly-unused address space remains small.
+///
+/// When the `serde` feature is enabled, snapshots can be serialized so that
+/// they can be saved to a file.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemorySnapshot {
+    /// The non-zero pages, each given as its base address and its contents,
+    /// in order of increasing address. Each page is `PAGE_SIZE` bytes long,
+    /// except that the last page of a `Memory` may be shorter.
+    pub pages: Vec<(usize, Vec<u8>)>,
+}
+
+impl MemorySnapshot {
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Self {
+        let pages = bytes
+            .chunks(PAGE_SIZE)
+            .enumerate()
+            .filter(|(_, page)| page.iter().any(|b| *b != 0))
+            .map(|(i, page)| (i * PAGE_SIZE, page.to_vec()))
+            .collect();
+        Self { pages }
+    }
+}
+
+/// A RAM that allocates its backing storage one page at a time as it is
+/// written, so that it can represent an address space much larger than the
+/// host memory it uses. Locations that have never been written read as zero.
+pub struct SparseMemory {
+    pages: BTreeMap<usize, Box<[u8; PAGE_SIZE]>>,
+}
+
+impl SparseMemory {
+    pub fn new() -> Self {
+        Self {
+            pages: BTreeMap::new(),
+        }
+    }
+
+    /// Captures the current contents of the memory.
+    pub fn snapshot(&self) -> MemorySnapshot {
+        let pages = self
+            .pages
+            .iter()
+            .filter(|(_, page)| page.iter().any(|b| *b != 0))
+            .map(|(base, page)| (*base, page.to_vec()))
+            .collect();
+        MemorySnapshot { pages }
+    }
+
+    /// Replaces the contents of the memory with a previously-captured
+    /// snapshot. Any location not included in the snapshot becomes zero.
+    pub fn restore(&mut self, snapshot: &MemorySnapshot) {
+        self.pages.clear();
+        for (base, data) in &snapshot.pages {
+            for (i, b) in data.iter().enumerate() {
+                self.write(base + i, *b);
+            }
+        }
+    }
+
+    fn read(&self, addr: usize) -> u8 {
+        match self.pages.get(&(addr - addr % PAGE_SIZE)) {
+            Some(page) => page[addr % PAGE_SIZE],
+            None => 0,
+        }
+    }
+
+    fn write(&mut self, addr: usize, v: u8) {
+        let page = self
+            .pages
+            .entry(addr - addr % PAGE_SIZE)
+            .or_insert_with(|| Box::new([0; PAGE_SIZE]));
+        page[addr % PAGE_SIZE] = v;
+    }
+
+    fn read_le(&self, addr: usize, size: usize) -> u128 {
+        (0..size).fold(0, |v, i| {
+            v | (self.read(addr.wrapping_add(i)) as u128) << (i * 8)
+        })
+    }
+
+    fn write_le(&mut self, addr: usize, size: usize, v: u128) {
+        for i in 0..size {
+            self.write(addr.wrapping_add(i), (v >> (i * 8)) as u8);
+        }
+    }
+}
+
+impl Default for SparseMemory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SnapshotMemory for SparseMemory {
+    fn snapshot_memory(&self) -> MemorySnapshot {
+        self.snapshot()
+    }
+
+    fn restore_memory(&mut self, snapshot: &MemorySnapshot) -> Result<(), MemoryError> {
+        self.restore(snapshot);
+        Ok(())
+    }
+}
+
+impl Bus<usize> for SparseMemory {
+    fn read_byte(&mut self, addr: usize) -> Result<Byte, MemoryError> {
+        Ok(self.read(addr))
+    }
+
+    fn write_byte(&mut self, addr: usize, data: Byte) -> Result<(), MemoryError> {
+        self.write(addr, data);
+        Ok(())
+    }
+
+    fn read_halfword(&mut self, addr: usize) -> Result<Halfword, MemoryError> {
+        Ok(self.read_le(addr, 2) as Halfword)
+    }
+
+    fn write_halfword(&mut self, addr: usize, data: Halfword) -> Result<(), MemoryError> {
+        self.write_le(addr, 2, data as u128);
+        Ok(())
+    }
+
+    fn read_word(&mut self, addr: usize) -> Result<Word, MemoryError> {
+        Ok(self.read_le(addr, 4) as Word)
+    }
+
+    fn write_word(&mut self, addr: usize, data: Word) -> Result<(), MemoryError> {
+        self.write_le(addr, 4, data as u128);
+        Ok(())
+    }
+
+    fn read_longword(&mut self, addr: usize) -> Result<Longword, MemoryError> {
+        Ok(self.read_le(addr, 8) as Longword)
+    }
+
+    fn write_longword(&mut self, addr: usize, data: Longword) -> Result<(), MemoryError> {
+        self.write_le(addr, 8, data as u128);
+        Ok(())
+    }
+
+    fn read_quadword(&mut self, addr: usize) -> Result<Quadword, MemoryError> {
+        Ok(self.read_le(addr, 16))
+    }
+
+    fn write_quadword(&mut self, addr: usize, data: Quadword) -> Result<(), MemoryError> {
+        self.write_le(addr, 16, data);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MemorySnapshot, SparseMemory, PAGE_SIZE};
+    use crate::memory::{AccessKind, Bus, Fault, Memory, MemoryError};
+    use std::vec;
+    use std::vec::Vec;
+
+    #[test]
+    fn snapshots() {
+        let mut sparse = SparseMemory::new();
+        assert_eq!(sparse.read_word(0x8000_0000).unwrap(), 0);
+        sparse.write_word(0x8000_0ffe, 0x44332211).unwrap();
+        sparse.write_byte(0x1000_0000, 0x55).unwrap();
+        assert_eq!(sparse.read_word(0x8000_0ffe).unwrap(), 0x44332211);
+        assert_eq!(sparse.read_halfword(0x8000_1000).unwrap(), 0x4433);
+
+        let snapshot = sparse.snapshot();
+        assert_eq!(
+            snapshot
+                .pages
+                .iter()
+                .map(|(base, _)| *base)
+                .collect::<Vec<_>>(),
+            [0x1000_0000, 0x8000_0000, 0x8000_1000]
+        );
+        sparse.write_word(0x8000_0ffe, 0).unwrap();
+        sparse.write_byte(0x2000_0000, 1).unwrap();
+        sparse.restore(&snapshot);
+        assert_eq!(sparse.read_word(0x8000_0ffe).unwrap(), 0x44332211);
+        assert_eq!(sparse.read_byte(0x2000_0000).unwrap(), 0);
+        assert_eq!(sparse.snapshot(), snapshot);
+
+        // Snapshots of a flat memory work the same way, and are
+        // interchangeable with those of a sparse memory.
+        let mut buf = [0u8; PAGE_SIZE + 16];
+        let mut ram = Memory::new_ram(&mut buf);
+        ram.write_byte(0, 7).unwrap();
+        ram.write_word(PAGE_SIZE, 0xdeadbeef).unwrap();
+        let snapshot = ram.snapshot();
+        assert_eq!(snapshot.pages.len(), 2);
+        assert_eq!(snapshot.pages[1].1.len(), 16);
+        ram.write_byte(0, 0).unwrap();
+        ram.restore(&snapshot).unwrap();
+        assert_eq!(ram.read_byte(0).unwrap(), 7);
+        sparse.restore(&snapshot);
+        assert_eq!(sparse.read_word(PAGE_SIZE).unwrap(), 0xdeadbeef);
+
+        let too_big = MemorySnapshot {
+            pages: vec![(PAGE_SIZE * 2, vec![1])],
+        };
+        assert_eq!(
+            ram.restore(&too_big),
+            Err(MemoryError::new(
+                Fault::AccessFault,
+                AccessKind::Store,
+                PAGE_SIZE as u128 * 2,
+                1
+            ))
+        );
+        assert_eq!(ram.read_byte(0).unwrap(), 7);
+    }
+}
@@ -1,28 +1,66 @@
 use crate::data::sign_extend;
-use crate::register::{FloatRegister, IntRegister};
+use crate::register::{FloatRegister, IntRegister, VectorRegister};
 
-/// Represents a raw RISC-V instruction word that is yet to be decoded.
+/// The length in bytes of the longest instruction that a `RawInstruction`
+/// can hold. Longer encodings are fetched only as far as their first parcel,
+/// and so never decode as valid instructions.
+pub const MAX_INSTRUCTION_LENGTH: usize = 16;
+
+/// Represents a raw RISC-V instruction that is yet to be decoded.
 ///
-/// It can represent both standard-length and compressed instructions, the
-/// latter of which are supported by ignoring the higher-order parcel.
+/// It can represent instructions of any length up to
+/// `MAX_INSTRUCTION_LENGTH`, made up of 16-bit parcels with the
+/// lowest-addressed parcel in the least significant bits. Any bits beyond
+/// the end of the instruction are zero, or ignored if built with `new`.
 #[derive(Debug, PartialEq)]
-pub struct RawInstruction(u32);
+pub struct RawInstruction(u128);
 
 impl RawInstruction {
     pub fn new(word: u32) -> Self {
-        return Self(word);
+        return Self(word as u128);
+    }
+
+    /// Assembles an instruction from its parcels, given in the order they
+    /// appear in memory. Parcels beyond `MAX_INSTRUCTION_LENGTH` are
+    /// ignored.
+    pub fn from_parcels(parcels: &[u16]) -> Self {
+        let bits = parcels
+            .iter()
+            .take(MAX_INSTRUCTION_LENGTH / 2)
+            .enumerate()
+            .fold(0, |bits, (i, parcel)| bits | (*parcel as u128) << (i * 16));
+        Self(bits)
+    }
+
+    /// Returns the low 32 bits of the instruction, which hold the whole of
+    /// a standard-length or compressed instruction. For a compressed
+    /// instruction built with `new`, this includes the unused higher-order
+    /// parcel.
+    pub fn bits(&self) -> u32 {
+        self.0 as u32
+    }
+
+    /// Returns all of the bits of the instruction, for instructions longer
+    /// than 32 bits.
+    pub fn wide_bits(&self) -> u128 {
+        self.0
+    }
+
+    // The low 32 bits, which all of the field accessors below work with.
+    fn word(&self) -> u32 {
+        self.0 as u32
     }
 
     pub fn opcode(&self) -> u8 {
         match self.length() {
-            2 => (self.0 & 0b0000000000000011) as u8,
-            4 => (self.0 & 0b0000000001111111) as u8,
-            _ => self.0 as u8,
+            2 => (self.word() & 0b0000000000000011) as u8,
+            4 => (self.word() & 0b0000000001111111) as u8,
+            _ => self.word() as u8,
         }
     }
 
     pub fn matches(&self, mask: u32, want: u32) -> bool {
-        (self.0 & mask) == want
+        (self.word() & mask) == want
     }
 
     pub fn length(&self) -> usize {
@@ -30,34 +68,34 @@ impl RawInstruction {
     }
 
     pub fn aq(&self) -> bool {
-        return (self.0 & 0b00000100000000000000000000000000) != 0;
+        return (self.word() & 0b00000100000000000000000000000000) != 0;
     }
 
     pub fn cfrd(&self) -> FloatRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000000000000111110000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000000111110000000) >> 7;
         return FloatRegister::numbered(raw as usize);
     }
 
     pub fn cfrdq(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000000111
-        raw |= (self.0 & 0b00000000000000000000000000011100) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000000011100) >> 2;
         return IntRegister::numbered(raw as usize);
     }
 
     pub fn cfrs2(&self) -> FloatRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000000000000000001111100) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000001111100) >> 2;
         return FloatRegister::numbered(raw as usize);
     }
 
     pub fn cfrs2q(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000000111
-        raw |= (self.0 & 0b00000000000000000000000000011100) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000000011100) >> 2;
         return IntRegister::numbered(raw as usize);
     }
 
@@ -65,28 +103,28 @@ impl RawInstruction {
         let width = 10;
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000001000000000
-        raw |= (self.0 & 0b00000000000000000001000000000000) >> 3;
+        raw |= (self.word() & 0b00000000000000000001000000000000) >> 3;
         // Fill 0b00000000000000000000000000010000
-        raw |= (self.0 & 0b00000000000000000000000001000000) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000001000000) >> 2;
         // Fill 0b00000000000000000000000001000000
-        raw |= (self.0 & 0b00000000000000000000000000100000) << 1;
+        raw |= (self.word() & 0b00000000000000000000000000100000) << 1;
         // Fill 0b00000000000000000000000110000000
-        raw |= (self.0 & 0b00000000000000000000000000011000) << 4;
+        raw |= (self.word() & 0b00000000000000000000000000011000) << 4;
         // Fill 0b00000000000000000000000000100000
-        raw |= (self.0 & 0b00000000000000000000000000000100) << 3;
+        raw |= (self.word() & 0b00000000000000000000000000000100) << 3;
         return sign_extend(raw, width);
     }
 
     pub fn cimm4spn(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000110000
-        raw |= (self.0 & 0b00000000000000000001100000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000001100000000000) >> 7;
         // Fill 0b00000000000000000000001111000000
-        raw |= (self.0 & 0b00000000000000000000011110000000) >> 1;
+        raw |= (self.word() & 0b00000000000000000000011110000000) >> 1;
         // Fill 0b00000000000000000000000000000100
-        raw |= (self.0 & 0b00000000000000000000000001000000) >> 4;
+        raw |= (self.word() & 0b00000000000000000000000001000000) >> 4;
         // Fill 0b00000000000000000000000000001000
-        raw |= (self.0 & 0b00000000000000000000000000100000) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000000100000) >> 2;
         return raw;
     }
 
@@ -94,24 +132,24 @@ impl RawInstruction {
         let width = 9;
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000100000000
-        raw |= (self.0 & 0b00000000000000000001000000000000) >> 4;
+        raw |= (self.word() & 0b00000000000000000001000000000000) >> 4;
         // Fill 0b00000000000000000000000000011000
-        raw |= (self.0 & 0b00000000000000000000110000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000000110000000000) >> 7;
         // Fill 0b00000000000000000000000011000000
-        raw |= (self.0 & 0b00000000000000000000000001100000) << 1;
+        raw |= (self.word() & 0b00000000000000000000000001100000) << 1;
         // Fill 0b00000000000000000000000000000110
-        raw |= (self.0 & 0b00000000000000000000000000011000) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000000011000) >> 2;
         // Fill 0b00000000000000000000000000100000
-        raw |= (self.0 & 0b00000000000000000000000000000100) << 3;
+        raw |= (self.word() & 0b00000000000000000000000000000100) << 3;
         return sign_extend(raw, width);
     }
 
     pub fn cimmd(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000111000
-        raw |= (self.0 & 0b00000000000000000001110000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000001110000000000) >> 7;
         // Fill 0b00000000000000000000000011000000
-        raw |= (self.0 & 0b00000000000000000000000001100000) << 1;
+        raw |= (self.word() & 0b00000000000000000000000001100000) << 1;
         return raw;
     }
 
@@ -119,9 +157,9 @@ impl RawInstruction {
         let width = 6;
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000100000
-        raw |= (self.0 & 0b00000000000000000001000000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000001000000000000) >> 7;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000000000000000001111100) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000001111100) >> 2;
         return sign_extend(raw, width);
     }
 
@@ -129,108 +167,108 @@ impl RawInstruction {
         let width = 12;
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000100000000000
-        raw |= (self.0 & 0b00000000000000000001000000000000) >> 1;
+        raw |= (self.word() & 0b00000000000000000001000000000000) >> 1;
         // Fill 0b00000000000000000000000000010000
-        raw |= (self.0 & 0b00000000000000000000100000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000000100000000000) >> 7;
         // Fill 0b00000000000000000000001100000000
-        raw |= (self.0 & 0b00000000000000000000011000000000) >> 1;
+        raw |= (self.word() & 0b00000000000000000000011000000000) >> 1;
         // Fill 0b00000000000000000000010000000000
-        raw |= (self.0 & 0b00000000000000000000000100000000) << 2;
+        raw |= (self.word() & 0b00000000000000000000000100000000) << 2;
         // Fill 0b00000000000000000000000001000000
-        raw |= (self.0 & 0b00000000000000000000000010000000) >> 1;
+        raw |= (self.word() & 0b00000000000000000000000010000000) >> 1;
         // Fill 0b00000000000000000000000010000000
-        raw |= (self.0 & 0b00000000000000000000000001000000) << 1;
+        raw |= (self.word() & 0b00000000000000000000000001000000) << 1;
         // Fill 0b00000000000000000000000000001110
-        raw |= (self.0 & 0b00000000000000000000000000111000) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000000111000) >> 2;
         // Fill 0b00000000000000000000000000100000
-        raw |= (self.0 & 0b00000000000000000000000000000100) << 3;
+        raw |= (self.word() & 0b00000000000000000000000000000100) << 3;
         return sign_extend(raw, width);
     }
 
     pub fn cimmldsp(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000100000
-        raw |= (self.0 & 0b00000000000000000001000000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000001000000000000) >> 7;
         // Fill 0b00000000000000000000000000011000
-        raw |= (self.0 & 0b00000000000000000000000001100000) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000001100000) >> 2;
         // Fill 0b00000000000000000000000111000000
-        raw |= (self.0 & 0b00000000000000000000000000011100) << 4;
+        raw |= (self.word() & 0b00000000000000000000000000011100) << 4;
         return raw;
     }
 
     pub fn cimmlqsp(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000100000
-        raw |= (self.0 & 0b00000000000000000001000000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000001000000000000) >> 7;
         // Fill 0b00000000000000000000000000010000
-        raw |= (self.0 & 0b00000000000000000000000001000000) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000001000000) >> 2;
         // Fill 0b00000000000000000000001111000000
-        raw |= (self.0 & 0b00000000000000000000000000111100) << 4;
+        raw |= (self.word() & 0b00000000000000000000000000111100) << 4;
         return raw;
     }
 
     pub fn cimmlwsp(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000100000
-        raw |= (self.0 & 0b00000000000000000001000000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000001000000000000) >> 7;
         // Fill 0b00000000000000000000000000011100
-        raw |= (self.0 & 0b00000000000000000000000001110000) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000001110000) >> 2;
         // Fill 0b00000000000000000000000011000000
-        raw |= (self.0 & 0b00000000000000000000000000001100) << 4;
+        raw |= (self.word() & 0b00000000000000000000000000001100) << 4;
         return raw;
     }
 
     pub fn cimmq(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000110000
-        raw |= (self.0 & 0b00000000000000000001100000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000001100000000000) >> 7;
         // Fill 0b00000000000000000000000100000000
-        raw |= (self.0 & 0b00000000000000000000010000000000) >> 2;
+        raw |= (self.word() & 0b00000000000000000000010000000000) >> 2;
         // Fill 0b00000000000000000000000011000000
-        raw |= (self.0 & 0b00000000000000000000000001100000) << 1;
+        raw |= (self.word() & 0b00000000000000000000000001100000) << 1;
         return raw;
     }
 
     pub fn cimmsdsp(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000111000
-        raw |= (self.0 & 0b00000000000000000001110000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000001110000000000) >> 7;
         // Fill 0b00000000000000000000000111000000
-        raw |= (self.0 & 0b00000000000000000000001110000000) >> 1;
+        raw |= (self.word() & 0b00000000000000000000001110000000) >> 1;
         return raw;
     }
 
     pub fn cimmsh5(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000000000000000001111100) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000001111100) >> 2;
         return raw;
     }
 
     pub fn cimmsh6(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000100000
-        raw |= (self.0 & 0b00000000000000000001000000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000001000000000000) >> 7;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000000000000000001111100) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000001111100) >> 2;
         return raw;
     }
 
     pub fn cimmsqsp(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000110000
-        raw |= (self.0 & 0b00000000000000000001100000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000001100000000000) >> 7;
         // Fill 0b00000000000000000000001111000000
-        raw |= (self.0 & 0b00000000000000000000011110000000) >> 1;
+        raw |= (self.word() & 0b00000000000000000000011110000000) >> 1;
         return raw;
     }
 
     pub fn cimmswsp(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000111100
-        raw |= (self.0 & 0b00000000000000000001111000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000001111000000000) >> 7;
         // Fill 0b00000000000000000000000011000000
-        raw |= (self.0 & 0b00000000000000000000000110000000) >> 1;
+        raw |= (self.word() & 0b00000000000000000000000110000000) >> 1;
         return raw;
     }
 
@@ -238,20 +276,20 @@ impl RawInstruction {
         let width = 18;
         let mut raw: u32 = 0;
         // Fill 0b00000000000000100000000000000000
-        raw |= (self.0 & 0b00000000000000000001000000000000) << 5;
+        raw |= (self.word() & 0b00000000000000000001000000000000) << 5;
         // Fill 0b00000000000000011111000000000000
-        raw |= (self.0 & 0b00000000000000000000000001111100) << 10;
+        raw |= (self.word() & 0b00000000000000000000000001111100) << 10;
         return sign_extend(raw, width);
     }
 
     pub fn cimmw(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000111000
-        raw |= (self.0 & 0b00000000000000000001110000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000001110000000000) >> 7;
         // Fill 0b00000000000000000000000000000100
-        raw |= (self.0 & 0b00000000000000000000000001000000) >> 4;
+        raw |= (self.word() & 0b00000000000000000000000001000000) >> 4;
         // Fill 0b00000000000000000000000001000000
-        raw |= (self.0 & 0b00000000000000000000000000100000) << 1;
+        raw |= (self.word() & 0b00000000000000000000000000100000) << 1;
         return raw;
     }
 
@@ -259,107 +297,107 @@ impl RawInstruction {
         let width = 6;
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000100000
-        raw |= (self.0 & 0b00000000000000000001000000000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000001000000000000) >> 7;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000000000000000001111100) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000001111100) >> 2;
         return sign_extend(raw, width);
     }
 
     pub fn crd(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000000000000111110000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000000111110000000) >> 7;
         return IntRegister::numbered(raw as usize);
     }
 
     pub fn crd0(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000000001
-        raw |= (self.0 & 0b00000000000000000001000000000000) >> 12;
+        raw |= (self.word() & 0b00000000000000000001000000000000) >> 12;
         return IntRegister::numbered(raw as usize);
     }
 
     pub fn crdq(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000000111
-        raw |= (self.0 & 0b00000000000000000000000000011100) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000000011100) >> 2;
         return IntRegister::c_numbered(raw as usize);
     }
 
     pub fn crs1(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000000000000111110000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000000111110000000) >> 7;
         return IntRegister::numbered(raw as usize);
     }
 
     pub fn crs1q(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000000111
-        raw |= (self.0 & 0b00000000000000000000001110000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000000001110000000) >> 7;
         return IntRegister::c_numbered(raw as usize);
     }
 
     pub fn crs1rd(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000000000000111110000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000000111110000000) >> 7;
         return IntRegister::numbered(raw as usize);
     }
 
     pub fn crs1rdq(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000000111
-        raw |= (self.0 & 0b00000000000000000000001110000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000000001110000000) >> 7;
         return IntRegister::c_numbered(raw as usize);
     }
 
     pub fn crs2(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000000000000000001111100) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000001111100) >> 2;
         return IntRegister::numbered(raw as usize);
     }
 
     pub fn crs2q(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000000111
-        raw |= (self.0 & 0b00000000000000000000000000011100) >> 2;
+        raw |= (self.word() & 0b00000000000000000000000000011100) >> 2;
         return IntRegister::c_numbered(raw as usize);
     }
 
     pub fn csr12(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000111111111111
-        raw |= (self.0 & 0b11111111111100000000000000000000) >> 20;
+        raw |= (self.word() & 0b11111111111100000000000000000000) >> 20;
         return raw;
     }
 
     pub fn frd(&self) -> FloatRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000000000000111110000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000000111110000000) >> 7;
         return FloatRegister::numbered(raw as usize);
     }
 
     pub fn frs1(&self) -> FloatRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000011111000000000000000) >> 15;
+        raw |= (self.word() & 0b00000000000011111000000000000000) >> 15;
         return FloatRegister::numbered(raw as usize);
     }
 
     pub fn frs2(&self) -> FloatRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000001111100000000000000000000) >> 20;
+        raw |= (self.word() & 0b00000001111100000000000000000000) >> 20;
         return FloatRegister::numbered(raw as usize);
     }
 
     pub fn frs3(&self) -> FloatRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b11111000000000000000000000000000) >> 27;
+        raw |= (self.word() & 0b11111000000000000000000000000000) >> 27;
         return FloatRegister::numbered(raw as usize);
     }
 
@@ -367,7 +405,7 @@ impl RawInstruction {
         let width = 12;
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000111111111111
-        raw |= (self.0 & 0b11111111111100000000000000000000) >> 20;
+        raw |= (self.word() & 0b11111111111100000000000000000000) >> 20;
         return sign_extend(raw, width);
     }
 
@@ -375,7 +413,7 @@ impl RawInstruction {
         let width = 32;
         let mut raw: u32 = 0;
         // Fill 0b11111111111111111111000000000000
-        raw |= (self.0 & 0b11111111111111111111000000000000);
+        raw |= (self.word() & 0b11111111111111111111000000000000);
         return sign_extend(raw, width);
     }
 
@@ -383,13 +421,13 @@ impl RawInstruction {
         let width = 21;
         let mut raw: u32 = 0;
         // Fill 0b00000000000100000000000000000000
-        raw |= (self.0 & 0b10000000000000000000000000000000) >> 11;
+        raw |= (self.word() & 0b10000000000000000000000000000000) >> 11;
         // Fill 0b00000000000000000000011111111110
-        raw |= (self.0 & 0b01111111111000000000000000000000) >> 20;
+        raw |= (self.word() & 0b01111111111000000000000000000000) >> 20;
         // Fill 0b00000000000000000000100000000000
-        raw |= (self.0 & 0b00000000000100000000000000000000) >> 9;
+        raw |= (self.word() & 0b00000000000100000000000000000000) >> 9;
         // Fill 0b00000000000011111111000000000000
-        raw |= (self.0 & 0b00000000000011111111000000000000);
+        raw |= (self.word() & 0b00000000000011111111000000000000);
         return sign_extend(raw, width);
     }
 
@@ -397,7 +435,7 @@ impl RawInstruction {
         let width = 12;
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000111111111111
-        raw |= (self.0 & 0b11111111111100000000000000000000) >> 20;
+        raw |= (self.word() & 0b11111111111100000000000000000000) >> 20;
         return sign_extend(raw, width);
     }
 
@@ -405,47 +443,47 @@ impl RawInstruction {
         let width = 32;
         let mut raw: u32 = 0;
         // Fill 0b11111111111111111111000000000000
-        raw |= (self.0 & 0b11111111111111111111000000000000);
+        raw |= (self.word() & 0b11111111111111111111000000000000);
         return sign_extend(raw, width);
     }
 
-    pub fn pred(&self) -> bool {
-        return (self.0 & 0b00001111000000000000000000000000) != 0;
+    pub fn pred(&self) -> u32 {
+        return (self.word() & 0b00001111000000000000000000000000) >> 24;
     }
 
     pub fn rd(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000000000000111110000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000000111110000000) >> 7;
         return IntRegister::numbered(raw as usize);
     }
 
     pub fn rl(&self) -> bool {
-        return (self.0 & 0b00000010000000000000000000000000) != 0;
+        return (self.word() & 0b00000010000000000000000000000000) != 0;
     }
 
     pub fn rm(&self) -> bool {
-        return (self.0 & 0b00000000000000000111000000000000) != 0;
+        return (self.word() & 0b00000000000000000111000000000000) != 0;
     }
 
     pub fn rs1(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000011111000000000000000) >> 15;
+        raw |= (self.word() & 0b00000000000011111000000000000000) >> 15;
         return IntRegister::numbered(raw as usize);
     }
 
     pub fn rs2(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000001111100000000000000000000) >> 20;
+        raw |= (self.word() & 0b00000001111100000000000000000000) >> 20;
         return IntRegister::numbered(raw as usize);
     }
 
     pub fn rs3(&self) -> IntRegister {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b11111000000000000000000000000000) >> 27;
+        raw |= (self.word() & 0b11111000000000000000000000000000) >> 27;
         return IntRegister::numbered(raw as usize);
     }
 
@@ -453,34 +491,34 @@ impl RawInstruction {
         let width = 13;
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000001000000000000
-        raw |= (self.0 & 0b10000000000000000000000000000000) >> 19;
+        raw |= (self.word() & 0b10000000000000000000000000000000) >> 19;
         // Fill 0b00000000000000000000011111100000
-        raw |= (self.0 & 0b01111110000000000000000000000000) >> 20;
+        raw |= (self.word() & 0b01111110000000000000000000000000) >> 20;
         // Fill 0b00000000000000000000000000011110
-        raw |= (self.0 & 0b00000000000000000000111100000000) >> 7;
+        raw |= (self.word() & 0b00000000000000000000111100000000) >> 7;
         // Fill 0b00000000000000000000100000000000
-        raw |= (self.0 & 0b00000000000000000000000010000000) << 4;
+        raw |= (self.word() & 0b00000000000000000000000010000000) << 4;
         return sign_extend(raw, width);
     }
 
     pub fn shamt5(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000001111100000000000000000000) >> 20;
+        raw |= (self.word() & 0b00000001111100000000000000000000) >> 20;
         return raw;
     }
 
     pub fn shamt6(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000111111
-        raw |= (self.0 & 0b00000011111100000000000000000000) >> 20;
+        raw |= (self.word() & 0b00000011111100000000000000000000) >> 20;
         return raw;
     }
 
     pub fn shamt7(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000001111111
-        raw |= (self.0 & 0b00000111111100000000000000000000) >> 20;
+        raw |= (self.word() & 0b00000111111100000000000000000000) >> 20;
         return raw;
     }
 
@@ -488,20 +526,67 @@ impl RawInstruction {
         let width = 12;
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000111111100000
-        raw |= (self.0 & 0b11111110000000000000000000000000) >> 20;
+        raw |= (self.word() & 0b11111110000000000000000000000000) >> 20;
+        // Fill 0b00000000000000000000000000011111
+        raw |= (self.word() & 0b00000000000000000000111110000000) >> 7;
+        return sign_extend(raw, width);
+    }
+
+    pub fn simm5(&self) -> i32 {
+        let width = 5;
+        let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000000000000111110000000) >> 7;
+        raw |= (self.word() & 0b00000000000011111000000000000000) >> 15;
         return sign_extend(raw, width);
     }
 
-    pub fn succ(&self) -> bool {
-        return (self.0 & 0b00000000111100000000000000000000) != 0;
+    pub fn succ(&self) -> u32 {
+        return (self.word() & 0b00000000111100000000000000000000) >> 20;
+    }
+
+    pub fn vd(&self) -> VectorRegister {
+        let mut raw: u32 = 0;
+        // Fill 0b00000000000000000000000000011111
+        raw |= (self.word() & 0b00000000000000000000111110000000) >> 7;
+        return VectorRegister::numbered(raw as usize);
+    }
+
+    pub fn vm(&self) -> bool {
+        return (self.word() & 0b00000010000000000000000000000000) != 0;
+    }
+
+    pub fn vs1(&self) -> VectorRegister {
+        let mut raw: u32 = 0;
+        // Fill 0b00000000000000000000000000011111
+        raw |= (self.word() & 0b00000000000011111000000000000000) >> 15;
+        return VectorRegister::numbered(raw as usize);
+    }
+
+    pub fn vs2(&self) -> VectorRegister {
+        let mut raw: u32 = 0;
+        // Fill 0b00000000000000000000000000011111
+        raw |= (self.word() & 0b00000001111100000000000000000000) >> 20;
+        return VectorRegister::numbered(raw as usize);
+    }
+
+    pub fn vtypei10(&self) -> u32 {
+        let mut raw: u32 = 0;
+        // Fill 0b00000000000000000000001111111111
+        raw |= (self.word() & 0b00111111111100000000000000000000) >> 20;
+        return raw;
+    }
+
+    pub fn vtypei11(&self) -> u32 {
+        let mut raw: u32 = 0;
+        // Fill 0b00000000000000000000011111111111
+        raw |= (self.word() & 0b01111111111100000000000000000000) >> 20;
+        return raw;
     }
 
     pub fn zimm(&self) -> u32 {
         let mut raw: u32 = 0;
         // Fill 0b00000000000000000000000000011111
-        raw |= (self.0 & 0b00000000000011111000000000000000) >> 15;
+        raw |= (self.word() & 0b00000000000011111000000000000000) >> 15;
         return raw;
     }
 }
@@ -524,9 +609,12 @@ pub enum Opcode {
     Nmsub = 0b1001011,
     Op = 0b0110011,
     Op32 = 0b0111011,
+    Op64 = 0b1111011,
     OpFp = 0b1010011,
     OpImm = 0b0010011,
     OpImm32 = 0b0011011,
+    OpImm64 = 0b1011011,
+    OpV = 0b1010111,
     Store = 0b0100011,
     StoreFp = 0b0100111,
     System = 0b1110011,
@@ -567,6 +655,20 @@ pub fn instruction_length(low_parcel: u16) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use super::RawInstruction;
+
+    #[test]
+    fn from_parcels() {
+        let raw = RawInstruction::from_parcels(&[0x0513, 0x0050]);
+        assert_eq!(raw, RawInstruction::new(0x00500513));
+        assert_eq!(raw.length(), 4);
+        assert_eq!(raw.rd().num(), 10);
+
+        let raw = RawInstruction::from_parcels(&[0x003f, 0x1111, 0x2222, 0x3333]);
+        assert_eq!(raw.length(), 8);
+        assert_eq!(raw.bits(), 0x1111003f);
+        assert_eq!(raw.wide_bits(), 0x3333_2222_1111_003f);
+    }
 
     #[test]
     fn instruction_length() {
@@ -4,6 +4,7 @@ use core::fmt;
 pub enum Register {
     Int(IntRegister),
     Float(FloatRegister),
+    Vector(VectorRegister),
     ControlStatus(ControlStatusRegister),
 }
 
@@ -33,6 +34,17 @@ impl IntRegister {
     pub fn num(&self) -> usize {
         self.0
     }
+
+    /// Returns the name given to the register by the standard calling
+    /// convention, such as "sp" for x2, as used in assembly language.
+    pub fn abi_name(&self) -> &'static str {
+        const NAMES: [&str; 32] = [
+            "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3",
+            "a4", "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11",
+            "t3", "t4", "t5", "t6",
+        ];
+        NAMES[self.0]
+    }
 }
 
 impl PartialEq for IntRegister {
@@ -47,6 +59,12 @@ impl fmt::Debug for IntRegister {
     }
 }
 
+impl fmt::Display for IntRegister {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.abi_name())
+    }
+}
+
 impl core::convert::From<IntRegister> for Register {
     fn from(ir: IntRegister) -> Register {
         Register::Int(ir)
@@ -75,6 +93,17 @@ impl FloatRegister {
     pub fn num(&self) -> usize {
         self.0
     }
+
+    /// Returns the name given to the register by the standard calling
+    /// convention, such as "fa0" for f10, as used in assembly language.
+    pub fn abi_name(&self) -> &'static str {
+        const NAMES: [&str; 32] = [
+            "ft0", "ft1", "ft2", "ft3", "ft4", "ft5", "ft6", "ft7", "fs0", "fs1", "fa0", "fa1",
+            "fa2", "fa3", "fa4", "fa5", "fa6", "fa7", "fs2", "fs3", "fs4", "fs5", "fs6", "fs7",
+            "fs8", "fs9", "fs10", "fs11", "ft8", "ft9", "ft10", "ft11",
+        ];
+        NAMES[self.0]
+    }
 }
 
 impl PartialEq for FloatRegister {
@@ -89,12 +118,58 @@ impl fmt::Debug for FloatRegister {
     }
 }
 
+impl fmt::Display for FloatRegister {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.abi_name())
+    }
+}
+
 impl core::convert::From<FloatRegister> for Register {
     fn from(fr: FloatRegister) -> Register {
         Register::Float(fr)
     }
 }
 
+#[derive(Clone, Copy)]
+pub struct VectorRegister(usize);
+
+impl VectorRegister {
+    pub fn numbered(n: usize) -> Self {
+        if n > 31 {
+            panic!("vector register number out of range (0-31 inclusive)");
+        }
+        return Self(n);
+    }
+
+    pub fn num(&self) -> usize {
+        self.0
+    }
+}
+
+impl PartialEq for VectorRegister {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl fmt::Debug for VectorRegister {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VectorRegister({})", self.0)
+    }
+}
+
+impl fmt::Display for VectorRegister {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}", self.0)
+    }
+}
+
+impl core::convert::From<VectorRegister> for Register {
+    fn from(vr: VectorRegister) -> Register {
+        Register::Vector(vr)
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct ControlStatusRegister(usize);
 
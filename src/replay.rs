@@ -0,0 +1,666 @@
+use crate::data::Byte;
+use crate::data::HalfwordUnsigned as Halfword;
+use crate::data::LongwordUnsigned as Longword;
+use crate::data::QuadwordUnsigned as Quadword;
+use crate::data::WordUnsigned as Word;
+use crate::exec::{step_rv32, ExecStatus};
+use crate::hart::{Hart, HartSnapshot, SingleThreadUserHart};
+use crate::isa::RV32;
+use crate::memory::{
+    AccessKind, AtomicOp, Bus, Fault, FenceSet, MemoryError, MemorySnapshot, SnapshotMemory,
+};
+
+use core::mem;
+use core::ops::Range;
+use std::vec;
+use std::vec::Vec;
+
+/// Everything needed to replay a run of an RV32 hart deterministically: its
+/// initial state, plus each of the inputs that came from outside of the
+/// guest program, in the order they were consumed.
+///
+/// When the `serde` feature is enabled, recordings can be serialized so that
+/// a run captured on one machine can be replayed on another.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Recording {
+    /// The state of the hart before the first step.
+    pub start: HartSnapshot<u32, f64>,
+
+    /// The contents of memory before the first step.
+    pub memory: MemorySnapshot,
+
+    /// The value returned by each read from a device range, in order.
+    pub device_reads: Vec<u128>,
+
+    /// The effects of the execution environment each time it handled a
+    /// status from the executor, in order.
+    pub events: Vec<EnvironmentEvent>,
+
+    /// The total number of steps that were recorded.
+    pub steps: u64,
+}
+
+/// The effects of the execution environment's handling of a single status
+/// from the executor, such as an `ecall`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnvironmentEvent {
+    /// The zero-based index of the step that produced the status.
+    pub step: u64,
+
+    /// The state of the hart once the environment had handled the status.
+    pub hart: HartSnapshot<u32, f64>,
+
+    /// Each write the environment made to memory, given as the address and
+    /// the bytes written there.
+    pub writes: Vec<(u32, Vec<u8>)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Record,
+    Capture,
+    Replay,
+}
+
+/// Wraps another `Bus` to intercept accesses to memory-mapped devices, whose
+/// behavior is outside of the guest program's control, so that a run can be
+/// recorded by `record_rv32` and later replayed by `Replay`.
+///
+/// While recording, the value returned by each read from a device range is
+/// logged. While replaying, those reads return the logged values instead of
+/// reaching the wrapped bus, and writes to device ranges are discarded.
+/// Accesses outside of the device ranges always pass through unchanged.
+///
+/// The wrapped bus must implement `SnapshotMemory` so that its contents can
+/// be captured at the start of a recording and at each replay checkpoint.
+pub struct DeviceLog<Wrapped: Bus<u32>> {
+    wrapped: Wrapped,
+    devices: Vec<Range<u32>>,
+    mode: Mode,
+    reads: Vec<u128>,
+    cursor: usize,
+    writes: Vec<(u32, Vec<u8>)>,
+}
+
+impl<Wrapped: Bus<u32>> DeviceLog<Wrapped> {
+    /// Consumes a bus and produces a wrapping `DeviceLog` that initially
+    /// has no device ranges.
+    pub fn new(wrapped: Wrapped) -> Self {
+        Self {
+            wrapped,
+            devices: Vec::new(),
+            mode: Mode::Record,
+            reads: Vec::new(),
+            cursor: 0,
+            writes: Vec::new(),
+        }
+    }
+
+    /// Marks the given address range as belonging to a device.
+    pub fn add_device(&mut self, range: Range<u32>) {
+        self.devices.push(range);
+    }
+
+    /// Borrows the wrapped bus.
+    pub fn wrapped(&mut self) -> &mut Wrapped {
+        &mut self.wrapped
+    }
+
+    fn is_device(&self, addr: u32, size: u32) -> bool {
+        let end = addr.wrapping_add(size);
+        self.devices
+            .iter()
+            .any(|range| addr < range.end && range.start < end)
+    }
+
+    fn read<T: Into<u128> + Copy>(
+        &mut self,
+        addr: u32,
+        size: u32,
+        from_log: fn(u128) -> T,
+        read: impl FnOnce(&mut Wrapped) -> Result<T, MemoryError>,
+    ) -> Result<T, MemoryError> {
+        if !self.is_device(addr, size) {
+            return read(&mut self.wrapped);
+        }
+        match self.mode {
+            Mode::Record => {
+                let v = read(&mut self.wrapped)?;
+                self.reads.push(v.into());
+                Ok(v)
+            }
+            Mode::Capture => read(&mut self.wrapped),
+            Mode::Replay => {
+                // Running out of logged values means that the replay has
+                // diverged from the recording.
+                let v = *self.reads.get(self.cursor).ok_or(MemoryError::new(
+                    Fault::AccessFault,
+                    AccessKind::Load,
+                    addr as u128,
+                    size as usize,
+                ))?;
+                self.cursor += 1;
+                Ok(from_log(v))
+            }
+        }
+    }
+
+    fn write<T: Into<u128> + Copy>(
+        &mut self,
+        addr: u32,
+        size: u32,
+        data: T,
+        write: impl FnOnce(&mut Wrapped, T) -> Result<(), MemoryError>,
+    ) -> Result<(), MemoryError> {
+        match self.mode {
+            Mode::Replay if self.is_device(addr, size) => Ok(()),
+            Mode::Capture => {
+                let bytes = data.into().to_le_bytes()[..size as usize].to_vec();
+                write(&mut self.wrapped, data)?;
+                self.writes.push((addr, bytes));
+                Ok(())
+            }
+            _ => write(&mut self.wrapped, data),
+        }
+    }
+}
+
+impl<Wrapped: Bus<u32>> Bus<u32> for DeviceLog<Wrapped> {
+    fn read_byte(&mut self, addr: u32) -> Result<Byte, MemoryError> {
+        self.read(addr, 1, |v| v as Byte, |bus| bus.read_byte(addr))
+    }
+
+    fn read_halfword(&mut self, addr: u32) -> Result<Halfword, MemoryError> {
+        self.read(addr, 2, |v| v as Halfword, |bus| bus.read_halfword(addr))
+    }
+
+    fn read_word(&mut self, addr: u32) -> Result<Word, MemoryError> {
+        self.read(addr, 4, |v| v as Word, |bus| bus.read_word(addr))
+    }
+
+    fn read_longword(&mut self, addr: u32) -> Result<Longword, MemoryError> {
+        self.read(addr, 8, |v| v as Longword, |bus| bus.read_longword(addr))
+    }
+
+    fn read_quadword(&mut self, addr: u32) -> Result<Quadword, MemoryError> {
+        self.read(addr, 16, |v| v, |bus| bus.read_quadword(addr))
+    }
+
+    fn write_byte(&mut self, addr: u32, data: Byte) -> Result<(), MemoryError> {
+        self.write(addr, 1, data, |bus, v| bus.write_byte(addr, v))
+    }
+
+    fn write_halfword(&mut self, addr: u32, data: Halfword) -> Result<(), MemoryError> {
+        self.write(addr, 2, data, |bus, v| bus.write_halfword(addr, v))
+    }
+
+    fn write_word(&mut self, addr: u32, data: Word) -> Result<(), MemoryError> {
+        self.write(addr, 4, data, |bus, v| bus.write_word(addr, v))
+    }
+
+    fn write_longword(&mut self, addr: u32, data: Longword) -> Result<(), MemoryError> {
+        self.write(addr, 8, data, |bus, v| bus.write_longword(addr, v))
+    }
+
+    fn write_quadword(&mut self, addr: u32, data: Quadword) -> Result<(), MemoryError> {
+        self.write(addr, 16, data, |bus, v| bus.write_quadword(addr, v))
+    }
+
+    fn fetch_halfword(&mut self, addr: u32) -> Result<Halfword, MemoryError> {
+        self.read(addr, 2, |v| v as Halfword, |bus| bus.fetch_halfword(addr))
+    }
+
+    // The value an atomic operation reads from a device is logged like any
+    // other read. While replaying, its write to a device is discarded.
+    fn amo_word(&mut self, addr: u32, op: AtomicOp, operand: Word) -> Result<Word, MemoryError> {
+        let old = self.read(
+            addr,
+            4,
+            |v| v as Word,
+            |bus| bus.amo_word(addr, op, operand),
+        )?;
+        if self.mode == Mode::Capture {
+            let new = op.apply(old, operand);
+            self.writes.push((addr, new.to_le_bytes().to_vec()));
+        }
+        Ok(old)
+    }
+
+    fn compare_exchange_word(
+        &mut self,
+        addr: u32,
+        current: Word,
+        new: Word,
+    ) -> Result<Result<Word, Word>, MemoryError> {
+        let old = self.read(
+            addr,
+            4,
+            |v| v as Word,
+            |bus| {
+                let result = bus.compare_exchange_word(addr, current, new)?;
+                Ok(result.unwrap_or_else(|actual| actual))
+            },
+        )?;
+        if old != current {
+            return Ok(Err(old));
+        }
+        if self.mode == Mode::Capture {
+            self.writes.push((addr, new.to_le_bytes().to_vec()));
+        }
+        Ok(Ok(old))
+    }
+
+    fn fence(&mut self, pred: FenceSet, succ: FenceSet) {
+        self.wrapped.fence(pred, succ)
+    }
+}
+
+impl<Wrapped: Bus<u32> + SnapshotMemory> SnapshotMemory for DeviceLog<Wrapped> {
+    fn snapshot_memory(&self) -> MemorySnapshot {
+        self.wrapped.snapshot_memory()
+    }
+
+    fn restore_memory(&mut self, snapshot: &MemorySnapshot) -> Result<(), MemoryError> {
+        self.wrapped.restore_memory(snapshot)
+    }
+}
+
+type ReplayHart<Inner> = SingleThreadUserHart<RV32, DeviceLog<Inner>>;
+
+/// Executes up to `max_steps` instructions on the given RV32 hart while
+/// recording all of the inputs that come from outside of the guest program,
+/// so that the run can be reproduced exactly by `Replay`.
+///
+/// Each status from the executor other than `ExecStatus::Running` is passed
+/// to `handler` along with the hart, so that the caller can emulate the
+/// execution environment. Whatever the handler does to the hart's registers
+/// and memory is recorded, and it returns `false` to end the recording.
+///
+/// The executor doesn't yet deliver interrupts itself, so the handler is
+/// also the place to raise them, such as in response to
+/// `ExecStatus::WaitingForInterrupt`. Because its effects are recorded
+/// against the step that produced the status, they are replayed at exactly
+/// the same point in the program.
+pub fn record_rv32<Inner, F>(
+    hart: &mut ReplayHart<Inner>,
+    max_steps: u64,
+    mut handler: F,
+) -> Recording
+where
+    Inner: Bus<u32> + SnapshotMemory,
+    F: FnMut(&mut ReplayHart<Inner>, ExecStatus<u32>) -> bool,
+{
+    let start = hart.snapshot();
+    let memory = hart.with_memory(|mem| {
+        mem.mode = Mode::Record;
+        mem.reads.clear();
+        mem.snapshot_memory()
+    });
+    let mut events = Vec::new();
+    let mut steps = 0;
+    while steps < max_steps {
+        let status = step_rv32(hart);
+        steps += 1;
+        if let ExecStatus::Running = status {
+            continue;
+        }
+        hart.with_memory(|mem| mem.mode = Mode::Capture);
+        let keep_going = handler(hart, status);
+        let writes = hart.with_memory(|mem| {
+            mem.mode = Mode::Record;
+            mem::take(&mut mem.writes)
+        });
+        events.push(EnvironmentEvent {
+            step: steps - 1,
+            hart: hart.snapshot(),
+            writes,
+        });
+        if !keep_going {
+            break;
+        }
+    }
+    Recording {
+        start,
+        memory,
+        device_reads: hart.with_memory(|mem| mem::take(&mut mem.reads)),
+        events,
+        steps,
+    }
+}
+
+struct Checkpoint {
+    position: u64,
+    hart: HartSnapshot<u32, f64>,
+    memory: MemorySnapshot,
+    cursor: usize,
+    next_event: usize,
+}
+
+/// Replays a `Recording` deterministically, allowing the run to be stepped
+/// both forwards and backwards.
+///
+/// Stepping forwards executes instructions as normal, except that device
+/// reads return their recorded values and the recorded effects of the
+/// execution environment are applied in place of calling a handler.
+/// Stepping backwards restores the nearest checkpoint at or before the
+/// target position and then executes forwards from there. Checkpoints are
+/// taken automatically every `interval` steps, trading memory for the speed
+/// of reverse stepping.
+pub struct Replay<Inner: Bus<u32> + SnapshotMemory> {
+    hart: ReplayHart<Inner>,
+    recording: Recording,
+    interval: u64,
+    position: u64,
+    next_event: usize,
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl<Inner: Bus<u32> + SnapshotMemory> Replay<Inner> {
+    /// Prepares to replay the given recording on the given hart, whose
+    /// memory is first restored to the recording's initial contents and
+    /// should have the same device ranges as when it was recorded.
+    pub fn new(
+        mut hart: ReplayHart<Inner>,
+        recording: Recording,
+        interval: u64,
+    ) -> Result<Self, MemoryError> {
+        hart.restore(&recording.start);
+        hart.with_memory(|mem| {
+            mem.mode = Mode::Replay;
+            mem.reads = recording.device_reads.clone();
+            mem.cursor = 0;
+            mem.restore_memory(&recording.memory)
+        })?;
+        let checkpoint = Checkpoint {
+            position: 0,
+            hart: recording.start,
+            memory: recording.memory.clone(),
+            cursor: 0,
+            next_event: 0,
+        };
+        Ok(Self {
+            hart,
+            recording,
+            interval: interval.max(1),
+            position: 0,
+            next_event: 0,
+            checkpoints: vec![checkpoint],
+        })
+    }
+
+    /// Returns the number of steps executed since the start of the
+    /// recording.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the total number of steps in the recording.
+    pub fn len(&self) -> u64 {
+        self.recording.steps
+    }
+
+    /// Returns true if the recording has no steps at all.
+    pub fn is_empty(&self) -> bool {
+        self.recording.steps == 0
+    }
+
+    /// Borrows the hart, so that its state can be inspected.
+    pub fn hart(&self) -> &ReplayHart<Inner> {
+        &self.hart
+    }
+
+    /// Consumes the replay and returns the hart in its current state.
+    pub fn into_hart(self) -> ReplayHart<Inner> {
+        self.hart
+    }
+
+    /// Executes the next recorded step, returning the status it produced,
+    /// or `None` if the end of the recording has been reached.
+    pub fn step(&mut self) -> Option<ExecStatus<u32>> {
+        if self.position >= self.recording.steps {
+            return None;
+        }
+        let status = step_rv32(&mut self.hart);
+        if let Some(event) = self.recording.events.get(self.next_event) {
+            if event.step == self.position {
+                self.hart.restore(&event.hart);
+                self.hart.with_memory(|mem| {
+                    for (addr, bytes) in &event.writes {
+                        for (i, b) in bytes.iter().enumerate() {
+                            // These writes succeeded when they were
+                            // recorded, against the same memory contents.
+                            let _ = mem.write_byte(addr.wrapping_add(i as u32), *b);
+                        }
+                    }
+                });
+                self.next_event += 1;
+            }
+        }
+        self.position += 1;
+        let last = self.checkpoints.last().map_or(0, |c| c.position);
+        if self.position.is_multiple_of(self.interval) && self.position > last {
+            let checkpoint = self.checkpoint();
+            self.checkpoints.push(checkpoint);
+        }
+        Some(status)
+    }
+
+    /// Moves back by one step, returning false if already at the start of
+    /// the recording.
+    pub fn step_back(&mut self) -> Result<bool, MemoryError> {
+        if self.position == 0 {
+            return Ok(false);
+        }
+        self.seek(self.position - 1)?;
+        Ok(true)
+    }
+
+    /// Moves to the given position, which is clamped to the length of the
+    /// recording.
+    pub fn seek(&mut self, position: u64) -> Result<(), MemoryError> {
+        let position = position.min(self.recording.steps);
+        let nearest = self
+            .checkpoints
+            .iter()
+            .rev()
+            .find(|c| c.position <= position)
+            .expect("there is always a checkpoint at the start");
+        if position < self.position || nearest.position > self.position {
+            let hart = nearest.hart;
+            let cursor = nearest.cursor;
+            let (from, next_event) = (nearest.position, nearest.next_event);
+            self.hart.restore(&hart);
+            let memory = &nearest.memory;
+            self.hart.with_memory(|mem| {
+                mem.cursor = cursor;
+                mem.restore_memory(memory)
+            })?;
+            self.position = from;
+            self.next_event = next_event;
+        }
+        while self.position < position {
+            self.step();
+        }
+        Ok(())
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        let (memory, cursor) = self
+            .hart
+            .with_memory(|mem| (mem.snapshot_memory(), mem.cursor));
+        Checkpoint {
+            position: self.position,
+            hart: self.hart.snapshot(),
+            memory,
+            cursor,
+            next_event: self.next_event,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{record_rv32, DeviceLog, Replay};
+    use crate::exec::ExecStatus;
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::RV32;
+    use crate::memory::{AddressConverter, Bus, SparseMemory};
+    use crate::register::{IntRegister, VectorRegister};
+    use std::vec;
+    use std::vec::Vec;
+
+    #[test]
+    fn record_and_replay() {
+        let program: [u32; 7] = [
+            0x10002583, // lw   a1, 0x100(zero)
+            0x00b60633, // add  a2, a2, a1
+            0x00000073, // ecall
+            0x08002683, // lw   a3, 0x80(zero)
+            0x00d60633, // add  a2, a2, a3
+            0x00a60633, // add  a2, a2, a0
+            0xfe9ff06f, // j    0
+        ];
+        let new_hart = |device_value: u32| {
+            let mut mem = SparseMemory::new();
+            for (i, word) in program.iter().enumerate() {
+                mem.write_word(i * 4, *word).unwrap();
+            }
+            mem.write_word(0x100, device_value).unwrap();
+            let mut mem = DeviceLog::new(AddressConverter::new(mem));
+            mem.add_device(0x100..0x104);
+            let hart: SingleThreadUserHart<RV32, _> = SingleThreadUserHart::new(mem);
+            hart
+        };
+        let a2 = IntRegister::numbered(12);
+
+        // The device's value changes on its own, and the environment call
+        // returns a value that the program can't predict.
+        let mut hart = new_hart(3);
+        let mut counter = 0;
+        let recording = record_rv32(&mut hart, 21, |hart, status| {
+            assert!(matches!(status, ExecStatus::EnvironmentCall(8)));
+            counter += 7;
+            hart.write_int_register(IntRegister::numbered(10), counter);
+            hart.with_memory(|mem| {
+                mem.write_word(0x80, counter * 100).unwrap();
+                mem.wrapped().write_word(0x100, counter).unwrap();
+            });
+            true
+        });
+        assert_eq!(recording.steps, 21);
+        assert_eq!(recording.device_reads, [3, 7, 14]);
+        assert_eq!(recording.events.len(), 3);
+        assert_eq!(recording.events[0].writes, [(0x80, vec![0xbc, 0x02, 0, 0])]);
+        let expected = hart.read_int_register(a2);
+        assert_eq!(expected, 3 + 707 + 7 + 1414 + 14 + 2121);
+
+        // Replaying against a device in a different state, and without an
+        // environment to call, gives exactly the same results.
+        let mut replay = Replay::new(new_hart(99), recording, 4).unwrap();
+        let mut history = Vec::new();
+        while replay.step().is_some() {
+            history.push(replay.hart().read_int_register(a2));
+        }
+        assert_eq!(replay.position(), 21);
+        assert_eq!(history.last(), Some(&expected));
+
+        replay.seek(10).unwrap();
+        assert_eq!(replay.hart().read_int_register(a2), history[9]);
+        assert!(replay.step_back().unwrap());
+        assert_eq!(replay.position(), 9);
+        assert_eq!(replay.hart().read_int_register(a2), history[8]);
+        assert_eq!(replay.hart().read_pc(), 8);
+        replay.seek(0).unwrap();
+        assert!(!replay.step_back().unwrap());
+        assert_eq!(replay.hart().read_int_register(a2), 0);
+        replay.seek(100).unwrap();
+        assert_eq!(replay.hart().read_int_register(a2), expected);
+        assert!(replay.step().is_none());
+    }
+
+    #[test]
+    fn checkpoint_inside_reservation() {
+        let program: [u32; 5] = [
+            0x10000513, // li   a0, 0x100
+            0x00500693, // li   a3, 5
+            0x100525af, // lr.w a1, (a0)
+            0x18d5262f, // sc.w a2, a3, (a0)
+            0x0000006f, // j    .
+        ];
+        let new_hart = || {
+            let mut mem = SparseMemory::new();
+            for (i, word) in program.iter().enumerate() {
+                mem.write_word(i * 4, *word).unwrap();
+            }
+            mem.write_word(0x100, 9).unwrap();
+            let hart: SingleThreadUserHart<RV32, _> =
+                SingleThreadUserHart::new(DeviceLog::new(AddressConverter::new(mem)));
+            hart
+        };
+        let a2 = IntRegister::numbered(12);
+
+        let mut hart = new_hart();
+        let recording = record_rv32(&mut hart, 5, |_, status| panic!("unexpected {:?}", status));
+        assert_eq!(hart.read_int_register(a2), 0, "sc.w succeeded");
+
+        // The checkpoint after the third step falls between the lr.w and the
+        // sc.w, so stepping back across the sc.w restores the reservation.
+        let mut replay = Replay::new(new_hart(), recording, 3).unwrap();
+        replay.seek(4).unwrap();
+        assert_eq!(replay.hart().read_int_register(a2), 0);
+        assert!(replay.step_back().unwrap());
+        replay.step();
+        assert_eq!(replay.hart().read_int_register(a2), 0, "sc.w succeeded");
+    }
+
+    #[test]
+    fn step_back_across_vector_instructions() {
+        let program: [u32; 5] = [
+            0xc10272d7, // vsetivli t0, 4, e32, m1, tu, mu
+            0x021101d7, // vadd.vv  v3, v1, v2
+            0xc08172d7, // vsetivli t0, 2, e16, m1, tu, mu
+            0x023181d7, // vadd.vv  v3, v3, v3
+            0x0000006f, // j        .
+        ];
+        let new_hart = || {
+            let mut mem = SparseMemory::new();
+            for (i, word) in program.iter().enumerate() {
+                mem.write_word(i * 4, *word).unwrap();
+            }
+            let mut hart: SingleThreadUserHart<RV32, _> =
+                SingleThreadUserHart::new(DeviceLog::new(AddressConverter::new(mem)));
+            let vector = hart.vector_registers().unwrap();
+            for i in 0..4 {
+                vector.set_element(VectorRegister::numbered(1), i, 32, i as u64 + 1);
+                vector.set_element(VectorRegister::numbered(2), i, 32, (i as u64 + 1) * 10);
+            }
+            hart
+        };
+        // The vector state as the hart's vl, vtype and the first 64 bits of
+        // v3.
+        let vector_state = |replay: &Replay<_>| {
+            let vector = replay.hart().snapshot().vector;
+            (vector.vl, vector.vtype, vector.registers[3][0])
+        };
+
+        let mut hart = new_hart();
+        let recording = record_rv32(&mut hart, 5, |_, status| panic!("unexpected {:?}", status));
+
+        // With a checkpoint every other step, stepping back from the end
+        // restores the vector state from both checkpoints.
+        let mut replay = Replay::new(new_hart(), recording, 2).unwrap();
+        replay.seek(4).unwrap();
+        assert_eq!(vector_state(&replay), (2, 0b001_000, 22 | 22 << 32));
+        assert!(replay.step_back().unwrap());
+        assert_eq!(vector_state(&replay), (2, 0b001_000, 11 | 22 << 32));
+        assert!(replay.step_back().unwrap());
+        assert_eq!(vector_state(&replay), (4, 0b010_000, 11 | 22 << 32));
+        assert!(replay.step_back().unwrap());
+        assert_eq!(vector_state(&replay), (4, 0b010_000, 0));
+        assert!(replay.step_back().unwrap());
+        assert_eq!(vector_state(&replay), (0, 0, 0));
+        assert!(replay.hart().snapshot().vector.vill);
+    }
+}
@@ -0,0 +1,111 @@
+mod semihosting_32;
+
+use crate::linux::{default_clock, FileSystem, Timespec};
+
+/// Represents the outcome of `Semihosting` handling an environment break.
+#[derive(Debug, PartialEq)]
+pub enum SemihostingStatus {
+    /// The semihosting request was handled and its result written to the
+    /// hart's registers, so execution can continue.
+    Handled,
+
+    /// The guest program requested termination with the given exit status,
+    /// via either `SYS_EXIT` or `SYS_EXIT_EXTENDED`.
+    Exited(i32),
+
+    /// The operation number given in `a0` is not supported. The result
+    /// register has been set to -1, so the caller may either continue
+    /// execution or stop.
+    Unsupported(u32),
+
+    /// The `ebreak` instruction is not part of the semihosting sequence, and
+    /// so should be treated as an ordinary breakpoint. The hart's state has
+    /// not been modified.
+    NotSemihosting,
+}
+
+/// The memory layout details reported to the guest by `SYS_HEAPINFO`, which
+/// the C runtime startup code typically uses to initialize its heap and stack.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HeapInfo {
+    pub heap_base: u64,
+    pub heap_limit: u64,
+    pub stack_base: u64,
+    pub stack_limit: u64,
+}
+
+/// Implements the RISC-V semihosting interface, which allows a bare-metal
+/// guest program to request services such as console and file I/O from its
+/// host, servicing the `ebreak` instructions reported by an executor as
+/// `ExecStatus::EnvironmentBreak`.
+///
+/// A semihosting request is an `ebreak` surrounded by the "magic" sequence
+/// `slli x0, x0, 0x1f` and `srai x0, x0, 7`, with the operation number in
+/// `a0` and its parameter (usually the address of a parameter block) in
+/// `a1`. The operations and their numbering are inherited from the ARM
+/// semihosting specification.
+///
+/// File operations are delegated to an implementation of `FileSystem`, the
+/// same trait used by `linux::SyscallEmulator`. Semihosting file handles are
+/// the file descriptors that the filesystem returns, and the special path
+/// `:tt` refers to descriptors 0, 1 and 2 depending on the open mode.
+pub struct Semihosting<'a, FS: FileSystem> {
+    fs: FS,
+    cmdline: &'a [u8],
+    heap_info: HeapInfo,
+    clock: fn() -> Timespec,
+    start: Timespec,
+    errno: i32,
+}
+
+impl<'a, FS: FileSystem> Semihosting<'a, FS> {
+    /// Creates a semihosting service that will use the given filesystem.
+    pub fn new(fs: FS) -> Self {
+        Self {
+            fs,
+            cmdline: &[],
+            heap_info: HeapInfo::default(),
+            clock: default_clock,
+            start: default_clock(),
+            errno: 0,
+        }
+    }
+
+    /// Sets the command line returned by `SYS_GET_CMDLINE`, which defaults
+    /// to an empty string.
+    pub fn set_command_line(&mut self, cmdline: &'a [u8]) {
+        self.cmdline = cmdline;
+    }
+
+    /// Sets the memory layout returned by `SYS_HEAPINFO`, which defaults to
+    /// all zeros to indicate that the guest should use its own defaults.
+    pub fn set_heap_info(&mut self, info: HeapInfo) {
+        self.heap_info = info;
+    }
+
+    /// Replaces the function used to answer `SYS_CLOCK`, `SYS_TIME` and
+    /// `SYS_ELAPSED`, and restarts the execution clock. The default clock
+    /// uses the host system time when the `std` feature is enabled, or
+    /// always returns zero otherwise.
+    pub fn set_clock(&mut self, clock: fn() -> Timespec) {
+        self.clock = clock;
+        self.start = clock();
+    }
+
+    /// Borrows the filesystem the semihosting service is using.
+    pub fn file_system(&mut self) -> &mut FS {
+        &mut self.fs
+    }
+
+    // Returns the time elapsed since the execution clock started.
+    fn elapsed(&self) -> Timespec {
+        let now = (self.clock)();
+        let mut sec = now.sec - self.start.sec;
+        let mut nsec = now.nsec - self.start.nsec;
+        if nsec < 0 {
+            sec -= 1;
+            nsec += 1_000_000_000;
+        }
+        Timespec { sec, nsec }
+    }
+}
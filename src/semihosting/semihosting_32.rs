@@ -0,0 +1,418 @@
+use super::{Semihosting, SemihostingStatus};
+use crate::hart::Hart;
+use crate::linux::open_flags::*;
+use crate::linux::{Errno, FileSystem, SeekFrom};
+use crate::memory::{Bus, MemoryError};
+use crate::register::IntRegister;
+
+// Semihosting operation numbers, from the ARM semihosting specification.
+const SYS_OPEN: u32 = 0x01;
+const SYS_CLOSE: u32 = 0x02;
+const SYS_WRITEC: u32 = 0x03;
+const SYS_WRITE0: u32 = 0x04;
+const SYS_WRITE: u32 = 0x05;
+const SYS_READ: u32 = 0x06;
+const SYS_READC: u32 = 0x07;
+const SYS_ISERROR: u32 = 0x08;
+const SYS_ISTTY: u32 = 0x09;
+const SYS_SEEK: u32 = 0x0a;
+const SYS_FLEN: u32 = 0x0c;
+const SYS_CLOCK: u32 = 0x10;
+const SYS_TIME: u32 = 0x11;
+const SYS_ERRNO: u32 = 0x13;
+const SYS_GET_CMDLINE: u32 = 0x15;
+const SYS_HEAPINFO: u32 = 0x16;
+const SYS_EXIT: u32 = 0x18;
+const SYS_EXIT_EXTENDED: u32 = 0x20;
+const SYS_ELAPSED: u32 = 0x30;
+const SYS_TICKFREQ: u32 = 0x31;
+
+// The SYS_EXIT reason code for a normal application exit. All other
+// reasons are treated as a failure.
+const ADP_STOPPED_APPLICATION_EXIT: u32 = 0x20026;
+
+// The instructions that make up the semihosting sequence.
+const SEQ_SLLI: u32 = 0x01f01013; // slli x0, x0, 0x1f
+const SEQ_EBREAK: u32 = 0x00100073; // ebreak
+const SEQ_SRAI: u32 = 0x40705013; // srai x0, x0, 7
+
+// The open flags for each pair of SYS_OPEN modes. Each mode has a text and
+// a binary variant, such as "r" and "rb", which we treat the same.
+const OPEN_MODES: [u32; 6] = [
+    O_RDONLY,                      // "r"
+    O_RDWR,                        // "r+"
+    O_WRONLY | O_CREAT | O_TRUNC,  // "w"
+    O_RDWR | O_CREAT | O_TRUNC,    // "w+"
+    O_WRONLY | O_CREAT | O_APPEND, // "a"
+    O_RDWR | O_CREAT | O_APPEND,   // "a+"
+];
+
+// The frequency of the tick counter reported by SYS_ELAPSED.
+const TICK_FREQ: u32 = 1_000_000;
+
+const PATH_MAX: usize = 4096;
+const CHUNK_SIZE: usize = 512;
+
+type Result<T> = core::result::Result<T, Errno>;
+
+impl<'a, FS: FileSystem> Semihosting<'a, FS> {
+    /// Services a semihosting request from an RV32 hart that has just
+    /// executed an `ebreak` at the given address, as reported by
+    /// `ExecStatus::EnvironmentBreak`.
+    ///
+    /// If the `ebreak` is not surrounded by the semihosting sequence then
+    /// the result is `SemihostingStatus::NotSemihosting` and the hart is left
+    /// unchanged. Otherwise the operation number is taken from `a0` and its
+    /// parameter from `a1`, and the result is written to `a0`. Execution
+    /// continues from the hart's current program counter, which skips the
+    /// remainder of the sequence harmlessly.
+    pub fn handle_rv32<Mem: Bus<u32>>(
+        &mut self,
+        hart: &mut impl Hart<u32, u32, f64, Mem>,
+        addr: u32,
+    ) -> SemihostingStatus {
+        if !hart.with_memory(|mem| is_semihosting_sequence(mem, addr)) {
+            return SemihostingStatus::NotSemihosting;
+        }
+
+        let op = hart.read_int_register(IntRegister::numbered(10));
+        let param = hart.read_int_register(IntRegister::numbered(11));
+        let result = match op {
+            SYS_OPEN => hart.with_memory(|mem| self.sys_open(mem, param)),
+            SYS_CLOSE => hart.with_memory(|mem| {
+                let [fd] = read_params(mem, param)?;
+                self.fs.close(fd as i32).map(|_| 0)
+            }),
+            SYS_WRITEC => hart.with_memory(|mem| {
+                let c = mem.read_byte(param).map_err(fault)?;
+                self.fs.write(1, &[c]).map(|_| 0)
+            }),
+            SYS_WRITE0 => hart.with_memory(|mem| self.sys_write0(mem, param)),
+            SYS_WRITE => hart.with_memory(|mem| self.sys_write(mem, param)),
+            SYS_READ => hart.with_memory(|mem| self.sys_read(mem, param)),
+            SYS_READC => self.sys_readc(),
+            SYS_ISERROR => hart.with_memory(|mem| {
+                let [status] = read_params(mem, param)?;
+                Ok(((status as i32) < 0) as u32)
+            }),
+            SYS_ISTTY => hart.with_memory(|mem| {
+                let [fd] = read_params(mem, param)?;
+                Ok(self.fs.is_terminal(fd as i32) as u32)
+            }),
+            SYS_SEEK => hart.with_memory(|mem| {
+                let [fd, pos] = read_params(mem, param)?;
+                self.fs
+                    .seek(fd as i32, pos as i64, SeekFrom::Start)
+                    .map(|_| 0)
+            }),
+            SYS_FLEN => hart.with_memory(|mem| {
+                let [fd] = read_params(mem, param)?;
+                self.fs.stat(fd as i32).map(|stat| stat.size as u32)
+            }),
+            SYS_CLOCK => {
+                let t = self.elapsed();
+                Ok((t.sec * 100 + t.nsec / 10_000_000) as u32)
+            }
+            SYS_TIME => Ok((self.clock)().sec as u32),
+            SYS_ERRNO => Ok(self.errno as u32),
+            SYS_GET_CMDLINE => hart.with_memory(|mem| self.sys_get_cmdline(mem, param)),
+            SYS_HEAPINFO => hart.with_memory(|mem| self.sys_heapinfo(mem, param)),
+            SYS_EXIT => {
+                // On 32-bit targets the parameter is the reason code itself,
+                // so there's no way to report a specific exit status.
+                let status = if param == ADP_STOPPED_APPLICATION_EXIT {
+                    0
+                } else {
+                    1
+                };
+                return SemihostingStatus::Exited(status);
+            }
+            SYS_EXIT_EXTENDED => match hart.with_memory(|mem| read_params(mem, param)) {
+                Ok([reason, subcode]) => {
+                    let status = if reason == ADP_STOPPED_APPLICATION_EXIT {
+                        subcode as i32
+                    } else {
+                        1
+                    };
+                    return SemihostingStatus::Exited(status);
+                }
+                Err(e) => Err(e),
+            },
+            SYS_ELAPSED => hart.with_memory(|mem| {
+                let t = self.elapsed();
+                let ticks = t.sec as u64 * TICK_FREQ as u64 + t.nsec as u64 / 1000;
+                write_guest(mem, param, &ticks.to_le_bytes())?;
+                Ok(0)
+            }),
+            SYS_TICKFREQ => Ok(TICK_FREQ),
+            _ => {
+                hart.write_int_register(IntRegister::numbered(10), u32::MAX);
+                return SemihostingStatus::Unsupported(op);
+            }
+        };
+
+        let v = match result {
+            Ok(v) => v,
+            Err(Errno(e)) => {
+                self.errno = e;
+                u32::MAX
+            }
+        };
+        hart.write_int_register(IntRegister::numbered(10), v);
+        SemihostingStatus::Handled
+    }
+
+    fn sys_open<Mem: Bus<u32>>(&mut self, mem: &mut Mem, param: u32) -> Result<u32> {
+        let [name, mode, len] = read_params(mem, param)?;
+        if mode as usize >= OPEN_MODES.len() * 2 {
+            return Err(Errno::EINVAL);
+        }
+        if len as usize > PATH_MAX {
+            return Err(Errno::ENAMETOOLONG);
+        }
+        let mut buf = [0u8; PATH_MAX];
+        let path = &mut buf[..len as usize];
+        read_guest(mem, name, path)?;
+
+        // The special path ":tt" refers to the console, with the mode
+        // selecting between its input, output and error streams.
+        if path == b":tt" {
+            return Ok(match mode {
+                0..=3 => 0,
+                4..=7 => 1,
+                _ => 2,
+            });
+        }
+        let flags = OPEN_MODES[mode as usize / 2];
+        self.fs.open(path, flags, 0o644).map(|fd| fd as u32)
+    }
+
+    fn sys_write0<Mem: Bus<u32>>(&mut self, mem: &mut Mem, mut addr: u32) -> Result<u32> {
+        let mut chunk = [0u8; CHUNK_SIZE];
+        loop {
+            let mut n = 0;
+            while n < CHUNK_SIZE {
+                let b = mem.read_byte(addr).map_err(fault)?;
+                if b == 0 {
+                    break;
+                }
+                chunk[n] = b;
+                n += 1;
+                addr = addr.wrapping_add(1);
+            }
+            self.fs.write(1, &chunk[..n])?;
+            if n < CHUNK_SIZE {
+                return Ok(0);
+            }
+        }
+    }
+
+    // SYS_WRITE and SYS_READ return the number of bytes _not_ transferred,
+    // so an error after a partial transfer is reported only through
+    // SYS_ERRNO.
+    fn sys_write<Mem: Bus<u32>>(&mut self, mem: &mut Mem, param: u32) -> Result<u32> {
+        let [fd, buf, count] = read_params(mem, param)?;
+        let mut chunk = [0u8; CHUNK_SIZE];
+        let mut total: u32 = 0;
+        while total < count {
+            let want = core::cmp::min((count - total) as usize, CHUNK_SIZE);
+            read_guest(mem, buf.wrapping_add(total), &mut chunk[..want])?;
+            let n = match self.fs.write(fd as i32, &chunk[..want]) {
+                Ok(n) => n,
+                Err(Errno(e)) => {
+                    self.errno = e;
+                    break;
+                }
+            };
+            total += n as u32;
+            if n < want {
+                break;
+            }
+        }
+        Ok(count - total)
+    }
+
+    fn sys_read<Mem: Bus<u32>>(&mut self, mem: &mut Mem, param: u32) -> Result<u32> {
+        let [fd, buf, count] = read_params(mem, param)?;
+        let mut chunk = [0u8; CHUNK_SIZE];
+        let mut total: u32 = 0;
+        while total < count {
+            let want = core::cmp::min((count - total) as usize, CHUNK_SIZE);
+            let n = match self.fs.read(fd as i32, &mut chunk[..want]) {
+                Ok(n) => n,
+                Err(Errno(e)) => {
+                    self.errno = e;
+                    break;
+                }
+            };
+            write_guest(mem, buf.wrapping_add(total), &chunk[..n])?;
+            total += n as u32;
+            if n < want {
+                break;
+            }
+        }
+        Ok(count - total)
+    }
+
+    fn sys_readc(&mut self) -> Result<u32> {
+        let mut c = [0u8; 1];
+        match self.fs.read(0, &mut c)? {
+            0 => Err(Errno::EIO),
+            _ => Ok(c[0] as u32),
+        }
+    }
+
+    fn sys_get_cmdline<Mem: Bus<u32>>(&mut self, mem: &mut Mem, param: u32) -> Result<u32> {
+        let [buf, size] = read_params(mem, param)?;
+        let len = self.cmdline.len() as u32;
+        // The buffer must have room for the terminating NUL.
+        if len >= size {
+            return Err(Errno::EINVAL);
+        }
+        write_guest(mem, buf, self.cmdline)?;
+        mem.write_byte(buf.wrapping_add(len), 0).map_err(fault)?;
+        mem.write_word(param.wrapping_add(4), len).map_err(fault)?;
+        Ok(0)
+    }
+
+    fn sys_heapinfo<Mem: Bus<u32>>(&mut self, mem: &mut Mem, param: u32) -> Result<u32> {
+        // The parameter is the address of a pointer to the block to fill.
+        let block = mem.read_word(param).map_err(fault)?;
+        let info = self.heap_info;
+        let fields = [
+            info.heap_base,
+            info.heap_limit,
+            info.stack_base,
+            info.stack_limit,
+        ];
+        for (i, v) in fields.iter().enumerate() {
+            mem.write_word(block.wrapping_add(i as u32 * 4), *v as u32)
+                .map_err(fault)?;
+        }
+        Ok(0)
+    }
+}
+
+fn is_semihosting_sequence<Mem: Bus<u32>>(mem: &mut Mem, addr: u32) -> bool {
+    let word = |mem: &mut Mem, a: u32| mem.read_word(a).ok();
+    word(mem, addr.wrapping_sub(4)) == Some(SEQ_SLLI)
+        && word(mem, addr) == Some(SEQ_EBREAK)
+        && word(mem, addr.wrapping_add(4)) == Some(SEQ_SRAI)
+}
+
+// Reads the given number of words from a semihosting parameter block.
+fn read_params<Mem: Bus<u32>, const N: usize>(mem: &mut Mem, addr: u32) -> Result<[u32; N]> {
+    let mut params = [0u32; N];
+    for (i, p) in params.iter_mut().enumerate() {
+        *p = mem
+            .read_word(addr.wrapping_add(i as u32 * 4))
+            .map_err(fault)?;
+    }
+    Ok(params)
+}
+
+fn read_guest<Mem: Bus<u32>>(mem: &mut Mem, addr: u32, buf: &mut [u8]) -> Result<()> {
+    for (i, b) in buf.iter_mut().enumerate() {
+        *b = mem.read_byte(addr.wrapping_add(i as u32)).map_err(fault)?;
+    }
+    Ok(())
+}
+
+fn write_guest<Mem: Bus<u32>>(mem: &mut Mem, addr: u32, buf: &[u8]) -> Result<()> {
+    for (i, b) in buf.iter().enumerate() {
+        mem.write_byte(addr.wrapping_add(i as u32), *b)
+            .map_err(fault)?;
+    }
+    Ok(())
+}
+
+fn fault(_: MemoryError) -> Errno {
+    Errno::EFAULT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Semihosting, SemihostingStatus};
+    use crate::exec::{step_rv32, ExecStatus};
+    use crate::hart::{Hart, SingleThreadUserHart};
+    use crate::isa::RV32;
+    use crate::linux::tests::CaptureFileSystem;
+    use crate::memory::{AddressConverter, Memory};
+    use crate::register::IntRegister;
+
+    #[test]
+    fn write_and_exit() {
+        let mut mem_buf = [0u8; 1024];
+        let program: [u32; 22] = [
+            0x00400513, // li a0, 4 (SYS_WRITE0)
+            0x10000593, // li a1, 0x100
+            0x01f01013, // slli x0, x0, 0x1f
+            0x00100073, // ebreak
+            0x40705013, // srai x0, x0, 7
+            0x00500513, // li a0, 5 (SYS_WRITE)
+            0x12000593, // li a1, 0x120
+            0x01f01013, // slli x0, x0, 0x1f
+            0x00100073, // ebreak
+            0x40705013, // srai x0, x0, 7
+            0x00100073, // ebreak (not semihosting)
+            0x00100513, // li a0, 1 (SYS_OPEN)
+            0x13000593, // li a1, 0x130
+            0x01f01013, // slli x0, x0, 0x1f
+            0x00100073, // ebreak
+            0x40705013, // srai x0, x0, 7
+            0x01800513, // li a0, 0x18 (SYS_EXIT)
+            0x000205b7, // lui a1, 0x20
+            0x02658593, // addi a1, a1, 0x26
+            0x01f01013, // slli x0, x0, 0x1f
+            0x00100073, // ebreak
+            0x40705013, // srai x0, x0, 7
+        ];
+        for (i, word) in program.iter().enumerate() {
+            mem_buf[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+        }
+        mem_buf[0x100..0x107].copy_from_slice(b"hello \0");
+        // SYS_WRITE block: fd 1, buffer 0x110, length 6.
+        mem_buf[0x110..0x116].copy_from_slice(b"world\n");
+        mem_buf[0x120..0x124].copy_from_slice(&1u32.to_le_bytes());
+        mem_buf[0x124..0x128].copy_from_slice(&0x110u32.to_le_bytes());
+        mem_buf[0x128..0x12c].copy_from_slice(&6u32.to_le_bytes());
+        // SYS_OPEN block: name 0x140, mode "w", length 3.
+        mem_buf[0x130..0x134].copy_from_slice(&0x140u32.to_le_bytes());
+        mem_buf[0x134..0x138].copy_from_slice(&4u32.to_le_bytes());
+        mem_buf[0x138..0x13c].copy_from_slice(&3u32.to_le_bytes());
+        mem_buf[0x140..0x144].copy_from_slice(b":tt\0");
+
+        let mem = Memory::new_ram(&mut mem_buf);
+        let mut hart: SingleThreadUserHart<RV32, AddressConverter<u32, usize, Memory>> =
+            SingleThreadUserHart::new(AddressConverter::new(mem));
+        let fs = CaptureFileSystem::new();
+        let mut sh = Semihosting::new(fs);
+
+        let mut results = [0u32; 3];
+        let mut calls = 0;
+        let mut breakpoints = 0;
+        let exit_code = loop {
+            match step_rv32(&mut hart) {
+                ExecStatus::EnvironmentBreak(addr) => match sh.handle_rv32(&mut hart, addr) {
+                    SemihostingStatus::Handled => {
+                        results[calls] = hart.read_int_register(IntRegister::numbered(10));
+                        calls += 1;
+                    }
+                    SemihostingStatus::Exited(code) => break code,
+                    SemihostingStatus::NotSemihosting => breakpoints += 1,
+                    SemihostingStatus::Unsupported(op) => panic!("unsupported operation {}", op),
+                },
+                ExecStatus::Running => {}
+                status => panic!("unexpected status {:?}", status),
+            }
+        };
+
+        assert_eq!(exit_code, 0, "exit code");
+        assert_eq!(breakpoints, 1, "ordinary breakpoints");
+        assert_eq!(results[0], 0, "write0 result");
+        assert_eq!(results[1], 0, "write result (bytes not written)");
+        assert_eq!(results[2], 1, "open :tt for writing");
+        let fs = sh.file_system();
+        assert_eq!(fs.stdout(), b"hello world\n");
+    }
+}
@@ -0,0 +1,625 @@
+use crate::data::Byte;
+use crate::data::HalfwordUnsigned as Halfword;
+use crate::data::Int;
+use crate::data::LongwordUnsigned as Longword;
+use crate::data::QuadwordUnsigned as Quadword;
+use crate::data::WordUnsigned as Word;
+use crate::exception::ExceptionCause;
+use crate::exec::{step_rv32, ExecStatus};
+use crate::hart::{Hart, HartSnapshot, SingleThreadUserHart};
+use crate::isa::{BaseISA, Extensions, RV32};
+use crate::memory::{AtomicMemory, AtomicOp, Bus, FenceSet, MemoryError};
+use crate::register::{CSRError, ControlStatusRegister, FloatRegister, IntRegister};
+use crate::vector::VectorRegisters;
+
+use core::sync::atomic::{self, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::vec::Vec;
+
+/// A handle to a memory bus that is shared between several harts.
+///
+/// Cloning a `SharedBus` produces another handle to the same bus. Each
+/// access made through the `Bus` implementation locks the bus for just the
+/// duration of that access, while `lock` allows a caller to make several
+/// accesses without any other hart intervening.
+pub struct SharedBus<Mem> {
+    mem: Arc<Mutex<Mem>>,
+}
+
+impl<Mem> SharedBus<Mem> {
+    /// Consumes a bus and produces the first handle to share it.
+    pub fn new(mem: Mem) -> Self {
+        Self {
+            mem: Arc::new(Mutex::new(mem)),
+        }
+    }
+
+    /// Locks the bus for exclusive access until the returned guard is
+    /// dropped.
+    pub fn lock(&self) -> MutexGuard<'_, Mem> {
+        // A panic while the bus was locked can't leave the memory itself in
+        // an inconsistent state, so there's no reason to refuse access.
+        self.mem.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<Mem> Clone for SharedBus<Mem> {
+    fn clone(&self) -> Self {
+        Self {
+            mem: Arc::clone(&self.mem),
+        }
+    }
+}
+
+impl<Addr, Mem: Bus<Addr>> Bus<Addr> for SharedBus<Mem> {
+    fn read_byte(&mut self, addr: Addr) -> Result<Byte, MemoryError> {
+        self.lock().read_byte(addr)
+    }
+
+    fn write_byte(&mut self, addr: Addr, data: Byte) -> Result<(), MemoryError> {
+        self.lock().write_byte(addr, data)
+    }
+
+    fn read_halfword(&mut self, addr: Addr) -> Result<Halfword, MemoryError> {
+        self.lock().read_halfword(addr)
+    }
+
+    fn write_halfword(&mut self, addr: Addr, data: Halfword) -> Result<(), MemoryError> {
+        self.lock().write_halfword(addr, data)
+    }
+
+    fn read_word(&mut self, addr: Addr) -> Result<Word, MemoryError> {
+        self.lock().read_word(addr)
+    }
+
+    fn write_word(&mut self, addr: Addr, data: Word) -> Result<(), MemoryError> {
+        self.lock().write_word(addr, data)
+    }
+
+    fn read_longword(&mut self, addr: Addr) -> Result<Longword, MemoryError> {
+        self.lock().read_longword(addr)
+    }
+
+    fn write_longword(&mut self, addr: Addr, data: Longword) -> Result<(), MemoryError> {
+        self.lock().write_longword(addr, data)
+    }
+
+    fn read_quadword(&mut self, addr: Addr) -> Result<Quadword, MemoryError> {
+        self.lock().read_quadword(addr)
+    }
+
+    fn write_quadword(&mut self, addr: Addr, data: Quadword) -> Result<(), MemoryError> {
+        self.lock().write_quadword(addr, data)
+    }
+
+    fn fetch_halfword(&mut self, addr: Addr) -> Result<Halfword, MemoryError> {
+        self.lock().fetch_halfword(addr)
+    }
+
+    // The read-modify-write operations hold the lock throughout, so that
+    // they are atomic with respect to the other harts.
+
+    fn amo_word(&mut self, addr: Addr, op: AtomicOp, operand: Word) -> Result<Word, MemoryError>
+    where
+        Addr: Copy,
+    {
+        self.lock().amo_word(addr, op, operand)
+    }
+
+    fn compare_exchange_word(
+        &mut self,
+        addr: Addr,
+        current: Word,
+        new: Word,
+    ) -> Result<Result<Word, Word>, MemoryError>
+    where
+        Addr: Copy,
+    {
+        self.lock().compare_exchange_word(addr, current, new)
+    }
+
+    fn fence(&mut self, pred: FenceSet, succ: FenceSet) {
+        self.lock().fence(pred, succ)
+    }
+}
+
+/// An implementation of `Hart` representing one of several harts in a
+/// symmetric multiprocessing system, all sharing a single memory bus.
+///
+/// Apart from sharing its bus and reporting its own hart ID in the `mhartid`
+/// CSR, this behaves in the same way as `SingleThreadUserHart`. Its
+/// `with_memory` method locks the shared bus for the duration of the
+/// callback, so that the executor's memory accesses are each made while
+/// holding the lock.
+pub struct SmpHart<ISA, Mem>
+where
+    ISA: BaseISA,
+    Mem: Bus<<ISA::Int as Int>::Unsigned>,
+{
+    inner: SingleThreadUserHart<ISA, SharedBus<Mem>>,
+    hart_id: ISA::Int,
+}
+
+impl<ISA, Mem> SmpHart<ISA, Mem>
+where
+    ISA: BaseISA,
+    Mem: Bus<<ISA::Int as Int>::Unsigned>,
+{
+    /// Creates a hart with the given ID, connected to the given shared bus.
+    pub fn new(bus: SharedBus<Mem>, hart_id: u32) -> Self {
+        Self {
+            inner: SingleThreadUserHart::new(bus),
+            hart_id: ISA::Int::from_unsigned_word(hart_id),
+        }
+    }
+
+    /// Returns the hart's ID, as reported in its `mhartid` CSR.
+    pub fn hart_id(&self) -> ISA::Int {
+        self.hart_id
+    }
+
+    /// Selects whether exceptions are reported to the caller of the
+    /// executor. See `SingleThreadUserHart::set_report_exceptions`.
+    pub fn set_report_exceptions(&mut self, report: bool) {
+        self.inner.set_report_exceptions(report);
+    }
+
+    /// Returns another handle to the bus that this hart is connected to.
+    pub fn bus(&mut self) -> SharedBus<Mem> {
+        self.inner.with_memory(|bus| bus.clone())
+    }
+
+    /// Captures the hart's current register state.
+    pub fn snapshot(&self) -> HartSnapshot<ISA::Int, ISA::Float> {
+        self.inner.snapshot()
+    }
+
+    /// Replaces the hart's register state with a previously-captured
+    /// snapshot. The memory bus is not affected.
+    pub fn restore(&mut self, snapshot: &HartSnapshot<ISA::Int, ISA::Float>) {
+        self.inner.restore(snapshot)
+    }
+}
+
+impl<ISA, Mem> Hart<<ISA::Int as Int>::Unsigned, ISA::Int, ISA::Float, Mem> for SmpHart<ISA, Mem>
+where
+    ISA: BaseISA,
+    Mem: Bus<<ISA::Int as Int>::Unsigned>,
+{
+    fn read_pc(&self) -> <ISA::Int as Int>::Unsigned {
+        self.inner.read_pc()
+    }
+
+    fn write_pc(&mut self, v: <ISA::Int as Int>::Unsigned) {
+        self.inner.write_pc(v)
+    }
+
+    fn extensions(&self) -> Extensions {
+        self.inner.extensions()
+    }
+
+    fn read_int_register(&self, reg: IntRegister) -> ISA::Int {
+        self.inner.read_int_register(reg)
+    }
+
+    fn write_int_register(&mut self, reg: IntRegister, v: ISA::Int) {
+        self.inner.write_int_register(reg, v)
+    }
+
+    fn read_float_register(&self, reg: FloatRegister) -> ISA::Float {
+        self.inner.read_float_register(reg)
+    }
+
+    fn write_float_register(&mut self, reg: FloatRegister, v: ISA::Float) {
+        self.inner.write_float_register(reg, v)
+    }
+
+    fn read_csr(&self, reg: ControlStatusRegister) -> Result<ISA::Int, CSRError> {
+        match reg.num() {
+            0x0F14 => Ok(self.hart_id), // mhartid
+            _ => self.inner.read_csr(reg),
+        }
+    }
+
+    fn write_csr(&mut self, reg: ControlStatusRegister, v: ISA::Int) -> Result<(), CSRError> {
+        self.inner.write_csr(reg, v)
+    }
+
+    fn with_memory<R>(&mut self, f: impl FnOnce(&mut Mem) -> R) -> R {
+        self.inner.with_memory(|bus| f(&mut bus.lock()))
+    }
+
+    fn reset(&mut self, cause: ISA::Int) {
+        self.inner.reset(cause)
+    }
+
+    fn exception(&mut self, cause: ExceptionCause, tval: <ISA::Int as Int>::Unsigned) -> bool {
+        self.inner.exception(cause, tval)
+    }
+
+    fn fence_data(&mut self, pred: FenceSet, succ: FenceSet) {
+        self.inner.fence_data(pred, succ)
+    }
+
+    fn set_reservation(&mut self, reservation: Option<(<ISA::Int as Int>::Unsigned, ISA::Int)>) {
+        self.inner.set_reservation(reservation)
+    }
+
+    fn take_reservation(&mut self) -> Option<(<ISA::Int as Int>::Unsigned, ISA::Int)> {
+        self.inner.take_reservation()
+    }
+
+    fn vector_registers(&mut self) -> Option<&mut VectorRegisters> {
+        self.inner.vector_registers()
+    }
+}
+
+/// An implementation of `Hart` representing one of several harts in a
+/// symmetric multiprocessing system, which can each run on a separate host
+/// thread, sharing an `AtomicMemory`.
+///
+/// Unlike `SmpHart`, no lock is taken to access memory, so harts running
+/// on different threads make progress in parallel. Atomic memory operations
+/// and store-conditional instructions map onto host atomic operations, and
+/// data fences, including those implied by the `aq` and `rl` bits of atomic
+/// instructions, map onto host fences.
+pub struct ParallelHart<ISA>
+where
+    ISA: BaseISA,
+    AtomicMemory: Bus<<ISA::Int as Int>::Unsigned>,
+{
+    inner: SingleThreadUserHart<ISA, AtomicMemory>,
+    hart_id: ISA::Int,
+}
+
+impl<ISA> ParallelHart<ISA>
+where
+    ISA: BaseISA,
+    AtomicMemory: Bus<<ISA::Int as Int>::Unsigned>,
+{
+    /// Creates a hart with the given ID, connected to the given memory.
+    pub fn new(mem: AtomicMemory, hart_id: u32) -> Self {
+        Self {
+            inner: SingleThreadUserHart::new(mem),
+            hart_id: ISA::Int::from_unsigned_word(hart_id),
+        }
+    }
+
+    /// Returns the hart's ID, as reported in its `mhartid` CSR.
+    pub fn hart_id(&self) -> ISA::Int {
+        self.hart_id
+    }
+
+    /// Selects whether exceptions are reported to the caller of the
+    /// executor. See `SingleThreadUserHart::set_report_exceptions`.
+    pub fn set_report_exceptions(&mut self, report: bool) {
+        self.inner.set_report_exceptions(report);
+    }
+
+    /// Captures the hart's current register state.
+    pub fn snapshot(&self) -> HartSnapshot<ISA::Int, ISA::Float> {
+        self.inner.snapshot()
+    }
+
+    /// Replaces the hart's register state with a previously-captured
+    /// snapshot. The memory is not affected.
+    pub fn restore(&mut self, snapshot: &HartSnapshot<ISA::Int, ISA::Float>) {
+        self.inner.restore(snapshot)
+    }
+}
+
+impl<ISA> Hart<<ISA::Int as Int>::Unsigned, ISA::Int, ISA::Float, AtomicMemory>
+    for ParallelHart<ISA>
+where
+    ISA: BaseISA,
+    AtomicMemory: Bus<<ISA::Int as Int>::Unsigned>,
+{
+    fn read_pc(&self) -> <ISA::Int as Int>::Unsigned {
+        self.inner.read_pc()
+    }
+
+    fn write_pc(&mut self, v: <ISA::Int as Int>::Unsigned) {
+        self.inner.write_pc(v)
+    }
+
+    fn extensions(&self) -> Extensions {
+        self.inner.extensions()
+    }
+
+    fn read_int_register(&self, reg: IntRegister) -> ISA::Int {
+        self.inner.read_int_register(reg)
+    }
+
+    fn write_int_register(&mut self, reg: IntRegister, v: ISA::Int) {
+        self.inner.write_int_register(reg, v)
+    }
+
+    fn read_float_register(&self, reg: FloatRegister) -> ISA::Float {
+        self.inner.read_float_register(reg)
+    }
+
+    fn write_float_register(&mut self, reg: FloatRegister, v: ISA::Float) {
+        self.inner.write_float_register(reg, v)
+    }
+
+    fn read_csr(&self, reg: ControlStatusRegister) -> Result<ISA::Int, CSRError> {
+        match reg.num() {
+            0x0F14 => Ok(self.hart_id), // mhartid
+            _ => self.inner.read_csr(reg),
+        }
+    }
+
+    fn write_csr(&mut self, reg: ControlStatusRegister, v: ISA::Int) -> Result<(), CSRError> {
+        self.inner.write_csr(reg, v)
+    }
+
+    fn with_memory<R>(&mut self, f: impl FnOnce(&mut AtomicMemory) -> R) -> R {
+        self.inner.with_memory(f)
+    }
+
+    fn reset(&mut self, cause: ISA::Int) {
+        self.inner.reset(cause)
+    }
+
+    fn exception(&mut self, cause: ExceptionCause, tval: <ISA::Int as Int>::Unsigned) -> bool {
+        self.inner.exception(cause, tval)
+    }
+
+    fn fence_data(&mut self, _pred: FenceSet, _succ: FenceSet) {
+        atomic::fence(Ordering::SeqCst);
+    }
+
+    fn fence_code(&mut self) {
+        // Instructions are fetched through the same atomic memory as data,
+        // so ordering the accesses is all that's needed.
+        atomic::fence(Ordering::SeqCst);
+    }
+
+    fn set_reservation(&mut self, reservation: Option<(<ISA::Int as Int>::Unsigned, ISA::Int)>) {
+        self.inner.set_reservation(reservation)
+    }
+
+    fn take_reservation(&mut self) -> Option<(<ISA::Int as Int>::Unsigned, ISA::Int)> {
+        self.inner.take_reservation()
+    }
+
+    fn vector_registers(&mut self) -> Option<&mut VectorRegisters> {
+        self.inner.vector_registers()
+    }
+}
+
+/// Runs each of the given RV32 harts on its own host thread, until each has
+/// either executed `max_steps` instructions or been halted by `handler`.
+/// Returns the number of instructions each hart executed.
+///
+/// Each status from the executor other than `ExecStatus::Running` is passed
+/// to `handler` along with the index of the hart that produced it and the
+/// hart itself. The handler returns `false` to halt that hart. Because the
+/// handler is called from several threads at once, it can only share state
+/// between harts by way of synchronization such as atomics or a mutex.
+///
+/// Unlike with `Scheduler`, the way the harts' steps interleave depends on
+/// the host, so it differs from run to run.
+pub fn run_parallel_rv32<F>(
+    harts: &mut [ParallelHart<RV32>],
+    max_steps: u64,
+    handler: F,
+) -> Vec<u64>
+where
+    F: Fn(usize, &mut ParallelHart<RV32>, ExecStatus<u32>) -> bool + Sync,
+{
+    let handler = &handler;
+    thread::scope(|scope| {
+        let threads: Vec<_> = harts
+            .iter_mut()
+            .enumerate()
+            .map(|(index, hart)| {
+                scope.spawn(move || {
+                    let mut steps = 0;
+                    while steps < max_steps {
+                        let status = step_rv32(hart);
+                        steps += 1;
+                        if let ExecStatus::Running = status {
+                            continue;
+                        }
+                        if !handler(index, hart, status) {
+                            break;
+                        }
+                    }
+                    steps
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .map(|t| t.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    })
+}
+
+/// Runs several RV32 harts by interleaving their steps in a fixed
+/// round-robin order, so that a multi-hart program behaves the same way
+/// every time it runs.
+///
+/// Each hart in turn executes up to `quantum` instructions before the next
+/// hart gets a turn. A quantum of one interleaves the harts as finely as
+/// possible, while larger quanta approximate harts that run mostly
+/// independently.
+pub struct Scheduler<H> {
+    harts: Vec<H>,
+    halted: Vec<bool>,
+    quantum: u64,
+    next: usize,
+    used: u64,
+}
+
+impl<H> Scheduler<H> {
+    /// Creates a scheduler for the given harts, which take turns in the
+    /// order given.
+    pub fn new(harts: Vec<H>, quantum: u64) -> Self {
+        Self {
+            halted: harts.iter().map(|_| false).collect(),
+            harts,
+            quantum: quantum.max(1),
+            next: 0,
+            used: 0,
+        }
+    }
+
+    /// Borrows the harts being scheduled.
+    pub fn harts(&self) -> &[H] {
+        &self.harts
+    }
+
+    /// Mutably borrows the harts being scheduled.
+    pub fn harts_mut(&mut self) -> &mut [H] {
+        &mut self.harts
+    }
+
+    /// Consumes the scheduler and returns its harts.
+    pub fn into_harts(self) -> Vec<H> {
+        self.harts
+    }
+
+    /// Returns true if the hart at the given index will no longer be
+    /// scheduled.
+    pub fn is_halted(&self, index: usize) -> bool {
+        self.halted[index]
+    }
+
+    /// Returns true if all of the harts have halted.
+    pub fn all_halted(&self) -> bool {
+        self.halted.iter().all(|h| *h)
+    }
+
+    /// Executes up to `max_steps` instructions in total across all of the
+    /// harts, returning the number actually executed.
+    ///
+    /// Each status from the executor other than `ExecStatus::Running` is
+    /// passed to `handler` along with the index of the hart that produced it
+    /// and the hart itself. The handler returns `false` to halt that hart,
+    /// after which it is no longer scheduled. Running stops early once all
+    /// of the harts have halted.
+    ///
+    /// A later call continues the schedule from where the previous one
+    /// stopped.
+    pub fn run_rv32<Mem, F>(&mut self, max_steps: u64, mut handler: F) -> u64
+    where
+        Mem: Bus<u32>,
+        H: Hart<u32, u32, f64, Mem>,
+        F: FnMut(usize, &mut H, ExecStatus<u32>) -> bool,
+    {
+        let mut steps = 0;
+        while steps < max_steps && !self.all_halted() {
+            if self.halted[self.next] || self.used >= self.quantum {
+                self.next = (self.next + 1) % self.harts.len();
+                self.used = 0;
+                continue;
+            }
+            let hart = &mut self.harts[self.next];
+            let status = step_rv32(hart);
+            steps += 1;
+            self.used += 1;
+            if let ExecStatus::Running = status {
+                continue;
+            }
+            if !handler(self.next, hart, status) {
+                self.halted[self.next] = true;
+            }
+        }
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_parallel_rv32, ParallelHart, Scheduler, SharedBus, SmpHart};
+    use crate::exec::ExecStatus;
+    use crate::hart::Hart;
+    use crate::isa::RV32;
+    use crate::memory::{AddressConverter, AtomicMemory, Bus, SparseMemory};
+    use crate::register::IntRegister;
+    use std::vec::Vec;
+
+    #[test]
+    fn round_robin() {
+        let program: [u32; 8] = [
+            0xf1402573, // csrr    a0, mhartid
+            0x00251593, // slli    a1, a0, 2
+            0x00150613, // addi    a2, a0, 1
+            0x10c5a023, // sw      a2, 0x100(a1)
+            0x08002283, // lw      t0, 0x80(zero)
+            0x00128293, // addi    t0, t0, 1
+            0x08502023, // sw      t0, 0x80(zero)
+            0x00000073, // ecall
+        ];
+        let run = |quantum| {
+            let mut mem = AddressConverter::<u32, usize, _>::new(SparseMemory::new());
+            for (i, word) in program.iter().enumerate() {
+                mem.write_word(i as u32 * 4, *word).unwrap();
+            }
+            let bus = SharedBus::new(mem);
+            let harts: Vec<SmpHart<RV32, _>> =
+                (0..2).map(|id| SmpHart::new(bus.clone(), id)).collect();
+            let mut scheduler = Scheduler::new(harts, quantum);
+            let mut ecalls = Vec::new();
+            let steps = scheduler.run_rv32(100, |index, hart, status| {
+                assert!(matches!(status, ExecStatus::EnvironmentCall(0x1c)));
+                ecalls.push((index, hart.read_int_register(IntRegister::numbered(10))));
+                false
+            });
+            assert_eq!(steps, 16);
+            assert!(scheduler.all_halted());
+            let mut mem = bus.lock();
+            assert_eq!(mem.read_word(0x100).unwrap(), 1);
+            assert_eq!(mem.read_word(0x104).unwrap(), 2);
+            (ecalls, mem.read_word(0x80).unwrap())
+        };
+
+        // With the finest interleaving, both harts load the counter before
+        // either stores it, so one of the increments is lost. That happens
+        // the same way every time.
+        assert_eq!(run(1), ([(0, 0), (1, 1)].to_vec(), 1));
+        assert_eq!(run(1), ([(0, 0), (1, 1)].to_vec(), 1));
+
+        // With a long enough quantum, each hart runs to completion in turn.
+        assert_eq!(run(8), ([(0, 0), (1, 1)].to_vec(), 2));
+    }
+
+    #[test]
+    fn parallel_atomics() {
+        let program: [u32; 13] = [
+            0x10000513, // li      a0, 0x100
+            0x10400593, // li      a1, 0x104
+            0x3e800293, // li      t0, 1000
+            0x00100313, // li      t1, 1
+            0x0065202f, // amoadd.w zero, t1, (a0)
+            0x1005a3af, // lr.w    t2, (a1)
+            0x00138393, // addi    t2, t2, 1
+            0x1875ae2f, // sc.w    t3, t2, (a1)
+            0xfe0e1ae3, // bnez    t3, pc - 12
+            0xfff28293, // addi    t0, t0, -1
+            0xfe0294e3, // bnez    t0, pc - 24
+            0x0ff0000f, // fence
+            0x00000073, // ecall
+        ];
+        let mut mem = AtomicMemory::new(0x108);
+        for (i, word) in program.iter().enumerate() {
+            mem.write_word(i as u32 * 4, *word).unwrap();
+        }
+        let mut harts: Vec<ParallelHart<RV32>> = (0..4)
+            .map(|id| ParallelHart::new(mem.clone(), id))
+            .collect();
+        let steps = run_parallel_rv32(&mut harts, 1_000_000, |_, _, status| {
+            assert!(matches!(status, ExecStatus::EnvironmentCall(0x30)));
+            false
+        });
+
+        // However the harts interleaved, none of the increments are lost.
+        assert!(steps.iter().all(|s| *s < 1_000_000));
+        assert!(harts.iter().all(|h| h.read_pc() == 0x34));
+        assert_eq!(mem.read_word(0x100).unwrap(), 4000);
+        assert_eq!(mem.read_word(0x104).unwrap(), 4000);
+    }
+}
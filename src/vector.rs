@@ -0,0 +1,342 @@
+use crate::register::VectorRegister;
+
+use core::convert::TryInto;
+
+/// The longest vector register length (VLEN), in bits, that a
+/// `VectorRegisters` can be created with.
+pub const MAX_VLEN: usize = 1024;
+
+/// The widest element (ELEN), in bits, that vector instructions can operate
+/// on.
+pub const ELEN: usize = 64;
+
+const MAX_VLENB: usize = MAX_VLEN / 8;
+
+/// The state belonging to the vector extension for one hart: the 32 vector
+/// registers and the CSRs that describe how instructions use them.
+///
+/// The vector register length is chosen when the state is created, and can
+/// be any power of two from 128 bits up to `MAX_VLEN`. Elements are stored
+/// little-endian, with element `i` of a register group starting at byte
+/// `i * SEW / 8` of its first register and continuing into the following
+/// registers of the group.
+#[derive(Clone)]
+pub struct VectorRegisters {
+    vlenb: usize,
+    data: [u8; 32 * MAX_VLENB],
+    vl: u32,
+    vtype: u32,
+    vill: bool,
+    vstart: u32,
+    vxrm: u32,
+    vxsat: bool,
+}
+
+impl VectorRegisters {
+    /// Creates the vector state for a hart whose vector registers are each
+    /// `vlen` bits long, in the state it has at reset.
+    ///
+    /// Panics if `vlen` is not a power of two between 128 and `MAX_VLEN`.
+    pub fn new(vlen: usize) -> Self {
+        if !vlen.is_power_of_two() || !(128..=MAX_VLEN).contains(&vlen) {
+            panic!("vector register length must be a power of two from 128 to MAX_VLEN bits");
+        }
+        Self {
+            vlenb: vlen / 8,
+            data: [0; 32 * MAX_VLENB],
+            vl: 0,
+            vtype: 0,
+            vill: true,
+            vstart: 0,
+            vxrm: 0,
+            vxsat: false,
+        }
+    }
+
+    /// Returns the registers and CSRs to their reset state, keeping the
+    /// vector register length.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.vlen());
+    }
+
+    /// Captures the vector registers and CSRs.
+    pub fn snapshot(&self) -> VectorSnapshot {
+        let mut registers = [[0; MAX_VLEN / 64]; 32];
+        for (num, reg) in registers.iter_mut().enumerate() {
+            let bytes = self.register(VectorRegister::numbered(num));
+            for (word, chunk) in reg.iter_mut().zip(bytes.chunks_exact(8)) {
+                *word = u64::from_le_bytes(chunk.try_into().unwrap());
+            }
+        }
+        VectorSnapshot {
+            vlen: self.vlen(),
+            registers,
+            vl: self.vl,
+            vtype: self.vtype,
+            vill: self.vill,
+            vstart: self.vstart,
+            vxrm: self.vxrm,
+            vxsat: self.vxsat,
+        }
+    }
+
+    /// Creates vector state from a previous snapshot, including its vector
+    /// register length.
+    ///
+    /// Panics if the snapshot's `vlen` is not a power of two between 128 and
+    /// `MAX_VLEN`.
+    pub fn from_snapshot(snapshot: &VectorSnapshot) -> Self {
+        let mut regs = Self::new(snapshot.vlen);
+        for (num, reg) in snapshot.registers.iter().enumerate() {
+            let bytes = regs.register_mut(VectorRegister::numbered(num));
+            for (chunk, word) in bytes.chunks_exact_mut(8).zip(reg.iter()) {
+                chunk.copy_from_slice(&word.to_le_bytes());
+            }
+        }
+        // A vtype that this implementation doesn't support can only have
+        // come from an altered snapshot, so it's treated like an unsupported
+        // vtype written by vsetvl.
+        match Self::vlmax_for(regs.vlen(), snapshot.vtype) {
+            Some(vlmax) if !snapshot.vill => {
+                regs.vtype = snapshot.vtype;
+                regs.vill = false;
+                regs.vl = snapshot.vl.min(vlmax);
+            }
+            _ => {}
+        }
+        regs.set_vstart(snapshot.vstart);
+        regs.set_vxrm(snapshot.vxrm);
+        regs.set_vxsat(snapshot.vxsat);
+        regs
+    }
+
+    /// Returns the length of each vector register in bits.
+    pub fn vlen(&self) -> usize {
+        self.vlenb * 8
+    }
+
+    /// Returns the length of each vector register in bytes, which is also
+    /// the value of the `vlenb` CSR.
+    pub fn vlenb(&self) -> usize {
+        self.vlenb
+    }
+
+    /// Returns the current vector length, from the `vl` CSR.
+    pub fn vl(&self) -> u32 {
+        self.vl
+    }
+
+    /// Returns the fields of the `vtype` CSR other than `vill`.
+    pub fn vtype(&self) -> u32 {
+        self.vtype
+    }
+
+    /// Returns true if the current `vtype` is unsupported, in which case
+    /// every vector instruction other than the `vset{i}vl{i}` instructions
+    /// is illegal.
+    pub fn vill(&self) -> bool {
+        self.vill
+    }
+
+    /// Returns the index of the first element that the next vector
+    /// instruction will process, from the `vstart` CSR.
+    pub fn vstart(&self) -> u32 {
+        self.vstart
+    }
+
+    pub fn set_vstart(&mut self, v: u32) {
+        // vstart only needs enough bits to index every byte element of a
+        // group of eight registers, which is VLEN elements.
+        self.vstart = v & (self.vlen() as u32 - 1);
+    }
+
+    /// Returns the fixed-point rounding mode, from the `vxrm` CSR.
+    pub fn vxrm(&self) -> u32 {
+        self.vxrm
+    }
+
+    pub fn set_vxrm(&mut self, v: u32) {
+        self.vxrm = v & 0b11;
+    }
+
+    /// Returns the fixed-point saturation flag, from the `vxsat` CSR.
+    pub fn vxsat(&self) -> bool {
+        self.vxsat
+    }
+
+    pub fn set_vxsat(&mut self, v: bool) {
+        self.vxsat = v;
+    }
+
+    /// Returns the selected element width (SEW) in bits.
+    pub fn sew(&self) -> usize {
+        8 << ((self.vtype >> 3) & 0b111)
+    }
+
+    /// Returns the number of registers in each register group: one for the
+    /// fractional register group multipliers, or LMUL otherwise.
+    pub fn group_size(&self) -> usize {
+        match self.vtype & 0b111 {
+            lmul @ 0..=3 => 1 << lmul,
+            _ => 1,
+        }
+    }
+
+    /// Returns the maximum number of elements that an instruction can
+    /// operate on under the current `vtype`, VLMAX.
+    pub fn vlmax(&self) -> u32 {
+        Self::vlmax_for(self.vlen(), self.vtype).unwrap_or(0)
+    }
+
+    /// Returns the number of registers in each register group that holds
+    /// VLMAX elements of `eew` bits, as for the memory operands of loads and
+    /// stores whose element width differs from SEW. Returns None if such a
+    /// group would need more than eight registers, or less than an eighth
+    /// of one.
+    pub fn group_size_for(&self, eew: usize) -> Option<usize> {
+        let bits = self.vlmax() as usize * eew;
+        if bits * 8 < self.vlen() || bits > self.vlen() * 8 {
+            return None;
+        }
+        Some((bits / self.vlen()).max(1))
+    }
+
+    // Returns VLMAX for the given vtype fields, or None if this
+    // implementation doesn't support that vtype.
+    fn vlmax_for(vlen: usize, vtype: u32) -> Option<u32> {
+        let vsew = (vtype >> 3) & 0b111;
+        let vlmul = vtype & 0b111;
+        if vsew > 3 || vlmul == 0b100 || vtype >> 8 != 0 {
+            return None;
+        }
+        let sew = 8 << vsew;
+        // The fractional multipliers (1/8, 1/4 and 1/2) are encoded as 5, 6
+        // and 7, and must still leave room for at least one element of ELEN
+        // bits.
+        let (num, den) = match vlmul {
+            0..=3 => (1 << vlmul, 1),
+            _ => (1, 1 << (8 - vlmul)),
+        };
+        if den > 1 && sew > ELEN / den {
+            return None;
+        }
+        Some((vlen * num / den / sew) as u32)
+    }
+
+    /// Selects a new `vtype` and sets `vl` to the number of elements that
+    /// instructions will process for an application vector length of
+    /// `avl`, as for the `vsetvl` family of instructions. Returns the new
+    /// `vl`.
+    ///
+    /// If the `vtype` is unsupported, `vill` is set and `vl` is set to zero.
+    pub fn configure(&mut self, avl: u32, vtype: u32) -> u32 {
+        self.vstart = 0;
+        match Self::vlmax_for(self.vlen(), vtype) {
+            Some(vlmax) => {
+                self.vtype = vtype;
+                self.vill = false;
+                self.vl = avl.min(vlmax);
+            }
+            None => {
+                self.vtype = 0;
+                self.vill = true;
+                self.vl = 0;
+            }
+        }
+        self.vl
+    }
+
+    /// Returns the bytes of the given register.
+    pub fn register(&self, reg: VectorRegister) -> &[u8] {
+        let start = reg.num() * self.vlenb;
+        &self.data[start..start + self.vlenb]
+    }
+
+    /// Returns the bytes of the given register for modification.
+    pub fn register_mut(&mut self, reg: VectorRegister) -> &mut [u8] {
+        let start = reg.num() * self.vlenb;
+        &mut self.data[start..start + self.vlenb]
+    }
+
+    /// Reads element `index` of the register group starting at `reg`,
+    /// where each element is `sew` bits wide, zero-extended to 64 bits.
+    pub fn element(&self, reg: VectorRegister, index: usize, sew: usize) -> u64 {
+        let bytes = sew / 8;
+        let start = reg.num() * self.vlenb + index * bytes;
+        let mut buf = [0u8; 8];
+        buf[..bytes].copy_from_slice(&self.data[start..start + bytes]);
+        u64::from_le_bytes(buf)
+    }
+
+    /// Writes the low `sew` bits of `v` to element `index` of the register
+    /// group starting at `reg`.
+    pub fn set_element(&mut self, reg: VectorRegister, index: usize, sew: usize, v: u64) {
+        let bytes = sew / 8;
+        let start = reg.num() * self.vlenb + index * bytes;
+        self.data[start..start + bytes].copy_from_slice(&v.to_le_bytes()[..bytes]);
+    }
+
+    /// Reads bit `index` of the mask held in the given register.
+    pub fn mask_bit(&self, reg: VectorRegister, index: usize) -> bool {
+        self.register(reg)[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// Writes bit `index` of the mask held in the given register.
+    pub fn set_mask_bit(&mut self, reg: VectorRegister, index: usize, v: bool) {
+        let byte = &mut self.register_mut(reg)[index / 8];
+        *byte = (*byte & !(1 << (index % 8))) | ((v as u8) << (index % 8));
+    }
+}
+
+/// A copy of the state in a `VectorRegisters`, as included in a hart
+/// snapshot.
+///
+/// Each register is held as little-endian 64-bit words, of which only the
+/// first `vlen / 64` are significant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VectorSnapshot {
+    pub vlen: usize,
+    pub registers: [[u64; MAX_VLEN / 64]; 32],
+    pub vl: u32,
+    pub vtype: u32,
+    pub vill: bool,
+    pub vstart: u32,
+    pub vxrm: u32,
+    pub vxsat: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VectorRegisters;
+    use crate::register::VectorRegister;
+
+    #[test]
+    fn configure() {
+        let mut regs = VectorRegisters::new(128);
+        assert!(regs.vill());
+
+        // e32, m2: eight elements.
+        assert_eq!(regs.configure(100, 0b010_001), 8);
+        assert_eq!((regs.sew(), regs.group_size(), regs.vlmax()), (32, 2, 8));
+        assert_eq!(regs.configure(5, 0b010_001), 5);
+        // e8, mf2: eight elements.
+        assert_eq!(regs.configure(u32::MAX, 0b000_111), 8);
+        // e64, mf2 leaves no room for an element, and e128 doesn't exist.
+        assert_eq!(regs.configure(4, 0b011_111), 0);
+        assert!(regs.vill());
+        assert_eq!(regs.configure(4, 0b100_000), 0);
+        assert!(regs.vill());
+
+        let v2 = VectorRegister::numbered(2);
+        regs.set_element(v2, 5, 16, 0xabcd);
+        assert_eq!(regs.element(v2, 5, 16), 0xabcd);
+        assert_eq!(regs.register(v2)[10..12], [0xcd, 0xab]);
+        // Element 9 of a group starting at v2 is in v3.
+        regs.set_element(v2, 9, 16, 0x1234);
+        assert_eq!(regs.element(VectorRegister::numbered(3), 1, 16), 0x1234);
+        regs.set_mask_bit(v2, 9, true);
+        assert!(regs.mask_bit(v2, 9));
+        assert_eq!(regs.register(v2)[1], 0x02);
+    }
+}
@@ -2,27 +2,58 @@ use core::{concat, stringify};
 use std::io;
 use std::io::BufRead;
 
-use riscv_emu::exec::{step_rv32, ExecStatus};
-use riscv_emu::isas::RV32;
+use riscv_emu::exec::{
+    run_rv32_traced, step_rv32, Commit, ExecStatus, RunLimits, StopReason, Tracer,
+};
+use riscv_emu::isas::{RV32, RV32E};
+use riscv_emu::ops;
+use riscv_emu::BaseISA;
+use riscv_emu::ExceptionCause;
 use riscv_emu::Int;
 use riscv_emu::IntRegister;
-use riscv_emu::{ops, Instruction, RawInstruction};
-use riscv_emu::{AddressConverter, AddressTransformer, Bus, Memory, MemoryError};
-use riscv_emu::{Hart, SingleThreadUserHart};
+use riscv_emu::{AddressConverter, AddressTransformer, Alignment, Bus, Memory, MemoryError};
+use riscv_emu::{Hart, MisalignedAccess, SingleThreadUserHart};
 
 macro_rules! rv32case {
     ($filename:ident, $result_base:expr) => {
+        rv32case!($filename, $result_base, Traps::Report);
+    };
+    ($filename:ident, $result_base:expr, $traps:expr) => {
         #[test]
         fn $filename() {
-            test_case(
+            test_case::<RV32>(
                 include_bytes!(concat!("rv32cases/", stringify!($filename), ".bin")),
                 include_bytes!(concat!("rv32cases/", stringify!($filename), ".want")),
                 $result_base,
+                $traps,
             );
         }
     };
 }
 
+// Runs a test program on an RV32E hart, which has only 16 integer registers.
+macro_rules! rv32ecase {
+    ($filename:ident, $result_base:expr) => {
+        #[test]
+        fn $filename() {
+            test_case::<RV32E>(
+                include_bytes!(concat!("rv32cases/", stringify!($filename), ".bin")),
+                include_bytes!(concat!("rv32cases/", stringify!($filename), ".want")),
+                $result_base,
+                Traps::Report,
+            );
+        }
+    };
+}
+
+// Selects what happens when a test program raises an exception.
+enum Traps {
+    // Fail the test.
+    Report,
+    // Run the test program's own trap handler.
+    Handle,
+}
+
 // Tests for the "I" integer base ISA
 rv32case!(I_ADDI_01, 0x80002000);
 rv32case!(I_ADD_01, 0x80002000);
@@ -48,8 +79,8 @@ rv32case!(I_LHU_01, 0x80002030);
 rv32case!(I_LH_01, 0x80002030);
 rv32case!(I_LUI_01, 0x80002000);
 rv32case!(I_LW_01, 0x80002030);
-//rv32case!(I_MISALIGN_JMP_01, 0x80002000); // (requires exception handling)
-//rv32case!(I_MISALIGN_LDST_01, 0x80002010); // (requires exception handling)
+rv32case!(I_MISALIGN_JMP_01, 0x80002000, Traps::Handle);
+rv32case!(I_MISALIGN_LDST_01, 0x80002010, Traps::Handle);
 rv32case!(I_NOP_01, 0x80002000);
 rv32case!(I_ORI_01, 0x80002000);
 rv32case!(I_OR_01, 0x80002000);
@@ -110,45 +141,122 @@ rv32case!(C_SW, 0x80002000);
 rv32case!(C_SWSP, 0x80002000);
 rv32case!(C_XOR, 0x80002000);
 
-fn test_case(img: &[u8], want_raw: &[u8], sig_start: u32) {
+// Tests for the "E" embedded base ISA, using the test programs from above
+// that only refer to registers x0 to x15.
+mod rv32e {
+    use super::*;
+
+    rv32ecase!(I_DELAY_SLOTS_01, 0x80002000);
+    rv32ecase!(C_ADDI16SP, 0x80002000);
+    rv32ecase!(C_ADDI4SPN, 0x80002000);
+    rv32ecase!(C_AND, 0x80002000);
+    rv32ecase!(C_ANDI, 0x80002000);
+    rv32ecase!(C_BEQZ, 0x80002000);
+    rv32ecase!(C_BNEZ, 0x80002000);
+    rv32ecase!(C_J, 0x80002000);
+    rv32ecase!(C_JAL, 0x80002000);
+    rv32ecase!(C_JR, 0x80002000);
+    rv32ecase!(C_LW, 0x80002080);
+    rv32ecase!(C_MV, 0x80002000);
+    rv32ecase!(C_OR, 0x80002000);
+    rv32ecase!(C_SRAI, 0x80002000);
+    rv32ecase!(C_SRLI, 0x80002000);
+    rv32ecase!(C_SUB, 0x80002000);
+    rv32ecase!(C_SW, 0x80002000);
+    rv32ecase!(C_XOR, 0x80002000);
+
+    // Every instruction that names one of x16 to x31, in any operand
+    // position, is illegal on RV32E and has no effect.
+    #[test]
+    fn upper_registers_illegal() {
+        for r in 16..32 {
+            let cases: [(u32, usize); 8] = [
+                ((2 << 20) | (1 << 15) | (r << 7) | 0x33, 4), // add xr, x1, x2
+                ((2 << 20) | (r << 15) | (1 << 7) | 0x33, 4), // add x1, xr, x2
+                ((r << 20) | (1 << 15) | (1 << 7) | 0x33, 4), // add x1, x1, xr
+                ((r << 15) | (2 << 12) | (1 << 7) | 0x03, 4), // lw x1, 0(xr)
+                ((r << 20) | (1 << 15) | (2 << 12) | 0x23, 4), // sw xr, 0(x1)
+                ((r << 15) | (1 << 7) | 0x67, 4),             // jalr x1, 0(xr)
+                (0x8002 | (r << 7) | (1 << 2), 2),            // c.mv xr, x1
+                (0x9002 | (1 << 7) | (r << 2), 2),            // c.add x1, xr
+            ];
+            for &(inst, len) in cases.iter() {
+                let mut mem_buf = [0u8; 16];
+                mem_buf[..len].copy_from_slice(&inst.to_le_bytes()[..len]);
+                let mem = AddressConverter::new(Memory::new_ram(&mut mem_buf));
+                let mut hart: SingleThreadUserHart<RV32E, _> = SingleThreadUserHart::new(mem);
+                hart.set_report_exceptions(true);
+                hart.write_int_register(IntRegister::numbered(1), 4);
+                hart.write_int_register(IntRegister::numbered(2), 8);
+
+                match step_rv32(&mut hart) {
+                    ExecStatus::Exception {
+                        cause: ExceptionCause::IllegalInstruction,
+                        pc: 0,
+                        tval: 0,
+                    } => (),
+                    status => panic!("0x{:08x} with x{}: got {:?}", inst, r, status),
+                }
+                assert_eq!(hart.read_pc(), 0);
+                assert_eq!(hart.read_int_register(IntRegister::numbered(1)), 4);
+                hart.with_memory(|mem| assert_eq!(mem.read_word(4), Ok(0)));
+            }
+        }
+
+        // The same instructions using x15 instead are fine.
+        let mut mem_buf = [0u8; 16];
+        mem_buf[..4].copy_from_slice(&0x002087b3u32.to_le_bytes()); // add x15, x1, x2
+        let mem = AddressConverter::new(Memory::new_ram(&mut mem_buf));
+        let mut hart: SingleThreadUserHart<RV32E, _> = SingleThreadUserHart::new(mem);
+        hart.set_report_exceptions(true);
+        hart.write_int_register(IntRegister::numbered(1), 4);
+        hart.write_int_register(IntRegister::numbered(2), 8);
+        assert!(matches!(step_rv32(&mut hart), ExecStatus::Running));
+        assert_eq!(hart.read_int_register(IntRegister::numbered(15)), 12);
+    }
+}
+
+fn test_case<ISA: BaseISA<Int = u32, Float = f64>>(
+    img: &[u8],
+    want_raw: &[u8],
+    sig_start: u32,
+    traps: Traps,
+) {
     let start_pc = 0x80000000;
     let mut mem_vec = img.to_owned();
+    if let Traps::Handle = traps {
+        use_user_level_traps(&mut mem_vec);
+    }
     let mem_buf = mem_vec.as_mut_slice();
-    let mem = MemLogger::new(AddressTransformer::new(
-        AddressConverter::new(Memory::new_ram(mem_buf)),
-        |addr: u32| Ok(addr.wrapping_sub(start_pc)),
+    let mem = MemLogger::new(Alignment::new(
+        AddressTransformer::new(
+            AddressConverter::new(Memory::new_ram(mem_buf)),
+            |addr: u32| Ok(addr.wrapping_sub(start_pc)),
+        ),
+        MisalignedAccess::Trap,
     ));
-    let mut hart: SingleThreadUserHart<RV32, _> = SingleThreadUserHart::new(mem);
+    let mut hart: SingleThreadUserHart<ISA, _> = SingleThreadUserHart::new(mem);
     hart.write_pc(start_pc);
+    hart.set_report_exceptions(matches!(traps, Traps::Report));
+    hart.set_misa_writable(true);
 
-    let mut steps = 0;
-    loop {
-        if steps >= 512 {
-            panic!(
-                "test program is still running after {} steps, so aborting",
-                steps
-            );
-        }
-        let pc = hart.read_pc();
-        hart.with_memory(|mem| match mem.read_word(pc) {
-            Ok(v) => {
-                let raw_inst = RawInstruction::new(v);
-                let inst: Instruction<ops::RV32, _> = Instruction::decode_raw(raw_inst, pc);
-                println!("0x{:08x}: {:?}", pc, inst.op);
-                if let ops::RV32::Invalid = inst.op {
-                    panic!("hit invalid instruction, so aborting");
-                }
-            }
-            Err(_) => {
-                println!("0x{:08x}: <failed to read instruction from memory>", pc);
-            }
-        });
-        steps += 1;
-        let status = step_rv32(&mut hart);
-        match status {
-            ExecStatus::EnvironmentCall(_) => break,
-            _ => (),
-        }
+    let limits = RunLimits::new().with_max_steps(512);
+    let result = run_rv32_traced(&mut hart, &mut PrintInstructions, &limits);
+    match result.reason {
+        StopReason::EnvironmentCall(_) => (),
+        StopReason::StepLimit => panic!(
+            "test program is still running after {} steps, so aborting",
+            result.retired
+        ),
+        StopReason::InvalidInstruction { pc, bits } => panic!(
+            "hit invalid instruction 0x{:08x} at 0x{:08x}, so aborting",
+            bits, pc
+        ),
+        StopReason::Exception { cause, pc, tval } => panic!(
+            "unhandled {:?} exception at 0x{:08x} (tval 0x{:08x}), so aborting",
+            cause, pc, tval
+        ),
+        reason => panic!("test program stopped unexpectedly: {:?}", reason),
     }
 
     // If we ran to completion then we'll read in the "want" data and compare
@@ -221,6 +329,48 @@ fn test_case(img: &[u8], want_raw: &[u8], sig_start: u32) {
     }
 }
 
+// The test programs' trap handlers use the machine-level trap CSRs and
+// `mret`, but `SingleThreadUserHart` only supports user-level trap handling.
+// The user-level trap CSRs have the same numbers apart from the privilege
+// level bits, so we rewrite the CSR instructions that refer to exactly those
+// machine-level CSRs, and each `mret`, to get a program that works the same
+// way. Other SYSTEM instructions are left untouched.
+fn use_user_level_traps(img: &mut [u8]) {
+    const MRET: u32 = 0x30200073;
+    const URET: u32 = 0x00200073;
+    const TRAP_CSRS: [u32; 8] = [
+        0x300, // mstatus
+        0x304, // mie
+        0x305, // mtvec
+        0x340, // mscratch
+        0x341, // mepc
+        0x342, // mcause
+        0x343, // mtval
+        0x344, // mip
+    ];
+    for word in img.chunks_exact_mut(4) {
+        let inst = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+        let funct3 = (inst >> 12) & 0b111;
+        let patched = if inst == MRET {
+            URET
+        } else if inst & 0x7f == 0x73 && funct3 != 0 && TRAP_CSRS.contains(&(inst >> 20)) {
+            inst - (0x300 << 20)
+        } else {
+            continue;
+        };
+        word.copy_from_slice(&patched.to_le_bytes());
+    }
+}
+
+// Prints each instruction as it is executed.
+struct PrintInstructions;
+
+impl Tracer<ops::RV32, u32> for PrintInstructions {
+    fn commit(&mut self, commit: &Commit<ops::RV32, u32>) {
+        println!("0x{:08x}: {:?}", commit.inst.pc, commit.inst.op);
+    }
+}
+
 struct MemLogger<Wrapped: Bus<u32>> {
     wrapped: Wrapped,
 }
@@ -0,0 +1,76 @@
+#![cfg(feature = "std")]
+
+use core::concat;
+
+use riscv_emu::litmus::{parse_expectations, run_litmus, Expectation, Exploration, LitmusTest};
+
+macro_rules! litmus {
+    ($name:ident, $filename:expr) => {
+        #[test]
+        fn $name() {
+            test_case(include_str!(concat!("litmus/", $filename, ".litmus")));
+        }
+    };
+}
+
+// Tests from the RISC-V litmus suite
+litmus!(sb, "SB");
+litmus!(sb_fence_rw_rws, "SB+fence.rw.rws");
+litmus!(mp, "MP");
+litmus!(mp_fence_rw_rws, "MP+fence.rw.rws");
+litmus!(mp_fence_w_w_fence_r_r, "MP+fence.w.w+fence.r.r");
+litmus!(lb, "LB");
+litmus!(lb_fence_rw_rws, "LB+fence.rw.rws");
+litmus!(two_plus_two_w, "2+2W");
+litmus!(two_plus_two_w_fence_rw_rws, "2+2W+fence.rw.rws");
+litmus!(iriw_fence_rw_rws, "IRIW+fence.rw.rws");
+litmus!(corr, "CoRR");
+litmus!(cowr, "CoWR");
+
+// Tests of our own
+litmus!(sb_amoswap_w_aqrls, "SB+amoswap.w.aqrls");
+
+// Tests whose conditions describe relaxed behaviour that the runner's store
+// buffers should be able to produce.
+const RELAXED: &[&str] = &["SB", "MP", "2+2W"];
+
+fn test_case(text: &str) {
+    let test = LitmusTest::parse(text).unwrap();
+    let expectations = parse_expectations(include_str!("litmus/expected.txt")).unwrap();
+    let expected = expectations
+        .iter()
+        .find(|(name, _)| *name == test.name)
+        .map(|(_, expected)| *expected)
+        .unwrap_or_else(|| panic!("no expected result for {}", test.name));
+
+    let exhaustive = run_litmus(
+        &test,
+        Exploration::Exhaustive {
+            max_runs: 1_000_000,
+        },
+    );
+    assert!(exhaustive.complete);
+    let random = run_litmus(
+        &test,
+        Exploration::Random {
+            runs: 1000,
+            seed: 1,
+        },
+    );
+    for result in [&exhaustive, &random] {
+        assert!(
+            result.agrees_with(expected),
+            "{} was witnessed by {:?}",
+            test.name,
+            result.outcomes.keys().collect::<Vec<_>>()
+        );
+    }
+    assert!(random
+        .outcomes
+        .keys()
+        .all(|o| exhaustive.outcomes.contains_key(o)));
+    if RELAXED.contains(&test.name.as_str()) {
+        assert_eq!(expected, Expectation::Allowed);
+        assert!(exhaustive.witnessed, "{} was not witnessed", test.name);
+    }
+}